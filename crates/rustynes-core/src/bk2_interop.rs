@@ -42,11 +42,14 @@
 //!
 //! # Deliberate limitations (mirroring `.fm2`)
 //!
-//! - **Standard gamepads, players 1 and 2 only.** [`FrameInput`] models two
-//!   ports; extra controller groups are parsed but dropped (their presence is
-//!   not silently misleading — only P1/P2 are mapped). The console Reset bit is
-//!   parsed but not represented on [`FrameInput`] (it has no reset bit), exactly
-//!   as in `.fm2`.
+//! - **Import maps players 1 and 2 only.** Extra controller groups and the
+//!   console group are parsed but dropped (their presence is not silently
+//!   misleading — only P1/P2 are mapped).
+//! - **Export carries what the `.rnm` extended record holds, bar devices**
+//!   (v2.5.0): Four Score frames add `P3`/`P4` groups, and console commands
+//!   become console-group columns (`Reset`, `Power`, `FDS Eject`,
+//!   `FDS Insert N`, `Insert Coin P1`/`P2`). Expansion-device input is
+//!   rejected rather than exported without it.
 //! - **Power-on start only.** See above.
 //!
 //! This module is `no_std`-clean: it uses only `core` + `alloc`.
@@ -57,7 +60,7 @@ use core::fmt::Write as _;
 
 use crate::Region;
 use crate::controller::Buttons;
-use crate::movie::{FrameInput, Movie, StartPoint};
+use crate::movie::{DeviceInput, FrameInput, Movie, MovieCommand, StartPoint};
 use thiserror::Error;
 
 /// The filename of the header member inside a `.bk2` ZIP.
@@ -198,6 +201,7 @@ pub fn import_bk2(
         // field, and synthesizing one here would attest a run this build never
         // performed. `Movie::verify` reports `NotAttested` for it, which is true.
         attestation: None,
+        match_info: None,
     };
     Ok((movie, meta))
 }
@@ -212,16 +216,31 @@ pub fn import_bk2(
 /// Only [`StartPoint::PowerOn`] movies export; a [`StartPoint::SaveState`] movie
 /// has no portable `.bk2` representation.
 ///
+/// The console group always has `Reset` and `Power`, plus a column for each
+/// disk or coin command the movie uses. A command is a one-frame press; a Vs.
+/// coin column stays held from [`MovieCommand::InsertCoin`] until
+/// [`MovieCommand::ClearCoin`], matching `BizHawk`'s held coin button. Any
+/// Four Score frame adds `P3` / `P4` groups for the whole log.
+///
 /// # Errors
 ///
 /// Returns [`Bk2Error::Unsupported`] if `movie` is anchored to an embedded save
-/// state.
+/// state or carries expansion-device input.
 pub fn export_bk2(movie: &Movie, opts: &Bk2ExportOpts) -> Result<Bk2Text, Bk2Error> {
     if !matches!(movie.start, StartPoint::PowerOn) {
         return Err(Bk2Error::Unsupported(
             "save-state-anchored movie has no portable .bk2 representation",
         ));
     }
+    if movie
+        .frames
+        .iter()
+        .any(|f| !matches!(f.device, DeviceInput::None))
+    {
+        return Err(Bk2Error::Unsupported(
+            "expansion-device input has no .bk2 encoding here",
+        ));
+    }
 
     let pal = matches!(movie.region, Region::Pal | Region::Dendy);
 
@@ -244,27 +263,119 @@ pub fn export_bk2(movie: &Movie, opts: &Bk2ExportOpts) -> Result<Bk2Text, Bk2Err
     }
 
     // --- Input Log.txt ---
-    // The console-buttons group carries Reset / Power; RustyNES movies never
-    // record either, so it is always released (`..`). Two controller groups.
+    let console = console_columns(&movie.frames);
+    let four_score = movie.frames.iter().any(|f| f.four_score);
+    let players: &[&str] = if four_score {
+        &["P1", "P2", "P3", "P4"]
+    } else {
+        &["P1", "P2"]
+    };
     let mut input_log = String::new();
-    input_log.push_str("[Input]\n");
-    input_log.push_str("LogKey:#Reset|Power|#P1 Up|P1 Down|P1 Left|P1 Right|P1 Start|P1 Select|P1 B|P1 A|#P2 Up|P2 Down|P2 Left|P2 Right|P2 Start|P2 Select|P2 B|P2 A|\n");
+    input_log.push_str("[Input]\nLogKey:#");
+    for col in &console {
+        let _ = write!(input_log, "{}|", col.name());
+    }
+    for p in players {
+        input_log.push('#');
+        for b in ["Up", "Down", "Left", "Right", "Start", "Select", "B", "A"] {
+            let _ = write!(input_log, "{p} {b}|");
+        }
+    }
+    input_log.push('\n');
     let mut pad = [0u8; 8];
+    let mut coins = [false; 2];
     for frame in &movie.frames {
-        // Console group: Reset + Power, both released.
-        input_log.push_str("|..|");
-        write_pad(frame.p1, &mut pad);
-        input_log.push_str(core::str::from_utf8(&pad).expect("pad bytes are ASCII"));
+        match frame.command {
+            MovieCommand::InsertCoin(a) if a < 2 => coins[usize::from(a)] = true,
+            MovieCommand::ClearCoin => coins = [false; 2],
+            _ => {}
+        }
         input_log.push('|');
-        write_pad(frame.p2, &mut pad);
-        input_log.push_str(core::str::from_utf8(&pad).expect("pad bytes are ASCII"));
-        input_log.push_str("|\n");
+        for col in &console {
+            input_log.push(if col.pressed(frame.command, coins) {
+                col.mnemonic()
+            } else {
+                '.'
+            });
+        }
+        input_log.push('|');
+        let pads = [frame.p1, frame.p2, frame.p3, frame.p4];
+        for buttons in &pads[..players.len()] {
+            write_pad(*buttons, &mut pad);
+            input_log.push_str(core::str::from_utf8(&pad).expect("pad bytes are ASCII"));
+            input_log.push('|');
+        }
+        input_log.push('\n');
     }
     input_log.push_str("[/Input]\n");
 
     Ok(Bk2Text { header, input_log })
 }
 
+/// A console-group column of an exported `.bk2` log.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ConsoleColumn {
+    Reset,
+    Power,
+    FdsEject,
+    FdsInsert(u8),
+    InsertCoin(u8),
+}
+
+impl ConsoleColumn {
+    /// The `LogKey` column name.
+    fn name(self) -> String {
+        match self {
+            Self::Reset => "Reset".to_string(),
+            Self::Power => "Power".to_string(),
+            Self::FdsEject => "FDS Eject".to_string(),
+            Self::FdsInsert(n) => alloc::format!("FDS Insert {n}"),
+            Self::InsertCoin(a) => alloc::format!("Insert Coin P{}", u16::from(a) + 1),
+        }
+    }
+
+    /// The letter written when the column is pressed.
+    const fn mnemonic(self) -> char {
+        match self {
+            Self::Reset => 'r',
+            Self::Power => 'P',
+            Self::FdsEject => 'E',
+            Self::FdsInsert(_) => 'I',
+            Self::InsertCoin(_) => 'C',
+        }
+    }
+
+    /// Whether the column is pressed on a frame with `command` and the held
+    /// coin state `coins`.
+    fn pressed(self, command: MovieCommand, coins: [bool; 2]) -> bool {
+        match self {
+            Self::Reset => command == MovieCommand::SoftReset,
+            Self::Power => command == MovieCommand::PowerCycle,
+            Self::FdsEject => command == MovieCommand::EjectDisk,
+            Self::FdsInsert(n) => command == MovieCommand::InsertDisk(n),
+            Self::InsertCoin(a) => coins.get(usize::from(a)).copied().unwrap_or(false),
+        }
+    }
+}
+
+/// The console columns `frames` needs: `Reset` and `Power` always, then the
+/// disk and coin columns its commands use, in first-use order.
+fn console_columns(frames: &[FrameInput]) -> Vec<ConsoleColumn> {
+    let mut cols = alloc::vec![ConsoleColumn::Reset, ConsoleColumn::Power];
+    for f in frames {
+        let col = match f.command {
+            MovieCommand::EjectDisk => ConsoleColumn::FdsEject,
+            MovieCommand::InsertDisk(n) => ConsoleColumn::FdsInsert(n),
+            MovieCommand::InsertCoin(a) if a < 2 => ConsoleColumn::InsertCoin(a),
+            _ => continue,
+        };
+        if !cols.contains(&col) {
+            cols.push(col);
+        }
+    }
+    cols
+}
+
 /// Render `buttons` into an eight-byte `U D L R S s B A` pad field (mnemonic
 /// letter when pressed, `.` when released).
 fn write_pad(buttons: Buttons, out: &mut [u8; 8]) {
@@ -560,6 +671,7 @@ mod tests {
             frames: varied_frames(),
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         let opts = Bk2ExportOpts {
             rerecord_count: 99,
@@ -594,6 +706,7 @@ mod tests {
             ],
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         let text = export_bk2(&movie, &Bk2ExportOpts::default()).expect("export");
         let lines: Vec<&str> = text
@@ -718,6 +831,7 @@ mod tests {
             frames: vec![FrameInput::new(Buttons::empty(), Buttons::empty())],
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         let out = export_bk2(&pal_movie, &Bk2ExportOpts::default()).expect("export");
         assert!(out.header.lines().any(|l| l == "PAL 1"));
@@ -819,6 +933,50 @@ mod tests {
         assert_eq!(movie.frames[0].p2, Buttons::empty());
     }
 
+    #[test]
+    fn export_carries_four_score_and_console_commands() {
+        let mut frames = vec![FrameInput::new(Buttons::A, Buttons::empty()); 5];
+        frames[0].four_score = true;
+        frames[0].p3 = Buttons::UP;
+        frames[0].p4 = Buttons::B;
+        frames[1].command = MovieCommand::PowerCycle;
+        frames[2].command = MovieCommand::InsertCoin(0);
+        frames[4].command = MovieCommand::ClearCoin;
+        let movie = Movie {
+            region: Region::Ntsc,
+            rom_sha256: TEST_SHA,
+            start: StartPoint::PowerOn,
+            frames,
+            rerecord_count: 0,
+            attestation: None,
+            match_info: None,
+        };
+        let text = export_bk2(&movie, &Bk2ExportOpts::default()).expect("export");
+        let mut lines = text.input_log.lines().skip(1);
+        let key = lines.next().unwrap();
+        assert!(key.starts_with("LogKey:#Reset|Power|Insert Coin P1|#P1 Up|"));
+        assert!(key.contains("#P4 Up|"));
+        let rows: Vec<&str> = lines.filter(|l| l.starts_with('|')).collect();
+        assert_eq!(rows[0], "|...|.......A|........|U.......|......B.|");
+        assert!(rows[1].starts_with("|.P.|"));
+        // The coin column is held from the insert until the clear.
+        let coin: Vec<char> = rows.iter().map(|r| r.chars().nth(3).unwrap()).collect();
+        assert_eq!(coin, ['.', '.', 'C', 'C', '.']);
+        // P1/P2 still import as before.
+        let (back, _) = import_bk2(&text.header, &text.input_log, TEST_SHA).expect("import");
+        assert!(back.frames.iter().all(|f| f.p1 == Buttons::A));
+
+        let mut with_device = movie;
+        with_device.frames[0].device = DeviceInput::FamilyKeyboard {
+            port: 1,
+            keys: [0; 9],
+        };
+        assert!(matches!(
+            export_bk2(&with_device, &Bk2ExportOpts::default()),
+            Err(Bk2Error::Unsupported(_))
+        ));
+    }
+
     #[test]
     fn export_rejects_save_state_movie() {
         let movie = Movie {
@@ -828,6 +986,7 @@ mod tests {
             frames: vec![],
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         assert!(matches!(
             export_bk2(&movie, &Bk2ExportOpts::default()),
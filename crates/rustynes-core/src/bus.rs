@@ -447,7 +447,31 @@ pub struct LockstepBus {
     /// Default `false` leaves the `$4016` read byte-identical (bit 2 is otherwise
     /// open-bus / 0), so the standard controller path is unaffected until a
     /// frontend explicitly drives the mic via [`Self::set_microphone`].
-    famicom_mic: bool,
+    ///
+    /// v2.5.0: held as a per-frame **pattern** rather than one bit — bit `i`
+    /// is the comparator output during the `i`-th eighth of the frame, and a
+    /// `$4016` read picks the bit for the scanline it lands on (see
+    /// [`Self::set_microphone_pattern`]). A held key is `0xFF`.
+    famicom_mic: u8,
+    /// Family BASIC **Data Recorder** cassette deck (v2.5.0). `None` (the
+    /// default) leaves `$4016` byte-identical; when attached, `$4016` writes
+    /// feed its output line (D0, gated by D2) and `$4016` reads OR in the
+    /// played-back tape level on D1. See [`crate::data_recorder`].
+    data_recorder: Option<crate::data_recorder::DataRecorder>,
+    /// The data in an attached ASCII **Turbo File** (v2.5.0). The device on
+    /// the port ([`crate::input_device::InputDevice::TurboFile`]) is only the
+    /// bit address and bank switch; `$4016` writes store into this image and
+    /// `$4017` reads sample it. Like the cassette above it is media, not
+    /// console state: it survives a power-cycle and stays out of the
+    /// per-frame snapshots (only [`crate::Nes::snapshot`] carries it).
+    turbo_file: Option<crate::input_device::TurboFileImage>,
+    /// v2.5.0 — R.O.B., watching the screen for flashed commands and pressing
+    /// controller II through its gyros. See [`crate::rob`].
+    rob: Option<crate::rob::Rob>,
+    /// v2.5.0 — Famicom 3D System shutter glasses: `$4016` D1 picks the eye,
+    /// latched per frame for the frontend's stereo presentation. See
+    /// [`crate::famicom_3d`].
+    famicom_3d: Option<crate::famicom_3d::Famicom3dGlasses>,
     /// v1.1.0 beta.1 (T-110-B4) — optional per-game nametable mirroring
     /// override. `None` (default) defers to the mapper's `nametable_address`
     /// (byte-identical). When `Some`, the standard `$2000-$3EFF` nametable
@@ -860,7 +884,11 @@ impl LockstepBus {
             // v2.3.6: ON by default. See `set_zapper_temporal_light` — the frame
             // model made a Duck Hunt hit impossible.
             zapper_temporal_light: true,
-            famicom_mic: false,
+            famicom_mic: 0,
+            data_recorder: None,
+            turbo_file: None,
+            rob: None,
+            famicom_3d: None,
             nt_mirroring_override: None,
             #[cfg(feature = "debug-hooks")]
             events: alloc::vec::Vec::new(),
@@ -1093,7 +1121,13 @@ impl LockstepBus {
         self.expansion_device = [None, None];
         // The microphone is a transient live signal; a power-cycle releases it
         // (the frontend re-drives it each frame while a key is held).
-        self.famicom_mic = false;
+        self.famicom_mic = 0;
+        // The cassette deck is not part of the console: the tape keeps its
+        // place and transport, re-anchored to the restarted cycle counter.
+        if let Some(tape) = &mut self.data_recorder {
+            tape.sync(self.cycle);
+            tape.rebase(0);
+        }
         self.cycle = 0;
         self.dma_pending = None;
         self.dma_cycles_owed = 0;
@@ -1506,7 +1540,7 @@ impl LockstepBus {
                 }
                 v
             }
-            0x4016 => 0x40 | self.peek_port(0) | (u8::from(self.famicom_mic) << 2),
+            0x4016 => 0x40 | self.peek_port(0) | self.mic_bit() | self.tape_in_bit(),
             0x4017 => 0x40 | self.peek_port(1),
             0x4000..=0x4014 | 0x4018..=0x401F => self.open_bus,
             0x4020..=0xFFFF => {
@@ -1729,15 +1763,170 @@ impl LockstepBus {
     /// `pressed` = the frontend's quantized "mic is loud" verdict. Additive:
     /// leaving it `false` (the default) keeps the `$4016` read byte-identical.
     pub const fn set_microphone(&mut self, pressed: bool) {
-        self.famicom_mic = pressed;
+        self.famicom_mic = if pressed { 0xFF } else { 0 };
     }
 
-    /// Whether the Famicom microphone signal is currently asserted.
+    /// Whether the Famicom microphone signal is asserted at any point of the
+    /// current frame's pattern.
     #[must_use]
     pub const fn microphone(&self) -> bool {
+        self.famicom_mic != 0
+    }
+
+    /// v2.5.0 — drive the microphone from a per-frame comparator pattern: bit
+    /// `i` (LSB first) is the `$4016.D2` level during the `i`-th eighth of the
+    /// frame, by scanline. Lets games that gauge loudness by polling the bit
+    /// repeatedly see a voice that is loud for only part of the frame.
+    pub const fn set_microphone_pattern(&mut self, pattern: u8) {
+        self.famicom_mic = pattern;
+    }
+
+    /// The current per-frame microphone pattern (see
+    /// [`Self::set_microphone_pattern`]).
+    #[must_use]
+    pub const fn microphone_pattern(&self) -> u8 {
         self.famicom_mic
     }
 
+    /// The microphone's `$4016.D2` contribution at the current scanline.
+    fn mic_bit(&self) -> u8 {
+        if self.famicom_mic == 0 {
+            return 0;
+        }
+        let lines = i32::from(self.ppu_region().prerender_line()) + 1;
+        let line = i32::from(self.ppu.scanline()).clamp(0, lines - 1);
+        let slice = (line * 8 / lines).clamp(0, 7);
+        ((self.famicom_mic >> slice) & 1) << 2
+    }
+
+    /// Insert (`Some`) or eject (`None`) the Data Recorder cassette. An
+    /// inserted tape's transport clock is anchored to the current cycle.
+    pub fn set_data_recorder(&mut self, tape: Option<crate::data_recorder::DataRecorder>) {
+        self.data_recorder = tape.map(|mut t| {
+            t.rebase(self.cycle);
+            t
+        });
+    }
+
+    /// The inserted Data Recorder cassette, if any.
+    #[must_use]
+    pub const fn data_recorder(&self) -> Option<&crate::data_recorder::DataRecorder> {
+        self.data_recorder.as_ref()
+    }
+
+    /// Mutable access to the inserted cassette, advanced to the current cycle
+    /// first so transport changes take effect from "now".
+    pub fn data_recorder_mut(&mut self) -> Option<&mut crate::data_recorder::DataRecorder> {
+        self.sync_data_recorder();
+        self.data_recorder.as_mut()
+    }
+
+    /// Apply a save-state's cassette transport to the inserted tape. `None`
+    /// (no tape when saved, or a pre-v2.5.0 blob) keeps the tape where it is,
+    /// re-anchored to the restored cycle counter.
+    pub(crate) fn restore_data_recorder_transport(
+        &mut self,
+        transport: Option<(crate::data_recorder::TapeMode, usize, bool, u64)>,
+    ) {
+        let cycle = self.cycle;
+        if let Some(tape) = &mut self.data_recorder {
+            match transport {
+                Some((mode, position, out_level, synced_cycle)) => {
+                    tape.set_transport_parts(mode, position, out_level, synced_cycle);
+                }
+                None => tape.rebase(cycle),
+            }
+        }
+    }
+
+    /// Advance the cassette transport to the current cycle (per frame).
+    pub(crate) fn sync_data_recorder(&mut self) {
+        let cycle = self.cycle;
+        if let Some(tape) = &mut self.data_recorder {
+            tape.sync(cycle);
+        }
+    }
+
+    /// Plug in (`true`) or unplug the Famicom 3D System glasses.
+    pub const fn set_famicom_3d(&mut self, enabled: bool) {
+        self.famicom_3d = if enabled {
+            Some(crate::famicom_3d::Famicom3dGlasses::new())
+        } else {
+            None
+        };
+    }
+
+    /// The attached 3D glasses, if any.
+    #[must_use]
+    pub const fn famicom_3d(&self) -> Option<&crate::famicom_3d::Famicom3dGlasses> {
+        self.famicom_3d.as_ref()
+    }
+
+    /// Apply a save-state's glasses state to attached glasses (absent in the
+    /// blob, or no glasses attached: unchanged).
+    pub(crate) const fn restore_famicom_3d(
+        &mut self,
+        state: Option<crate::famicom_3d::Famicom3dGlasses>,
+    ) {
+        if let (Some(glasses), Some(saved)) = (&mut self.famicom_3d, state) {
+            *glasses = saved;
+        }
+    }
+
+    /// Latch the 3D glasses' eye for the frame that just completed.
+    pub(crate) const fn latch_famicom_3d_eye(&mut self) {
+        if let Some(glasses) = &mut self.famicom_3d {
+            glasses.end_frame();
+        }
+    }
+
+    /// Set up (`Some`) or remove (`None`) R.O.B.
+    pub const fn set_rob(&mut self, rob: Option<crate::rob::Rob>) {
+        self.rob = rob;
+    }
+
+    /// The attached R.O.B., if any.
+    #[must_use]
+    pub const fn rob(&self) -> Option<&crate::rob::Rob> {
+        self.rob.as_ref()
+    }
+
+    /// Apply a save-state's R.O.B. state. Like the cassette, the robot belongs
+    /// to the session: a blob's robot only lands on an attached one, and a
+    /// blob without one (or a pre-v2.5.0 blob) leaves it untouched.
+    pub(crate) const fn restore_rob(&mut self, state: Option<crate::rob::Rob>) {
+        if let (Some(rob), Some(saved)) = (&mut self.rob, state) {
+            *rob = saved;
+        }
+    }
+
+    /// Controller II buttons R.O.B.'s gyros are holding down this frame.
+    pub(crate) fn rob_pressed(&self) -> Buttons {
+        self.rob
+            .as_ref()
+            .map_or(Buttons::empty(), crate::rob::Rob::pressed)
+    }
+
+    /// Feed the completed frame to R.O.B.'s photodiode (screen centre, the
+    /// Zapper's aperture model). No-op with no robot attached.
+    pub(crate) fn sample_rob(&mut self) {
+        if let Some(rob) = &mut self.rob {
+            let bright = crate::input_device::ZapperState::aperture_is_bright(
+                self.ppu.framebuffer(),
+                128,
+                120,
+            );
+            rob.step_frame(bright);
+        }
+    }
+
+    /// The Data Recorder's `$4016` D1 contribution (0 with no tape).
+    fn tape_in_bit(&self) -> u8 {
+        self.data_recorder
+            .as_ref()
+            .map_or(0, |t| t.read(self.cycle))
+    }
+
     /// Update an attached Power Pad's live button mask (bit `i` = mat button
     /// `i+1`) on `port`. No-op if the attached device is not a Power Pad.
     ///
@@ -1851,6 +2040,159 @@ impl LockstepBus {
         }
     }
 
+    /// v2.5.0 — update an attached Oeka Kids tablet's pen on `port`. No-op if
+    /// the attached device is not an Oeka Kids tablet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in `0..=1`.
+    pub const fn set_oeka_kids_tablet(
+        &mut self,
+        port: usize,
+        x: u16,
+        y: u16,
+        touch: bool,
+        button: bool,
+    ) {
+        assert!(port < 2, "oeka kids tablet port must be 0..=1");
+        if let Some(crate::input_device::InputDevice::OekaKidsTablet(t)) =
+            &mut self.expansion_device[port]
+        {
+            t.set(x, y, touch, button);
+        }
+    }
+
+    /// v2.5.0 — update an attached Hori Track's ball motion and pad buttons on
+    /// `port`. No-op if the attached device is not a Hori Track.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in `0..=1`.
+    pub const fn set_hori_track(&mut self, port: usize, dx: i16, dy: i16, buttons: u8) {
+        assert!(port < 2, "hori track port must be 0..=1");
+        if let Some(crate::input_device::InputDevice::HoriTrack(h)) =
+            &mut self.expansion_device[port]
+        {
+            h.set(dx, dy, buttons);
+        }
+    }
+
+    /// v2.5.0 — update an attached Pachinko controller's buttons and plunger
+    /// on `port`. No-op if the attached device is not a Pachinko controller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in `0..=1`.
+    pub const fn set_pachinko(&mut self, port: usize, buttons: u8, plunger: u8) {
+        assert!(port < 2, "pachinko port must be 0..=1");
+        if let Some(crate::input_device::InputDevice::Pachinko(p)) =
+            &mut self.expansion_device[port]
+        {
+            p.set(buttons, plunger);
+        }
+    }
+
+    /// v2.5.0 — update an attached Top Rider's handlebars, throttle and
+    /// switches on `port`. No-op if the attached device is not a Top Rider.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in `0..=1`.
+    pub const fn set_top_rider(&mut self, port: usize, steer: i8, throttle: u8, keys: u8) {
+        assert!(port < 2, "top rider port must be 0..=1");
+        if let Some(crate::input_device::InputDevice::TopRider(t)) =
+            &mut self.expansion_device[port]
+        {
+            t.set(steer, throttle, keys);
+        }
+    }
+
+    /// v2.5.0 — update an attached Power Glove's hand and gesture program on
+    /// `port`. No-op if the attached device is not a Power Glove.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in `0..=1`.
+    pub const fn set_power_glove(
+        &mut self,
+        port: usize,
+        hand: crate::input_device::PowerGloveHand,
+        program: crate::input_device::PowerGloveProgram,
+    ) {
+        assert!(port < 2, "power glove port must be 0..=1");
+        if let Some(crate::input_device::InputDevice::PowerGlove(g)) =
+            &mut self.expansion_device[port]
+        {
+            g.set(hand, program);
+        }
+    }
+
+    /// The Turbo File attached on `port`, if that is the port's device.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in `0..=1`.
+    #[must_use]
+    pub const fn turbo_file(&self, port: usize) -> Option<&crate::input_device::TurboFileState> {
+        assert!(port < 2, "turbo file port must be 0..=1");
+        match &self.expansion_device[port] {
+            Some(crate::input_device::InputDevice::TurboFile(f)) => Some(f),
+            _ => None,
+        }
+    }
+
+    /// Update an attached Turbo File's bank switch on `port`. No-op if the
+    /// attached device is not a Turbo File.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in `0..=1`.
+    pub const fn set_turbo_file_bank(&mut self, port: usize, bank: u8) {
+        assert!(port < 2, "turbo file port must be 0..=1");
+        if let Some(crate::input_device::InputDevice::TurboFile(f)) =
+            &mut self.expansion_device[port]
+        {
+            f.set_bank(bank);
+        }
+    }
+
+    /// Insert (`Some`) or eject (`None`) the Turbo File image.
+    pub fn set_turbo_file_image(&mut self, image: Option<crate::input_device::TurboFileImage>) {
+        self.turbo_file = image;
+    }
+
+    /// The inserted Turbo File image, if any.
+    #[must_use]
+    pub const fn turbo_file_image(&self) -> Option<&crate::input_device::TurboFileImage> {
+        self.turbo_file.as_ref()
+    }
+
+    /// Mutable access to the inserted Turbo File image (clearing the dirty
+    /// flag after a flush).
+    pub const fn turbo_file_image_mut(
+        &mut self,
+    ) -> Option<&mut crate::input_device::TurboFileImage> {
+        self.turbo_file.as_mut()
+    }
+
+    /// Put a snapshot's Turbo File image back. An unchanged image is left
+    /// alone (the common rollback case, and it keeps the dirty flag); a
+    /// changed one is marked dirty so the frontend persists what the restore
+    /// brought back. An image of an impossible size can't come from
+    /// `encode_bus` and is skipped.
+    pub(crate) fn restore_turbo_file_image(&mut self, body: &[u8]) {
+        if let Some(image) = self.turbo_file.as_mut()
+            && image.storage().len() == body.len()
+        {
+            image.restore_storage(body);
+            return;
+        }
+        if let Some(mut image) = crate::input_device::TurboFileImage::from_storage(body.to_vec()) {
+            image.mark_dirty();
+            self.turbo_file = Some(image);
+        }
+    }
+
     /// v1.1.0 beta.1 (T-110-B4) — set (`Some`) or clear (`None`) the per-game
     /// nametable mirroring override. A frontend load-time correction; `None`
     /// (default) defers to the mapper (byte-identical).
@@ -2448,6 +2790,20 @@ impl LockstepBus {
         self.mapper.disk_image_bytes()
     }
 
+    /// Re-serialize the FDS disk image to the raw QD layout (stored CRCs
+    /// included) for a lossless export. Empty for cartridge builds.
+    #[must_use]
+    pub fn disk_image_qd_bytes(&self) -> Vec<u8> {
+        self.mapper.disk_image_qd_bytes()
+    }
+
+    /// Swap the inserted FDS disk image (see [`Nes::replace_disk_image`]).
+    ///
+    /// [`Nes::replace_disk_image`]: crate::Nes::replace_disk_image
+    pub fn replace_disk_image(&mut self, bytes: &[u8]) -> bool {
+        self.mapper.replace_disk_image(bytes)
+    }
+
     /// Whether the FDS disk image has unsaved writes.
     #[must_use]
     pub fn disk_is_dirty(&self) -> bool {
@@ -2464,6 +2820,24 @@ impl LockstepBus {
         self.mapper.set_disk_write_protected(protected);
     }
 
+    /// Whether the FDS device runs the high-level-emulated BIOS.
+    #[must_use]
+    pub fn fds_hle_active(&self) -> bool {
+        self.mapper.fds_hle_active()
+    }
+
+    /// The BIOS error code the HLE boot sequence stopped on, if any.
+    #[must_use]
+    pub fn fds_hle_boot_error(&self) -> Option<u8> {
+        self.mapper.fds_hle_boot_error()
+    }
+
+    /// Unimplemented HLE BIOS entry points the game has called.
+    #[must_use]
+    pub fn fds_hle_unsupported_calls(&self) -> Vec<u16> {
+        self.mapper.fds_hle_unsupported_calls()
+    }
+
     /// Commit a controller-strobe write to all controllers, resetting the
     /// Four Score read sequence + reloading its signature when enabled.
     const fn commit_controller_strobe(&mut self, value: u8) {
@@ -2473,12 +2847,8 @@ impl LockstepBus {
         // latches on it; the Zapper ignores it). Done unconditionally — the
         // standard controllers above are still strobed, so detaching a device
         // returns to byte-identical behavior.
-        if let Some(d) = &mut self.expansion_device[0] {
-            d.write_strobe(value);
-        }
-        if let Some(d) = &mut self.expansion_device[1] {
-            d.write_strobe(value);
-        }
+        self.strobe_expansion_device(0, value);
+        self.strobe_expansion_device(1, value);
         if self.four_score {
             self.controllers34[0].write_strobe(value);
             self.controllers34[1].write_strobe(value);
@@ -2489,6 +2859,18 @@ impl LockstepBus {
         }
     }
 
+    /// Forward a strobe write to `port`'s overlay device, if any. A Turbo
+    /// File stores into the bus-held image.
+    const fn strobe_expansion_device(&mut self, port: usize, value: u8) {
+        match &mut self.expansion_device[port] {
+            Some(crate::input_device::InputDevice::TurboFile(f)) => {
+                f.write_strobe(value, self.turbo_file.as_mut());
+            }
+            Some(d) => d.write_strobe(value),
+            None => {}
+        }
+    }
+
     /// Read the D0 controller bit for `port` (0 = `$4016`, 1 = `$4017`),
     /// advancing the shift register. Four Score off → just
     /// `controllers[port].read()`; on → the multiplexed 24-read sequence
@@ -2527,6 +2909,9 @@ impl LockstepBus {
                 Err(_) => z.read_before_visible(),
             };
         }
+        if let Some(crate::input_device::InputDevice::TurboFile(f)) = &self.expansion_device[port] {
+            return f.read(self.turbo_file.as_ref());
+        }
         if let Some(d) = &mut self.expansion_device[port] {
             return d.read();
         }
@@ -2569,6 +2954,9 @@ impl LockstepBus {
                 |sl| z.read_at_scanline(self.ppu.framebuffer(), sl),
             );
         }
+        if let Some(crate::input_device::InputDevice::TurboFile(f)) = &self.expansion_device[port] {
+            return f.read(self.turbo_file.as_ref());
+        }
         if let Some(d) = &self.expansion_device[port] {
             return d.peek();
         }
@@ -3476,8 +3864,8 @@ impl LockstepBus {
                 // normal controller read (line ~3890): D2 carries the Famicom
                 // built-in microphone. Default-off (mic released) leaves `mic`
                 // = 0, so the returned byte is byte-identical to prior releases.
-                let mic = u8::from(self.famicom_mic) << 2;
-                let v = (sample & 0xE0) | self.controllers[0].read() | mic;
+                let mic = self.mic_bit();
+                let v = (sample & 0xE0) | self.controllers[0].read() | mic | self.tape_in_bit();
                 self.open_bus = v;
                 v
             }
@@ -3815,8 +4203,8 @@ impl LockstepBus {
             // "Standard controller" + AccuracyCoin `CPU Behavior ::
             // Open Bus` Test 6.
             0x4016 => {
-                let mic = u8::from(self.famicom_mic) << 2;
-                let base = (self.open_bus & 0xE0) | self.read_port(0) | mic;
+                let mic = self.mic_bit();
+                let base = (self.open_bus & 0xE0) | self.read_port(0) | mic | self.tape_in_bit();
                 self.vs_overlay_4016(base)
             }
             0x4017 => {
@@ -3900,6 +4288,56 @@ const fn override_nt_addr(m: rustynes_mappers::Mirroring, addr: u16) -> u16 {
     (m.physical_bank(table as u8) as u16) * NT + local
 }
 
+/// Trap port of the high-level-emulated FDS BIOS ([`rustynes_mappers::fds_hle_bios`]).
+const FDS_HLE_TRIGGER: u16 = 0x40A3;
+
+/// The console memory a trapped HLE FDS BIOS service reaches: work RAM and
+/// the PPU's nametable / palette storage, borrowed alongside the mapper.
+struct FdsHleBusHost<'a> {
+    ram: &'a mut [u8; RAM_SIZE],
+    ppu: &'a mut Ppu,
+}
+
+impl rustynes_mappers::FdsHleHost for FdsHleBusHost<'_> {
+    fn read_ram(&mut self, addr: u16) -> u8 {
+        self.ram[(addr & 0x07FF) as usize]
+    }
+
+    fn write_ram(&mut self, addr: u16, value: u8) {
+        self.ram[(addr & 0x07FF) as usize] = value;
+    }
+
+    fn read_ciram(&mut self, offset: usize) -> u8 {
+        self.ppu.ciram()[offset & 0x07FF]
+    }
+
+    fn write_ciram(&mut self, offset: usize, value: u8) {
+        self.ppu.hle_write_ciram(offset, value);
+    }
+
+    fn read_palette(&mut self, addr: u16) -> u8 {
+        let idx = (addr & 0x1F) as usize;
+        let idx = if idx & 0x13 == 0x10 { idx & 0x0F } else { idx };
+        self.ppu.palette_ram()[idx]
+    }
+
+    fn write_palette(&mut self, addr: u16, value: u8) {
+        self.ppu.hle_write_palette(addr, value);
+    }
+}
+
+impl LockstepBus {
+    /// Run the HLE FDS BIOS service the mapper trapped, lending it work RAM
+    /// and the PPU's nametable / palette storage.
+    fn run_fds_hle_service(&mut self) {
+        let mut host = FdsHleBusHost {
+            ram: &mut self.ram,
+            ppu: &mut self.ppu,
+        };
+        self.mapper.run_fds_hle_service(&mut host);
+    }
+}
+
 /// Adapter that exposes the [`PpuBus`] interface over a `&mut dyn Mapper`.
 struct PpuBusAdapter<'a> {
     mapper: &'a mut dyn Mapper,
@@ -4232,6 +4670,12 @@ impl Bus for LockstepBus {
                 // edge is observed → no latch).  See
                 // `docs/audit/session-24-phase3-controller-strobing-2026-05-23.md`.
                 self.controller_write_value = value;
+                if let Some(tape) = &mut self.data_recorder {
+                    tape.write(value, self.cycle);
+                }
+                if let Some(glasses) = &mut self.famicom_3d {
+                    glasses.write(value);
+                }
                 // Parity convention: in `RustyNES` the CPU `cpu_write` runs
                 // INSIDE `tick_one_cpu_cycle` AFTER `self.cycle` has
                 // been incremented to the post-cycle value (see
@@ -4283,7 +4727,14 @@ impl Bus for LockstepBus {
                 self.vs_4016_bit1_dirty = true;
             }
             0x4018..=0x401F => {}
-            0x4020..=0xFFFF => self.mapper.cpu_write(addr, value),
+            0x4020..=0xFFFF => {
+                self.mapper.cpu_write(addr, value);
+                // v2.5.0 — a write to the HLE FDS BIOS trap port runs the
+                // trapped service before the instruction continues.
+                if addr == FDS_HLE_TRIGGER && self.mapper.fds_hle_pending() {
+                    self.run_fds_hle_service();
+                }
+            }
         }
         #[cfg(feature = "irq-timing-trace")]
         {
@@ -4969,6 +5420,87 @@ mod four_score_tests {
         );
     }
 
+    #[test]
+    fn data_recorder_plays_into_4016_bit1() {
+        use crate::data_recorder::{DataRecorder, TAPE_CYCLES_PER_SAMPLE};
+        let mut bus = test_bus();
+        assert_eq!(bus.peek_cpu(0x4016) & 0x02, 0x00, "no tape -> D1 clear");
+        bus.set_data_recorder(Some(DataRecorder::from_levels([true, false, true])));
+        assert_eq!(
+            bus.peek_cpu(0x4016) & 0x02,
+            0x00,
+            "stopped deck -> D1 clear"
+        );
+        bus.data_recorder_mut().unwrap().play();
+        assert_eq!(bus.peek_cpu(0x4016) & 0x02, 0x02, "sample 0 high");
+        bus.set_cycle(bus.cycle() + TAPE_CYCLES_PER_SAMPLE);
+        assert_eq!(bus.peek_cpu(0x4016) & 0x02, 0x00, "sample 1 low");
+        assert_eq!(
+            bus.peek_cpu(0x4017) & 0x02,
+            0x00,
+            "tape never touches $4017"
+        );
+    }
+
+    #[test]
+    fn data_recorder_transport_round_trips_through_save_state() {
+        use crate::data_recorder::{DataRecorder, TAPE_CYCLES_PER_SAMPLE, TapeMode};
+        let tape = DataRecorder::from_levels((0..64).map(|i| i % 2 == 0));
+        let mut bus = test_bus();
+        bus.set_data_recorder(Some(tape.clone()));
+        bus.data_recorder_mut().unwrap().play();
+        bus.set_cycle(bus.cycle() + 10 * TAPE_CYCLES_PER_SAMPLE);
+        bus.sync_data_recorder();
+        let blob = crate::bus_snapshot::encode_bus(&bus);
+
+        let mut restored = test_bus();
+        restored.set_data_recorder(Some(tape));
+        crate::bus_snapshot::decode_bus(&mut restored, &blob).unwrap();
+        let deck = restored.data_recorder().unwrap();
+        assert_eq!(deck.mode(), TapeMode::Playing);
+        assert_eq!(deck.position(), 10);
+        assert_eq!(
+            restored.peek_cpu(0x4016) & 0x02,
+            bus.peek_cpu(0x4016) & 0x02
+        );
+    }
+
+    #[test]
+    fn famicom_3d_latches_4016_bit1_and_round_trips() {
+        use crate::famicom_3d::StereoEye;
+        let mut bus = test_bus();
+        bus.set_famicom_3d(true);
+        bus.cpu_write(0x4016, 0x02);
+        bus.latch_famicom_3d_eye();
+        assert_eq!(bus.famicom_3d().unwrap().eye(), StereoEye::Right);
+        let blob = crate::bus_snapshot::encode_bus(&bus);
+
+        let mut restored = test_bus();
+        restored.set_famicom_3d(true);
+        crate::bus_snapshot::decode_bus(&mut restored, &blob).unwrap();
+        assert_eq!(restored.famicom_3d(), bus.famicom_3d());
+    }
+
+    #[test]
+    fn rob_state_round_trips_through_save_state() {
+        use crate::rob::{Rob, RobCommand, RobMode};
+        let mut rob = Rob::new(RobMode::Gyromite);
+        rob.execute(RobCommand::Left);
+        rob.execute(RobCommand::Down1);
+        let mut bus = test_bus();
+        bus.set_rob(Some(rob.clone()));
+        let blob = crate::bus_snapshot::encode_bus(&bus);
+
+        let mut restored = test_bus();
+        restored.set_rob(Some(Rob::new(RobMode::Gyromite)));
+        crate::bus_snapshot::decode_bus(&mut restored, &blob).unwrap();
+        assert_eq!(restored.rob(), Some(&rob));
+        // A robot-less session ignores the saved robot.
+        let mut plain = test_bus();
+        crate::bus_snapshot::decode_bus(&mut plain, &blob).unwrap();
+        assert!(plain.rob().is_none());
+    }
+
     #[test]
     fn four_score_off_reads_like_standard_controller() {
         let mut bus = test_bus();
@@ -5064,13 +5596,15 @@ mod four_score_tests {
         // more (dmc_halt + 3 uni_oam flags + uni_oam_addr u16 + ppu_clock
         // u64 + dma_mc_consumed u64); the v2.1.0 tail appends 2 more (one
         // expansion-device tag byte per port, both `None`); the v1.1.0 beta.1
-        // tail appends 1 more (the nametable mirroring-override tag, `None`).
-        // Truncating all 36 simulates a pre-v1.7.0 save, which must still load
-        // with the adapter off (and no expansion device / override).
+        // tail appends 1 more (the nametable mirroring-override tag, `None`);
+        // the v2.5.0 tail appends 4 more (the Data Recorder, R.O.B., 3D
+        // glasses and Turbo File image tags, none attached). Truncating all
+        // 40 simulates a pre-v1.7.0 save, which must still load with the
+        // adapter off (and no expansion device / override).
         let mut bus = test_bus();
         bus.set_four_score(true);
         let blob = crate::bus_snapshot::encode_bus(&bus);
-        let old = &blob[..blob.len() - 36];
+        let old = &blob[..blob.len() - 40];
         let mut restored = test_bus();
         restored.set_four_score(true); // prove decode actively turns it off
         crate::bus_snapshot::decode_bus(&mut restored, old).unwrap();
@@ -5107,6 +5641,127 @@ mod four_score_tests {
         }
     }
 
+    #[test]
+    fn oeka_kids_tablet_mid_report_round_trips_through_save_state() {
+        use crate::input_device::{InputDevice, OekaKidsTabletState};
+        let mut bus = test_bus();
+        bus.set_expansion_device(
+            1,
+            Some(InputDevice::OekaKidsTablet(OekaKidsTabletState::new())),
+        );
+        bus.set_oeka_kids_tablet(1, 64, 96, true, false);
+        // Latch, enter shift mode and clock one bit out.
+        bus.commit_controller_strobe(0x00);
+        bus.commit_controller_strobe(0x01);
+        bus.commit_controller_strobe(0x03);
+        let blob = crate::bus_snapshot::encode_bus(&bus);
+        let mut restored = test_bus();
+        crate::bus_snapshot::decode_bus(&mut restored, &blob).unwrap();
+        match restored.expansion_device(1) {
+            Some(InputDevice::OekaKidsTablet(t)) => {
+                assert_eq!((t.x_raw(), t.y_raw()), (64, 96));
+                assert!(t.touch_raw() && t.strobe_raw() && t.clock_raw());
+            }
+            other => panic!("port 1 should be an Oeka Kids tablet, got {other:?}"),
+        }
+        assert_eq!(
+            restored.peek_cpu(0x4017) & 0x0C,
+            bus.peek_cpu(0x4017) & 0x0C
+        );
+    }
+
+    #[test]
+    fn turbo_file_reads_on_4017_d2_and_round_trips_its_image() {
+        use crate::input_device::{InputDevice, TurboFileImage, TurboFileState};
+        let mut bus = test_bus();
+        bus.set_expansion_device(1, Some(InputDevice::TurboFile(TurboFileState::new())));
+        bus.set_turbo_file_image(Some(TurboFileImage::new_twin()));
+        bus.set_turbo_file_bank(1, 1);
+        bus.commit_controller_strobe(0x00);
+        for bit in [1, 0, 1] {
+            bus.commit_controller_strobe(0x06 | bit);
+            bus.commit_controller_strobe(0x02 | bit);
+        }
+        bus.commit_controller_strobe(0x00);
+        assert_eq!(bus.cpu_read(0x4017) & 0x04, 0x04, "bit 0 reads back on D2");
+        // Clock on to bit 1, writing the bit just read back as a game does.
+        bus.commit_controller_strobe(0x07);
+        bus.commit_controller_strobe(0x03);
+        assert_eq!(bus.cpu_read(0x4017) & 0x04, 0x00, "bit 1");
+        let image = bus.turbo_file_image().unwrap();
+        assert_eq!(
+            image.storage()[crate::input_device::TURBO_FILE_BANK_SIZE],
+            0b101
+        );
+        assert!(image.is_dirty());
+
+        // The snapshot carries the port state and the image.
+        let blob = crate::bus_snapshot::encode_bus(&bus);
+        let mut restored = test_bus();
+        crate::bus_snapshot::decode_bus(&mut restored, &blob).unwrap();
+        let file = restored.turbo_file(1).unwrap();
+        assert_eq!(file, bus.turbo_file(1).unwrap());
+        assert_eq!(file.bank(), 1);
+        assert_eq!(
+            restored.turbo_file_image().unwrap().storage(),
+            bus.turbo_file_image().unwrap().storage()
+        );
+        assert!(restored.turbo_file_image().unwrap().is_dirty());
+
+        // Restoring over the same image leaves a flushed one clean.
+        bus.turbo_file_image_mut().unwrap().clear_dirty();
+        crate::bus_snapshot::decode_bus(&mut bus, &blob).unwrap();
+        assert!(!bus.turbo_file_image().unwrap().is_dirty());
+    }
+
+    #[test]
+    fn new_expansion_controllers_round_trip_through_save_state() {
+        use crate::input_device::{
+            HoriTrackState, InputDevice, PachinkoState, PowerGloveHand, PowerGloveProgram,
+            PowerGloveState, TopRiderState,
+        };
+        let devices = [
+            InputDevice::HoriTrack(HoriTrackState::new()),
+            InputDevice::Pachinko(PachinkoState::new()),
+            InputDevice::TopRider(TopRiderState::new()),
+            InputDevice::PowerGlove(PowerGloveState::new(PowerGloveProgram::Punch)),
+        ];
+        for device in devices {
+            let mut bus = test_bus();
+            bus.set_expansion_device(1, Some(device));
+            bus.set_hori_track(1, 3, -2, 0x81);
+            bus.set_pachinko(1, 0x10, 0xA0);
+            bus.set_top_rider(1, -2, 3, 0x05);
+            bus.set_power_glove(
+                1,
+                PowerGloveHand {
+                    x: 30,
+                    fingers: 0xFF,
+                    ..PowerGloveHand::default()
+                },
+                PowerGloveProgram::Punch,
+            );
+            // Latch and shift one bit out, so the restore lands mid-report.
+            bus.commit_controller_strobe(0x01);
+            bus.commit_controller_strobe(0x00);
+            let _ = bus.cpu_read(0x4017);
+            let blob = crate::bus_snapshot::encode_bus(&bus);
+            let mut restored = test_bus();
+            crate::bus_snapshot::decode_bus(&mut restored, &blob).unwrap();
+            assert_eq!(
+                core::mem::discriminant(restored.expansion_device(1).as_ref().unwrap()),
+                core::mem::discriminant(&device),
+            );
+            for _ in 0..24 {
+                assert_eq!(
+                    restored.cpu_read(0x4017) & 0x03,
+                    bus.cpu_read(0x4017) & 0x03,
+                    "{device:?}"
+                );
+            }
+        }
+    }
+
     /// With the beam-relative Zapper model on, a debugger peek of `$4017` must
     /// return the SAME light contribution the CPU read produces — at the
     /// pre-render line and at a visible line — and must not advance device
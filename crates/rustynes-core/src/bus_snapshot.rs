@@ -7,9 +7,12 @@
 
 use crate::bus::LockstepBus;
 use crate::controller::Controller;
+use crate::data_recorder::TapeMode;
+use crate::famicom_3d::Famicom3dGlasses;
 use crate::input_device::{
     FamilyKeyboardState, InputDevice, SnesMouseState, VausState, ZapperState,
 };
+use crate::rob::{ROB_STATE_LEN, Rob};
 use crate::save_state::{BinReader, BinWriter, SnapshotError};
 use alloc::format;
 use alloc::vec::Vec;
@@ -92,6 +95,49 @@ pub fn encode_bus(bus: &LockstepBus) -> Vec<u8> {
     // v1.1.0 beta.1 (T-110-B4) — per-game nametable mirroring override (trailing
     // field; pre-v1.1.0 blobs lack it and decode as `None` = no override).
     w.u8(encode_mirroring_override(bus.mirroring_override()));
+    // v2.5.0 Data Recorder transport (trailing field; 0 = no tape inserted).
+    // Only the transport travels — the tape itself belongs to the session,
+    // like an FDS disk. Pre-v2.5.0 blobs lack it and leave the deck as is.
+    match bus.data_recorder() {
+        None => w.u8(0),
+        Some(tape) => {
+            let (mode, position, out_level, synced_cycle) = tape.transport_parts();
+            w.u8(1);
+            w.u8(mode.to_u8());
+            w.u64(position as u64);
+            w.u8(u8::from(out_level));
+            w.u64(synced_cycle);
+        }
+    }
+    // v2.5.0 R.O.B. state (trailing field; 0 = no robot). Pre-v2.5.0 blobs
+    // lack it and leave an attached robot as is.
+    match bus.rob() {
+        None => w.u8(0),
+        Some(rob) => {
+            w.u8(1);
+            for b in rob.to_bytes() {
+                w.u8(b);
+            }
+        }
+    }
+    // v2.5.0 Famicom 3D System glasses (trailing field; 0 = not attached).
+    match bus.famicom_3d() {
+        None => w.u8(0),
+        Some(glasses) => {
+            w.u8(1);
+            w.u8(glasses.to_u8());
+        }
+    }
+    // v2.5.0 Turbo File image (trailing field; 0 = none inserted). It travels
+    // in every snapshot, like cartridge PRG-RAM in the MAP section, so a
+    // rewind or a netplay rollback undoes the writes it rolls past.
+    match bus.turbo_file_image() {
+        None => w.u8(0),
+        Some(image) => {
+            w.u8(1);
+            w.lp_bytes(image.storage());
+        }
+    }
     w.into_vec()
 }
 
@@ -124,6 +170,8 @@ const fn decode_mirroring_override(tag: u8) -> Option<rustynes_mappers::Mirrorin
 }
 
 /// Encode one port's optional overlay device (tag byte + fields).
+// One arm per device tag, mirroring `decode_expansion_device`.
+#[allow(clippy::too_many_lines)]
 fn encode_expansion_device(w: &mut BinWriter, device: Option<&InputDevice>) {
     match device {
         None => w.u8(0),
@@ -196,6 +244,55 @@ fn encode_expansion_device(w: &mut BinWriter, device: Option<&InputDevice>) {
             w.u8(b.sensors_raw());
             w.bool(b.select_raw());
         }
+        Some(InputDevice::OekaKidsTablet(t)) => {
+            w.u8(10);
+            w.u16(t.x_raw());
+            w.u16(t.y_raw());
+            w.bool(t.touch_raw());
+            w.bool(t.button_raw());
+            w.u32(t.report_raw());
+            w.bool(t.strobe_raw());
+            w.bool(t.clock_raw());
+        }
+        Some(InputDevice::HoriTrack(h)) => {
+            w.u8(11);
+            w.i16(h.dx_raw());
+            w.i16(h.dy_raw());
+            w.u8(h.buttons_raw());
+            w.u32(h.shift_raw());
+            w.u8(h.read_count_raw());
+            w.bool(h.strobe_raw());
+        }
+        Some(InputDevice::Pachinko(p)) => {
+            w.u8(12);
+            w.u8(p.buttons_raw());
+            w.u8(p.plunger_raw());
+            w.u16(p.shift_raw());
+            w.u8(p.read_count_raw());
+            w.bool(p.strobe_raw());
+        }
+        Some(InputDevice::TopRider(t)) => {
+            w.u8(13);
+            w.u8(t.steer_raw().cast_unsigned());
+            w.u8(t.throttle_raw());
+            w.u8(t.keys_raw());
+            w.u16(t.shift_raw());
+            w.u8(t.read_count_raw());
+            w.bool(t.strobe_raw());
+        }
+        Some(InputDevice::PowerGlove(g)) => {
+            w.u8(14);
+            for b in g.to_bytes() {
+                w.u8(b);
+            }
+        }
+        // The image is held by the bus and encoded at the tail of the section.
+        Some(InputDevice::TurboFile(f)) => {
+            w.u8(15);
+            w.u8(f.bank());
+            w.u16(f.position_raw());
+            w.u8(f.last_write_raw());
+        }
     }
 }
 
@@ -303,6 +400,75 @@ fn decode_expansion_device(r: &mut BinReader<'_>) -> Result<Option<InputDevice>,
                 crate::input_device::BandaiHyperShotState::from_parts(sensors, select),
             ))
         }
+        10 => {
+            let x = r.u16()?;
+            let y = r.u16()?;
+            let touch = r.bool()?;
+            let button = r.bool()?;
+            let report = r.u32()?;
+            let strobe = r.bool()?;
+            let clock = r.bool()?;
+            Some(InputDevice::OekaKidsTablet(
+                crate::input_device::OekaKidsTabletState::from_parts(
+                    x, y, touch, button, report, strobe, clock,
+                ),
+            ))
+        }
+        11 => {
+            let dx = r.i16()?;
+            let dy = r.i16()?;
+            let buttons = r.u8()?;
+            let shift = r.u32()?;
+            let read_count = r.u8()?;
+            let strobe = r.bool()?;
+            Some(InputDevice::HoriTrack(
+                crate::input_device::HoriTrackState::from_parts(
+                    dx, dy, buttons, shift, read_count, strobe,
+                ),
+            ))
+        }
+        12 => {
+            let buttons = r.u8()?;
+            let plunger = r.u8()?;
+            let shift = r.u16()?;
+            let read_count = r.u8()?;
+            let strobe = r.bool()?;
+            Some(InputDevice::Pachinko(
+                crate::input_device::PachinkoState::from_parts(
+                    buttons, plunger, shift, read_count, strobe,
+                ),
+            ))
+        }
+        13 => {
+            let steer = r.u8()?.cast_signed();
+            let throttle = r.u8()?;
+            let keys = r.u8()?;
+            let shift = r.u16()?;
+            let read_count = r.u8()?;
+            let strobe = r.bool()?;
+            Some(InputDevice::TopRider(
+                crate::input_device::TopRiderState::from_parts(
+                    steer, throttle, keys, shift, read_count, strobe,
+                ),
+            ))
+        }
+        14 => {
+            let mut b = [0u8; crate::input_device::POWER_GLOVE_STATE_LEN];
+            for byte in &mut b {
+                *byte = r.u8()?;
+            }
+            Some(InputDevice::PowerGlove(
+                crate::input_device::PowerGloveState::from_bytes(&b),
+            ))
+        }
+        15 => {
+            let bank = r.u8()?;
+            let position = r.u16()?;
+            let last_write = r.u8()?;
+            Some(InputDevice::TurboFile(
+                crate::input_device::TurboFileState::from_parts(bank, position, last_write),
+            ))
+        }
         // 0 (None) or any unknown tag => no device.
         _ => None,
     })
@@ -414,6 +580,39 @@ pub fn decode_bus(bus: &mut LockstepBus, data: &[u8]) -> Result<(), SnapshotErro
         None
     };
     bus.set_mirroring_override(mirroring_override);
+    // v2.5.0 Data Recorder transport (trailing-default: absent = none saved).
+    let tape = if r.remaining() >= 1 && r.u8()? == 1 {
+        let mode = TapeMode::from_u8(r.u8()?);
+        let position = usize::try_from(r.u64()?).unwrap_or(usize::MAX);
+        let out_level = r.u8()? != 0;
+        let synced_cycle = r.u64()?;
+        Some((mode, position, out_level, synced_cycle))
+    } else {
+        None
+    };
+    bus.restore_data_recorder_transport(tape);
+    // v2.5.0 R.O.B. state (trailing-default: absent = none saved).
+    let rob = if r.remaining() >= 1 && r.u8()? == 1 {
+        let mut b = [0u8; ROB_STATE_LEN];
+        for byte in &mut b {
+            *byte = r.u8()?;
+        }
+        Some(Rob::from_bytes(&b))
+    } else {
+        None
+    };
+    bus.restore_rob(rob);
+    // v2.5.0 Famicom 3D System glasses (trailing-default: absent = none saved).
+    let glasses = if r.remaining() >= 1 && r.u8()? == 1 {
+        Some(Famicom3dGlasses::from_u8(r.u8()?))
+    } else {
+        None
+    };
+    bus.restore_famicom_3d(glasses);
+    // v2.5.0 Turbo File image (trailing-default: absent = none saved).
+    if r.remaining() >= 1 && r.u8()? == 1 {
+        bus.restore_turbo_file_image(r.lp_bytes()?);
+    }
     bus.set_bus_misc_state(BusMiscState {
         dma_pending,
         dma_cycles_owed,
@@ -0,0 +1,476 @@
+//! Family BASIC **Data Recorder** (HVC-008 cassette deck) emulation.
+//!
+//! The Data Recorder hangs off the Family BASIC keyboard's audio jacks. Per
+//! the `NESdev` "Family BASIC Data Recorder" page (and Mesen2's
+//! `FamilyBasicDataRecorder`), software sees it on `$4016`:
+//!
+//! ```text
+//! $4016 write  ---- -E-O   O: tape audio out, E: keyboard/recorder enable
+//! $4016 read   ---- --I-   I: tape audio in (thresholded)
+//! ```
+//!
+//! The output bit only reaches the tape while `E` is set; the keyboard's key
+//! switches keep `$4017` D1..D4 to themselves.
+//!
+//! The tape is a 1-bit level stream sampled every [`TAPE_CYCLES_PER_SAMPLE`]
+//! CPU cycles (~32 kHz on NTSC). Keying it to the CPU cycle counter instead of
+//! wall-clock time keeps playback and recording deterministic: the level a
+//! `$4016` read sees is a pure function of the tape contents, the transport
+//! position, and the cycle of the read. The bus advances the deck lazily on
+//! each `$4016` write and once per frame, so a stopped deck costs nothing.
+//!
+//! Tapes load from and save to WAV ([`DataRecorder::from_wav`],
+//! [`DataRecorder::to_wav`]). Loading thresholds the analog signal with a
+//! DC-centred Schmitt trigger (hysteresis at 1/8 of the peak swing) before
+//! resampling to the tape rate, so a noisy dub of a real cassette reads
+//! cleanly. Save-states carry the transport (mode + position), not the tape
+//! itself — like a disk in a drive, the medium belongs to the session.
+
+use alloc::vec::Vec;
+use thiserror::Error;
+
+/// CPU cycles per tape sample (`1_789_773 / 56` ~ 31.96 kHz on NTSC).
+pub const TAPE_CYCLES_PER_SAMPLE: u64 = 56;
+
+/// Nominal tape sample rate in Hz, used as the WAV rate on export and as the
+/// resampling target on import.
+pub const TAPE_SAMPLE_RATE: u32 = 31_960;
+
+/// Transport state of the deck.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TapeMode {
+    /// Motor off; the position does not move.
+    #[default]
+    Stopped,
+    /// Playing back into `$4016` D1 until the end of the tape.
+    Playing,
+    /// Recording the `$4016` D0 output level, overwriting / extending the tape.
+    Recording,
+}
+
+impl TapeMode {
+    /// Save-state tag byte.
+    #[must_use]
+    pub const fn to_u8(self) -> u8 {
+        match self {
+            Self::Stopped => 0,
+            Self::Playing => 1,
+            Self::Recording => 2,
+        }
+    }
+
+    /// Inverse of [`Self::to_u8`]; unknown bytes decode as `Stopped`.
+    #[must_use]
+    pub const fn from_u8(v: u8) -> Self {
+        match v {
+            1 => Self::Playing,
+            2 => Self::Recording,
+            _ => Self::Stopped,
+        }
+    }
+}
+
+/// Errors from [`DataRecorder::from_wav`].
+#[derive(Debug, Error, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum WavError {
+    /// Not a `RIFF` / `WAVE` file.
+    #[error("not a RIFF/WAVE file")]
+    NotWav,
+    /// The `fmt ` or `data` chunk is missing or truncated.
+    #[error("WAV file is missing its {0} chunk")]
+    MissingChunk(&'static str),
+    /// A sample encoding other than 8/16/24/32-bit PCM or 32-bit float.
+    #[error("unsupported WAV encoding (format {format}, {bits} bits)")]
+    Unsupported {
+        /// `wFormatTag` from the `fmt ` chunk.
+        format: u16,
+        /// `wBitsPerSample` from the `fmt ` chunk.
+        bits: u16,
+    },
+}
+
+/// A cassette in the Data Recorder plus the deck's transport.
+#[derive(Clone, Debug, Default)]
+pub struct DataRecorder {
+    /// Tape levels, one bit per sample, LSB-first within each byte.
+    bits: Vec<u8>,
+    /// Number of valid samples in `bits`.
+    len: usize,
+    /// Transport position in samples, as of `synced_cycle`.
+    position: usize,
+    mode: TapeMode,
+    /// Last `$4016` D0 level written while the recorder was enabled.
+    out_level: bool,
+    /// CPU cycle the transport was last advanced to.
+    synced_cycle: u64,
+}
+
+impl DataRecorder {
+    /// A blank tape, stopped at the start.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a tape from already-thresholded levels at [`TAPE_SAMPLE_RATE`].
+    #[must_use]
+    pub fn from_levels(levels: impl IntoIterator<Item = bool>) -> Self {
+        let mut tape = Self::new();
+        for level in levels {
+            tape.set_sample(tape.len, level);
+        }
+        tape
+    }
+
+    /// Load a tape from a WAV file (8/16/24/32-bit PCM or 32-bit float, any
+    /// rate, first channel only).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WavError`] when the file is not a WAV or uses an unsupported
+    /// encoding.
+    pub fn from_wav(bytes: &[u8]) -> Result<Self, WavError> {
+        let wav = parse_wav(bytes)?;
+        let levels = threshold(&wav.samples);
+        if levels.is_empty() || wav.rate == 0 {
+            return Ok(Self::new());
+        }
+        // Nearest-earlier resample from the source rate onto the tape rate.
+        let src_len = levels.len() as u64;
+        let out_len = src_len * u64::from(TAPE_SAMPLE_RATE) / u64::from(wav.rate);
+        Ok(Self::from_levels((0..out_len).map(|i| {
+            let src = i * u64::from(wav.rate) / u64::from(TAPE_SAMPLE_RATE);
+            #[allow(clippy::cast_possible_truncation)] // src < levels.len()
+            levels[src as usize]
+        })))
+    }
+
+    /// Encode the tape as an 8-bit mono WAV at [`TAPE_SAMPLE_RATE`].
+    #[must_use]
+    pub fn to_wav(&self) -> Vec<u8> {
+        #[allow(clippy::cast_possible_truncation)] // tapes are far below 4 GiB
+        let data_len = self.len as u32;
+        let mut out = Vec::with_capacity(44 + self.len);
+        out.extend_from_slice(b"RIFF");
+        out.extend_from_slice(&(36 + data_len).to_le_bytes());
+        out.extend_from_slice(b"WAVEfmt ");
+        out.extend_from_slice(&16u32.to_le_bytes());
+        out.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        out.extend_from_slice(&1u16.to_le_bytes()); // mono
+        out.extend_from_slice(&TAPE_SAMPLE_RATE.to_le_bytes());
+        out.extend_from_slice(&TAPE_SAMPLE_RATE.to_le_bytes()); // byte rate
+        out.extend_from_slice(&1u16.to_le_bytes()); // block align
+        out.extend_from_slice(&8u16.to_le_bytes());
+        out.extend_from_slice(b"data");
+        out.extend_from_slice(&data_len.to_le_bytes());
+        out.extend((0..self.len).map(|i| if self.sample(i) { 0xC0 } else { 0x40 }));
+        out
+    }
+
+    /// Tape length in samples.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the tape holds no samples.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Transport position in samples (as of the last bus sync).
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Current transport mode.
+    #[must_use]
+    pub const fn mode(&self) -> TapeMode {
+        self.mode
+    }
+
+    /// Start playback from the current position.
+    pub const fn play(&mut self) {
+        self.mode = TapeMode::Playing;
+    }
+
+    /// Start recording at the current position (overwriting what follows).
+    pub const fn record(&mut self) {
+        self.mode = TapeMode::Recording;
+    }
+
+    /// Stop the motor.
+    pub const fn stop(&mut self) {
+        self.mode = TapeMode::Stopped;
+    }
+
+    /// Stop and return to the start of the tape.
+    pub const fn rewind(&mut self) {
+        self.mode = TapeMode::Stopped;
+        self.position = 0;
+    }
+
+    /// Move the transport to `position` (clamped to the tape length).
+    pub fn seek(&mut self, position: usize) {
+        self.position = position.min(self.len);
+    }
+
+    /// Level of sample `i` (`false` past the end).
+    #[must_use]
+    pub fn sample(&self, i: usize) -> bool {
+        i < self.len && self.bits[i / 8] & (1 << (i % 8)) != 0
+    }
+
+    fn set_sample(&mut self, i: usize, level: bool) {
+        if i / 8 >= self.bits.len() {
+            self.bits.resize(i / 8 + 1, 0);
+        }
+        if level {
+            self.bits[i / 8] |= 1 << (i % 8);
+        } else {
+            self.bits[i / 8] &= !(1 << (i % 8));
+        }
+        self.len = self.len.max(i + 1);
+    }
+
+    /// Whole samples elapsed between the last sync and `cycle`.
+    const fn samples_until(&self, cycle: u64) -> u64 {
+        (cycle / TAPE_CYCLES_PER_SAMPLE).saturating_sub(self.synced_cycle / TAPE_CYCLES_PER_SAMPLE)
+    }
+
+    /// Advance the transport to `cycle`, recording the held output level
+    /// into every elapsed sample when recording. Playback stops at the end.
+    pub(crate) fn sync(&mut self, cycle: u64) {
+        let elapsed = usize::try_from(self.samples_until(cycle)).unwrap_or(usize::MAX);
+        match self.mode {
+            TapeMode::Stopped => {}
+            TapeMode::Playing => {
+                self.position = self.position.saturating_add(elapsed);
+                if self.position >= self.len {
+                    self.position = self.len;
+                    self.mode = TapeMode::Stopped;
+                }
+            }
+            TapeMode::Recording => {
+                for _ in 0..elapsed {
+                    self.set_sample(self.position, self.out_level);
+                    self.position += 1;
+                }
+            }
+        }
+        self.synced_cycle = cycle;
+    }
+
+    /// Re-anchor the transport clock without moving the tape (attach, power
+    /// cycle, or a save-state that predates the recorder).
+    pub(crate) const fn rebase(&mut self, cycle: u64) {
+        self.synced_cycle = cycle;
+    }
+
+    /// Handle a `$4016` write at `cycle`.
+    pub(crate) fn write(&mut self, value: u8, cycle: u64) {
+        self.sync(cycle);
+        if value & 0x04 != 0 {
+            self.out_level = value & 0x01 != 0;
+        }
+    }
+
+    /// The `$4016` D1 contribution at `cycle` (side-effect free).
+    #[must_use]
+    pub(crate) fn read(&self, cycle: u64) -> u8 {
+        if self.mode != TapeMode::Playing {
+            return 0;
+        }
+        let at = usize::try_from(self.samples_until(cycle))
+            .map_or(usize::MAX, |n| self.position.saturating_add(n));
+        u8::from(self.sample(at)) << 1
+    }
+
+    /// Transport parts for the save-state: mode, position, held output level,
+    /// and the cycle the position is anchored to.
+    #[must_use]
+    pub(crate) const fn transport_parts(&self) -> (TapeMode, usize, bool, u64) {
+        (self.mode, self.position, self.out_level, self.synced_cycle)
+    }
+
+    /// Restore transport parts captured by [`Self::transport_parts`]. The
+    /// position is clamped to this tape's length.
+    pub(crate) fn set_transport_parts(
+        &mut self,
+        mode: TapeMode,
+        position: usize,
+        out_level: bool,
+        synced_cycle: u64,
+    ) {
+        self.mode = mode;
+        self.position = position.min(self.len);
+        self.out_level = out_level;
+        self.synced_cycle = synced_cycle;
+    }
+}
+
+struct Wav {
+    rate: u32,
+    /// First-channel samples, normalized to `i32` (16-bit scale).
+    samples: Vec<i32>,
+}
+
+fn le16(b: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([b[at], b[at + 1]])
+}
+
+fn le32(b: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([b[at], b[at + 1], b[at + 2], b[at + 3]])
+}
+
+fn parse_wav(bytes: &[u8]) -> Result<Wav, WavError> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(WavError::NotWav);
+    }
+    let mut fmt: Option<(u16, u16, u32, u16)> = None;
+    let mut data: Option<&[u8]> = None;
+    let mut at = 12;
+    while at + 8 <= bytes.len() {
+        let id = &bytes[at..at + 4];
+        let size = le32(bytes, at + 4) as usize;
+        let body = &bytes[at + 8..(at + 8).saturating_add(size).min(bytes.len())];
+        if id == b"fmt " {
+            if body.len() < 16 {
+                return Err(WavError::MissingChunk("fmt"));
+            }
+            fmt = Some((le16(body, 0), le16(body, 2), le32(body, 4), le16(body, 14)));
+        } else if id == b"data" {
+            data = Some(body);
+        }
+        // Chunks are word-aligned.
+        at = at
+            .saturating_add(8)
+            .saturating_add(size)
+            .saturating_add(size & 1);
+    }
+    let (format, channels, rate, bits) = fmt.ok_or(WavError::MissingChunk("fmt"))?;
+    let data = data.ok_or(WavError::MissingChunk("data"))?;
+    // WAVE_FORMAT_EXTENSIBLE is read as integer PCM of its bit depth.
+    let float = format == 3;
+    let ok = matches!((format, bits), (1 | 0xFFFE, 8 | 16 | 24 | 32) | (3, 32));
+    if !ok || channels == 0 {
+        return Err(WavError::Unsupported { format, bits });
+    }
+    let width = usize::from(bits / 8);
+    let frame = width * usize::from(channels);
+    let samples = data
+        .chunks_exact(frame)
+        .map(|f| decode_sample(&f[..width], bits, float))
+        .collect();
+    Ok(Wav { rate, samples })
+}
+
+/// Decode one little-endian sample onto a 16-bit signed scale.
+fn decode_sample(b: &[u8], bits: u16, float: bool) -> i32 {
+    match (bits, float) {
+        (8, _) => (i32::from(b[0]) - 128) << 8,
+        (16, _) => i32::from(i16::from_le_bytes([b[0], b[1]])),
+        (24, _) => i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 16,
+        (32, true) => {
+            let v = f32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+            #[allow(clippy::cast_possible_truncation)] // clamped to i16 range
+            let s = (v.clamp(-1.0, 1.0) * 32767.0) as i32;
+            s
+        }
+        _ => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) >> 16,
+    }
+}
+
+/// DC-centred Schmitt trigger: the level flips high above `mean + h` and low
+/// below `mean - h`, with `h` an eighth of the peak deviation from the mean.
+fn threshold(samples: &[i32]) -> Vec<bool> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+    let sum: i64 = samples.iter().map(|&s| i64::from(s)).sum();
+    #[allow(clippy::cast_possible_wrap)] // sample counts fit in i64
+    let mean = sum / samples.len() as i64;
+    let peak = samples
+        .iter()
+        .map(|&s| (i64::from(s) - mean).abs())
+        .max()
+        .unwrap_or(0);
+    let h = peak / 8;
+    let mut level = false;
+    samples
+        .iter()
+        .map(|&s| {
+            let d = i64::from(s) - mean;
+            if d > h {
+                level = true;
+            } else if d < -h {
+                level = false;
+            }
+            level
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wav_round_trip_preserves_levels() {
+        let levels: Vec<bool> = (0..1000).map(|i| (i / 7) % 3 == 0).collect();
+        let tape = DataRecorder::from_levels(levels.iter().copied());
+        let back = DataRecorder::from_wav(&tape.to_wav()).expect("own WAV parses");
+        assert_eq!(back.len(), levels.len());
+        assert!((0..levels.len()).all(|i| back.sample(i) == levels[i]));
+    }
+
+    #[test]
+    fn threshold_rejects_noise_inside_hysteresis_band() {
+        // A square wave with +/-100 ripple on a +/-10000 swing and a DC offset.
+        let samples: Vec<i32> = (0..400)
+            .map(|i| {
+                let base = if (i / 50) % 2 == 0 { 12_000 } else { -8_000 };
+                base + if i % 2 == 0 { 100 } else { -100 }
+            })
+            .collect();
+        let levels = threshold(&samples);
+        for (i, &l) in levels.iter().enumerate() {
+            assert_eq!(l, (i / 50) % 2 == 0, "sample {i}");
+        }
+    }
+
+    #[test]
+    fn playback_tracks_cpu_cycles_and_stops_at_end() {
+        let mut tape = DataRecorder::from_levels([false, true, true, false]);
+        tape.play();
+        assert_eq!(tape.read(0), 0);
+        assert_eq!(tape.read(TAPE_CYCLES_PER_SAMPLE), 0x02);
+        assert_eq!(tape.read(3 * TAPE_CYCLES_PER_SAMPLE), 0);
+        tape.sync(10 * TAPE_CYCLES_PER_SAMPLE);
+        assert_eq!(tape.mode(), TapeMode::Stopped);
+        assert_eq!(tape.position(), 4);
+    }
+
+    #[test]
+    fn recording_captures_gated_output_level() {
+        let mut tape = DataRecorder::new();
+        tape.record();
+        tape.write(0x05, 0); // enabled, out = 1
+        tape.write(0x00, 3 * TAPE_CYCLES_PER_SAMPLE); // disabled: level held
+        tape.write(0x04, 5 * TAPE_CYCLES_PER_SAMPLE); // enabled, out = 0
+        tape.sync(8 * TAPE_CYCLES_PER_SAMPLE);
+        let got: Vec<bool> = (0..tape.len()).map(|i| tape.sample(i)).collect();
+        assert_eq!(got, [true, true, true, true, true, false, false, false]);
+    }
+
+    #[test]
+    fn rejects_non_wav_input() {
+        assert_eq!(
+            DataRecorder::from_wav(b"not a wav").unwrap_err(),
+            WavError::NotWav
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! **Famicom 3D System** (HVC-031) shutter-glasses latch.
+//!
+//! The 3D System is a pair of LCD shutter glasses on the expansion port.
+//! Stereo titles (*Famicom Grand Prix II: 3D Hot Rally*, *Highway Star*,
+//! *Cosmic Epsilon*, ...) draw the two eyes' views on alternate frames and
+//! toggle `$4016` D1 to tell the glasses which shutter to open:
+//!
+//! ```text
+//! $4016 write  ---- --S-   S: 0 = left eye's frame, 1 = right eye's frame
+//! ```
+//!
+//! The glasses never answer on a read, so the only thing to model is the
+//! shutter level and which eye each completed frame was drawn for. The level
+//! is latched at the end of every frame ([`Famicom3dGlasses::end_frame`]):
+//! games flip it in vblank, before the frame it describes is drawn. Pairing
+//! frames and presenting them (anaglyph, side-by-side, one eye) is left to the
+//! frontend — the framebuffer stream itself is untouched.
+//!
+//! Some titles wire the polarity the other way round; frontends offer an
+//! eye swap rather than the core guessing per game.
+
+/// Which eye a frame was drawn for.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum StereoEye {
+    /// `$4016` D1 low.
+    #[default]
+    Left,
+    /// `$4016` D1 high.
+    Right,
+}
+
+impl StereoEye {
+    /// The opposite eye.
+    #[must_use]
+    pub const fn other(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// Shutter level + the eye latched for the last completed frame.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Famicom3dGlasses {
+    shutter: bool,
+    frame_eye: StereoEye,
+}
+
+impl Famicom3dGlasses {
+    /// Glasses with the left shutter open.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            shutter: false,
+            frame_eye: StereoEye::Left,
+        }
+    }
+
+    /// A `$4016` write: D1 drives the shutters.
+    pub const fn write(&mut self, value: u8) {
+        self.shutter = value & 0x02 != 0;
+    }
+
+    /// Latch the shutter level as the eye of the frame that just completed.
+    pub const fn end_frame(&mut self) {
+        self.frame_eye = if self.shutter {
+            StereoEye::Right
+        } else {
+            StereoEye::Left
+        };
+    }
+
+    /// The eye the last completed frame was drawn for.
+    #[must_use]
+    pub const fn eye(&self) -> StereoEye {
+        self.frame_eye
+    }
+
+    /// Save-state byte: D0 = shutter level, D1 = latched eye.
+    #[must_use]
+    pub(crate) const fn to_u8(self) -> u8 {
+        (self.shutter as u8) | ((matches!(self.frame_eye, StereoEye::Right) as u8) << 1)
+    }
+
+    /// Inverse of [`Self::to_u8`].
+    #[must_use]
+    pub(crate) const fn from_u8(v: u8) -> Self {
+        Self {
+            shutter: v & 0x01 != 0,
+            frame_eye: if v & 0x02 != 0 {
+                StereoEye::Right
+            } else {
+                StereoEye::Left
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latches_d1_at_frame_end() {
+        let mut g = Famicom3dGlasses::new();
+        g.write(0x02);
+        assert_eq!(g.eye(), StereoEye::Left, "not latched until the frame ends");
+        g.end_frame();
+        assert_eq!(g.eye(), StereoEye::Right);
+        // D0 (the controller strobe) does not touch the shutters.
+        g.write(0x01);
+        g.end_frame();
+        assert_eq!(g.eye(), StereoEye::Left);
+    }
+
+    #[test]
+    fn state_byte_round_trips() {
+        let mut g = Famicom3dGlasses::new();
+        g.write(0x02);
+        g.end_frame();
+        g.write(0x00);
+        assert_eq!(Famicom3dGlasses::from_u8(g.to_u8()), g);
+        assert_eq!(StereoEye::Left.other(), StereoEye::Right);
+    }
+}
@@ -280,8 +280,9 @@ impl ZapperState {
     /// A3 (v2.2.3) factored this out of [`Self::sample_light`] so the
     /// frame-granular and beam-relative models cannot drift apart — the
     /// temporal model differs from the frame model ONLY in *when* it samples,
-    /// never in what counts as light.
-    fn aperture_is_bright(framebuffer: &[u8], x: u16, y: u16) -> bool {
+    /// never in what counts as light. v2.5.0 — R.O.B.'s head photodiode
+    /// ([`crate::rob`]) shares it for the same reason.
+    pub(crate) fn aperture_is_bright(framebuffer: &[u8], x: u16, y: u16) -> bool {
         Self::aperture_is_bright_painted(framebuffer, x, y, None)
     }
 
@@ -1226,6 +1227,1276 @@ impl BandaiHyperShotState {
     }
 }
 
+/// The Bandai **Oeka Kids** drawing tablet overlay state (v2.5.0), used by the
+/// two Oeka Kids titles on the Bandai `96` board.
+///
+/// Per the `NESdev` "Oeka Kids tablet" page the tablet answers on `$4017`
+/// through an 18-bit serial report, clocked by `$4016` writes:
+///
+/// ```text
+/// $4016 write:               $4017 read:
+/// 7  bit  0                  7  bit  0
+/// ---- ----                  ---- ----
+/// xxxx xxCS                  xxxx DRxx
+///        ||                       ||
+///        |+- 1 = shift mode,       |+-- ready (S=1, C=0)
+///        |   0 = latch report      +--- serial data, inverted (S=1, C=1)
+///        +-- rising edge shifts
+/// ```
+///
+/// The report is `XXXXXXXX YYYYYYYY TB` MSB-first: 8-bit tablet X and Y, then
+/// pen-touch and pen-button. The board samples the pen at 8 bits per axis;
+/// host coordinates are kept at full NES-screen precision and scaled to the
+/// tablet's surface (which is offset from the picture) only when the report
+/// latches. The read is a pure function of the latched report and the last
+/// write, so the device is deterministic.
+#[allow(clippy::struct_excessive_bools)] // independent pen/latch lines, not a FSM
+#[derive(Clone, Copy, Debug)]
+pub struct OekaKidsTabletState {
+    /// Pen X in NES screen pixels (`u16::MAX` = off the tablet).
+    pub(crate) x: u16,
+    /// Pen Y in NES screen pixels (`u16::MAX` = off the tablet).
+    pub(crate) y: u16,
+    /// Pen tip on the surface.
+    pub(crate) touch: bool,
+    /// Pen barrel button pressed.
+    pub(crate) button: bool,
+    /// The latched report; the data bit is read from bit 18 after a shift.
+    pub(crate) report: u32,
+    /// `$4016` bit 0 from the last write (1 = shift mode).
+    pub(crate) strobe: bool,
+    /// `$4016` bit 1 from the last write (the shift clock).
+    pub(crate) clock: bool,
+}
+
+impl Default for OekaKidsTabletState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OekaKidsTabletState {
+    /// Data-bit position in [`Self::report`] after the first clock.
+    const DATA_BIT: u32 = 1 << 18;
+
+    /// New tablet with the pen lifted off the surface.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            x: u16::MAX,
+            y: u16::MAX,
+            touch: false,
+            button: false,
+            report: 0,
+            strobe: false,
+            clock: false,
+        }
+    }
+
+    /// Set the live pen position (NES screen pixels, `u16::MAX` = off the
+    /// tablet), tip contact and barrel button. Off the tablet the pen can
+    /// neither touch nor click.
+    pub const fn set(&mut self, x: u16, y: u16, touch: bool, button: bool) {
+        let on = x != u16::MAX && y != u16::MAX;
+        self.x = x;
+        self.y = y;
+        self.touch = touch && on;
+        self.button = button && on;
+    }
+
+    /// The 18-bit report for the live pen state.
+    #[must_use]
+    pub const fn report_now(&self) -> u32 {
+        let (tx, ty) = if self.x == u16::MAX || self.y == u16::MAX {
+            (0, 0)
+        } else {
+            // The sensing surface is offset from the picture: NES x 0 maps to
+            // tablet ~7, NES y 14 to tablet 0.
+            let tx = (self.x as u32 + 8) * 240 / 256;
+            let ty = (self.y as u32).saturating_sub(14) * 256 / 240;
+            (
+                if tx > 255 { 255 } else { tx },
+                if ty > 255 { 255 } else { ty },
+            )
+        };
+        (tx << 10) | (ty << 2) | ((self.touch as u32) << 1) | self.button as u32
+    }
+
+    /// Handle a `$4016` write. Shift mode (bit 0 set) shifts the report on a
+    /// rising edge of bit 1; latch mode (bit 0 clear) reloads the report from
+    /// the live pen.
+    pub const fn write_strobe(&mut self, value: u8) {
+        self.strobe = value & 0x01 != 0;
+        let clock = value & 0x02 != 0;
+        if self.strobe {
+            if clock && !self.clock {
+                self.report <<= 1;
+            }
+        } else {
+            self.report = self.report_now();
+        }
+        self.clock = clock;
+    }
+
+    /// Read the device byte for a `$4017` access: bit 2 = ready while shift
+    /// mode waits with the clock low, bit 3 = the inverted data bit while the
+    /// clock is high, `0` in latch mode. The caller ORs in the open-bus bits.
+    #[must_use]
+    pub const fn read(&self) -> u8 {
+        if !self.strobe {
+            0x00
+        } else if !self.clock {
+            0x04
+        } else if self.report & Self::DATA_BIT != 0 {
+            0x00
+        } else {
+            0x08
+        }
+    }
+
+    /// Side-effect-free sample (debugger peek) — identical to [`Self::read`].
+    #[must_use]
+    pub const fn peek(&self) -> u8 {
+        self.read()
+    }
+
+    /// Reconstruct from save-state parts.
+    #[allow(clippy::fn_params_excessive_bools)]
+    #[must_use]
+    pub const fn from_parts(
+        x: u16,
+        y: u16,
+        touch: bool,
+        button: bool,
+        report: u32,
+        strobe: bool,
+        clock: bool,
+    ) -> Self {
+        Self {
+            x,
+            y,
+            touch,
+            button,
+            report,
+            strobe,
+            clock,
+        }
+    }
+
+    /// Raw pen X (save-state).
+    #[must_use]
+    pub const fn x_raw(&self) -> u16 {
+        self.x
+    }
+    /// Raw pen Y (save-state).
+    #[must_use]
+    pub const fn y_raw(&self) -> u16 {
+        self.y
+    }
+    /// Raw pen-touch (save-state).
+    #[must_use]
+    pub const fn touch_raw(&self) -> bool {
+        self.touch
+    }
+    /// Raw pen-button (save-state).
+    #[must_use]
+    pub const fn button_raw(&self) -> bool {
+        self.button
+    }
+    /// Raw latched report (save-state).
+    #[must_use]
+    pub const fn report_raw(&self) -> u32 {
+        self.report
+    }
+    /// Raw shift-mode bit (save-state).
+    #[must_use]
+    pub const fn strobe_raw(&self) -> bool {
+        self.strobe
+    }
+    /// Raw shift-clock bit (save-state).
+    #[must_use]
+    pub const fn clock_raw(&self) -> bool {
+        self.clock
+    }
+}
+
+/// Bit count of the [`HoriTrackState`] serial report.
+const HORI_TRACK_REPORT_BITS: u8 = 24;
+
+/// The Hori **Track** trackball controller state (v2.5.0), a Famicom
+/// expansion-port controller with the standard pad buttons beside the ball.
+///
+/// A strobe latches a 24-bit report which the port's reads shift out
+/// LSb-first on **D1** (the Famicom expansion data line); after the report
+/// the line idles high:
+///
+/// ```text
+/// bits  0..=7   A B Select Start Up Down Left Right   (1 = pressed)
+/// bits  8..=11  ball Y motion  \ 4-bit two's complement clamped to -8..=7,
+/// bits 12..=15  ball X motion  / bit-reversed and inverted on the wire
+/// bits 16..=23  $09 (fixed identification byte)
+/// ```
+///
+/// Motion is per latch (the frame's accumulated ball travel), positive X to
+/// the right and positive Y down, exactly like [`SnesMouseState`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HoriTrackState {
+    /// Ball delta-X this frame (clamped into `-8..=7` on latch).
+    pub(crate) dx: i16,
+    /// Ball delta-Y this frame (clamped into `-8..=7` on latch).
+    pub(crate) dy: i16,
+    /// Pad buttons in [`crate::Buttons`] bit order.
+    pub(crate) buttons: u8,
+    /// 24-bit shift register, LSb-first readout.
+    pub(crate) shift: u32,
+    /// Report bits shifted out so far (0..=24).
+    pub(crate) read_count: u8,
+    /// Last strobe level written (bit 0 of `$4016`).
+    pub(crate) strobe: bool,
+}
+
+impl HoriTrackState {
+    /// New trackball at rest with no button held.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            dx: 0,
+            dy: 0,
+            buttons: 0,
+            shift: 0,
+            read_count: 0,
+            strobe: false,
+        }
+    }
+
+    /// One axis as it travels on the wire: clamped, bit-reversed, inverted.
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)] // 4-bit field
+    const fn enc_axis(v: i16) -> u32 {
+        let v = if v < -8 {
+            -8
+        } else if v > 7 {
+            7
+        } else {
+            v
+        };
+        let n = (v as u8 & 0x0F) as u32;
+        let rev = ((n & 1) << 3) | ((n & 2) << 1) | ((n & 4) >> 1) | ((n & 8) >> 3);
+        !rev & 0x0F
+    }
+
+    /// The 24-bit report for the live state.
+    const fn encode(&self) -> u32 {
+        (self.buttons as u32)
+            | (Self::enc_axis(self.dy) << 8)
+            | (Self::enc_axis(self.dx) << 12)
+            | (0x09 << 16)
+    }
+
+    /// Update the ball motion and pad buttons ([`crate::Buttons`] bits). Takes
+    /// effect on the next latch.
+    pub const fn set(&mut self, dx: i16, dy: i16, buttons: u8) {
+        self.dx = dx;
+        self.dy = dy;
+        self.buttons = buttons;
+        if self.strobe {
+            self.shift = self.encode();
+            self.read_count = 0;
+        }
+    }
+
+    /// Handle a `$4016` strobe write: a high level (re)latches the report.
+    pub const fn write_strobe(&mut self, value: u8) {
+        self.strobe = value & 1 != 0;
+        if self.strobe {
+            self.shift = self.encode();
+            self.read_count = 0;
+        }
+    }
+
+    /// Read the device byte, shifting out the next report bit on D1. The
+    /// caller ORs in the open-bus upper bits.
+    pub const fn read(&mut self) -> u8 {
+        if self.strobe {
+            self.shift = self.encode();
+            self.read_count = 0;
+        }
+        if self.read_count >= HORI_TRACK_REPORT_BITS {
+            return 0x02;
+        }
+        let bit = (self.shift & 1) as u8;
+        self.shift >>= 1;
+        self.read_count += 1;
+        bit << 1
+    }
+
+    /// Side-effect-free sample of the next D1 bit (debugger peek).
+    #[must_use]
+    pub const fn peek(&self) -> u8 {
+        if self.read_count >= HORI_TRACK_REPORT_BITS {
+            0x02
+        } else {
+            ((self.shift & 1) as u8) << 1
+        }
+    }
+
+    /// Reconstruct from save-state parts.
+    #[must_use]
+    pub const fn from_parts(
+        dx: i16,
+        dy: i16,
+        buttons: u8,
+        shift: u32,
+        read_count: u8,
+        strobe: bool,
+    ) -> Self {
+        Self {
+            dx,
+            dy,
+            buttons,
+            shift,
+            read_count,
+            strobe,
+        }
+    }
+
+    /// Raw delta-X (save-state).
+    #[must_use]
+    pub const fn dx_raw(&self) -> i16 {
+        self.dx
+    }
+    /// Raw delta-Y (save-state).
+    #[must_use]
+    pub const fn dy_raw(&self) -> i16 {
+        self.dy
+    }
+    /// Raw button byte (save-state).
+    #[must_use]
+    pub const fn buttons_raw(&self) -> u8 {
+        self.buttons
+    }
+    /// Raw shift register (save-state).
+    #[must_use]
+    pub const fn shift_raw(&self) -> u32 {
+        self.shift
+    }
+    /// Raw read counter (save-state).
+    #[must_use]
+    pub const fn read_count_raw(&self) -> u8 {
+        self.read_count
+    }
+    /// Raw strobe level (save-state).
+    #[must_use]
+    pub const fn strobe_raw(&self) -> bool {
+        self.strobe
+    }
+}
+
+/// Bit count of the [`PachinkoState`] serial report.
+const PACHINKO_REPORT_BITS: u8 = 16;
+
+/// The Coconuts Japan **Pachinko** controller state (v2.5.0): a pad with an
+/// analog launch plunger, for the Famicom pachinko titles.
+///
+/// Same framing as the [`HoriTrackState`]: a strobe latches the report, reads
+/// shift it out LSb-first on **D1**, and the line idles high afterwards.
+///
+/// ```text
+/// bits 0..=7   A B Select Start Up Down Left Right   (1 = pressed)
+/// bits 8..=15  plunger position, inverted (0 on the wire = fully pulled)
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PachinkoState {
+    /// Pad buttons in [`crate::Buttons`] bit order.
+    pub(crate) buttons: u8,
+    /// Plunger travel, `0` = at rest .. `255` = fully pulled.
+    pub(crate) plunger: u8,
+    /// 16-bit shift register, LSb-first readout.
+    pub(crate) shift: u16,
+    /// Report bits shifted out so far (0..=16).
+    pub(crate) read_count: u8,
+    /// Last strobe level written (bit 0 of `$4016`).
+    pub(crate) strobe: bool,
+}
+
+impl PachinkoState {
+    /// New controller with the plunger at rest.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buttons: 0,
+            plunger: 0,
+            shift: 0,
+            read_count: 0,
+            strobe: false,
+        }
+    }
+
+    /// The 16-bit report for the live state.
+    const fn encode(self) -> u16 {
+        (self.buttons as u16) | (((self.plunger ^ 0xFF) as u16) << 8)
+    }
+
+    /// Update the pad buttons ([`crate::Buttons`] bits) and plunger travel.
+    /// Takes effect on the next latch.
+    pub const fn set(&mut self, buttons: u8, plunger: u8) {
+        self.buttons = buttons;
+        self.plunger = plunger;
+        if self.strobe {
+            self.shift = self.encode();
+            self.read_count = 0;
+        }
+    }
+
+    /// Handle a `$4016` strobe write: a high level (re)latches the report.
+    pub const fn write_strobe(&mut self, value: u8) {
+        self.strobe = value & 1 != 0;
+        if self.strobe {
+            self.shift = self.encode();
+            self.read_count = 0;
+        }
+    }
+
+    /// Read the device byte, shifting out the next report bit on D1.
+    pub const fn read(&mut self) -> u8 {
+        if self.strobe {
+            self.shift = self.encode();
+            self.read_count = 0;
+        }
+        if self.read_count >= PACHINKO_REPORT_BITS {
+            return 0x02;
+        }
+        let bit = (self.shift & 1) as u8;
+        self.shift >>= 1;
+        self.read_count += 1;
+        bit << 1
+    }
+
+    /// Side-effect-free sample of the next D1 bit (debugger peek).
+    #[must_use]
+    pub const fn peek(&self) -> u8 {
+        if self.read_count >= PACHINKO_REPORT_BITS {
+            0x02
+        } else {
+            ((self.shift & 1) as u8) << 1
+        }
+    }
+
+    /// Reconstruct from save-state parts.
+    #[must_use]
+    pub const fn from_parts(
+        buttons: u8,
+        plunger: u8,
+        shift: u16,
+        read_count: u8,
+        strobe: bool,
+    ) -> Self {
+        Self {
+            buttons,
+            plunger,
+            shift,
+            read_count,
+            strobe,
+        }
+    }
+
+    /// Raw button byte (save-state).
+    #[must_use]
+    pub const fn buttons_raw(&self) -> u8 {
+        self.buttons
+    }
+    /// Raw plunger travel (save-state).
+    #[must_use]
+    pub const fn plunger_raw(&self) -> u8 {
+        self.plunger
+    }
+    /// Raw shift register (save-state).
+    #[must_use]
+    pub const fn shift_raw(&self) -> u16 {
+        self.shift
+    }
+    /// Raw read counter (save-state).
+    #[must_use]
+    pub const fn read_count_raw(&self) -> u8 {
+        self.read_count
+    }
+    /// Raw strobe level (save-state).
+    #[must_use]
+    pub const fn strobe_raw(&self) -> bool {
+        self.strobe
+    }
+}
+
+/// [`TopRiderState`] key bit: front/rear brake lever.
+pub const TOP_RIDER_BRAKE: u8 = 0x01;
+/// [`TopRiderState`] key bit: gear shift in high.
+pub const TOP_RIDER_SHIFT: u8 = 0x02;
+/// [`TopRiderState`] key bit: handlebars pulled back (wheelie).
+pub const TOP_RIDER_WHEELIE: u8 = 0x04;
+/// [`TopRiderState`] key bit: Start.
+pub const TOP_RIDER_START: u8 = 0x08;
+/// [`TopRiderState`] key bit: Select.
+pub const TOP_RIDER_SELECT: u8 = 0x10;
+
+/// Bit count of the [`TopRiderState`] serial report.
+const TOP_RIDER_REPORT_BITS: u8 = 16;
+
+/// The Bandai **Top Rider** ride-on motorbike controller state (v2.5.0), for
+/// *Top Rider*. The handlebars and throttle grip are quantised to three
+/// steps each way; the rest are switches.
+///
+/// Same framing as the [`HoriTrackState`] (strobe latch, LSb-first on **D1**,
+/// idle high):
+///
+/// ```text
+/// bits 0..=1  steer left  (0..=3)     bit 6   brake
+/// bits 2..=3  steer right (0..=3)     bit 7   shift (high gear)
+/// bits 4..=5  throttle    (0..=3)     bit 8   wheelie
+///                                     bit 9   Start
+///                                     bit 10  Select
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TopRiderState {
+    /// Handlebar position, `-3` (full left) ..= `3` (full right).
+    pub(crate) steer: i8,
+    /// Throttle, `0..=3`.
+    pub(crate) throttle: u8,
+    /// `TOP_RIDER_*` switch bits.
+    pub(crate) keys: u8,
+    /// 16-bit shift register, LSb-first readout.
+    pub(crate) shift: u16,
+    /// Report bits shifted out so far (0..=16).
+    pub(crate) read_count: u8,
+    /// Last strobe level written (bit 0 of `$4016`).
+    pub(crate) strobe: bool,
+}
+
+impl TopRiderState {
+    /// New bike, bars centred, throttle closed.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            steer: 0,
+            throttle: 0,
+            keys: 0,
+            shift: 0,
+            read_count: 0,
+            strobe: false,
+        }
+    }
+
+    /// The 16-bit report for the live state.
+    #[allow(clippy::cast_sign_loss)] // magnitudes only
+    const fn encode(self) -> u16 {
+        let (left, right) = if self.steer < 0 {
+            (self.steer.unsigned_abs(), 0)
+        } else {
+            (0, self.steer as u8)
+        };
+        // The five `TOP_RIDER_*` switches sit in report order from bit 6.
+        (left as u16)
+            | ((right as u16) << 2)
+            | ((self.throttle as u16) << 4)
+            | ((self.keys as u16) << 6)
+    }
+
+    /// Update the handlebars (`-3..=3`, clamped), throttle (`0..=3`, clamped)
+    /// and `TOP_RIDER_*` switches. Takes effect on the next latch.
+    pub const fn set(&mut self, steer: i8, throttle: u8, keys: u8) {
+        self.steer = if steer < -3 {
+            -3
+        } else if steer > 3 {
+            3
+        } else {
+            steer
+        };
+        self.throttle = if throttle > 3 { 3 } else { throttle };
+        self.keys = keys & 0x1F;
+        if self.strobe {
+            self.shift = self.encode();
+            self.read_count = 0;
+        }
+    }
+
+    /// Handle a `$4016` strobe write: a high level (re)latches the report.
+    pub const fn write_strobe(&mut self, value: u8) {
+        self.strobe = value & 1 != 0;
+        if self.strobe {
+            self.shift = self.encode();
+            self.read_count = 0;
+        }
+    }
+
+    /// Read the device byte, shifting out the next report bit on D1.
+    pub const fn read(&mut self) -> u8 {
+        if self.strobe {
+            self.shift = self.encode();
+            self.read_count = 0;
+        }
+        if self.read_count >= TOP_RIDER_REPORT_BITS {
+            return 0x02;
+        }
+        let bit = (self.shift & 1) as u8;
+        self.shift >>= 1;
+        self.read_count += 1;
+        bit << 1
+    }
+
+    /// Side-effect-free sample of the next D1 bit (debugger peek).
+    #[must_use]
+    pub const fn peek(&self) -> u8 {
+        if self.read_count >= TOP_RIDER_REPORT_BITS {
+            0x02
+        } else {
+            ((self.shift & 1) as u8) << 1
+        }
+    }
+
+    /// Reconstruct from save-state parts.
+    #[must_use]
+    pub const fn from_parts(
+        steer: i8,
+        throttle: u8,
+        keys: u8,
+        shift: u16,
+        read_count: u8,
+        strobe: bool,
+    ) -> Self {
+        Self {
+            steer,
+            throttle,
+            keys,
+            shift,
+            read_count,
+            strobe,
+        }
+    }
+
+    /// Raw handlebar position (save-state).
+    #[must_use]
+    pub const fn steer_raw(&self) -> i8 {
+        self.steer
+    }
+    /// Raw throttle (save-state).
+    #[must_use]
+    pub const fn throttle_raw(&self) -> u8 {
+        self.throttle
+    }
+    /// Raw switch bits (save-state).
+    #[must_use]
+    pub const fn keys_raw(&self) -> u8 {
+        self.keys
+    }
+    /// Raw shift register (save-state).
+    #[must_use]
+    pub const fn shift_raw(&self) -> u16 {
+        self.shift
+    }
+    /// Raw read counter (save-state).
+    #[must_use]
+    pub const fn read_count_raw(&self) -> u8 {
+        self.read_count
+    }
+    /// Raw strobe level (save-state).
+    #[must_use]
+    pub const fn strobe_raw(&self) -> bool {
+        self.strobe
+    }
+}
+
+/// The command the host bit-bangs into a [`PowerGloveState`] to switch it to
+/// 12-byte packet ("hi-res") mode, as sent by *Super Glove Ball*.
+pub const POWER_GLOVE_INIT: [u8; 7] = [0x06, 0xC1, 0x08, 0x00, 0x02, 0xFF, 0x01];
+
+/// Length of a [`PowerGloveState`] hi-res packet.
+pub const POWER_GLOVE_PACKET_LEN: usize = 12;
+
+/// Bytes in a [`PowerGloveState`] save-state record.
+pub(crate) const POWER_GLOVE_STATE_LEN: usize = 32;
+
+/// A Power Glove gesture program: how the glove turns the hand into pad
+/// buttons in its default (joypad) mode.
+///
+/// The real glove stores these behind its `PROG` key; these are the generic
+/// layouts most games were played with.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PowerGloveProgram {
+    /// Hand offset from centre steers the D-pad; index finger = A, thumb = B.
+    #[default]
+    Joystick,
+    /// Hand offset steers the D-pad; a clenched fist = B, and a fist thrust
+    /// toward the screen = A (the punch games).
+    Punch,
+    /// Glove tracking off: only the forearm keypad reaches the game.
+    Keypad,
+}
+
+impl PowerGloveProgram {
+    /// Every program, in menu order.
+    pub const ALL: [Self; 3] = [Self::Joystick, Self::Punch, Self::Keypad];
+
+    /// Human label.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Joystick => "Joystick",
+            Self::Punch => "Punch",
+            Self::Keypad => "Keypad only",
+        }
+    }
+
+    const fn to_u8(self) -> u8 {
+        match self {
+            Self::Joystick => 0,
+            Self::Punch => 1,
+            Self::Keypad => 2,
+        }
+    }
+
+    const fn from_u8(v: u8) -> Self {
+        match v {
+            1 => Self::Punch,
+            2 => Self::Keypad,
+            _ => Self::Joystick,
+        }
+    }
+}
+
+/// The tracked hand a [`PowerGloveState`] reports.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PowerGloveHand {
+    /// Horizontal offset from the calibrated centre (positive = right).
+    pub x: i8,
+    /// Vertical offset from the calibrated centre (positive = up).
+    pub y: i8,
+    /// Distance offset (positive = toward the screen).
+    pub z: i8,
+    /// Wrist roll in 30-degree steps, `0..=11`.
+    pub roll: u8,
+    /// Finger flex, two bits each (0 = straight .. 3 = bent): thumb in bits
+    /// 7-6, index 5-4, middle 3-2, ring 1-0.
+    pub fingers: u8,
+    /// The forearm keypad's pad buttons ([`crate::Buttons`] bits).
+    pub keypad: u8,
+}
+
+impl PowerGloveHand {
+    /// Flex of finger `i` (0 = thumb .. 3 = ring), `0..=3`.
+    #[must_use]
+    pub const fn finger(&self, i: u8) -> u8 {
+        (self.fingers >> (6 - 2 * (i & 3))) & 0b11
+    }
+
+    /// All four fingers at least half bent.
+    #[must_use]
+    pub const fn fist(&self) -> bool {
+        self.finger(0) >= 2 && self.finger(1) >= 2 && self.finger(2) >= 2 && self.finger(3) >= 2
+    }
+}
+
+/// The Mattel **Power Glove** state (v2.5.0) on an NES controller port.
+///
+/// In its default **joypad mode** the glove is a standard controller: a strobe
+/// latches the buttons its [`PowerGloveProgram`] derives from the hand (plus
+/// the keypad) and reads shift them out LSb-first on D0, then 1s.
+///
+/// While in joypad mode every read also samples the strobe line as a command
+/// bit (the host bit-bangs data on the latch, clocked by the reads). Once the
+/// last 56 bits equal [`POWER_GLOVE_INIT`] the glove switches to **hi-res
+/// mode**, where each strobe falling edge loads the next byte of a 12-byte
+/// packet, shifted out MSb-first on D0:
+///
+/// ```text
+/// A0  X  Y  Z  roll  fingers  keypad  00  00  3F  FF  FF
+/// ```
+///
+/// The packet is sampled from the live hand whenever byte 0 loads, so one
+/// packet never mixes two frames. A console reset does not leave hi-res mode;
+/// re-attaching the glove does.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PowerGloveState {
+    hand: PowerGloveHand,
+    program: PowerGloveProgram,
+    /// Hi-res packet mode active.
+    hires: bool,
+    /// Command bits received so far (newest in bit 0).
+    command: u64,
+    /// The packet being sent.
+    packet: [u8; POWER_GLOVE_PACKET_LEN],
+    /// Index of the next packet byte to load.
+    index: u8,
+    /// Shift register (joypad buttons or the current packet byte).
+    shift: u8,
+    /// Bits shifted out of `shift` so far (0..=8).
+    read_count: u8,
+    /// Last strobe level written (bit 0 of `$4016`).
+    strobe: bool,
+}
+
+impl PowerGloveState {
+    /// Distance from centre past which the hand presses a D-pad direction.
+    const DEADZONE: i8 = 16;
+    /// Thrust past which a fist counts as a punch.
+    const PUNCH_Z: i8 = 32;
+
+    /// New glove in joypad mode with the hand at rest.
+    #[must_use]
+    pub const fn new(program: PowerGloveProgram) -> Self {
+        Self {
+            hand: PowerGloveHand {
+                x: 0,
+                y: 0,
+                z: 0,
+                roll: 0,
+                fingers: 0,
+                keypad: 0,
+            },
+            program,
+            hires: false,
+            command: 0,
+            packet: [0; POWER_GLOVE_PACKET_LEN],
+            index: 0,
+            shift: 0,
+            read_count: 0,
+            strobe: false,
+        }
+    }
+
+    /// Update the tracked hand and the active gesture program.
+    pub const fn set(&mut self, hand: PowerGloveHand, program: PowerGloveProgram) {
+        self.hand = hand;
+        self.program = program;
+        if self.strobe && !self.hires {
+            self.shift = self.joypad();
+            self.read_count = 0;
+        }
+    }
+
+    /// The tracked hand.
+    #[must_use]
+    pub const fn hand(&self) -> PowerGloveHand {
+        self.hand
+    }
+
+    /// The active gesture program.
+    #[must_use]
+    pub const fn program(&self) -> PowerGloveProgram {
+        self.program
+    }
+
+    /// `true` once the host has switched the glove to hi-res packet mode.
+    #[must_use]
+    pub const fn hires(&self) -> bool {
+        self.hires
+    }
+
+    /// The joypad-mode button byte the program derives from the hand.
+    #[must_use]
+    pub const fn joypad(&self) -> u8 {
+        use crate::Buttons;
+        let h = &self.hand;
+        let mut b = h.keypad;
+        let dpad = match self.program {
+            PowerGloveProgram::Keypad => return b,
+            PowerGloveProgram::Joystick => {
+                if h.finger(1) >= 2 {
+                    b |= Buttons::A.bits();
+                }
+                if h.finger(0) >= 2 {
+                    b |= Buttons::B.bits();
+                }
+                true
+            }
+            PowerGloveProgram::Punch => {
+                if h.fist() {
+                    b |= if h.z >= Self::PUNCH_Z {
+                        Buttons::A.bits()
+                    } else {
+                        Buttons::B.bits()
+                    };
+                }
+                true
+            }
+        };
+        if dpad {
+            if h.x >= Self::DEADZONE {
+                b |= Buttons::RIGHT.bits();
+            } else if h.x <= -Self::DEADZONE {
+                b |= Buttons::LEFT.bits();
+            }
+            if h.y >= Self::DEADZONE {
+                b |= Buttons::UP.bits();
+            } else if h.y <= -Self::DEADZONE {
+                b |= Buttons::DOWN.bits();
+            }
+        }
+        b
+    }
+
+    /// The hi-res packet for the live hand.
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)] // signed axes travel as raw bytes
+    pub const fn packet_now(&self) -> [u8; POWER_GLOVE_PACKET_LEN] {
+        let h = &self.hand;
+        [
+            0xA0,
+            h.x as u8,
+            h.y as u8,
+            h.z as u8,
+            if h.roll > 11 { 11 } else { h.roll },
+            h.fingers,
+            h.keypad,
+            0x00,
+            0x00,
+            0x3F,
+            0xFF,
+            0xFF,
+        ]
+    }
+
+    /// Handle a `$4016` strobe write. Joypad mode latches the buttons while
+    /// high; hi-res mode loads the next packet byte on the falling edge.
+    pub const fn write_strobe(&mut self, value: u8) {
+        let strobe = value & 1 != 0;
+        if self.hires {
+            if self.strobe && !strobe {
+                if self.index == 0 {
+                    self.packet = self.packet_now();
+                }
+                self.shift = self.packet[self.index as usize];
+                self.index = if self.index as usize + 1 == POWER_GLOVE_PACKET_LEN {
+                    0
+                } else {
+                    self.index + 1
+                };
+                self.read_count = 0;
+            }
+        } else if strobe {
+            self.shift = self.joypad();
+            self.read_count = 0;
+        }
+        self.strobe = strobe;
+    }
+
+    /// Read the device byte (D0). In joypad mode the read also clocks the
+    /// strobe level in as a command bit.
+    pub const fn read(&mut self) -> u8 {
+        if self.hires {
+            if self.read_count >= 8 {
+                return 1;
+            }
+            let bit = self.shift >> 7;
+            self.shift <<= 1;
+            self.read_count += 1;
+            return bit;
+        }
+        self.command = (self.command << 1) | self.strobe as u64;
+        if self.command & 0x00FF_FFFF_FFFF_FFFF == Self::init_word() {
+            self.hires = true;
+            self.index = 0;
+            self.read_count = 8;
+            return 1;
+        }
+        if self.strobe {
+            self.shift = self.joypad();
+            self.read_count = 0;
+        }
+        if self.read_count >= 8 {
+            return 1;
+        }
+        let bit = self.shift & 1;
+        self.shift >>= 1;
+        self.read_count += 1;
+        bit
+    }
+
+    /// Side-effect-free sample of the next D0 bit (debugger peek).
+    #[must_use]
+    pub const fn peek(&self) -> u8 {
+        if self.read_count >= 8 {
+            1
+        } else if self.hires {
+            self.shift >> 7
+        } else {
+            self.shift & 1
+        }
+    }
+
+    /// [`POWER_GLOVE_INIT`] as the 56-bit word the command register holds.
+    const fn init_word() -> u64 {
+        let mut w = 0u64;
+        let mut i = 0;
+        while i < POWER_GLOVE_INIT.len() {
+            w = (w << 8) | POWER_GLOVE_INIT[i] as u64;
+            i += 1;
+        }
+        w
+    }
+
+    /// Save-state record.
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)] // signed axes travel as raw bytes
+    pub(crate) fn to_bytes(self) -> [u8; POWER_GLOVE_STATE_LEN] {
+        let mut b = [0u8; POWER_GLOVE_STATE_LEN];
+        let h = &self.hand;
+        b[..6].copy_from_slice(&[h.x as u8, h.y as u8, h.z as u8, h.roll, h.fingers, h.keypad]);
+        b[6] = self.program.to_u8();
+        b[7] = u8::from(self.hires) | (u8::from(self.strobe) << 1);
+        b[8..16].copy_from_slice(&self.command.to_le_bytes());
+        b[16..28].copy_from_slice(&self.packet);
+        b[28] = self.index;
+        b[29] = self.shift;
+        b[30] = self.read_count;
+        b
+    }
+
+    /// Inverse of [`Self::to_bytes`].
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)] // raw bytes back to signed axes
+    pub(crate) fn from_bytes(b: &[u8; POWER_GLOVE_STATE_LEN]) -> Self {
+        let mut command = [0u8; 8];
+        command.copy_from_slice(&b[8..16]);
+        let mut packet = [0u8; POWER_GLOVE_PACKET_LEN];
+        packet.copy_from_slice(&b[16..28]);
+        Self {
+            hand: PowerGloveHand {
+                x: b[0] as i8,
+                y: b[1] as i8,
+                z: b[2] as i8,
+                roll: b[3],
+                fingers: b[4],
+                keypad: b[5],
+            },
+            program: PowerGloveProgram::from_u8(b[6]),
+            hires: b[7] & 1 != 0,
+            strobe: b[7] & 2 != 0,
+            command: u64::from_le_bytes(command),
+            packet,
+            index: if (b[28] as usize) < POWER_GLOVE_PACKET_LEN {
+                b[28]
+            } else {
+                0
+            },
+            shift: b[29],
+            read_count: b[30],
+        }
+    }
+}
+
+/// Size of one ASCII Turbo File storage bank (8 KiB).
+pub const TURBO_FILE_BANK_SIZE: usize = 0x2000;
+
+/// Banks in a Turbo File Twin (a 4-position switch picks the active one).
+pub const TURBO_FILE_TWIN_BANKS: usize = 4;
+
+/// ASCII **Turbo File** / **Turbo File Twin** external storage (v2.5.0).
+///
+/// A battery-backed serial SRAM on the expansion port, used by *Derby
+/// Stallion*, *Best Play Pro Yakyuu* and a few dozen other Famicom titles.
+/// `$4016` writes drive it (D1 = 0 rewinds the bit address to 0; a falling
+/// edge on D2 writes D0 to the current bit and advances it) and `$4017` D2
+/// reads back the bit at the current address. Bits are addressed
+/// LSb-first within each byte. Games read by writing each bit back as they
+/// go, so the data survives.
+///
+/// This is the port side only — the bit address, the Twin's bank switch and
+/// the last strobe — so it stays small and `Copy` like every other device.
+/// The stored data is a [`TurboFileImage`] the bus holds beside it, the way a
+/// Data Recorder holds its tape.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TurboFileState {
+    bank: u8,
+    position: u16,
+    last_write: u8,
+}
+
+impl TurboFileState {
+    /// A freshly-plugged Turbo File: address 0, switch on bank 0.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            bank: 0,
+            position: 0,
+            last_write: 0,
+        }
+    }
+
+    /// Restore from save-state parts (the bank is folded into range).
+    #[must_use]
+    pub const fn from_parts(bank: u8, position: u16, last_write: u8) -> Self {
+        Self {
+            bank: bank & 0x03,
+            position,
+            last_write,
+        }
+    }
+
+    /// The Twin's bank switch. A plain Turbo File has no switch and ignores
+    /// it.
+    #[must_use]
+    pub const fn bank(&self) -> u8 {
+        self.bank
+    }
+
+    /// Flip the Twin's bank switch (`0..=3`, folded into range).
+    pub const fn set_bank(&mut self, bank: u8) {
+        self.bank = bank & 0x03;
+    }
+
+    /// A `$4016` write: D1 low rewinds; a D2 falling edge stores D0 into
+    /// `image` and advances the address. With no image the address still
+    /// moves but nothing is stored.
+    pub const fn write_strobe(&mut self, value: u8, image: Option<&mut TurboFileImage>) {
+        if value & 0x02 == 0 {
+            self.position = 0;
+        }
+        if value & 0x04 == 0 && self.last_write & 0x04 != 0 {
+            if let Some(image) = image {
+                image.write_bit(self.bank, self.position, value & 0x01);
+            }
+            self.position = self.position.wrapping_add(1);
+        }
+        self.last_write = value;
+    }
+
+    /// The current bit of `image` on `$4017` D2 (reads have no side
+    /// effects). With no image the line reads 0.
+    #[must_use]
+    pub const fn read(&self, image: Option<&TurboFileImage>) -> u8 {
+        match image {
+            Some(image) => image.bit(self.bank, self.position) << 2,
+            None => 0,
+        }
+    }
+
+    /// The bit address within the active bank (save-state support). An
+    /// 8 KiB bank is exactly 65 536 bits, so it wraps with the `u16`.
+    #[must_use]
+    pub const fn position_raw(&self) -> u16 {
+        self.position
+    }
+
+    /// The last `$4016` value seen (save-state support).
+    #[must_use]
+    pub const fn last_write_raw(&self) -> u8 {
+        self.last_write
+    }
+}
+
+/// The data in a Turbo File (8 KiB) or Turbo File Twin (four 8 KiB banks).
+///
+/// Host-persistent, like a battery save: [`Self::storage`] plus
+/// [`Self::is_dirty`] let the frontend write it back. Every snapshot carries
+/// it, like cartridge PRG-RAM, so a rewind or a netplay rollback undoes the
+/// writes it rolls past.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TurboFileImage {
+    data: alloc::vec::Vec<u8>,
+    dirty: bool,
+}
+
+impl TurboFileImage {
+    /// A blank (zero-filled) single-bank Turbo File.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_banks(1)
+    }
+
+    /// A blank (zero-filled) four-bank Turbo File Twin.
+    #[must_use]
+    pub fn new_twin() -> Self {
+        Self::with_banks(TURBO_FILE_TWIN_BANKS)
+    }
+
+    fn with_banks(banks: usize) -> Self {
+        Self {
+            data: alloc::vec![0; banks * TURBO_FILE_BANK_SIZE],
+            dirty: false,
+        }
+    }
+
+    /// Wrap a persisted image: 8 KiB is a Turbo File, 32 KiB a Twin. Any
+    /// other length is rejected (`None`).
+    #[must_use]
+    pub fn from_storage(data: alloc::vec::Vec<u8>) -> Option<Self> {
+        let banks = data.len() / TURBO_FILE_BANK_SIZE;
+        if !data.len().is_multiple_of(TURBO_FILE_BANK_SIZE)
+            || !matches!(banks, 1 | TURBO_FILE_TWIN_BANKS)
+        {
+            return None;
+        }
+        Some(Self { data, dirty: false })
+    }
+
+    /// `true` for the four-bank Turbo File Twin.
+    #[must_use]
+    pub const fn is_twin(&self) -> bool {
+        self.data.len() > TURBO_FILE_BANK_SIZE
+    }
+
+    /// The whole image (every bank), for persisting.
+    #[must_use]
+    pub const fn storage(&self) -> &[u8] {
+        self.data.as_slice()
+    }
+
+    /// `true` once a game has written to the image since the last
+    /// [`Self::clear_dirty`].
+    #[must_use]
+    pub const fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Mark the image as persisted.
+    pub const fn clear_dirty(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Mark the image as changed since it was persisted.
+    pub(crate) const fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Overwrite the image with a snapshot's same-sized copy, marking it
+    /// dirty only if that changed anything.
+    pub(crate) fn restore_storage(&mut self, data: &[u8]) {
+        if self.data.as_slice() != data {
+            self.data.copy_from_slice(data);
+            self.dirty = true;
+        }
+    }
+
+    /// Byte index and bit of `position` in `bank` (bank 0 unless a Twin).
+    const fn cursor(&self, bank: u8, position: u16) -> (usize, u8) {
+        let bank = if self.is_twin() { bank as usize } else { 0 };
+        (
+            bank * TURBO_FILE_BANK_SIZE + (position / 8) as usize,
+            (position % 8) as u8,
+        )
+    }
+
+    const fn bit(&self, bank: u8, position: u16) -> u8 {
+        let (byte, bit) = self.cursor(bank, position);
+        (self.data.as_slice()[byte] >> bit) & 0x01
+    }
+
+    const fn write_bit(&mut self, bank: u8, position: u16, value: u8) {
+        let (byte, bit) = self.cursor(bank, position);
+        let data = self.data.as_mut_slice();
+        let old = data[byte];
+        let new = (old & !(1 << bit)) | (value << bit);
+        if new != old {
+            data[byte] = new;
+            self.dirty = true;
+        }
+    }
+}
+
+impl Default for TurboFileImage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// An optional non-standard device overlaid on a controller port. When set,
 /// the bus's `$4016`/`$4017` read path returns this device's byte instead of
 /// the standard controller / Four Score serial byte.
@@ -1257,6 +2528,23 @@ pub enum InputDevice {
     /// **Bandai Hyper Shot** / Exciting Boxing punching bag (v1.3.0 Workstream
     /// F1): an 8-sensor expansion controller multiplexed into two groups.
     BandaiHyperShot(BandaiHyperShotState),
+    /// Bandai **Oeka Kids** drawing tablet (v2.5.0): an 18-bit serial pen
+    /// report clocked by `$4016` writes.
+    OekaKidsTablet(OekaKidsTabletState),
+    /// Hori **Track** trackball (v2.5.0): a 24-bit serial report on D1.
+    HoriTrack(HoriTrackState),
+    /// Coconuts **Pachinko** controller (v2.5.0): pad + analog plunger, a
+    /// 16-bit serial report on D1.
+    Pachinko(PachinkoState),
+    /// Bandai **Top Rider** bike (v2.5.0): a 16-bit serial report on D1.
+    TopRider(TopRiderState),
+    /// Mattel **Power Glove** (v2.5.0): joypad mode or the 12-byte hi-res
+    /// packet protocol on D0.
+    PowerGlove(PowerGloveState),
+    /// ASCII **Turbo File** / Turbo File Twin (v2.5.0): serial SRAM written
+    /// through `$4016` and read back on `$4017` D2. The bus serves it with
+    /// its [`TurboFileImage`]; the methods below see it with none inserted.
+    TurboFile(TurboFileState),
 }
 
 impl InputDevice {
@@ -1270,6 +2558,12 @@ impl InputDevice {
             Self::FamilyKeyboard(k) | Self::SuborKeyboard(k) => k.write_strobe(value),
             Self::KonamiHyperShot(h) => h.write_strobe(value),
             Self::BandaiHyperShot(b) => b.write_strobe(value),
+            Self::OekaKidsTablet(t) => t.write_strobe(value),
+            Self::HoriTrack(h) => h.write_strobe(value),
+            Self::Pachinko(p) => p.write_strobe(value),
+            Self::TopRider(t) => t.write_strobe(value),
+            Self::PowerGlove(g) => g.write_strobe(value),
+            Self::TurboFile(f) => f.write_strobe(value, None),
             Self::Zapper(_) => {}
         }
     }
@@ -1285,6 +2579,12 @@ impl InputDevice {
             Self::FamilyKeyboard(k) | Self::SuborKeyboard(k) => k.read(),
             Self::KonamiHyperShot(h) => h.read(),
             Self::BandaiHyperShot(b) => b.read(),
+            Self::OekaKidsTablet(t) => t.read(),
+            Self::HoriTrack(h) => h.read(),
+            Self::Pachinko(p) => p.read(),
+            Self::TopRider(t) => t.read(),
+            Self::PowerGlove(g) => g.read(),
+            Self::TurboFile(f) => f.read(None),
         }
     }
 
@@ -1299,6 +2599,12 @@ impl InputDevice {
             Self::FamilyKeyboard(k) | Self::SuborKeyboard(k) => k.peek(),
             Self::KonamiHyperShot(h) => h.peek(),
             Self::BandaiHyperShot(b) => b.peek(),
+            Self::OekaKidsTablet(t) => t.peek(),
+            Self::HoriTrack(h) => h.peek(),
+            Self::Pachinko(p) => p.peek(),
+            Self::TopRider(t) => t.peek(),
+            Self::PowerGlove(g) => g.peek(),
+            Self::TurboFile(f) => f.read(None),
         }
     }
 }
@@ -1829,6 +3135,214 @@ mod tests {
         let _ = bd.peek();
     }
 
+    /// Clock the 18-bit report out of the tablet the way the Oeka Kids
+    /// driver does: latch, enter shift mode, then one rising edge per bit.
+    fn tablet_read_report(t: &mut OekaKidsTabletState) -> u32 {
+        t.write_strobe(0);
+        t.write_strobe(1);
+        assert_eq!(t.read(), 0x04, "ready while the clock is low");
+        let mut report = 0;
+        for _ in 0..18 {
+            t.write_strobe(0b11);
+            report = (report << 1) | u32::from(t.read() & 0x08 == 0);
+            t.write_strobe(0b01);
+        }
+        report
+    }
+
+    #[test]
+    fn oeka_kids_tablet_reports_position_touch_and_button() {
+        let mut t = OekaKidsTabletState::new();
+        t.set(120, 134, true, false);
+        let report = tablet_read_report(&mut t);
+        assert_eq!(report >> 10, (120 + 8) * 240 / 256, "x");
+        assert_eq!((report >> 2) & 0xFF, (134 - 14) * 256 / 240, "y");
+        assert_eq!(report & 0b11, 0b10, "touch, no button");
+        t.set(120, 134, true, true);
+        assert_eq!(tablet_read_report(&mut t) & 0b11, 0b11);
+    }
+
+    #[test]
+    fn oeka_kids_tablet_off_surface_reports_nothing() {
+        let mut t = OekaKidsTabletState::new();
+        t.set(u16::MAX, u16::MAX, true, true);
+        assert_eq!(tablet_read_report(&mut t), 0);
+        t.write_strobe(0);
+        assert_eq!(t.read(), 0, "latch mode reads zero");
+    }
+
+    #[test]
+    fn oeka_kids_tablet_save_state_round_trip() {
+        let mut t = OekaKidsTabletState::new();
+        t.set(200, 100, true, true);
+        t.write_strobe(0);
+        t.write_strobe(1);
+        t.write_strobe(0b11);
+        let r = OekaKidsTabletState::from_parts(
+            t.x_raw(),
+            t.y_raw(),
+            t.touch_raw(),
+            t.button_raw(),
+            t.report_raw(),
+            t.strobe_raw(),
+            t.clock_raw(),
+        );
+        assert_eq!(r.peek(), t.peek());
+        assert_eq!(r.report_raw(), t.report_raw());
+        let mut d = InputDevice::OekaKidsTablet(r);
+        d.write_strobe(0b01);
+        assert_eq!(d.read(), 0x04);
+    }
+
+    /// Strobe a D1 serial device and clock `bits` report bits out LSb-first.
+    fn d1_report(d: &mut InputDevice, bits: u32) -> u32 {
+        d.write_strobe(1);
+        d.write_strobe(0);
+        (0..bits).fold(0, |acc, i| acc | (u32::from(d.read() >> 1 & 1) << i))
+    }
+
+    #[test]
+    fn hori_track_reports_buttons_and_reversed_inverted_motion() {
+        let mut h = HoriTrackState::new();
+        h.set(
+            1,
+            -1,
+            crate::Buttons::A.bits() | crate::Buttons::LEFT.bits(),
+        );
+        let mut d = InputDevice::HoriTrack(h);
+        let r = d1_report(&mut d, 24);
+        assert_eq!(r & 0xFF, 0x41, "buttons");
+        // dy = -1 = 0b1111 -> reversed 1111 -> inverted 0000.
+        assert_eq!((r >> 8) & 0xF, 0b0000, "y");
+        // dx = 1 = 0b0001 -> reversed 1000 -> inverted 0111.
+        assert_eq!((r >> 12) & 0xF, 0b0111, "x");
+        assert_eq!(r >> 16, 0x09, "id byte");
+        assert_eq!(d.read(), 0x02, "idles high after the report");
+        // Motion clamps to the 4-bit range: +100 reads as +7.
+        h.set(100, 0, 0);
+        let mut d = InputDevice::HoriTrack(h);
+        assert_eq!((d1_report(&mut d, 24) >> 12) & 0xF, !0b1110 & 0xF);
+    }
+
+    #[test]
+    fn pachinko_reports_inverted_plunger() {
+        let mut p = PachinkoState::new();
+        p.set(crate::Buttons::START.bits(), 0x30);
+        let mut d = InputDevice::Pachinko(p);
+        let r = d1_report(&mut d, 16);
+        assert_eq!(r & 0xFF, crate::Buttons::START.bits().into());
+        assert_eq!(r >> 8, 0xCF);
+        let s = PachinkoState::from_parts(
+            p.buttons_raw(),
+            p.plunger_raw(),
+            p.shift_raw(),
+            p.read_count_raw(),
+            p.strobe_raw(),
+        );
+        assert_eq!((s.plunger_raw(), s.peek()), (0x30, p.peek()));
+    }
+
+    #[test]
+    fn top_rider_reports_steering_throttle_and_switches() {
+        let mut t = TopRiderState::new();
+        t.set(-9, 2, TOP_RIDER_BRAKE | TOP_RIDER_START);
+        let mut d = InputDevice::TopRider(t);
+        let r = d1_report(&mut d, 16);
+        assert_eq!(r & 0b11, 3, "full left lock (clamped)");
+        assert_eq!((r >> 2) & 0b11, 0, "no right");
+        assert_eq!((r >> 4) & 0b11, 2, "throttle");
+        assert_eq!(r >> 6, u32::from(TOP_RIDER_BRAKE | TOP_RIDER_START));
+        t.set(2, 0, 0);
+        let mut d = InputDevice::TopRider(t);
+        assert_eq!((d1_report(&mut d, 16) >> 2) & 0b11, 2, "right");
+    }
+
+    #[test]
+    fn power_glove_joypad_programs_map_gestures() {
+        use crate::Buttons;
+        let mut g = PowerGloveState::new(PowerGloveProgram::Joystick);
+        let hand = PowerGloveHand {
+            x: 40,
+            y: -40,
+            fingers: 0b00_11_00_00, // index bent
+            ..PowerGloveHand::default()
+        };
+        g.set(hand, PowerGloveProgram::Joystick);
+        assert_eq!(
+            g.joypad(),
+            (Buttons::A | Buttons::RIGHT | Buttons::DOWN).bits()
+        );
+        let fist = PowerGloveHand {
+            fingers: 0xFF,
+            z: 50,
+            keypad: Buttons::START.bits(),
+            ..PowerGloveHand::default()
+        };
+        g.set(fist, PowerGloveProgram::Punch);
+        assert_eq!(g.joypad(), (Buttons::A | Buttons::START).bits());
+        g.set(fist, PowerGloveProgram::Keypad);
+        assert_eq!(g.joypad(), Buttons::START.bits());
+        // The joypad byte reads like a standard controller.
+        g.set(hand, PowerGloveProgram::Joystick);
+        let mut d = InputDevice::PowerGlove(g);
+        d.write_strobe(1);
+        d.write_strobe(0);
+        let bits: u8 = (0..8).fold(0, |acc, i| acc | (d.read() << i));
+        assert_eq!(bits, (Buttons::A | Buttons::RIGHT | Buttons::DOWN).bits());
+        assert_eq!(d.read(), 1);
+    }
+
+    /// Bit-bang `bytes` into the glove MSb-first on the latch line (leaving
+    /// the latch at the last bit's level).
+    fn glove_send(g: &mut PowerGloveState, bytes: &[u8]) {
+        for byte in bytes {
+            for i in (0..8).rev() {
+                g.write_strobe((byte >> i) & 1);
+                let _ = g.read();
+            }
+        }
+    }
+
+    #[test]
+    fn power_glove_init_switches_to_hires_packets() {
+        let mut g = PowerGloveState::new(PowerGloveProgram::Joystick);
+        let hand = PowerGloveHand {
+            x: -5,
+            y: 7,
+            z: 3,
+            roll: 4,
+            fingers: 0b01_10_11_00,
+            keypad: 0x08,
+        };
+        g.set(hand, PowerGloveProgram::Joystick);
+        // An ordinary poll never trips the command decoder.
+        for _ in 0..20 {
+            g.write_strobe(1);
+            g.write_strobe(0);
+            for _ in 0..8 {
+                let _ = g.read();
+            }
+        }
+        assert!(!g.hires());
+        glove_send(&mut g, &POWER_GLOVE_INIT);
+        assert!(g.hires());
+        let mut packet = [0u8; POWER_GLOVE_PACKET_LEN];
+        for byte in &mut packet {
+            g.write_strobe(1);
+            g.write_strobe(0);
+            *byte = (0..8).fold(0, |acc, _| (acc << 1) | g.read());
+        }
+        assert_eq!(packet, g.packet_now());
+        assert_eq!(&packet[..7], &[0xA0, 0xFB, 7, 3, 4, 0b01_10_11_00, 0x08]);
+        // The save-state record round-trips mid-packet.
+        g.write_strobe(1);
+        g.write_strobe(0);
+        let _ = g.read();
+        let r = PowerGloveState::from_bytes(&g.to_bytes());
+        assert_eq!(r.to_bytes(), g.to_bytes());
+        assert_eq!(r.peek(), g.peek());
+    }
+
     // ---------------------------------------------------------------
     // A3 (v2.2.3): beam-relative temporal light integration.
     // ---------------------------------------------------------------
@@ -1993,4 +3507,83 @@ mod tests {
         // ...and it still carries the trigger bit like every other read.
         assert_eq!(z.read_before_visible() & 0b0001_0000, 0b0001_0000);
     }
+
+    /// Clock one bit into a Turbo File the way a game does (D1 held high,
+    /// D2 pulsed with the data on D0).
+    fn turbo_file_clock(f: &mut TurboFileState, image: &mut TurboFileImage, bit: u8) {
+        f.write_strobe(0x06 | bit, Some(image));
+        f.write_strobe(0x02 | bit, Some(image));
+    }
+
+    #[test]
+    fn turbo_file_stores_bits_and_rewinds_on_d1_low() {
+        let mut f = TurboFileState::new();
+        let mut image = TurboFileImage::new();
+        f.write_strobe(0x00, Some(&mut image)); // reset the address
+        for i in 0..16 {
+            turbo_file_clock(&mut f, &mut image, u8::from(0xA55A_u16 >> i & 1 != 0));
+        }
+        assert!(image.is_dirty());
+        assert_eq!(
+            &image.storage()[..2],
+            &[0x5A, 0xA5],
+            "LSb-first within each byte"
+        );
+
+        // Read back: rewind, then sample D2 and write each bit straight back.
+        f.write_strobe(0x00, Some(&mut image));
+        let mut word = 0u16;
+        for i in 0..16 {
+            let bit = f.read(Some(&image)) >> 2;
+            assert_eq!(f.read(Some(&image)) & !0x04, 0, "only D2 is driven");
+            word |= u16::from(bit) << i;
+            turbo_file_clock(&mut f, &mut image, bit);
+        }
+        assert_eq!(word, 0xA55A);
+        assert_eq!(
+            &image.storage()[..2],
+            &[0x5A, 0xA5],
+            "a read pass is lossless"
+        );
+
+        // No falling D2 edge, no write.
+        image.clear_dirty();
+        f.write_strobe(0x00, Some(&mut image));
+        f.write_strobe(0x03, Some(&mut image));
+        f.write_strobe(0x02, Some(&mut image));
+        assert!(!image.is_dirty());
+
+        // Unplugged media: the line reads 0.
+        assert_eq!(f.read(None), 0);
+    }
+
+    #[test]
+    fn turbo_file_twin_banks_are_independent() {
+        let mut f = TurboFileState::new();
+        let mut image = TurboFileImage::new_twin();
+        assert!(image.is_twin());
+        assert_eq!(
+            image.storage().len(),
+            TURBO_FILE_TWIN_BANKS * TURBO_FILE_BANK_SIZE
+        );
+        f.set_bank(2);
+        f.write_strobe(0x00, Some(&mut image));
+        turbo_file_clock(&mut f, &mut image, 1);
+        assert_eq!(image.storage()[2 * TURBO_FILE_BANK_SIZE], 0x01);
+        f.set_bank(0);
+        f.write_strobe(0x00, Some(&mut image));
+        assert_eq!(f.read(Some(&image)), 0, "bank 0 is untouched");
+        f.set_bank(6);
+        assert_eq!(f.bank(), 2, "the switch folds into 0..=3");
+
+        // A plain Turbo File has no switch: every bank setting hits its one
+        // bank.
+        let mut single = TurboFileImage::new();
+        f.write_strobe(0x00, Some(&mut single));
+        turbo_file_clock(&mut f, &mut single, 1);
+        assert_eq!(single.storage()[0], 0x01);
+        assert!(TurboFileImage::from_storage(alloc::vec![0; TURBO_FILE_BANK_SIZE]).is_some());
+        assert!(TurboFileImage::from_storage(alloc::vec![0; 2 * TURBO_FILE_BANK_SIZE]).is_none());
+        assert!(TurboFileImage::from_storage(alloc::vec![0; 100]).is_none());
+    }
 }
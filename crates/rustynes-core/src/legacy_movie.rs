@@ -197,6 +197,7 @@ pub fn import_fcm(
         // Imported: no attestation (the source format has no such field, and
         // synthesizing one would attest a run this build never performed).
         attestation: None,
+        match_info: None,
     };
     Ok((
         movie,
@@ -415,6 +416,7 @@ pub fn import_fmv(
         // Imported: no attestation (the source format has no such field, and
         // synthesizing one would attest a run this build never performed).
         attestation: None,
+        match_info: None,
     };
     Ok((
         movie,
@@ -536,6 +538,7 @@ pub fn import_vmv(
         // Imported: no attestation (the source format has no such field, and
         // synthesizing one would attest a run this build never performed).
         attestation: None,
+        match_info: None,
     };
     Ok((
         movie,
@@ -30,7 +30,9 @@ mod bus_snapshot;
 mod controller;
 #[cfg(feature = "cpu-boot-trace")]
 pub mod cpu_boot_trace;
+pub mod data_recorder;
 pub mod debug;
+pub mod famicom_3d;
 pub mod genie;
 pub mod input_device;
 #[cfg(feature = "irq-timing-trace")]
@@ -43,6 +45,9 @@ mod movie;
 pub mod movie_interop;
 mod nes;
 mod rewind;
+// v2.5.0 — R.O.B. (Robotic Operating Buddy): screen-flash command decoder,
+// arm / hand pose and the Gyromite gyros that press controller II.
+pub mod rob;
 pub mod save_state;
 pub mod scheduler;
 pub mod vs_db;
@@ -70,19 +75,28 @@ pub use bus::LockstepBus;
 #[cfg(feature = "debug-hooks")]
 pub use bus::{AccessRec, EventBpKind, EventBreakHit, EventKind, EventRec, InterruptRec};
 pub use controller::{Buttons, Controller};
+pub use data_recorder::{
+    DataRecorder, TAPE_CYCLES_PER_SAMPLE, TAPE_SAMPLE_RATE, TapeMode, WavError,
+};
 pub use debug::{ApuDebugView, CpuDebugView, MapperDebugView, PpuDebugView};
+pub use famicom_3d::{Famicom3dGlasses, StereoEye};
 pub use genie::{GenieCode, GenieError};
 pub use input_device::{
-    BandaiHyperShotState, FamilyKeyboardState, InputDevice, KonamiHyperShotState, PowerPadState,
-    SnesMouseState, VausState, ZapperState,
+    BandaiHyperShotState, FamilyKeyboardState, HoriTrackState, InputDevice, KonamiHyperShotState,
+    OekaKidsTabletState, POWER_GLOVE_INIT, POWER_GLOVE_PACKET_LEN, PachinkoState, PowerGloveHand,
+    PowerGloveProgram, PowerGloveState, PowerPadState, SnesMouseState, TOP_RIDER_BRAKE,
+    TOP_RIDER_SELECT, TOP_RIDER_SHIFT, TOP_RIDER_START, TOP_RIDER_WHEELIE, TURBO_FILE_BANK_SIZE,
+    TURBO_FILE_TWIN_BANKS, TopRiderState, TurboFileImage, TurboFileState, VausState, ZapperState,
 };
 pub use legacy_movie::{
     LegacyMeta, LegacyMovieError, import_fcm, import_fmv, import_mc2, import_vmv,
 };
 pub use movie::{
     ATTESTATION_CHECKPOINT_INTERVAL, ATTESTATION_MAGIC, ATTESTATION_VERSION, Attestation,
-    AttestationBuilder, BYTES_PER_FRAME, FrameInput, MOVIE_FORMAT_VERSION, MOVIE_MAGIC, Movie,
-    MovieError, MoviePlayer, MovieRecorder, StartPoint, VerifyOutcome, recorded_before_v2_timebase,
+    AttestationBuilder, BYTES_PER_FRAME, DEVICE_INPUT_WIRE_LEN, DeviceInput,
+    EXTENDED_BYTES_PER_FRAME, FrameInput, MATCH_MAGIC, MATCH_VERSION, MOVIE_FORMAT_VERSION,
+    MOVIE_MAGIC, MatchInfo, Movie, MovieCommand, MovieError, MoviePlayer, MovieRecorder,
+    StartPoint, UnrecordableDevice, VerifyOutcome, recorded_before_v2_timebase,
 };
 #[cfg(feature = "debug-hooks")]
 pub use nes::TraceRec;
@@ -94,6 +108,7 @@ pub use nes::{
 pub use rewind::{
     REWIND_DEFAULT_KEYFRAME_PERIOD, REWIND_DEFAULT_MAX_BYTES, RewindError, RewindRing,
 };
+pub use rob::{Gyro, ROB_GYRO_SPIN_FRAMES, ROB_MAX_HEIGHT, ROB_SLOTS, Rob, RobCommand, RobMode};
 pub use rustynes_ppu::{PaletteInit, PpuRevision};
 pub use save_state::{
     BinReader, BinWriter, FORMAT_VERSION, HEADER_LEN, Header, MAGIC, ROM_HASH_TAG_LEN, Section,
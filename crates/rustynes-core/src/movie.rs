@@ -16,18 +16,29 @@
 //! ```text
 //! HEADER:
 //!     magic           : "RNESMOV1"   (8 bytes)
-//!     format version  : u16 LE        (currently 1 = MOVIE_FORMAT_VERSION)
+//!     format version  : u16 LE        (2 plain, 3 extended; MOVIE_FORMAT_VERSION)
 //!     region          : u8            (0 = NTSC, 1 = PAL, 2 = Dendy)
 //!     flags           : u8            (bit0 = embedded save-state start point)
 //!     rom sha-256     : [u8; 32]      (full hash — authoritative ROM identity)
 //!     frame count     : u32 LE
-//!     bytes per frame : u8            (currently 3: P1, P2, expansion-reserved)
+//!     bytes per frame : u8            (3 plain: P1, P2, expansion; 19 extended)
 //! START POINT (only when flags bit0 set):
 //!     length-prefixed `.rns` save-state blob (u32 LE length + bytes)
 //! INPUT STREAM:
 //!     frame_count * bytes_per_frame raw bytes; each frame = [p1, p2, expansion]
+//!     (extended: the same three, then the fields listed on FrameInput::encode)
 //! ```
 //!
+//! The expansion byte carries the Famicom microphone pattern (v2.5.0; see
+//! [`Nes::set_microphone_pattern`]). Movies recorded before it was defined
+//! store `0` there — the mic released — which is what they played with.
+//!
+//! A movie that used the Four Score, a recordable expansion device, or a
+//! console command (reset, power cycle, disk swap, Vs. coin) is written with
+//! the wider extended record and format version 3 (v2.5.0). Every other movie
+//! is still written as a plain version-2 movie, byte for byte what earlier
+//! builds produced, so they keep reading it.
+//!
 //! This module is `no_std`-clean: it uses only `core` + `alloc` and the
 //! `BinWriter` / `BinReader` primitives from [`crate::save_state`].
 
@@ -35,6 +46,7 @@ use alloc::vec::Vec;
 
 use crate::Region;
 use crate::controller::Buttons;
+use crate::input_device::InputDevice;
 use crate::nes::Nes;
 use crate::save_state::{BinReader, BinWriter, SnapshotError};
 use thiserror::Error;
@@ -60,7 +72,12 @@ pub const MOVIE_MAGIC: &[u8; 8] = b"RNESMOV1";
 ///   epoch, not silently promising equivalence. See
 ///   [`recorded_before_v2_timebase`] for the check callers (TAS tooling,
 ///   frontend movie-load UI) should use before relying on verify-replay.
-pub const MOVIE_FORMAT_VERSION: u16 = 2;
+/// - **v3 (v2.5.0)**: the extended frame record
+///   ([`EXTENDED_BYTES_PER_FRAME`]) carrying Four Score players, expansion-
+///   device state and console commands. Stamped only on a movie that needs it
+///   ([`Movie::is_extended`]); a plain movie is still written as v2, so an
+///   older build rejects only the movies it could not have replayed anyway.
+pub const MOVIE_FORMAT_VERSION: u16 = 3;
 
 /// Peek a `.rnm` blob's header to learn its recording epoch.
 ///
@@ -97,18 +114,40 @@ pub fn recorded_before_v2_timebase(bytes: &[u8]) -> Result<bool, MovieError> {
     Ok(format_version < 2)
 }
 
-/// Bytes stored per recorded frame: player 1, player 2, and a reserved
-/// expansion-port byte (always `0` in v1).
+/// Bytes stored per recorded frame in a plain movie: player 1, player 2, and
+/// an expansion byte (the Famicom microphone pattern since v2.5.0; `0`
+/// before).
 ///
 /// Stored explicitly in the header so a future device byte can grow the
 /// record without a container-version bump.
 pub const BYTES_PER_FRAME: u8 = 3;
 
+/// v2.5.0 — bytes stored per recorded frame in an extended (v3) movie.
+///
+/// The plain three bytes, then P3, P4, a port-flags byte (bit0 = Four Score
+/// active), the [`MovieCommand`] code and argument, and the [`DeviceInput`]
+/// kind, port and nine-byte payload (sized for the keyboard matrix). See
+/// [`FrameInput::encode`] for the exact offsets.
+pub const EXTENDED_BYTES_PER_FRAME: u8 = 19;
+
+/// Format version stamped on a movie that needs only the plain record — the
+/// epoch marker described on [`MOVIE_FORMAT_VERSION`]'s v2 entry.
+const PLAIN_FORMAT_VERSION: u16 = 2;
+
 /// Header flag: an embedded `.rns` save-state start point follows the header.
 const FLAG_HAS_SAVE_STATE: u8 = 0x01;
 
+/// Extended-record port flag: the Four Score adapter was active.
+const PORT_FLAG_FOUR_SCORE: u8 = 0x01;
+
+/// Size of the [`DeviceInput`] payload inside an extended record.
+const DEVICE_PAYLOAD_LEN: usize = 9;
+
 /// Per-frame controller input: the `Buttons` bits for both standard ports
-/// plus a reserved expansion byte. Bit layout matches FCEUX `.fm2`
+/// plus an expansion byte.
+///
+/// An extended movie adds the Four Score players, the expansion-port device
+/// and a console command. Bit layout matches FCEUX `.fm2`
 /// (`bit0=A .. bit7=Right`), which is exactly [`Buttons::bits`].
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub struct FrameInput {
@@ -116,8 +155,19 @@ pub struct FrameInput {
     pub p1: Buttons,
     /// Player 2 (`$4017`) button state.
     pub p2: Buttons,
-    /// Reserved expansion-port byte (currently always `0`).
+    /// Expansion byte: the Famicom microphone pattern latched for the frame
+    /// (v2.5.0, [`Nes::set_microphone_pattern`]); `0` = mic released.
     pub expansion: u8,
+    /// v2.5.0 — Four Score player 3 button state.
+    pub p3: Buttons,
+    /// v2.5.0 — Four Score player 4 button state.
+    pub p4: Buttons,
+    /// v2.5.0 — whether the Four Score adapter was active for the frame.
+    pub four_score: bool,
+    /// v2.5.0 — the expansion-port device state latched for the frame.
+    pub device: DeviceInput,
+    /// v2.5.0 — the console command applied before the frame ran.
+    pub command: MovieCommand,
 }
 
 impl FrameInput {
@@ -128,10 +178,517 @@ impl FrameInput {
             p1,
             p2,
             expansion: 0,
+            p3: Buttons::empty(),
+            p4: Buttons::empty(),
+            four_score: false,
+            device: DeviceInput::None,
+            command: MovieCommand::None,
+        }
+    }
+
+    /// `true` if the frame carries anything the plain three-byte record
+    /// cannot hold: Four Score players, a device state, or a command.
+    #[must_use]
+    pub const fn is_extended(&self) -> bool {
+        self.four_score
+            || !self.p3.is_empty()
+            || !self.p4.is_empty()
+            || !matches!(self.device, DeviceInput::None)
+            || !matches!(self.command, MovieCommand::None)
+    }
+
+    /// The frame's full extended record. A plain movie stores only the first
+    /// [`BYTES_PER_FRAME`] bytes of it.
+    ///
+    /// ```text
+    /// [0] p1   [1] p2   [2] expansion (mic pattern)
+    /// [3] p3   [4] p4   [5] port flags (bit0 = Four Score)
+    /// [6] command code  [7] command argument
+    /// [8] device kind   [9] device port   [10..19] device payload
+    /// ```
+    #[must_use]
+    pub fn encode(&self) -> [u8; EXTENDED_BYTES_PER_FRAME as usize] {
+        let mut rec = [0u8; EXTENDED_BYTES_PER_FRAME as usize];
+        rec[0] = self.p1.bits();
+        rec[1] = self.p2.bits();
+        rec[2] = self.expansion;
+        rec[3] = self.p3.bits();
+        rec[4] = self.p4.bits();
+        rec[5] = if self.four_score {
+            PORT_FLAG_FOUR_SCORE
+        } else {
+            0
+        };
+        [rec[6], rec[7]] = self.command.encode();
+        let (kind, port, payload) = self.device.encode();
+        rec[8] = kind;
+        rec[9] = port;
+        rec[10..].copy_from_slice(&payload);
+        rec
+    }
+
+    /// Decode a record of any width up to [`EXTENDED_BYTES_PER_FRAME`].
+    /// Missing trailing bytes read as `0`, and unknown command or device
+    /// codes decode as `None`, so a malformed record never panics.
+    #[must_use]
+    pub fn decode(rec: &[u8]) -> Self {
+        let mut full = [0u8; EXTENDED_BYTES_PER_FRAME as usize];
+        let n = rec.len().min(full.len());
+        full[..n].copy_from_slice(&rec[..n]);
+        let mut payload = [0u8; DEVICE_PAYLOAD_LEN];
+        payload.copy_from_slice(&full[10..]);
+        Self {
+            p1: Buttons::from_bits_truncate(full[0]),
+            p2: Buttons::from_bits_truncate(full[1]),
+            expansion: full[2],
+            p3: Buttons::from_bits_truncate(full[3]),
+            p4: Buttons::from_bits_truncate(full[4]),
+            four_score: full[5] & PORT_FLAG_FOUR_SCORE != 0,
+            command: MovieCommand::decode(full[6], full[7]),
+            device: DeviceInput::decode(full[8], full[9], payload),
+        }
+    }
+
+    /// Apply this frame to `nes`: the command first (a reset or power cycle
+    /// must not land after the buttons it precedes), then the controllers,
+    /// the mic pattern and — for an `extended` movie — the Four Score
+    /// players and the expansion device.
+    ///
+    /// `extended` is [`Movie::is_extended`] for the movie being played. A
+    /// plain movie leaves the Four Score setting and ports 3/4 alone, exactly
+    /// as playback did before the extended record existed; an extended one
+    /// drives them every frame, so a mid-run adapter toggle replays too.
+    pub fn apply(&self, nes: &mut Nes, extended: bool) {
+        self.command.apply(nes);
+        nes.set_buttons(0, self.p1);
+        nes.set_buttons(1, self.p2);
+        nes.set_microphone_pattern(self.expansion);
+        if extended {
+            nes.set_four_score(self.four_score);
+            nes.set_buttons(2, self.p3);
+            nes.set_buttons(3, self.p4);
+            self.device.apply(nes);
+        }
+    }
+}
+
+/// v2.5.0 — a console command recorded on the frame it was issued, applied
+/// before that frame's input on playback.
+///
+/// One per frame. The frontend issues these from menu actions and hotkeys,
+/// and [`MovieRecorder::queue_command`] refuses a second one for the same
+/// frame rather than dropping the first; a host that can wait (the Vs. coin
+/// release) carries its command out a frame later instead.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MovieCommand {
+    /// No command.
+    #[default]
+    None,
+    /// Soft reset ([`Nes::reset`]).
+    SoftReset,
+    /// Power cycle ([`Nes::power_cycle`]).
+    PowerCycle,
+    /// Eject the FDS disk.
+    EjectDisk,
+    /// Insert FDS side `n` (zero-based).
+    InsertDisk(u8),
+    /// Latch a Vs. System coin on acceptor `n` (0 = #1, 1 = #2).
+    InsertCoin(u8),
+    /// Release every latched Vs. System coin signal.
+    ClearCoin,
+}
+
+impl MovieCommand {
+    /// `(code, argument)` as stored in an extended record.
+    const fn encode(self) -> [u8; 2] {
+        match self {
+            Self::None => [0, 0],
+            Self::SoftReset => [1, 0],
+            Self::PowerCycle => [2, 0],
+            Self::EjectDisk => [3, 0],
+            Self::InsertDisk(side) => [4, side],
+            Self::InsertCoin(acceptor) => [5, acceptor],
+            Self::ClearCoin => [6, 0],
+        }
+    }
+
+    const fn decode(code: u8, arg: u8) -> Self {
+        match code {
+            1 => Self::SoftReset,
+            2 => Self::PowerCycle,
+            3 => Self::EjectDisk,
+            4 => Self::InsertDisk(arg),
+            5 => Self::InsertCoin(arg),
+            6 => Self::ClearCoin,
+            _ => Self::None,
+        }
+    }
+
+    /// Carry the command out on `nes`.
+    pub fn apply(self, nes: &mut Nes) {
+        match self {
+            Self::None => {}
+            Self::SoftReset => nes.reset(),
+            Self::PowerCycle => nes.power_cycle(),
+            Self::EjectDisk => nes.set_disk_side(None),
+            Self::InsertDisk(side) => nes.set_disk_side(Some(usize::from(side))),
+            Self::InsertCoin(acceptor) => nes.insert_coin(acceptor),
+            Self::ClearCoin => nes.clear_coin(),
+        }
+    }
+}
+
+/// v2.5.0 — the expansion-device state a frame was played with.
+///
+/// Covers the devices whose per-frame state is host input the emulator
+/// cannot re-derive: the Zapper aim and trigger, the Vaus paddle, the SNES
+/// mouse, the Power Pad / Family Trainer mats and the two keyboard matrices.
+/// The mic pattern keeps its own
+/// [`FrameInput::expansion`] byte. `port` is the controller port the device
+/// was attached to (`0` or `1`).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DeviceInput {
+    /// No recorded device.
+    #[default]
+    None,
+    /// NES Zapper aim point and trigger ([`Nes::set_zapper`]).
+    Zapper {
+        /// Controller port.
+        port: u8,
+        /// Aim X in screen pixels.
+        x: u16,
+        /// Aim Y in scanlines.
+        y: u16,
+        /// Trigger pulled.
+        trigger: bool,
+    },
+    /// Arkanoid Vaus paddle ([`Nes::set_paddle`]).
+    Paddle {
+        /// Controller port.
+        port: u8,
+        /// Raw potentiometer position.
+        position: u8,
+        /// Fire button held.
+        fire: bool,
+    },
+    /// SNES mouse ([`Nes::set_snes_mouse`]). The deltas are stored already
+    /// clamped to the +/-127 the device latches, so nothing is lost.
+    Mouse {
+        /// Controller port.
+        port: u8,
+        /// Horizontal delta.
+        dx: i8,
+        /// Vertical delta.
+        dy: i8,
+        /// Left button held.
+        left: bool,
+        /// Right button held.
+        right: bool,
+        /// Sensitivity (0 low / 1 medium / 2 high).
+        sensitivity: u8,
+    },
+    /// Family BASIC keyboard matrix ([`Nes::set_family_keyboard`]).
+    FamilyKeyboard {
+        /// Controller port.
+        port: u8,
+        /// One byte per matrix row.
+        keys: [u8; 9],
+    },
+    /// Subor keyboard matrix ([`Nes::set_subor_keyboard`]).
+    SuborKeyboard {
+        /// Controller port.
+        port: u8,
+        /// One byte per matrix row.
+        keys: [u8; 9],
+    },
+    /// Power Pad mat ([`Nes::set_power_pad`]).
+    PowerPad {
+        /// Controller port.
+        port: u8,
+        /// Pressed-button mask (bit `i` = mat button `i+1`).
+        buttons: u16,
+    },
+    /// Family Trainer mat ([`Nes::set_family_trainer`]).
+    FamilyTrainer {
+        /// Controller port.
+        port: u8,
+        /// Pressed-button mask (bit `i` = mat button `i+1`).
+        buttons: u16,
+    },
+}
+
+/// Size of a [`DeviceInput`] in its standalone wire form
+/// ([`DeviceInput::to_bytes`]): kind, port, then the record payload.
+pub const DEVICE_INPUT_WIRE_LEN: usize = 2 + DEVICE_PAYLOAD_LEN;
+
+impl DeviceInput {
+    /// Read the recordable device attached to `nes`, preferring port 1 (where
+    /// the frontend attaches expansion devices) over port 0.
+    #[must_use]
+    pub fn capture(nes: &Nes) -> Self {
+        for port in [1u8, 0] {
+            let device = match nes.expansion_device(usize::from(port)) {
+                Some(InputDevice::Zapper(z)) => Self::Zapper {
+                    port,
+                    x: z.x_raw(),
+                    y: z.y_raw(),
+                    trigger: z.trigger_raw(),
+                },
+                Some(InputDevice::Vaus(v)) => Self::Paddle {
+                    port,
+                    position: v.position_raw(),
+                    fire: v.fire_raw(),
+                },
+                Some(InputDevice::SnesMouse(m)) => Self::Mouse {
+                    port,
+                    dx: clamp_delta(m.dx_raw()),
+                    dy: clamp_delta(m.dy_raw()),
+                    left: m.left_raw(),
+                    right: m.right_raw(),
+                    sensitivity: m.sensitivity_raw(),
+                },
+                Some(InputDevice::FamilyKeyboard(k)) => Self::FamilyKeyboard {
+                    port,
+                    keys: k.keys_raw(),
+                },
+                Some(InputDevice::SuborKeyboard(k)) => Self::SuborKeyboard {
+                    port,
+                    keys: k.keys_raw(),
+                },
+                Some(InputDevice::PowerPad(p)) => Self::PowerPad {
+                    port,
+                    buttons: p.buttons_raw(),
+                },
+                Some(InputDevice::FamilyTrainer(p)) => Self::FamilyTrainer {
+                    port,
+                    buttons: p.buttons_raw(),
+                },
+                _ => continue,
+            };
+            return device;
+        }
+        Self::None
+    }
+
+    /// Check that [`Self::capture`] sees everything the devices on `nes`
+    /// feed the game. A frame records one device, so this fails when both
+    /// ports drive one or when a port holds a device with no `DeviceInput`
+    /// form. A Turbo File has no live input and passes.
+    ///
+    /// # Errors
+    ///
+    /// [`UnrecordableDevice`] naming what a recording would lose.
+    pub fn recordable(nes: &Nes) -> Result<(), UnrecordableDevice> {
+        let mut driven = 0;
+        for port in 0..2 {
+            let name = match nes.expansion_device(port) {
+                None | Some(InputDevice::TurboFile(_)) => continue,
+                Some(
+                    InputDevice::Zapper(_)
+                    | InputDevice::Vaus(_)
+                    | InputDevice::SnesMouse(_)
+                    | InputDevice::FamilyKeyboard(_)
+                    | InputDevice::SuborKeyboard(_)
+                    | InputDevice::PowerPad(_)
+                    | InputDevice::FamilyTrainer(_),
+                ) => {
+                    driven += 1;
+                    continue;
+                }
+                Some(InputDevice::KonamiHyperShot(_)) => "Konami Hyper Shot",
+                Some(InputDevice::BandaiHyperShot(_)) => "Bandai Hyper Shot",
+                Some(InputDevice::OekaKidsTablet(_)) => "Oeka Kids tablet",
+                Some(InputDevice::HoriTrack(_)) => "Hori Track",
+                Some(InputDevice::Pachinko(_)) => "Pachinko controller",
+                Some(InputDevice::TopRider(_)) => "Top Rider",
+                Some(InputDevice::PowerGlove(_)) => "Power Glove",
+            };
+            return Err(UnrecordableDevice::Unsupported(name));
+        }
+        if driven > 1 {
+            return Err(UnrecordableDevice::BothPorts);
+        }
+        Ok(())
+    }
+
+    /// Drive the device on `nes`, attaching it first if it is absent (which
+    /// is what lets a bare verifier replay a Zapper movie). `None` leaves the
+    /// ports untouched.
+    pub fn apply(self, nes: &mut Nes) {
+        match self {
+            Self::None => {}
+            Self::Zapper {
+                port,
+                x,
+                y,
+                trigger,
+            } => nes.set_zapper(usize::from(port), x, y, trigger),
+            Self::Paddle {
+                port,
+                position,
+                fire,
+            } => nes.set_paddle(usize::from(port), position, fire),
+            Self::Mouse {
+                port,
+                dx,
+                dy,
+                left,
+                right,
+                sensitivity,
+            } => nes.set_snes_mouse(
+                usize::from(port),
+                i16::from(dx),
+                i16::from(dy),
+                left,
+                right,
+                sensitivity,
+            ),
+            Self::FamilyKeyboard { port, keys } => {
+                nes.set_family_keyboard(usize::from(port), keys);
+            }
+            Self::SuborKeyboard { port, keys } => {
+                nes.set_subor_keyboard(usize::from(port), keys);
+            }
+            Self::PowerPad { port, buttons } => nes.set_power_pad(usize::from(port), buttons),
+            Self::FamilyTrainer { port, buttons } => {
+                nes.set_family_trainer(usize::from(port), buttons);
+            }
+        }
+    }
+
+    /// The controller port the device drives, or `None` for [`Self::None`].
+    #[must_use]
+    pub const fn port(self) -> Option<u8> {
+        match self {
+            Self::None => None,
+            Self::Zapper { port, .. }
+            | Self::Paddle { port, .. }
+            | Self::Mouse { port, .. }
+            | Self::FamilyKeyboard { port, .. }
+            | Self::SuborKeyboard { port, .. }
+            | Self::PowerPad { port, .. }
+            | Self::FamilyTrainer { port, .. } => Some(port),
+        }
+    }
+
+    /// Standalone wire form: the same kind, port and payload an extended
+    /// record stores, for carriers other than a movie (netplay).
+    #[must_use]
+    pub fn to_bytes(self) -> [u8; DEVICE_INPUT_WIRE_LEN] {
+        let (kind, port, payload) = self.encode();
+        let mut out = [0u8; DEVICE_INPUT_WIRE_LEN];
+        out[0] = kind;
+        out[1] = port;
+        out[2..].copy_from_slice(&payload);
+        out
+    }
+
+    /// Inverse of [`Self::to_bytes`], with the same corrupt-input guarantees
+    /// as a movie record: unknown kinds become [`Self::None`] and the port is
+    /// masked to `0..=1`.
+    #[must_use]
+    pub fn from_bytes(bytes: &[u8; DEVICE_INPUT_WIRE_LEN]) -> Self {
+        let mut payload = [0u8; DEVICE_PAYLOAD_LEN];
+        payload.copy_from_slice(&bytes[2..]);
+        Self::decode(bytes[0], bytes[1], payload)
+    }
+
+    /// `(kind, port, payload)` as stored in an extended record.
+    fn encode(self) -> (u8, u8, [u8; DEVICE_PAYLOAD_LEN]) {
+        let mut p = [0u8; DEVICE_PAYLOAD_LEN];
+        match self {
+            Self::None => (0, 0, p),
+            Self::Zapper {
+                port,
+                x,
+                y,
+                trigger,
+            } => {
+                p[..2].copy_from_slice(&x.to_le_bytes());
+                p[2..4].copy_from_slice(&y.to_le_bytes());
+                p[4] = u8::from(trigger);
+                (1, port, p)
+            }
+            Self::Paddle {
+                port,
+                position,
+                fire,
+            } => {
+                p[0] = position;
+                p[1] = u8::from(fire);
+                (2, port, p)
+            }
+            Self::Mouse {
+                port,
+                dx,
+                dy,
+                left,
+                right,
+                sensitivity,
+            } => {
+                p[0] = dx.to_le_bytes()[0];
+                p[1] = dy.to_le_bytes()[0];
+                p[2] = u8::from(left) | (u8::from(right) << 1);
+                p[3] = sensitivity;
+                (3, port, p)
+            }
+            Self::FamilyKeyboard { port, keys } => (4, port, keys),
+            Self::SuborKeyboard { port, keys } => (5, port, keys),
+            Self::PowerPad { port, buttons } => {
+                p[..2].copy_from_slice(&buttons.to_le_bytes());
+                (6, port, p)
+            }
+            Self::FamilyTrainer { port, buttons } => {
+                p[..2].copy_from_slice(&buttons.to_le_bytes());
+                (7, port, p)
+            }
+        }
+    }
+
+    /// Inverse of [`Self::encode`]. The port is masked to `0..=1` so a
+    /// corrupt record can never reach the `set_*` port assertions.
+    const fn decode(kind: u8, port: u8, p: [u8; DEVICE_PAYLOAD_LEN]) -> Self {
+        let port = port & 0x01;
+        match kind {
+            1 => Self::Zapper {
+                port,
+                x: u16::from_le_bytes([p[0], p[1]]),
+                y: u16::from_le_bytes([p[2], p[3]]),
+                trigger: p[4] != 0,
+            },
+            2 => Self::Paddle {
+                port,
+                position: p[0],
+                fire: p[1] != 0,
+            },
+            3 => Self::Mouse {
+                port,
+                dx: i8::from_le_bytes([p[0]]),
+                dy: i8::from_le_bytes([p[1]]),
+                left: p[2] & 0x01 != 0,
+                right: p[2] & 0x02 != 0,
+                sensitivity: p[3],
+            },
+            4 => Self::FamilyKeyboard { port, keys: p },
+            5 => Self::SuborKeyboard { port, keys: p },
+            6 => Self::PowerPad {
+                port,
+                buttons: u16::from_le_bytes([p[0], p[1]]) & 0x0FFF,
+            },
+            7 => Self::FamilyTrainer {
+                port,
+                buttons: u16::from_le_bytes([p[0], p[1]]) & 0x0FFF,
+            },
+            _ => Self::None,
         }
     }
 }
 
+/// Clamp a mouse delta into the +/-127 the device latches.
+fn clamp_delta(d: i16) -> i8 {
+    i8::try_from(d.clamp(-127, 127)).unwrap_or(0)
+}
+
 /// Marker for the optional attestation tail: `"RNAT"` little-endian.
 ///
 /// Read as a `u32` after the re-record count. A movie with no attestation simply
@@ -268,9 +825,17 @@ impl AttestationBuilder {
     }
 
     /// Fold in one frame: the input applied, then the video it produced.
+    ///
+    /// An extended frame (v2.5.0) folds in its whole record; a plain frame
+    /// folds in only the three plain bytes, so every attestation recorded
+    /// before the extended record existed still verifies.
     pub fn push_frame(&mut self, input: FrameInput, framebuffer: &[u8]) {
-        self.hash
-            .write(&[input.p1.bits(), input.p2.bits(), input.expansion]);
+        let rec = input.encode();
+        if input.is_extended() {
+            self.hash.write(&rec);
+        } else {
+            self.hash.write(&rec[..usize::from(BYTES_PER_FRAME)]);
+        }
         self.hash.write(framebuffer);
         self.frame_count = self.frame_count.saturating_add(1);
         if self
@@ -354,10 +919,9 @@ pub enum VerifyOutcome {
 /// The checkpoint count is bounded by what the remaining input could actually
 /// hold before reserving, for the same reason `frame_count` is: a hostile
 /// four-byte field must not be able to request a multi-gigabyte allocation.
+///
+/// Called with the reader just past the [`ATTESTATION_MAGIC`] marker.
 fn read_attestation(r: &mut BinReader<'_>, frames: usize) -> Option<Attestation> {
-    if r.u32().ok()? != ATTESTATION_MAGIC {
-        return None;
-    }
     if r.u16().ok()? != ATTESTATION_VERSION {
         return None;
     }
@@ -379,6 +943,50 @@ fn read_attestation(r: &mut BinReader<'_>, frames: usize) -> Option<Attestation>
     })
 }
 
+/// Marker for the optional netplay-match tail: `"RNMP"` little-endian.
+///
+/// Follows the attestation tail (or the re-record count when there is none).
+/// An older reader stops before it, exactly as with [`ATTESTATION_MAGIC`].
+pub const MATCH_MAGIC: u32 = u32::from_le_bytes(*b"RNMP");
+
+/// Netplay-match tail schema version.
+pub const MATCH_VERSION: u16 = 1;
+
+/// What a recorded netplay match needs beyond its input stream to replay.
+///
+/// A match runs under settings the peers negotiated in the handshake — OAM
+/// decay, a PPU revision, Game Genie codes and the rest — none of which live in
+/// the save-state start point. A verifier that replays the inputs on its own
+/// defaults would diverge from the first frame the settings touch, so the
+/// recording carries them.
+///
+/// The core does not know the netplay layer's settings schema, so they travel
+/// as its canonical encoding, opaque here: `rustynes-netplay` writes them and
+/// decodes them again before a replay.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchInfo {
+    /// Players in the match. Player `n` is controller port `n` of the input
+    /// stream; more than two means the Four Score was on.
+    pub num_players: u8,
+    /// The negotiated emulation settings, in the netplay layer's canonical
+    /// encoding.
+    pub settings: Vec<u8>,
+}
+
+/// Read the optional netplay-match tail, just past its [`MATCH_MAGIC`]
+/// marker. Like [`read_attestation`], anything unusable yields `None`.
+fn read_match_info(r: &mut BinReader<'_>) -> Option<MatchInfo> {
+    if r.u16().ok()? != MATCH_VERSION {
+        return None;
+    }
+    let num_players = r.u8().ok()?;
+    let settings = r.lp_bytes().ok()?.to_vec();
+    Some(MatchInfo {
+        num_players,
+        settings,
+    })
+}
+
 /// Where a movie begins. Clean-room analogue of Mesen2's `RecordMovieFrom`.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum StartPoint {
@@ -391,6 +999,18 @@ pub enum StartPoint {
     SaveState(Vec<u8>),
 }
 
+/// Why the devices on a console can't be recorded
+/// ([`DeviceInput::recordable`]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub enum UnrecordableDevice {
+    /// The device has no [`DeviceInput`] form.
+    #[error("the {0} can't be recorded in a movie")]
+    Unsupported(&'static str),
+    /// Both controller ports drive a device; a movie frame holds one.
+    #[error("a movie frame records one expansion device, and both ports have one")]
+    BothPorts,
+}
+
 /// Errors produced by movie encode / decode / playback.
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -473,6 +1093,11 @@ pub struct Movie {
     /// additive-tail trick that field itself used, and the reason no container
     /// version bump was needed.
     pub attestation: Option<Attestation>,
+    /// Optional netplay-match metadata: the player count and negotiated
+    /// settings a match recording replays under. `None` for every movie that
+    /// is not a recorded match. Appended behind [`MATCH_MAGIC`], after the
+    /// attestation tail.
+    pub match_info: Option<MatchInfo>,
 }
 
 impl Movie {
@@ -488,16 +1113,30 @@ impl Movie {
         self.frames.is_empty()
     }
 
+    /// v2.5.0 — `true` if any frame needs the extended record
+    /// ([`FrameInput::is_extended`]), i.e. the movie serializes as format v3.
+    #[must_use]
+    pub fn is_extended(&self) -> bool {
+        self.frames.iter().any(FrameInput::is_extended)
+    }
+
     /// Serialize the movie to its `.rnm` byte representation.
     ///
     /// Deterministic: the same `Movie` always produces identical bytes.
     #[must_use]
     pub fn serialize(&self) -> Vec<u8> {
         let frame_count = u32::try_from(self.frames.len()).expect("frame count exceeds u32");
-        let body_hint = self.frames.len() * usize::from(BYTES_PER_FRAME);
+        // v2.5.0 — the extended record only when some frame needs it, so a
+        // plain movie stays byte-identical to what earlier builds wrote.
+        let (version, width) = if self.is_extended() {
+            (MOVIE_FORMAT_VERSION, EXTENDED_BYTES_PER_FRAME)
+        } else {
+            (PLAIN_FORMAT_VERSION, BYTES_PER_FRAME)
+        };
+        let body_hint = self.frames.len() * usize::from(width);
         let mut w = BinWriter::with_capacity(48 + body_hint);
         w.bytes(MOVIE_MAGIC);
-        w.u16(MOVIE_FORMAT_VERSION);
+        w.u16(version);
         w.u8(region_to_byte(self.region));
         let flags = match &self.start {
             StartPoint::PowerOn => 0,
@@ -506,14 +1145,12 @@ impl Movie {
         w.u8(flags);
         w.bytes(&self.rom_sha256);
         w.u32(frame_count);
-        w.u8(BYTES_PER_FRAME);
+        w.u8(width);
         if let StartPoint::SaveState(blob) = &self.start {
             w.lp_bytes(blob);
         }
         for f in &self.frames {
-            w.u8(f.p1.bits());
-            w.u8(f.p2.bits());
-            w.u8(f.expansion);
+            w.bytes(&f.encode()[..usize::from(width)]);
         }
         // Trailing re-record count (v1.8.9). Appended AFTER the fixed-count input
         // stream so a reader that stops at `frame_count` records — including older
@@ -532,6 +1169,12 @@ impl Movie {
                 w.u64(c);
             }
         }
+        if let Some(info) = &self.match_info {
+            w.u32(MATCH_MAGIC);
+            w.u16(MATCH_VERSION);
+            w.u8(info.num_players);
+            w.lp_bytes(&info.settings);
+        }
         w.into_vec()
     }
 
@@ -576,7 +1219,7 @@ impl Movie {
         // Frame count + width.
         let frame_count = r.u32().map_err(map_eof)? as usize;
         let bytes_per_frame = r.u8().map_err(map_eof)?;
-        if bytes_per_frame == 0 || bytes_per_frame > BYTES_PER_FRAME {
+        if bytes_per_frame == 0 || bytes_per_frame > EXTENDED_BYTES_PER_FRAME {
             // A newer movie packs more device bytes than we understand; we
             // fail cleanly rather than mis-parse (the reserved byte exists
             // precisely so this stays a graceful error, not a corruption).
@@ -586,11 +1229,12 @@ impl Movie {
             // so the `for _ in 0..frame_count` loop below would push
             // `frame_count` (an untrusted u32, up to ~4.3 billion) empty frames
             // out of a finite file — an OOM DoS (found by the `movie` fuzz
-            // target). A real movie always writes the fixed `BYTES_PER_FRAME`
-            // (>= 1), so rejecting 0 costs no legitimate file.
+            // target). A real movie always writes `BYTES_PER_FRAME` or
+            // `EXTENDED_BYTES_PER_FRAME` (>= 1), so rejecting 0 costs no
+            // legitimate file.
             return Err(MovieError::UnsupportedFrameWidth {
                 got: bytes_per_frame,
-                max: BYTES_PER_FRAME,
+                max: EXTENDED_BYTES_PER_FRAME,
             });
         }
         // Start point.
@@ -616,13 +1260,9 @@ impl Movie {
         let mut frames = Vec::with_capacity(frame_count.min(max_plausible_frames));
         for _ in 0..frame_count {
             let rec = r.take(width).map_err(map_eof)?;
-            // rec[0] = p1, rec[1] = p2 (present whenever width >= 2, which it
-            // always is for v1's width of 3); rec[2] = expansion when width
-            // >= 3. Lower widths default the missing fields.
-            let p1 = Buttons::from_bits_truncate(rec.first().copied().unwrap_or(0));
-            let p2 = Buttons::from_bits_truncate(rec.get(1).copied().unwrap_or(0));
-            let expansion = rec.get(2).copied().unwrap_or(0);
-            frames.push(FrameInput { p1, p2, expansion });
+            // A plain record is the first three bytes of an extended one;
+            // `decode` defaults whatever a narrower width leaves out.
+            frames.push(FrameInput::decode(rec));
         }
         // Optional trailing re-record count (v1.8.9). Absent in pre-v1.8.9 `.rnm`
         // files, which stop exactly at the input stream — default to 0.
@@ -630,7 +1270,28 @@ impl Movie {
         // Optional attestation tail (v2.3.2 "Lucid"). Absent in every movie
         // recorded before it existed, and in any recorded without it — so a
         // missing or unrecognized marker yields `None` rather than an error.
-        let attestation = read_attestation(&mut r, frames.len());
+        // The netplay-match tail follows it the same way. A tail that does
+        // not parse ends the chain: past it the reader's position means
+        // nothing.
+        let mut attestation = None;
+        let mut match_info = None;
+        while let Ok(marker) = r.u32() {
+            match marker {
+                ATTESTATION_MAGIC if attestation.is_none() => {
+                    attestation = read_attestation(&mut r, frames.len());
+                    if attestation.is_none() {
+                        break;
+                    }
+                }
+                MATCH_MAGIC if match_info.is_none() => {
+                    match_info = read_match_info(&mut r);
+                    if match_info.is_none() {
+                        break;
+                    }
+                }
+                _ => break,
+            }
+        }
         Ok(Self {
             region,
             rom_sha256,
@@ -638,6 +1299,7 @@ impl Movie {
             frames,
             rerecord_count,
             attestation,
+            match_info,
         })
     }
 
@@ -764,6 +1426,10 @@ pub struct MovieRecorder {
     /// v2.3.2 "Lucid" — optional attestation accumulator. `None` (the default)
     /// records a plain movie, byte-for-byte what previous versions produced.
     attestation: Option<AttestationBuilder>,
+    /// v2.5.0 — the console command queued for the next captured frame.
+    pending_command: MovieCommand,
+    /// Netplay-match metadata stamped on the finished movie, if any.
+    match_info: Option<MatchInfo>,
 }
 
 impl MovieRecorder {
@@ -779,6 +1445,8 @@ impl MovieRecorder {
             start: StartPoint::PowerOn,
             frames: Vec::new(),
             attestation: None,
+            pending_command: MovieCommand::None,
+            match_info: None,
         }
     }
 
@@ -793,6 +1461,8 @@ impl MovieRecorder {
             start: StartPoint::SaveState(nes.snapshot()),
             frames: Vec::new(),
             attestation: None,
+            pending_command: MovieCommand::None,
+            match_info: None,
         }
     }
 
@@ -801,34 +1471,40 @@ impl MovieRecorder {
     /// `set_buttons` calls — this captures exactly the inputs the upcoming
     /// frame consumes.
     ///
-    /// # Two ports only, including under a Four Score
-    ///
-    /// [`FrameInput`] models ports 0 and 1, so this reads `nes.buttons(0)` and
-    /// `nes.buttons(1)` and **nothing else**. The core itself carries four —
-    /// the frontend calls `set_buttons(2)` / `set_buttons(3)` whenever the Four
-    /// Score adapter is active — so recording a four-player session captures
-    /// half of what drove it, and replaying that movie diverges from the run it
-    /// came from.
-    ///
-    /// Stated here rather than left to be discovered, because the failure is
-    /// silent at record time: nothing about a `.rnm` says which ports it could
-    /// not hold, and the divergence only appears on playback. The frontend's
-    /// Replay panel says so where the topology is displayed, and
-    /// `Movie::verify`'s attestation catches it after the fact.
-    ///
-    /// Widening [`FrameInput`] is a `.rnm` format epoch change (ADR 0028), not
-    /// an additive one, which is why this is a documented limit rather than a
-    /// fix. The `.fm2` importer already takes the same position for the same
-    /// reason — it keeps pads 1 and 2, drops 3 and 4, and preserves the
-    /// `fourscore` flag so the caller is not silently misled.
+    /// Reads all four controller ports, the Four Score setting, the mic
+    /// pattern and the attached expansion device ([`DeviceInput::capture`]),
+    /// plus any command queued with [`Self::queue_command`] since the last
+    /// capture. A session that never touches any of the v2.5.0 additions
+    /// still produces a plain movie.
     pub fn capture(&mut self, nes: &Nes) {
         self.frames.push(FrameInput {
             p1: nes.buttons(0),
             p2: nes.buttons(1),
-            expansion: 0,
+            expansion: nes.microphone_pattern(),
+            p3: nes.buttons(2),
+            p4: nes.buttons(3),
+            four_score: nes.four_score(),
+            device: DeviceInput::capture(nes),
+            command: core::mem::take(&mut self.pending_command),
         });
     }
 
+    /// v2.5.0 — record a console command the host is about to carry out (or
+    /// just has) between frames. It is stored on the next captured frame,
+    /// which is the frame playback applies it before.
+    ///
+    /// One command per frame: a second call before the next capture is
+    /// refused (`false`) and the first stays queued. The host must still
+    /// perform the command itself — this only records it — so a host that
+    /// can defer should queue first and act only once this returns `true`.
+    pub const fn queue_command(&mut self, command: MovieCommand) -> bool {
+        if !matches!(self.pending_command, MovieCommand::None) {
+            return false;
+        }
+        self.pending_command = command;
+        true
+    }
+
     /// Record an explicit frame of input (for callers that drive input
     /// programmatically rather than through `set_buttons`).
     pub fn capture_input(&mut self, input: FrameInput) {
@@ -885,6 +1561,12 @@ impl MovieRecorder {
         }
     }
 
+    /// Mark the recording as a netplay match played under `info`, so a
+    /// verifier can configure itself the way the peers were before replaying.
+    pub fn set_match_info(&mut self, info: MatchInfo) {
+        self.match_info = Some(info);
+    }
+
     /// Finish recording and produce the [`Movie`].
     #[must_use]
     pub fn finish(self) -> Movie {
@@ -897,6 +1579,7 @@ impl MovieRecorder {
             // sets a real count when it exports an edited movie.
             rerecord_count: 0,
             attestation: self.attestation.map(AttestationBuilder::finish),
+            match_info: self.match_info,
         }
     }
 }
@@ -918,13 +1601,19 @@ impl MovieRecorder {
 pub struct MoviePlayer<'a> {
     movie: &'a Movie,
     cursor: usize,
+    /// [`Movie::is_extended`], computed once up front.
+    extended: bool,
 }
 
 impl<'a> MoviePlayer<'a> {
     /// Create a player positioned at frame 0 of `movie`.
     #[must_use]
-    pub const fn new(movie: &'a Movie) -> Self {
-        Self { movie, cursor: 0 }
+    pub fn new(movie: &'a Movie) -> Self {
+        Self {
+            movie,
+            cursor: 0,
+            extended: movie.is_extended(),
+        }
     }
 
     /// Total frames in the movie.
@@ -957,8 +1646,8 @@ impl<'a> MoviePlayer<'a> {
         self.movie.frames.get(self.cursor).copied()
     }
 
-    /// Apply the next frame's recorded input to `nes` via `set_buttons` and
-    /// advance the cursor. Returns `false` (without applying anything) once
+    /// Apply the next frame's recorded input to `nes` ([`FrameInput::apply`])
+    /// and advance the cursor. Returns `false` (without applying anything) once
     /// the movie is exhausted — the caller stops its replay loop on `false`.
     ///
     /// Call this *before* [`Nes::run_frame`], mirroring the record-side
@@ -967,8 +1656,7 @@ impl<'a> MoviePlayer<'a> {
         let Some(input) = self.movie.frames.get(self.cursor).copied() else {
             return false;
         };
-        nes.set_buttons(0, input.p1);
-        nes.set_buttons(1, input.p2);
+        input.apply(nes, self.extended);
         self.cursor += 1;
         true
     }
@@ -1113,6 +1801,47 @@ mod tests {
         assert!(parsed.attestation.is_none());
     }
 
+    /// The netplay-match tail rides after the attestation, round-trips with
+    /// or without one, and an attestation-only reader still finds its tail.
+    #[test]
+    fn match_info_round_trips_behind_the_attestation() {
+        let rom = synth_nrom();
+        let info = MatchInfo {
+            num_players: 3,
+            settings: vec![1, 2, 3, 4, 5],
+        };
+
+        let mut nes = Nes::from_rom(&rom).expect("parse");
+        let mut rec = MovieRecorder::power_on(&nes);
+        rec.enable_attestation();
+        rec.set_match_info(info.clone());
+        for _ in 0..4 {
+            rec.capture(&nes);
+            let fb = nes.run_frame().to_vec();
+            rec.attest_frame(&fb);
+        }
+        let attested = rec.finish();
+        let full = attested.serialize();
+        assert_eq!(Movie::deserialize(&full).expect("round-trip"), attested);
+        assert_eq!(attested.match_info.as_ref(), Some(&info));
+
+        // Without the match tail the attestation still parses: what a build
+        // that predates match recording sees.
+        let match_len = 4 + 2 + 1 + 4 + info.settings.len();
+        let older = Movie::deserialize(&full[..full.len() - match_len]).expect("parse");
+        assert_eq!(older.attestation, attested.attestation);
+        assert!(older.match_info.is_none());
+
+        // And a match recorded without an attestation keeps its metadata.
+        let mut plain = MovieRecorder::power_on(&nes);
+        plain.set_match_info(info.clone());
+        plain.capture(&nes);
+        let plain = plain.finish();
+        let back = Movie::deserialize(&plain.serialize()).expect("round-trip");
+        assert!(back.attestation.is_none());
+        assert_eq!(back.match_info, Some(info));
+    }
+
     /// Tampering with the input stream must be detected. This is the property
     /// that makes an attestation worth anything.
     #[test]
@@ -1392,6 +2121,7 @@ mod tests {
             frames: inputs,
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         let bytes = movie.serialize();
         let back = Movie::deserialize(&bytes).expect("round-trip");
@@ -1407,6 +2137,7 @@ mod tests {
             frames: synthetic_inputs(10),
             rerecord_count: 4242,
             attestation: None,
+            match_info: None,
         };
         let bytes = movie.serialize();
         // A full round-trip preserves the count.
@@ -1429,6 +2160,7 @@ mod tests {
             frames: synthetic_inputs(8),
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         let bytes = movie.serialize();
         let back = Movie::deserialize(&bytes).expect("round-trip");
@@ -1454,6 +2186,7 @@ mod tests {
             frames: Vec::new(),
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         let mut bytes = movie.serialize();
         // Bump the format-version field (offset 8) past what we support.
@@ -1490,6 +2223,7 @@ mod tests {
             frames: Vec::new(),
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         let mut bytes = movie.serialize();
         bytes[FRAME_COUNT_OFF..FRAME_COUNT_OFF + 4].copy_from_slice(&u32::MAX.to_le_bytes());
@@ -1509,6 +2243,7 @@ mod tests {
             frames: synthetic_inputs(10),
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         let bytes = movie.serialize();
         // Lop off the last few input bytes — must error, not panic.
@@ -1581,6 +2316,7 @@ mod tests {
             frames: synthetic_inputs(20),
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
 
         let run = |movie: &Movie| -> (u64, u64, u64) {
@@ -1673,6 +2409,7 @@ mod tests {
             frames: Vec::new(),
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         let mut nes = Nes::from_rom(&rom).unwrap();
         assert!(matches!(
@@ -1695,6 +2432,7 @@ mod tests {
             )],
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         let bytes = movie.serialize();
         // Input stream begins right after the 49-byte fixed header (no
@@ -1703,7 +2441,242 @@ mod tests {
         let p2 = bytes[50];
         assert_eq!(p1, (Buttons::A | Buttons::RIGHT).bits());
         assert_eq!(p2, (Buttons::B | Buttons::START).bits());
-        assert_eq!(bytes[51], 0, "expansion byte reserved/zero");
+        assert_eq!(bytes[51], 0, "no microphone -> zero expansion byte");
+    }
+
+    #[test]
+    fn microphone_pattern_records_and_replays_in_expansion_byte() {
+        let rom = synth_nrom();
+        let patterns = [0x00u8, 0xFF, 0x3C, 0x81];
+        let mut nes = Nes::from_rom(&rom).expect("boot");
+        let mut rec = MovieRecorder::power_on(&nes);
+        for &p in &patterns {
+            nes.set_microphone_pattern(p);
+            rec.capture(&nes);
+            nes.run_frame();
+        }
+        let movie = Movie::deserialize(&rec.finish().serialize()).expect("round trip");
+        let stored: Vec<u8> = movie.frames.iter().map(|f| f.expansion).collect();
+        assert_eq!(stored, patterns);
+
+        let mut replay = Nes::from_rom(&rom).expect("boot");
+        movie.seek_to_start(&mut replay).expect("seek");
+        let mut player = MoviePlayer::new(&movie);
+        for &p in &patterns {
+            assert!(player.apply_next(&mut replay));
+            assert_eq!(replay.microphone_pattern(), p);
+            replay.run_frame();
+        }
+    }
+
+    /// v2.5.0 — a movie that only touches the plain fields is still written
+    /// as a v2, three-byte movie; one extended frame switches the whole movie
+    /// to the v3 record, and every field survives the round trip.
+    #[test]
+    fn extended_record_is_written_only_when_needed_and_round_trips() {
+        let mut movie = Movie {
+            region: Region::Ntsc,
+            rom_sha256: [0x42; 32],
+            start: StartPoint::PowerOn,
+            frames: synthetic_inputs(4),
+            rerecord_count: 0,
+            attestation: None,
+            match_info: None,
+        };
+        let plain = movie.serialize();
+        assert_eq!(u16::from_le_bytes([plain[8], plain[9]]), 2);
+        assert_eq!(plain[48], BYTES_PER_FRAME);
+
+        movie.frames[1].p3 = Buttons::START;
+        movie.frames[1].p4 = Buttons::LEFT | Buttons::B;
+        movie.frames[1].four_score = true;
+        movie.frames[2].device = DeviceInput::Zapper {
+            port: 1,
+            x: 300,
+            y: 17,
+            trigger: true,
+        };
+        movie.frames[2].command = MovieCommand::InsertDisk(3);
+        movie.frames[3].device = DeviceInput::Mouse {
+            port: 0,
+            dx: -127,
+            dy: 5,
+            left: false,
+            right: true,
+            sensitivity: 2,
+        };
+        movie.frames[3].command = MovieCommand::SoftReset;
+        movie.frames[0].device = DeviceInput::PowerPad {
+            port: 1,
+            buttons: 0x0A05,
+        };
+        assert!(movie.is_extended());
+        let bytes = movie.serialize();
+        assert_eq!(
+            u16::from_le_bytes([bytes[8], bytes[9]]),
+            MOVIE_FORMAT_VERSION
+        );
+        assert_eq!(bytes[48], EXTENDED_BYTES_PER_FRAME);
+        assert_eq!(Movie::deserialize(&bytes).expect("round trip"), movie);
+    }
+
+    /// v2.5.0 — the recorder captures P3/P4, the Four Score setting, the
+    /// attached device and a queued command; the player puts all of them
+    /// back, and the attestation covers them.
+    #[test]
+    fn four_score_device_and_commands_record_and_replay() {
+        let rom = synth_nrom();
+        let mut nes = Nes::from_rom(&rom).expect("boot");
+        let mut rec = MovieRecorder::power_on(&nes);
+        rec.enable_attestation();
+        nes.set_four_score(true);
+        for i in 0..6u16 {
+            nes.set_buttons(2, Buttons::A);
+            nes.set_buttons(3, Buttons::from_bits_truncate(0x10 << (i % 4)));
+            nes.set_zapper(1, 40 + i, 100, i % 2 == 0);
+            if i == 3 {
+                nes.reset();
+                assert!(rec.queue_command(MovieCommand::SoftReset));
+                assert!(
+                    !rec.queue_command(MovieCommand::ClearCoin),
+                    "the frame's slot is taken"
+                );
+            }
+            rec.capture(&nes);
+            let fb = nes.run_frame().to_vec();
+            rec.attest_frame(&fb);
+        }
+        let movie = Movie::deserialize(&rec.finish().serialize()).expect("round trip");
+        assert!(
+            movie
+                .frames
+                .iter()
+                .all(|f| f.four_score && f.p3 == Buttons::A)
+        );
+        assert_eq!(movie.frames[3].command, MovieCommand::SoftReset);
+        assert_eq!(movie.frames[4].command, MovieCommand::None);
+        assert_eq!(
+            movie.frames[5].device,
+            DeviceInput::Zapper {
+                port: 1,
+                x: 45,
+                y: 100,
+                trigger: false,
+            }
+        );
+
+        // A bare emulator with no Four Score and no Zapper replays it.
+        let mut fresh = Nes::from_rom(&rom).expect("boot");
+        assert!(matches!(
+            movie.verify(&mut fresh).expect("verify runs"),
+            VerifyOutcome::Match { frames: 6, .. }
+        ));
+        assert!(fresh.four_score());
+        assert_eq!(fresh.buttons(2), Buttons::A);
+        assert!(matches!(
+            fresh.expansion_device(1),
+            Some(InputDevice::Zapper(_))
+        ));
+
+        // The extended fields are attested: editing P3 alone must not verify.
+        let mut edited = movie;
+        edited.frames[2].p3 = Buttons::B;
+        let mut fresh = Nes::from_rom(&rom).expect("boot");
+        assert!(matches!(
+            edited.verify(&mut fresh).expect("verify runs"),
+            VerifyOutcome::Mismatch { .. }
+        ));
+    }
+
+    /// A corrupt extended record decodes to something harmless: unknown
+    /// codes become `None` and the device port is masked into range.
+    #[test]
+    fn corrupt_extended_record_decodes_safely() {
+        let mut rec = [0xFFu8; EXTENDED_BYTES_PER_FRAME as usize];
+        rec[6] = 0xEE;
+        let f = FrameInput::decode(&rec);
+        assert_eq!(f.command, MovieCommand::None);
+        assert_eq!(f.device, DeviceInput::None);
+        rec[8] = 2;
+        assert!(matches!(
+            FrameInput::decode(&rec).device,
+            DeviceInput::Paddle { port: 1, .. }
+        ));
+        rec[8] = 7;
+        assert_eq!(
+            FrameInput::decode(&rec).device,
+            DeviceInput::FamilyTrainer {
+                port: 1,
+                buttons: 0x0FFF,
+            }
+        );
+    }
+
+    /// The standalone wire form round-trips every device kind, including the
+    /// mats, and decodes garbage harmlessly.
+    #[test]
+    fn device_input_wire_form_round_trips() {
+        let devices = [
+            DeviceInput::None,
+            DeviceInput::Zapper {
+                port: 0,
+                x: 255,
+                y: 239,
+                trigger: true,
+            },
+            DeviceInput::Paddle {
+                port: 1,
+                position: 0x9C,
+                fire: false,
+            },
+            DeviceInput::Mouse {
+                port: 1,
+                dx: -3,
+                dy: 127,
+                left: true,
+                right: false,
+                sensitivity: 1,
+            },
+            DeviceInput::FamilyKeyboard {
+                port: 1,
+                keys: [1, 2, 3, 4, 5, 6, 7, 8, 9],
+            },
+            DeviceInput::PowerPad {
+                port: 0,
+                buttons: 0x0801,
+            },
+            DeviceInput::FamilyTrainer {
+                port: 1,
+                buttons: 0x0030,
+            },
+        ];
+        for d in devices {
+            assert_eq!(DeviceInput::from_bytes(&d.to_bytes()), d);
+        }
+        let garbage = [0xFFu8; DEVICE_INPUT_WIRE_LEN];
+        assert_eq!(DeviceInput::from_bytes(&garbage), DeviceInput::None);
+        assert_eq!(DeviceInput::None.port(), None);
+    }
+
+    /// A device a frame can't hold, or one on each port, is reported rather
+    /// than recorded as if absent.
+    #[test]
+    fn recordable_rejects_devices_a_frame_cannot_hold() {
+        let mut nes = Nes::from_rom(&synth_nrom()).expect("parse");
+        assert_eq!(DeviceInput::recordable(&nes), Ok(()));
+        nes.set_zapper(1, 10, 20, false);
+        assert_eq!(DeviceInput::recordable(&nes), Ok(()));
+        nes.set_power_pad(0, 0);
+        assert_eq!(
+            DeviceInput::recordable(&nes),
+            Err(UnrecordableDevice::BothPorts)
+        );
+        let mut nes = Nes::from_rom(&synth_nrom()).expect("parse");
+        nes.set_hori_track(1, 0, 0, 0);
+        assert_eq!(
+            DeviceInput::recordable(&nes),
+            Err(UnrecordableDevice::Unsupported("Hori Track"))
+        );
     }
 
     #[test]
@@ -1717,6 +2690,7 @@ mod tests {
             frames: vec![],
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         let bytes = movie.serialize();
         assert!(matches!(recorded_before_v2_timebase(&bytes), Ok(false)));
@@ -17,14 +17,15 @@
 //!   import (cross-emulator save-state blobs are not portable), and a
 //!   [`StartPoint::SaveState`] [`Movie`] is rejected on export. Both surface
 //!   [`Fm2Error::Unsupported`].
-//! - **Two controllers stored.** [`FrameInput`] models players 1 and 2 only.
-//!   A `fourscore` `.fm2` (four pads) is imported by keeping pads 1 and 2 and
-//!   dropping pads 3 and 4; the fourscore flag is preserved in [`Fm2Meta`] so
-//!   the caller is not silently misled. (TODO: carry P3/P4 once `FrameInput`
-//!   grows beyond two ports.)
-//! - **Soft reset has no home on [`FrameInput`].** The per-frame command
-//!   field's `MOVIECMD_RESET` bit (value 1) is parsed without error but is
-//!   *not* applied to any frame today; see [`import_fm2`].
+//! - **Four Score.** A `fourscore` `.fm2` carries all four pads, which map
+//!   onto [`FrameInput::p3`] / [`FrameInput::p4`] with
+//!   [`FrameInput::four_score`] set (v2.5.0).
+//! - **Console commands.** The per-frame command field maps onto
+//!   [`FrameInput::command`]: reset, power, FDS insert/select and Vs. coin.
+//!   See [`import_fm2`] and [`export_fm2`] for the few sequences FCEUX's
+//!   toggle-style disk commands cannot express.
+//! - **No expansion devices.** A movie carrying [`FrameInput::device`] state
+//!   is rejected on export rather than exported without it.
 //!
 //! # The `RLDUTSBA` pad order (a classic footgun)
 //!
@@ -43,7 +44,7 @@ use core::fmt::Write as _;
 
 use crate::Region;
 use crate::controller::Buttons;
-use crate::movie::{FrameInput, Movie, StartPoint};
+use crate::movie::{DeviceInput, FrameInput, Movie, MovieCommand, StartPoint};
 use thiserror::Error;
 
 /// The only FCEUX `.fm2` format version this module understands.
@@ -56,6 +57,26 @@ const SI_GAMEPAD: u32 = 1;
 /// frame (`MOVIECMD_RESET`).
 const MOVIECMD_RESET: u32 = 1;
 
+/// FCEUX command bit: power cycle (`MOVIECMD_POWER`).
+const MOVIECMD_POWER: u32 = 2;
+
+/// FCEUX command bit: toggle the FDS disk between ejected and inserted
+/// (`MOVIECMD_FDS_INSERT`).
+const MOVIECMD_FDS_INSERT: u32 = 4;
+
+/// FCEUX command bit: step to the next FDS side while ejected
+/// (`MOVIECMD_FDS_SELECT`). FCEUX runs it after the insert toggle, so an
+/// eject and a select can share a frame.
+const MOVIECMD_FDS_SELECT: u32 = 8;
+
+/// FCEUX command bit: insert a Vs. System coin (`MOVIECMD_VS_INSERTCOIN`).
+const MOVIECMD_VS_INSERTCOIN: u32 = 16;
+
+/// Frames an imported Vs. coin stays latched before the import releases it
+/// with [`MovieCommand::ClearCoin`] — the same hold the frontend's coin key
+/// uses. FCEUX releases its coin on its own, so the `.fm2` does not say.
+const COIN_HOLD_FRAMES: usize = 3;
+
 /// The eight-character gamepad column order used by `.fm2`, paired with the
 /// [`Buttons`] flag each column drives. Index 0 is the first character of a
 /// pad field. Order is FCEUX's reversed `RLDUTSBA`.
@@ -87,9 +108,8 @@ pub struct Fm2Meta {
     /// hex-encoded). Not validated against the ROM -- the SHA-256 identity is
     /// supplied separately by the caller.
     pub rom_checksum_md5: Option<String>,
-    /// `true` if the movie declared `fourscore 1` (four controllers). When
-    /// set, only pads 1 and 2 made it into the [`Movie`]; pads 3 and 4 were
-    /// dropped (see the module docs).
+    /// `true` if the movie declared `fourscore 1` (four controllers). Every
+    /// imported frame then has [`FrameInput::four_score`] set.
     pub fourscore: bool,
     /// `true` if the movie declared `palFlag 1`.
     pub pal: bool,
@@ -148,9 +168,8 @@ pub struct Fm2ExportOpts {
     pub rom_filename: Option<String>,
     /// Value to emit for the `romChecksum` header, if any.
     pub rom_checksum_md5: Option<String>,
-    /// Emit `fourscore 1` and four pad columns per line when `true`. The
-    /// extra pads (3 and 4) are always released, since [`FrameInput`] models
-    /// only two controllers.
+    /// Emit `fourscore 1` and four pad columns per line when `true`. Also
+    /// implied by any frame with [`FrameInput::four_score`] set.
     pub fourscore: bool,
 }
 
@@ -166,19 +185,21 @@ pub struct Fm2ExportOpts {
 /// such cross-emulator save-state blobs are not portable, so a `savestate`
 /// header is rejected.
 ///
-/// # Soft reset handling
+/// # Console commands
 ///
-/// The per-frame command field's `MOVIECMD_RESET` bit (value 1) is parsed (so
-/// such lines do not error) but is **not** represented anywhere on the
-/// resulting [`Movie`], because [`FrameInput`] has no reset bit. A reset
-/// command therefore affects neither the frame count nor playback today.
+/// The per-frame command field becomes [`FrameInput::command`] (v2.5.0).
+/// Power wins over reset on the same frame. FCEUX's FDS bits are toggles, so
+/// they are tracked from its power-on state (side A inserted): insert ejects
+/// or re-inserts, select steps the side while ejected. A Vs. coin becomes
+/// [`MovieCommand::InsertCoin`] with a [`MovieCommand::ClearCoin`] a few
+/// frames later, since FCEUX releases its coin without a command.
 ///
 /// # Errors
 ///
 /// Returns [`Fm2Error`] for a missing/wrong `version`, an unparseable integer
-/// header, an unsupported device or `savestate` start point, or a malformed
-/// input-log line (bad pipes, wrong field count, wrong pad length). Never
-/// panics on malformed input.
+/// header, an unsupported device or `savestate` start point, a frame needing
+/// two console commands at once, or a malformed input-log line (bad pipes,
+/// wrong field count, wrong pad length). Never panics on malformed input.
 pub fn import_fm2(text: &str, rom_sha256: [u8; 32]) -> Result<(Movie, Fm2Meta), Fm2Error> {
     let mut meta = Fm2Meta::default();
     let mut saw_version = false;
@@ -186,6 +207,7 @@ pub fn import_fm2(text: &str, rom_sha256: [u8; 32]) -> Result<(Movie, Fm2Meta),
     let mut port1_gamepad = true;
     let mut frames: Vec<FrameInput> = Vec::new();
     let mut input_line_no = 0usize;
+    let mut disk = Fm2DiskState::default();
 
     for raw in text.lines() {
         // Trim a trailing '\r' so CRLF and LF both work; leave interior
@@ -195,7 +217,8 @@ pub fn import_fm2(text: &str, rom_sha256: [u8; 32]) -> Result<(Movie, Fm2Meta),
         if line.starts_with('|') {
             // Input-log line.
             input_line_no += 1;
-            let input = parse_input_line(line, input_line_no, meta.fourscore)?;
+            let (mut input, cmd) = parse_input_line(line, input_line_no, meta.fourscore)?;
+            input.command = disk.command(cmd, input_line_no)?;
             frames.push(input);
             continue;
         }
@@ -266,6 +289,7 @@ pub fn import_fm2(text: &str, rom_sha256: [u8; 32]) -> Result<(Movie, Fm2Meta),
         ));
     }
 
+    release_coins(&mut frames);
     let movie = Movie {
         region: if meta.pal { Region::Pal } else { Region::Ntsc },
         rom_sha256,
@@ -276,6 +300,7 @@ pub fn import_fm2(text: &str, rom_sha256: [u8; 32]) -> Result<(Movie, Fm2Meta),
         // Imported: no attestation (the source format has no such field, and
         // synthesizing one would attest a run this build never performed).
         attestation: None,
+        match_info: None,
     };
     Ok((movie, meta))
 }
@@ -290,18 +315,32 @@ pub fn import_fm2(text: &str, rom_sha256: [u8; 32]) -> Result<(Movie, Fm2Meta),
 /// (one line per frame, with a trailing empty `port2` field per the spec).
 ///
 /// Only [`StartPoint::PowerOn`] movies export; a [`StartPoint::SaveState`]
-/// movie has no portable `.fm2` representation.
+/// movie has no portable `.fm2` representation. Four Score frames export as
+/// a `fourscore 1` movie and console commands as the `c` field bits.
 ///
 /// # Errors
 ///
 /// Returns [`Fm2Error::Unsupported`] if `movie` is anchored to an embedded
-/// save state.
+/// save state, carries expansion-device input, or has a console command
+/// FCEUX cannot express: coin acceptor #2, or a disk swap without an eject
+/// frame before it or that skips a side.
 pub fn export_fm2(movie: &Movie, opts: &Fm2ExportOpts) -> Result<String, Fm2Error> {
     if !matches!(movie.start, StartPoint::PowerOn) {
         return Err(Fm2Error::Unsupported(
             "save-state-anchored movie has no portable .fm2 representation",
         ));
     }
+    if movie
+        .frames
+        .iter()
+        .any(|f| !matches!(f.device, DeviceInput::None))
+    {
+        return Err(Fm2Error::Unsupported(
+            "expansion-device input has no .fm2 encoding here",
+        ));
+    }
+    let commands = export_commands(&movie.frames)?;
+    let fourscore = opts.fourscore || movie.frames.iter().any(|f| f.four_score);
 
     let pal = matches!(movie.region, Region::Pal | Region::Dendy);
     let mut out = String::new();
@@ -313,7 +352,7 @@ pub fn export_fm2(movie: &Movie, opts: &Fm2ExportOpts) -> Result<String, Fm2Erro
     let _ = writeln!(out, "emuVersion {}", emu_version_tag());
     let _ = writeln!(out, "rerecordCount {}", opts.rerecord_count);
     let _ = writeln!(out, "palFlag {}", u8::from(pal));
-    let _ = writeln!(out, "fourscore {}", u8::from(opts.fourscore));
+    let _ = writeln!(out, "fourscore {}", u8::from(fourscore));
     let _ = writeln!(out, "port0 {SI_GAMEPAD}");
     let _ = writeln!(out, "port1 {SI_GAMEPAD}");
     out.push_str("port2 0\n");
@@ -327,25 +366,22 @@ pub fn export_fm2(movie: &Movie, opts: &Fm2ExportOpts) -> Result<String, Fm2Erro
         let _ = writeln!(out, "comment author {author}");
     }
 
-    // Input log: one line per frame. RustyNES movies never carry a per-frame
-    // reset command, so field `c` is always 0.
+    // Input log: one line per frame, field `c` first.
     let mut pad = [0u8; 8];
-    for frame in &movie.frames {
-        out.push_str("|0|");
+    for (frame, cmd) in movie.frames.iter().zip(commands) {
+        let _ = write!(out, "|{cmd}|");
         write_pad(frame.p1, &mut pad);
         out.push_str(core::str::from_utf8(&pad).expect("pad bytes are ASCII"));
         out.push('|');
         write_pad(frame.p2, &mut pad);
         out.push_str(core::str::from_utf8(&pad).expect("pad bytes are ASCII"));
         out.push('|');
-        if opts.fourscore {
-            // Players 3 and 4 are always released (FrameInput has no P3/P4).
-            write_pad(Buttons::empty(), &mut pad);
-            let empty = core::str::from_utf8(&pad).expect("pad bytes are ASCII");
-            out.push_str(empty);
-            out.push('|');
-            out.push_str(empty);
-            out.push('|');
+        if fourscore {
+            for buttons in [frame.p3, frame.p4] {
+                write_pad(buttons, &mut pad);
+                out.push_str(core::str::from_utf8(&pad).expect("pad bytes are ASCII"));
+                out.push('|');
+            }
         }
         // Trailing empty `port2` field (SIFC_NONE is always empty).
         out.push_str("|\n");
@@ -368,10 +404,129 @@ fn write_pad(buttons: Buttons, out: &mut [u8; 8]) {
     }
 }
 
+/// Translate each frame's [`MovieCommand`] into an FCEUX `c` field value.
+fn export_commands(frames: &[FrameInput]) -> Result<Vec<u32>, Fm2Error> {
+    let mut out = alloc::vec![0u32; frames.len()];
+    // FCEUX powers on with side A inserted.
+    let mut side = 0u8;
+    let mut ejected_at: Option<usize> = None;
+    for (i, frame) in frames.iter().enumerate() {
+        out[i] = match frame.command {
+            MovieCommand::None | MovieCommand::ClearCoin => 0,
+            MovieCommand::SoftReset => MOVIECMD_RESET,
+            MovieCommand::PowerCycle => MOVIECMD_POWER,
+            MovieCommand::EjectDisk if ejected_at.is_some() => 0,
+            MovieCommand::EjectDisk => {
+                ejected_at = Some(i);
+                MOVIECMD_FDS_INSERT
+            }
+            MovieCommand::InsertDisk(n) => {
+                let Some(at) = ejected_at.take() else {
+                    if n == side {
+                        continue;
+                    }
+                    return Err(Fm2Error::Unsupported(
+                        "a disk swap with no eject frame before it has no .fm2 encoding",
+                    ));
+                };
+                if n == side.wrapping_add(1) {
+                    // The select rides on the eject frame, after the toggle.
+                    out[at] |= MOVIECMD_FDS_SELECT;
+                    side = n;
+                } else if n != side {
+                    return Err(Fm2Error::Unsupported(
+                        "a disk swap that skips a side has no .fm2 encoding",
+                    ));
+                }
+                MOVIECMD_FDS_INSERT
+            }
+            MovieCommand::InsertCoin(0) => MOVIECMD_VS_INSERTCOIN,
+            MovieCommand::InsertCoin(_) => {
+                return Err(Fm2Error::Unsupported(
+                    "coin acceptor #2 has no .fm2 encoding",
+                ));
+            }
+        };
+    }
+    Ok(out)
+}
+
+/// FCEUX's toggle-style FDS state, tracked across an import so each `c`
+/// field can become an absolute [`MovieCommand`].
+#[derive(Default)]
+struct Fm2DiskState {
+    /// `true` while the disk is out.
+    ejected: bool,
+    /// The side FCEUX has selected.
+    side: u8,
+}
+
+impl Fm2DiskState {
+    /// The [`MovieCommand`] for one frame's `c` value.
+    fn command(&mut self, cmd: u32, line_no: usize) -> Result<MovieCommand, Fm2Error> {
+        let system = if cmd & MOVIECMD_POWER != 0 {
+            MovieCommand::PowerCycle
+        } else if cmd & MOVIECMD_RESET != 0 {
+            MovieCommand::SoftReset
+        } else {
+            MovieCommand::None
+        };
+        let disk = if cmd & MOVIECMD_FDS_INSERT == 0 {
+            MovieCommand::None
+        } else {
+            self.ejected = !self.ejected;
+            if self.ejected {
+                MovieCommand::EjectDisk
+            } else {
+                MovieCommand::InsertDisk(self.side)
+            }
+        };
+        if cmd & MOVIECMD_FDS_SELECT != 0 && self.ejected {
+            self.side = self.side.wrapping_add(1);
+        }
+        let coin = if cmd & MOVIECMD_VS_INSERTCOIN != 0 {
+            MovieCommand::InsertCoin(0)
+        } else {
+            MovieCommand::None
+        };
+        let mut picked = MovieCommand::None;
+        for c in [system, disk, coin] {
+            if c == MovieCommand::None {
+                continue;
+            }
+            if picked != MovieCommand::None {
+                return Err(Fm2Error::Malformed {
+                    line: line_no,
+                    reason: "more than one console command on one frame",
+                });
+            }
+            picked = c;
+        }
+        Ok(picked)
+    }
+}
+
+/// Follow every imported coin with a [`MovieCommand::ClearCoin`]
+/// [`COIN_HOLD_FRAMES`] later, when that frame is free.
+fn release_coins(frames: &mut [FrameInput]) {
+    for i in 0..frames.len() {
+        if matches!(frames[i].command, MovieCommand::InsertCoin(_))
+            && let Some(later) = frames.get_mut(i + COIN_HOLD_FRAMES)
+            && matches!(later.command, MovieCommand::None)
+        {
+            later.command = MovieCommand::ClearCoin;
+        }
+    }
+}
+
 /// Parse a single input-log line (already known to start with `|`) into a
-/// [`FrameInput`]. `line_no` is the 1-based input-log line number used in
-/// errors; `fourscore` selects the 4-pad layout.
-fn parse_input_line(line: &str, line_no: usize, fourscore: bool) -> Result<FrameInput, Fm2Error> {
+/// [`FrameInput`] plus its raw `c` field. `line_no` is the 1-based input-log
+/// line number used in errors; `fourscore` selects the 4-pad layout.
+fn parse_input_line(
+    line: &str,
+    line_no: usize,
+    fourscore: bool,
+) -> Result<(FrameInput, u32), Fm2Error> {
     if !line.ends_with('|') {
         return Err(Fm2Error::Malformed {
             line: line_no,
@@ -394,7 +549,7 @@ fn parse_input_line(line: &str, line_no: usize, fourscore: bool) -> Result<Frame
         line: line_no,
         reason: "missing command field",
     })?;
-    let _cmd = parse_command(cmd_field, line_no)?;
+    let cmd = parse_command(cmd_field, line_no)?;
 
     let pad_count = if fourscore { 4 } else { 2 };
     let mut pads = [Buttons::empty(); 4];
@@ -418,13 +573,17 @@ fn parse_input_line(line: &str, line_no: usize, fourscore: bool) -> Result<Frame
         }
     }
 
-    // pads[0] = P1, pads[1] = P2 (pads 2/3 dropped for fourscore).
-    Ok(FrameInput::new(pads[0], pads[1]))
+    let mut input = FrameInput::new(pads[0], pads[1]);
+    if fourscore {
+        input.p3 = pads[2];
+        input.p4 = pads[3];
+        input.four_score = true;
+    }
+    Ok((input, cmd))
 }
 
-/// Parse the variable-length decimal command bitfield. Returns whether the
-/// reset bit was set (currently informational only).
-fn parse_command(field: &str, line_no: usize) -> Result<bool, Fm2Error> {
+/// Parse the variable-length decimal command bitfield.
+fn parse_command(field: &str, line_no: usize) -> Result<u32, Fm2Error> {
     // The command field is conventionally empty or a small decimal integer.
     let value: u32 = if field.is_empty() {
         0
@@ -434,7 +593,7 @@ fn parse_command(field: &str, line_no: usize) -> Result<bool, Fm2Error> {
             reason: "command field is not a decimal integer",
         })?
     };
-    Ok(value & MOVIECMD_RESET != 0)
+    Ok(value)
 }
 
 /// Parse one eight-character `RLDUTSBA` gamepad field into [`Buttons`].
@@ -508,6 +667,7 @@ mod tests {
             frames: varied_frames(),
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         let opts = Fm2ExportOpts {
             rerecord_count: 42,
@@ -544,6 +704,7 @@ mod tests {
             ],
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         let text = export_fm2(&movie, &Fm2ExportOpts::default()).expect("export");
         // Pull the two input-log lines.
@@ -596,6 +757,7 @@ mod tests {
             frames: vec![FrameInput::new(Buttons::empty(), Buttons::empty())],
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         let out = export_fm2(&pal_movie, &Fm2ExportOpts::default()).expect("export");
         assert!(
@@ -613,19 +775,87 @@ mod tests {
     }
 
     #[test]
-    fn reset_command_parses_without_error() {
-        // c = 1 means MOVIECMD_RESET. We parse it (don't crash) but it is not
-        // represented on FrameInput, so the frame is otherwise a normal frame.
-        let text = "version 3\nport0 1\nport1 1\nport2 0\n|1|........|........||\n";
-        let (movie, _) = import_fm2(text, TEST_SHA).expect("reset command must parse");
-        assert_eq!(movie.frames.len(), 1);
+    fn reset_and_power_commands_import() {
+        // c = 1 is MOVIECMD_RESET, c = 2 MOVIECMD_POWER; power wins when both
+        // are set.
+        let text = "version 3\nport0 1\nport1 1\nport2 0\n\
+                    |1|........|........||\n|3|........|........||\n";
+        let (movie, _) = import_fm2(text, TEST_SHA).expect("commands must parse");
+        assert_eq!(movie.frames.len(), 2);
+        assert_eq!(movie.frames[0].command, MovieCommand::SoftReset);
+        assert_eq!(movie.frames[1].command, MovieCommand::PowerCycle);
         assert_eq!(movie.frames[0].p1, Buttons::empty());
     }
 
     #[test]
-    fn fourscore_layout_parses_two_of_four_pads() {
-        // Four pad fields; only P1/P2 are retained. P1 = A, P2 = B, P3/P4 set
-        // (and dropped). fourscore must survive in meta.
+    fn console_commands_round_trip() {
+        let mut frames = vec![FrameInput::default(); 12];
+        frames[1].command = MovieCommand::SoftReset;
+        frames[2].command = MovieCommand::EjectDisk;
+        frames[5].command = MovieCommand::InsertDisk(1);
+        frames[7].command = MovieCommand::InsertCoin(0);
+        frames[10].command = MovieCommand::ClearCoin;
+        let movie = Movie {
+            region: Region::Ntsc,
+            rom_sha256: TEST_SHA,
+            start: StartPoint::PowerOn,
+            frames,
+            rerecord_count: 0,
+            attestation: None,
+            match_info: None,
+        };
+        let text = export_fm2(&movie, &Fm2ExportOpts::default()).expect("export");
+        let cmds: Vec<&str> = text
+            .lines()
+            .filter(|l| l.starts_with('|'))
+            .map(|l| l.split('|').nth(1).unwrap())
+            .collect();
+        // The eject frame carries the side select (4 | 8).
+        assert_eq!(cmds[1], "1");
+        assert_eq!(cmds[2], "12");
+        assert_eq!(cmds[5], "4");
+        assert_eq!(cmds[7], "16");
+        assert_eq!(cmds[10], "0", "FCEUX releases its coin on its own");
+        let (back, _) = import_fm2(&text, TEST_SHA).expect("import");
+        assert_eq!(back.frames, movie.frames);
+    }
+
+    #[test]
+    fn inexpressible_commands_and_devices_are_rejected_on_export() {
+        let mut movie = Movie {
+            region: Region::Ntsc,
+            rom_sha256: TEST_SHA,
+            start: StartPoint::PowerOn,
+            frames: vec![FrameInput::default(); 2],
+            rerecord_count: 0,
+            attestation: None,
+            match_info: None,
+        };
+        // A side swap with no eject frame before it.
+        movie.frames[1].command = MovieCommand::InsertDisk(1);
+        assert!(matches!(
+            export_fm2(&movie, &Fm2ExportOpts::default()),
+            Err(Fm2Error::Unsupported(_))
+        ));
+        movie.frames[1].command = MovieCommand::InsertCoin(1);
+        assert!(matches!(
+            export_fm2(&movie, &Fm2ExportOpts::default()),
+            Err(Fm2Error::Unsupported(_))
+        ));
+        movie.frames[1].command = MovieCommand::None;
+        movie.frames[0].device = DeviceInput::Paddle {
+            port: 1,
+            position: 0x80,
+            fire: false,
+        };
+        assert!(matches!(
+            export_fm2(&movie, &Fm2ExportOpts::default()),
+            Err(Fm2Error::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn fourscore_layout_keeps_all_four_pads() {
         let text = "version 3\nfourscore 1\nport0 1\nport1 1\nport2 0\n\
                     |0|.......A|......B.|R.......|.L......||\n";
         let (movie, meta) = import_fm2(text, TEST_SHA).expect("fourscore import");
@@ -633,21 +863,20 @@ mod tests {
         assert_eq!(movie.frames.len(), 1);
         assert_eq!(movie.frames[0].p1, Buttons::A);
         assert_eq!(movie.frames[0].p2, Buttons::B);
+        assert_eq!(movie.frames[0].p3, Buttons::RIGHT);
+        assert_eq!(movie.frames[0].p4, Buttons::LEFT);
+        assert!(movie.frames[0].four_score);
 
-        // Export with fourscore emits four pad fields.
-        let out = export_fm2(
-            &movie,
-            &Fm2ExportOpts {
-                fourscore: true,
-                ..Default::default()
-            },
-        )
-        .expect("export");
+        // The Four Score frames alone make the export a fourscore movie.
+        let out = export_fm2(&movie, &Fm2ExportOpts::default()).expect("export");
+        assert!(out.lines().any(|l| l == "fourscore 1"));
         let log_line = out.lines().find(|l| l.starts_with('|')).unwrap();
         // |0|p1|p2|p3|p4||  -> split has ["",0,p1,p2,p3,p4,"",""]; four of the
         // fields are 8-char pads.
         let pad_count = log_line.split('|').filter(|p| p.len() == 8).count();
         assert_eq!(pad_count, 4, "fourscore export must emit four pad fields");
+        let (back, _) = import_fm2(&out, TEST_SHA).expect("re-import");
+        assert_eq!(back.frames, movie.frames);
     }
 
     #[test]
@@ -746,6 +975,7 @@ mod tests {
             frames: vec![],
             rerecord_count: 0,
             attestation: None,
+            match_info: None,
         };
         assert!(matches!(
             export_fm2(&movie, &Fm2ExportOpts::default()),
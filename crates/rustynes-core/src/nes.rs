@@ -22,7 +22,7 @@ use crate::bus::LockstepBus;
 use crate::controller::Buttons;
 use crate::debug::{ApuDebugView, CpuDebugView, MapperDebugView, PpuDebugView};
 use crate::genie::{GenieCode, GenieError};
-use crate::input_device::InputDevice;
+use crate::input_device::{InputDevice, TurboFileImage, TurboFileState};
 use crate::rewind::{REWIND_DEFAULT_KEYFRAME_PERIOD, REWIND_DEFAULT_MAX_BYTES, RewindRing};
 use crate::save_state::{self, ROM_HASH_TAG_LEN, SnapshotError};
 
@@ -357,6 +357,19 @@ impl Nes {
         Self::from_disk_with_sample_rate(disk_bytes, bios_bytes, crate::bus::DEFAULT_SAMPLE_RATE)
     }
 
+    /// Build an FDS emulator that boots without a `disksys.rom`, using the
+    /// clean-room high-level-emulated BIOS ([`rustynes_mappers::fds_hle_bios`]).
+    /// The boot sequence skips the logo animation and disk API calls complete
+    /// instantly; see [`Self::fds_hle_boot_error`] /
+    /// [`Self::fds_hle_unsupported_calls`] for why a title fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying [`RomError`] if the disk image is unparseable.
+    pub fn from_disk_hle(disk_bytes: &[u8], sample_rate: u32) -> Result<Self, RomError> {
+        Self::from_disk_with_sample_rate(disk_bytes, &rustynes_mappers::fds_hle_bios(), sample_rate)
+    }
+
     /// Build an FDS emulator with an explicit audio sample rate. See
     /// [`Nes::from_disk`].
     ///
@@ -583,6 +596,16 @@ impl Nes {
         // VBL detection or DMA-stall heavy frames before declaring "stuck".
         const MAX_CYCLES_PER_FRAME: u64 = 150_000;
         let start = self.bus.cycle();
+        // v2.5.0 — R.O.B.'s gyros hold controller II's A/B for the frame. The
+        // player's own buttons are put back afterwards, so input latched by
+        // the frontend (and recorded to a movie) is never mixed with them.
+        let rob_user_buttons = if self.bus.rob().is_some() {
+            let user = self.bus.controller(1).buttons();
+            self.bus.set_buttons(1, user | self.bus.rob_pressed());
+            Some(user)
+        } else {
+            None
+        };
         // v2.3.7 "Overtone" — anchor this frame's mix trace. The trace is
         // per-frame (the index IS the cycle offset from here); the REGISTER
         // attribution deliberately is not, because "which instruction last wrote
@@ -705,6 +728,16 @@ impl Nes {
         // frame. This is a no-op (and the run loop above is byte-identical)
         // when no Zapper is attached, so the determinism contract holds.
         self.bus.sample_zapper_light();
+        // R.O.B. watches the same completed frame for its flashed commands.
+        if let Some(user) = rob_user_buttons {
+            self.bus.set_buttons(1, user);
+            self.bus.sample_rob();
+        }
+        // The 3D glasses' eye for the frame just drawn.
+        self.bus.latch_famicom_3d_eye();
+        // Keep an inserted cassette's transport position current for the UI
+        // (reads compute the level lazily, so this only matters for display).
+        self.bus.sync_data_recorder();
         // After the frame completes, push state into the rewind ring so
         // the frontend's hold-F5 UX has somewhere to walk back from.
         // v2.8.0 Phase 3 — run-ahead suppresses the capture for its hidden
@@ -1345,8 +1378,7 @@ impl Nes {
     /// state.
     ///
     /// Used by the TAS movie recorder (`crate::movie`) to capture the inputs
-    /// applied before each [`Self::run_frame`]. (Movies record players 1 & 2;
-    /// Four Score players 3/4 are not part of the `.rnm` stream.)
+    /// applied before each [`Self::run_frame`] — all four ports since v2.5.0.
     ///
     /// # Panics
     ///
@@ -1485,6 +1517,26 @@ impl Nes {
         self.bus.disk_image_bytes()
     }
 
+    /// Re-serialize the FDS disk image to the raw QD layout: 65536-byte sides
+    /// with every block followed by its stored CRC-16, so a written disk (or
+    /// one whose copy protection relies on a bad CRC) exports its physical
+    /// medium losslessly. Loads back through [`Self::from_disk`]. Empty for
+    /// cartridge builds.
+    #[must_use]
+    pub fn disk_image_qd_bytes(&self) -> Vec<u8> {
+        self.bus.disk_image_qd_bytes()
+    }
+
+    /// Swap the inserted FDS disk for the image in `bytes` (`.fds` or the
+    /// lossless QD layout of [`Self::disk_image_qd_bytes`]) without rebuilding
+    /// the emulator. The inserted side stays inserted if the new image has it,
+    /// and the image starts clean. [`Self::rom_sha256`] is unchanged. Used to
+    /// run a netplay session on a shared copy of the disk. Returns `false`,
+    /// leaving the disk alone, for cartridge builds or unparseable bytes.
+    pub fn replace_disk_image(&mut self, bytes: &[u8]) -> bool {
+        self.bus.replace_disk_image(bytes)
+    }
+
     /// Whether the FDS disk image has unsaved writes since the last
     /// [`Self::clear_disk_dirty`]. A frontend checks this on quit / periodically
     /// to decide whether to persist the disk.
@@ -1505,6 +1557,29 @@ impl Nes {
         self.bus.set_disk_write_protected(protected);
     }
 
+    /// Whether this FDS build runs the high-level-emulated BIOS
+    /// ([`Self::from_disk_hle`]) rather than a `disksys.rom` dump.
+    #[must_use]
+    pub fn fds_hle_active(&self) -> bool {
+        self.bus.fds_hle_active()
+    }
+
+    /// The BIOS error code the HLE boot sequence stopped on (`$20` = license
+    /// screen file missing, `$04-$0A` = disk id mismatch, ...), or `None` while
+    /// booting normally. Always `None` with a real BIOS.
+    #[must_use]
+    pub fn fds_hle_boot_error(&self) -> Option<u8> {
+        self.bus.fds_hle_boot_error()
+    }
+
+    /// Documented BIOS entry points the game called that the HLE BIOS does not
+    /// implement, in first-call order. A non-empty list usually explains a
+    /// title that boots but misbehaves.
+    #[must_use]
+    pub fn fds_hle_unsupported_calls(&self) -> Vec<u16> {
+        self.bus.fds_hle_unsupported_calls()
+    }
+
     /// Attach a non-standard overlay input device on `port` (0 = `$4016`, 1 =
     /// `$4017`). Pass `None` to unplug it and return the port to the standard
     /// controller / Four Score path (byte-identical reads). Devices are
@@ -1610,6 +1685,75 @@ impl Nes {
         self.bus.set_microphone(pressed);
     }
 
+    /// v2.5.0 — drive the Famicom microphone from a per-frame comparator
+    /// **pattern**: bit `i` (LSB first) is the `$4016.D2` level during the
+    /// `i`-th eighth of the frame, and each `$4016` read samples the bit for
+    /// the scanline it lands on. A captured voice thresholded on the host maps
+    /// onto this; a held key is `0xFF` ([`Self::set_microphone`]). The pattern
+    /// is what `.rnm` movies record, so a captured-mic run replays exactly.
+    pub const fn set_microphone_pattern(&mut self, pattern: u8) {
+        self.bus.set_microphone_pattern(pattern);
+    }
+
+    /// The microphone pattern latched for the current frame.
+    #[must_use]
+    pub const fn microphone_pattern(&self) -> u8 {
+        self.bus.microphone_pattern()
+    }
+
+    /// v2.5.0 — insert (`Some`) or eject (`None`) a Family BASIC **Data
+    /// Recorder** cassette. While inserted, `$4016` writes drive the deck's
+    /// output line and `$4016` reads see the played-back level on D1; transport
+    /// is controlled through [`Self::data_recorder_mut`]. Opt-in: with no tape
+    /// the `$4016` path stays byte-identical.
+    pub fn set_data_recorder(&mut self, tape: Option<crate::DataRecorder>) {
+        self.bus.set_data_recorder(tape);
+    }
+
+    /// The inserted Data Recorder cassette, if any.
+    #[must_use]
+    pub const fn data_recorder(&self) -> Option<&crate::DataRecorder> {
+        self.bus.data_recorder()
+    }
+
+    /// Mutable access to the inserted cassette (play / record / stop /
+    /// rewind), caught up to the current cycle first.
+    pub fn data_recorder_mut(&mut self) -> Option<&mut crate::DataRecorder> {
+        self.bus.data_recorder_mut()
+    }
+
+    /// v2.5.0 — plug in (`true`) or unplug the **Famicom 3D System** shutter
+    /// glasses. While plugged in, each frame's `$4016` D1 level is latched as
+    /// the eye it was drawn for ([`Self::stereo_eye`]); the framebuffer itself
+    /// is unchanged, so pairing and presenting the eyes is up to the frontend.
+    pub const fn set_famicom_3d(&mut self, enabled: bool) {
+        self.bus.set_famicom_3d(enabled);
+    }
+
+    /// The eye the last completed frame was drawn for, or `None` with no 3D
+    /// glasses attached.
+    #[must_use]
+    pub const fn stereo_eye(&self) -> Option<crate::StereoEye> {
+        match self.bus.famicom_3d() {
+            Some(glasses) => Some(glasses.eye()),
+            None => None,
+        }
+    }
+
+    /// v2.5.0 — set up (`Some`) or remove (`None`) **R.O.B.** for *Gyromite* /
+    /// *Stack-Up*. The robot decodes the commands the game flashes on screen
+    /// and, in Gyromite, presses controller II's A/B through its gyros.
+    /// Opt-in: with no robot the frame loop is byte-identical.
+    pub const fn set_rob(&mut self, rob: Option<crate::Rob>) {
+        self.bus.set_rob(rob);
+    }
+
+    /// The attached R.O.B., if any.
+    #[must_use]
+    pub const fn rob(&self) -> Option<&crate::Rob> {
+        self.bus.rob()
+    }
+
     /// Attach an NES Power Pad / Family Fun Fitness mat on `port` (typically
     /// port 1 / `$4017`) and set its live button mask (bit `i` = mat button
     /// `i+1`, 0..=11). Convenience wrapper that attaches the device if absent
@@ -1803,6 +1947,170 @@ impl Nes {
         self.bus.set_bandai_hyper_shot(port, sensors);
     }
 
+    /// v2.5.0 — attach a Bandai **Oeka Kids** drawing tablet on `port` and set
+    /// its pen: position in NES screen pixels (`u16::MAX` = off the tablet),
+    /// tip contact and barrel button. Used by the Oeka Kids titles on mapper
+    /// 96. Opt-in: the no-device path stays byte-identical.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in `0..=1`.
+    pub fn set_oeka_kids_tablet(&mut self, port: usize, x: u16, y: u16, touch: bool, button: bool) {
+        if !matches!(
+            self.bus.expansion_device(port),
+            Some(InputDevice::OekaKidsTablet(_))
+        ) {
+            self.bus.set_expansion_device(
+                port,
+                Some(InputDevice::OekaKidsTablet(
+                    crate::input_device::OekaKidsTabletState::new(),
+                )),
+            );
+        }
+        self.bus.set_oeka_kids_tablet(port, x, y, touch, button);
+    }
+
+    /// v2.5.0 — attach a Hori **Track** trackball on `port` and set this
+    /// frame's ball motion (positive = right / down) and pad buttons
+    /// ([`Buttons`] bits). Opt-in: the no-device path stays byte-identical.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in `0..=1`.
+    pub fn set_hori_track(&mut self, port: usize, dx: i16, dy: i16, buttons: u8) {
+        if !matches!(
+            self.bus.expansion_device(port),
+            Some(InputDevice::HoriTrack(_))
+        ) {
+            self.bus.set_expansion_device(
+                port,
+                Some(InputDevice::HoriTrack(
+                    crate::input_device::HoriTrackState::new(),
+                )),
+            );
+        }
+        self.bus.set_hori_track(port, dx, dy, buttons);
+    }
+
+    /// v2.5.0 — attach a Coconuts **Pachinko** controller on `port` and set its
+    /// pad buttons ([`Buttons`] bits) and plunger travel (`0` = at rest ..
+    /// `255` = fully pulled). Opt-in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in `0..=1`.
+    pub fn set_pachinko(&mut self, port: usize, buttons: u8, plunger: u8) {
+        if !matches!(
+            self.bus.expansion_device(port),
+            Some(InputDevice::Pachinko(_))
+        ) {
+            self.bus.set_expansion_device(
+                port,
+                Some(InputDevice::Pachinko(
+                    crate::input_device::PachinkoState::new(),
+                )),
+            );
+        }
+        self.bus.set_pachinko(port, buttons, plunger);
+    }
+
+    /// v2.5.0 — attach a Bandai **Top Rider** bike on `port` and set its
+    /// handlebars (`-3..=3`), throttle (`0..=3`) and `TOP_RIDER_*` switches.
+    /// Opt-in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in `0..=1`.
+    pub fn set_top_rider(&mut self, port: usize, steer: i8, throttle: u8, keys: u8) {
+        if !matches!(
+            self.bus.expansion_device(port),
+            Some(InputDevice::TopRider(_))
+        ) {
+            self.bus.set_expansion_device(
+                port,
+                Some(InputDevice::TopRider(
+                    crate::input_device::TopRiderState::new(),
+                )),
+            );
+        }
+        self.bus.set_top_rider(port, steer, throttle, keys);
+    }
+
+    /// v2.5.0 — attach a Mattel **Power Glove** on `port` and set the tracked
+    /// hand and gesture program. A freshly attached glove starts in joypad
+    /// mode; the game switches it to hi-res packets itself. Opt-in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in `0..=1`.
+    pub fn set_power_glove(
+        &mut self,
+        port: usize,
+        hand: crate::input_device::PowerGloveHand,
+        program: crate::input_device::PowerGloveProgram,
+    ) {
+        if !matches!(
+            self.bus.expansion_device(port),
+            Some(InputDevice::PowerGlove(_))
+        ) {
+            self.bus.set_expansion_device(
+                port,
+                Some(InputDevice::PowerGlove(
+                    crate::input_device::PowerGloveState::new(program),
+                )),
+            );
+        }
+        self.bus.set_power_glove(port, hand, program);
+    }
+
+    /// v2.5.0 — plug an ASCII **Turbo File** (or Twin) into `port` with its
+    /// bank switch at `bank`, or just flip the switch if one is already
+    /// there. The data lives in the image inserted with
+    /// [`Self::set_turbo_file_image`]; with none, the port reads 0. Opt-in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in `0..=1`.
+    pub fn set_turbo_file(&mut self, port: usize, bank: u8) {
+        if self.bus.turbo_file(port).is_none() {
+            self.bus
+                .set_expansion_device(port, Some(InputDevice::TurboFile(TurboFileState::new())));
+        }
+        self.bus.set_turbo_file_bank(port, bank);
+    }
+
+    /// The Turbo File attached on `port`, if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not in `0..=1`.
+    #[must_use]
+    pub const fn turbo_file(&self, port: usize) -> Option<&TurboFileState> {
+        self.bus.turbo_file(port)
+    }
+
+    /// v2.5.0 — insert (`Some`) or eject (`None`) the Turbo File image. The
+    /// frontend owns its persistence: build it with
+    /// [`TurboFileImage::from_storage`] and write [`TurboFileImage::storage`]
+    /// back when it is dirty. The image survives a power-cycle and travels
+    /// in every snapshot, like cartridge PRG-RAM, so a rewind or a netplay
+    /// rollback undoes the writes it rolls past.
+    pub fn set_turbo_file_image(&mut self, image: Option<TurboFileImage>) {
+        self.bus.set_turbo_file_image(image);
+    }
+
+    /// The inserted Turbo File image, if any.
+    #[must_use]
+    pub const fn turbo_file_image(&self) -> Option<&TurboFileImage> {
+        self.bus.turbo_file_image()
+    }
+
+    /// Mutable access to the inserted Turbo File image (clearing the dirty
+    /// flag after a flush).
+    pub const fn turbo_file_image_mut(&mut self) -> Option<&mut TurboFileImage> {
+        self.bus.turbo_file_image_mut()
+    }
+
     /// v1.1.0 beta.1 (T-110-B4) — set (`Some`) or clear (`None`) a per-game
     /// **nametable mirroring override**, a load-time correction for ROMs whose
     /// iNES header carries the wrong mirroring flag (supplied by the frontend's
@@ -1814,6 +2122,13 @@ impl Nes {
         self.bus.set_mirroring_override(m);
     }
 
+    /// The active nametable mirroring override (`None` = the mapper decides).
+    /// Read by netplay's settings negotiation.
+    #[must_use]
+    pub const fn mirroring_override(&self) -> Option<rustynes_mappers::Mirroring> {
+        self.bus.mirroring_override()
+    }
+
     /// Whether the loaded mapper's nametable mirroring is **hardwired** by the
     /// cartridge (solder pads / header bit) rather than controlled by the
     /// mapper's own registers at runtime.
@@ -4629,4 +4944,44 @@ mod tests {
             "the fresh instance must count its own restores, not inherit a stored value"
         );
     }
+
+    /// v2.5.0 — a Turbo File write rolled back by a core snapshot (the
+    /// netplay rollback and run-ahead path) is undone, so re-simulating the
+    /// frame writes it again rather than seeing it already there.
+    #[test]
+    fn turbo_file_writes_roll_back_with_core_snapshots() {
+        use crate::input_device::TurboFileImage;
+        let mut rom = synth_nrom(16, 8);
+        // $C000: rewind the Turbo File, clock a 1 into bit 0, then spin.
+        let program = [
+            0xA9, 0x00, 0x8D, 0x16, 0x40, // LDA #$00 ; STA $4016
+            0xA9, 0x07, 0x8D, 0x16, 0x40, // LDA #$07 ; STA $4016
+            0xA9, 0x03, 0x8D, 0x16, 0x40, // LDA #$03 ; STA $4016
+            0x4C, 0x0F, 0xC0, // JMP $C00F
+        ];
+        rom[16..16 + program.len()].copy_from_slice(&program);
+        let mut nes = Nes::from_rom(&rom).unwrap();
+        nes.set_turbo_file(1, 0);
+        nes.set_turbo_file_image(Some(TurboFileImage::new()));
+        // The power-on frame ends before the program starts.
+        nes.run_frame();
+        assert_eq!(nes.turbo_file_image().unwrap().storage()[0], 0x00);
+        let mut core = Vec::new();
+        nes.snapshot_core_into(&mut core);
+        let mut slim = Vec::new();
+        nes.snapshot_core_into_slim(&mut slim);
+
+        nes.run_frame();
+        assert_eq!(nes.turbo_file_image().unwrap().storage()[0], 0x01);
+        nes.turbo_file_image_mut().unwrap().clear_dirty();
+
+        for snap in [&core, &slim] {
+            nes.restore_quiet(snap).unwrap();
+            let image = nes.turbo_file_image().unwrap();
+            assert_eq!(image.storage()[0], 0x00, "the write is rolled back");
+            assert!(image.is_dirty(), "the undo is due a flush");
+            nes.run_frame();
+            assert_eq!(nes.turbo_file_image().unwrap().storage()[0], 0x01);
+        }
+    }
 }
@@ -0,0 +1,495 @@
+//! **R.O.B.** (Robotic Operating Buddy, HVC-012 / NES-012) simulation.
+//!
+//! R.O.B. has no cable: it watches the television through a photodiode in
+//! its head and obeys commands the game sends as full-screen flashes, one
+//! bit per frame (a bright frame is `1`, a dark frame `0`). Per the `NESdev`
+//! "R.O.B." page a command is 13 frames long — the 5-bit lead-in `00010`
+//! followed by an 8-bit opcode — so the robot keeps a 13-bit shift register
+//! and acts whenever its top five bits match the lead-in.
+//!
+//! The light sample reuses the Zapper's photodiode model
+//! ([`crate::ZapperState`]'s 3x3 Rec.601 aperture) aimed at the centre of the
+//! completed frame, so "what counts as a flash" cannot drift between the two
+//! light-sensing peripherals.
+//!
+//! Around the robot sits the Gyromite play field. Five arm positions map to
+//! five slots:
+//!
+//! ```text
+//! slot 0  red tray   — a spinning gyro weighs down controller II's A button
+//! slot 1  blue tray  — likewise for the B button
+//! slot 2  spinner    — spins up whatever gyro is set on it
+//! slot 3  rest       — gyro 0 starts here
+//! slot 4  rest       — gyro 1 starts here
+//! ```
+//!
+//! The hand can only pick up a gyro at the bottom of its travel, and only
+//! drops one into an empty slot. A gyro slows down once it leaves the
+//! spinner; a stopped gyro topples and no longer presses its button.
+//! Stack-Up uses the same arm without gyros (its blocks are not modelled).
+//!
+//! Everything advances once per frame from the rendered framebuffer, so the
+//! robot is as deterministic as the frame it watches: movies record only the
+//! player's own input and replay the robot's presses exactly.
+
+use crate::controller::Buttons;
+
+/// Arm positions (slots) left to right.
+pub const ROB_SLOTS: u8 = 5;
+
+/// Highest arm height step (0 = table level).
+pub const ROB_MAX_HEIGHT: u8 = 5;
+
+/// Frames a gyro keeps spinning after leaving the spinner (~1 minute).
+pub const ROB_GYRO_SPIN_FRAMES: u16 = 3600;
+
+/// Frames the head LED stays lit after a recognised command.
+const LED_COMMAND_FRAMES: u16 = 20;
+
+/// Frames the head LED stays lit for an explicit LED test.
+const LED_TEST_FRAMES: u16 = 120;
+
+/// Bits in one command (lead-in + opcode).
+const COMMAND_BITS: u32 = 13;
+
+/// The 5-bit lead-in that precedes every opcode.
+const LEAD_IN: u16 = 0b00010;
+
+/// The red tray slot (controller II A).
+const SLOT_A_TRAY: u8 = 0;
+/// The blue tray slot (controller II B).
+const SLOT_B_TRAY: u8 = 1;
+/// The spinner slot.
+const SLOT_SPINNER: u8 = 2;
+
+/// Save-state tag for "gyro is in the hand".
+const SLOT_HAND: u8 = 0xFF;
+
+/// Serialized size of [`Rob`] (see [`Rob::to_bytes`]).
+pub(crate) const ROB_STATE_LEN: usize = 17;
+
+/// Which game's play field surrounds the robot.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RobMode {
+    /// *Gyromite*: two gyros, the spinner and the A/B button trays.
+    #[default]
+    Gyromite,
+    /// *Stack-Up*: arm and hand only.
+    StackUp,
+}
+
+/// One decoded R.O.B. opcode.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RobCommand {
+    /// Return to the home pose (slot 2, raised, hand open).
+    Recalibrate,
+    /// Raise the arm one step.
+    Up1,
+    /// Lower the arm one step.
+    Down1,
+    /// Raise the arm two steps.
+    Up2,
+    /// Lower the arm two steps.
+    Down2,
+    /// Rotate one slot left.
+    Left,
+    /// Rotate one slot right.
+    Right,
+    /// Close the hand (grabbing a gyro at table level).
+    Close,
+    /// Open the hand (releasing a held gyro into an empty slot).
+    Open,
+    /// Light the head LED.
+    LedTest,
+}
+
+impl RobCommand {
+    /// Every command, in opcode-table order.
+    pub const ALL: [Self; 10] = [
+        Self::Recalibrate,
+        Self::Up1,
+        Self::Down1,
+        Self::Up2,
+        Self::Down2,
+        Self::Left,
+        Self::Right,
+        Self::Close,
+        Self::Open,
+        Self::LedTest,
+    ];
+
+    /// The 8-bit opcode the game flashes for this command.
+    #[must_use]
+    pub const fn opcode(self) -> u8 {
+        match self {
+            Self::Recalibrate => 0xE8,
+            Self::Up1 => 0xBA,
+            Self::Down1 => 0xFB,
+            Self::Up2 => 0xAE,
+            Self::Down2 => 0xFA,
+            Self::Left => 0xAB,
+            Self::Right => 0xEA,
+            Self::Close => 0xBE,
+            Self::Open => 0xEE,
+            Self::LedTest => 0xEB,
+        }
+    }
+
+    /// Decode an opcode; unknown opcodes are ignored by the robot.
+    #[must_use]
+    pub fn from_opcode(op: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.opcode() == op)
+    }
+
+    /// Short display name for the frontend panel.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Recalibrate => "Recalibrate",
+            Self::Up1 => "Up 1",
+            Self::Down1 => "Down 1",
+            Self::Up2 => "Up 2",
+            Self::Down2 => "Down 2",
+            Self::Left => "Left",
+            Self::Right => "Right",
+            Self::Close => "Close",
+            Self::Open => "Open",
+            Self::LedTest => "LED test",
+        }
+    }
+}
+
+/// One Gyromite gyro.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Gyro {
+    /// The slot it stands in, or `None` while held in R.O.B.'s hand.
+    pub slot: Option<u8>,
+    /// Remaining spin, in frames (0 = stopped).
+    pub spin: u16,
+}
+
+/// The robot: flash decoder, arm pose and (in Gyromite) the two gyros.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rob {
+    mode: RobMode,
+    sensor: u16,
+    rotation: u8,
+    height: u8,
+    hand_closed: bool,
+    led_frames: u16,
+    last_command: Option<RobCommand>,
+    gyros: [Gyro; 2],
+}
+
+impl Rob {
+    /// A robot in the home pose with both gyros on their rests.
+    #[must_use]
+    pub const fn new(mode: RobMode) -> Self {
+        Self {
+            mode,
+            sensor: 0,
+            rotation: SLOT_SPINNER,
+            height: ROB_MAX_HEIGHT,
+            hand_closed: false,
+            led_frames: 0,
+            last_command: None,
+            gyros: [
+                Gyro {
+                    slot: Some(3),
+                    spin: 0,
+                },
+                Gyro {
+                    slot: Some(4),
+                    spin: 0,
+                },
+            ],
+        }
+    }
+
+    /// The play field this robot was set up for.
+    #[must_use]
+    pub const fn mode(&self) -> RobMode {
+        self.mode
+    }
+
+    /// Current arm slot (`0..ROB_SLOTS`).
+    #[must_use]
+    pub const fn rotation(&self) -> u8 {
+        self.rotation
+    }
+
+    /// Current arm height (`0..=ROB_MAX_HEIGHT`, 0 = table level).
+    #[must_use]
+    pub const fn height(&self) -> u8 {
+        self.height
+    }
+
+    /// `true` while the hand is closed.
+    #[must_use]
+    pub const fn hand_closed(&self) -> bool {
+        self.hand_closed
+    }
+
+    /// `true` while the head LED is lit.
+    #[must_use]
+    pub const fn led_on(&self) -> bool {
+        self.led_frames > 0
+    }
+
+    /// The most recent command the robot recognised.
+    #[must_use]
+    pub const fn last_command(&self) -> Option<RobCommand> {
+        self.last_command
+    }
+
+    /// The gyros on the field (empty in Stack-Up).
+    #[must_use]
+    pub const fn gyros(&self) -> &[Gyro] {
+        match self.mode {
+            RobMode::Gyromite => &self.gyros,
+            RobMode::StackUp => &[],
+        }
+    }
+
+    /// Controller II buttons currently held down by a spinning gyro on a
+    /// tray. Always empty in Stack-Up.
+    #[must_use]
+    pub fn pressed(&self) -> Buttons {
+        let mut b = Buttons::empty();
+        for g in self.gyros() {
+            if g.spin == 0 {
+                continue;
+            }
+            match g.slot {
+                Some(SLOT_A_TRAY) => b |= Buttons::A,
+                Some(SLOT_B_TRAY) => b |= Buttons::B,
+                _ => {}
+            }
+        }
+        b
+    }
+
+    /// Advance one frame: shift in the frame's light bit, act on a completed
+    /// command, then run the gyros (spinner, spin-down) and the LED timer.
+    pub fn step_frame(&mut self, bright: bool) {
+        self.sensor = ((self.sensor << 1) | u16::from(bright)) & ((1 << COMMAND_BITS) - 1);
+        if self.sensor >> 8 == LEAD_IN {
+            #[allow(clippy::cast_possible_truncation)] // low byte is the opcode.
+            let op = self.sensor as u8;
+            self.sensor = 0;
+            if let Some(cmd) = RobCommand::from_opcode(op) {
+                self.execute(cmd);
+            }
+        }
+        for g in &mut self.gyros {
+            if g.slot == Some(SLOT_SPINNER) {
+                g.spin = ROB_GYRO_SPIN_FRAMES;
+            } else {
+                g.spin = g.spin.saturating_sub(1);
+            }
+        }
+        self.led_frames = self.led_frames.saturating_sub(1);
+    }
+
+    /// Carry out one command immediately.
+    pub fn execute(&mut self, cmd: RobCommand) {
+        self.last_command = Some(cmd);
+        self.led_frames = self.led_frames.max(LED_COMMAND_FRAMES);
+        match cmd {
+            RobCommand::Recalibrate => {
+                self.rotation = SLOT_SPINNER;
+                self.height = ROB_MAX_HEIGHT;
+                self.open_hand();
+            }
+            RobCommand::Up1 => self.height = (self.height + 1).min(ROB_MAX_HEIGHT),
+            RobCommand::Up2 => self.height = (self.height + 2).min(ROB_MAX_HEIGHT),
+            RobCommand::Down1 => self.height = self.height.saturating_sub(1),
+            RobCommand::Down2 => self.height = self.height.saturating_sub(2),
+            RobCommand::Left => self.rotation = self.rotation.saturating_sub(1),
+            RobCommand::Right => self.rotation = (self.rotation + 1).min(ROB_SLOTS - 1),
+            RobCommand::Close => self.close_hand(),
+            RobCommand::Open => self.open_hand(),
+            RobCommand::LedTest => self.led_frames = LED_TEST_FRAMES,
+        }
+    }
+
+    /// Index of the gyro standing in `slot`, if any.
+    fn gyro_in(&self, slot: u8) -> Option<usize> {
+        if self.mode != RobMode::Gyromite {
+            return None;
+        }
+        self.gyros.iter().position(|g| g.slot == Some(slot))
+    }
+
+    /// Index of the gyro in the hand, if any.
+    fn held_gyro(&self) -> Option<usize> {
+        if self.mode != RobMode::Gyromite {
+            return None;
+        }
+        self.gyros.iter().position(|g| g.slot.is_none())
+    }
+
+    fn close_hand(&mut self) {
+        if !self.hand_closed
+            && self.height == 0
+            && let Some(i) = self.gyro_in(self.rotation)
+        {
+            self.gyros[i].slot = None;
+        }
+        self.hand_closed = true;
+    }
+
+    fn open_hand(&mut self) {
+        if let Some(i) = self.held_gyro() {
+            if self.gyro_in(self.rotation).is_some() {
+                return; // the slot is taken: the fingers stay on the gyro
+            }
+            self.gyros[i].slot = Some(self.rotation);
+        }
+        self.hand_closed = false;
+    }
+
+    /// Serialize for the save-state BUS section.
+    pub(crate) fn to_bytes(&self) -> [u8; ROB_STATE_LEN] {
+        let mut b = [0u8; ROB_STATE_LEN];
+        b[0] = match self.mode {
+            RobMode::Gyromite => 0,
+            RobMode::StackUp => 1,
+        };
+        b[1..3].copy_from_slice(&self.sensor.to_le_bytes());
+        b[3] = self.rotation;
+        b[4] = self.height;
+        b[5] = u8::from(self.hand_closed);
+        b[6..8].copy_from_slice(&self.led_frames.to_le_bytes());
+        b[8] = self.last_command.map_or(0, RobCommand::opcode);
+        for (i, g) in self.gyros.iter().enumerate() {
+            let at = 9 + i * 4;
+            b[at] = g.slot.unwrap_or(SLOT_HAND);
+            b[at + 1..at + 3].copy_from_slice(&g.spin.to_le_bytes());
+        }
+        b
+    }
+
+    /// Inverse of [`Self::to_bytes`]; out-of-range fields are clamped.
+    pub(crate) fn from_bytes(b: &[u8; ROB_STATE_LEN]) -> Self {
+        let mut rob = Self::new(if b[0] == 1 {
+            RobMode::StackUp
+        } else {
+            RobMode::Gyromite
+        });
+        rob.sensor = u16::from_le_bytes([b[1], b[2]]) & ((1 << COMMAND_BITS) - 1);
+        rob.rotation = b[3].min(ROB_SLOTS - 1);
+        rob.height = b[4].min(ROB_MAX_HEIGHT);
+        rob.hand_closed = b[5] != 0;
+        rob.led_frames = u16::from_le_bytes([b[6], b[7]]);
+        rob.last_command = RobCommand::from_opcode(b[8]);
+        for (i, g) in rob.gyros.iter_mut().enumerate() {
+            let at = 9 + i * 4;
+            g.slot = match b[at] {
+                SLOT_HAND => None,
+                s => Some(s.min(ROB_SLOTS - 1)),
+            };
+            g.spin = u16::from_le_bytes([b[at + 1], b[at + 2]]);
+        }
+        rob
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flash one command (lead-in + opcode, MSB first) into the robot.
+    fn send(rob: &mut Rob, cmd: RobCommand) {
+        let word = (LEAD_IN << 8) | u16::from(cmd.opcode());
+        for bit in (0..COMMAND_BITS).rev() {
+            rob.step_frame(word >> bit & 1 != 0);
+        }
+    }
+
+    #[test]
+    fn decodes_flashed_commands_after_lead_in() {
+        let mut rob = Rob::new(RobMode::Gyromite);
+        // Idle dark frames never form a command.
+        for _ in 0..40 {
+            rob.step_frame(false);
+        }
+        assert_eq!(rob.last_command(), None);
+        send(&mut rob, RobCommand::Left);
+        assert_eq!(rob.last_command(), Some(RobCommand::Left));
+        assert_eq!(rob.rotation(), SLOT_SPINNER - 1);
+        assert!(rob.led_on());
+        send(&mut rob, RobCommand::Down2);
+        assert_eq!(rob.height(), ROB_MAX_HEIGHT - 2);
+    }
+
+    #[test]
+    fn every_opcode_round_trips() {
+        for cmd in RobCommand::ALL {
+            assert_eq!(RobCommand::from_opcode(cmd.opcode()), Some(cmd));
+        }
+        assert_eq!(RobCommand::from_opcode(0x00), None);
+    }
+
+    #[test]
+    fn gyro_moved_to_spinner_then_tray_presses_a() {
+        let mut rob = Rob::new(RobMode::Gyromite);
+        // Fetch gyro 0 from its rest (slot 3).
+        rob.execute(RobCommand::Right);
+        rob.execute(RobCommand::Down2);
+        rob.execute(RobCommand::Down2);
+        rob.execute(RobCommand::Down1);
+        assert_eq!(rob.height(), 0);
+        rob.execute(RobCommand::Close);
+        assert_eq!(rob.gyros()[0].slot, None);
+        // Spin it up.
+        rob.execute(RobCommand::Left);
+        rob.execute(RobCommand::Open);
+        rob.step_frame(false);
+        assert_eq!(rob.gyros()[0].spin, ROB_GYRO_SPIN_FRAMES);
+        assert!(rob.pressed().is_empty(), "the spinner presses nothing");
+        // Carry it to the red tray: A is held while it spins.
+        rob.execute(RobCommand::Close);
+        rob.execute(RobCommand::Left);
+        rob.execute(RobCommand::Left);
+        rob.execute(RobCommand::Open);
+        assert_eq!(rob.gyros()[0].slot, Some(SLOT_A_TRAY));
+        assert_eq!(rob.pressed(), Buttons::A);
+        for _ in 0..ROB_GYRO_SPIN_FRAMES {
+            rob.step_frame(false);
+        }
+        assert!(rob.pressed().is_empty(), "a stopped gyro topples");
+    }
+
+    #[test]
+    fn hand_cannot_drop_into_an_occupied_slot() {
+        let mut rob = Rob::new(RobMode::Gyromite);
+        rob.execute(RobCommand::Right);
+        rob.execute(RobCommand::Down2);
+        rob.execute(RobCommand::Down2);
+        rob.execute(RobCommand::Down1);
+        rob.execute(RobCommand::Close);
+        rob.execute(RobCommand::Right); // over gyro 1's rest
+        rob.execute(RobCommand::Open);
+        assert!(rob.hand_closed());
+        assert_eq!(rob.gyros()[0].slot, None);
+    }
+
+    #[test]
+    fn stack_up_has_no_gyros_and_state_round_trips() {
+        let mut rob = Rob::new(RobMode::StackUp);
+        rob.execute(RobCommand::Down1);
+        rob.execute(RobCommand::Close);
+        assert!(rob.gyros().is_empty());
+        assert!(rob.pressed().is_empty());
+        rob.step_frame(true);
+        assert_eq!(Rob::from_bytes(&rob.to_bytes()), rob);
+        let mut g = Rob::new(RobMode::Gyromite);
+        g.execute(RobCommand::Right);
+        g.execute(RobCommand::Down2);
+        g.execute(RobCommand::Down2);
+        g.execute(RobCommand::Down1);
+        g.execute(RobCommand::Close);
+        assert_eq!(Rob::from_bytes(&g.to_bytes()), g);
+    }
+}
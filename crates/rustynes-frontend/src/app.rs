@@ -112,7 +112,7 @@ use wasm_bindgen::JsCast;
 // wasm32, so using this consistently fixes the type mismatch.
 use web_time::Instant;
 
-use rustynes_core::{Buttons, Nes};
+use rustynes_core::{Buttons, MovieCommand, Nes};
 use winit::application::ApplicationHandler;
 use winit::dpi::LogicalSize;
 use winit::event::WindowEvent;
@@ -681,6 +681,10 @@ const DISPLAY_SYNC_RATE_FALLBACK: f32 = 0.02;
 pub struct App {
     rom_bytes: Vec<u8>,
     rom_label: String,
+    /// v2.5.0 — the loaded ROM's per-game port-2 device (the `<rom>.json`
+    /// `expansion_device`). While set it wins over `[input] expansion_device`;
+    /// `None` follows the global setting. See [`Self::expansion_device`].
+    per_game_expansion: Option<crate::config::ExpansionDevice>,
     /// v2.8.0 Phase 5 — the emulation core: ALL per-frame produce state
     /// (the `Nes`, movie, run-ahead, perf, presented framebuffer, pacing
     /// deadlines, …) extracted from `App` into `emu.rs`. `App` keeps the
@@ -752,6 +756,10 @@ pub struct App {
     /// `wasm.rs` instead, so this field is native-only.
     #[cfg(not(target_arch = "wasm32"))]
     audio: Option<AudioOutput>,
+    /// v2.5.0 — host microphone capture for the Famicom mic (`[input]
+    /// microphone_capture`); `None` when off or the device failed to open.
+    #[cfg(not(target_arch = "wasm32"))]
+    mic_capture: Option<crate::mic_capture::MicCapture>,
     input: InputState,
     config: Config,
     #[cfg(not(target_arch = "wasm32"))]
@@ -832,6 +840,18 @@ pub struct App {
     /// player 1). Native-only; its held mask folds into `frame_inputs`.
     #[cfg(not(target_arch = "wasm32"))]
     virtual_pad: crate::virtual_pad::VirtualPad,
+    /// v2.5.0 — the `LiveSplit` Server connection mirroring the autosplitter,
+    /// when one is open. Native-only (TCP).
+    #[cfg(not(target_arch = "wasm32"))]
+    livesplit: Option<crate::livesplit::LiveSplitClient>,
+    /// v2.5.0 — the loaded ROM's practice segments, keyed by its hash.
+    /// Native-only (the anchors are files).
+    #[cfg(not(target_arch = "wasm32"))]
+    practice: Option<([u8; 32], crate::practice::PracticeSet)>,
+    /// v2.5.0 — why the current practice segment's end condition did not
+    /// compile, shown in the Practice panel.
+    #[cfg(not(target_arch = "wasm32"))]
+    practice_error: Option<String>,
     /// v1.0.0 — cached previous value of `config.ui.pixel_aspect_correction`,
     /// so a change made in the menu / settings window is detected after the
     /// egui pass and pushed into the gfx letterbox (mirrors the NTSC live-apply
@@ -999,6 +1019,13 @@ pub struct App {
 /// Frames to hold a Vs. System coin-insert latch (~50 ms at 60 fps).
 const VS_COIN_HOLD_FRAMES: u8 = 3;
 
+/// v2.5.0 — the movie command for an FDS side change (`None` = eject).
+fn disk_command(side: Option<usize>) -> MovieCommand {
+    side.map_or(MovieCommand::EjectDisk, |s| {
+        MovieCommand::InsertDisk(u8::try_from(s).unwrap_or(u8::MAX))
+    })
+}
+
 /// Resolve the effective Vs. System DIP-switch byte from the config and an
 /// optional per-game database entry (v2.7.0).
 ///
@@ -1079,23 +1106,24 @@ impl App {
     ///
     /// Returns an `io::Error` if the file can't be read.
     #[cfg(not(target_arch = "wasm32"))]
+    #[allow(clippy::too_many_lines)]
     pub fn new(rom_path: &std::path::Path) -> std::io::Result<Self> {
         // The CLI / initial-ROM path must run the same `.zip` extraction +
         // same-stem soft-patching as `load_rom_from_path` (see the helper).
-        let (rom_bytes, rom_label) = load_and_preprocess_rom(rom_path)?;
+        let (mut rom_bytes, rom_label) = load_and_preprocess_rom(rom_path)?;
         let config = Config::load_or_default();
         let input = InputState::from_config(&config.input);
         let data_dir = Config::default_data_dir();
         // v2.3.4 — hand the game-DB crate the overlay directory. It no longer
         // reaches for the user's config dir itself, so that a test harness can
         // link it without inheriting whatever the developer has saved locally.
-        let mut rom_bytes = rom_bytes;
         configure_game_db_and_patch_startup_rom(data_dir.clone(), &mut rom_bytes, rom_path);
         let ui = crate::ui_shell::UiShell::new(&config);
         let prev_par_correction = config.ui.pixel_aspect_correction;
         Ok(Self {
             rom_bytes,
             rom_label,
+            per_game_expansion: None,
             emu: crate::emu::EmuHandle::new(crate::emu::EmuCore::new()),
             present_staging: Vec::new(),
             present_index_staging: Vec::new(),
@@ -1117,6 +1145,7 @@ impl App {
             #[cfg(all(feature = "hd-pack", not(target_arch = "wasm32")))]
             present_watched_mem: crate::hdpack::WatchedMemory::new(),
             audio: None,
+            mic_capture: None,
             input,
             config,
             data_dir,
@@ -1142,6 +1171,9 @@ impl App {
             speed: 1.0,
             save_states_ui: crate::save_states_ui::SaveStatesUi::default(),
             virtual_pad: crate::virtual_pad::VirtualPad::default(),
+            livesplit: None,
+            practice: None,
+            practice_error: None,
             prev_par_correction,
             gamepad: gilrs::Gilrs::new()
                 .map_err(|e| {
@@ -1243,6 +1275,7 @@ impl App {
         Self {
             rom_bytes: Vec::new(),
             rom_label: "(no ROM)".to_string(),
+            per_game_expansion: None,
             emu: crate::emu::EmuHandle::new(crate::emu::EmuCore::new()),
             present_staging: Vec::new(),
             present_index_staging: Vec::new(),
@@ -1439,6 +1472,20 @@ impl App {
             // loaded: a Latency Oracle report with its Apply button still live
             // (PR #385 review), or a 2,048-entry RAM Atlas that looks like a map.
             d.clear_rom_bound_analysis();
+            #[cfg(not(target_arch = "wasm32"))]
+            d.set_autosplit_file(None, &self.config.autosplit.livesplit_addr);
+            #[cfg(not(target_arch = "wasm32"))]
+            d.set_practice(
+                None,
+                Vec::new(),
+                true,
+                &self.config.input.system.practice_reload,
+            );
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.practice = None;
+            self.practice_error = None;
         }
         // Stop the dedicated emulation thread from producing frames.
         #[cfg(all(not(target_arch = "wasm32"), feature = "emu-thread"))]
@@ -1668,6 +1715,9 @@ impl App {
         // DIP value wins over the `[vs] dip` / Vs.-DB precedence. Both flow
         // through the same core setters the game-DB editor uses; a no-op for a
         // non-Vs. cart / absent override, so the default path is byte-identical.
+        // v2.5.0 — its port-2 device choice is attached by the
+        // `sync_expansion_device` below.
+        self.per_game_expansion = per_game.as_ref().and_then(|c| c.expansion_device);
         if let Some(cfg) = per_game.as_ref() {
             if let Some(m) = cfg
                 .overrides
@@ -1763,6 +1813,9 @@ impl App {
             }
         }
         self.apply_cheats_for_current_rom();
+        // v2.5.0 — install this ROM's speedrun splits and practice segments.
+        self.load_splits_for_current_rom();
+        self.load_practice_for_current_rom();
         // v1.2.0 C3 — auto-load a configured HD-pack for this ROM (no-op when
         // none is configured, so the default presentation is byte-identical).
         #[cfg(all(feature = "hd-pack", not(target_arch = "wasm32")))]
@@ -1796,6 +1849,8 @@ impl App {
         // the player-2 port. No-op when ExpansionDevice::None.
         #[cfg(not(target_arch = "wasm32"))]
         self.sync_expansion_device();
+        // v2.5.0 — plug in the Famicom 3D System glasses if a stereo mode is set.
+        self.sync_famicom_3d();
         // v1.1.0 beta.2 — for an NSF music file, feed the header metadata to the
         // NSF player panel and pop it open (the framebuffer is blank, so the
         // panel is the primary UI).
@@ -2074,11 +2129,15 @@ impl App {
     /// Resolve the FDS BIOS (`disksys.rom`) bytes.
     ///
     /// Uses the configured [`crate::config::FdsConfig::bios_path`] when it
-    /// points at a readable 8 KiB file. Otherwise prompts the user once via an
-    /// `rfd` file dialog, validates the selection is exactly 8 KiB, and
-    /// persists the chosen path to the config file. Returns `None` (with a
-    /// clear status message) when the user cancels or the file is the wrong
-    /// size, so the caller can abort the load without crashing. Native-only.
+    /// points at a readable 8 KiB file. With no path set and
+    /// [`crate::config::FdsConfig::hle_bios`] on, returns the built-in HLE BIOS
+    /// without prompting. Otherwise prompts the user once via an `rfd` file
+    /// dialog, validates the selection is exactly 8 KiB, and persists the
+    /// chosen path to the config file. Cancelling the prompt falls back to the
+    /// HLE BIOS only when `hle_bios` is on. Returns `None` (with a clear status
+    /// message) when the prompt is cancelled with `hle_bios` off or the picked
+    /// file is unreadable or the wrong size, so the caller can abort the load
+    /// without crashing. Native-only.
     #[cfg(not(target_arch = "wasm32"))]
     fn resolve_fds_bios(&mut self) -> Option<Vec<u8>> {
         // 1) Try the configured path first.
@@ -2097,13 +2156,27 @@ impl App {
             }
         }
 
-        // 2) Prompt for it.
+        // 2) No BIOS configured: the built-in HLE BIOS when selected.
+        if self.config.fds.bios_path.is_none() && self.config.fds.hle_bios {
+            return Some(rustynes_core::rustynes_mappers::fds_hle_bios());
+        }
+
+        // 3) Prompt for it.
         let Some(path) = rfd::FileDialog::new()
             .set_title("Select Famicom Disk System BIOS (disksys.rom)")
             .add_filter("FDS BIOS", &["rom", "bin"])
             .pick_file()
         else {
-            eprintln!("rustynes: FDS BIOS selection cancelled; cannot load disk image");
+            if self.config.fds.hle_bios {
+                eprintln!(
+                    "rustynes: FDS BIOS selection cancelled; booting with the built-in HLE BIOS"
+                );
+                return Some(rustynes_core::rustynes_mappers::fds_hle_bios());
+            }
+            eprintln!("rustynes: FDS BIOS selection cancelled; disk not loaded");
+            self.ui.set_status(crate::ui_shell::StatusMessage::error(
+                "FDS needs disksys.rom: pick one, or turn on the built-in HLE BIOS in Settings",
+            ));
             return None;
         };
         let bytes = match std::fs::read(&path) {
@@ -2178,16 +2251,27 @@ impl App {
     }
 
     /// Flush the FDS writable disk (see [`crate::emu::EmuCore::flush_fds_save`]).
+    /// Skipped while netplay is active: the session plays on a copy of the
+    /// disk that must not reach the `.fds.sav`.
     #[cfg(not(target_arch = "wasm32"))]
     fn flush_fds_save(&self) {
+        if self.netplay.is_active() {
+            return;
+        }
         let data_dir = self.data_dir.clone();
         self.emu.lock().flush_fds_save(data_dir.as_deref());
     }
 
+    /// Flush the Turbo File (see [`crate::emu::EmuCore::flush_turbo_file`]).
+    #[cfg(not(target_arch = "wasm32"))]
+    fn flush_turbo_file(&self) {
+        self.emu.lock().flush_turbo_file(self.data_dir.as_deref());
+    }
+
     /// Cycle the inserted FDS disk side: ejected -> side 0 -> side 1 -> ... ->
     /// wrap back to ejected. A no-op for non-FDS games. Flushes any pending
     /// writes to the `.fds.sav` (native) before swapping so they aren't lost.
-    fn cycle_disk_side(&self) {
+    fn cycle_disk_side(&mut self) {
         // Flush before swapping so an in-progress write isn't lost across the
         // eject. Native-only (the wasm build has no `.fds.sav` filesystem).
         #[cfg(not(target_arch = "wasm32"))]
@@ -2206,6 +2290,14 @@ impl App {
             Some(s) if s + 1 < count => Some(s + 1),
             Some(_) => None,
         };
+        if !guard.movie.record_command(disk_command(next)) {
+            drop(guard);
+            self.refuse_movie_command();
+            return;
+        }
+        let Some(nes) = guard.nes.as_mut() else {
+            return;
+        };
         nes.set_disk_side(next);
         match next {
             Some(s) => eprintln!("rustynes: FDS disk -> Side {}/{count}", s + 1),
@@ -2216,26 +2308,749 @@ impl App {
     /// v1.8.9 — insert a specific FDS disk side (`Some(i)`) or eject (`None`) — the
     /// Multi-Disk submenu's direct counterpart to [`Self::cycle_disk_side`] (a
     /// multi-disk game prompts "insert side N"). An out-of-range side is ignored.
-    fn set_disk_side(&self, side: Option<usize>) {
+    fn set_disk_side(&mut self, side: Option<usize>) {
         #[cfg(not(target_arch = "wasm32"))]
         self.flush_fds_save();
         let mut guard = self.emu.lock();
         let Some(nes) = guard.nes.as_mut() else {
             return;
         };
-        let count = nes.disk_side_count();
-        if count == 0 {
-            return;
-        }
-        if let Some(s) = side
-            && s >= count
+        let count = nes.disk_side_count();
+        if count == 0 {
+            return;
+        }
+        if let Some(s) = side
+            && s >= count
+        {
+            return; // stale menu click past the side count.
+        }
+        if !guard.movie.record_command(disk_command(side)) {
+            drop(guard);
+            self.refuse_movie_command();
+            return;
+        }
+        let Some(nes) = guard.nes.as_mut() else {
+            return;
+        };
+        nes.set_disk_side(side);
+        match side {
+            Some(s) => eprintln!("rustynes: FDS disk -> Side {}/{count}", s + 1),
+            None => eprintln!("rustynes: FDS disk ejected"),
+        }
+    }
+
+    /// v2.5.0 — apply a Family BASIC Data Recorder control. Transport changes
+    /// go through [`Nes::data_recorder_mut`], which catches the deck up to the
+    /// current cycle first, so they take effect from this frame on.
+    fn tape_action(&mut self, action: crate::ui_shell::TapeAction) {
+        use crate::ui_shell::TapeAction;
+        #[cfg(not(target_arch = "wasm32"))]
+        let picked = match action {
+            TapeAction::InsertWav => {
+                let Some(path) = rfd::FileDialog::new()
+                    .set_title("Insert Data Recorder tape")
+                    .add_filter("WAV audio", &["wav"])
+                    .pick_file()
+                else {
+                    return;
+                };
+                let tape = std::fs::read(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|b| {
+                        rustynes_core::DataRecorder::from_wav(&b).map_err(|e| e.to_string())
+                    });
+                match tape {
+                    Ok(t) => Some(t),
+                    Err(e) => {
+                        self.ui.set_status(StatusMessage::error(format!(
+                            "Tape load failed: {}: {e}",
+                            path.display()
+                        )));
+                        return;
+                    }
+                }
+            }
+            _ => None,
+        };
+        let mut guard = self.emu.lock();
+        let Some(nes) = guard.nes.as_mut() else {
+            return;
+        };
+        let message = match action {
+            TapeAction::InsertWav => {
+                #[cfg(not(target_arch = "wasm32"))]
+                nes.set_data_recorder(picked);
+                "Tape inserted"
+            }
+            TapeAction::InsertBlank => {
+                nes.set_data_recorder(Some(rustynes_core::DataRecorder::new()));
+                "Blank tape inserted"
+            }
+            TapeAction::Eject => {
+                nes.set_data_recorder(None);
+                "Tape ejected"
+            }
+            TapeAction::SaveWav => {
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    let Some(wav) = nes.data_recorder().map(rustynes_core::DataRecorder::to_wav)
+                    else {
+                        return;
+                    };
+                    drop(guard);
+                    let Some(path) = rfd::FileDialog::new()
+                        .set_title("Save Data Recorder tape")
+                        .add_filter("WAV audio", &["wav"])
+                        .set_file_name("tape.wav")
+                        .save_file()
+                    else {
+                        return;
+                    };
+                    let status = match crate::atomic_write::write_atomic(&path, &wav) {
+                        Ok(()) => StatusMessage::success(format!("Tape saved: {}", path.display())),
+                        Err(e) => StatusMessage::error(format!("Tape save failed: {e}")),
+                    };
+                    self.ui.set_status(status);
+                }
+                return;
+            }
+            TapeAction::Play | TapeAction::Record | TapeAction::Stop | TapeAction::Rewind => {
+                let Some(deck) = nes.data_recorder_mut() else {
+                    return;
+                };
+                match action {
+                    TapeAction::Play => deck.play(),
+                    TapeAction::Record => deck.record(),
+                    TapeAction::Stop => deck.stop(),
+                    _ => deck.rewind(),
+                }
+                return;
+            }
+        };
+        drop(guard);
+        self.ui.set_status(StatusMessage::info(message));
+    }
+
+    /// v2.5.0 — apply a ghost replay control.
+    fn ghost_action(&mut self, action: crate::ui_shell::GhostAction) {
+        use crate::ui_shell::GhostAction;
+        match action {
+            GhostAction::Load => {
+                #[cfg(not(target_arch = "wasm32"))]
+                self.load_ghost();
+            }
+            GhostAction::Restart => {
+                let restarted = self.emu.lock().ghost.as_mut().map(|g| g.restart());
+                if let Some(Err(e)) = restarted {
+                    self.ui
+                        .set_status(StatusMessage::error(format!("Ghost restart failed: {e}")));
+                }
+            }
+            GhostAction::Unload => {
+                self.emu.lock().ghost = None;
+                self.ui.set_status(StatusMessage::info("Ghost unloaded"));
+            }
+            GhostAction::Configure => {
+                let settings = self.config.ghost;
+                if let Some(ghost) = self.emu.lock().ghost.as_mut() {
+                    ghost.configure(settings);
+                }
+            }
+        }
+    }
+
+    /// v2.5.0 — pick a movie and race it as a ghost. The ghost gets a console
+    /// of its own, built from the loaded cartridge with the same `[emulation]`
+    /// hardware knobs, so a movie recorded here replays there identically.
+    /// `.rnm` loads as-is; the external formats go through the import path.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_ghost(&mut self) {
+        let rom_sha = self.emu.lock().nes.as_ref().map(|n| *n.rom_sha256());
+        let Some(rom_sha) = rom_sha else {
+            self.ui
+                .set_status(StatusMessage::info("Load a ROM before loading a ghost"));
+            return;
+        };
+        if is_fds_image(&self.rom_bytes) || is_nsf_image(&self.rom_bytes) {
+            self.ui
+                .set_status(StatusMessage::info("Ghost replay needs a cartridge game"));
+            return;
+        }
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Load ghost")
+            .add_filter("TAS movie", &["rnm", "fm2", "bk2", "fcm", "fmv", "vmv"])
+            .set_directory(self.movies_dir().unwrap_or_else(|| PathBuf::from(".")))
+            .pick_file()
+        else {
+            return;
+        };
+        let is_rnm = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("rnm"));
+        let movie = if is_rnm {
+            std::fs::read(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|b| rustynes_core::Movie::deserialize(&b).map_err(|e| e.to_string()))
+        } else {
+            Self::parse_movie_file(&path, rom_sha)
+        };
+        let ghost = movie.and_then(|movie| {
+            let mut nes = Nes::from_rom(&self.rom_bytes).map_err(|e| e.to_string())?;
+            self.configure_ppu_hardware(&mut nes);
+            crate::ghost::Ghost::new(nes, movie, self.config.ghost).map_err(|e| e.to_string())
+        });
+        match ghost {
+            Ok(ghost) => {
+                let len = ghost.status().len;
+                self.emu.lock().ghost = Some(Box::new(ghost));
+                self.ui.set_status(StatusMessage::success(format!(
+                    "Ghost loaded ({len} frames)"
+                )));
+            }
+            Err(e) => self
+                .ui
+                .set_status(StatusMessage::error(format!("Ghost load failed: {e}"))),
+        }
+    }
+
+    /// v2.5.0 — install the loaded ROM's split file and hand it to the
+    /// Autosplitter panel; connect to `LiveSplit` if `[autosplit]` asks for it
+    /// and the ROM has splits. A ROM with no split file gets no timer.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_splits_for_current_rom(&mut self) {
+        let Some(rom_sha) = self.emu.lock().nes.as_ref().map(|n| *n.rom_sha256()) else {
+            return;
+        };
+        let file = self
+            .data_dir
+            .as_ref()
+            .map(|dir| crate::autosplit::load(dir, &rom_sha))
+            .unwrap_or_default();
+        let installed = self.install_splits(file, rom_sha);
+        if installed && self.config.autosplit.livesplit_auto_connect && self.livesplit.is_none() {
+            let addr = self.config.autosplit.livesplit_addr.clone();
+            self.connect_livesplit(&addr);
+        }
+    }
+
+    /// Compile `file` into the running autosplitter (none without a start
+    /// condition) and refresh the panel. Returns whether a timer is installed.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn install_splits(&mut self, file: crate::autosplit::SplitFile, rom_sha: [u8; 32]) -> bool {
+        let timer = crate::autosplit::Autosplitter::new(file.clone(), rom_sha);
+        let errors = timer.errors().to_vec();
+        let installed = !file.start.trim().is_empty();
+        self.emu.lock().autosplit = installed.then(|| Box::new(timer));
+        if let Some(d) = self.debugger.as_mut() {
+            d.set_autosplit_file(Some((file, errors)), &self.config.autosplit.livesplit_addr);
+        }
+        installed
+    }
+
+    /// v2.5.0 — act on an Autosplitter panel request.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn handle_autosplit_request(&mut self, req: crate::debugger::AutosplitRequest) {
+        use crate::debugger::AutosplitRequest;
+        match req {
+            AutosplitRequest::Apply(file) => {
+                let Some(rom_sha) = self.emu.lock().nes.as_ref().map(|n| *n.rom_sha256()) else {
+                    return;
+                };
+                let saved = self
+                    .data_dir
+                    .as_ref()
+                    .map(|dir| crate::autosplit::save(dir, &rom_sha, &file));
+                let status = match saved {
+                    Some(Err(e)) => StatusMessage::error(format!("Splits not saved: {e}")),
+                    _ => StatusMessage::success("Splits applied"),
+                };
+                self.install_splits(file, rom_sha);
+                self.ui.set_status(status);
+            }
+            AutosplitRequest::ResetTimer => {
+                if let Some(timer) = self.emu.lock().autosplit.as_mut() {
+                    timer.reset();
+                }
+            }
+            AutosplitRequest::ExportLss => self.export_lss(),
+            AutosplitRequest::Connect(addr) => {
+                if addr != self.config.autosplit.livesplit_addr {
+                    self.config.autosplit.livesplit_addr.clone_from(&addr);
+                    let _ = self.config.save();
+                }
+                self.connect_livesplit(&addr);
+            }
+            AutosplitRequest::Disconnect => {
+                self.livesplit = None;
+                self.ui
+                    .set_status(StatusMessage::info("LiveSplit disconnected"));
+            }
+        }
+    }
+
+    /// Start opening the `LiveSplit` Server connection at `addr`. The client
+    /// connects on its own thread; [`Self::pump_autosplit`] reports how it
+    /// went.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn connect_livesplit(&mut self, addr: &str) {
+        match crate::livesplit::LiveSplitClient::connect(addr) {
+            Ok(client) => {
+                self.livesplit = Some(client);
+                self.ui.set_status(StatusMessage::info(format!(
+                    "Connecting to LiveSplit ({addr})..."
+                )));
+            }
+            Err(e) => {
+                self.livesplit = None;
+                self.ui.set_status(StatusMessage::error(format!(
+                    "LiveSplit connection to {addr} failed: {e}"
+                )));
+            }
+        }
+    }
+
+    /// Save the loaded ROM's splits as a `LiveSplit` `.lss` file.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_lss(&mut self) {
+        let lss = {
+            let guard = self.emu.lock();
+            let Some(nes) = guard.nes.as_ref() else {
+                return;
+            };
+            let rom_sha = *nes.rom_sha256();
+            let frame = nes.frame_duration();
+            let file = match guard.autosplit.as_ref() {
+                Some(timer) => timer.file().clone(),
+                None => self
+                    .data_dir
+                    .as_ref()
+                    .map(|dir| crate::autosplit::load(dir, &rom_sha))
+                    .unwrap_or_default(),
+            };
+            file.to_lss(&self.rom_label, frame)
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Export LiveSplit splits")
+            .add_filter("LiveSplit splits", &["lss"])
+            .set_file_name(format!("{}.lss", self.rom_label))
+            .save_file()
+        else {
+            return;
+        };
+        let status = match crate::atomic_write::write_atomic(&path, lss.as_bytes()) {
+            Ok(()) => StatusMessage::success(format!("Splits exported: {}", path.display())),
+            Err(e) => StatusMessage::error(format!("Splits export failed: {e}")),
+        };
+        self.ui.set_status(status);
+    }
+
+    /// v2.5.0 — forward the autosplitter's timer events to `LiveSplit`, save
+    /// a new personal best, and refresh the Autosplitter panel. A failed
+    /// connect or a lost connection drops the client and says so.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn pump_autosplit(&mut self) {
+        self.poll_livesplit();
+        let pumped = {
+            let mut guard = self.emu.lock();
+            guard.autosplit.as_mut().map(|timer| {
+                let best = timer
+                    .take_best_changed()
+                    .then(|| (*timer.rom_sha256(), timer.file().clone()));
+                (timer.take_events(), timer.view(), best)
+            })
+        };
+        let view = pumped.map(|(events, view, best)| {
+            if let Some((rom_sha, file)) = best {
+                self.save_personal_best(rom_sha, file, &view);
+            }
+            if let Some(client) = self.livesplit.as_ref() {
+                for (event, at) in events {
+                    client.send(event, at);
+                }
+            }
+            view
+        });
+        let livesplit = self
+            .livesplit
+            .as_ref()
+            .filter(|c| c.is_connected())
+            .map(|c| c.addr().to_owned());
+        if let Some(d) = self.debugger.as_mut() {
+            d.set_autosplit_status(view, livesplit);
+        }
+    }
+
+    /// Report what the `LiveSplit` worker has learned since the last frame.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn poll_livesplit(&mut self) {
+        use crate::livesplit::LiveSplitEvent;
+        while let Some(client) = self.livesplit.as_mut()
+            && let Some(event) = client.poll()
+        {
+            let addr = client.addr().to_owned();
+            let status = match event {
+                LiveSplitEvent::Connected => {
+                    StatusMessage::success(format!("LiveSplit connected ({addr})"))
+                }
+                LiveSplitEvent::Failed(e) => {
+                    self.livesplit = None;
+                    StatusMessage::error(format!("LiveSplit connection to {addr} failed: {e}"))
+                }
+                LiveSplitEvent::Lost(e) => {
+                    self.livesplit = None;
+                    StatusMessage::error(format!("LiveSplit disconnected: {e}"))
+                }
+            };
+            self.ui.set_status(status);
+        }
+    }
+
+    /// Persist a finished run's new personal best and show it in the panel.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_personal_best(
+        &mut self,
+        rom_sha: [u8; 32],
+        file: crate::autosplit::SplitFile,
+        view: &crate::autosplit::TimerView,
+    ) {
+        let saved = self
+            .data_dir
+            .as_ref()
+            .map(|dir| crate::autosplit::save(dir, &rom_sha, &file));
+        self.ui.set_status(match saved {
+            Some(Err(e)) => StatusMessage::error(format!("Personal best not saved: {e}")),
+            _ => StatusMessage::success(format!("New personal best: {}", view.game_time())),
+        });
+        if let Some(d) = self.debugger.as_mut() {
+            d.set_autosplit_file(
+                Some((file, Vec::new())),
+                &self.config.autosplit.livesplit_addr,
+            );
+        }
+    }
+
+    /// v2.5.0 — load the loaded ROM's practice segments and hand them, with
+    /// every anchor thumbnail, to the Practice panel.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_practice_for_current_rom(&mut self) {
+        let Some(rom_sha) = self.emu.lock().nes.as_ref().map(|n| *n.rom_sha256()) else {
+            return;
+        };
+        let set = self
+            .data_dir
+            .as_ref()
+            .map(|dir| crate::practice::load(dir, &rom_sha))
+            .unwrap_or_default();
+        let ids: Vec<u32> = set.segments.iter().map(|s| s.id).collect();
+        self.practice = Some((rom_sha, set));
+        self.practice_error = None;
+        let thumbs = self.practice_thumbnails(&ids);
+        self.push_practice(thumbs, true);
+    }
+
+    /// The anchor thumbnails of segments `ids` (those with one on disk).
+    #[cfg(not(target_arch = "wasm32"))]
+    fn practice_thumbnails(&self, ids: &[u32]) -> Vec<(u32, Vec<u8>)> {
+        let (Some(dir), Some((rom_sha, _))) = (self.data_dir.as_ref(), self.practice.as_ref())
+        else {
+            return Vec::new();
+        };
+        ids.iter()
+            .filter_map(|&id| {
+                let blob = crate::practice::load_anchor(dir, rom_sha, id).ok()?;
+                let rgba = rustynes_core::Nes::extract_thumbnail(&blob).ok()??;
+                Some((id, rgba))
+            })
+            .collect()
+    }
+
+    /// Push the practice set (and any changed thumbnails) to the panel.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn push_practice(&mut self, thumbs: Vec<(u32, Vec<u8>)>, reset_thumbs: bool) {
+        let set = self.practice.as_ref().map(|(_, set)| set.clone());
+        let key = &self.config.input.system.practice_reload;
+        if let Some(d) = self.debugger.as_mut() {
+            d.set_practice(set, thumbs, reset_thumbs, key);
+        }
+    }
+
+    /// Persist the practice set, reporting a failure.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_practice(&mut self) {
+        let (Some(dir), Some((rom_sha, set))) = (self.data_dir.as_ref(), self.practice.as_ref())
+        else {
+            return;
+        };
+        if let Err(e) = crate::practice::save(dir, rom_sha, set) {
+            self.ui.set_status(StatusMessage::error(format!(
+                "Practice segments not saved: {e}"
+            )));
+        }
+    }
+
+    /// v2.5.0 — act on a Practice panel request.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn handle_practice_request(&mut self, req: crate::debugger::PracticeRequest) {
+        use crate::debugger::PracticeRequest;
+        let Some((rom_sha, set)) = self.practice.as_mut() else {
+            return;
+        };
+        let rom_sha = *rom_sha;
+        match req {
+            PracticeRequest::Add(name) => {
+                let id = set.add(name);
+                if self.capture_practice_anchor(rom_sha, id) {
+                    self.save_practice();
+                } else if let Some((_, set)) = self.practice.as_mut() {
+                    set.remove(id);
+                }
+            }
+            PracticeRequest::Recapture(id) => {
+                self.capture_practice_anchor(rom_sha, id);
+            }
+            PracticeRequest::Load(id) => self.load_practice_segment(id),
+            PracticeRequest::Edit { id, name, end } => {
+                if let Err(e) = crate::practice::Attempt::new(rom_sha, id, &end) {
+                    self.ui
+                        .set_status(StatusMessage::error(format!("End condition: {e}")));
+                    return;
+                }
+                if let Some(seg) = set.segment_mut(id) {
+                    seg.name = name;
+                    seg.end = end;
+                }
+                self.save_practice();
+            }
+            PracticeRequest::Remove(id) => {
+                set.remove(id);
+                if let Some(dir) = self.data_dir.as_ref() {
+                    let _ = crate::practice::remove_anchor(dir, &rom_sha, id);
+                }
+                let mut guard = self.emu.lock();
+                if guard.practice.as_ref().is_some_and(|a| a.segment() == id) {
+                    guard.practice = None;
+                }
+                drop(guard);
+                self.save_practice();
+            }
+            PracticeRequest::ClearHistory(id) => {
+                if let Some(seg) = set.segment_mut(id) {
+                    seg.attempts.clear();
+                    seg.resets = 0;
+                }
+                self.save_practice();
+            }
+            PracticeRequest::ExportCsv => self.export_practice_csv(),
+        }
+        self.push_practice(Vec::new(), false);
+    }
+
+    /// Write the current state as segment `id`'s anchor and push its
+    /// thumbnail. Returns whether the anchor was written.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn capture_practice_anchor(&mut self, rom_sha: [u8; 32], id: u32) -> bool {
+        let Some(blob) = self
+            .emu
+            .lock()
+            .nes
+            .as_ref()
+            .map(rustynes_core::Nes::snapshot)
+        else {
+            return false;
+        };
+        let Some(dir) = self.data_dir.as_ref() else {
+            self.ui.set_status(StatusMessage::error(
+                "Practice needs a data directory for its save states",
+            ));
+            return false;
+        };
+        if let Err(e) = crate::practice::save_anchor(dir, &rom_sha, id, &blob) {
+            self.ui.set_status(StatusMessage::error(format!(
+                "Practice state not saved: {e}"
+            )));
+            return false;
+        }
+        let thumbs = rustynes_core::Nes::extract_thumbnail(&blob)
+            .ok()
+            .flatten()
+            .map(|rgba| vec![(id, rgba)])
+            .unwrap_or_default();
+        self.push_practice(thumbs, false);
+        self.ui
+            .set_status(StatusMessage::success("Practice segment anchored here"));
+        true
+    }
+
+    /// v2.5.0 — the practice reload hotkey: load the current segment.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn practice_reload(&mut self) {
+        let current = self
+            .practice
+            .as_ref()
+            .and_then(|(_, set)| set.current())
+            .map(|s| s.id);
+        match current {
+            Some(id) => self.load_practice_segment(id),
+            None => self.ui.set_status(StatusMessage::info(
+                "No practice segment (Movies & Recording > Practice... to add one)",
+            )),
+        }
+    }
+
+    /// Make segment `id` current, load its anchor and start timing an
+    /// attempt. Reloading before the running attempt finished counts a
+    /// reset against its segment.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_practice_segment(&mut self, id: u32) {
+        if self.ra_hardcore_blocks() {
+            self.toast_hardcore("Practice disabled (hardcore)");
+            return;
+        }
+        if self.replay_interaction_locked() {
+            self.ui
+                .set_status(StatusMessage::info("Practice disabled during movie"));
+            return;
+        }
+        let Some((rom_sha, set)) = self.practice.as_mut() else {
+            return;
+        };
+        let Some(idx) = set.segments.iter().position(|s| s.id == id) else {
+            return;
+        };
+        let blob = self.data_dir.as_ref().map_or_else(
+            || Err(std::io::ErrorKind::NotFound.into()),
+            |dir| crate::practice::load_anchor(dir, rom_sha, id),
+        );
+        let blob = match blob {
+            Ok(blob) => blob,
+            Err(e) => {
+                self.ui.set_status(StatusMessage::error(format!(
+                    "Practice state unreadable: {e}"
+                )));
+                return;
+            }
+        };
+        set.current = idx;
+        let seg = &set.segments[idx];
+        let (name, end) = (seg.name.clone(), seg.end.clone());
+        let attempt = crate::practice::Attempt::new(*rom_sha, id, &end);
+        let abandoned = {
+            let mut guard = self.emu.lock();
+            let emu = &mut *guard;
+            let Some(nes) = emu.nes.as_mut() else {
+                return;
+            };
+            if let Err(e) = nes.restore(&blob) {
+                self.ui.set_status(StatusMessage::error(format!(
+                    "Practice restore failed: {e}"
+                )));
+                return;
+            }
+            let abandoned = emu
+                .practice
+                .as_ref()
+                .filter(|a| !a.is_finished())
+                .map(|a| a.segment());
+            emu.practice = match attempt {
+                Ok(mut a) => {
+                    a.arm(nes);
+                    self.practice_error = None;
+                    Some(Box::new(a))
+                }
+                Err(e) => {
+                    self.practice_error = Some(e);
+                    None
+                }
+            };
+            abandoned
+        };
+        if let Some(seg) = abandoned.and_then(|prev| set.segment_mut(prev)) {
+            seg.resets += 1;
+        }
+        self.save_practice();
+        self.push_practice(Vec::new(), false);
+        self.ui
+            .set_status(StatusMessage::info(format!("Practice: {name}")));
+    }
+
+    /// Save every practice attempt as CSV.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_practice_csv(&mut self) {
+        let Some(frame) = self
+            .emu
+            .lock()
+            .nes
+            .as_ref()
+            .map(rustynes_core::Nes::frame_duration)
+        else {
+            return;
+        };
+        let Some((_, set)) = self.practice.as_ref() else {
+            return;
+        };
+        let csv = set.to_csv(frame);
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Export practice attempts")
+            .add_filter("CSV", &["csv"])
+            .set_file_name(format!("{}-practice.csv", self.rom_label))
+            .save_file()
+        else {
+            return;
+        };
+        let status = match crate::atomic_write::write_atomic(&path, csv.as_bytes()) {
+            Ok(()) => StatusMessage::success(format!("Attempts exported: {}", path.display())),
+            Err(e) => StatusMessage::error(format!("Attempts export failed: {e}")),
+        };
+        self.ui.set_status(status);
+    }
+
+    /// v2.5.0 — the per-frame autosplitter and practice bookkeeping.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn pump_speedrun_tools(&mut self) {
+        self.pump_autosplit();
+        self.pump_practice();
+    }
+
+    /// v2.5.0 — record a finished practice attempt and refresh the Practice
+    /// panel's attempt read-out.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn pump_practice(&mut self) {
+        let pumped = {
+            let mut guard = self.emu.lock();
+            guard.practice.as_mut().map(|a| {
+                (
+                    a.take_result().map(|frames| (a.segment(), frames)),
+                    a.view(),
+                )
+            })
+        };
+        let (finished, view) = pumped.unzip();
+        if let (Some((id, frames)), Some(view)) = (finished.flatten(), view.as_ref())
+            && let Some(seg) = self
+                .practice
+                .as_mut()
+                .and_then(|(_, set)| set.segment_mut(id))
         {
-            return; // stale menu click past the side count.
+            seg.attempts.push(frames);
+            let best = seg.stats().map_or(frames, |s| s.best);
+            let msg = format!(
+                "{}: {} (best {})",
+                seg.name,
+                view.time(),
+                crate::autosplit::format_time(best, view.frame)
+            );
+            self.ui.set_status(if best == frames {
+                StatusMessage::success(msg)
+            } else {
+                StatusMessage::info(msg)
+            });
+            self.save_practice();
+            self.push_practice(Vec::new(), false);
         }
-        nes.set_disk_side(side);
-        match side {
-            Some(s) => eprintln!("rustynes: FDS disk -> Side {}/{count}", s + 1),
-            None => eprintln!("rustynes: FDS disk ejected"),
+        let error = self.practice_error.clone();
+        if let Some(d) = self.debugger.as_mut() {
+            d.set_practice_status(view, error);
         }
     }
 
@@ -2616,7 +3431,7 @@ impl App {
     /// finish the movie, serialize it, and prompt for a `.rnm` save path
     /// via the rfd dialog. No-op if no ROM is loaded.
     #[cfg(not(target_arch = "wasm32"))]
-    fn handle_movie_record_toggle(&self) {
+    fn handle_movie_record_toggle(&mut self) {
         if self.emu.lock().movie.is_recording() {
             // Finish under a short lock; the (blocking) rfd save dialog runs
             // with the guard dropped.
@@ -2643,7 +3458,12 @@ impl App {
             // attestation recorded under it could never verify. Refusing up
             // front is clearer than emitting one that gets silently dropped.
             let attest = self.config.input.run_ahead == 0;
-            emu.movie.start_recording_power_on(nes, attest);
+            if let Err(e) = emu.movie.start_recording_power_on(nes, attest) {
+                drop(guard);
+                self.ui
+                    .set_status(StatusMessage::error(format!("Can't record: {e}")));
+                return;
+            }
             if !attest {
                 eprintln!(
                     "rustynes: movie attestation skipped (run-ahead is enabled; \
@@ -2656,6 +3476,28 @@ impl App {
         }
     }
 
+    /// v2.5.0 — a recording the movie hook ended because a device it can't
+    /// record was attached (see [`crate::movie_ui::MovieUi::take_halted`]):
+    /// say why, then offer the frames recorded so far as the record toggle's
+    /// stop would.
+    fn surface_halted_recording(&mut self) {
+        let Some((movie, why)) = self.emu.lock().movie.take_halted() else {
+            return;
+        };
+        self.ui
+            .set_status(StatusMessage::error(format!("Recording stopped: {why}")));
+        #[cfg(not(target_arch = "wasm32"))]
+        self.movie_save_dialog(&movie);
+        #[cfg(target_arch = "wasm32")]
+        crate::wasm_io::save_file_with_fallback(
+            "rustynes-movie.rnm",
+            "RustyNES TAS movie",
+            ".rnm",
+            "application/octet-stream",
+            movie.serialize(),
+        );
+    }
+
     /// `F7` — toggle TAS movie playback (native).
     ///
     /// **Start**: open a `.rnm` file via the rfd dialog, deserialize it,
@@ -2735,7 +3577,7 @@ impl App {
     /// the user can diverge from a replayed run and record their own
     /// continuation. No-op if no ROM is loaded.
     #[cfg(not(target_arch = "wasm32"))]
-    fn handle_movie_branch(&self) {
+    fn handle_movie_branch(&mut self) {
         let mut guard = self.emu.lock();
         let emu = &mut *guard;
         let Some(nes) = emu.nes.as_ref() else {
@@ -2744,7 +3586,12 @@ impl App {
         };
         // v2.3.2 "Lucid": see the power-on path for the run-ahead caveat.
         let attest = self.config.input.run_ahead == 0;
-        emu.movie.start_recording_branch(nes, attest);
+        if let Err(e) = emu.movie.start_recording_branch(nes, attest) {
+            drop(guard);
+            self.ui
+                .set_status(StatusMessage::error(format!("Can't record: {e}")));
+            return;
+        }
         eprintln!("rustynes: movie branch — recording from current state");
     }
 
@@ -3530,7 +4377,7 @@ impl App {
     /// browser download of the `.rnm` bytes (the `rfd` save dialog has no
     /// web equivalent). No-op if no ROM is loaded.
     #[cfg(target_arch = "wasm32")]
-    fn handle_movie_record_toggle_wasm(&self) {
+    fn handle_movie_record_toggle_wasm(&mut self) {
         let mut guard = self.emu.lock();
         let emu = &mut *guard;
         if emu.movie.is_recording() {
@@ -3565,7 +4412,12 @@ impl App {
             // attestation recorded under it could never verify. Refusing up
             // front is clearer than emitting one that gets silently dropped.
             let attest = self.config.input.run_ahead == 0;
-            emu.movie.start_recording_power_on(nes, attest);
+            if let Err(e) = emu.movie.start_recording_power_on(nes, attest) {
+                drop(guard);
+                self.ui
+                    .set_status(StatusMessage::error(format!("Can't record: {e}")));
+                return;
+            }
             if !attest {
                 eprintln!(
                     "rustynes: movie attestation skipped (run-ahead is enabled; \
@@ -3601,7 +4453,7 @@ impl App {
     /// v1.6.0 Sprint 4 — F8 branch the current state into a new recording
     /// (wasm32). The browser counterpart of [`Self::handle_movie_branch`].
     #[cfg(target_arch = "wasm32")]
-    fn handle_movie_branch_wasm(&self) {
+    fn handle_movie_branch_wasm(&mut self) {
         let mut guard = self.emu.lock();
         let emu = &mut *guard;
         let Some(nes) = emu.nes.as_ref() else {
@@ -3610,7 +4462,12 @@ impl App {
         };
         // v2.3.2 "Lucid": see the power-on path for the run-ahead caveat.
         let attest = self.config.input.run_ahead == 0;
-        emu.movie.start_recording_branch(nes, attest);
+        if let Err(e) = emu.movie.start_recording_branch(nes, attest) {
+            drop(guard);
+            self.ui
+                .set_status(StatusMessage::error(format!("Can't record: {e}")));
+            return;
+        }
         crate::wasm_io::log("movie branch — recording from current state");
     }
 
@@ -3685,16 +4542,17 @@ impl App {
 
     /// v2.2.0 — build an FDS `Nes` from `self.rom_bytes` (the uploaded disk)
     /// plus the uploaded BIOS (wasm32). There is no writable-disk `.fds.sav`
-    /// on wasm (no filesystem). Returns `None` (logging a hint) when the BIOS
-    /// has not been uploaded yet, so the caller keeps waiting.
+    /// on wasm (no filesystem). Without an uploaded BIOS the disk boots on the
+    /// built-in HLE BIOS; an upload applies from the next disk load. Returns
+    /// `None` (logging) when the disk fails to parse.
     #[cfg(target_arch = "wasm32")]
     fn build_fds_nes_wasm(&self, sample_rate: u32) -> Option<Nes> {
-        let Some(bios) = self.fds_bios_bytes.clone() else {
+        let bios = self.fds_bios_bytes.clone().unwrap_or_else(|| {
             crate::wasm_io::log(
-                "FDS disk loaded, but no BIOS yet — upload disksys.rom via the FDS BIOS button",
+                "No disksys.rom uploaded — booting with the built-in HLE BIOS (upload one via the FDS BIOS button for the real BIOS)",
             );
-            return None;
-        };
+            rustynes_core::rustynes_mappers::fds_hle_bios()
+        });
         match Nes::from_disk_with_sample_rate(&self.rom_bytes, &bios, sample_rate) {
             Ok(n) => Some(n),
             Err(e) => {
@@ -3724,7 +4582,7 @@ impl App {
             rustynes_core::Region::Dendy => ("Dendy", 50),
             rustynes_core::Region::Ntsc => ("NTSC", 60),
         };
-        let port2 = match self.config.input.expansion_device {
+        let port2 = match self.expansion_device() {
             ExpansionDevice::None => "Standard pad",
             ExpansionDevice::Zapper => "Zapper",
             ExpansionDevice::Vaus => "Arkanoid Vaus",
@@ -3735,6 +4593,13 @@ impl App {
             ExpansionDevice::SuborKeyboard => "Subor keyboard",
             ExpansionDevice::KonamiHyperShot => "Konami Hyper Shot",
             ExpansionDevice::BandaiHyperShot => "Bandai Hyper Shot",
+            ExpansionDevice::OekaKidsTablet => "Oeka Kids tablet",
+            ExpansionDevice::HoriTrack => "Hori Track",
+            ExpansionDevice::Pachinko => "Pachinko controller",
+            ExpansionDevice::TopRider => "Top Rider",
+            ExpansionDevice::PowerGlove => "Power Glove",
+            ExpansionDevice::TurboFile => "Turbo File",
+            ExpansionDevice::TurboFileTwin => "Turbo File Twin",
         };
         crate::movie_ui::ReplayInfo {
             region: region_label,
@@ -3745,15 +4610,49 @@ impl App {
         }
     }
 
+    /// v2.5.0 — plug the Famicom 3D System glasses in or out of the running
+    /// `Nes` per `[graphics] famicom_3d`, and hand the stereo mode to the
+    /// produce path's presenter. Called after a ROM loads and whenever the
+    /// setting changes.
+    fn sync_famicom_3d(&self) {
+        let mode = self.config.graphics.famicom_3d;
+        let mut guard = self.emu.lock();
+        let emu = &mut *guard;
+        emu.stereo
+            .configure(mode, self.config.graphics.famicom_3d_swap_eyes);
+        if let Some(nes) = emu.nes.as_mut()
+            && nes.stereo_eye().is_some() != mode.enabled()
+        {
+            nes.set_famicom_3d(mode.enabled());
+        }
+    }
+
+    /// v2.5.0 — the effective player-2 port device: the loaded ROM's
+    /// per-game choice when it has one, else `[input] expansion_device`.
+    const fn expansion_device(&self) -> crate::config::ExpansionDevice {
+        match self.per_game_expansion {
+            Some(device) => device,
+            None => self.config.input.expansion_device,
+        }
+    }
+
     /// v2.1.0 — (re)attach the configured non-standard device on the player-2
     /// port, or detach it (returning to the standard controller). Called after
     /// a ROM loads and whenever the device selection changes.
     #[cfg(not(target_arch = "wasm32"))]
     fn sync_expansion_device(&self) {
         use crate::config::ExpansionDevice;
-        let device = self.config.input.expansion_device;
+        let device = self.expansion_device();
         let mut guard = self.emu.lock();
+        // Persist a Turbo File before any swap below can drop it.
+        guard.flush_turbo_file(self.data_dir.as_deref());
         if let Some(nes) = guard.nes.as_mut() {
+            if !matches!(
+                device,
+                ExpansionDevice::TurboFile | ExpansionDevice::TurboFileTwin
+            ) {
+                nes.set_turbo_file_image(None);
+            }
             match device {
                 ExpansionDevice::None => nes.set_expansion_device(1, None),
                 ExpansionDevice::Zapper => {
@@ -3783,6 +4682,37 @@ impl App {
                 ExpansionDevice::BandaiHyperShot => {
                     nes.set_bandai_hyper_shot(1, 0);
                 }
+                ExpansionDevice::OekaKidsTablet => {
+                    nes.set_oeka_kids_tablet(1, u16::MAX, u16::MAX, false, false);
+                }
+                ExpansionDevice::HoriTrack => nes.set_hori_track(1, 0, 0, 0),
+                ExpansionDevice::Pachinko => nes.set_pachinko(1, 0, 0),
+                ExpansionDevice::TopRider => nes.set_top_rider(1, 0, 0, 0),
+                ExpansionDevice::PowerGlove => nes.set_power_glove(
+                    1,
+                    rustynes_core::PowerGloveHand::default(),
+                    self.config.input.power_glove_program.program(),
+                ),
+                // Only (re)loaded when the device kind changes, so flipping
+                // the Twin's bank switch keeps the image in place.
+                ExpansionDevice::TurboFile | ExpansionDevice::TurboFileTwin => {
+                    let twin = matches!(device, ExpansionDevice::TurboFileTwin);
+                    if nes
+                        .turbo_file_image()
+                        .map(rustynes_core::TurboFileImage::is_twin)
+                        != Some(twin)
+                    {
+                        let image = crate::emu::load_turbo_file(self.data_dir.as_deref(), twin);
+                        nes.set_turbo_file_image(Some(image));
+                    }
+                    nes.set_turbo_file(1, self.config.input.turbo_file_bank);
+                }
+            }
+            // v2.5.0 — R.O.B. Only (re)built when the play field changes, so a
+            // re-sync from an unrelated Input edit keeps the robot's pose.
+            let want = self.config.input.rob.mode();
+            if nes.rob().map(rustynes_core::Rob::mode) != want {
+                nes.set_rob(want.map(rustynes_core::Rob::new));
             }
         }
     }
@@ -3901,10 +4831,10 @@ impl App {
             // which reported "on screen" for a cursor sitting on a black bar.
             // The knob keeps the full-window sweep, matching the Vaus branch of
             // `mouse_nes` for the same reason given there.
-            let (knob, on_screen) = self.cursor_pos.map_or((0x80u8, false), |(cx, cy)| {
+            let (knob, cursor_nes) = self.cursor_pos.map_or((0x80u8, None), |(cx, cy)| {
                 let (ww, wh) = self.window_size;
                 let nx = (cx / f64::from(ww.max(1))) * 256.0;
-                let on = crate::gfx::window_to_nes_pixel(
+                let pixel = crate::gfx::window_to_nes_pixel(
                     ww,
                     wh,
                     self.config.ui.pixel_aspect_correction,
@@ -3914,12 +4844,12 @@ impl App {
                     ),
                     cx,
                     cy,
-                )
-                .is_some();
+                );
                 #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
                 let knob = nx.clamp(0.0, 255.0) as u8;
-                (knob, on)
+                (knob, pixel)
             });
+            let on_screen = cursor_nes.is_some();
             #[allow(clippy::cast_possible_truncation)]
             let (mdx, mdy) = {
                 let (ax, ay) = self.mouse_motion_accum;
@@ -3936,8 +4866,10 @@ impl App {
             )
             .unwrap_or(u8::MAX);
             let khs = self.input.konami_hyper_shot();
-            match self.config.input.expansion_device {
-                ExpansionDevice::None => ExpansionMini::None,
+            match self.expansion_device() {
+                ExpansionDevice::None
+                | ExpansionDevice::TurboFile
+                | ExpansionDevice::TurboFileTwin => ExpansionMini::None,
                 ExpansionDevice::Zapper => ExpansionMini::Zapper {
                     trigger: self.mouse_pressed,
                     on_screen,
@@ -3977,6 +4909,17 @@ impl App {
                 ExpansionDevice::BandaiHyperShot => ExpansionMini::BandaiHyperShot {
                     mask: self.input.bandai_hyper_shot(),
                 },
+                ExpansionDevice::OekaKidsTablet => ExpansionMini::OekaKidsTablet {
+                    touch: self.mouse_pressed,
+                    button: self.mouse_right_pressed,
+                    on_screen,
+                },
+                ExpansionDevice::HoriTrack
+                | ExpansionDevice::Pachinko
+                | ExpansionDevice::TopRider
+                | ExpansionDevice::PowerGlove => {
+                    self.analog_miniature(pads[0], (mdx, mdy), cursor_nes)
+                }
             }
         };
         MiniaturesSnapshot {
@@ -3986,8 +4929,74 @@ impl App {
         }
     }
 
+    /// v2.5.0 — the input-miniature for an analog expansion controller, from
+    /// the same mappings the latch feeds the core.
+    fn analog_miniature(
+        &self,
+        pad: Buttons,
+        (mdx, mdy): (i16, i16),
+        cursor_nes: Option<(u16, u16)>,
+    ) -> crate::debugger::ExpansionMini {
+        use crate::config::ExpansionDevice;
+        use crate::debugger::ExpansionMini;
+        let analog = self.input.analog(0);
+        match self.expansion_device() {
+            ExpansionDevice::HoriTrack => {
+                let (dx, dy) = crate::input::hori_track_motion((mdx, mdy), analog);
+                let to_i8 = |v: i16| i8::try_from((v * 16).clamp(-127, 127)).unwrap_or(0);
+                ExpansionMini::Analog {
+                    name: "Hori Track",
+                    x: to_i8(dx),
+                    y: to_i8(-dy),
+                    level: 0,
+                    buttons: pad.bits(),
+                }
+            }
+            ExpansionDevice::Pachinko => ExpansionMini::Analog {
+                name: "Pachinko",
+                x: 0,
+                y: 0,
+                level: crate::input::pachinko_plunger(
+                    analog,
+                    cursor_nes.filter(|_| self.mouse_pressed).map(|(_, y)| y),
+                ),
+                buttons: pad.bits(),
+            },
+            ExpansionDevice::TopRider => {
+                let (steer, throttle, keys) = crate::input::top_rider_controls(pad, analog);
+                ExpansionMini::Analog {
+                    name: "Top Rider",
+                    x: steer * 42,
+                    y: 0,
+                    level: throttle * 85,
+                    buttons: keys,
+                }
+            }
+            ExpansionDevice::PowerGlove => {
+                let hand = crate::input::power_glove_hand(
+                    cursor_nes.unwrap_or((u16::MAX, u16::MAX)),
+                    self.mouse_pressed,
+                    self.mouse_right_pressed,
+                    pad,
+                    analog,
+                );
+                ExpansionMini::Analog {
+                    name: "Power Glove",
+                    x: hand.x,
+                    y: hand.y,
+                    level: hand.z.cast_unsigned().wrapping_add(128),
+                    buttons: hand.keypad,
+                }
+            }
+            _ => ExpansionMini::None,
+        }
+    }
+
     /// Build the per-pace input snapshot for the emulation core from the
     /// winit-thread-resident input state (keyboard maps, gilrs, mouse).
+    // A flat field-per-input struct literal; splitting it would only scatter
+    // the per-field provenance comments.
+    #[allow(clippy::too_many_lines)]
     fn frame_inputs(&self) -> crate::emu::FrameInputs {
         let hardcore_blocked = self.ra_hardcore_blocks();
         // v1.2.0 Workstream F1/F2 — fold the on-screen touch overlay into the
@@ -4032,15 +5041,15 @@ impl App {
             // v2.2.0 "Capstone" — Famicom microphone hold-to-talk (native only;
             // no key source on wasm). Byte-identical when released.
             #[cfg(not(target_arch = "wasm32"))]
-            microphone: self.input.microphone(),
+            microphone: self.microphone_pattern(),
             #[cfg(target_arch = "wasm32")]
-            microphone: false,
+            microphone: 0,
             // v2.7.0 — RA hardcore disables rewind; fold the gate here.
             rewind_held: self.input.rewind_held() && !hardcore_blocked,
             hardcore_blocked,
             run_ahead: self.config.input.run_ahead,
             #[cfg(not(target_arch = "wasm32"))]
-            expansion: self.config.input.expansion_device,
+            expansion: self.expansion_device(),
             // Map the cursor (physical window px) to the 256x240 NES screen.
             //
             // v2.3.6 workstream 0, defect 2 — the ZAPPER aim now inverts the
@@ -4062,7 +5071,7 @@ impl App {
             #[cfg(not(target_arch = "wasm32"))]
             mouse_nes: self.cursor_pos.map_or((u16::MAX, u16::MAX), |(cx, cy)| {
                 if matches!(
-                    self.config.input.expansion_device,
+                    self.expansion_device(),
                     crate::config::ExpansionDevice::Vaus
                 ) {
                     let (ww, wh) = self.window_size;
@@ -4136,6 +5145,12 @@ impl App {
             konami_hyper_shot: self.input.konami_hyper_shot(),
             #[cfg(not(target_arch = "wasm32"))]
             bandai_hyper_shot: self.input.bandai_hyper_shot(),
+            // v2.5.0 — the first pad's sticks / triggers for the analog
+            // expansion controllers.
+            #[cfg(not(target_arch = "wasm32"))]
+            analog: self.input.analog(0),
+            #[cfg(not(target_arch = "wasm32"))]
+            power_glove_program: self.config.input.power_glove_program.program(),
         }
     }
 
@@ -4545,6 +5560,13 @@ impl App {
     fn do_reset(&mut self) {
         {
             let mut guard = self.emu.lock();
+            // v2.5.0 — a recording replays the reset on the next frame; when
+            // that frame already holds a command, hold the reset back.
+            if !guard.movie.record_command(MovieCommand::SoftReset) {
+                drop(guard);
+                self.refuse_movie_command();
+                return;
+            }
             if let Some(nes) = guard.nes.as_mut() {
                 nes.reset();
                 // v1.0.0 (UX3 BUG-3) — re-apply the configured Game Genie codes
@@ -4666,6 +5688,12 @@ impl App {
             MenuAction::SetDiskSide(side) => {
                 self.set_disk_side(side);
             }
+            MenuAction::Tape(action) => {
+                self.tape_action(action);
+            }
+            MenuAction::Ghost(action) => {
+                self.ghost_action(action);
+            }
             MenuAction::Screenshot => {
                 #[cfg(not(target_arch = "wasm32"))]
                 self.take_screenshot();
@@ -4791,8 +5819,13 @@ impl App {
                 let mut guard = self.emu.lock();
                 let emu = &mut *guard;
                 if let Some(nes) = emu.nes.as_mut() {
-                    nes.insert_coin(0);
-                    emu.vs_coin_frames = VS_COIN_HOLD_FRAMES;
+                    if emu.movie.record_command(MovieCommand::InsertCoin(0)) {
+                        nes.insert_coin(0);
+                        emu.vs_coin_frames = VS_COIN_HOLD_FRAMES;
+                    } else {
+                        drop(guard);
+                        self.refuse_movie_command();
+                    }
                 } else if let Some(dual) = emu.dual.as_mut() {
                     // v2.1.2 F2.1 — coin into the MAIN cabinet acceptor.
                     dual.insert_coin(0);
@@ -4966,6 +5999,43 @@ impl App {
     /// is the determinism-safe default the app always launches at.
     const SPEED_PRESETS: [f32; 7] = [0.25, 0.5, 0.75, 1.0, 1.5, 2.0, 3.0];
 
+    /// v2.5.0 — this frame's Famicom-mic slice pattern: the held
+    /// hold-to-talk key turns every slice on, combined with the host capture's
+    /// per-slice levels.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn microphone_pattern(&self) -> u8 {
+        let key = if self.input.microphone() { 0xFF } else { 0 };
+        key | self
+            .mic_capture
+            .as_ref()
+            .map_or(0, crate::mic_capture::MicCapture::pattern)
+    }
+
+    /// v2.5.0 — open (or close) the Famicom-microphone host capture to match
+    /// `[input] microphone_*`. Called at startup and on every capture-setting
+    /// edit; a device that fails to open is reported and capture stays off.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn sync_mic_capture(&mut self) {
+        // Drop first so a re-open of the same device never holds it twice.
+        self.mic_capture = None;
+        let input = &self.config.input;
+        if !input.microphone_capture {
+            return;
+        }
+        match crate::mic_capture::MicCapture::open(
+            input.microphone_device.as_deref(),
+            input.microphone_threshold,
+            input.microphone_hysteresis,
+        ) {
+            Ok(capture) => self.mic_capture = Some(capture),
+            Err(e) => self
+                .ui
+                .set_status(crate::ui_shell::StatusMessage::error(format!(
+                    "Microphone capture unavailable: {e}"
+                ))),
+        }
+    }
+
     /// v1.0.0 — apply the configured master volume / mute to the live audio
     /// output gain (the single cpal consume point). Native-only; cheap, called
     /// at startup + on every volume / mute edit. No-op when audio is disabled.
@@ -6008,6 +7078,16 @@ impl App {
     /// corruption, the default power-up palette is all-zero, and RAM powers up
     /// zeroed. All configured behavior is deterministic (seeded / uniform fills).
     fn apply_ppu_hardware_config(&self) {
+        let mut guard = self.emu.lock();
+        if let Some(nes) = guard.nes.as_mut() {
+            self.configure_ppu_hardware(nes);
+        }
+    }
+
+    /// Push the `[emulation]` hardware knobs into `nes`. Split out of
+    /// [`Self::apply_ppu_hardware_config`] so a ghost console boots exactly
+    /// like the live one.
+    fn configure_ppu_hardware(&self, nes: &mut Nes) {
         use rustynes_core::{PaletteInit, PowerOnRam, PpuRevision};
         let revision = if self.config.emulation.ppu_oamaddr_corruption {
             PpuRevision::Rp2c02G
@@ -6024,18 +7104,15 @@ impl App {
         } else {
             PowerOnRam::Zeroed
         };
-        let mut guard = self.emu.lock();
-        if let Some(nes) = guard.nes.as_mut() {
-            nes.set_ppu_revision(revision);
-            nes.set_power_up_palette(palette);
-            nes.set_power_on_ram(ram);
-            // v2.2.3 — the specialized PPU fast dot path. Unlike the three
-            // knobs above this is a performance selector, not an accuracy
-            // model: both paths emit the identical frame (pinned every frame
-            // by `fast_dotloop_diff`), so pushing it here is purely about
-            // honouring the user's escape hatch. Default on.
-            nes.set_fast_dotloop(self.config.emulation.fast_dotloop);
-        }
+        nes.set_ppu_revision(revision);
+        nes.set_power_up_palette(palette);
+        nes.set_power_on_ram(ram);
+        // v2.2.3 — the specialized PPU fast dot path. Unlike the three
+        // knobs above this is a performance selector, not an accuracy
+        // model: both paths emit the identical frame (pinned every frame
+        // by `fast_dotloop_diff`), so pushing it here is purely about
+        // honouring the user's escape hatch. Default on.
+        nes.set_fast_dotloop(self.config.emulation.fast_dotloop);
     }
 
     /// v1.4.0 Workstream C — query the loaded mapper's expansion-audio chip name
@@ -6423,8 +7500,13 @@ impl App {
                 let mut guard = self.emu.lock();
                 let emu = &mut *guard;
                 if let Some(nes) = emu.nes.as_mut() {
-                    nes.insert_coin(0);
-                    emu.vs_coin_frames = VS_COIN_HOLD_FRAMES;
+                    if emu.movie.record_command(MovieCommand::InsertCoin(0)) {
+                        nes.insert_coin(0);
+                        emu.vs_coin_frames = VS_COIN_HOLD_FRAMES;
+                    } else {
+                        drop(guard);
+                        self.refuse_movie_command();
+                    }
                 } else if let Some(dual) = emu.dual.as_mut() {
                     // v2.1.2 F2.1 — coin into the MAIN cabinet acceptor.
                     dual.insert_coin(0);
@@ -6447,6 +7529,11 @@ impl App {
             SysAction::SpeedUp => self.step_speed(true),
             SysAction::SpeedDown => self.step_speed(false),
             SysAction::SpeedReset => self.set_speed(1.0),
+            SysAction::PracticeReload => {
+                // v2.5.0 — practice segments are native-only (save-state files).
+                #[cfg(not(target_arch = "wasm32"))]
+                self.practice_reload();
+            }
         }
     }
 
@@ -6570,6 +7657,13 @@ impl App {
     fn do_power_cycle(&mut self) {
         {
             let mut guard = self.emu.lock();
+            // v2.5.0 — a recording replays the power cycle on the next frame;
+            // when that frame already holds a command, hold it back.
+            if !guard.movie.record_command(MovieCommand::PowerCycle) {
+                drop(guard);
+                self.refuse_movie_command();
+                return;
+            }
             if let Some(nes) = guard.nes.as_mut() {
                 nes.power_cycle();
                 // v1.0.0 (UX3 BUG-3) — re-apply the configured Game Genie codes
@@ -6620,6 +7714,15 @@ impl App {
         ra.reset(&mut |a| nes.cpu_bus_peek(a));
     }
 
+    /// v2.5.0 — a console command arrived for a frame whose recorded command
+    /// slot is already taken (see [`crate::movie_ui::MovieUi::record_command`]);
+    /// it was held back rather than run unrecorded, so say so.
+    fn refuse_movie_command(&mut self) {
+        self.ui.set_status(StatusMessage::info(
+            "Recording: one console command per frame, try again",
+        ));
+    }
+
     /// v2.7.0 — log a "blocked in hardcore mode" message (and, when an RA
     /// session is active, surface it as an on-screen toast). A plain no-op-ish
     /// helper available in both feature states so the gated call sites compile
@@ -6648,6 +7751,9 @@ impl App {
     #[cfg(not(target_arch = "wasm32"))]
     fn produce_one_frame_netplay(&mut self) {
         let raw_local = self.input.player1();
+        // The player-2-port expansion device rides with the input; the
+        // session keeps it only on the P2 seat (its own port).
+        let device = crate::emu::expansion_device_input(&self.frame_inputs());
         let turbo_mask = self.turbo_mask();
         let turbo_period = self.config.input.turbo_period;
         let mut guard = self.emu.lock();
@@ -6659,7 +7765,7 @@ impl App {
         // is sent: the gated bits are what cross the wire + are stored in the
         // rollback ring, so both peers replay them verbatim (deterministic).
         let local = crate::emu::apply_turbo(raw_local, nes.frame(), turbo_mask, turbo_period);
-        let tick = self.netplay.tick(nes, local);
+        let tick = self.netplay.tick_with_device(nes, local, device);
 
         // Push the freshly produced frame's audio, mirroring the single-player
         // path. Only on an actual produced frame (a connecting / stalled / error
@@ -6688,12 +7794,34 @@ impl App {
             emu.present_fb.clear();
             emu.present_fb.extend_from_slice(nes.framebuffer());
         }
+        drop(guard);
+        // The authored input carried the mouse motion; a stalled tick's input
+        // is dropped, so the motion keeps accumulating for the next one.
+        if tick.authored_input {
+            self.drain_mouse_motion();
+        }
 
         // Surface the latest status into the debugger HUD + panel.
         let status = self.netplay.status();
         if let Some(debugger) = self.debugger.as_mut() {
             debugger.set_netplay_status(netplay_status_view(&status));
         }
+
+        // Toast each chat line the other players sent and add it to the
+        // panel's chat pane.
+        for line in self.netplay.take_chat() {
+            self.ui.set_status(StatusMessage::info(format!(
+                "P{}: {}",
+                line.from + 1,
+                line.text
+            )));
+            if let Some(debugger) = self.debugger.as_mut() {
+                debugger.push_netplay_chat(crate::debugger::ChatEntryView {
+                    from: line.from,
+                    text: line.text,
+                });
+            }
+        }
     }
 
     /// v2.7.0 — the browser (WebRTC) netplay produce path, used in place of the
@@ -6732,15 +7860,24 @@ impl App {
             emu.present_fb.clear();
             emu.present_fb.extend_from_slice(nes.framebuffer());
         }
-        // Surface the latest status into the lobby UI.
+        // Surface the latest status into the lobby UI, and toast each chat
+        // line the other players sent.
         let phase = driver.phase();
         let message = driver.message();
         self.wasm_lobby.set_status(phase, message);
+        for line in driver.take_chat() {
+            self.ui.set_status(StatusMessage::info(format!(
+                "P{}: {}",
+                line.from + 1,
+                line.text
+            )));
+            self.wasm_lobby.push_chat(line, false);
+        }
     }
 
-    /// v2.7.0 — act on a browser-netplay lobby request (connect / leave). A
-    /// Connect is rejected (logged) when no ROM is loaded (the session needs the
-    /// ROM hash for the handshake). wasm-only.
+    /// v2.7.0 — act on a browser-netplay lobby request (connect / chat /
+    /// leave). A Connect is rejected (logged) when no ROM is loaded (the
+    /// session needs the ROM hash for the handshake). wasm-only.
     #[cfg(target_arch = "wasm32")]
     fn handle_lobby_request(&mut self, req: crate::wasm_lobby::LobbyRequest) {
         use crate::wasm_lobby::LobbyRequest;
@@ -6754,9 +7891,34 @@ impl App {
                     String::new(),
                 );
             }
+            LobbyRequest::Chat { text } => {
+                let sent = self
+                    .browser_netplay
+                    .as_mut()
+                    .map_or(Ok(false), |driver| driver.send_chat(&text));
+                match sent {
+                    Ok(true) => {
+                        let from = self
+                            .browser_netplay
+                            .as_ref()
+                            .map_or(0, crate::wasm_netplay::BrowserNetplay::slot);
+                        self.wasm_lobby
+                            .push_chat(rustynes_netplay::ChatLine { from, text }, true);
+                    }
+                    Ok(false) => {
+                        self.ui
+                            .set_status(StatusMessage::info("Chat needs a connection"));
+                    }
+                    Err(e) => {
+                        self.ui
+                            .set_status(StatusMessage::info(format!("Chat not sent: {e}")));
+                    }
+                }
+            }
             LobbyRequest::Connect {
                 signaling_url,
                 room,
+                password,
                 host: _,
                 num_players,
             } => {
@@ -6766,8 +7928,9 @@ impl App {
                 };
                 let mut driver = crate::wasm_netplay::BrowserNetplay::new(rom_hash);
                 driver.set_num_players(num_players);
+                driver.set_game(&self.rom_label);
                 let ice = self.config.netplay.stun_servers.clone();
-                match driver.connect(&signaling_url, &room, &ice) {
+                match driver.connect(&signaling_url, &room, &password, &ice) {
                     Ok(()) => {
                         self.browser_netplay = Some(driver);
                     }
@@ -6782,7 +7945,66 @@ impl App {
         }
     }
 
-    /// v2.3.0 — act on a netplay panel request (host / join / leave).
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Leave the netplay session and put back everything it borrowed: the
+    /// user's own settings and save, and the emulation thread.
+    fn leave_netplay(&mut self) {
+        self.netplay.leave();
+        if let Some(recording) = self.netplay.take_recording() {
+            self.save_match_recording(recording);
+        }
+        // A joiner that adopted the host's emulation settings gets its
+        // own back now that the session is over.
+        if let Some(own) = self.netplay.take_restore_settings()
+            && let Some(nes) = self.emu.lock().nes.as_mut()
+        {
+            own.apply(nes);
+        }
+        // Every peer gets its pre-session battery RAM / FDS disk
+        // back, except a host that keeps the session's writes.
+        if let Some(own) = self.netplay.take_restore_save()
+            && let Some(nes) = self.emu.lock().nes.as_mut()
+        {
+            own.apply(nes);
+        }
+        // v2.8.0 Phase 5 increment 3 — netplay released: resume the
+        // emulation thread (single-player produce returns to it). The
+        // thread also re-bases its pacer from `next_frame_time`.
+        #[cfg(all(not(target_arch = "wasm32"), feature = "emu-thread"))]
+        if let Some(thread) = self.emu_thread.as_ref() {
+            self.emu.lock().next_frame_time = Some(Instant::now());
+            thread.control().set_netplay_paused(false);
+        }
+        // Clear the HUD back to Idle.
+        if let Some(debugger) = self.debugger.as_mut() {
+            debugger.set_netplay_status(netplay_status_view(&self.netplay.status()));
+        }
+    }
+
+    /// Send a chat line typed in the netplay panel and echo it into the chat
+    /// pane, or toast why it was not sent. Native-only.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn send_netplay_chat(&mut self, text: String) {
+        match self.netplay.send_chat(&text) {
+            Ok(true) => {
+                let from = self.netplay.status().diagnostics.local_player;
+                if let Some(debugger) = self.debugger.as_mut() {
+                    debugger.push_netplay_chat(crate::debugger::ChatEntryView { from, text });
+                }
+            }
+            Ok(false) => {
+                self.ui
+                    .set_status(StatusMessage::info("Chat needs a running match"));
+            }
+            Err(e) => {
+                self.ui
+                    .set_status(StatusMessage::info(format!("Chat not sent: {e}")));
+            }
+        }
+    }
+
+    /// v2.3.0 — act on a netplay panel request (host / join / input delay /
+    /// chat / leave).
     ///
     /// Host = player 0 (P1); joiner = player 1 (P2). A host/join is rejected
     /// (logged) when no ROM is loaded, when a TAS movie is recording/playing
@@ -6792,21 +8014,11 @@ impl App {
     fn handle_netplay_request(&mut self, req: crate::debugger::NetplayRequest) {
         use crate::debugger::NetplayRequest;
         match req {
-            NetplayRequest::Leave => {
-                self.netplay.leave();
-                // v2.8.0 Phase 5 increment 3 — netplay released: resume the
-                // emulation thread (single-player produce returns to it). The
-                // thread also re-bases its pacer from `next_frame_time`.
-                #[cfg(all(not(target_arch = "wasm32"), feature = "emu-thread"))]
-                if let Some(thread) = self.emu_thread.as_ref() {
-                    self.emu.lock().next_frame_time = Some(Instant::now());
-                    thread.control().set_netplay_paused(false);
-                }
-                // Clear the HUD back to Idle.
-                if let Some(debugger) = self.debugger.as_mut() {
-                    debugger.set_netplay_status(netplay_status_view(&self.netplay.status()));
-                }
+            NetplayRequest::Leave => self.leave_netplay(),
+            NetplayRequest::SetInputDelay { delay } => {
+                self.netplay.schedule_input_delay(delay);
             }
+            NetplayRequest::Chat { text } => self.send_netplay_chat(text),
             NetplayRequest::Host { .. }
             | NetplayRequest::Join { .. }
             | NetplayRequest::Spectate { .. }
@@ -6827,8 +8039,12 @@ impl App {
                      record/playback — stop the movie first"
                 );
             }
-            NetplayRequest::Host { port, num_players } => {
-                let Some(rom_hash) = self.emu.lock().nes.as_ref().map(|n| *n.rom_sha256()) else {
+            NetplayRequest::Host {
+                port,
+                num_players,
+                passphrase,
+            } => {
+                let Some((rom_hash, settings, save)) = self.netplay_identity() else {
                     return;
                 };
                 // v2.8.0 Phase 5 increment 3 — pause the emulation thread
@@ -6839,32 +8055,52 @@ impl App {
                 self.pause_emu_thread_for_netplay();
                 // Host "listen" mode: bind the local port and learn the joiner's
                 // address from its first Sync — no remote to pre-enter or parse.
-                self.netplay.start_host(port, num_players, rom_hash);
-            }
-            NetplayRequest::Join { remote } => {
-                let Some(rom_hash) = self.emu.lock().nes.as_ref().map(|n| *n.rom_sha256()) else {
+                self.netplay
+                    .set_write_back_save(self.config.netplay.write_back_host_save);
+                self.netplay
+                    .set_record_match(self.config.netplay.record_matches);
+                self.netplay
+                    .set_adaptive_input_delay(self.config.netplay.adaptive_input_delay);
+                self.netplay.set_passphrase(&passphrase);
+                self.netplay
+                    .start_host(port, num_players, rom_hash, settings, save);
+            }
+            NetplayRequest::Join { remote, passphrase } => {
+                let Some((rom_hash, settings, save)) = self.netplay_identity() else {
                     return;
                 };
                 match remote.parse::<std::net::SocketAddr>() {
                     Ok(addr) => {
                         #[cfg(all(not(target_arch = "wasm32"), feature = "emu-thread"))]
                         self.pause_emu_thread_for_netplay();
-                        self.netplay.start_join(addr, rom_hash);
+                        self.netplay
+                            .set_record_match(self.config.netplay.record_matches);
+                        self.netplay.set_passphrase(&passphrase);
+                        self.netplay.start_join(
+                            addr,
+                            rom_hash,
+                            settings,
+                            save,
+                            self.config.netplay.use_host_save,
+                        );
                     }
                     Err(e) => eprintln!("rustynes: bad host address {remote:?}: {e}"),
                 }
             }
             // v1.7.0 H8 — read-only spectator: same ROM + emu-thread plumbing as
             // Join, but the spectator never authors input (see `start_spectate`).
-            NetplayRequest::Spectate { remote } => {
-                let Some(rom_hash) = self.emu.lock().nes.as_ref().map(|n| *n.rom_sha256()) else {
+            NetplayRequest::Spectate { remote, passphrase } => {
+                let Some((rom_hash, settings, _)) = self.netplay_identity() else {
                     return;
                 };
                 match remote.parse::<std::net::SocketAddr>() {
                     Ok(addr) => {
                         #[cfg(all(not(target_arch = "wasm32"), feature = "emu-thread"))]
                         self.pause_emu_thread_for_netplay();
-                        self.netplay.start_spectate(addr, rom_hash);
+                        self.netplay
+                            .set_record_match(self.config.netplay.record_matches);
+                        self.netplay.set_passphrase(&passphrase);
+                        self.netplay.start_spectate(addr, rom_hash, &settings);
                     }
                     Err(e) => eprintln!("rustynes: bad host address {remote:?}: {e}"),
                 }
@@ -6872,6 +8108,77 @@ impl App {
         }
     }
 
+    /// Write a finished netplay match recording to
+    /// `<data_dir>/movies/netplay-<unix time>.rnm`, reporting the outcome on
+    /// the status line. An empty recording (the session never confirmed a
+    /// frame) is dropped.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_match_recording(&mut self, recording: rustynes_netplay::MatchRecorder) {
+        let interruption = recording.interruption();
+        let Some(movie) = recording.finish() else {
+            return;
+        };
+        let Some(dir) = self.movies_dir() else {
+            eprintln!("rustynes: no data directory; match recording discarded");
+            return;
+        };
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let path = dir.join(format!("netplay-{stamp}.rnm"));
+        let written =
+            std::fs::create_dir_all(&dir).and_then(|()| std::fs::write(&path, movie.serialize()));
+        let message = match (written, interruption) {
+            (Ok(()), Some(rustynes_netplay::Interruption::Recovery)) => format!(
+                "Match recorded up to a desync recovery ({} frames) -> {}",
+                movie.len(),
+                path.display()
+            ),
+            (Ok(()), Some(rustynes_netplay::Interruption::Device(why))) => format!(
+                "Match recording stopped early: {why} ({} frames) -> {}",
+                movie.len(),
+                path.display()
+            ),
+            (Ok(()), None) => format!(
+                "Match recorded ({} frames) -> {}",
+                movie.len(),
+                path.display()
+            ),
+            (Err(e), _) => format!("Match recording not saved: {e}"),
+        };
+        eprintln!("rustynes: {message}");
+        self.ui.set_status(StatusMessage::info(message));
+    }
+
+    /// The loaded ROM's SHA-256 plus the determinism-relevant emulation
+    /// settings and the battery RAM / FDS disk a netplay handshake
+    /// negotiates. `None` with no ROM loaded.
+    ///
+    /// An FDS game is identified by its pristine disk's hash: the running
+    /// image may have been reloaded from a `.fds.sav`, which the save
+    /// exchange reconciles instead. Pending disk writes are flushed first,
+    /// since the session runs on a copy and suppresses flushing.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn netplay_identity(
+        &self,
+    ) -> Option<(
+        [u8; 32],
+        rustynes_netplay::NetSettings,
+        rustynes_netplay::SessionSave,
+    )> {
+        self.flush_fds_save();
+        let emu = self.emu.lock();
+        let nes = emu.nes.as_ref()?;
+        let settings = rustynes_netplay::NetSettings::capture(nes)
+            .with_sprite_limit_disabled(self.config.enhancements.disable_sprite_limit);
+        let rom_hash = emu.fds_disk_sha256.unwrap_or_else(|| *nes.rom_sha256());
+        Some((
+            rom_hash,
+            settings,
+            rustynes_netplay::SessionSave::capture(nes),
+        ))
+    }
+
     /// v2.8.0 Phase 5 increment 3 — pause the emulation thread for a netplay
     /// session and confirm it has parked, so a stray single-player frame can
     /// never advance the core out from under the rollback session. The
@@ -7267,6 +8574,11 @@ impl App {
     /// which is the failure this whole investigation is chasing.
     #[cfg(not(target_arch = "wasm32"))]
     fn fill_winit_thread_perf(&self, perf_view: &mut crate::perf::PerfView) {
+        if let Some(gfx) = self.gfx.as_ref() {
+            perf_view.present_mode = format!("{:?}", gfx.effective_present_mode());
+            perf_view.present_mode_fell_back = gfx.present_mode_fell_back();
+            perf_view.gpu_ms = gfx.last_gpu_pass_ms();
+        }
         let r = self.render_perf.stats();
         perf_view.render_ui = r.ui;
         perf_view.render_gpu = r.gpu;
@@ -7333,6 +8645,7 @@ impl App {
     }
 
     #[cfg(not(target_arch = "wasm32"))]
+    #[allow(clippy::too_many_lines)]
     fn post_produce_housekeeping(&mut self) {
         // v2.3.0 "Datum II" — refresh detached tool windows once per produced
         // frame, but only those whose content actually changes: `Live` panels
@@ -7351,6 +8664,9 @@ impl App {
         // v2.2.0 — persist the FDS writable disk if it changed this frame.
         // Cheap when clean / non-FDS (a `disk_is_dirty()` check only).
         self.flush_fds_save();
+        // v2.5.0 — likewise a Turbo File a game has just saved to.
+        self.flush_turbo_file();
+        self.surface_halted_recording();
 
         // Push the measured fps + movie status into the debugger so the
         // user can read them from the top toolbar. One scoped lock builds
@@ -7364,6 +8680,7 @@ impl App {
             trace_events,
             step_satisfied,
             step_still_pending,
+            rob,
         ) = {
             let mut guard = self.emu.lock();
             let emu = &mut *guard;
@@ -7425,6 +8742,7 @@ impl App {
                 trace_events,
                 step_satisfied,
                 step_still_pending,
+                emu.nes.as_ref().and_then(|n| n.rob().cloned()),
             )
         };
         // Outside the lock: pausing / panel / status need no core access.
@@ -7442,11 +8760,6 @@ impl App {
             perf_view.drc_ratio = audio.drc_ratio_now();
             perf_view.audio_latency_target_ms = audio.latency_target_ms();
         }
-        if let Some(gfx) = self.gfx.as_ref() {
-            perf_view.present_mode = format!("{:?}", gfx.effective_present_mode());
-            perf_view.present_mode_fell_back = gfx.present_mode_fell_back();
-            perf_view.gpu_ms = gfx.last_gpu_pass_ms();
-        }
         self.fill_winit_thread_perf(&mut perf_view);
         // v2.8.0 — opt-in perf logging (the Perf panel "Logging" checkbox):
         // reconcile the logger with the checkbox, append the interval row,
@@ -7474,11 +8787,13 @@ impl App {
             players: input_players,
             expansion: crate::debugger::ExpansionMini::None,
         };
+        self.pump_speedrun_tools();
         if let Some(debugger) = self.debugger.as_mut() {
             debugger.set_fps(fps);
             // v1.7.0 "Forge" beta.5 (#51) — the single consolidated "Input
             // Display" panel (standard pads + active expansion device).
             debugger.set_input_display(miniatures);
+            debugger.set_rob(rob);
             debugger.set_movie_status(movie_status);
             debugger.set_replay_info(movie_status, replay_info);
             debugger.set_perf_log_note(log_note);
@@ -8303,6 +9618,7 @@ impl App {
                 // the user changed this frame. Cheap (flat primitives + one
                 // String clone); no determinism surface.
                 crate::wasm_share::publish_live(&self.config);
+                self.surface_halted_recording();
 
                 let (fps, movie_status, region, mut perf_view) = {
                     let emu = self.emu.lock();
@@ -8401,6 +9717,8 @@ impl App {
         // enumerated cpal output devices (native-only; cheap one-time scan).
         #[cfg(not(target_arch = "wasm32"))]
         debugger.set_audio_output_devices(AudioOutput::output_device_names());
+        #[cfg(not(target_arch = "wasm32"))]
+        debugger.set_audio_input_devices(crate::mic_capture::MicCapture::input_device_names());
         // v2.8.0 Phase 0 — surface a present-mode fallback instead of
         // silently double-gating the wall-clock pacer against vsync.
         if gfx.present_mode_fell_back() {
@@ -8487,6 +9805,8 @@ impl App {
             // gain now that the queue exists. Default (1.0, not muted) is a
             // no-op so the default sound is byte-identical.
             self.apply_audio_gain();
+            // v2.5.0 — open the Famicom-microphone capture when configured.
+            self.sync_mic_capture();
             // v1.1.0 beta.2 — apply the persisted graphic-EQ params (off by
             // default → byte-identical).
             self.apply_audio_eq();
@@ -8561,8 +9881,8 @@ impl App {
         }
         #[cfg(target_arch = "wasm32")]
         if is_fds_image(&self.rom_bytes) {
-            // wasm: if the BIOS isn't uploaded yet, keep waiting (the user can
-            // upload it, which then retries the build via `set_fds_bios_wasm`).
+            // wasm: boots on the uploaded BIOS, else the built-in HLE BIOS;
+            // only an unparseable disk keeps waiting for another file.
             if let Some(nes) = self.build_fds_nes_wasm(sample_rate) {
                 return self.finish_start_nes(nes, event_loop);
             }
@@ -8624,6 +9944,7 @@ impl App {
         {
             let per_game = crate::game_db::rom_crc32(&self.rom_bytes)
                 .and_then(|crc| crate::per_game::resolve(crc, None));
+            self.per_game_expansion = per_game.as_ref().and_then(|c| c.expansion_device);
             if let Some(cfg) = per_game.as_ref() {
                 if let Some(m) = cfg
                     .overrides
@@ -8685,6 +10006,11 @@ impl App {
         // v1.6.0 — apply this ROM's persisted Game Genie cheats (native).
         #[cfg(not(target_arch = "wasm32"))]
         self.apply_cheats_for_current_rom();
+        // v2.5.0 — and its speedrun splits and practice segments.
+        #[cfg(not(target_arch = "wasm32"))]
+        self.load_splits_for_current_rom();
+        #[cfg(not(target_arch = "wasm32"))]
+        self.load_practice_for_current_rom();
         // v1.0.0 — re-push the per-APU-channel mute mask onto the fresh `Nes`
         // (booted all-on); default 0x3F = byte-identical audio.
         self.apply_apu_channel_mask();
@@ -8712,6 +10038,8 @@ impl App {
         // v2.1.0 — attach the configured non-standard input device (native).
         #[cfg(not(target_arch = "wasm32"))]
         self.sync_expansion_device();
+        // v2.5.0 — Famicom 3D System glasses + stereo presentation.
+        self.sync_famicom_3d();
         // First frame kick. On native this redraw just presents; the
         // wall-clock pacer in `about_to_wait` drives production. On wasm32
         // this is the FIRST `requestAnimationFrame` of the rAF-driven
@@ -9146,6 +10474,17 @@ impl ApplicationHandler<AppEvent> for App {
                 // Track the cursor for the Zapper aim / Vaus paddle position.
                 self.cursor_pos = Some((position.x, position.y));
             }
+            // v2.5.0 — a touchscreen or stylus drives the Oeka Kids pen: the
+            // touch point is the cursor and contact is the pen tip. Other
+            // devices keep their mouse-only mapping.
+            #[cfg(not(target_arch = "wasm32"))]
+            WindowEvent::Touch(touch)
+                if self.expansion_device() == crate::config::ExpansionDevice::OekaKidsTablet =>
+            {
+                use winit::event::TouchPhase;
+                self.cursor_pos = Some((touch.location.x, touch.location.y));
+                self.mouse_pressed = matches!(touch.phase, TouchPhase::Started | TouchPhase::Moved);
+            }
             #[cfg(not(target_arch = "wasm32"))]
             WindowEvent::MouseInput { state, button, .. } => {
                 // v1.0.0 — a click on the always-on shell (a menu / window /
@@ -9593,6 +10932,24 @@ impl ApplicationHandler<AppEvent> for App {
                         },
                     )
                 };
+                // v2.5.0 — Data Recorder transport readout for the Emulation menu.
+                let tape = self
+                    .emu
+                    .lock()
+                    .nes
+                    .as_ref()
+                    .and_then(Nes::data_recorder)
+                    .map(|t| {
+                        #[allow(clippy::cast_precision_loss)] // display-only seconds
+                        let secs = |samples: usize| {
+                            samples as f32 / rustynes_core::TAPE_SAMPLE_RATE as f32
+                        };
+                        crate::ui_shell::TapeStatus {
+                            mode: t.mode(),
+                            position_secs: secs(t.position()),
+                            length_secs: secs(t.len()),
+                        }
+                    });
                 let netplay_active = self.netplay_is_active();
                 let run_ahead = self.config.input.run_ahead;
                 // Keep the shell's save-slot mirror in sync with the app's.
@@ -9604,6 +10961,11 @@ impl ApplicationHandler<AppEvent> for App {
                     netplay_active,
                     disk_sides,
                     inserted_disk_side,
+                    family_keyboard: self.expansion_device()
+                        == crate::config::ExpansionDevice::FamilyKeyboard,
+                    tape,
+                    ghost: self.emu.lock().ghost.as_ref().map(|g| g.status()),
+                    autosplit: self.emu.lock().autosplit.as_ref().map(|a| a.view()),
                     vs_system,
                     mapper_label: &mapper_label,
                     region_label: &region_label,
@@ -10631,6 +11993,15 @@ impl ApplicationHandler<AppEvent> for App {
                 if settings.audio_eq {
                     self.apply_audio_eq();
                 }
+                // v2.5.0 — Famicom 3D System mode / eye swap.
+                if settings.famicom_3d {
+                    self.sync_famicom_3d();
+                }
+                // v2.5.0 — Famicom-microphone capture re-open.
+                #[cfg(not(target_arch = "wasm32"))]
+                if settings.microphone {
+                    self.sync_mic_capture();
+                }
                 // v1.7.0 H3 — stereo DSP live-apply (pan / reverb / crossfeed,
                 // a frontend output stage; bypass-by-default is byte-identical).
                 #[cfg(not(target_arch = "wasm32"))]
@@ -10843,6 +12214,26 @@ impl ApplicationHandler<AppEvent> for App {
                     self.handle_netplay_request(req);
                 }
 
+                // v2.5.0 — act on an Autosplitter panel request.
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(req) = self
+                    .debugger
+                    .as_mut()
+                    .and_then(DebuggerOverlay::take_autosplit_request)
+                {
+                    self.handle_autosplit_request(req);
+                }
+
+                // v2.5.0 — act on a Practice panel request.
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(req) = self
+                    .debugger
+                    .as_mut()
+                    .and_then(DebuggerOverlay::take_practice_request)
+                {
+                    self.handle_practice_request(req);
+                }
+
                 // v2.7.0 — act on a browser-netplay lobby connect/leave the user
                 // clicked this frame. wasm-only (WebRTC over a signaling server).
                 #[cfg(target_arch = "wasm32")]
@@ -10896,6 +12287,8 @@ impl ApplicationHandler<AppEvent> for App {
             // lost on quit. No-op when clean / non-FDS. Native-only.
             #[cfg(not(target_arch = "wasm32"))]
             self.flush_fds_save();
+            #[cfg(not(target_arch = "wasm32"))]
+            self.flush_turbo_file();
             // v2.7.0 — save the RA progress sidecar on quit. No-op when no RA
             // session / game. Native-only + feature-gated.
             #[cfg(all(not(target_arch = "wasm32"), feature = "retroachievements"))]
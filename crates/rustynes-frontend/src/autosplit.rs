@@ -0,0 +1,779 @@
+//! v2.5.0 — speedrun autosplitter.
+//!
+//! A [`SplitFile`] describes one game's run as conditions over the machine,
+//! written in the debugger's expression syntax (see
+//! [`crate::debugger::expr`]): `[$0760] == 1 && [$0770] == 0` and the like.
+//! The [`Autosplitter`] evaluates them at the end of every forward frame and
+//! drives a frame-counted timer:
+//!
+//! - **start** — the timer starts the frame this becomes true;
+//! - **split** — each `[[split]]`, in order, ends a segment the frame its
+//!   condition becomes true; the last one finishes the run;
+//! - **reset** — abandons the run the frame it becomes true;
+//! - **pause** — game time stands still while it holds (load removal), and
+//!   with `pause_on_lag` also on every frame the game polled no controller.
+//!
+//! Conditions fire on the rising edge, so a flag that stays set for a while
+//! counts once. Real time counts every forward frame of the run; game time
+//! skips the paused ones. Both are frames, converted with the console's own
+//! frame duration, so a run times the same however fast the host ran it.
+//!
+//! The timer publishes what happened as [`TimerEvent`]s for the `LiveSplit`
+//! client (see [`crate::livesplit`]) and a [`TimerView`] for the overlay.
+//! Split files live beside the cheats, one per ROM:
+//!
+//! ```text
+//! <data_dir>/splits/<rom_sha256_hex>.toml
+//! ```
+//!
+//! ```toml
+//! name = "Any%"
+//! start = "[$0770] == 1"
+//! reset = "[$0770] == 0"
+//! pause = "[$0772] == 0"
+//! pause_on_lag = false
+//! best = [4120, 8733]
+//!
+//! [[split]]
+//! name = "World 1"
+//! condition = "[$075F] == 1"
+//!
+//! [[split]]
+//! name = "World 2"
+//! condition = "[$075F] == 2"
+//! ```
+//!
+//! `best` is the personal best: cumulative game-time frames at each split,
+//! rewritten when a finished run beats it. [`SplitFile::to_lss`] exports the
+//! splits and that PB as a `LiveSplit` `.lss` file.
+
+use std::fmt::Write as _;
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use rustynes_core::Nes;
+use serde::{Deserialize, Serialize};
+
+use crate::debugger::ReplayCtx;
+use crate::debugger::expr::{EvalContext, Expr};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::save_state::hex_sha256;
+
+/// One segment of a run: its name and the condition that ends it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SplitDef {
+    /// Segment name, as `LiveSplit` and the overlay show it.
+    pub name: String,
+    /// Expression that ends the segment when it becomes true.
+    pub condition: String,
+}
+
+/// One game's split definitions and personal best (the per-ROM TOML file).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SplitFile {
+    /// Category name (`Any%`, `Warpless`, ...).
+    pub name: String,
+    /// Starts the timer. Empty never starts it (no autosplitting).
+    pub start: String,
+    /// Resets the timer. Empty never resets it.
+    pub reset: String,
+    /// Pauses game time while true (load removal). Empty never pauses.
+    pub pause: String,
+    /// Also pause game time on lag frames.
+    pub pause_on_lag: bool,
+    /// The segments, in run order.
+    #[serde(rename = "split")]
+    pub splits: Vec<SplitDef>,
+    /// Personal best: cumulative game-time frames at each split. Empty, or
+    /// of a different length than `splits`, means no PB yet.
+    pub best: Vec<u64>,
+}
+
+impl SplitFile {
+    /// The personal best, when it matches the current split list.
+    #[must_use]
+    pub fn personal_best(&self) -> Option<&[u64]> {
+        (!self.best.is_empty() && self.best.len() == self.splits.len())
+            .then_some(self.best.as_slice())
+    }
+
+    /// Export as a `LiveSplit` `.lss` splits file. `game` names the game;
+    /// `frame` is the console's frame duration, used to convert the PB.
+    #[must_use]
+    pub fn to_lss(&self, game: &str, frame: Duration) -> String {
+        let pb = self.personal_best();
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<Run version=\"1.7.0\">\n  <GameIcon />\n");
+        let _ = writeln!(out, "  <GameName>{}</GameName>", xml_escape(game));
+        let _ = writeln!(
+            out,
+            "  <CategoryName>{}</CategoryName>",
+            xml_escape(&self.name)
+        );
+        out.push_str("  <Metadata />\n  <Offset>00:00:00</Offset>\n");
+        out.push_str("  <AttemptCount>0</AttemptCount>\n  <AttemptHistory />\n");
+        out.push_str("  <Segments>\n");
+        let mut prev = 0;
+        for (i, split) in self.splits.iter().enumerate() {
+            out.push_str("    <Segment>\n");
+            let _ = writeln!(out, "      <Name>{}</Name>", xml_escape(&split.name));
+            out.push_str("      <Icon />\n      <SplitTimes>\n");
+            out.push_str("        <SplitTime name=\"Personal Best\"");
+            match pb {
+                Some(best) => {
+                    let _ = write!(
+                        out,
+                        ">\n          <GameTime>{}</GameTime>\n        </SplitTime>\n",
+                        lss_time(best[i], frame)
+                    );
+                }
+                None => out.push_str(" />\n"),
+            }
+            out.push_str("      </SplitTimes>\n");
+            match pb {
+                Some(best) => {
+                    let _ = writeln!(
+                        out,
+                        "      <BestSegmentTime>\n        <GameTime>{}</GameTime>\n      \
+                         </BestSegmentTime>",
+                        lss_time(best[i].saturating_sub(prev), frame)
+                    );
+                    prev = best[i];
+                }
+                None => out.push_str("      <BestSegmentTime />\n"),
+            }
+            out.push_str("      <SegmentHistory />\n    </Segment>\n");
+        }
+        out.push_str("  </Segments>\n  <AutoSplitterSettings />\n</Run>\n");
+        out
+    }
+}
+
+/// Path of the split file for one ROM.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub fn split_path(data_dir: &Path, rom_sha256: &[u8; 32]) -> PathBuf {
+    data_dir
+        .join("splits")
+        .join(format!("{}.toml", hex_sha256(rom_sha256)))
+}
+
+/// Load the split file for the ROM identified by `rom_sha256`.
+///
+/// A missing file yields an empty [`SplitFile`]; an unreadable or
+/// unparseable one logs a warning and does the same, so a broken split file
+/// never blocks loading the ROM.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub fn load(data_dir: &Path, rom_sha256: &[u8; 32]) -> SplitFile {
+    let path = split_path(data_dir, rom_sha256);
+    let text = match fs::read_to_string(&path) {
+        Ok(t) => t,
+        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => return SplitFile::default(),
+        Err(e) => {
+            eprintln!(
+                "rustynes: splits {} unreadable, ignoring: {e}",
+                path.display()
+            );
+            return SplitFile::default();
+        }
+    };
+    toml::from_str(&text).unwrap_or_else(|e| {
+        eprintln!(
+            "rustynes: splits {} unparseable, ignoring: {e}",
+            path.display()
+        );
+        SplitFile::default()
+    })
+}
+
+/// Persist `file` as the split file for the ROM identified by `rom_sha256`.
+///
+/// # Errors
+/// Any I/O error writing the file (a serialization failure is mapped to
+/// [`std::io::ErrorKind::InvalidData`], as [`crate::cheats::save`] does).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save(data_dir: &Path, rom_sha256: &[u8; 32], file: &SplitFile) -> std::io::Result<()> {
+    let text = toml::to_string_pretty(file)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    crate::atomic_write::write_atomic(&split_path(data_dir, rom_sha256), text.as_bytes())
+}
+
+/// Where the timer is in a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimerPhase {
+    /// Waiting for the start condition.
+    #[default]
+    Idle,
+    /// Timing a run.
+    Running,
+    /// The last split fired; waiting for a reset.
+    Finished,
+}
+
+/// Something the timer did this frame, for an external timer to mirror.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerEvent {
+    /// A run started.
+    Start,
+    /// A segment ended (the last one also finishes the run).
+    Split,
+    /// The run was abandoned.
+    Reset,
+    /// Game time stopped (a pause condition or lag frame began).
+    PauseGameTime,
+    /// Game time resumed.
+    ResumeGameTime,
+}
+
+/// A snapshot of the timer for the overlay and the autosplitter panel.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimerView {
+    /// Where the timer is.
+    pub phase: TimerPhase,
+    /// Real-time frames of the current run.
+    pub real_frames: u64,
+    /// Game-time frames of the current run.
+    pub game_frames: u64,
+    /// Whether game time is paused this frame.
+    pub paused: bool,
+    /// Segments completed so far.
+    pub split_index: usize,
+    /// Segments in the run.
+    pub split_count: usize,
+    /// Name of the segment being run, if any.
+    pub segment: Option<String>,
+    /// Game-time frames ahead (negative) or behind the PB at the latest
+    /// split, when there is a PB to compare with.
+    pub delta: Option<i64>,
+    /// The console's frame duration, for converting the frame counts.
+    pub frame: Duration,
+}
+
+impl TimerView {
+    /// The game time as a clock string.
+    #[must_use]
+    pub fn game_time(&self) -> String {
+        format_time(self.game_frames, self.frame)
+    }
+
+    /// The real time as a clock string.
+    #[must_use]
+    pub fn real_time(&self) -> String {
+        format_time(self.real_frames, self.frame)
+    }
+
+    /// The PB delta as a signed clock string (`+1.25`, `-0:03.40`).
+    #[must_use]
+    pub fn delta_label(&self) -> Option<String> {
+        self.delta.map(|d| {
+            let sign = if d < 0 { '-' } else { '+' };
+            format!("{sign}{}", format_time(d.unsigned_abs(), self.frame))
+        })
+    }
+}
+
+/// The compiled conditions of one [`SplitFile`].
+#[derive(Debug, Default)]
+struct Conditions {
+    start: Option<Expr>,
+    reset: Option<Expr>,
+    pause: Option<Expr>,
+    splits: Vec<Option<Expr>>,
+}
+
+/// Last frame's value of each edge-triggered condition.
+#[derive(Debug, Default, Clone, Copy)]
+struct Edges {
+    start: bool,
+    reset: bool,
+    split: bool,
+}
+
+/// The autosplitter runtime for one loaded ROM.
+#[derive(Debug)]
+pub struct Autosplitter {
+    rom_sha256: [u8; 32],
+    file: SplitFile,
+    cond: Conditions,
+    errors: Vec<String>,
+    phase: TimerPhase,
+    next: usize,
+    real_frames: u64,
+    game_frames: u64,
+    paused: bool,
+    /// Game time at each split of the current run.
+    times: Vec<u64>,
+    was: Edges,
+    frame: Duration,
+    /// Events not yet taken, each with the game time it happened at.
+    events: Vec<(TimerEvent, Duration)>,
+    best_changed: bool,
+}
+
+impl Autosplitter {
+    /// Compile `file` for the ROM identified by `rom_sha256`. A condition that
+    /// fails to parse is left out (it never fires) and reported by
+    /// [`Self::errors`].
+    #[must_use]
+    pub fn new(file: SplitFile, rom_sha256: [u8; 32]) -> Self {
+        let mut errors = Vec::new();
+        let mut compile = |what: &str, src: &str| {
+            if src.trim().is_empty() {
+                return None;
+            }
+            Expr::parse(src)
+                .map_err(|e| errors.push(format!("{what}: {e}")))
+                .ok()
+        };
+        let cond = Conditions {
+            start: compile("start", &file.start),
+            reset: compile("reset", &file.reset),
+            pause: compile("pause", &file.pause),
+            splits: file
+                .splits
+                .iter()
+                .map(|s| compile(&format!("split \"{}\"", s.name), &s.condition))
+                .collect(),
+        };
+        Self {
+            rom_sha256,
+            file,
+            cond,
+            errors,
+            phase: TimerPhase::Idle,
+            next: 0,
+            real_frames: 0,
+            game_frames: 0,
+            paused: false,
+            times: Vec::new(),
+            was: Edges::default(),
+            frame: Duration::from_nanos(16_639_267),
+            events: Vec::new(),
+            best_changed: false,
+        }
+    }
+
+    /// SHA-256 of the ROM the splits belong to.
+    #[must_use]
+    pub const fn rom_sha256(&self) -> &[u8; 32] {
+        &self.rom_sha256
+    }
+
+    /// The split definitions (with the PB as last updated).
+    #[must_use]
+    pub const fn file(&self) -> &SplitFile {
+        &self.file
+    }
+
+    /// Conditions that failed to compile, as `what: message`.
+    #[must_use]
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Evaluate the conditions against `nes` at the end of a forward frame.
+    pub fn tick(&mut self, nes: &mut Nes) {
+        self.frame = nes.frame_duration();
+        let lagged = !nes.was_input_polled_this_frame();
+        let ctx = ReplayCtx::end_of_frame(nes);
+        self.step(&ctx, lagged);
+    }
+
+    /// [`Self::tick`] against any evaluation context.
+    pub(crate) fn step(&mut self, ctx: &dyn EvalContext, lagged: bool) {
+        let eval = |e: &Option<Expr>| e.as_ref().is_some_and(|e| e.eval_bool(ctx));
+        let start = eval(&self.cond.start);
+        let reset = eval(&self.cond.reset);
+        let start_edge = start && !self.was.start;
+        let reset_edge = reset && !self.was.reset;
+        self.was.start = start;
+        self.was.reset = reset;
+
+        match self.phase {
+            TimerPhase::Idle => {
+                if start_edge {
+                    self.phase = TimerPhase::Running;
+                    self.next = 0;
+                    self.real_frames = 0;
+                    self.game_frames = 0;
+                    self.paused = false;
+                    self.times.clear();
+                    self.was.split = self.split_condition(ctx);
+                    self.emit(TimerEvent::Start);
+                }
+            }
+            TimerPhase::Running => {
+                if reset_edge {
+                    self.reset();
+                    return;
+                }
+                self.real_frames += 1;
+                let paused = (lagged && self.file.pause_on_lag) || eval(&self.cond.pause);
+                if paused != self.paused {
+                    self.paused = paused;
+                    self.emit(if paused {
+                        TimerEvent::PauseGameTime
+                    } else {
+                        TimerEvent::ResumeGameTime
+                    });
+                }
+                if !paused {
+                    self.game_frames += 1;
+                }
+                let split = self.split_condition(ctx);
+                let split_edge = split && !self.was.split;
+                self.was.split = split;
+                if split_edge {
+                    self.times.push(self.game_frames);
+                    self.next += 1;
+                    self.emit(TimerEvent::Split);
+                    if self.next == self.file.splits.len() {
+                        self.finish();
+                    } else {
+                        // The next segment's condition only counts once it
+                        // goes from false to true from here on.
+                        self.was.split = self.split_condition(ctx);
+                    }
+                }
+            }
+            TimerPhase::Finished => {
+                if reset_edge {
+                    self.reset();
+                }
+            }
+        }
+    }
+
+    fn split_condition(&self, ctx: &dyn EvalContext) -> bool {
+        self.cond
+            .splits
+            .get(self.next)
+            .and_then(Option::as_ref)
+            .is_some_and(|e| e.eval_bool(ctx))
+    }
+
+    fn finish(&mut self) {
+        self.phase = TimerPhase::Finished;
+        let beaten = self
+            .file
+            .personal_best()
+            .and_then(<[u64]>::last)
+            .is_none_or(|&pb| self.game_frames < pb);
+        if beaten {
+            self.file.best.clone_from(&self.times);
+            self.best_changed = true;
+        }
+    }
+
+    /// Abandon the run (the reset condition, or the user's Reset button).
+    pub fn reset(&mut self) {
+        if self.phase != TimerPhase::Idle {
+            self.phase = TimerPhase::Idle;
+            self.paused = false;
+            self.emit(TimerEvent::Reset);
+        }
+    }
+
+    fn emit(&mut self, event: TimerEvent) {
+        self.events.push((event, self.game_time()));
+    }
+
+    /// Take the events since the last call, oldest first, each with the game
+    /// time it happened at.
+    pub fn take_events(&mut self) -> Vec<(TimerEvent, Duration)> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// `true` once after a finished run set a new PB (the caller persists
+    /// [`Self::file`]).
+    pub const fn take_best_changed(&mut self) -> bool {
+        std::mem::replace(&mut self.best_changed, false)
+    }
+
+    /// The current game time.
+    #[must_use]
+    pub fn game_time(&self) -> Duration {
+        frames_to_duration(self.game_frames, self.frame)
+    }
+
+    /// Snapshot the timer for display.
+    #[must_use]
+    pub fn view(&self) -> TimerView {
+        let delta = self.times.len().checked_sub(1).and_then(|i| {
+            let pb = *self.file.personal_best()?.get(i)?;
+            i64::try_from(self.times[i])
+                .ok()
+                .zip(i64::try_from(pb).ok())
+                .map(|(live, pb)| live - pb)
+        });
+        TimerView {
+            phase: self.phase,
+            real_frames: self.real_frames,
+            game_frames: self.game_frames,
+            paused: self.paused,
+            split_index: self.next,
+            split_count: self.file.splits.len(),
+            segment: (self.phase == TimerPhase::Running)
+                .then(|| self.file.splits.get(self.next).map(|s| s.name.clone()))
+                .flatten(),
+            delta,
+            frame: self.frame,
+        }
+    }
+}
+
+fn frames_to_duration(frames: u64, frame: Duration) -> Duration {
+    Duration::from_nanos(
+        u64::try_from(frame.as_nanos().saturating_mul(u128::from(frames))).unwrap_or(u64::MAX),
+    )
+}
+
+/// `frames` as `m:ss.cc`, or `h:mm:ss.cc` past the hour.
+#[must_use]
+pub fn format_time(frames: u64, frame: Duration) -> String {
+    let t = frames_to_duration(frames, frame);
+    let secs = t.as_secs();
+    let centis = t.subsec_millis() / 10;
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}.{centis:02}")
+    } else {
+        format!("{m}:{s:02}.{centis:02}")
+    }
+}
+
+/// `frames` as the `hh:mm:ss.fffffff` time span `LiveSplit` files carry.
+fn lss_time(frames: u64, frame: Duration) -> String {
+    let t = frames_to_duration(frames, frame);
+    let secs = t.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}.{:07}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        t.subsec_nanos() / 100
+    )
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debugger::expr::AccessContext;
+    use std::cell::RefCell;
+
+    /// A machine that is nothing but 2 KiB of RAM.
+    struct Ram(RefCell<[u8; 0x800]>);
+
+    impl Ram {
+        fn new() -> Self {
+            Self(RefCell::new([0; 0x800]))
+        }
+        fn set(&self, addr: u16, v: u8) {
+            self.0.borrow_mut()[usize::from(addr)] = v;
+        }
+    }
+
+    impl EvalContext for Ram {
+        fn a(&self) -> u8 {
+            0
+        }
+        fn x(&self) -> u8 {
+            0
+        }
+        fn y(&self) -> u8 {
+            0
+        }
+        fn s(&self) -> u8 {
+            0
+        }
+        fn p(&self) -> u8 {
+            0
+        }
+        fn pc(&self) -> u16 {
+            0
+        }
+        fn scanline(&self) -> i16 {
+            0
+        }
+        fn dot(&self) -> u16 {
+            0
+        }
+        fn frame(&self) -> u64 {
+            0
+        }
+        fn peek(&self, addr: u16) -> u8 {
+            self.0.borrow()[usize::from(addr & 0x7FF)]
+        }
+        fn access(&self) -> AccessContext {
+            AccessContext::default()
+        }
+    }
+
+    fn two_worlds() -> SplitFile {
+        SplitFile {
+            name: "Any%".into(),
+            start: "[$10] == 1".into(),
+            reset: "[$10] == 0".into(),
+            pause: "[$11] != 0".into(),
+            pause_on_lag: false,
+            splits: vec![
+                SplitDef {
+                    name: "World 1".into(),
+                    condition: "[$12] == 1".into(),
+                },
+                SplitDef {
+                    name: "World 2".into(),
+                    condition: "[$12] == 2".into(),
+                },
+            ],
+            best: Vec::new(),
+        }
+    }
+
+    fn events(auto: &mut Autosplitter) -> Vec<TimerEvent> {
+        auto.take_events().into_iter().map(|(e, _)| e).collect()
+    }
+
+    fn run(auto: &mut Autosplitter, ram: &Ram, frames: usize) {
+        for _ in 0..frames {
+            auto.step(ram, false);
+        }
+    }
+
+    #[test]
+    fn a_run_starts_splits_pauses_and_sets_a_pb() {
+        let ram = Ram::new();
+        let mut auto = Autosplitter::new(two_worlds(), [0; 32]);
+        assert!(auto.errors().is_empty());
+        run(&mut auto, &ram, 5);
+        assert_eq!(auto.view().phase, TimerPhase::Idle);
+
+        ram.set(0x10, 1);
+        run(&mut auto, &ram, 1);
+        assert_eq!(events(&mut auto), [TimerEvent::Start]);
+        run(&mut auto, &ram, 10);
+        ram.set(0x11, 1); // a load screen
+        run(&mut auto, &ram, 4);
+        ram.set(0x11, 0);
+        ram.set(0x12, 1);
+        run(&mut auto, &ram, 1);
+        let v = auto.view();
+        assert_eq!((v.real_frames, v.game_frames), (15, 11));
+        assert_eq!(v.segment.as_deref(), Some("World 2"));
+        assert_eq!(
+            events(&mut auto),
+            [
+                TimerEvent::PauseGameTime,
+                TimerEvent::ResumeGameTime,
+                TimerEvent::Split
+            ]
+        );
+
+        ram.set(0x12, 2);
+        run(&mut auto, &ram, 1);
+        assert_eq!(auto.view().phase, TimerPhase::Finished);
+        let (event, at) = auto.take_events()[0];
+        assert_eq!(event, TimerEvent::Split);
+        assert_eq!(at, auto.game_time());
+        assert!(auto.take_best_changed());
+        assert_eq!(auto.file().best, [11, 12]);
+
+        // Start stays true, so only the reset's edge matters from here.
+        ram.set(0x10, 0);
+        run(&mut auto, &ram, 1);
+        assert_eq!(auto.view().phase, TimerPhase::Idle);
+        assert_eq!(events(&mut auto), [TimerEvent::Reset]);
+    }
+
+    #[test]
+    fn a_held_condition_counts_once_and_lag_pauses_when_asked() {
+        let ram = Ram::new();
+        let mut file = two_worlds();
+        file.pause_on_lag = true;
+        file.best = vec![100, 200];
+        let mut auto = Autosplitter::new(file, [0; 32]);
+        ram.set(0x10, 1);
+        ram.set(0x12, 1); // already true at the start
+        run(&mut auto, &ram, 3);
+        assert_eq!(auto.view().split_index, 0);
+        ram.set(0x12, 0);
+        run(&mut auto, &ram, 1);
+        auto.step(&ram, true);
+        auto.step(&ram, true);
+        ram.set(0x12, 1);
+        run(&mut auto, &ram, 1);
+        let v = auto.view();
+        assert_eq!(v.split_index, 1);
+        assert_eq!((v.real_frames, v.game_frames), (6, 4));
+        assert_eq!(v.delta, Some(4 - 100));
+        assert!(v.delta_label().unwrap().starts_with('-'));
+    }
+
+    #[test]
+    fn bad_conditions_are_reported_and_never_fire() {
+        let mut file = two_worlds();
+        file.start = "[$10] ==".into();
+        let auto = Autosplitter::new(file, [0; 32]);
+        assert_eq!(auto.errors().len(), 1);
+        assert!(auto.errors()[0].starts_with("start:"));
+    }
+
+    #[test]
+    fn times_format_as_clocks() {
+        let second = Duration::from_secs(1);
+        assert_eq!(format_time(83, second / 4), "0:20.75");
+        assert_eq!(format_time(3_725, second), "1:02:05.00");
+        assert_eq!(lss_time(90, second / 2), "00:00:45.0000000");
+    }
+
+    #[test]
+    fn lss_export_carries_segments_and_the_pb() {
+        let mut file = two_worlds();
+        file.splits[0].name = "A & B".into();
+        let no_pb = file.to_lss("Game", Duration::from_secs(1));
+        assert!(no_pb.contains("<Name>A &amp; B</Name>"));
+        assert!(no_pb.contains("<SplitTime name=\"Personal Best\" />"));
+        file.best = vec![60, 150];
+        let lss = file.to_lss("Game", Duration::from_secs(1));
+        assert!(lss.contains("<GameTime>00:01:00.0000000</GameTime>"));
+        assert!(lss.contains("<GameTime>00:02:30.0000000</GameTime>"));
+        // The second segment's best is its own length, not the cumulative.
+        assert!(lss.contains("<GameTime>00:01:30.0000000</GameTime>"));
+        assert!(lss.contains("<CategoryName>Any%</CategoryName>"));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn split_files_persist_per_rom() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let sha = [7u8; 32];
+        assert_eq!(load(tmp.path(), &sha), SplitFile::default());
+        let mut file = two_worlds();
+        file.best = vec![1, 2];
+        save(tmp.path(), &sha, &file).unwrap();
+        assert!(split_path(tmp.path(), &sha).starts_with(tmp.path().join("splits")));
+        assert_eq!(load(tmp.path(), &sha), file);
+        assert_eq!(load(tmp.path(), &[8u8; 32]), SplitFile::default());
+    }
+}
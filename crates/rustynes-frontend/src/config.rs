@@ -86,6 +86,7 @@ pub struct RememberedLatency {
 /// config (no Four Score tables / flag) loads unchanged: `four_score`
 /// defaults off and the P3/P4 maps stay dormant until the toggle is
 /// enabled.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct InputConfig {
     /// Player 1 keyboard mapping.
@@ -205,6 +206,35 @@ pub struct InputConfig {
     /// [`PowerPadLayout::SideA`] is the previous fixed mapping (byte-identical).
     #[serde(default)]
     pub power_pad_layout: PowerPadLayout,
+    /// v2.5.0 — R.O.B. set-up: off (the default, frame loop byte-identical),
+    /// or the *Gyromite* / *Stack-Up* play field. Applied on ROM load and
+    /// whenever the Input window changes it.
+    #[serde(default)]
+    pub rob: RobSetting,
+    /// v2.5.0 — the Power Glove gesture program used in its joypad mode (see
+    /// [`ExpansionDevice::PowerGlove`]). Defaults to the joystick layout.
+    #[serde(default)]
+    pub power_glove_program: PowerGloveProgramSetting,
+    /// v2.5.0 — the Turbo File Twin's bank switch (`0..=3`; see
+    /// [`ExpansionDevice::TurboFileTwin`]).
+    #[serde(default)]
+    pub turbo_file_bank: u8,
+    /// v2.5.0 — drive the Famicom microphone from a host input device (the
+    /// hold-to-talk key still works alongside it). Off by default, so no
+    /// input stream is opened and `$4016` stays byte-identical.
+    #[serde(default)]
+    pub microphone_capture: bool,
+    /// v2.5.0 — capture device name; `None` (default) or an absent device
+    /// uses the host default input.
+    #[serde(default)]
+    pub microphone_device: Option<String>,
+    /// v2.5.0 — peak amplitude (0..1) that turns the microphone on.
+    #[serde(default = "default_microphone_threshold")]
+    pub microphone_threshold: f32,
+    /// v2.5.0 — how far below the threshold the envelope must fall before the
+    /// microphone turns off again (Schmitt-trigger hysteresis).
+    #[serde(default = "default_microphone_hysteresis")]
+    pub microphone_hysteresis: f32,
     /// System-level bindings.
     pub system: SystemBindings,
 }
@@ -214,6 +244,84 @@ const fn default_pointer_scale() -> f32 {
     1.0
 }
 
+/// Serde default for [`InputConfig::microphone_threshold`].
+const fn default_microphone_threshold() -> f32 {
+    0.08
+}
+
+/// Serde default for [`InputConfig::microphone_hysteresis`].
+const fn default_microphone_hysteresis() -> f32 {
+    0.03
+}
+
+/// v2.5.0 — which R.O.B. play field (if any) sits in front of the screen.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum RobSetting {
+    /// No robot. The default.
+    #[default]
+    Off,
+    /// *Gyromite*: gyros, spinner and the controller-II button trays.
+    Gyromite,
+    /// *Stack-Up*: arm and hand only.
+    StackUp,
+}
+
+impl RobSetting {
+    /// Human label for the Input window combo.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Gyromite => "Gyromite",
+            Self::StackUp => "Stack-Up",
+        }
+    }
+
+    /// The core play field, or `None` for no robot.
+    #[must_use]
+    pub const fn mode(self) -> Option<rustynes_core::RobMode> {
+        match self {
+            Self::Off => None,
+            Self::Gyromite => Some(rustynes_core::RobMode::Gyromite),
+            Self::StackUp => Some(rustynes_core::RobMode::StackUp),
+        }
+    }
+}
+
+/// v2.5.0 — the Power Glove gesture program (mirrors
+/// [`rustynes_core::PowerGloveProgram`]).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum PowerGloveProgramSetting {
+    /// Hand offset steers the D-pad; index finger = A, thumb = B. The default.
+    #[default]
+    Joystick,
+    /// Hand offset steers the D-pad; fist = B, a thrust fist = A.
+    Punch,
+    /// Only the forearm keypad reaches the game.
+    Keypad,
+}
+
+impl PowerGloveProgramSetting {
+    /// Every program, in menu order.
+    pub const ALL: [Self; 3] = [Self::Joystick, Self::Punch, Self::Keypad];
+
+    /// The core program.
+    #[must_use]
+    pub const fn program(self) -> rustynes_core::PowerGloveProgram {
+        match self {
+            Self::Joystick => rustynes_core::PowerGloveProgram::Joystick,
+            Self::Punch => rustynes_core::PowerGloveProgram::Punch,
+            Self::Keypad => rustynes_core::PowerGloveProgram::Keypad,
+        }
+    }
+
+    /// Human label for the Input window combo.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        self.program().label()
+    }
+}
+
 /// v1.5.0 "Lens" Workstream D4 — NES Power Pad / Family Trainer mat layout side.
 ///
 /// The physical mat is labelled with an "A" side and a "B" side whose button
@@ -291,6 +399,12 @@ pub struct FdsConfig {
     /// the chosen path here. Native-only (no filesystem on wasm32).
     #[serde(default)]
     pub bios_path: Option<PathBuf>,
+    /// v2.5.0 — boot `.fds` images with the built-in clean-room HLE BIOS
+    /// (`rustynes_mappers::fds_hle_bios`) when [`Self::bios_path`] is unset,
+    /// instead of prompting for a `disksys.rom`. Off by default; with it off,
+    /// cancelling the prompt abandons the disk load.
+    #[serde(default)]
+    pub hle_bios: bool,
 }
 
 /// Netplay (v2.3.0) configuration — only the last-used host port + join
@@ -300,6 +414,7 @@ pub struct FdsConfig {
 /// `[netplay]` section) loads unchanged. Netplay is native-only (it drives a
 /// UDP socket via `std::net`); the section is harmless on wasm32 (where the
 /// netplay panel is a "native-only" note), so it stays in the shared `Config`.
+#[allow(clippy::struct_excessive_bools)] // independent per-session toggles.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct NetplayConfig {
     /// Local UDP port the host binds (default 7000). Pre-filled into the
@@ -328,6 +443,25 @@ pub struct NetplayConfig {
     /// these at its own `coturn` (STUN + TURN). Native netplay ignores this.
     #[serde(default = "default_stun_servers")]
     pub stun_servers: Vec<String>,
+    /// A joiner whose battery save or FDS disk differs from the host's plays
+    /// the session on a copy of the host's (default `true`) instead of
+    /// refusing the match. Its own save is untouched either way.
+    #[serde(default = "default_use_host_save")]
+    pub use_host_save: bool,
+    /// A host keeps the save RAM / disk writes the session made once it
+    /// ends (default `false`: the session runs on a copy that is dropped).
+    #[serde(default)]
+    pub write_back_host_save: bool,
+    /// Record every match this peer plays or watches as an attested `.rnm`
+    /// under `<data_dir>/movies/`, written when the session ends (default
+    /// `false`).
+    #[serde(default)]
+    pub record_matches: bool,
+    /// A host adjusts the match's input delay to the measured link on its
+    /// own (default `false`: the netplay panel only suggests a delay and the
+    /// host applies it).
+    #[serde(default)]
+    pub adaptive_input_delay: bool,
 }
 
 const fn default_netplay_port() -> u16 {
@@ -338,6 +472,10 @@ const fn default_netplay_players() -> u8 {
     2
 }
 
+const fn default_use_host_save() -> bool {
+    true
+}
+
 fn default_stun_servers() -> Vec<String> {
     rustynes_netplay::DEFAULT_STUN_SERVERS
         .iter()
@@ -353,6 +491,10 @@ impl Default for NetplayConfig {
             num_players: default_netplay_players(),
             signaling_url: String::new(),
             stun_servers: default_stun_servers(),
+            use_host_save: default_use_host_save(),
+            write_back_host_save: false,
+            record_matches: false,
+            adaptive_input_delay: false,
         }
     }
 }
@@ -396,6 +538,79 @@ pub enum ExpansionDevice {
     /// an 8-sensor expansion controller (keys per
     /// `input::BANDAI_HYPER_SHOT_KEYS`).
     BandaiHyperShot,
+    /// Bandai Oeka Kids drawing tablet (v2.5.0) — the cursor (or a touch
+    /// point) is the pen; left mouse / a finger down = pen touching the
+    /// surface, right mouse = the pen button.
+    OekaKidsTablet,
+    /// Hori Track trackball (v2.5.0) — mouse motion (or the right stick)
+    /// rolls the ball; the player-1 bindings are its pad buttons.
+    HoriTrack,
+    /// Coconuts Pachinko controller (v2.5.0) — the right trigger, the left
+    /// stick pulled down, or the cursor height while the left mouse is held
+    /// pulls the plunger; the player-1 bindings are its pad buttons.
+    Pachinko,
+    /// Bandai Top Rider bike (v2.5.0) — left stick / Left-Right steer, right
+    /// trigger / A is the throttle, left trigger / B brakes, Up pulls a
+    /// wheelie, Down holds high gear, plus Start / Select.
+    TopRider,
+    /// Mattel Power Glove (v2.5.0) — the cursor (or left stick) moves the
+    /// hand, left mouse clenches a fist, right mouse thrusts toward the
+    /// screen; the right stick and triggers drive depth, roll and fingers, and
+    /// the player-1 bindings are the forearm keypad.
+    PowerGlove,
+    /// ASCII Turbo File (v2.5.0) — 8 KiB of external save storage, kept in
+    /// `<data-dir>/turbo-file/turbo-file.bin` like a battery save.
+    TurboFile,
+    /// ASCII Turbo File Twin (v2.5.0) — four switch-selected 8 KiB banks
+    /// (`[input] turbo_file_bank`), kept in `turbo-file-twin.bin`.
+    TurboFileTwin,
+}
+
+impl ExpansionDevice {
+    /// Every device, in the order the pickers list them.
+    pub const ALL: [Self; 17] = [
+        Self::None,
+        Self::Zapper,
+        Self::Vaus,
+        Self::PowerPad,
+        Self::SnesMouse,
+        Self::FamilyKeyboard,
+        Self::FamilyTrainer,
+        Self::SuborKeyboard,
+        Self::KonamiHyperShot,
+        Self::BandaiHyperShot,
+        Self::OekaKidsTablet,
+        Self::HoriTrack,
+        Self::Pachinko,
+        Self::TopRider,
+        Self::PowerGlove,
+        Self::TurboFile,
+        Self::TurboFileTwin,
+    ];
+
+    /// Picker label.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::None => "Standard controller",
+            Self::Zapper => "Zapper (light gun)",
+            Self::Vaus => "Vaus (Arkanoid paddle)",
+            Self::PowerPad => "Power Pad (mat)",
+            Self::SnesMouse => "SNES mouse",
+            Self::FamilyKeyboard => "Family BASIC keyboard",
+            Self::FamilyTrainer => "Family Trainer (mat)",
+            Self::SuborKeyboard => "Subor keyboard",
+            Self::KonamiHyperShot => "Konami Hyper Shot",
+            Self::BandaiHyperShot => "Bandai Hyper Shot",
+            Self::OekaKidsTablet => "Oeka Kids tablet",
+            Self::HoriTrack => "Hori Track (trackball)",
+            Self::Pachinko => "Pachinko controller",
+            Self::TopRider => "Top Rider (bike)",
+            Self::PowerGlove => "Power Glove",
+            Self::TurboFile => "Turbo File (storage)",
+            Self::TurboFileTwin => "Turbo File Twin (storage)",
+        }
+    }
 }
 
 impl Default for InputConfig {
@@ -419,6 +634,13 @@ impl Default for InputConfig {
             mouse_sensitivity: 0,
             pointer_scale: default_pointer_scale(),
             power_pad_layout: PowerPadLayout::default(),
+            rob: RobSetting::default(),
+            power_glove_program: PowerGloveProgramSetting::default(),
+            turbo_file_bank: 0,
+            microphone_capture: false,
+            microphone_device: None,
+            microphone_threshold: default_microphone_threshold(),
+            microphone_hysteresis: default_microphone_hysteresis(),
             system: SystemBindings::default(),
         }
     }
@@ -655,6 +877,11 @@ pub struct SystemBindings {
     /// `#[serde(default)]` so older configs keep loading.
     #[serde(default = "default_speed_reset")]
     pub speed_reset: String,
+    /// v2.5.0 — reload the current practice segment and start a timed
+    /// attempt (default `Backspace`). `#[serde(default)]` so older configs
+    /// keep loading.
+    #[serde(default = "default_practice_reload")]
+    pub practice_reload: String,
 }
 
 fn default_debug_overlay() -> String {
@@ -717,6 +944,10 @@ fn default_speed_reset() -> String {
     "Digit0".into()
 }
 
+fn default_practice_reload() -> String {
+    "Backspace".into()
+}
+
 impl Default for SystemBindings {
     fn default() -> Self {
         Self {
@@ -741,6 +972,7 @@ impl Default for SystemBindings {
             speed_up: default_speed_up(),
             speed_down: default_speed_down(),
             speed_reset: default_speed_reset(),
+            practice_reload: default_practice_reload(),
         }
     }
 }
@@ -769,6 +1001,7 @@ impl Default for RewindConfig {
 /// Graphics configuration.
 // `crt_scanline` is an `f32`, so this config is `PartialEq` only (not `Eq`).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(clippy::struct_excessive_bools)] // independent presentation toggles.
 pub struct GraphicsConfig {
     /// wgpu present mode: `"Mailbox"` (default), `"Fifo"`, or
     /// `"Immediate"`. The native frontend paces frames on a wall clock,
@@ -933,6 +1166,13 @@ pub struct GraphicsConfig {
     /// `DualSystem` cabinet is loaded; irrelevant to single-console presentation.
     #[serde(default = "default_dual_screen_layout")]
     pub dual_screen_layout: String,
+    /// v2.5.0 — Famicom 3D System: `Off` (default, glasses unplugged, frames
+    /// present as drawn) or how each stereo pair is presented.
+    #[serde(default)]
+    pub famicom_3d: crate::stereo::StereoMode,
+    /// v2.5.0 — swap the eyes for titles wired with the opposite polarity.
+    #[serde(default)]
+    pub famicom_3d_swap_eyes: bool,
 }
 
 fn default_dual_screen_layout() -> String {
@@ -1152,6 +1392,8 @@ impl Default for GraphicsConfig {
             ntsc_palette_enabled: false,
             ntsc_palette: NtscPaletteConfig::default(),
             dual_screen_layout: default_dual_screen_layout(),
+            famicom_3d: crate::stereo::StereoMode::Off,
+            famicom_3d_swap_eyes: false,
         }
     }
 }
@@ -1697,6 +1939,12 @@ pub struct Config {
     /// Vs. System arcade defaults (DIP switches) (v2.5.0).
     #[serde(default)]
     pub vs: VsConfig,
+    /// Ghost replay overlay settings (v2.5.0).
+    #[serde(default)]
+    pub ghost: GhostConfig,
+    /// Speedrun autosplitter settings (v2.5.0).
+    #[serde(default)]
+    pub autosplit: AutosplitConfig,
     /// `RetroAchievements` defaults (login token + hardcore) (v2.7.0).
     #[serde(default)]
     pub retroachievements: RetroAchievementsConfig,
@@ -1923,6 +2171,62 @@ pub struct VsConfig {
     pub dip_set: bool,
 }
 
+/// `[ghost]` section — how a loaded ghost replay is drawn and aligned (see
+/// [`crate::ghost`]). Consulted only while a ghost is loaded.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct GhostConfig {
+    /// Draw the ghost's sprites over the live picture. Default on.
+    pub overlay: bool,
+    /// Sprite overlay opacity in percent (`0..=100`). Default 50.
+    pub opacity: u8,
+    /// Show the whole ghost picture as a picture-in-picture inset.
+    pub pip: bool,
+    /// Keep the ghost in step frame by frame (default) or by split point.
+    pub align: crate::ghost::GhostAlign,
+    /// CPU address of the split-point RAM byte.
+    pub split_addr: u16,
+    /// The value at [`Self::split_addr`] that marks a split.
+    pub split_value: u8,
+}
+
+impl Default for GhostConfig {
+    fn default() -> Self {
+        Self {
+            overlay: true,
+            opacity: 50,
+            pip: false,
+            align: crate::ghost::GhostAlign::Frame,
+            split_addr: 0,
+            split_value: 0,
+        }
+    }
+}
+
+/// `[autosplit]` section — the speedrun timer's overlay and its `LiveSplit`
+/// link (see [`crate::autosplit`]). The split conditions themselves are per
+/// ROM, in `<data_dir>/splits/`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct AutosplitConfig {
+    /// Draw the timer over the game while a split file is loaded. Default on.
+    pub overlay: bool,
+    /// `LiveSplit` Server address (`host:port`).
+    pub livesplit_addr: String,
+    /// Connect to `LiveSplit` whenever a ROM with splits loads. Default off.
+    pub livesplit_auto_connect: bool,
+}
+
+impl Default for AutosplitConfig {
+    fn default() -> Self {
+        Self {
+            overlay: true,
+            livesplit_addr: "127.0.0.1:16834".into(),
+            livesplit_auto_connect: false,
+        }
+    }
+}
+
 impl Config {
     /// Resolve the standard config-file path (e.g. `~/.config/rustynes/config.toml`).
     #[must_use]
@@ -2707,6 +3011,7 @@ mod tests {
         assert_eq!(expected.speed_up, "Equal");
         assert_eq!(expected.speed_down, "Minus");
         assert_eq!(expected.speed_reset, "Digit0");
+        assert_eq!(expected.practice_reload, "Backspace");
 
         // (d) serialising then re-parsing the registry is a fixed point.
         let s = toml::to_string_pretty(&expected).unwrap();
@@ -3181,6 +3486,116 @@ debug_overlay = "Backquote"
         );
     }
 
+    #[test]
+    fn fds_hle_bios_defaults_off_and_persists() {
+        assert!(!Config::default().fds.hle_bios);
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        let mut cfg = Config::default();
+        cfg.fds.hle_bios = true;
+        cfg.save_to(&path).unwrap();
+        assert!(Config::load_from(&path).unwrap().fds.hle_bios);
+    }
+
+    #[test]
+    fn microphone_capture_defaults_off_and_persists() {
+        let input = Config::default().input;
+        assert!(!input.microphone_capture);
+        assert!(input.microphone_hysteresis < input.microphone_threshold);
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        let mut cfg = Config::default();
+        cfg.input.microphone_capture = true;
+        cfg.input.microphone_device = Some("USB Mic".to_owned());
+        cfg.input.microphone_threshold = 0.2;
+        cfg.save_to(&path).unwrap();
+        let back = Config::load_from(&path).unwrap().input;
+        assert!(back.microphone_capture);
+        assert_eq!(back.microphone_device.as_deref(), Some("USB Mic"));
+        assert!((back.microphone_threshold - 0.2).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn famicom_3d_defaults_off_and_persists() {
+        let g = Config::default().graphics;
+        assert_eq!(g.famicom_3d, crate::stereo::StereoMode::Off);
+        assert!(!g.famicom_3d_swap_eyes);
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        let mut cfg = Config::default();
+        cfg.graphics.famicom_3d = crate::stereo::StereoMode::Anaglyph;
+        cfg.graphics.famicom_3d_swap_eyes = true;
+        cfg.save_to(&path).unwrap();
+        let back = Config::load_from(&path).unwrap().graphics;
+        assert_eq!(back.famicom_3d, crate::stereo::StereoMode::Anaglyph);
+        assert!(back.famicom_3d_swap_eyes);
+    }
+
+    #[test]
+    fn ghost_defaults_and_persists() {
+        let g = Config::default().ghost;
+        assert!(g.overlay && !g.pip);
+        assert_eq!(g.opacity, 50);
+        assert_eq!(g.align, crate::ghost::GhostAlign::Frame);
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        let mut cfg = Config::default();
+        cfg.ghost.pip = true;
+        cfg.ghost.align = crate::ghost::GhostAlign::Split;
+        cfg.ghost.split_addr = 0x0760;
+        cfg.ghost.split_value = 3;
+        cfg.save_to(&path).unwrap();
+        assert_eq!(Config::load_from(&path).unwrap().ghost, cfg.ghost);
+    }
+
+    #[test]
+    fn autosplit_defaults_and_persists() {
+        let a = Config::default().autosplit;
+        assert!(a.overlay && !a.livesplit_auto_connect);
+        assert_eq!(a.livesplit_addr, "127.0.0.1:16834");
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        let mut cfg = Config::default();
+        cfg.autosplit.livesplit_addr = "192.168.1.5:16834".into();
+        cfg.autosplit.livesplit_auto_connect = true;
+        cfg.save_to(&path).unwrap();
+        assert_eq!(Config::load_from(&path).unwrap().autosplit, cfg.autosplit);
+    }
+
+    #[test]
+    fn rob_defaults_off_and_persists() {
+        assert_eq!(Config::default().input.rob, RobSetting::Off);
+        assert_eq!(RobSetting::Off.mode(), None);
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        let mut cfg = Config::default();
+        cfg.input.rob = RobSetting::Gyromite;
+        cfg.save_to(&path).unwrap();
+        let back = Config::load_from(&path).unwrap().input;
+        assert_eq!(back.rob, RobSetting::Gyromite);
+        assert_eq!(back.rob.mode(), Some(rustynes_core::RobMode::Gyromite));
+    }
+
+    #[test]
+    fn power_glove_program_defaults_to_joystick_and_persists() {
+        assert_eq!(
+            Config::default().input.power_glove_program,
+            PowerGloveProgramSetting::Joystick
+        );
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        let mut cfg = Config::default();
+        cfg.input.expansion_device = ExpansionDevice::PowerGlove;
+        cfg.input.power_glove_program = PowerGloveProgramSetting::Punch;
+        cfg.save_to(&path).unwrap();
+        let back = Config::load_from(&path).unwrap().input;
+        assert_eq!(back.expansion_device, ExpansionDevice::PowerGlove);
+        assert_eq!(
+            back.power_glove_program.program(),
+            rustynes_core::PowerGloveProgram::Punch
+        );
+    }
+
     #[test]
     fn gamepad_default_deadzone_is_half() {
         let cfg = Config::default();
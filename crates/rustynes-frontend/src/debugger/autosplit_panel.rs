@@ -0,0 +1,284 @@
+//! v2.5.0 — **Autosplitter** panel: the running timer, the `LiveSplit` link,
+//! and an editor for the loaded ROM's split file.
+//!
+//! The panel edits a draft copy of the [`SplitFile`]; nothing changes until
+//! Apply, which hands the draft to the app as an [`AutosplitRequest`] to
+//! recompile, install and save under the ROM's hash. Conditions use the
+//! debugger's expression syntax (the same as a conditional breakpoint), and a
+//! condition that fails to compile is listed under the editor.
+
+use crate::autosplit::{SplitDef, SplitFile, TimerPhase, TimerView};
+
+const ERROR: egui::Color32 = egui::Color32::from_rgb(0xE0, 0x40, 0x40);
+const AHEAD: egui::Color32 = egui::Color32::from_rgb(0x40, 0xC0, 0x60);
+const BEHIND: egui::Color32 = egui::Color32::from_rgb(0xE0, 0x60, 0x40);
+
+/// What the user asked the app to do from the panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AutosplitRequest {
+    /// Install and save this split file for the loaded ROM.
+    Apply(SplitFile),
+    /// Abandon the current run.
+    ResetTimer,
+    /// Save the splits as a `LiveSplit` `.lss` file (native file dialog).
+    ExportLss,
+    /// Connect to the `LiveSplit` server at this `host:port` (the app keeps it
+    /// as `[autosplit] livesplit_addr`).
+    Connect(String),
+    /// Drop the `LiveSplit` connection.
+    Disconnect,
+}
+
+/// Panel state: the draft being edited plus what the app pushes each frame.
+#[derive(Debug, Default)]
+pub struct AutosplitPanelState {
+    draft: SplitFile,
+    dirty: bool,
+    errors: Vec<String>,
+    view: Option<TimerView>,
+    livesplit: Option<String>,
+    addr: String,
+    rom_loaded: bool,
+    request: Option<AutosplitRequest>,
+}
+
+impl AutosplitPanelState {
+    /// Replace the draft with the loaded ROM's split file and its compile
+    /// errors (on ROM load, and after an Apply).
+    pub fn set_file(&mut self, file: Option<(SplitFile, Vec<String>)>) {
+        self.rom_loaded = file.is_some();
+        let (draft, errors) = file.unwrap_or_default();
+        self.draft = draft;
+        self.errors = errors;
+        self.dirty = false;
+    }
+
+    /// Seed the `LiveSplit` address field (from config, once).
+    pub fn set_livesplit_addr(&mut self, addr: &str) {
+        if self.addr.is_empty() {
+            addr.clone_into(&mut self.addr);
+        }
+    }
+
+    /// Push the timer snapshot and the `LiveSplit` server address (when
+    /// connected).
+    pub fn set_status(&mut self, view: Option<TimerView>, livesplit: Option<String>) {
+        self.view = view;
+        self.livesplit = livesplit;
+    }
+
+    /// Take the pending request, if any.
+    pub fn take_request(&mut self) -> Option<AutosplitRequest> {
+        self.request.take()
+    }
+}
+
+/// Render the Autosplitter window.
+pub fn show(
+    ctx: &egui::Context,
+    detached: &mut std::collections::HashSet<&'static str>,
+    open: &mut bool,
+    state: &mut AutosplitPanelState,
+) {
+    super::detachable_window(
+        ctx,
+        detached,
+        "autosplit",
+        "Autosplitter",
+        super::WindowCfg {
+            default_width: Some(460.0),
+            ..Default::default()
+        },
+        open,
+        |ui| {
+            if !state.rom_loaded {
+                ui.label("Load a ROM to define its splits.");
+                return;
+            }
+            timer(ui, state);
+            ui.separator();
+            livesplit(ui, state);
+            ui.separator();
+            editor(ui, state);
+        },
+    );
+}
+
+fn timer(ui: &mut egui::Ui, state: &mut AutosplitPanelState) {
+    let Some(view) = state.view.as_ref() else {
+        ui.weak("No splits installed. Define a start condition and Apply.");
+        return;
+    };
+    egui::Grid::new("autosplit-timer")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Timer");
+            ui.label(match view.phase {
+                TimerPhase::Idle => "waiting for start",
+                TimerPhase::Running if view.paused => "running (game time paused)",
+                TimerPhase::Running => "running",
+                TimerPhase::Finished => "finished",
+            });
+            ui.end_row();
+            ui.label("Game time");
+            ui.monospace(view.game_time());
+            ui.end_row();
+            ui.label("Real time");
+            ui.monospace(view.real_time());
+            ui.end_row();
+            ui.label("Split");
+            ui.monospace(format!(
+                "{}/{}{}",
+                view.split_index,
+                view.split_count,
+                view.segment
+                    .as_deref()
+                    .map_or_else(String::new, |s| format!("  {s}"))
+            ));
+            ui.end_row();
+            if let Some(delta) = view.delta_label() {
+                ui.label("vs PB");
+                let color = if view.delta.is_some_and(|d| d <= 0) {
+                    AHEAD
+                } else {
+                    BEHIND
+                };
+                ui.colored_label(color, delta);
+                ui.end_row();
+            }
+        });
+    if ui
+        .add_enabled(
+            view.phase != TimerPhase::Idle,
+            egui::Button::new("Reset timer"),
+        )
+        .clicked()
+    {
+        state.request = Some(AutosplitRequest::ResetTimer);
+    }
+}
+
+fn livesplit(ui: &mut egui::Ui, state: &mut AutosplitPanelState) {
+    ui.horizontal(|ui| {
+        ui.label("LiveSplit");
+        if let Some(addr) = state.livesplit.as_deref() {
+            ui.colored_label(AHEAD, format!("connected to {addr}"));
+            if ui.button("Disconnect").clicked() {
+                state.request = Some(AutosplitRequest::Disconnect);
+            }
+        } else {
+            ui.add(
+                egui::TextEdit::singleline(&mut state.addr)
+                    .hint_text("127.0.0.1:16834")
+                    .font(egui::TextStyle::Monospace)
+                    .desired_width(160.0),
+            );
+            if ui.button("Connect").clicked() {
+                state.request = Some(AutosplitRequest::Connect(state.addr.trim().to_owned()));
+            }
+        }
+    });
+    ui.weak("Start LiveSplit's server first (Control > Start TCP Server).");
+}
+
+fn editor(ui: &mut egui::Ui, state: &mut AutosplitPanelState) {
+    let draft = &mut state.draft;
+    let mut changed = false;
+    egui::Grid::new("autosplit-conditions")
+        .num_columns(2)
+        .show(ui, |ui| {
+            for (label, text, hint) in [
+                ("Category", &mut draft.name, "Any%"),
+                ("Start", &mut draft.start, "[$0770] == 1"),
+                ("Reset", &mut draft.reset, "[$0770] == 0"),
+                ("Pause", &mut draft.pause, "load screen, e.g. [$0772] == 0"),
+            ] {
+                ui.label(label);
+                changed |= ui
+                    .add(
+                        egui::TextEdit::singleline(text)
+                            .hint_text(hint)
+                            .font(egui::TextStyle::Monospace)
+                            .desired_width(320.0),
+                    )
+                    .changed();
+                ui.end_row();
+            }
+        });
+    changed |= ui
+        .checkbox(&mut draft.pause_on_lag, "Pause game time on lag frames")
+        .changed();
+
+    ui.separator();
+    let mut remove = None;
+    egui::Grid::new("autosplit-splits")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("Segment");
+            ui.label("Ends when");
+            ui.label("");
+            ui.end_row();
+            for (i, split) in draft.splits.iter_mut().enumerate() {
+                changed |= ui
+                    .add(egui::TextEdit::singleline(&mut split.name).desired_width(120.0))
+                    .changed();
+                changed |= ui
+                    .add(
+                        egui::TextEdit::singleline(&mut split.condition)
+                            .font(egui::TextStyle::Monospace)
+                            .desired_width(220.0),
+                    )
+                    .changed();
+                if ui.button("\u{2715}").clicked() {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+    if let Some(i) = remove {
+        draft.splits.remove(i);
+        changed = true;
+    }
+    if ui.button("Add split").clicked() {
+        let n = draft.splits.len() + 1;
+        draft.splits.push(SplitDef {
+            name: format!("Split {n}"),
+            condition: String::new(),
+        });
+        changed = true;
+    }
+    state.dirty |= changed;
+
+    if let Some(best) = draft.personal_best() {
+        ui.weak(format!(
+            "Personal best: {} game-time frames",
+            best.last().copied().unwrap_or_default()
+        ));
+    } else if !draft.best.is_empty() {
+        ui.weak("The personal best no longer matches the split list and will be replaced.");
+    }
+    for e in &state.errors {
+        ui.colored_label(ERROR, e);
+    }
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(state.dirty, egui::Button::new("Apply"))
+            .on_hover_text("Install these splits and save them for this ROM")
+            .clicked()
+        {
+            state.request = Some(AutosplitRequest::Apply(state.draft.clone()));
+        }
+        if ui
+            .add_enabled(
+                !state.draft.splits.is_empty(),
+                egui::Button::new("Export .lss..."),
+            )
+            .on_hover_text("Save the splits and PB as a LiveSplit splits file")
+            .clicked()
+        {
+            state.request = Some(AutosplitRequest::ExportLss);
+        }
+    });
+}
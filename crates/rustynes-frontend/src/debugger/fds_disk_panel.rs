@@ -0,0 +1,637 @@
+//! v2.5.0 — FDS disk browser: sides, files, preview, extract, inject.
+//!
+//! Surfaced from **Tools -> Game Data -> FDS Disk Browser**. Parses a Famicom
+//! Disk System image — either a `.fds` file on disk or the disk currently in
+//! the drive — into its per-side block structure via the mapper crate's
+//! [`parse_side_files`] walk, and lists every file with its id, name, load
+//! address, size, and kind (PRG / CHR / VRAM). Files past a side's file amount
+//! are listed as *hidden*: the BIOS never enumerates them, but games load them
+//! by reading on past the last counted file.
+//!
+//! The selected file previews as a hex dump or, for CHR data, as decoded 2bpp
+//! tiles. Files can be extracted one at a time or all at once, and a file can
+//! be replaced or a new one appended; [`FdsSideFiles::to_side`] rewrites the
+//! file headers' sizes and the file-amount byte, and the side is re-recorded
+//! onto the in-memory [`FdsDisk`] block by block, so only the blocks an edit
+//! touched get fresh CRCs. **Save disk as...** writes the headerless `.fds`
+//! form (the same serialization `Nes::disk_image_bytes` produces for
+//! `.fds.sav`), or the QD form, stored CRCs included, for a `.qd` name.
+//!
+//! The browser never touches the running core: "Load inserted disk" takes a
+//! *copy* of the drive's image in its lossless QD form, so deliberately bad
+//! CRCs survive, and edits only ever reach a file the user saves. Native-only
+//! (file pickers + `std::fs`).
+//!
+//! [`parse_side_files`]: rustynes_core::rustynes_mappers::parse_side_files
+
+use rustynes_core::Nes;
+use rustynes_core::rustynes_mappers::{
+    FdsDisk, FdsFile, FdsFileKind, FdsFsError, FdsSideFiles, parse_fds,
+};
+
+/// Bytes shown by the hex preview before it truncates (the full file is still
+/// what Extract writes).
+const HEX_PREVIEW_LIMIT: usize = 4096;
+
+/// Tiles per row of the CHR preview texture.
+const CHR_TILES_PER_ROW: usize = 16;
+
+/// How the selected file is previewed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Preview {
+    /// Offset / hex / ASCII dump.
+    #[default]
+    Hex,
+    /// Decoded 2bpp pattern tiles in a grey ramp.
+    Chr,
+}
+
+/// Persistent state of the FDS disk browser.
+#[derive(Default)]
+pub struct FdsDiskPanelState {
+    /// The disk being browsed. `None` until a file or the inserted disk is
+    /// loaded.
+    loaded: Option<LoadedDisk>,
+    /// Selected side index.
+    side: usize,
+    /// Selected file index within the side's file list.
+    file: Option<usize>,
+    preview: Preview,
+    /// CHR preview texture, keyed by (disk generation, side, file) so it is
+    /// only re-uploaded when the selection changes or an edit lands.
+    chr_tex: Option<((u64, usize, usize), egui::TextureHandle)>,
+    /// Last status / error line.
+    status: String,
+}
+
+struct LoadedDisk {
+    /// Where the image came from (a path, or the inserted disk).
+    source: String,
+    disk: FdsDisk,
+    /// Whether an injection has changed the image since it was loaded/saved.
+    modified: bool,
+    /// Bumped by every edit; keys [`Self::parsed`] and the CHR preview.
+    generation: u64,
+    /// The file list of one side as parsed at one generation, so a side is
+    /// walked once per edit rather than once per frame.
+    parsed: Option<(usize, u64, Result<FdsSideFiles, FdsFsError>)>,
+}
+
+impl LoadedDisk {
+    const fn new(source: String, disk: FdsDisk) -> Self {
+        Self {
+            source,
+            disk,
+            modified: false,
+            generation: 0,
+            parsed: None,
+        }
+    }
+
+    /// Take the parsed file list of `side`, walking the side again only when
+    /// the side or the disk changed since it was last handed back.
+    fn take_parsed(&mut self, side: usize) -> Result<FdsSideFiles, FdsFsError> {
+        match self.parsed.take() {
+            Some((s, generation, files)) if s == side && generation == self.generation => files,
+            _ => self.disk.side_files(side),
+        }
+    }
+}
+
+/// Render the FDS disk browser window. `nes` is only consulted for the "Load
+/// inserted disk" action.
+pub fn show(
+    ctx: &egui::Context,
+    detached: &mut std::collections::HashSet<&'static str>,
+    open: &mut bool,
+    state: &mut FdsDiskPanelState,
+    nes: Option<&Nes>,
+) {
+    super::detachable_window(
+        ctx,
+        detached,
+        "fds_disk",
+        "FDS Disk Browser",
+        super::WindowCfg {
+            default_pos: Some([140.0, 90.0]),
+            default_size: Some([560.0, 560.0]),
+            resizable: Some(true),
+            ..Default::default()
+        },
+        open,
+        |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("Open .fds file...").clicked() {
+                    open_file(state);
+                }
+                let inserted = nes.filter(|n| n.disk_side_count() > 0);
+                let clicked = ui
+                    .add_enabled(inserted.is_some(), egui::Button::new("Load inserted disk"))
+                    .on_disabled_hover_text("No FDS disk is loaded in the emulator.")
+                    .clicked();
+                if clicked && let Some(nes) = inserted {
+                    load_bytes(state, &nes.disk_image_qd_bytes(), "inserted disk".into());
+                }
+            });
+            let Some(loaded) = state.loaded.as_mut() else {
+                ui.separator();
+                ui.weak("Open a .fds image (or load the inserted disk) to browse its files.");
+                status_line(ui, &state.status);
+                return;
+            };
+            ui.monospace(format!(
+                "source: {}{}",
+                loaded.source,
+                if loaded.modified { "  (modified)" } else { "" }
+            ));
+            ui.separator();
+
+            side_picker(ui, loaded, &mut state.side, &mut state.file);
+            let side_idx = state.side;
+            let generation = loaded.generation;
+            let mut files = match loaded.take_parsed(side_idx) {
+                Ok(files) => files,
+                Err(e) => {
+                    ui.colored_label(egui::Color32::LIGHT_RED, format!("side unreadable: {e}"));
+                    loaded.parsed = Some((side_idx, generation, Err(e)));
+                    status_line(ui, &state.status);
+                    return;
+                }
+            };
+            disk_info(ui, &files);
+            ui.separator();
+            file_table(ui, &files, &mut state.file);
+            ui.separator();
+
+            let selected = state.file.filter(|&i| i < files.files.len());
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(selected.is_some(), egui::Button::new("Extract..."))
+                    .clicked()
+                    && let Some(i) = selected
+                {
+                    state.status = extract_one(side_idx, &files.files[i]);
+                }
+                if ui.button("Extract all...").clicked() {
+                    state.status = extract_all(side_idx, &files);
+                }
+                if ui
+                    .add_enabled(selected.is_some(), egui::Button::new("Replace..."))
+                    .clicked()
+                    && let Some(i) = selected
+                {
+                    state.status = replace_file(loaded, side_idx, &mut files, i);
+                }
+                if ui.button("Add file...").clicked() {
+                    state.status = add_file(loaded, side_idx, &mut files, &mut state.file);
+                }
+                if ui.button("Save disk as...").clicked() {
+                    state.status = save_disk(loaded);
+                }
+            });
+
+            if let Some(i) = selected {
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Preview:");
+                    ui.selectable_value(&mut state.preview, Preview::Hex, "Hex");
+                    ui.selectable_value(&mut state.preview, Preview::Chr, "CHR tiles");
+                });
+                let data = &files.files[i].data;
+                let key = (loaded.generation, side_idx, i);
+                match state.preview {
+                    Preview::Hex => hex_preview(ui, data),
+                    Preview::Chr => chr_preview(ui, ctx, &mut state.chr_tex, key, data),
+                }
+            }
+            // An edit this frame bumped the generation, so the next frame
+            // parses the re-recorded side instead of reusing this list.
+            loaded.parsed = Some((side_idx, generation, Ok(files)));
+            status_line(ui, &state.status);
+        },
+    );
+}
+
+fn status_line(ui: &mut egui::Ui, status: &str) {
+    if !status.is_empty() {
+        ui.separator();
+        ui.weak(status);
+    }
+}
+
+/// Side selector. Resets the file selection when the side changes.
+fn side_picker(ui: &mut egui::Ui, loaded: &LoadedDisk, side: &mut usize, file: &mut Option<usize>) {
+    let count = loaded.disk.side_count();
+    *side = (*side).min(count.saturating_sub(1));
+    let before = *side;
+    egui::ComboBox::from_label("Side")
+        .selected_text(side_label(*side))
+        .show_ui(ui, |ui| {
+            for s in 0..count {
+                ui.selectable_value(side, s, side_label(s));
+            }
+        });
+    if *side != before {
+        *file = None;
+    }
+}
+
+/// `Disk 1 side A`-style label for a zero-based side index.
+fn side_label(side: usize) -> String {
+    let letter = if side.is_multiple_of(2) { 'A' } else { 'B' };
+    format!("Disk {} side {letter}", side / 2 + 1)
+}
+
+fn disk_info(ui: &mut egui::Ui, files: &FdsSideFiles) {
+    let info = &files.info;
+    ui.monospace(format!(
+        "game {}  rev {}  maker ${:02X}  disk {} side {}  boot id <= ${:02X}",
+        info.game_code(),
+        info.revision(),
+        info.manufacturer(),
+        info.disk_number() + 1,
+        if info.side_number() == 0 { 'A' } else { 'B' },
+        info.boot_file_id(),
+    ));
+    ui.monospace(format!(
+        "{} counted file(s), {} hidden",
+        files.counted_files(),
+        files.files.len() - files.counted_files()
+    ));
+}
+
+/// The file list: one selectable row per file.
+fn file_table(ui: &mut egui::Ui, files: &FdsSideFiles, selected: &mut Option<usize>) {
+    let counted = files.counted_files();
+    egui::ScrollArea::vertical()
+        .id_salt("fds-files")
+        .max_height(200.0)
+        .show(ui, |ui| {
+            egui::Grid::new("fds-file-grid")
+                .num_columns(7)
+                .striped(true)
+                .show(ui, |ui| {
+                    for h in ["#", "id", "name", "kind", "load", "size", ""] {
+                        ui.strong(h);
+                    }
+                    ui.end_row();
+                    for (i, f) in files.files.iter().enumerate() {
+                        let label = format!("{:02X}", f.number);
+                        if ui.selectable_label(*selected == Some(i), label).clicked() {
+                            *selected = Some(i);
+                        }
+                        ui.monospace(format!("${:02X}", f.id));
+                        ui.monospace(f.name_lossy());
+                        ui.monospace(f.kind.label());
+                        ui.monospace(format!("${:04X}", f.load_address));
+                        ui.monospace(format!("{}", f.data.len()));
+                        if i >= counted {
+                            ui.weak("hidden");
+                        } else {
+                            ui.label("");
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+/// Offset / hex / ASCII dump of the first [`HEX_PREVIEW_LIMIT`] bytes.
+fn hex_preview(ui: &mut egui::Ui, data: &[u8]) {
+    let shown = &data[..data.len().min(HEX_PREVIEW_LIMIT)];
+    let rows = shown.len().div_ceil(16);
+    egui::ScrollArea::vertical()
+        .id_salt("fds-hex")
+        .max_height(220.0)
+        .show_rows(ui, 14.0, rows, |ui, range| {
+            for row in range {
+                let chunk = &shown[row * 16..(row * 16 + 16).min(shown.len())];
+                let hex: Vec<String> = chunk.iter().map(|b| format!("{b:02X}")).collect();
+                let ascii: String = chunk
+                    .iter()
+                    .map(|&b| {
+                        if b.is_ascii_graphic() {
+                            char::from(b)
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect();
+                ui.monospace(format!("{:04X}  {:<47}  {ascii}", row * 16, hex.join(" ")));
+            }
+        });
+    if data.len() > shown.len() {
+        ui.weak(format!("showing {} of {} bytes", shown.len(), data.len()));
+    }
+}
+
+/// Decode `data` as 2bpp NES tiles into an RGBA buffer, [`CHR_TILES_PER_ROW`]
+/// tiles wide. Returns `(width, height, rgba)`.
+fn chr_rgba(data: &[u8]) -> (usize, usize, Vec<u8>) {
+    const GREYS: [u8; 4] = [0x00, 0x55, 0xAA, 0xFF];
+    let tiles = data.len() / 16;
+    let rows = tiles.div_ceil(CHR_TILES_PER_ROW).max(1);
+    let (w, h) = (CHR_TILES_PER_ROW * 8, rows * 8);
+    let mut rgba = vec![0u8; w * h * 4];
+    for (t, tile) in data.chunks_exact(16).enumerate() {
+        let (tx, ty) = ((t % CHR_TILES_PER_ROW) * 8, (t / CHR_TILES_PER_ROW) * 8);
+        for y in 0..8 {
+            let (lo, hi) = (tile[y], tile[y + 8]);
+            for x in 0..8 {
+                let bit = 7 - x;
+                let idx = ((lo >> bit) & 1) | (((hi >> bit) & 1) << 1);
+                let g = GREYS[usize::from(idx)];
+                let p = ((ty + y) * w + tx + x) * 4;
+                rgba[p..p + 4].copy_from_slice(&[g, g, g, 0xFF]);
+            }
+        }
+    }
+    (w, h, rgba)
+}
+
+fn chr_preview(
+    ui: &mut egui::Ui,
+    ctx: &egui::Context,
+    tex: &mut Option<((u64, usize, usize), egui::TextureHandle)>,
+    key: (u64, usize, usize),
+    data: &[u8],
+) {
+    if data.len() < 16 {
+        ui.weak("File is shorter than one 16-byte tile.");
+        return;
+    }
+    if tex.as_ref().is_none_or(|(k, _)| *k != key) {
+        let (w, h, rgba) = chr_rgba(data);
+        let image = egui::ColorImage::from_rgba_unmultiplied([w, h], &rgba);
+        *tex = Some((
+            key,
+            ctx.load_texture("fds-chr", image, egui::TextureOptions::NEAREST),
+        ));
+    }
+    if let Some((_, handle)) = tex {
+        let size = handle.size_vec2() * 2.0;
+        egui::ScrollArea::vertical()
+            .id_salt("fds-chr")
+            .max_height(260.0)
+            .show(ui, |ui| {
+                ui.image((handle.id(), size));
+            });
+    }
+}
+
+fn open_file(state: &mut FdsDiskPanelState) {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("FDS disk image", &["fds", "qd", "sav"])
+        .pick_file()
+    else {
+        return;
+    };
+    match std::fs::read(&path) {
+        Ok(bytes) => load_bytes(state, &bytes, path.display().to_string()),
+        Err(e) => state.status = format!("read failed: {e}"),
+    }
+}
+
+fn load_bytes(state: &mut FdsDiskPanelState, bytes: &[u8], source: String) {
+    match parse_fds(bytes) {
+        Ok(disk) => {
+            state.status = format!("{} side(s) loaded", disk.side_count());
+            state.loaded = Some(LoadedDisk::new(source, disk));
+            state.side = 0;
+            state.file = None;
+            state.chr_tex = None;
+        }
+        Err(e) => state.status = format!("not an FDS image: {e}"),
+    }
+}
+
+/// File-name stem for an extracted file: `s<side>-<nn>-<name>`.
+fn extract_stem(side: usize, file: &FdsFile) -> String {
+    let name: String = file
+        .name_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("s{side}-{:02X}-{name}", file.number)
+}
+
+/// Extension for an extracted file, from its kind.
+fn kind_ext(kind: FdsFileKind) -> &'static str {
+    match kind {
+        FdsFileKind::Prg => "prg",
+        FdsFileKind::Chr => "chr",
+        FdsFileKind::Vram => "vram",
+        FdsFileKind::Other(_) => "bin",
+    }
+}
+
+fn extract_one(side: usize, file: &FdsFile) -> String {
+    let default = format!("{}.{}", extract_stem(side, file), kind_ext(file.kind));
+    let Some(path) = rfd::FileDialog::new().set_file_name(&default).save_file() else {
+        return String::new();
+    };
+    match crate::atomic_write::write_atomic(&path, &file.data) {
+        Ok(()) => format!("wrote {} bytes to {}", file.data.len(), path.display()),
+        Err(e) => format!("write failed: {e}"),
+    }
+}
+
+fn extract_all(side: usize, files: &FdsSideFiles) -> String {
+    let Some(dir) = rfd::FileDialog::new().pick_folder() else {
+        return String::new();
+    };
+    for file in &files.files {
+        let path = dir.join(format!(
+            "{}.{}",
+            extract_stem(side, file),
+            kind_ext(file.kind)
+        ));
+        if let Err(e) = crate::atomic_write::write_atomic(&path, &file.data) {
+            return format!("write failed ({}): {e}", path.display());
+        }
+    }
+    format!(
+        "extracted {} file(s) to {}",
+        files.files.len(),
+        dir.display()
+    )
+}
+
+/// Re-record the edited `files` onto side `side` of the loaded disk.
+fn commit_side(loaded: &mut LoadedDisk, side: usize, files: &FdsSideFiles) -> Result<(), String> {
+    let bytes = files.to_side().map_err(|e| e.to_string())?;
+    loaded.disk.replace_side(side, &bytes);
+    loaded.modified = true;
+    loaded.generation += 1;
+    Ok(())
+}
+
+fn pick_input_file() -> Option<(std::path::PathBuf, Vec<u8>)> {
+    let path = rfd::FileDialog::new().pick_file()?;
+    let bytes = std::fs::read(&path).ok()?;
+    Some((path, bytes))
+}
+
+fn replace_file(
+    loaded: &mut LoadedDisk,
+    side: usize,
+    files: &mut FdsSideFiles,
+    index: usize,
+) -> String {
+    let Some((path, data)) = pick_input_file() else {
+        return String::new();
+    };
+    let len = data.len();
+    if let Err(e) = files.replace_file(index, data) {
+        return format!("replace failed: {e}");
+    }
+    match commit_side(loaded, side, files) {
+        Ok(()) => format!(
+            "replaced file {index} with {} ({len} bytes)",
+            path.display()
+        ),
+        Err(e) => format!("replace failed: {e}"),
+    }
+}
+
+/// Append a file after the last counted file. The kind comes from the
+/// extension (`.chr` / `.vram`, else PRG); the name from the file stem; the id
+/// is one past the highest id on the side. The load address starts at the
+/// kind's usual base and can be patched with a hex editor afterwards.
+fn add_file(
+    loaded: &mut LoadedDisk,
+    side: usize,
+    files: &mut FdsSideFiles,
+    selected: &mut Option<usize>,
+) -> String {
+    let Some((path, data)) = pick_input_file() else {
+        return String::new();
+    };
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    let (kind, load_address) = match ext.as_deref() {
+        Some("chr") => (FdsFileKind::Chr, 0x0000),
+        Some("vram") => (FdsFileKind::Vram, 0x2000),
+        _ => (FdsFileKind::Prg, 0x6000),
+    };
+    let mut name = [b' '; 8];
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("FILE")
+        .to_ascii_uppercase();
+    for (dst, src) in name
+        .iter_mut()
+        .zip(stem.bytes().filter(u8::is_ascii_graphic))
+    {
+        *dst = src;
+    }
+    let id = files
+        .files
+        .iter()
+        .map(|f| f.id)
+        .max()
+        .map_or(0, |m| m.wrapping_add(1));
+    let file = FdsFile {
+        number: 0,
+        id,
+        name,
+        load_address,
+        kind,
+        data,
+    };
+    let index = match files.insert_file(file) {
+        Ok(i) => i,
+        Err(e) => return format!("add failed: {e}"),
+    };
+    match commit_side(loaded, side, files) {
+        Ok(()) => {
+            *selected = Some(index);
+            format!("added {} as file {index}", path.display())
+        }
+        Err(e) => format!("add failed: {e}"),
+    }
+}
+
+/// Write the (possibly edited) disk as a headerless `.fds` image, or as a QD
+/// image with its stored CRCs when the name ends in `.qd`.
+fn save_disk(loaded: &mut LoadedDisk) -> String {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("FDS disk image", &["fds"])
+        .add_filter("QD disk image (keeps CRCs)", &["qd"])
+        .set_file_name("disk.fds")
+        .save_file()
+    else {
+        return String::new();
+    };
+    let qd = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("qd"));
+    let bytes = if qd {
+        loaded.disk.to_qd_bytes()
+    } else {
+        loaded.disk.to_bytes()
+    };
+    match crate::atomic_write::write_atomic(&path, &bytes) {
+        Ok(()) => {
+            loaded.modified = false;
+            loaded.source = path.display().to_string();
+            format!("saved {}", path.display())
+        }
+        Err(e) => format!("write failed: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chr_rgba_decodes_both_planes() {
+        // One tile: row 0 low plane all set, row 1 high plane all set.
+        let mut tile = [0u8; 16];
+        tile[0] = 0xFF;
+        tile[9] = 0xFF;
+        let (w, h, rgba) = chr_rgba(&tile);
+        assert_eq!((w, h), (CHR_TILES_PER_ROW * 8, 8));
+        assert_eq!(rgba[0], 0x55, "low plane -> colour 1");
+        assert_eq!(rgba[w * 4], 0xAA, "high plane -> colour 2");
+        assert_eq!(rgba[2 * w * 4], 0x00, "blank row -> colour 0");
+    }
+
+    #[test]
+    fn an_edit_invalidates_the_parsed_side() {
+        let mut side = vec![0u8; 65500];
+        side[0] = 0x01;
+        side[1..15].copy_from_slice(b"*NINTENDO-HVC*");
+        side[56..58].copy_from_slice(&[0x02, 0x00]);
+        let mut loaded = LoadedDisk::new("test".into(), parse_fds(&side).unwrap());
+        let mut files = loaded.take_parsed(0).unwrap();
+        assert!(files.files.is_empty());
+        let stale = files.clone();
+        let generation = loaded.generation;
+        let file = FdsFile {
+            number: 0,
+            id: 1,
+            name: *b"NEW     ",
+            load_address: 0x6000,
+            kind: FdsFileKind::Prg,
+            data: vec![0xAB; 3],
+        };
+        files.insert_file(file).unwrap();
+        commit_side(&mut loaded, 0, &files).unwrap();
+        // A list handed back under the pre-edit generation is not reused.
+        loaded.parsed = Some((0, generation, Ok(stale)));
+        let files = loaded.take_parsed(0).unwrap();
+        assert_eq!(files.files[0].data, [0xAB; 3]);
+        assert_ne!(loaded.generation, generation);
+    }
+
+    #[test]
+    fn side_labels_pair_sides_into_disks() {
+        assert_eq!(side_label(0), "Disk 1 side A");
+        assert_eq!(side_label(3), "Disk 2 side B");
+    }
+}
@@ -14,6 +14,7 @@
 use rustynes_core::Nes;
 use rustynes_core::rustynes_mappers::{Mirroring, Region};
 
+use crate::config::ExpansionDevice;
 use crate::game_db::{self, GameDbEntry};
 
 /// Editor UI state: the edit buffers + which CRC they were loaded for.
@@ -38,6 +39,11 @@ pub struct GameDbPanelState {
     dip_bits: [bool; 8],
     /// v1.7.0 H4 — last per-game-overlay (DIP) action result.
     per_game_status: Option<String>,
+    /// v2.5.0 — the per-game port-2 device being edited (`None` = follow the
+    /// global `[input] expansion_device`).
+    expansion_device: Option<ExpansionDevice>,
+    /// v2.5.0 — last per-game port-2 device action result.
+    device_status: Option<String>,
 }
 
 /// Pack the 8 edited DIP bits (switch 1 = index 0 = bit 0) into a byte.
@@ -87,13 +93,16 @@ impl GameDbPanelState {
         // ever reads/writes the config-dir overlay), so pass `None`.
         #[cfg(not(target_arch = "wasm32"))]
         {
-            let dip = crate::per_game::resolve(crc, None).and_then(|c| c.dip_switches);
+            let per_game = crate::per_game::resolve(crc, None);
+            let dip = per_game.as_ref().and_then(|c| c.dip_switches);
             self.dip_override = dip.is_some();
             self.dip_bits = dip_bits_from(dip.unwrap_or(0));
+            self.expansion_device = per_game.and_then(|c| c.expansion_device);
         }
         self.loaded_crc = Some(crc);
         self.status = None;
         self.per_game_status = None;
+        self.device_status = None;
     }
 
     /// Build a [`GameDbEntry`] from the current edit buffers.
@@ -252,6 +261,12 @@ pub fn show(
             // per-game `<rom>.json` overlay (config-dir, keyed by CRC) and apply
             // live via the same `set_vs_dip` core setter the load path uses.
             dip_switch_section(ui, state, nes, crc);
+
+            // v2.5.0 — a per-game port-2 device, for the titles that need one
+            // (a Turbo File save game, a Zapper shooter). Native-only: the
+            // browser build has no per-game files.
+            #[cfg(not(target_arch = "wasm32"))]
+            expansion_device_section(ui, state, crc);
         },
     );
 }
@@ -353,6 +368,56 @@ fn persist_dip(crc: u32, state: &GameDbPanelState) -> String {
     }
 }
 
+/// Render the per-game port-2 device picker. v2.5.0.
+#[cfg(not(target_arch = "wasm32"))]
+fn expansion_device_section(ui: &mut egui::Ui, state: &mut GameDbPanelState, crc: u32) {
+    const GLOBAL: &str = "(global setting)";
+    ui.separator();
+    ui.heading("Port 2 Device");
+    ui.label(
+        egui::RichText::new(
+            "Attach a specific port-2 device whenever this game loads, in place \
+             of the Input setting (e.g. a Turbo File for a game that saves to \
+             one). Applies on the next ROM load.",
+        )
+        .small()
+        .weak(),
+    );
+    egui::ComboBox::from_id_salt("gdb_expansion_device")
+        .selected_text(
+            state
+                .expansion_device
+                .map_or(GLOBAL, ExpansionDevice::label),
+        )
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut state.expansion_device, None, GLOBAL);
+            for device in ExpansionDevice::ALL {
+                ui.selectable_value(&mut state.expansion_device, Some(device), device.label());
+            }
+        });
+    if ui.button("Save Device to Per-Game File").clicked() {
+        state.device_status = Some(persist_expansion_device(crc, state.expansion_device));
+    }
+    if let Some(msg) = &state.device_status {
+        ui.label(egui::RichText::new(msg).small());
+    }
+}
+
+/// Persist the per-game port-2 device into this ROM's `<rom>.json` overlay,
+/// merging with whatever else it carries. v2.5.0.
+#[cfg(not(target_arch = "wasm32"))]
+fn persist_expansion_device(crc: u32, device: Option<ExpansionDevice>) -> String {
+    let mut cfg = crate::per_game::resolve(crc, None).unwrap_or_default();
+    cfg.expansion_device = device;
+    match crate::per_game::save_overlay(crc, &cfg) {
+        Ok(()) if device.is_some() => {
+            "Saved the port-2 device to the per-game overlay.".to_string()
+        }
+        Ok(()) => "Cleared the per-game port-2 device.".to_string(),
+        Err(e) => format!("Save failed: {e}"),
+    }
+}
+
 /// On wasm there is no filesystem overlay; the edit applies live only.
 #[cfg(target_arch = "wasm32")]
 fn persist_dip(_crc: u32, _state: &GameDbPanelState) -> String {
@@ -99,6 +99,30 @@ pub enum ExpansionMini {
         /// The 8-sensor mask (bits `0..8`).
         mask: u8,
     },
+    /// Oeka Kids drawing tablet.
+    OekaKidsTablet {
+        /// Pen tip on the surface.
+        touch: bool,
+        /// Pen button held.
+        button: bool,
+        /// The pen is over the drawing surface.
+        on_screen: bool,
+    },
+    /// An analog expansion controller (v2.5.0): Hori Track, Pachinko, Top
+    /// Rider or Power Glove.
+    Analog {
+        /// Device name.
+        name: &'static str,
+        /// Ball motion / handlebars / hand position, `-127..=127` each
+        /// (positive = right / up).
+        x: i8,
+        /// See `x`.
+        y: i8,
+        /// Plunger / throttle / hand depth, `0..=255`.
+        level: u8,
+        /// Pad (or glove keypad) buttons, [`Buttons`] bits.
+        buttons: u8,
+    },
 }
 
 /// The per-frame input-miniatures snapshot the app pushes to the debugger.
@@ -408,6 +432,76 @@ fn draw_expansion(ui: &mut egui::Ui, exp: ExpansionMini) {
             label(ui, "Bandai Hyper Shot");
             draw_mat(ui, 4, 2, u32::from(mask));
         }
+        ExpansionMini::OekaKidsTablet {
+            touch,
+            button,
+            on_screen,
+        } => {
+            label(ui, "Oeka Kids");
+            let (p, o) = card(ui, 120.0, 48.0);
+            // Drawing surface (lit while the pen is over it).
+            p.rect_filled(
+                Rect::from_min_size(o + Vec2::new(10.0, 8.0), Vec2::new(64.0, 32.0)),
+                CornerRadius::same(2),
+                fill(on_screen),
+            );
+            p.circle_filled(o + Vec2::new(92.0, 16.0), 6.0, fill(touch));
+            p.circle_filled(o + Vec2::new(92.0, 34.0), 6.0, fill(button));
+            p.text(
+                o + Vec2::new(108.0, 16.0),
+                egui::Align2::CENTER_CENTER,
+                "tip",
+                egui::FontId::proportional(8.0),
+                LABEL,
+            );
+            p.text(
+                o + Vec2::new(108.0, 34.0),
+                egui::Align2::CENTER_CENTER,
+                "btn",
+                egui::FontId::proportional(8.0),
+                LABEL,
+            );
+        }
+        ExpansionMini::Analog {
+            name,
+            x,
+            y,
+            level,
+            buttons,
+        } => {
+            label(ui, name);
+            draw_analog(ui, x, y, level, buttons);
+        }
+    }
+}
+
+/// v2.5.0 — an analog controller: a position well with its dot, a level bar
+/// and one lamp per held button.
+fn draw_analog(ui: &mut egui::Ui, x: i8, y: i8, level: u8, buttons: u8) {
+    let (p, o) = card(ui, 120.0, 48.0);
+    let well = Rect::from_min_size(o + Vec2::new(8.0, 6.0), Vec2::splat(36.0));
+    p.rect_stroke(
+        well,
+        CornerRadius::same(3),
+        Stroke::new(1.0, OUTLINE),
+        egui::StrokeKind::Inside,
+    );
+    let dot = well.center() + Vec2::new(f32::from(x), -f32::from(y)) * (16.0 / 127.0);
+    p.circle_filled(dot, 4.0, ACTIVE);
+    let bar = Rect::from_min_size(o + Vec2::new(52.0, 6.0), Vec2::new(8.0, 36.0));
+    p.rect_filled(bar, CornerRadius::same(2), IDLE);
+    let h = 36.0 * f32::from(level) / 255.0;
+    p.rect_filled(
+        Rect::from_min_max(Pos2::new(bar.left(), bar.bottom() - h), bar.right_bottom()),
+        CornerRadius::same(2),
+        ACTIVE,
+    );
+    for i in 0..8u8 {
+        let c = o + Vec2::new(
+            72.0 + f32::from(i % 4) * 12.0,
+            16.0 + f32::from(i / 4) * 16.0,
+        );
+        p.circle_filled(c, 4.0, fill(buttons & (1 << i) != 0));
     }
 }
 
@@ -100,6 +100,7 @@ enum Slot {
     SpeedUp,
     SpeedDown,
     SpeedReset,
+    PracticeReload,
     // Player 1 gamepad.
     G1Up,
     G1Down,
@@ -230,6 +231,7 @@ const KB_ROWS: &[(Slot, &str)] = &[
     (Slot::SpeedUp, "Speed up"),
     (Slot::SpeedDown, "Speed down"),
     (Slot::SpeedReset, "Speed reset"),
+    (Slot::PracticeReload, "Practice reload"),
     (Slot::MovieRecord, "Movie record"),
     (Slot::MoviePlay, "Movie play"),
     (Slot::MovieBranch, "Movie branch"),
@@ -462,6 +464,53 @@ fn device_config_ui(ui: &mut egui::Ui, state: &mut InputPanelState, config: &mut
                 });
             });
         }
+        // v2.5.0 — Hori Track: the ball reads the same scaled mouse motion as
+        // the SNES mouse.
+        ExpansionDevice::HoriTrack => {
+            ui.group(|ui| {
+                ui.label(egui::RichText::new("Hori Track").strong());
+                pointer_scale_slider(ui, state, config);
+            });
+        }
+        // v2.5.0 — Power Glove: the joypad-mode gesture program.
+        ExpansionDevice::PowerGlove => {
+            ui.group(|ui| {
+                ui.label(egui::RichText::new("Power Glove").strong());
+                ui.horizontal(|ui| {
+                    ui.label("Gesture program");
+                    let mut program = config.input.power_glove_program;
+                    egui::ComboBox::from_id_salt("dev-glove-program")
+                        .selected_text(program.label())
+                        .show_ui(ui, |ui| {
+                            for variant in crate::config::PowerGloveProgramSetting::ALL {
+                                ui.selectable_value(&mut program, variant, variant.label());
+                            }
+                        });
+                    if program != config.input.power_glove_program {
+                        config.input.power_glove_program = program;
+                        state.bindings_dirty = true;
+                    }
+                });
+            });
+        }
+        // Turbo File Twin: the bank switch on the case. Flipping it is
+        // picked up live, like moving the real switch.
+        ExpansionDevice::TurboFileTwin => {
+            ui.group(|ui| {
+                ui.label(egui::RichText::new("Turbo File Twin").strong());
+                ui.horizontal(|ui| {
+                    ui.label("Bank switch");
+                    let mut bank = config.input.turbo_file_bank.min(3);
+                    for b in 0..4u8 {
+                        ui.selectable_value(&mut bank, b, format!("{}", b + 1));
+                    }
+                    if bank != config.input.turbo_file_bank {
+                        config.input.turbo_file_bank = bank;
+                        state.bindings_dirty = true;
+                    }
+                });
+            });
+        }
         _ => {}
     }
 }
@@ -604,45 +653,11 @@ pub fn body(ui: &mut egui::Ui, state: &mut InputPanelState, config: &mut Config)
             use crate::config::ExpansionDevice;
             let mut dev = config.input.expansion_device;
             egui::ComboBox::from_label("Port 2 device ($4017)")
-                .selected_text(match dev {
-                    ExpansionDevice::None => "Standard controller",
-                    ExpansionDevice::Zapper => "Zapper (light gun)",
-                    ExpansionDevice::Vaus => "Vaus (Arkanoid paddle)",
-                    ExpansionDevice::PowerPad => "Power Pad (mat)",
-                    ExpansionDevice::SnesMouse => "SNES mouse",
-                    ExpansionDevice::FamilyKeyboard => "Family BASIC keyboard",
-                    ExpansionDevice::FamilyTrainer => "Family Trainer (mat)",
-                    ExpansionDevice::SuborKeyboard => "Subor keyboard",
-                    ExpansionDevice::KonamiHyperShot => "Konami Hyper Shot",
-                    ExpansionDevice::BandaiHyperShot => "Bandai Hyper Shot",
-                })
+                .selected_text(dev.label())
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut dev, ExpansionDevice::None, "Standard controller");
-                    ui.selectable_value(&mut dev, ExpansionDevice::Zapper, "Zapper (light gun)");
-                    ui.selectable_value(&mut dev, ExpansionDevice::Vaus, "Vaus (Arkanoid paddle)");
-                    ui.selectable_value(&mut dev, ExpansionDevice::PowerPad, "Power Pad (mat)");
-                    ui.selectable_value(&mut dev, ExpansionDevice::SnesMouse, "SNES mouse");
-                    ui.selectable_value(
-                        &mut dev,
-                        ExpansionDevice::FamilyKeyboard,
-                        "Family BASIC keyboard",
-                    );
-                    ui.selectable_value(
-                        &mut dev,
-                        ExpansionDevice::FamilyTrainer,
-                        "Family Trainer (mat)",
-                    );
-                    ui.selectable_value(&mut dev, ExpansionDevice::SuborKeyboard, "Subor keyboard");
-                    ui.selectable_value(
-                        &mut dev,
-                        ExpansionDevice::KonamiHyperShot,
-                        "Konami Hyper Shot",
-                    );
-                    ui.selectable_value(
-                        &mut dev,
-                        ExpansionDevice::BandaiHyperShot,
-                        "Bandai Hyper Shot",
-                    );
+                    for variant in ExpansionDevice::ALL {
+                        ui.selectable_value(&mut dev, variant, variant.label());
+                    }
                 });
             if dev != config.input.expansion_device {
                 config.input.expansion_device = dev;
@@ -650,6 +665,24 @@ pub fn body(ui: &mut egui::Ui, state: &mut InputPanelState, config: &mut Config)
             }
         }
 
+        // v2.5.0 — R.O.B. sits in front of the screen rather than on a port,
+        // so it is chosen alongside (not instead of) the port-2 device.
+        {
+            use crate::config::RobSetting;
+            let mut rob = config.input.rob;
+            egui::ComboBox::from_label("R.O.B.")
+                .selected_text(rob.label())
+                .show_ui(ui, |ui| {
+                    for variant in [RobSetting::Off, RobSetting::Gyromite, RobSetting::StackUp] {
+                        ui.selectable_value(&mut rob, variant, variant.label());
+                    }
+                });
+            if rob != config.input.rob {
+                config.input.rob = rob;
+                state.bindings_dirty = true;
+            }
+        }
+
         // v1.5.0 "Lens" Workstream D4 — device-config controls, shown
         // contextually for the selected port-2 device (Arkanoid Vaus + SNES
         // mouse sensitivity, Power Pad / Family Trainer layout variants). All
@@ -768,6 +801,7 @@ fn read_slot(cfg: &Config, slot: Slot) -> String {
         Slot::SpeedUp => i.system.speed_up.clone(),
         Slot::SpeedDown => i.system.speed_down.clone(),
         Slot::SpeedReset => i.system.speed_reset.clone(),
+        Slot::PracticeReload => i.system.practice_reload.clone(),
         Slot::G1Up => i.gamepad1.up.clone(),
         Slot::G1Down => i.gamepad1.down.clone(),
         Slot::G1Left => i.gamepad1.left.clone(),
@@ -859,6 +893,7 @@ fn apply_slot(cfg: &mut Config, slot: Slot, name: &str) {
         Slot::SpeedUp => &mut i.system.speed_up,
         Slot::SpeedDown => &mut i.system.speed_down,
         Slot::SpeedReset => &mut i.system.speed_reset,
+        Slot::PracticeReload => &mut i.system.practice_reload,
         Slot::G1Up => &mut i.gamepad1.up,
         Slot::G1Down => &mut i.gamepad1.down,
         Slot::G1Left => &mut i.gamepad1.left,
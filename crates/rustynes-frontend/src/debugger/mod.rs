@@ -58,6 +58,11 @@
 //!
 //! - `input_rebind_panel` — modal key/gamepad rebinding flow.
 //! - `input_miniatures_panel` — the live "Input Display" controller HUD.
+//! - `rob_panel` — animated R.O.B. arm / gyro view (native).
+//! - `autosplit_panel` — speedrun timer, `LiveSplit` link and split editor
+//!   (native).
+//! - `practice_panel` — practice segments, attempt timing and history
+//!   (native).
 //! - `cheat_panel` — Game Genie + raw-RAM cheat list with per-ROM persistence.
 //! - `settings_panel` — graphics / audio / rewind config editor.
 //! - `netplay_panel` / `cheevos_panel` — netplay + `RetroAchievements`
@@ -68,6 +73,7 @@
 //! - `nsf_panel` — NSF music-player controls.
 //! - `header_editor` — iNES / NES 2.0 header editor (native, on-disk).
 //! - `game_db_panel` — per-game ROM-database editor.
+//! - `fds_disk_panel` — FDS disk file browser / extractor / injector (native).
 //! - `doc_panel` — in-app Documentation browser (native).
 //! - `hd_pixel_panel` — HD-pack per-pixel inspector (`hd-pack`).
 //! - `perf_panel` — frame-pacing / audio-health instrumentation.
@@ -91,7 +97,15 @@ mod apu_panel;
 // v2.1.6 "Expansion Audio" B7 — shared oscilloscope/VU primitives + the Audio
 // Mixer tool panel (per-source mix-balance sliders + per-channel scopes).
 mod audio_mixer;
+// v2.5.0 — speedrun timer readout, LiveSplit link and split-file editor
+// (native-only: split files and the LiveSplit socket need std::fs / std::net).
 mod audio_scope;
+#[cfg(not(target_arch = "wasm32"))]
+mod autosplit_panel;
+// v2.5.0 — practice segments with anchor thumbnails and attempt statistics
+// (native-only: the anchors are save-state files).
+#[cfg(not(target_arch = "wasm32"))]
+mod practice_panel;
 // v1.7.0 "Forge" Workstream A3 — inline 6502 assembler used by the CPU panel.
 mod assembler;
 // v1.7.0 "Forge" Workstream A2 — iNES/NES 2.0 header editor + Cartridge Info
@@ -119,8 +133,13 @@ mod basic_bot_panel;
 // v1.6.0 "Studio" Workstream C (C1) — the debugger expression evaluator (CPU /
 // PPU / memory / access-context tokens + the C-style operator set). Shared by
 // the watch panel's conditional breakpoints / watchpoints / watch window /
-// conditional trace. Pure + frontend-only; the unit tests live in the module.
-mod expr;
+// conditional trace, and (v2.5.0) the autosplitter's split conditions. Pure +
+// frontend-only; the unit tests live in the module.
+pub(crate) mod expr;
+// v2.5.0 — FDS disk browser: per-side file list, hex / CHR preview, extract
+// and inject. Native-only (file pickers + std::fs).
+#[cfg(not(target_arch = "wasm32"))]
+mod fds_disk_panel;
 mod game_db_panel;
 // v2.2.0 "Capstone" — read-only ROM Info browser (per-game DB + No-Intro CRC +
 // decoded cartridge header for the loaded ROM).
@@ -148,6 +167,9 @@ mod netplay_panel;
 mod nsf_panel;
 mod oam_panel;
 mod replay_panel;
+// v2.5.0 — animated R.O.B. side panel (the robot is attached natively only).
+#[cfg(not(target_arch = "wasm32"))]
+mod rob_panel;
 // v2.8.0 Phase 0 — frame-pacing / audio-health instrumentation panel.
 mod audio_provenance_panel;
 mod perf_panel;
@@ -165,14 +187,20 @@ mod trace_panel;
 // `expr` evaluator + the core's observational `debug-hooks` per-frame logs.
 mod watch_panel;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use autosplit_panel::AutosplitRequest;
 pub use cheevos_panel::{CheevosRequest, CheevosStatusView};
+#[cfg(not(target_arch = "wasm32"))]
+pub use practice_panel::PracticeRequest;
 // v1.5.0 "Lens" Workstream A1 — the input-miniatures snapshot the app pushes.
 pub use input_miniatures_panel::{ExpansionMini, MiniaturesSnapshot};
 pub use netplay_panel::{
-    CrcCompareView, NetplayDiagnosticsView, NetplayPhaseView, NetplayRequest, NetplayStatusView,
+    ChatEntryView, CrcCompareView, DelayChangeView, NetplayDiagnosticsView, NetplayPhaseView,
+    NetplayRequest, NetplayStatusView,
 };
 pub use script_panel::ScriptAction;
 pub use settings_panel::SettingsApply;
+pub(crate) use watch_panel::ReplayCtx;
 
 /// A non-chip tool panel surfaced directly from the menu bar (v1.0.0).
 ///
@@ -211,6 +239,15 @@ pub enum ToolPanel {
     /// device HUD (v1.7.0 "Forge" beta.5, #51; the v1.5.0 "Lens" Workstream A1
     /// Input Miniatures overlay absorbed the former standalone Input Display).
     InputDisplay,
+    /// v2.5.0 — the animated R.O.B. panel. Unconditional variant so the menu
+    /// IA + dispatch match stay exhaustive; the panel itself is native-only.
+    Rob,
+    /// v2.5.0 — the speedrun autosplitter (timer, `LiveSplit`, split editor).
+    /// Unconditional variant; the panel itself is native-only.
+    Autosplit,
+    /// v2.5.0 — practice mode (segments, attempt timing and history).
+    /// Unconditional variant; the panel itself is native-only.
+    Practice,
     /// Replay / TAS window (v1.5.0 "Lens" Workstream C2).
     Replay,
     /// `TAStudio` piano-roll editor (v1.6.0 "Studio" Workstream A2).
@@ -233,6 +270,10 @@ pub enum ToolPanel {
     /// a moment in the mix back to the channels and the writing instruction.
     /// Unconditional variant for the same reason as [`Self::PixelProvenance`].
     AudioProvenance,
+    /// v2.5.0 — the FDS disk browser: sides, files, preview, extract / inject.
+    /// Unconditional variant so the menu IA + dispatch match stay exhaustive;
+    /// the panel itself is native-only.
+    FdsDisk,
 }
 
 /// A chip-inspection panel surfaced from the Debug menu (v1.0.0).
@@ -564,10 +605,14 @@ pub fn detached_window_meta(id: &'static str) -> (&'static str, (u32, u32)) {
         "mapper" => ("Mapper", (460, 520)),
         "audio_mixer" => ("Audio Mixer", (400, 520)),
         "input_display" => ("Input Display", (560, 320)),
+        "rob" => ("R.O.B.", (360, 360)),
+        "autosplit" => ("Autosplitter", (480, 560)),
+        "practice" => ("Practice", (540, 560)),
         "replay" => ("Replay", (380, 360)),
         "cheat" => ("Cheats", (460, 440)),
         "game_db" => ("Game Database", (560, 480)),
         "rom_info" => ("ROM Info", (520, 520)),
+        "fds_disk" => ("FDS Disk Browser", (560, 560)),
         "latency_oracle" => ("Latency Oracle", (380, 420)),
         "ram_atlas" => ("RAM Atlas", (560, 620)),
         "divergence_lens" => ("Divergence Lens", (420, 520)),
@@ -615,9 +660,11 @@ pub fn detached_refresh(id: &'static str) -> DetachedRefresh {
     match id {
         // Live per-frame state.
         "cpu" | "ppu" | "oam" | "apu" | "memory" | "memory_compare" | "event" | "trace"
-        | "watch" | "perf" | "audio_mixer" | "input_display" | "provenance" => Live,
+        | "watch" | "perf" | "audio_mixer" | "input_display" | "provenance" | "rob" => Live,
         // Slowly-changing status / playback progress.
-        "mapper" | "nsf" | "replay" | "netplay" | "cheevos" | "tas" => Throttled,
+        "mapper" | "nsf" | "replay" | "netplay" | "cheevos" | "tas" | "autosplit" | "practice" => {
+            Throttled
+        }
         // Static / edit-driven panels (cheat, rom_info, game_db, header_editor,
         // settings, documentation, script, basic_bot) and anything unknown.
         _ => OnInteraction,
@@ -736,6 +783,9 @@ pub struct DebuggerOverlay {
     show_game_db: bool,
     /// Read-only ROM Info browser open flag (v2.2.0 "Capstone").
     show_rom_info: bool,
+    /// v2.5.0 — FDS disk browser open flag (native-only).
+    #[cfg(not(target_arch = "wasm32"))]
+    show_fds_disk: bool,
     /// v2.3.6 — Latency Oracle panel visible.
     show_latency: bool,
     /// v2.3.6 — RAM Atlas window visible.
@@ -749,6 +799,15 @@ pub struct DebuggerOverlay {
     /// "Input Display" panel open flag (v1.7.0 "Forge" beta.5, #51; née the
     /// v1.5.0 A1 Input Miniatures overlay).
     show_input_display: bool,
+    /// v2.5.0 — R.O.B. panel open flag (native-only).
+    #[cfg(not(target_arch = "wasm32"))]
+    show_rob: bool,
+    /// v2.5.0 — Autosplitter panel open flag (native-only).
+    #[cfg(not(target_arch = "wasm32"))]
+    show_autosplit: bool,
+    /// v2.5.0 — Practice panel open flag (native-only).
+    #[cfg(not(target_arch = "wasm32"))]
+    show_practice: bool,
     /// v1.5.0 A4 — HD-pack pixel inspector open flag (native + `hd-pack`).
     #[cfg(all(not(target_arch = "wasm32"), feature = "hd-pack"))]
     show_hd_pixel: bool,
@@ -802,6 +861,17 @@ pub struct DebuggerOverlay {
     /// The live input snapshot the app pushes each frame (standard pads + the
     /// active expansion device), drawn by the "Input Display" panel.
     input_display: MiniaturesSnapshot,
+    /// v2.5.0 — R.O.B. panel state + the robot the app pushes each frame.
+    #[cfg(not(target_arch = "wasm32"))]
+    rob_ui: rob_panel::RobPanelState,
+    #[cfg(not(target_arch = "wasm32"))]
+    rob: Option<rustynes_core::Rob>,
+    /// v2.5.0 — Autosplitter panel state (native-only).
+    #[cfg(not(target_arch = "wasm32"))]
+    autosplit_ui: autosplit_panel::AutosplitPanelState,
+    /// v2.5.0 — Practice panel state (native-only).
+    #[cfg(not(target_arch = "wasm32"))]
+    practice_ui: practice_panel::PracticePanelState,
     /// v1.5.0 A4 — HD-pack pixel inspector state (native + `hd-pack`).
     #[cfg(all(not(target_arch = "wasm32"), feature = "hd-pack"))]
     hd_pixel_ui: hd_pixel_panel::HdPixelPanelState,
@@ -831,6 +901,9 @@ pub struct DebuggerOverlay {
     game_db_ui: game_db_panel::GameDbPanelState,
     /// Read-only ROM Info panel state (v2.2.0 "Capstone").
     rom_info_ui: rom_info_panel::RomInfoPanelState,
+    /// v2.5.0 — FDS disk browser state (native-only).
+    #[cfg(not(target_arch = "wasm32"))]
+    fds_disk_ui: fds_disk_panel::FdsDiskPanelState,
     latency_ui: latency_panel::LatencyPanel,
     /// v2.3.6 — RAM Atlas panel state.
     atlas_ui: atlas_panel::AtlasPanel,
@@ -1015,6 +1088,8 @@ impl DebuggerOverlay {
             show_perf: false,
             show_game_db: false,
             show_rom_info: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            show_fds_disk: false,
             show_latency: false,
             show_atlas: false,
             writes_locked: false,
@@ -1022,6 +1097,12 @@ impl DebuggerOverlay {
             show_audio_provenance: false,
             audio_provenance_ui: audio_provenance_panel::AudioProvenancePanelState::default(),
             show_input_display: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            show_rob: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            show_autosplit: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            show_practice: false,
             #[cfg(all(not(target_arch = "wasm32"), feature = "hd-pack"))]
             show_hd_pixel: false,
             cpu_ui: cpu_panel::CpuPanelState::default(),
@@ -1046,6 +1127,14 @@ impl DebuggerOverlay {
             input_ui: input_rebind_panel::InputPanelState::default(),
             input_display_ui: input_miniatures_panel::InputMiniaturesPanelState,
             input_display: MiniaturesSnapshot::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            rob_ui: rob_panel::RobPanelState::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            rob: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            autosplit_ui: autosplit_panel::AutosplitPanelState::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            practice_ui: practice_panel::PracticePanelState::default(),
             #[cfg(all(not(target_arch = "wasm32"), feature = "hd-pack"))]
             hd_pixel_ui: hd_pixel_panel::HdPixelPanelState::default(),
             #[cfg(not(target_arch = "wasm32"))]
@@ -1058,6 +1147,8 @@ impl DebuggerOverlay {
             cheat_ui: cheat_panel::CheatPanelState::default(),
             game_db_ui: game_db_panel::GameDbPanelState::default(),
             rom_info_ui: rom_info_panel::RomInfoPanelState,
+            #[cfg(not(target_arch = "wasm32"))]
+            fds_disk_ui: fds_disk_panel::FdsDiskPanelState::default(),
             latency_ui: latency_panel::LatencyPanel::default(),
             atlas_ui: atlas_panel::AtlasPanel::default(),
             #[cfg(feature = "debug-hooks")]
@@ -1468,6 +1559,13 @@ impl DebuggerOverlay {
         self.settings_ui.set_audio_output_devices(names);
     }
 
+    /// v2.5.0 — forward the enumerated input devices to the settings panel's
+    /// microphone picker.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_audio_input_devices(&mut self, names: Vec<String>) {
+        self.settings_ui.set_audio_input_devices(names);
+    }
+
     /// v2.8.0 Phase 0 — push the latest performance snapshot (produced /
     /// presented / produce-cost interval stats + audio health) for the
     /// Performance panel. Called from the app's pacer alongside
@@ -1506,6 +1604,11 @@ impl DebuggerOverlay {
         self.netplay_ui.set_status(status);
     }
 
+    /// Append a sent or received netplay chat line to the panel's chat pane.
+    pub fn push_netplay_chat(&mut self, line: ChatEntryView) {
+        self.netplay_ui.push_chat(line);
+    }
+
     /// v2.3.0 — return (and clear) the pending netplay host/join/leave request
     /// the user clicked in the netplay panel. The app acts on it by driving
     /// its `NetplayUi` (`start_host` / `start_join` / leave).
@@ -1635,6 +1738,12 @@ impl DebuggerOverlay {
             ToolPanel::Input => self.show_input = true,
             ToolPanel::GameDb => self.show_game_db = true,
             ToolPanel::RomInfo => self.show_rom_info = true,
+            ToolPanel::FdsDisk => {
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    self.show_fds_disk = true;
+                }
+            }
             ToolPanel::LatencyOracle => self.show_latency = true,
             ToolPanel::RamAtlas => self.show_atlas = true,
             #[cfg(feature = "debug-hooks")]
@@ -1642,6 +1751,24 @@ impl DebuggerOverlay {
             ToolPanel::PixelProvenance => self.show_provenance = true,
             ToolPanel::AudioProvenance => self.show_audio_provenance = true,
             ToolPanel::InputDisplay => self.show_input_display = true,
+            ToolPanel::Rob => {
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    self.show_rob = true;
+                }
+            }
+            ToolPanel::Autosplit => {
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    self.show_autosplit = true;
+                }
+            }
+            ToolPanel::Practice => {
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    self.show_practice = true;
+                }
+            }
             ToolPanel::Replay => self.show_replay = true,
             ToolPanel::BasicBot => self.show_basic_bot = true,
             ToolPanel::AudioMixer => self.show_audio_mixer = true,
@@ -1663,6 +1790,74 @@ impl DebuggerOverlay {
         self.input_display = snap;
     }
 
+    /// v2.5.0 — push the attached R.O.B. (a per-frame clone) for the R.O.B.
+    /// panel; `None` when no robot is set up.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_rob(&mut self, rob: Option<rustynes_core::Rob>) {
+        self.rob = rob;
+    }
+
+    /// v2.5.0 — hand the Autosplitter panel the loaded ROM's split file and
+    /// its compile errors (`None` with no ROM). Replaces any unsaved draft.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_autosplit_file(
+        &mut self,
+        file: Option<(crate::autosplit::SplitFile, Vec<String>)>,
+        livesplit_addr: &str,
+    ) {
+        self.autosplit_ui.set_file(file);
+        self.autosplit_ui.set_livesplit_addr(livesplit_addr);
+    }
+
+    /// v2.5.0 — push the timer snapshot and the connected `LiveSplit` address
+    /// for the Autosplitter panel.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_autosplit_status(
+        &mut self,
+        view: Option<crate::autosplit::TimerView>,
+        livesplit: Option<String>,
+    ) {
+        self.autosplit_ui.set_status(view, livesplit);
+    }
+
+    /// v2.5.0 — take the Autosplitter panel's pending request.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn take_autosplit_request(&mut self) -> Option<AutosplitRequest> {
+        self.autosplit_ui.take_request()
+    }
+
+    /// v2.5.0 — hand the Practice panel the loaded ROM's segments (`None`
+    /// with no ROM) and the anchor thumbnails that changed; `reset_thumbs`
+    /// drops the cached ones first. `reload_key` labels the reload hint.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_practice(
+        &mut self,
+        set: Option<crate::practice::PracticeSet>,
+        thumbs: Vec<(u32, Vec<u8>)>,
+        reset_thumbs: bool,
+        reload_key: &str,
+    ) {
+        self.practice_ui
+            .set_practice(set, thumbs, reset_thumbs, reload_key);
+    }
+
+    /// v2.5.0 — push the attempt in progress and the last end-condition
+    /// error for the Practice panel.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_practice_status(
+        &mut self,
+        attempt: Option<crate::practice::AttemptView>,
+        error: Option<String>,
+    ) {
+        self.practice_ui.set_status(attempt, error);
+    }
+
+    /// v2.5.0 — take the Practice panel's pending request.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn take_practice_request(&mut self) -> Option<PracticeRequest> {
+        self.practice_ui.take_request()
+    }
+
     /// v1.5.0 "Lens" Workstream I10 — open the in-app Documentation browser
     /// (Help -> Documentation). Native-only.
     #[cfg(not(target_arch = "wasm32"))]
@@ -1964,12 +2159,18 @@ impl DebuggerOverlay {
     /// `nes`-reading tool panels are **Cheats** (`show_cheat`), the
     /// **ROM Database** editor (`show_game_db`), and the read-only **ROM Info**
     /// browser (`show_rom_info`), the **Pixel Provenance** inspector
-    /// (`show_provenance`), and the **Latency Oracle** (`show_latency`). If you
-    /// add another panel that
+    /// (`show_provenance`), the **Latency Oracle** (`show_latency`), and the
+    /// native **FDS Disk Browser** (`show_fds_disk`, for "Load inserted disk").
+    /// If you add another panel that
     /// takes `&Nes` / `&mut Nes` in `tool_panels`, add its `show_*` flag here too.
     #[must_use]
     pub const fn any_nes_tool_open(&self) -> bool {
-        self.show_cheat
+        #[cfg(not(target_arch = "wasm32"))]
+        let fds_disk = self.show_fds_disk;
+        #[cfg(target_arch = "wasm32")]
+        let fds_disk = false;
+        fds_disk
+            || self.show_cheat
             || self.show_game_db
             || self.show_rom_info
             || self.show_provenance
@@ -2703,6 +2904,34 @@ impl DebuggerOverlay {
                 &self.input_display,
             );
         }
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.show_rob {
+            rob_panel::show(
+                ctx,
+                &mut self.detached_panels,
+                &mut self.show_rob,
+                &mut self.rob_ui,
+                self.rob.as_ref(),
+            );
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.show_autosplit {
+            autosplit_panel::show(
+                ctx,
+                &mut self.detached_panels,
+                &mut self.show_autosplit,
+                &mut self.autosplit_ui,
+            );
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.show_practice {
+            practice_panel::show(
+                ctx,
+                &mut self.detached_panels,
+                &mut self.show_practice,
+                &mut self.practice_ui,
+            );
+        }
         if self.show_replay {
             // v1.5.0 "Lens" C2 — control + read-out surface; reads the pushed
             // status snapshot, not `nes`, so it renders in the always-on path.
@@ -2820,6 +3049,18 @@ impl DebuggerOverlay {
                 self.rom_crc_full,
             );
         }
+        // v2.5.0 — FDS disk browser. Works on a copy of the image (a file, or
+        // the inserted disk's bytes), so it only ever reads `nes`.
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.show_fds_disk {
+            fds_disk_panel::show(
+                ctx,
+                &mut self.detached_panels,
+                &mut self.show_fds_disk,
+                &mut self.fds_disk_ui,
+                nes.as_deref(),
+            );
+        }
         if self.show_settings {
             settings_panel::show(
                 ctx,
@@ -83,17 +83,60 @@ pub struct NetplayDiagnosticsView {
     pub total_compares: u64,
     /// Total mismatched comparisons recorded this session.
     pub mismatches: u64,
+    /// Desyncs repaired by an authoritative state transfer from the host.
+    pub recoveries: u64,
+    /// The frame the most recent recovery resynchronized at, if any.
+    pub last_recovery_frame: Option<u32>,
     /// The most recent comparison `(frame, local_crc, remote_crc, matched,
     /// same_framebuffer)`, for the local-vs-remote CRC readout.
     pub last_compare: Option<CrcCompareView>,
     /// The most recent comparisons (oldest first), capped at
     /// [`NetplayDiagnosticsView::HISTORY_SHOWN`] entries for the panel table.
     pub recent: Vec<CrcCompareView>,
+    /// The input delay local input is authored with (frames).
+    pub input_delay: u32,
+    /// A delay change `(frame, delay)` the host scheduled that is not yet in
+    /// effect.
+    pub scheduled_input_delay: Option<(u32, u32)>,
+    /// The delay the link measurements suggest, when it differs from the
+    /// current one.
+    pub suggested_input_delay: Option<u32>,
+    /// The last measured input round trip in frames, if any.
+    pub round_trip_frames: Option<u32>,
+    /// The most recent input-delay changes (oldest first), capped at
+    /// [`NetplayDiagnosticsView::DELAY_CHANGES_SHOWN`].
+    pub delay_changes: Vec<DelayChangeView>,
 }
 
 impl NetplayDiagnosticsView {
     /// Maximum CRC-history rows carried into the view for the panel table.
     pub const HISTORY_SHOWN: usize = 12;
+    /// Maximum input-delay changes carried into the view.
+    pub const DELAY_CHANGES_SHOWN: usize = 4;
+}
+
+/// One applied input-delay change, copied for the read-only panel view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DelayChangeView {
+    /// The first frame run with the new delay.
+    pub frame: u32,
+    /// The delay before the change.
+    pub from: u32,
+    /// The delay after it.
+    pub to: u32,
+}
+
+/// Chat lines the panel keeps (oldest dropped first).
+const CHAT_LINES_KEPT: usize = 50;
+
+/// One chat line of the running match, sent or received.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChatEntryView {
+    /// The sending player's index (this peer's own lines carry its
+    /// `local_player`).
+    pub from: u8,
+    /// The line itself.
+    pub text: String,
 }
 
 /// One recorded CRC comparison, copied for the read-only panel view.
@@ -142,16 +185,33 @@ pub enum NetplayRequest {
         /// How many players (2..=4) the host runs the session with. 3-4
         /// players use the Four Score adapter.
         num_players: u8,
+        /// Room passphrase; empty plays unsecured.
+        passphrase: String,
     },
     /// Join a host at the given `host:port` address.
     Join {
         /// The host's `IP:port`.
         remote: String,
+        /// Room passphrase; empty plays unsecured.
+        passphrase: String,
     },
     /// v1.7.0 H8 — spectate (watch read-only) a match at the given `host:port`.
     Spectate {
         /// The host's `IP:port`.
         remote: String,
+        /// Room passphrase; empty watches unsecured.
+        passphrase: String,
+    },
+    /// Host only: switch every peer in the running match to `delay` frames
+    /// of input delay.
+    SetInputDelay {
+        /// The new input delay (frames).
+        delay: u32,
+    },
+    /// Send a chat line to the other players of the running match.
+    Chat {
+        /// The line to send.
+        text: String,
     },
     /// Leave the current session (back to single-player).
     Leave,
@@ -174,6 +234,9 @@ pub struct NetplayPanelState {
     join_remote: String,
     /// v1.7.0 H8 — spectate "host:port" address buffer.
     spectate_remote: String,
+    /// Room passphrase shared by host / join / spectate. Session-only: it is
+    /// never written to the config.
+    passphrase: String,
     /// `true` once the fields have been seeded from config (so we don't
     /// clobber user edits on later syncs).
     seeded: bool,
@@ -181,6 +244,10 @@ pub struct NetplayPanelState {
     request: Option<NetplayRequest>,
     /// Latest status pushed by the app.
     status: NetplayStatusView,
+    /// The chat line being typed.
+    chat_draft: String,
+    /// The match's chat so far, oldest first (at most [`CHAT_LINES_KEPT`]).
+    chat: std::collections::VecDeque<ChatEntryView>,
 }
 
 impl NetplayPanelState {
@@ -194,6 +261,14 @@ impl NetplayPanelState {
         self.status = status;
     }
 
+    /// Append a chat line (sent or received) to the chat pane.
+    pub fn push_chat(&mut self, line: ChatEntryView) {
+        if self.chat.len() >= CHAT_LINES_KEPT {
+            self.chat.pop_front();
+        }
+        self.chat.push_back(line);
+    }
+
     /// The current status snapshot (used by the app for the toolbar HUD).
     #[must_use]
     pub fn status(&self) -> &NetplayStatusView {
@@ -297,6 +372,7 @@ fn body(ui: &mut egui::Ui, state: &mut NetplayPanelState, config: &mut crate::co
     // --- Status block ---
     ui.label(egui::RichText::new("Status").strong());
     let st = &state.status;
+    let mut apply_delay = None;
     match st.phase {
         Idle => {
             ui.label("Single-player (not connected).");
@@ -347,6 +423,7 @@ fn body(ui: &mut egui::Ui, state: &mut NetplayPanelState, config: &mut crate::co
             if !sync.is_empty() {
                 ui.label(sync.join("   "));
             }
+            apply_delay = input_delay_line(ui, st);
             diagnostics_section(ui, &st.diagnostics);
         }
         Spectating => {
@@ -380,6 +457,38 @@ fn body(ui: &mut egui::Ui, state: &mut NetplayPanelState, config: &mut crate::co
     ui.separator();
 
     let active = !matches!(st.phase, Idle);
+    let chatting = matches!(st.phase, InGame).then_some(st.diagnostics.local_player);
+    if let Some(delay) = apply_delay {
+        state.request = Some(NetplayRequest::SetInputDelay { delay });
+    }
+
+    // --- Chat (in game) ---
+    if let Some(local) = chatting {
+        if let Some(text) = chat_section(ui, state, local) {
+            state.request = Some(NetplayRequest::Chat { text });
+        }
+        ui.separator();
+    }
+
+    // --- Passphrase (session-only, never saved) ---
+    ui.add_enabled_ui(!active, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("passphrase:");
+            ui.add(
+                egui::TextEdit::singleline(&mut state.passphrase)
+                    .password(true)
+                    .hint_text("optional")
+                    .desired_width(180.0),
+            )
+            .on_hover_text(
+                "Everyone in the room enters the same passphrase. Traffic is \
+                 then encrypted and authenticated, and anything sent without \
+                 it is ignored. Leave empty to play unsecured.",
+            );
+        });
+    });
+
+    ui.separator();
 
     // --- Host ---
     ui.add_enabled_ui(!active, |ui| {
@@ -407,13 +516,34 @@ fn body(ui: &mut egui::Ui, state: &mut NetplayPanelState, config: &mut crate::co
             )
             .weak(),
         );
+        ui.checkbox(
+            &mut config.netplay.adaptive_input_delay,
+            "Adapt the input delay to the link",
+        )
+        .on_hover_text(
+            "Raise or lower everyone's input delay as the connection \
+             changes. Off: a better delay is only suggested, for you to \
+             apply.",
+        );
+        ui.checkbox(
+            &mut config.netplay.write_back_host_save,
+            "Keep the session's save afterwards",
+        )
+        .on_hover_text(
+            "Off: the match plays on a copy of your battery save / FDS disk \
+             and your own is restored when you leave.",
+        );
         if ui.button("Host").clicked()
             && let Ok(port) = state.host_port.trim().parse::<u16>()
         {
             let num_players = state.host_num_players.clamp(2, 4);
             config.netplay.host_port = port;
             config.netplay.num_players = num_players;
-            state.request = Some(NetplayRequest::Host { port, num_players });
+            state.request = Some(NetplayRequest::Host {
+                port,
+                num_players,
+                passphrase: state.passphrase.clone(),
+            });
         }
     });
 
@@ -430,10 +560,17 @@ fn body(ui: &mut egui::Ui, state: &mut NetplayPanelState, config: &mut crate::co
                     .desired_width(180.0),
             );
         });
+        ui.checkbox(&mut config.netplay.use_host_save, "Use the host's save")
+            .on_hover_text(
+                "If your battery save or FDS disk differs from the host's, play \
+                 the match on a copy of theirs (yours is left untouched) \
+                 instead of refusing it.",
+            );
         if ui.button("Join").clicked() {
             config.netplay.last_join_address = state.join_remote.trim().to_string();
             state.request = Some(NetplayRequest::Join {
                 remote: state.join_remote.trim().to_string(),
+                passphrase: state.passphrase.clone(),
             });
         }
     });
@@ -465,12 +602,22 @@ fn body(ui: &mut egui::Ui, state: &mut NetplayPanelState, config: &mut crate::co
         if ui.button("Spectate").clicked() {
             state.request = Some(NetplayRequest::Spectate {
                 remote: state.spectate_remote.trim().to_string(),
+                passphrase: state.passphrase.clone(),
             });
         }
     });
 
     ui.separator();
 
+    ui.add_enabled_ui(!active, |ui| {
+        ui.checkbox(&mut config.netplay.record_matches, "Record the match")
+            .on_hover_text(
+                "Save the match as an attested .rnm movie in your movies \
+                 folder when you leave. Anyone with the ROM can replay it and \
+                 check it with `rustynes verify`.",
+            );
+    });
+
     // --- Leave ---
     ui.add_enabled_ui(active, |ui| {
         if ui.button("Leave").clicked() {
@@ -489,6 +636,66 @@ fn body(ui: &mut egui::Ui, state: &mut NetplayPanelState, config: &mut crate::co
     );
 }
 
+/// Render the in-game chat pane: the match's lines so far and a one-line
+/// composer. Returns the line to send when the user presses Send or Enter.
+#[cfg(not(target_arch = "wasm32"))]
+fn chat_section(ui: &mut egui::Ui, state: &mut NetplayPanelState, local: u8) -> Option<String> {
+    ui.label(egui::RichText::new("Chat").strong());
+    egui::ScrollArea::vertical()
+        .id_salt("netplay-chat")
+        .max_height(96.0)
+        .stick_to_bottom(true)
+        .show(ui, |ui| {
+            for line in &state.chat {
+                let who = if line.from == local {
+                    "you".to_string()
+                } else {
+                    format!("P{}", line.from + 1)
+                };
+                ui.label(format!("{who}: {}", line.text));
+            }
+        });
+    let mut send = None;
+    ui.horizontal(|ui| {
+        let edit = ui.add(
+            egui::TextEdit::singleline(&mut state.chat_draft)
+                .hint_text("message")
+                .char_limit(rustynes_netplay::MAX_CHAT_BYTES)
+                .desired_width(240.0),
+        );
+        let entered = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if (ui.button("Send").clicked() || entered) && !state.chat_draft.trim().is_empty() {
+            send = Some(state.chat_draft.trim().to_string());
+            state.chat_draft.clear();
+        }
+    });
+    send
+}
+
+/// Render the in-game input-delay line: the current delay, the measured round
+/// trip, a scheduled change, and the suggested delay. A host gets a button to
+/// apply the suggestion, whose delay is returned when clicked.
+#[cfg(not(target_arch = "wasm32"))]
+fn input_delay_line(ui: &mut egui::Ui, st: &NetplayStatusView) -> Option<u32> {
+    let diag = &st.diagnostics;
+    let mut apply = None;
+    ui.horizontal(|ui| {
+        let rtt = diag
+            .round_trip_frames
+            .map_or_else(String::new, |f| format!(" (round trip {f} frames)"));
+        ui.label(format!("input delay: {}{rtt}", diag.input_delay));
+        if let Some((frame, delay)) = diag.scheduled_input_delay {
+            ui.label(format!("-> {delay} at frame {frame}"));
+        } else if let Some(delay) = diag.suggested_input_delay {
+            ui.label(egui::RichText::new(format!("suggested: {delay}")).weak());
+            if st.is_host && ui.button(format!("Use {delay}")).clicked() {
+                apply = Some(delay);
+            }
+        }
+    });
+    apply
+}
+
 /// Render the read-only "Diagnostics" section (v1.3.0 Workstream G1): the room
 /// / input topology, the in-sync / desynced status, lifetime compare counts,
 /// the last local-vs-remote CRC, and a rolling CRC-match history table. All
@@ -540,13 +747,31 @@ fn diagnostics_section(ui: &mut egui::Ui, diag: &NetplayDiagnosticsView) {
                 let frame = diag
                     .first_desync_frame
                     .map_or_else(|| "?".to_string(), |f| f.to_string());
-                ui.colored_label(
-                    egui::Color32::from_rgb(0xE0, 0x40, 0x40),
-                    format!(
-                        "DESYNCED at frame {frame} ({} mismatches / {} compares)",
-                        diag.mismatches, diag.total_compares,
-                    ),
-                );
+                if diag.recoveries > 0 && diag.consecutive_mismatches == 0 {
+                    // Diverged, but a state transfer has since resynchronized
+                    // the peers and the checksums agree again.
+                    ui.colored_label(
+                        egui::Color32::from_rgb(0xE0, 0xB0, 0x40),
+                        format!(
+                            "recovered (first desync at frame {frame}; {} mismatches / {} compares)",
+                            diag.mismatches, diag.total_compares,
+                        ),
+                    );
+                } else {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(0xE0, 0x40, 0x40),
+                        format!(
+                            "DESYNCED at frame {frame} ({} mismatches / {} compares)",
+                            diag.mismatches, diag.total_compares,
+                        ),
+                    );
+                }
+                if diag.recoveries > 0 {
+                    let at = diag
+                        .last_recovery_frame
+                        .map_or_else(String::new, |f| format!(", last at frame {f}"));
+                    ui.label(format!("recoveries: {}{at}", diag.recoveries));
+                }
                 if diag.consecutive_mismatches > 0 {
                     ui.label(format!(
                         "consecutive mismatches: {}",
@@ -596,6 +821,16 @@ fn diagnostics_section(ui: &mut egui::Ui, diag: &NetplayDiagnosticsView) {
                         }
                     });
             }
+
+            // --- Input-delay changes ---
+            if !diag.delay_changes.is_empty() {
+                ui.separator();
+                ui.label(egui::RichText::new("Input delay changes").strong());
+                // Newest first, like the CRC history.
+                for c in diag.delay_changes.iter().rev() {
+                    ui.label(format!("frame {}: {} -> {}", c.frame, c.from, c.to));
+                }
+            }
         });
 }
 
@@ -622,6 +857,19 @@ mod tests {
         assert!(s.take_request().is_none());
     }
 
+    #[test]
+    fn chat_pane_keeps_the_newest_lines() {
+        let mut s = NetplayPanelState::default();
+        for i in 0..CHAT_LINES_KEPT + 3 {
+            s.push_chat(ChatEntryView {
+                from: 1,
+                text: format!("line {i}"),
+            });
+        }
+        assert_eq!(s.chat.len(), CHAT_LINES_KEPT);
+        assert_eq!(s.chat.front().map(|l| l.text.as_str()), Some("line 3"));
+    }
+
     #[test]
     fn status_round_trips() {
         let mut s = NetplayPanelState::default();
@@ -645,6 +893,8 @@ mod tests {
             consecutive_mismatches: 3,
             total_compares: 10,
             mismatches: 3,
+            recoveries: 1,
+            last_recovery_frame: Some(91),
             last_compare: Some(CrcCompareView {
                 frame: 90,
                 local: 0xAAAA,
@@ -659,6 +909,15 @@ mod tests {
                 matched: true,
                 same_framebuffer: true,
             }],
+            input_delay: 3,
+            scheduled_input_delay: None,
+            suggested_input_delay: Some(5),
+            round_trip_frames: Some(9),
+            delay_changes: vec![DelayChangeView {
+                frame: 120,
+                from: 2,
+                to: 3,
+            }],
         };
         s.set_status(NetplayStatusView {
             phase: NetplayPhaseView::InGame,
@@ -669,10 +928,14 @@ mod tests {
         assert!(!st.diagnostics.in_sync);
         assert_eq!(st.diagnostics.first_desync_frame, Some(60));
         assert_eq!(st.diagnostics.consecutive_mismatches, 3);
+        assert_eq!(st.diagnostics.recoveries, 1);
+        assert_eq!(st.diagnostics.last_recovery_frame, Some(91));
         assert_eq!(st.diagnostics.local_player, 1);
         assert_eq!(st.diagnostics.recent.len(), 1);
         let last = st.diagnostics.last_compare.expect("last compare set");
         assert!(!last.matched);
         assert!(last.same_framebuffer);
+        assert_eq!(st.diagnostics.suggested_input_delay, Some(5));
+        assert_eq!(st.diagnostics.delay_changes[0].to, 3);
     }
 }
@@ -0,0 +1,348 @@
+//! v2.5.0 — **Practice** panel: the loaded ROM's practice segments, each
+//! with its anchor thumbnail and attempt statistics, plus the attempt in
+//! progress.
+//!
+//! The app owns the [`PracticeSet`] and pushes a copy here whenever it
+//! changes, with the anchors' thumbnails (decoded from the `.rns` blobs by
+//! `Nes::extract_thumbnail`). Thumbnails are uploaded once per push, never
+//! per frame. Every edit goes back to the app as a [`PracticeRequest`].
+
+use std::collections::HashMap;
+
+use crate::autosplit::format_time;
+use crate::practice::{AttemptView, PracticeSet};
+
+const ERROR: egui::Color32 = egui::Color32::from_rgb(0xE0, 0x40, 0x40);
+const FINISHED: egui::Color32 = egui::Color32::from_rgb(0x40, 0xC0, 0x60);
+
+/// Thumbnail size in the segment list (half the captured 128x120).
+const THUMB: egui::Vec2 = egui::vec2(64.0, 60.0);
+
+/// What the user asked the app to do from the panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PracticeRequest {
+    /// Add a segment with this name, anchored at the current state.
+    Add(String),
+    /// Re-anchor segment `id` at the current state.
+    Recapture(u32),
+    /// Make segment `id` current and load it (starts an attempt).
+    Load(u32),
+    /// Rename segment `id` and set its end condition.
+    Edit {
+        /// Segment id.
+        id: u32,
+        /// New name.
+        name: String,
+        /// New end condition.
+        end: String,
+    },
+    /// Delete segment `id` and its anchor.
+    Remove(u32),
+    /// Forget segment `id`'s attempt history.
+    ClearHistory(u32),
+    /// Save every attempt as CSV (native file dialog).
+    ExportCsv,
+}
+
+/// Panel state: the pushed set, thumbnails and the selected segment's draft.
+#[derive(Default)]
+pub struct PracticePanelState {
+    set: Option<PracticeSet>,
+    /// Thumbnails waiting for a context to upload them.
+    pending: Vec<(u32, Vec<u8>)>,
+    thumbs: HashMap<u32, egui::TextureHandle>,
+    attempt: Option<AttemptView>,
+    error: Option<String>,
+    new_name: String,
+    reload_key: String,
+    /// Selected segment and its name / end-condition drafts.
+    edit: Option<(u32, String, String)>,
+    request: Option<PracticeRequest>,
+}
+
+impl PracticePanelState {
+    /// Replace the segment list (`None` with no ROM). `thumbs` carries the
+    /// anchors whose thumbnail changed; `reset_thumbs` drops every cached one
+    /// first (on ROM change). `reload_key` is the hotkey's binding, for the
+    /// hint line.
+    pub fn set_practice(
+        &mut self,
+        set: Option<PracticeSet>,
+        thumbs: Vec<(u32, Vec<u8>)>,
+        reset_thumbs: bool,
+        reload_key: &str,
+    ) {
+        reload_key.clone_into(&mut self.reload_key);
+        if reset_thumbs || set.is_none() {
+            self.thumbs.clear();
+            self.pending.clear();
+            self.edit = None;
+        }
+        if let Some(set) = set.as_ref() {
+            self.thumbs
+                .retain(|id, _| set.segments.iter().any(|s| s.id == *id));
+            if let Some((id, ..)) = self.edit
+                && !set.segments.iter().any(|s| s.id == id)
+            {
+                self.edit = None;
+            }
+        }
+        self.pending.extend(thumbs);
+        self.set = set;
+    }
+
+    /// Push the attempt in progress and the last end-condition error.
+    pub fn set_status(&mut self, attempt: Option<AttemptView>, error: Option<String>) {
+        self.attempt = attempt;
+        self.error = error;
+    }
+
+    /// Take the pending request, if any.
+    pub fn take_request(&mut self) -> Option<PracticeRequest> {
+        self.request.take()
+    }
+
+    fn upload_pending(&mut self, ctx: &egui::Context) {
+        let w = rustynes_core::THUMBNAIL_WIDTH;
+        let h = rustynes_core::THUMBNAIL_HEIGHT;
+        for (id, rgba) in self.pending.drain(..) {
+            if rgba.len() != w * h * 4 {
+                self.thumbs.remove(&id);
+                continue;
+            }
+            let image = egui::ColorImage::from_rgba_unmultiplied([w, h], &rgba);
+            self.thumbs.insert(
+                id,
+                ctx.load_texture(
+                    format!("practice-thumb-{id}"),
+                    image,
+                    egui::TextureOptions::NEAREST,
+                ),
+            );
+        }
+    }
+}
+
+/// Render the Practice window.
+pub fn show(
+    ctx: &egui::Context,
+    detached: &mut std::collections::HashSet<&'static str>,
+    open: &mut bool,
+    state: &mut PracticePanelState,
+) {
+    state.upload_pending(ctx);
+    super::detachable_window(
+        ctx,
+        detached,
+        "practice",
+        "Practice",
+        super::WindowCfg {
+            default_width: Some(520.0),
+            ..Default::default()
+        },
+        open,
+        |ui| {
+            let Some(set) = state.set.as_ref() else {
+                ui.label("Load a ROM to practice its segments.");
+                return;
+            };
+            attempt(ui, state, set);
+            ui.separator();
+            segments(ui, state);
+            ui.separator();
+            footer(ui, state);
+        },
+    );
+}
+
+fn attempt(ui: &mut egui::Ui, state: &PracticePanelState, set: &PracticeSet) {
+    match state.attempt.as_ref() {
+        Some(view) => {
+            let name = set
+                .segments
+                .iter()
+                .find(|s| s.id == view.segment)
+                .map_or("?", |s| s.name.as_str());
+            ui.horizontal(|ui| {
+                ui.label(format!("{name}:"));
+                if view.finished {
+                    ui.colored_label(FINISHED, format!("finished in {}", view.time()));
+                } else {
+                    ui.monospace(view.time());
+                }
+            });
+        }
+        None => {
+            ui.weak(format!(
+                "Press {} (or Load) to start an attempt at the current segment.",
+                state.reload_key
+            ));
+        }
+    }
+    if let Some(e) = state.error.as_deref() {
+        ui.colored_label(ERROR, format!("End condition: {e}"));
+    }
+}
+
+fn segments(ui: &mut egui::Ui, state: &mut PracticePanelState) {
+    let Some(set) = state.set.as_ref() else {
+        return;
+    };
+    if set.segments.is_empty() {
+        ui.weak("No segments yet. Play to the start of one and add it below.");
+        return;
+    }
+    let frame = state
+        .attempt
+        .as_ref()
+        .map_or_else(|| std::time::Duration::from_nanos(16_639_267), |a| a.frame);
+    let mut request = None;
+    let mut select = None;
+    egui::ScrollArea::vertical()
+        .max_height(320.0)
+        .show(ui, |ui| {
+            egui::Grid::new("practice-segments")
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (i, seg) in set.segments.iter().enumerate() {
+                        match state.thumbs.get(&seg.id) {
+                            Some(tex) => {
+                                ui.add(egui::Image::new((tex.id(), THUMB)));
+                            }
+                            None => {
+                                ui.add_sized(THUMB, egui::Label::new("-"));
+                            }
+                        }
+                        ui.vertical(|ui| {
+                            let marker = if i == set.current { "\u{25B6} " } else { "" };
+                            ui.strong(format!("{marker}{}", seg.name));
+                            if seg.end.trim().is_empty() {
+                                ui.weak("no end condition");
+                            } else {
+                                ui.monospace(&seg.end);
+                            }
+                        });
+                        ui.vertical(|ui| match seg.stats() {
+                            Some(s) => {
+                                ui.label(format!("best {}", format_time(s.best, frame)));
+                                ui.label(format!("avg {}", format_time(s.average, frame)));
+                                ui.weak(format!("{} done, {} reset", s.count, seg.resets));
+                            }
+                            None => {
+                                ui.weak(format!("no attempts, {} reset", seg.resets));
+                            }
+                        });
+                        ui.vertical(|ui| {
+                            if ui.button("Load").clicked() {
+                                request = Some(PracticeRequest::Load(seg.id));
+                            }
+                            if ui.button("Edit").clicked() {
+                                select = Some((seg.id, seg.name.clone(), seg.end.clone()));
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+        });
+    if select.is_some() {
+        state.edit = select;
+    }
+    if request.is_some() {
+        state.request = request;
+    }
+    editor(ui, state);
+}
+
+fn editor(ui: &mut egui::Ui, state: &mut PracticePanelState) {
+    let Some((id, name, end)) = state.edit.as_mut() else {
+        return;
+    };
+    let id = *id;
+    ui.separator();
+    egui::Grid::new("practice-edit")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Name");
+            ui.add(egui::TextEdit::singleline(name).desired_width(320.0));
+            ui.end_row();
+            ui.label("Ends when");
+            ui.add(
+                egui::TextEdit::singleline(end)
+                    .hint_text("[$0760] == 1")
+                    .font(egui::TextStyle::Monospace)
+                    .desired_width(320.0),
+            );
+            ui.end_row();
+        });
+    let mut request = None;
+    let mut close = false;
+    ui.horizontal(|ui| {
+        if ui.button("Apply").clicked() {
+            request = Some(PracticeRequest::Edit {
+                id,
+                name: name.trim().to_owned(),
+                end: end.trim().to_owned(),
+            });
+        }
+        if ui
+            .button("Re-anchor here")
+            .on_hover_text("Replace this segment's save state with the current state")
+            .clicked()
+        {
+            request = Some(PracticeRequest::Recapture(id));
+        }
+        if ui.button("Clear history").clicked() {
+            request = Some(PracticeRequest::ClearHistory(id));
+        }
+        if ui.button("Delete").clicked() {
+            request = Some(PracticeRequest::Remove(id));
+            close = true;
+        }
+        if ui.button("Close").clicked() {
+            close = true;
+        }
+    });
+    if close {
+        state.edit = None;
+    }
+    if request.is_some() {
+        state.request = request;
+    }
+}
+
+fn footer(ui: &mut egui::Ui, state: &mut PracticePanelState) {
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut state.new_name)
+                .hint_text("segment name")
+                .desired_width(180.0),
+        );
+        if ui
+            .button("Add segment here")
+            .on_hover_text("Anchor a new segment at the current state")
+            .clicked()
+        {
+            let name = state.new_name.trim();
+            let name = if name.is_empty() {
+                let n = state.set.as_ref().map_or(0, |s| s.segments.len()) + 1;
+                format!("Segment {n}")
+            } else {
+                name.to_owned()
+            };
+            state.request = Some(PracticeRequest::Add(name));
+            state.new_name.clear();
+        }
+        let has_attempts = state
+            .set
+            .as_ref()
+            .is_some_and(|s| s.segments.iter().any(|seg| !seg.attempts.is_empty()));
+        if ui
+            .add_enabled(has_attempts, egui::Button::new("Export CSV..."))
+            .on_hover_text("Save every attempt time as CSV")
+            .clicked()
+        {
+            state.request = Some(PracticeRequest::ExportCsv);
+        }
+    });
+}
@@ -0,0 +1,240 @@
+//! v2.5.0 — live **R.O.B.** side panel.
+//!
+//! Draws the robot over its play field: the five arm slots (the Gyromite red
+//! and blue button trays, the spinner and two rests), the arm at its current
+//! slot and height, the hand, the gyros with their spin, and the head LED.
+//! The core moves the arm in whole steps the moment a command decodes; the
+//! panel eases its drawing toward that pose so a command reads as a motion.
+//!
+//! Read-only: the app pushes a clone of [`rustynes_core::Rob`] each frame
+//! (via [`crate::debugger::DebuggerOverlay::set_rob`]), so the panel never
+//! touches the core.
+
+use egui::{Color32, CornerRadius, Pos2, Rect, Sense, Stroke, Vec2};
+use rustynes_core::{ROB_GYRO_SPIN_FRAMES, ROB_MAX_HEIGHT, ROB_SLOTS, Rob, RobMode};
+
+const BODY: Color32 = Color32::from_rgb(0xC8, 0xC4, 0xBA);
+const OUTLINE: Color32 = Color32::from_rgb(0x70, 0x68, 0x58);
+const FLOOR: Color32 = Color32::from_rgb(0x21, 0x1F, 0x1C);
+const LED_ON: Color32 = Color32::from_rgb(0xFF, 0x40, 0x30);
+const LED_OFF: Color32 = Color32::from_rgb(0x50, 0x20, 0x1C);
+const GYRO: Color32 = Color32::from_rgb(0xE0, 0xB0, 0x30);
+
+/// Per-slot label and floor colour.
+const SLOTS: [(&str, Color32); 5] = [
+    ("A", Color32::from_rgb(0xC4, 0x2C, 0x24)),
+    ("B", Color32::from_rgb(0x2C, 0x58, 0xC4)),
+    ("Spin", Color32::from_rgb(0x49, 0x43, 0x37)),
+    ("Rest", Color32::from_rgb(0x49, 0x43, 0x37)),
+    ("Rest", Color32::from_rgb(0x49, 0x43, 0x37)),
+];
+
+/// Fraction of the remaining distance the drawn pose covers per repaint.
+const EASE: f32 = 0.2;
+
+/// The eased pose the panel draws.
+#[derive(Debug, Default)]
+pub struct RobPanelState {
+    rotation: f32,
+    height: f32,
+    grip: f32,
+    primed: bool,
+}
+
+impl RobPanelState {
+    /// Move the drawn pose toward `rob`'s; snaps on the first frame.
+    #[allow(clippy::cast_precision_loss)] // small step counts.
+    fn follow(&mut self, rob: &Rob) -> bool {
+        let target = (
+            f32::from(rob.rotation()),
+            f32::from(rob.height()),
+            if rob.hand_closed() { 1.0 } else { 0.0 },
+        );
+        if !self.primed {
+            (self.rotation, self.height, self.grip) = target;
+            self.primed = true;
+            return false;
+        }
+        let step = |v: &mut f32, t: f32| {
+            *v += (t - *v) * EASE;
+            if (t - *v).abs() < 0.01 {
+                *v = t;
+            }
+        };
+        step(&mut self.rotation, target.0);
+        step(&mut self.height, target.1);
+        step(&mut self.grip, target.2);
+        (self.rotation, self.height, self.grip) != target
+    }
+}
+
+/// Render the R.O.B. window.
+pub fn show(
+    ctx: &egui::Context,
+    detached: &mut std::collections::HashSet<&'static str>,
+    open: &mut bool,
+    state: &mut RobPanelState,
+    rob: Option<&Rob>,
+) {
+    super::detachable_window(
+        ctx,
+        detached,
+        "rob",
+        "R.O.B.",
+        super::WindowCfg {
+            resizable: Some(false),
+            ..Default::default()
+        },
+        open,
+        |ui| {
+            let Some(rob) = rob else {
+                state.primed = false;
+                ui.label("R.O.B. is not set up.");
+                ui.weak("Choose Gyromite or Stack-Up in the Input window.");
+                return;
+            };
+            if state.follow(rob) {
+                ui.ctx().request_repaint();
+            }
+            draw(ui, state, rob);
+            ui.separator();
+            egui::Grid::new("rob-status").num_columns(2).show(ui, |ui| {
+                ui.label("Mode");
+                ui.label(match rob.mode() {
+                    RobMode::Gyromite => "Gyromite",
+                    RobMode::StackUp => "Stack-Up",
+                });
+                ui.end_row();
+                ui.label("Last command");
+                ui.monospace(rob.last_command().map_or("—", |c| c.label()));
+                ui.end_row();
+                ui.label("Pose");
+                ui.monospace(format!(
+                    "slot {}  height {}  hand {}",
+                    rob.rotation(),
+                    rob.height(),
+                    if rob.hand_closed() { "closed" } else { "open" }
+                ));
+                ui.end_row();
+                if rob.mode() == RobMode::Gyromite {
+                    let pressed = rob.pressed();
+                    ui.label("P2 held");
+                    ui.monospace(
+                        match (
+                            pressed.contains(rustynes_core::Buttons::A),
+                            pressed.contains(rustynes_core::Buttons::B),
+                        ) {
+                            (true, true) => "A + B",
+                            (true, false) => "A",
+                            (false, true) => "B",
+                            (false, false) => "—",
+                        },
+                    );
+                    ui.end_row();
+                }
+            });
+        },
+    );
+}
+
+/// The robot and its play field, front view.
+#[allow(clippy::cast_precision_loss)] // slot / height indices are tiny.
+fn draw(ui: &mut egui::Ui, state: &RobPanelState, rob: &Rob) {
+    let (rect, _) = ui.allocate_exact_size(Vec2::new(320.0, 220.0), Sense::hover());
+    let p = ui.painter_at(rect);
+    p.rect_filled(rect, CornerRadius::same(4), FLOOR);
+    let slot_w = rect.width() / f32::from(ROB_SLOTS);
+    let floor_y = rect.bottom() - 28.0;
+    let slot_x = |s: f32| rect.left() + slot_w * (s + 0.5);
+
+    // Slots.
+    for (i, (name, colour)) in SLOTS.iter().enumerate() {
+        let x = slot_x(i as f32);
+        let pad =
+            Rect::from_center_size(Pos2::new(x, floor_y + 6.0), Vec2::new(slot_w - 12.0, 8.0));
+        p.rect_filled(pad, CornerRadius::same(2), *colour);
+        p.text(
+            Pos2::new(x, rect.bottom() - 8.0),
+            egui::Align2::CENTER_CENTER,
+            name,
+            egui::FontId::proportional(11.0),
+            Color32::LIGHT_GRAY,
+        );
+    }
+
+    // Arm: a column rising from the base, the hand at the current height.
+    let travel = floor_y - rect.top() - 70.0;
+    let arm_x = slot_x(state.rotation);
+    let hand_y = floor_y - 14.0 - travel * state.height / f32::from(ROB_MAX_HEIGHT);
+    let stroke = Stroke::new(1.0, OUTLINE);
+    let base = Rect::from_center_size(
+        Pos2::new(rect.center().x, rect.top() + 40.0),
+        Vec2::new(46.0, 28.0),
+    );
+    p.rect(
+        base,
+        CornerRadius::same(6),
+        BODY,
+        stroke,
+        egui::StrokeKind::Inside,
+    );
+    p.line_segment(
+        [
+            Pos2::new(base.center().x, base.bottom()),
+            Pos2::new(arm_x, hand_y - 10.0),
+        ],
+        Stroke::new(4.0, BODY),
+    );
+    // Head + LED.
+    let head = Rect::from_center_size(
+        Pos2::new(base.center().x, base.top() - 12.0),
+        Vec2::new(30.0, 18.0),
+    );
+    p.rect(
+        head,
+        CornerRadius::same(4),
+        BODY,
+        stroke,
+        egui::StrokeKind::Inside,
+    );
+    p.circle_filled(
+        head.center(),
+        4.0,
+        if rob.led_on() { LED_ON } else { LED_OFF },
+    );
+    // Hand: two fingers that close with `grip`.
+    let spread = 12.0 - 7.0 * state.grip;
+    for side in [-1.0, 1.0] {
+        p.line_segment(
+            [
+                Pos2::new(arm_x + side * 10.0, hand_y - 10.0),
+                Pos2::new(arm_x + side * spread, hand_y + 6.0),
+            ],
+            Stroke::new(3.0, BODY),
+        );
+    }
+    p.line_segment(
+        [
+            Pos2::new(arm_x - 10.0, hand_y - 10.0),
+            Pos2::new(arm_x + 10.0, hand_y - 10.0),
+        ],
+        Stroke::new(3.0, BODY),
+    );
+
+    // Gyros: on their slot, or between the fingers.
+    let t = ui.input(|i| i.time) as f32;
+    for g in rob.gyros() {
+        let centre = g.slot.map_or_else(
+            || Pos2::new(arm_x, hand_y),
+            |s| Pos2::new(slot_x(f32::from(s)), floor_y - 6.0),
+        );
+        p.circle(centre, 9.0, GYRO, stroke);
+        if g.spin > 0 {
+            let speed = f32::from(g.spin) / f32::from(ROB_GYRO_SPIN_FRAMES);
+            let a = t * 20.0 * speed;
+            let d = Vec2::new(a.cos(), a.sin()) * 8.0;
+            p.line_segment([centre - d, centre + d], Stroke::new(1.5, OUTLINE));
+            ui.ctx().request_repaint();
+        }
+    }
+}
@@ -133,6 +133,13 @@ pub struct SettingsApply {
     /// `[emulation]` PPU knobs into the core under the emu lock. Both settings
     /// emit the identical frame, so this only honours the user's escape hatch.
     pub fast_dotloop: bool,
+    /// v2.5.0 — a Famicom-microphone capture setting changed (enable, device,
+    /// threshold or hysteresis); the app re-opens (or closes) the host input
+    /// stream to match `[input] microphone_*`.
+    pub microphone: bool,
+    /// v2.5.0 — the Famicom 3D System stereo mode or eye swap changed; the
+    /// app plugs the glasses in / out and reconfigures the stereo presenter.
+    pub famicom_3d: bool,
 }
 
 impl SettingsApply {
@@ -158,6 +165,7 @@ impl SettingsApply {
             || self.palette_select
             || self.oam_decay
             || self.fast_dotloop
+            || self.microphone
     }
 }
 
@@ -197,6 +205,10 @@ pub struct SettingsPanelState {
     /// host enumeration is native-only); empty on wasm / when no devices.
     #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
     audio_output_devices: Vec<String>,
+    /// v2.5.0 — the enumerated input device names for the Famicom-microphone
+    /// capture picker. Populated once by the app at startup (native-only).
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    audio_input_devices: Vec<String>,
     /// v1.2.0 C2 — index into [`crate::shader_pass::BuiltinPass::all`] for the
     /// "Add pass" picker.
     stack_add_index: usize,
@@ -295,6 +307,12 @@ impl SettingsPanelState {
     pub fn set_audio_output_devices(&mut self, names: Vec<String>) {
         self.audio_output_devices = names;
     }
+
+    /// v2.5.0 — populate the Famicom-microphone capture device picker list.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_audio_input_devices(&mut self, names: Vec<String>) {
+        self.audio_input_devices = names;
+    }
 }
 
 /// Render the settings panel. Edits `config` in place; native builds persist
@@ -449,7 +467,15 @@ fn fds_section(ui: &mut egui::Ui, state: &mut SettingsPanelState, config: &mut C
         if let Some(s) = &state.fds_bios_status {
             ui.label(s);
         }
-        ui.weak("Required to boot .fds disk images. Takes effect on the next FDS load.");
+        ui.checkbox(
+            &mut config.fds.hle_bios,
+            "Use the built-in HLE BIOS when disksys.rom is not set",
+        )
+        .on_hover_text(
+            "Boots disks through a clean-room BIOS whose disk calls complete instantly. \
+             Most licensed titles work; the logo screen is skipped.",
+        );
+        ui.weak("Takes effect on the next FDS load.");
     });
 }
 
@@ -657,6 +683,9 @@ pub fn video_section(ui: &mut egui::Ui, state: &mut SettingsPanelState, config:
     // (it must not block the render / hold the emu lock here). Presentation-only.
     palette_section(ui, state, config);
 
+    // v2.5.0 — Famicom 3D System stereo presentation. Off = glasses unplugged.
+    famicom_3d_section(ui, state, config);
+
     ui.add_space(4.0);
     // v1.0.0 — reset the Graphics section to its defaults (guarded by a
     // two-click confirm so it isn't a foot-gun), then re-apply live.
@@ -687,6 +716,8 @@ pub fn video_section(ui: &mut egui::Ui, state: &mut SettingsPanelState, config:
         // saved preset bank (a reset of live settings should not throw away the
         // user's named presets).
         let stack_changed = !config.graphics.shader_stack.passes.is_empty();
+        let stereo_changed = config.graphics.famicom_3d != def.famicom_3d
+            || config.graphics.famicom_3d_swap_eyes != def.famicom_3d_swap_eyes;
         let saved_presets = std::mem::take(&mut config.graphics.shader_presets);
         // v1.5.0 D1 — preserve the user's named palette bank across a reset
         // (like the shader presets); only the *active* selection clears.
@@ -702,6 +733,7 @@ pub fn video_section(ui: &mut egui::Ui, state: &mut SettingsPanelState, config:
         state.apply.palette_clear |= palette_changed;
         state.apply.palette_select |= active_palette_changed;
         state.apply.shader_stack |= stack_changed;
+        state.apply.famicom_3d |= stereo_changed;
         if active_palette_changed {
             // The active selection cleared back to the built-in; re-seed the
             // palette editor's swatches from it next frame.
@@ -711,6 +743,36 @@ pub fn video_section(ui: &mut egui::Ui, state: &mut SettingsPanelState, config:
     }
 }
 
+/// v2.5.0 — Famicom 3D System: how stereo titles' alternating eye frames are
+/// paired and shown. Any mode other than Off plugs the glasses into the core.
+fn famicom_3d_section(ui: &mut egui::Ui, state: &mut SettingsPanelState, config: &mut Config) {
+    let before = (
+        config.graphics.famicom_3d,
+        config.graphics.famicom_3d_swap_eyes,
+    );
+    ui.horizontal(|ui| {
+        ui.label("Famicom 3D System");
+        egui::ComboBox::from_id_salt("settings-famicom-3d")
+            .selected_text(config.graphics.famicom_3d.label())
+            .show_ui(ui, |ui| {
+                for mode in crate::stereo::StereoMode::ALL {
+                    ui.selectable_value(&mut config.graphics.famicom_3d, mode, mode.label());
+                }
+            });
+        if config.graphics.famicom_3d.enabled() {
+            ui.checkbox(&mut config.graphics.famicom_3d_swap_eyes, "Swap eyes");
+        }
+    });
+    if (
+        config.graphics.famicom_3d,
+        config.graphics.famicom_3d_swap_eyes,
+    ) != before
+    {
+        state.apply.famicom_3d = true;
+        save_config(config);
+    }
+}
+
 /// v1.5.0 "Lens" Workstream D2 — per-side overscan WYSIWYG editor.
 ///
 /// Replaces the binary "hide overscan" toggle's discoverability gap: the
@@ -1714,6 +1776,8 @@ pub fn audio_section(ui: &mut egui::Ui, state: &mut SettingsPanelState, config:
             ui.weak("(restart to apply)");
         });
 
+        microphone_section(ui, state, config);
+
         // Manual persist for anything still in-flight; the live gain is already
         // applied above.
         if ui.button("Save audio settings").clicked() {
@@ -1774,6 +1838,71 @@ pub fn audio_section(ui: &mut egui::Ui, state: &mut SettingsPanelState, config:
     }
 }
 
+/// v2.5.0 — Famicom microphone capture: enable, input device and the Schmitt
+/// trigger's threshold / hysteresis. Each edit re-opens the stream live.
+#[cfg(not(target_arch = "wasm32"))]
+fn microphone_section(ui: &mut egui::Ui, state: &mut SettingsPanelState, config: &mut Config) {
+    egui::CollapsingHeader::new("Famicom microphone").show(ui, |ui| {
+        let input = &mut config.input;
+        let mut changed = ui
+            .checkbox(&mut input.microphone_capture, "Capture from input device")
+            .on_hover_text(
+                "Drive the controller-II microphone ($4016 D2) from a host mic. \
+                 The level is recorded per frame, so movies and netplay stay deterministic.",
+            )
+            .changed();
+        ui.add_enabled_ui(input.microphone_capture, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Input device");
+                let selected = input
+                    .microphone_device
+                    .clone()
+                    .unwrap_or_else(|| "System default".to_owned());
+                egui::ComboBox::from_id_salt("settings-mic-device")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        if ui
+                            .selectable_label(input.microphone_device.is_none(), "System default")
+                            .clicked()
+                        {
+                            input.microphone_device = None;
+                            changed = true;
+                        }
+                        for name in &state.audio_input_devices {
+                            let on = input.microphone_device.as_deref() == Some(name.as_str());
+                            if ui.selectable_label(on, name).clicked() {
+                                input.microphone_device = Some(name.clone());
+                                changed = true;
+                            }
+                        }
+                    });
+            });
+            changed |= ui
+                .add(
+                    egui::Slider::new(&mut input.microphone_threshold, 0.005..=0.5)
+                        .logarithmic(true)
+                        .text("Threshold"),
+                )
+                .changed();
+            let max_hysteresis = input.microphone_threshold;
+            changed |= ui
+                .add(
+                    egui::Slider::new(&mut input.microphone_hysteresis, 0.0..=max_hysteresis)
+                        .text("Hysteresis"),
+                )
+                .changed();
+        });
+        if changed {
+            state.apply.microphone = true;
+            save_config(config);
+        }
+    });
+}
+
+/// wasm32 has no host capture path; the section is omitted.
+#[cfg(target_arch = "wasm32")]
+fn microphone_section(_ui: &mut egui::Ui, _state: &mut SettingsPanelState, _config: &mut Config) {}
+
 /// The Advanced section: run-ahead depth + rewind enable / window / keyframe.
 pub fn advanced_section(ui: &mut egui::Ui, state: &mut SettingsPanelState, config: &mut Config) {
     ui.heading("Latency");
@@ -196,7 +196,7 @@ impl Default for WatchPanelState {
 /// ([`Nes::cpu_bus_peek`]) takes `&mut self` (it borrows the mapper, which
 /// reads `&mut` — but, per its docs, advances no emulator-visible state, so the
 /// peek stays observational and determinism is intact).
-struct ReplayCtx<'a> {
+pub struct ReplayCtx<'a> {
     nes: RefCell<&'a mut Nes>,
     a: u8,
     x: u8,
@@ -210,6 +210,28 @@ struct ReplayCtx<'a> {
     access: AccessContext,
 }
 
+impl<'a> ReplayCtx<'a> {
+    /// A context over `nes`'s end-of-frame state with no access in flight —
+    /// what the watch window and the autosplitter evaluate against.
+    pub fn end_of_frame(nes: &'a mut Nes) -> Self {
+        let cpu = nes.cpu_snapshot();
+        let ppu = nes.ppu_snapshot();
+        Self {
+            nes: RefCell::new(nes),
+            a: cpu.a,
+            x: cpu.x,
+            y: cpu.y,
+            s: cpu.s,
+            p: cpu.p,
+            pc: cpu.pc,
+            scanline: ppu.scanline,
+            dot: ppu.dot,
+            frame: ppu.frame,
+            access: AccessContext::default(),
+        }
+    }
+}
+
 impl EvalContext for ReplayCtx<'_> {
     fn a(&self) -> u8 {
         self.a
@@ -458,21 +480,7 @@ impl WatchPanelState {
     /// Evaluate the watch-window rows against the current (end-of-frame)
     /// machine state, returning `(source, value-string)` pairs for display.
     fn eval_watch_rows(&self, nes: &mut Nes) -> Vec<(String, String, bool)> {
-        let cpu = nes.cpu_snapshot();
-        let ppu = nes.ppu_snapshot();
-        let ctx = ReplayCtx {
-            nes: RefCell::new(nes),
-            a: cpu.a,
-            x: cpu.x,
-            y: cpu.y,
-            s: cpu.s,
-            p: cpu.p,
-            pc: cpu.pc,
-            scanline: ppu.scanline,
-            dot: ppu.dot,
-            frame: ppu.frame,
-            access: AccessContext::default(),
-        };
+        let ctx = ReplayCtx::end_of_frame(nes);
         self.watch_rows
             .iter()
             .map(|row| {
@@ -20,7 +20,7 @@
 
 use std::time::Duration;
 
-use rustynes_core::{Buttons, Nes};
+use rustynes_core::{Buttons, MovieCommand, Nes};
 use web_time::Instant;
 
 use crate::cheats::RawCheat;
@@ -278,9 +278,11 @@ pub struct FrameInputs {
     pub buttons: [Buttons; 4],
     /// Whether the Four Score adapter is enabled (players 3/4 latch).
     pub four_score: bool,
-    /// v2.2.0 "Capstone" — Famicom built-in microphone held (read on `$4016`
-    /// bit 2). `false` (default) keeps the `$4016` read byte-identical.
-    pub microphone: bool,
+    /// v2.2.0 "Capstone" — Famicom built-in microphone (read on `$4016` bit 2).
+    /// v2.5.0 — a per-frame pattern (bit `i` = level during the `i`-th eighth
+    /// of the frame, see [`rustynes_core::Nes::set_microphone_pattern`]); `0`
+    /// (default) keeps the `$4016` read byte-identical.
+    pub microphone: u8,
     /// The rewind gesture is held (already hardcore-gated by `App`).
     pub rewind_held: bool,
     /// RA hardcore gating is active (disables raw cheats; rewind is
@@ -345,6 +347,190 @@ pub struct FrameInputs {
     /// device is a Bandai Hyper Shot. 0 by default = byte-identical latch.
     #[cfg(not(target_arch = "wasm32"))]
     pub bandai_hyper_shot: u8,
+    /// v2.5.0 — player 1's analog sticks and triggers, consumed only by the
+    /// analog expansion controllers (Hori Track, Pachinko, Top Rider, Power
+    /// Glove). Centred by default.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub analog: crate::input::AnalogInputs,
+    /// v2.5.0 — the configured Power Glove gesture program.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub power_glove_program: rustynes_core::PowerGloveProgram,
+}
+
+/// The state of the player-2-port expansion device as netplay carries it
+/// ([`rustynes_core::DeviceInput`]), mirroring what [`latch_expansion`] would
+/// latch. Devices netplay does not carry (the Hyper Shots, the tablet, the
+/// analog controllers) report none, and stay idle in a netplay match.
+/// Native-only (mouse input source).
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn expansion_device_input(inputs: &FrameInputs) -> rustynes_core::DeviceInput {
+    use rustynes_core::DeviceInput;
+    let (nx, ny) = inputs.mouse_nes;
+    match inputs.expansion {
+        ExpansionDevice::Zapper => DeviceInput::Zapper {
+            port: 1,
+            x: nx,
+            y: ny,
+            trigger: inputs.mouse_pressed,
+        },
+        ExpansionDevice::Vaus => DeviceInput::Paddle {
+            port: 1,
+            position: if nx == u16::MAX {
+                0x80
+            } else {
+                u8::try_from(nx.min(255)).unwrap_or(u8::MAX)
+            },
+            fire: inputs.mouse_pressed,
+        },
+        ExpansionDevice::PowerPad => DeviceInput::PowerPad {
+            port: 1,
+            buttons: inputs.power_pad,
+        },
+        ExpansionDevice::FamilyTrainer => DeviceInput::FamilyTrainer {
+            port: 1,
+            buttons: inputs.power_pad,
+        },
+        ExpansionDevice::SnesMouse => {
+            let clamp = |d: i16| i8::try_from(d.clamp(-127, 127)).unwrap_or(0);
+            DeviceInput::Mouse {
+                port: 1,
+                dx: clamp(inputs.mouse_delta.0),
+                dy: clamp(inputs.mouse_delta.1),
+                left: inputs.mouse_pressed,
+                right: inputs.mouse_right,
+                sensitivity: inputs.mouse_sensitivity.min(2),
+            }
+        }
+        ExpansionDevice::FamilyKeyboard => DeviceInput::FamilyKeyboard {
+            port: 1,
+            keys: inputs.family_keyboard,
+        },
+        ExpansionDevice::SuborKeyboard => DeviceInput::SuborKeyboard {
+            port: 1,
+            keys: inputs.family_keyboard,
+        },
+        _ => DeviceInput::None,
+    }
+}
+
+/// v2.1.0 — feed the frame's mouse / analog input into the expansion device
+/// configured on the player-2 port ($4017). Native-only (mouse input source).
+#[cfg(not(target_arch = "wasm32"))]
+fn latch_expansion(nes: &mut rustynes_core::Nes, inputs: &FrameInputs) {
+    let (nx, ny) = inputs.mouse_nes;
+    match inputs.expansion {
+        // The Turbo File has no live input: the game drives it entirely.
+        ExpansionDevice::None | ExpansionDevice::TurboFile | ExpansionDevice::TurboFileTwin => {}
+        ExpansionDevice::Zapper => {
+            nes.set_zapper(1, nx, ny, inputs.mouse_pressed);
+        }
+        ExpansionDevice::Vaus => {
+            #[allow(clippy::cast_possible_truncation)]
+            let pos = if nx == u16::MAX {
+                0x80
+            } else {
+                nx.min(255) as u8
+            };
+            nes.set_paddle(1, pos, inputs.mouse_pressed);
+        }
+        ExpansionDevice::PowerPad => {
+            nes.set_power_pad(1, inputs.power_pad);
+        }
+        ExpansionDevice::SnesMouse => {
+            let (dx, dy) = inputs.mouse_delta;
+            // v1.5.0 D4 — reported sensitivity is now configurable
+            // (was hardcoded 0); default 0 keeps the report
+            // byte-identical.
+            nes.set_snes_mouse(
+                1,
+                dx,
+                dy,
+                inputs.mouse_pressed,
+                inputs.mouse_right,
+                inputs.mouse_sensitivity.min(2),
+            );
+        }
+        ExpansionDevice::FamilyKeyboard => {
+            nes.set_family_keyboard(1, inputs.family_keyboard);
+        }
+        ExpansionDevice::FamilyTrainer => {
+            nes.set_family_trainer(1, inputs.power_pad);
+        }
+        ExpansionDevice::SuborKeyboard => {
+            nes.set_subor_keyboard(1, inputs.family_keyboard);
+        }
+        ExpansionDevice::KonamiHyperShot => {
+            nes.set_konami_hyper_shot(1, inputs.konami_hyper_shot);
+        }
+        ExpansionDevice::BandaiHyperShot => {
+            nes.set_bandai_hyper_shot(1, inputs.bandai_hyper_shot);
+        }
+        ExpansionDevice::OekaKidsTablet => {
+            nes.set_oeka_kids_tablet(1, nx, ny, inputs.mouse_pressed, inputs.mouse_right);
+        }
+        // v2.5.0 — the analog expansion controllers take their
+        // pad buttons from the player-1 bindings (pre-turbo, as
+        // the device's own buttons have no autofire).
+        ExpansionDevice::HoriTrack => {
+            let (dx, dy) = crate::input::hori_track_motion(inputs.mouse_delta, inputs.analog);
+            nes.set_hori_track(1, dx, dy, inputs.buttons[0].bits());
+        }
+        ExpansionDevice::Pachinko => {
+            let held = inputs.mouse_pressed && ny != u16::MAX;
+            let plunger = crate::input::pachinko_plunger(inputs.analog, held.then_some(ny));
+            nes.set_pachinko(1, inputs.buttons[0].bits(), plunger);
+        }
+        ExpansionDevice::TopRider => {
+            let (steer, throttle, keys) =
+                crate::input::top_rider_controls(inputs.buttons[0], inputs.analog);
+            nes.set_top_rider(1, steer, throttle, keys);
+        }
+        ExpansionDevice::PowerGlove => {
+            let hand = crate::input::power_glove_hand(
+                inputs.mouse_nes,
+                inputs.mouse_pressed,
+                inputs.mouse_right,
+                inputs.buttons[0],
+                inputs.analog,
+            );
+            nes.set_power_glove(1, hand, inputs.power_glove_program);
+        }
+    }
+}
+
+/// v2.5.0 — where the Turbo File image lives:
+/// `<data_dir>/turbo-file/turbo-file.bin` (8 KiB) or `turbo-file-twin.bin`
+/// (32 KiB). One image per device, shared by every game, as with the real
+/// cartridge.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub fn turbo_file_path(data_dir: &std::path::Path, twin: bool) -> std::path::PathBuf {
+    data_dir.join("turbo-file").join(if twin {
+        "turbo-file-twin.bin"
+    } else {
+        "turbo-file.bin"
+    })
+}
+
+/// v2.5.0 — the persisted Turbo File (or Twin) image, or a blank one when
+/// there is none yet (or it is the wrong size for the device).
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub fn load_turbo_file(
+    data_dir: Option<&std::path::Path>,
+    twin: bool,
+) -> rustynes_core::TurboFileImage {
+    data_dir
+        .and_then(|d| std::fs::read(turbo_file_path(d, twin)).ok())
+        .and_then(rustynes_core::TurboFileImage::from_storage)
+        .filter(|f| f.is_twin() == twin)
+        .unwrap_or_else(|| {
+            if twin {
+                rustynes_core::TurboFileImage::new_twin()
+            } else {
+                rustynes_core::TurboFileImage::new()
+            }
+        })
 }
 
 /// v1.1.0 beta.1 (T-110-B2) — apply turbo/autofire to one port's buttons.
@@ -481,6 +667,22 @@ pub struct EmuCore {
     /// buffers. The compose runs under the same brief present lock as the
     /// single-console `present_fb` copy (a cheap memcpy, not heavy work).
     pub present_fb_sub: Vec<u8>,
+    /// v2.5.0 — Famicom 3D System pairing: rewrites [`Self::present_fb`] per
+    /// the configured stereo mode (the core framebuffer is never touched).
+    pub stereo: crate::stereo::StereoPresenter,
+    /// v2.5.0 — a movie replaying beside live play on its own console, drawn
+    /// over [`Self::present_fb`] after the stereo pass. `None` when no ghost
+    /// is loaded.
+    pub ghost: Option<Box<crate::ghost::Ghost>>,
+    /// v2.5.0 — the speedrun timer, ticked after every forward frame. `None`
+    /// when the loaded ROM has no split file.
+    pub autosplit: Option<Box<crate::autosplit::Autosplitter>>,
+    /// v2.5.0 — the practice attempt being timed, ticked after every forward
+    /// frame. `None` until a practice segment is loaded.
+    pub practice: Option<Box<crate::practice::Attempt>>,
+    /// v2.5.0 — the eye the harvested [`Self::present_fb`] was drawn for
+    /// (`None` with no 3D glasses attached).
+    present_eye: Option<rustynes_core::StereoEye>,
     /// v1.8.9 — the 8 KiB CHR pattern space ($0000-$1FFF) captured at PRODUCE
     /// time (the same visible frame as `present_fb`). With run-ahead active, the
     /// `nes` is rolled back to the persistent frame after the visible frame is
@@ -703,6 +905,29 @@ impl EmuCore {
     pub fn set_nes(&mut self, nes: Nes) {
         self.mapper_name = nes.mapper_info().name;
         self.dual = None;
+        // A ghost only outlives a reload of the ROM it was recorded on.
+        if self
+            .ghost
+            .as_ref()
+            .is_some_and(|g| g.rom_sha256() != nes.rom_sha256())
+        {
+            self.ghost = None;
+        }
+        // So do the splits.
+        if self
+            .autosplit
+            .as_ref()
+            .is_some_and(|a| a.rom_sha256() != nes.rom_sha256())
+        {
+            self.autosplit = None;
+        }
+        if self
+            .practice
+            .as_ref()
+            .is_some_and(|p| p.rom_sha256() != nes.rom_sha256())
+        {
+            self.practice = None;
+        }
         self.nes = Some(nes);
     }
 
@@ -711,6 +936,9 @@ impl EmuCore {
     pub fn set_dual(&mut self, dual: Box<rustynes_core::VsDualSystem>) {
         self.mapper_name = dual.main().mapper_info().name;
         self.nes = None;
+        self.ghost = None;
+        self.autosplit = None;
+        self.practice = None;
         self.dual = Some(dual);
     }
 
@@ -719,6 +947,9 @@ impl EmuCore {
     pub fn clear_rom(&mut self) {
         self.nes = None;
         self.dual = None;
+        self.ghost = None;
+        self.autosplit = None;
+        self.practice = None;
         self.mapper_name.clear();
     }
 
@@ -733,6 +964,11 @@ impl EmuCore {
             perf: PerfStats::default(),
             present_fb: Vec::new(),
             present_fb_sub: Vec::new(),
+            stereo: crate::stereo::StereoPresenter::default(),
+            ghost: None,
+            autosplit: None,
+            practice: None,
+            present_eye: None,
             #[cfg(all(feature = "hd-pack", not(target_arch = "wasm32")))]
             hd_chr_snapshot: Vec::new(),
             #[cfg(all(feature = "hd-pack", not(target_arch = "wasm32")))]
@@ -836,7 +1072,7 @@ impl EmuCore {
             }
             // v2.2.0 "Capstone" — Famicom microphone ($4016 bit 2). Latched at
             // the SAME point as the buttons; byte-identical when released.
-            nes.set_microphone(inputs.microphone);
+            nes.set_microphone_pattern(inputs.microphone);
             // v1.2.0 (T-110-E2) — Lua setInput override: replace ports 0/1 AFTER
             // the keyboard/turbo latch, so the script's recorded bitmask wins for
             // this frame. Applied at the late-latch point, so a session that
@@ -851,56 +1087,7 @@ impl EmuCore {
             // v2.1.0 — feed the mouse into any attached non-standard device
             // on the player-2 port ($4017). Native-only (mouse input source).
             #[cfg(not(target_arch = "wasm32"))]
-            {
-                let (nx, ny) = inputs.mouse_nes;
-                match inputs.expansion {
-                    ExpansionDevice::None => {}
-                    ExpansionDevice::Zapper => {
-                        nes.set_zapper(1, nx, ny, inputs.mouse_pressed);
-                    }
-                    ExpansionDevice::Vaus => {
-                        #[allow(clippy::cast_possible_truncation)]
-                        let pos = if nx == u16::MAX {
-                            0x80
-                        } else {
-                            nx.min(255) as u8
-                        };
-                        nes.set_paddle(1, pos, inputs.mouse_pressed);
-                    }
-                    ExpansionDevice::PowerPad => {
-                        nes.set_power_pad(1, inputs.power_pad);
-                    }
-                    ExpansionDevice::SnesMouse => {
-                        let (dx, dy) = inputs.mouse_delta;
-                        // v1.5.0 D4 — reported sensitivity is now configurable
-                        // (was hardcoded 0); default 0 keeps the report
-                        // byte-identical.
-                        nes.set_snes_mouse(
-                            1,
-                            dx,
-                            dy,
-                            inputs.mouse_pressed,
-                            inputs.mouse_right,
-                            inputs.mouse_sensitivity.min(2),
-                        );
-                    }
-                    ExpansionDevice::FamilyKeyboard => {
-                        nes.set_family_keyboard(1, inputs.family_keyboard);
-                    }
-                    ExpansionDevice::FamilyTrainer => {
-                        nes.set_family_trainer(1, inputs.power_pad);
-                    }
-                    ExpansionDevice::SuborKeyboard => {
-                        nes.set_subor_keyboard(1, inputs.family_keyboard);
-                    }
-                    ExpansionDevice::KonamiHyperShot => {
-                        nes.set_konami_hyper_shot(1, inputs.konami_hyper_shot);
-                    }
-                    ExpansionDevice::BandaiHyperShot => {
-                        nes.set_bandai_hyper_shot(1, inputs.bandai_hyper_shot);
-                    }
-                }
-            }
+            latch_expansion(nes, inputs);
             // v1.2.0 Workstream F2 — Power Pad on wasm. The native expansion
             // block above is gated out on wasm (it needs the cursor / mouse
             // fields, which don't exist there); the Power Pad only needs the
@@ -1274,7 +1461,22 @@ impl EmuCore {
             // restored state.
             self.present_fb.clear();
             self.present_fb.extend_from_slice(nes.framebuffer());
+            self.present_eye = nes.stereo_eye();
         } else {
+            // v2.5.0 — Vs. System coin latch: a coin-insert (F10) holds the
+            // acceptor signal for a few frames, then auto-clears. Ahead of the
+            // movie hook so a recording captures the release on the frame it
+            // takes effect. A recording has one command slot per frame; when a
+            // reset or disk swap already took it, the release waits a frame
+            // rather than replacing that command.
+            if self.vs_coin_frames > 0 {
+                if self.vs_coin_frames > 1 {
+                    self.vs_coin_frames -= 1;
+                } else if self.movie.record_command(MovieCommand::ClearCoin) {
+                    self.vs_coin_frames = 0;
+                    nes.clear_coin();
+                }
+            }
             // v1.4.0 Sprint 4.2 — TAS movie hook, AFTER the live
             // `set_buttons` latch and BEFORE `run_frame`. When recording it
             // captures the held input; when playing it overrides the held
@@ -1295,14 +1497,6 @@ impl EmuCore {
             // pre-frame save-state, so it never perturbs emulation. Skipped on
             // rewind (this branch is the non-rewind path).
             self.history.record_frame(nes);
-            // v2.5.0 — Vs. System coin latch: a coin-insert (F10) holds the
-            // acceptor signal for a few frames, then auto-clears.
-            if self.vs_coin_frames > 0 {
-                self.vs_coin_frames -= 1;
-                if self.vs_coin_frames == 0 {
-                    nes.clear_coin();
-                }
-            }
             // v2.8.0 Phase 3 — run-ahead (native): run the persistent frame
             // + N hidden/visible frames, harvest the VISIBLE frame's
             // framebuffer + audio, then roll back to the persistent frame.
@@ -1311,6 +1505,7 @@ impl EmuCore {
                 self.runahead.run_frame_ahead(nes, run_ahead_n);
                 self.present_fb.clear();
                 self.present_fb.extend_from_slice(nes.framebuffer());
+                self.present_eye = nes.stereo_eye();
                 // v1.8.9 — capture CHR from the VISIBLE frame BEFORE `finish()`
                 // rolls back, so animated HD-pack tiles stay in sync (no flicker).
                 #[cfg(all(feature = "hd-pack", not(target_arch = "wasm32")))]
@@ -1360,6 +1555,7 @@ impl EmuCore {
                 // reused buffer.
                 self.present_fb.clear();
                 self.present_fb.extend_from_slice(nes.framebuffer());
+                self.present_eye = nes.stereo_eye();
                 // v1.8.9 — keep the HD-pack CHR snapshot in lock-step with the
                 // presented frame (uniform with the run-ahead path above).
                 #[cfg(all(feature = "hd-pack", not(target_arch = "wasm32")))]
@@ -1447,6 +1643,27 @@ impl EmuCore {
             if !nes.was_input_polled_this_frame() {
                 self.lag_frames = self.lag_frames.saturating_add(1);
             }
+            // v2.5.0 — the ghost runs its frame beside this forward frame
+            // (it peeks the live RAM only for the split point).
+            if let Some(ghost) = self.ghost.as_mut() {
+                ghost.step(nes);
+            }
+            // v2.5.0 — the autosplitter reads the same end-of-frame state.
+            if let Some(autosplit) = self.autosplit.as_mut() {
+                autosplit.tick(nes);
+            }
+            // v2.5.0 — and so does the practice attempt.
+            if let Some(practice) = self.practice.as_mut() {
+                practice.tick(nes);
+            }
+        }
+        // v2.5.0 — Famicom 3D System: pair the harvested frame with the other
+        // eye's latest per the stereo mode. After the attestation hook, so the
+        // movie still hashes the frame as drawn.
+        self.stereo.present(&mut self.present_fb, self.present_eye);
+        // v2.5.0 — then draw the ghost over the presented copy.
+        if let Some(ghost) = self.ghost.as_mut() {
+            ghost.compose(&mut self.present_fb);
         }
 
         // v2.7.0 — drive RetroAchievements after the frame. Only the
@@ -1677,6 +1894,35 @@ impl EmuCore {
             Err(e) => eprintln!("rustynes: FDS disk save failed {}: {e}", path.display()),
         }
     }
+
+    /// v2.5.0 — write the inserted Turbo File image back to
+    /// [`turbo_file_path`] when a game has changed it. Cheap when clean (a
+    /// dirty-flag check). Native-only; no-op with no image or data dir.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn flush_turbo_file(&mut self, data_dir: Option<&std::path::Path>) {
+        let Some(data_dir) = data_dir else { return };
+        let Some(file) = self
+            .nes
+            .as_mut()
+            .and_then(rustynes_core::Nes::turbo_file_image_mut)
+        else {
+            return;
+        };
+        if !file.is_dirty() {
+            return;
+        }
+        let path = turbo_file_path(data_dir, file.is_twin());
+        if let Some(parent) = path.parent()
+            && let Err(e) = std::fs::create_dir_all(parent)
+        {
+            eprintln!("rustynes: could not create turbo-file dir: {e}");
+            return;
+        }
+        match crate::atomic_write::write_atomic(&path, file.storage()) {
+            Ok(()) => file.clear_dirty(),
+            Err(e) => eprintln!("rustynes: Turbo File save failed {}: {e}", path.display()),
+        }
+    }
 }
 
 impl Default for EmuCore {
@@ -1953,7 +2199,7 @@ mod tests {
         FrameInputs {
             buttons: [Buttons::empty(); 4],
             four_score: false,
-            microphone: false,
+            microphone: 0,
             rewind_held: false,
             hardcore_blocked: false,
             run_ahead: 0,
@@ -1969,9 +2215,52 @@ mod tests {
             family_keyboard: [0; 9],
             konami_hyper_shot: 0,
             bandai_hyper_shot: 0,
+            analog: crate::input::AnalogInputs::default(),
+            power_glove_program: rustynes_core::PowerGloveProgram::Joystick,
         }
     }
 
+    /// The device state netplay carries drives the port exactly as the
+    /// single-player latch does, and a device netplay does not carry
+    /// reports none.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn netplay_device_input_matches_the_latch() {
+        let rom = synth_nrom();
+        let mut inputs = quiet_inputs();
+        inputs.mouse_nes = (131, 77);
+        inputs.mouse_pressed = true;
+        inputs.mouse_delta = (-300, 9);
+        inputs.mouse_right = true;
+        inputs.mouse_sensitivity = 2;
+        inputs.power_pad = 0x0421;
+        for device in [
+            ExpansionDevice::Zapper,
+            ExpansionDevice::Vaus,
+            ExpansionDevice::PowerPad,
+            ExpansionDevice::SnesMouse,
+            ExpansionDevice::FamilyTrainer,
+        ] {
+            inputs.expansion = device;
+            let mut latched = Nes::from_rom(&rom).unwrap();
+            latch_expansion(&mut latched, &inputs);
+            let mut carried = Nes::from_rom(&rom).unwrap();
+            let wire = expansion_device_input(&inputs);
+            wire.apply(&mut carried);
+            assert_eq!(wire.port(), Some(1), "{device:?}");
+            assert_eq!(
+                rustynes_core::DeviceInput::capture(&carried),
+                rustynes_core::DeviceInput::capture(&latched),
+                "{device:?}"
+            );
+        }
+        inputs.expansion = ExpansionDevice::KonamiHyperShot;
+        assert_eq!(
+            expansion_device_input(&inputs),
+            rustynes_core::DeviceInput::None
+        );
+    }
+
     /// v1.7.0 "Forge" Workstream A1 — the gated-writeback contract: a queued
     /// `DebugPoke` applies after a frame when UNLOCKED, but is a no-op (and the
     /// queue is cleared) when `writes_locked` (TAS replay / netplay) — proving
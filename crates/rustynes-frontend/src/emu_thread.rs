@@ -96,14 +96,16 @@ pub mod regime {
 pub struct SharedInput {
     buttons: [AtomicU8; 4],
     four_score: AtomicBool,
-    /// v2.2.0 "Capstone" — Famicom microphone held (`$4016` bit 2).
-    microphone: AtomicBool,
+    /// v2.2.0 "Capstone" — Famicom microphone (`$4016` bit 2); v2.5.0 — the
+    /// per-frame slice pattern.
+    microphone: AtomicU8,
     rewind_held: AtomicBool,
     hardcore_blocked: AtomicBool,
     run_ahead: AtomicU8,
     /// `ExpansionDevice` as `u8` (0 None / 1 Zapper / 2 Vaus / 3 Power Pad /
     /// 4 SNES mouse / 5 Family BASIC keyboard / 6 Family Trainer / 7 Subor
-    /// keyboard / 8 Konami Hyper Shot / 9 Bandai Hyper Shot).
+    /// keyboard / 8 Konami Hyper Shot / 9 Bandai Hyper Shot / 10 Oeka Kids
+    /// tablet / 11 Hori Track / 12 Pachinko / 13 Top Rider / 14 Power Glove).
     expansion: AtomicU8,
     /// `(x as u16) << 16 | (y as u16)` NES-screen coords (`u16::MAX` = off).
     mouse: AtomicU32,
@@ -127,6 +129,10 @@ pub struct SharedInput {
     konami_hyper_shot: AtomicU8,
     /// v1.3.0 Workstream F1 — Bandai Hyper Shot sensor mask.
     bandai_hyper_shot: AtomicU8,
+    /// v2.5.0 — player 1's sticks + triggers ([`crate::input::AnalogInputs::pack`]).
+    analog: AtomicU64,
+    /// v2.5.0 — Power Glove program (0 Joystick / 1 Punch / 2 Keypad).
+    power_glove_program: AtomicU8,
 }
 
 impl SharedInput {
@@ -160,6 +166,13 @@ impl SharedInput {
                 ExpansionDevice::SuborKeyboard => 7,
                 ExpansionDevice::KonamiHyperShot => 8,
                 ExpansionDevice::BandaiHyperShot => 9,
+                ExpansionDevice::OekaKidsTablet => 10,
+                ExpansionDevice::HoriTrack => 11,
+                ExpansionDevice::Pachinko => 12,
+                ExpansionDevice::TopRider => 13,
+                ExpansionDevice::PowerGlove => 14,
+                ExpansionDevice::TurboFile => 15,
+                ExpansionDevice::TurboFileTwin => 16,
             },
             Ordering::Relaxed,
         );
@@ -192,6 +205,15 @@ impl SharedInput {
             .store(inputs.konami_hyper_shot, Ordering::Relaxed);
         self.bandai_hyper_shot
             .store(inputs.bandai_hyper_shot, Ordering::Relaxed);
+        self.analog.store(inputs.analog.pack(), Ordering::Relaxed);
+        self.power_glove_program.store(
+            match inputs.power_glove_program {
+                rustynes_core::PowerGloveProgram::Joystick => 0,
+                rustynes_core::PowerGloveProgram::Punch => 1,
+                rustynes_core::PowerGloveProgram::Keypad => 2,
+            },
+            Ordering::Relaxed,
+        );
     }
 
     /// Reconstruct the [`FrameInputs`] the emu thread feeds to the produce
@@ -220,6 +242,13 @@ impl SharedInput {
                 7 => ExpansionDevice::SuborKeyboard,
                 8 => ExpansionDevice::KonamiHyperShot,
                 9 => ExpansionDevice::BandaiHyperShot,
+                10 => ExpansionDevice::OekaKidsTablet,
+                11 => ExpansionDevice::HoriTrack,
+                12 => ExpansionDevice::Pachinko,
+                13 => ExpansionDevice::TopRider,
+                14 => ExpansionDevice::PowerGlove,
+                15 => ExpansionDevice::TurboFile,
+                16 => ExpansionDevice::TurboFileTwin,
                 _ => ExpansionDevice::None,
             },
             #[allow(clippy::cast_possible_truncation)]
@@ -245,6 +274,12 @@ impl SharedInput {
             },
             konami_hyper_shot: self.konami_hyper_shot.load(Ordering::Relaxed),
             bandai_hyper_shot: self.bandai_hyper_shot.load(Ordering::Relaxed),
+            analog: crate::input::AnalogInputs::unpack(self.analog.load(Ordering::Relaxed)),
+            power_glove_program: match self.power_glove_program.load(Ordering::Relaxed) {
+                1 => rustynes_core::PowerGloveProgram::Punch,
+                2 => rustynes_core::PowerGloveProgram::Keypad,
+                _ => rustynes_core::PowerGloveProgram::Joystick,
+            },
         }
     }
 }
@@ -1094,7 +1129,7 @@ mod tests {
                 Buttons::all(),
             ],
             four_score: true,
-            microphone: false,
+            microphone: 0,
             rewind_held: true,
             hardcore_blocked: false,
             run_ahead: 2,
@@ -1110,6 +1145,12 @@ mod tests {
             family_keyboard: [0x01, 0x80, 0x00, 0xFF, 0x10, 0x00, 0x00, 0x00, 0x55],
             konami_hyper_shot: 0b1011,
             bandai_hyper_shot: 0b1100_0011,
+            analog: crate::input::AnalogInputs {
+                left_x: -90,
+                right_trigger: 200,
+                ..Default::default()
+            },
+            power_glove_program: rustynes_core::PowerGloveProgram::Punch,
         };
         si.publish(&inputs);
         let got = si.load();
@@ -1134,6 +1175,11 @@ mod tests {
         );
         assert_eq!(got.konami_hyper_shot, 0b1011);
         assert_eq!(got.bandai_hyper_shot, 0b1100_0011);
+        assert_eq!(got.analog, inputs.analog);
+        assert_eq!(
+            got.power_glove_program,
+            rustynes_core::PowerGloveProgram::Punch
+        );
     }
 
     #[test]
@@ -1142,7 +1188,7 @@ mod tests {
         let mut inputs = FrameInputs {
             buttons: [Buttons::empty(); 4],
             four_score: false,
-            microphone: false,
+            microphone: 0,
             rewind_held: false,
             hardcore_blocked: false,
             run_ahead: 0,
@@ -1158,12 +1204,23 @@ mod tests {
             family_keyboard: [0; 9],
             konami_hyper_shot: 0,
             bandai_hyper_shot: 0,
+            analog: crate::input::AnalogInputs::default(),
+            power_glove_program: rustynes_core::PowerGloveProgram::Joystick,
         };
         si.publish(&inputs);
         assert_eq!(si.load().mouse_nes, (u16::MAX, u16::MAX));
         inputs.run_ahead = 999; // clamps into u8 then back to u32.
         si.publish(&inputs);
         assert_eq!(si.load().run_ahead, 255);
+        for device in ExpansionDevice::ALL {
+            inputs.expansion = device;
+            si.publish(&inputs);
+            assert_eq!(
+                si.load().expansion,
+                device,
+                "every device survives the atomics"
+            );
+        }
     }
 
     #[test]
@@ -56,6 +56,10 @@ pub fn classify(bytes: &[u8]) -> BiosStatus {
     if bytes.len() != BIOS_SIZE {
         return BiosStatus::WrongSize(bytes.len());
     }
+    // An exported copy of the built-in clean-room BIOS.
+    if rustynes_core::rustynes_mappers::is_hle_bios(bytes) {
+        return BiosStatus::Recognized("RustyNES HLE BIOS (built-in)");
+    }
     let hex = sha256_hex(bytes);
     KNOWN
         .iter()
@@ -69,6 +73,12 @@ pub fn classify(bytes: &[u8]) -> BiosStatus {
 mod tests {
     use super::*;
 
+    #[test]
+    fn hle_bios_is_recognized() {
+        let s = classify(&rustynes_core::rustynes_mappers::fds_hle_bios());
+        assert_eq!(s, BiosStatus::Recognized("RustyNES HLE BIOS (built-in)"));
+    }
+
     #[test]
     fn wrong_size_is_rejected() {
         let s = classify(&[0u8; 4096]);
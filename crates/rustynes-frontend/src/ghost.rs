@@ -0,0 +1,436 @@
+//! v2.5.0 — ghost replay: race a recorded run.
+//!
+//! A [`Ghost`] is a second, private [`Nes`] that replays a movie alongside
+//! live play. After each live frame it runs one frame of its own — or, when
+//! aligned by split, waits at the next split point for the live game or
+//! catches up to it — and [`Ghost::compose`] draws its sprites
+//! semi-transparently over the presented frame, optionally with the whole
+//! ghost picture as a picture-in-picture inset. Like the stereo presenter it
+//! only rewrites the *presented* copy: the live console, its movie and its
+//! attestation never see the ghost.
+//!
+//! A split point is a RAM byte reaching a value (a level counter, a "stage
+//! clear" flag). Each time it becomes true on either console the frame is
+//! noted, and the readout's delta is how many frames later the live run
+//! reached the latest split than the ghost did.
+//!
+//! The ghost's sprites come from its per-pixel `HdTileSource` telemetry when
+//! the `hd-pack` feature is built in, and otherwise from decoding its OAM
+//! against the pattern table it points at.
+
+use std::fmt::Write as _;
+
+use rustynes_core::{Movie, MovieError, Nes};
+use serde::{Deserialize, Serialize};
+
+use crate::config::GhostConfig;
+
+const W: usize = 256;
+const H: usize = 240;
+const FRAME_LEN: usize = W * H * 4;
+
+/// The inset is the ghost picture at a third of the size.
+const PIP_SCALE: usize = 3;
+const PIP_W: usize = W / PIP_SCALE;
+const PIP_H: usize = H / PIP_SCALE;
+/// Inset position: top-right, clear of the 8-line overscan band.
+const PIP_X: usize = W - PIP_W - 8;
+const PIP_Y: usize = 12;
+const PIP_BORDER: [u8; 4] = [0xE0, 0xE0, 0xE0, 0xFF];
+
+/// Ghost frames run per live frame at most while catching up to a split, so
+/// a ghost far behind closes the gap quickly without stalling the live game.
+const CATCH_UP_FRAMES: u32 = 120;
+
+/// How the ghost is kept in step with live play.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum GhostAlign {
+    /// One ghost frame per live frame from the moment the ghost starts. The
+    /// default.
+    #[default]
+    Frame,
+    /// Re-aligned at every split: a ghost that reaches a split first waits
+    /// there, and one that falls behind fast-forwards to it.
+    Split,
+}
+
+impl GhostAlign {
+    /// Every mode, in menu order.
+    pub const ALL: [Self; 2] = [Self::Frame, Self::Split];
+
+    /// Human label for the menu.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Frame => "By frame",
+            Self::Split => "By split point",
+        }
+    }
+}
+
+/// The ghost's progress, snapshotted for the status bar and menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GhostStatus {
+    /// Ghost frames played.
+    pub frame: usize,
+    /// Frames in the ghost's movie.
+    pub len: usize,
+    /// Live minus ghost frame count at the latest split both have reached
+    /// (positive: the live run is behind). `None` before the first one.
+    pub delta: Option<i64>,
+    /// `true` while the ghost waits at a split for the live run.
+    pub holding: bool,
+}
+
+impl GhostStatus {
+    /// "GHOST 1234/5000 +12" for the status bar.
+    #[must_use]
+    pub fn label(self) -> String {
+        let mut s = format!("GHOST {}/{}", self.frame, self.len);
+        if let Some(d) = self.delta {
+            let _ = write!(s, " {d:+}");
+        }
+        if self.holding {
+            s.push_str(" (at split)");
+        } else if self.frame >= self.len {
+            s.push_str(" (done)");
+        }
+        s
+    }
+}
+
+/// The frames on which each console reached a split.
+#[derive(Debug, Default)]
+struct SplitClock {
+    live: Vec<u64>,
+    ghost: Vec<u64>,
+    live_was: bool,
+    ghost_was: bool,
+}
+
+impl SplitClock {
+    fn observe_live(&mut self, hit: bool, frame: u64) {
+        if hit && !self.live_was {
+            self.live.push(frame);
+        }
+        self.live_was = hit;
+    }
+
+    fn observe_ghost(&mut self, hit: bool, frame: u64) {
+        if hit && !self.ghost_was {
+            self.ghost.push(frame);
+        }
+        self.ghost_was = hit;
+    }
+
+    const fn ghost_ahead(&self) -> bool {
+        self.ghost.len() > self.live.len()
+    }
+
+    const fn live_ahead(&self) -> bool {
+        self.live.len() > self.ghost.len()
+    }
+
+    fn delta(&self) -> Option<i64> {
+        let k = self.live.len().min(self.ghost.len()).checked_sub(1)?;
+        let frames = |f: u64| i64::try_from(f).unwrap_or(i64::MAX);
+        Some(frames(self.live[k]) - frames(self.ghost[k]))
+    }
+}
+
+/// A movie replaying on its own console beside live play.
+pub struct Ghost {
+    nes: Nes,
+    movie: Movie,
+    extended: bool,
+    cursor: usize,
+    settings: GhostConfig,
+    live_frames: u64,
+    splits: SplitClock,
+    mask: Vec<bool>,
+    audio_sink: Vec<f32>,
+}
+
+impl Ghost {
+    /// Seek `nes` — a fresh console for the movie's ROM — to the movie's start
+    /// and wrap it as a ghost.
+    ///
+    /// # Errors
+    ///
+    /// Whatever [`Movie::seek_to_start`] reports: a ROM mismatch or a bad
+    /// embedded save-state.
+    pub fn new(mut nes: Nes, movie: Movie, settings: GhostConfig) -> Result<Self, MovieError> {
+        movie.seek_to_start(&mut nes)?;
+        Ok(Self {
+            nes,
+            extended: movie.is_extended(),
+            movie,
+            cursor: 0,
+            settings,
+            live_frames: 0,
+            splits: SplitClock::default(),
+            mask: vec![false; W * H],
+            audio_sink: vec![0.0; 4096],
+        })
+    }
+
+    /// SHA-256 of the ROM the ghost runs.
+    #[must_use]
+    pub const fn rom_sha256(&self) -> &[u8; 32] {
+        &self.movie.rom_sha256
+    }
+
+    /// Apply new settings. Changing the split point forgets the splits seen
+    /// so far, since they measured a different one.
+    pub fn configure(&mut self, settings: GhostConfig) {
+        if (settings.split_addr, settings.split_value)
+            != (self.settings.split_addr, self.settings.split_value)
+        {
+            self.splits = SplitClock::default();
+        }
+        self.settings = settings;
+    }
+
+    /// Start the ghost over from its first frame, e.g. when the live run
+    /// restarts.
+    ///
+    /// # Errors
+    ///
+    /// As for [`Self::new`].
+    pub fn restart(&mut self) -> Result<(), MovieError> {
+        self.movie.seek_to_start(&mut self.nes)?;
+        self.cursor = 0;
+        self.live_frames = 0;
+        self.splits = SplitClock::default();
+        Ok(())
+    }
+
+    /// Progress for the readout.
+    #[must_use]
+    pub fn status(&self) -> GhostStatus {
+        GhostStatus {
+            frame: self.cursor,
+            len: self.movie.len(),
+            delta: self.splits.delta(),
+            holding: self.settings.align == GhostAlign::Split && self.splits.ghost_ahead(),
+        }
+    }
+
+    /// Advance alongside one completed live frame. `live` is only peeked for
+    /// the split point. Rewinding the live game leaves the ghost where it is.
+    pub fn step(&mut self, live: &mut Nes) {
+        self.live_frames += 1;
+        let hit = live.peek(self.settings.split_addr) == self.settings.split_value;
+        self.splits.observe_live(hit, self.live_frames);
+        match self.settings.align {
+            GhostAlign::Frame => {
+                self.advance();
+            }
+            GhostAlign::Split => {
+                if self.splits.ghost_ahead() {
+                    return;
+                }
+                let mut budget = CATCH_UP_FRAMES;
+                while self.advance() && self.splits.live_ahead() && budget > 0 {
+                    budget -= 1;
+                }
+            }
+        }
+    }
+
+    /// Run the ghost's next movie frame. `false` once the movie is over (the
+    /// ghost then stays on its last frame).
+    fn advance(&mut self) -> bool {
+        let Some(input) = self.movie.frames.get(self.cursor).copied() else {
+            return false;
+        };
+        input.apply(&mut self.nes, self.extended);
+        self.nes.run_frame();
+        self.cursor += 1;
+        // The ghost is silent; keep its sample buffer from building up.
+        while self.nes.drain_audio_into(&mut self.audio_sink) == self.audio_sink.len() {}
+        let hit = self.nes.peek(self.settings.split_addr) == self.settings.split_value;
+        self.splits
+            .observe_ghost(hit, u64::try_from(self.cursor).unwrap_or(u64::MAX));
+        true
+    }
+
+    /// Draw the ghost into the presented `frame` per the settings. A short
+    /// frame is left alone.
+    pub fn compose(&mut self, frame: &mut [u8]) {
+        if frame.len() != FRAME_LEN {
+            return;
+        }
+        if self.settings.overlay {
+            sprite_mask(&mut self.nes, &mut self.mask);
+            blend_sprites(
+                frame,
+                self.nes.framebuffer(),
+                &self.mask,
+                self.settings.opacity,
+            );
+        }
+        if self.settings.pip {
+            draw_inset(frame, self.nes.framebuffer());
+        }
+    }
+}
+
+/// Mark the pixels the ghost's sprites drew, from the PPU's per-pixel
+/// tile-source telemetry.
+#[cfg(feature = "hd-pack")]
+#[allow(clippy::needless_pass_by_ref_mut)] // shares the OAM path's signature
+fn sprite_mask(nes: &mut Nes, mask: &mut [bool]) {
+    for (m, t) in mask.iter_mut().zip(nes.hd_tile_source()) {
+        *m = t.is_sprite;
+    }
+}
+
+/// Mark the opaque pixels of every sprite in the ghost's OAM, decoding each
+/// one's pattern from CHR with its flips and the 8x8 / 8x16 size.
+#[cfg(not(feature = "hd-pack"))]
+fn sprite_mask(nes: &mut Nes, mask: &mut [bool]) {
+    mask.fill(false);
+    let oam = nes.oam();
+    let ppu = nes.ppu_snapshot();
+    let height: u16 = if ppu.sprite_size_16 { 16 } else { 8 };
+    for sprite in oam.chunks_exact(4) {
+        let (y, tile, attr, x) = (sprite[0], sprite[1], sprite[2], sprite[3]);
+        // Y $EF and up is off-screen (the usual way to hide a sprite).
+        if y >= 0xEF {
+            continue;
+        }
+        let base = if height == 16 {
+            u16::from(tile & 1) * 0x1000 + u16::from(tile & 0xFE) * 16
+        } else {
+            ppu.sprite_pattern_base + u16::from(tile) * 16
+        };
+        for row in 0..height {
+            let sy = usize::from(y) + 1 + usize::from(row);
+            if sy >= H {
+                break;
+            }
+            let r = if attr & 0x80 != 0 {
+                height - 1 - row
+            } else {
+                row
+            };
+            let addr = base + r / 8 * 16 + r % 8;
+            let bits = nes.ppu_bus_peek(addr) | nes.ppu_bus_peek(addr + 8);
+            for col in 0..8u8 {
+                let sx = usize::from(x) + usize::from(col);
+                if sx >= W {
+                    break;
+                }
+                let bit = if attr & 0x40 != 0 { col } else { 7 - col };
+                if (bits >> bit) & 1 != 0 {
+                    mask[sy * W + sx] = true;
+                }
+            }
+        }
+    }
+}
+
+/// Mix the ghost's masked pixels into `frame` at `opacity` percent.
+fn blend_sprites(frame: &mut [u8], ghost: &[u8], mask: &[bool], opacity: u8) {
+    let a = u16::from(opacity.min(100)) * 255 / 100;
+    for ((out, g), _) in frame
+        .chunks_exact_mut(4)
+        .zip(ghost.chunks_exact(4))
+        .zip(mask)
+        .filter(|(_, m)| **m)
+    {
+        for c in 0..3 {
+            let mixed = (u16::from(out[c]) * (255 - a) + u16::from(g[c]) * a + 127) / 255;
+            out[c] = u8::try_from(mixed).unwrap_or(u8::MAX);
+        }
+    }
+}
+
+/// Copy the ghost picture, shrunk by [`PIP_SCALE`], into the top-right
+/// corner of `frame` inside a one-pixel border.
+fn draw_inset(frame: &mut [u8], ghost: &[u8]) {
+    for y in PIP_Y - 1..=PIP_Y + PIP_H {
+        for x in PIP_X - 1..=PIP_X + PIP_W {
+            let d = (y * W + x) * 4;
+            let src = if (PIP_Y..PIP_Y + PIP_H).contains(&y) && (PIP_X..PIP_X + PIP_W).contains(&x)
+            {
+                let s = ((y - PIP_Y) * PIP_SCALE * W + (x - PIP_X) * PIP_SCALE) * 4;
+                &ghost[s..s + 4]
+            } else {
+                &PIP_BORDER[..]
+            };
+            frame[d..d + 4].copy_from_slice(src);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(r: u8, g: u8, b: u8) -> Vec<u8> {
+        [r, g, b, 0xFF].repeat(W * H)
+    }
+
+    #[test]
+    fn only_masked_pixels_take_the_ghost_colour() {
+        let mut frame = solid(0, 0, 0);
+        let ghost = solid(200, 100, 50);
+        let mut mask = vec![false; W * H];
+        mask[1] = true;
+        blend_sprites(&mut frame, &ghost, &mask, 50);
+        assert_eq!(&frame[..4], &[0, 0, 0, 0xFF]);
+        assert_eq!(&frame[4..8], &[100, 50, 25, 0xFF]);
+        blend_sprites(&mut frame, &ghost, &mask, 100);
+        assert_eq!(&frame[4..8], &[200, 100, 50, 0xFF]);
+    }
+
+    #[test]
+    fn inset_sits_in_the_top_right_inside_a_border() {
+        let mut frame = solid(0, 0, 0);
+        draw_inset(&mut frame, &solid(90, 90, 90));
+        let px = |x: usize, y: usize| frame[(y * W + x) * 4];
+        assert_eq!(px(PIP_X, PIP_Y), 90);
+        assert_eq!(px(PIP_X - 1, PIP_Y), PIP_BORDER[0]);
+        assert_eq!(px(PIP_X + PIP_W, PIP_Y + PIP_H), PIP_BORDER[0]);
+        assert_eq!(px(0, 0), 0);
+        assert_eq!(px(PIP_X - 2, PIP_Y), 0);
+    }
+
+    #[test]
+    fn splits_count_rising_edges_and_report_the_latest_delta() {
+        let mut c = SplitClock::default();
+        assert_eq!(c.delta(), None);
+        c.observe_ghost(true, 100);
+        c.observe_ghost(true, 101); // still set: not a new split
+        assert!(c.ghost_ahead());
+        c.observe_live(true, 112);
+        assert!(!c.ghost_ahead() && !c.live_ahead());
+        assert_eq!(c.delta(), Some(12));
+        c.observe_live(false, 113);
+        c.observe_live(true, 200);
+        assert!(c.live_ahead());
+        c.observe_ghost(false, 150);
+        c.observe_ghost(true, 210);
+        assert_eq!(c.delta(), Some(-10));
+    }
+
+    #[test]
+    fn status_label_shows_delta_and_state() {
+        let s = GhostStatus {
+            frame: 10,
+            len: 20,
+            delta: Some(3),
+            holding: true,
+        };
+        assert_eq!(s.label(), "GHOST 10/20 +3 (at split)");
+        let done = GhostStatus {
+            frame: 20,
+            delta: None,
+            holding: false,
+            ..s
+        };
+        assert_eq!(done.label(), "GHOST 20/20 (done)");
+    }
+}
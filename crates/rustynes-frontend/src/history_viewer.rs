@@ -175,6 +175,7 @@ impl HistoryViewer {
             p1: nes.buttons(0),
             p2: nes.buttons(1),
             expansion: 0,
+            ..FrameInput::default()
         };
         // Stash a start-anchor on the cadence (and always on the very first
         // recorded frame, so an early export still has a base).
@@ -324,6 +325,7 @@ impl HistoryViewer {
             // video output an attestation hashes, so one cannot be reconstructed
             // after the fact. Re-record the run with attestation on to get one.
             attestation: None,
+            match_info: None,
         })
     }
 
@@ -78,6 +78,12 @@ pub mod glyph {
     pub const FORWARD_FAST: char = '\u{f050}';
     /// `stop` — stop recording / stop playback.
     pub const STOP: char = '\u{f04d}';
+    /// `backward-fast` — rewind a tape to its start (v2.5.0).
+    pub const BACKWARD_FAST: char = '\u{f049}';
+    /// `circle` — start recording (v2.5.0).
+    pub const CIRCLE: char = '\u{f111}';
+    /// `cassette-tape` — Family BASIC Data Recorder (v2.5.0).
+    pub const CASSETTE_TAPE: char = '\u{f8ab}';
     /// `forward`/sliders — run-ahead & speed sliders.
     pub const SLIDERS: char = '\u{f1de}';
     /// `globe` — region.
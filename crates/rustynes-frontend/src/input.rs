@@ -113,6 +113,9 @@ pub enum SysAction {
     /// v1.0.0 — reset the emulation speed to 100% (default `Digit0`). Emitted
     /// on press only.
     SpeedReset,
+    /// v2.5.0 — reload the current practice segment (default `Backspace`).
+    /// Emitted on press only.
+    PracticeReload,
 }
 
 /// Keyboard layout resolved from the loaded [`InputConfig`].
@@ -200,6 +203,11 @@ impl KeyBindings {
         try_bind(&mut system, &cfg.system.speed_up, SysAction::SpeedUp);
         try_bind(&mut system, &cfg.system.speed_down, SysAction::SpeedDown);
         try_bind(&mut system, &cfg.system.speed_reset, SysAction::SpeedReset);
+        try_bind(
+            &mut system,
+            &cfg.system.practice_reload,
+            SysAction::PracticeReload,
+        );
         Self {
             player1,
             player2,
@@ -640,6 +648,9 @@ pub struct InputState {
     /// separate from the digital pad bits so a stick recentering past
     /// the deadzone clears only the stick contribution.
     gamepad_axis: [Buttons; MAX_PADS],
+    /// v2.5.0 — raw stick / trigger positions per player, for the analog
+    /// expansion controllers (Hori Track, Pachinko, Top Rider, Power Glove).
+    analog: [AnalogInputs; MAX_PADS],
     /// Resolved keyboard bindings.
     bindings: KeyBindings,
     /// Resolved per-player gamepad maps (0..=3).
@@ -733,6 +744,178 @@ pub const BANDAI_HYPER_SHOT_KEYS: [KeyCode; 8] = [
 /// and *Kid Icarus* poll it. Fixed for now (a rebindable mic key is a follow-up).
 pub const MICROPHONE_KEY: KeyCode = KeyCode::KeyM;
 
+/// v2.5.0 — one pad's analog sticks and triggers, quantised for the analog
+/// expansion controllers.
+///
+/// Sticks are `-127..=127` (positive = right / up, the `gilrs` convention);
+/// triggers are `0..=255`. Centred / released is all zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnalogInputs {
+    /// Left stick X.
+    pub left_x: i8,
+    /// Left stick Y (positive = up).
+    pub left_y: i8,
+    /// Right stick X.
+    pub right_x: i8,
+    /// Right stick Y (positive = up).
+    pub right_y: i8,
+    /// Left analog trigger.
+    pub left_trigger: u8,
+    /// Right analog trigger.
+    pub right_trigger: u8,
+}
+
+impl AnalogInputs {
+    /// Pack into a `u64` (one byte per field, little-endian in field order),
+    /// for the emulation thread's atomic input mailbox.
+    #[must_use]
+    pub const fn pack(self) -> u64 {
+        u64::from_le_bytes([
+            self.left_x.cast_unsigned(),
+            self.left_y.cast_unsigned(),
+            self.right_x.cast_unsigned(),
+            self.right_y.cast_unsigned(),
+            self.left_trigger,
+            self.right_trigger,
+            0,
+            0,
+        ])
+    }
+
+    /// Inverse of [`Self::pack`].
+    #[must_use]
+    pub const fn unpack(v: u64) -> Self {
+        let b = v.to_le_bytes();
+        Self {
+            left_x: b[0].cast_signed(),
+            left_y: b[1].cast_signed(),
+            right_x: b[2].cast_signed(),
+            right_y: b[3].cast_signed(),
+            left_trigger: b[4],
+            right_trigger: b[5],
+        }
+    }
+}
+
+/// A `gilrs` stick value (`-1.0..=1.0`) as `-127..=127`.
+#[allow(clippy::cast_possible_truncation)] // clamped into range first
+fn stick_i8(value: f32) -> i8 {
+    (value.clamp(-1.0, 1.0) * 127.0).round() as i8
+}
+
+/// A `gilrs` trigger value (`0.0..=1.0`) as `0..=255`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamped
+fn trigger_u8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// v2.5.0 — Hori Track ball motion for one frame: the mouse motion plus the
+/// right stick (full deflection = the report's 7-count maximum).
+#[must_use]
+pub fn hori_track_motion(mouse_delta: (i16, i16), analog: AnalogInputs) -> (i16, i16) {
+    (
+        mouse_delta.0 + i16::from(analog.right_x) / 16,
+        mouse_delta.1 - i16::from(analog.right_y) / 16,
+    )
+}
+
+/// v2.5.0 — Pachinko plunger travel (`0` = rest .. `255` = fully pulled).
+///
+/// The deepest of the right trigger, the left stick pulled down, and — while
+/// the left mouse button is held — the cursor's height on the screen.
+#[must_use]
+pub fn pachinko_plunger(analog: AnalogInputs, mouse_y: Option<u16>) -> u8 {
+    let stick = u8::try_from((-i16::from(analog.left_y)).clamp(0, 127) * 2).unwrap_or(u8::MAX);
+    let mouse = mouse_y.map_or(0, |y| {
+        u8::try_from(u32::from(y.min(239)) * 255 / 239).unwrap_or(u8::MAX)
+    });
+    analog.right_trigger.max(stick).max(mouse)
+}
+
+/// v2.5.0 — Top Rider handlebars, throttle and `TOP_RIDER_*` switches.
+///
+/// From the player-1 pad and its analog sticks: the left stick (or Left /
+/// Right at full lock) steers, the right trigger (or A, wide open) is the
+/// throttle, the left trigger (or B) brakes, Up pulls a wheelie and Down holds
+/// high gear.
+#[must_use]
+pub fn top_rider_controls(buttons: Buttons, analog: AnalogInputs) -> (i8, u8, u8) {
+    use rustynes_core::{
+        TOP_RIDER_BRAKE, TOP_RIDER_SELECT, TOP_RIDER_SHIFT, TOP_RIDER_START, TOP_RIDER_WHEELIE,
+    };
+    let steer = if buttons.contains(Buttons::LEFT) {
+        -3
+    } else if buttons.contains(Buttons::RIGHT) {
+        3
+    } else {
+        analog.left_x / 32
+    };
+    let throttle = if buttons.contains(Buttons::A) {
+        3
+    } else {
+        analog.right_trigger / 64
+    };
+    let mut keys = 0;
+    for (on, bit) in [
+        (
+            buttons.contains(Buttons::B) || analog.left_trigger >= 128,
+            TOP_RIDER_BRAKE,
+        ),
+        (buttons.contains(Buttons::DOWN), TOP_RIDER_SHIFT),
+        (buttons.contains(Buttons::UP), TOP_RIDER_WHEELIE),
+        (buttons.contains(Buttons::START), TOP_RIDER_START),
+        (buttons.contains(Buttons::SELECT), TOP_RIDER_SELECT),
+    ] {
+        if on {
+            keys |= bit;
+        }
+    }
+    (steer, throttle, keys)
+}
+
+/// v2.5.0 — the tracked Power Glove hand.
+///
+/// The cursor (NES pixels, `u16::MAX` = off-screen) sets X/Y relative to the
+/// screen centre, falling back to the left stick off-screen; the right stick
+/// sets depth (Y) and wrist roll (X);
+/// left mouse clenches a fist and right mouse thrusts it toward the screen;
+/// the left / right triggers bend the thumb / index finger; the player-1
+/// buttons are the forearm keypad.
+#[must_use]
+pub fn power_glove_hand(
+    mouse_nes: (u16, u16),
+    mouse_left: bool,
+    mouse_right: bool,
+    buttons: Buttons,
+    analog: AnalogInputs,
+) -> rustynes_core::PowerGloveHand {
+    let to_i8 = |v: i32| i8::try_from(v.clamp(-127, 127)).unwrap_or_default();
+    let (x, y) = if mouse_nes.0 == u16::MAX || mouse_nes.1 == u16::MAX {
+        (analog.left_x, analog.left_y)
+    } else {
+        (
+            to_i8(i32::from(mouse_nes.0) - 128),
+            to_i8(120 - i32::from(mouse_nes.1)),
+        )
+    };
+    let z = if mouse_right { 64 } else { analog.right_y };
+    let roll = u8::try_from((i32::from(analog.right_x) / 43).rem_euclid(12)).unwrap_or(0);
+    let flex = |t: u8| t >> 6;
+    let fingers = if mouse_left || mouse_right {
+        0xFF
+    } else {
+        (flex(analog.left_trigger) << 6) | (flex(analog.right_trigger) << 4)
+    };
+    rustynes_core::PowerGloveHand {
+        x,
+        y,
+        z,
+        roll,
+        fingers,
+        keypad: buttons.bits(),
+    }
+}
+
 /// v1.2.0 Workstream D — host-key -> Family BASIC keyboard matrix-index map.
 ///
 /// The Famicom Family BASIC keyboard is a `9 x 8` switch matrix (`9` rows x
@@ -825,6 +1008,7 @@ impl InputState {
             keyboard_buttons: [Buttons::empty(); MAX_PADS],
             gamepad_buttons: [Buttons::empty(); MAX_PADS],
             gamepad_axis: [Buttons::empty(); MAX_PADS],
+            analog: [AnalogInputs::default(); MAX_PADS],
             bindings,
             gamepad_maps,
             pad_assignment: PadAssignment::default(),
@@ -939,6 +1123,9 @@ impl InputState {
         match *event {
             gilrs::EventType::ButtonPressed(btn, _) => self.set_gamepad_button(player, btn, true),
             gilrs::EventType::ButtonReleased(btn, _) => self.set_gamepad_button(player, btn, false),
+            gilrs::EventType::ButtonChanged(btn, value, _) => {
+                self.set_gamepad_trigger(player, btn, value);
+            }
             gilrs::EventType::AxisChanged(axis, value, _) => {
                 self.set_gamepad_axis(player, axis, value);
             }
@@ -964,10 +1151,23 @@ impl InputState {
     /// Only `LeftStickX` / `LeftStickY` are mapped — the right stick and
     /// the dedicated D-pad axes are ignored here (the D-pad arrives as
     /// `Button::DPad*` events). `Axis::Unknown` is ignored.
+    ///
+    /// v2.5.0 — every stick axis (and the `LeftZ` / `RightZ` triggers some
+    /// backends report as axes) is also recorded raw in [`Self::analog`].
     pub fn set_gamepad_axis(&mut self, player: usize, axis: gilrs::Axis, value: f32) {
         let Some(slot) = self.gamepad_axis.get_mut(player) else {
             return;
         };
+        let analog = &mut self.analog[player];
+        match axis {
+            gilrs::Axis::LeftStickX => analog.left_x = stick_i8(value),
+            gilrs::Axis::LeftStickY => analog.left_y = stick_i8(value),
+            gilrs::Axis::RightStickX => analog.right_x = stick_i8(value),
+            gilrs::Axis::RightStickY => analog.right_y = stick_i8(value),
+            gilrs::Axis::LeftZ => analog.left_trigger = trigger_u8(value),
+            gilrs::Axis::RightZ => analog.right_trigger = trigger_u8(value),
+            _ => {}
+        }
         let dz = self.gamepad_maps[player].deadzone();
         match axis {
             gilrs::Axis::LeftStickX => {
@@ -983,6 +1183,27 @@ impl InputState {
         }
     }
 
+    /// v2.5.0 — record an analog trigger (`LeftTrigger2` / `RightTrigger2`)
+    /// position for a player. Other buttons' analog values are ignored; their
+    /// digital press arrives separately.
+    pub fn set_gamepad_trigger(&mut self, player: usize, btn: gilrs::Button, value: f32) {
+        let Some(analog) = self.analog.get_mut(player) else {
+            return;
+        };
+        match btn {
+            gilrs::Button::LeftTrigger2 => analog.left_trigger = trigger_u8(value),
+            gilrs::Button::RightTrigger2 => analog.right_trigger = trigger_u8(value),
+            _ => {}
+        }
+    }
+
+    /// v2.5.0 — the raw stick / trigger positions for `player` (0..=3).
+    /// Out-of-range indices return centred sticks.
+    #[must_use]
+    pub fn analog(&self, player: usize) -> AnalogInputs {
+        self.analog.get(player).copied().unwrap_or_default()
+    }
+
     /// `true` while the rewind key is held.
     #[must_use]
     pub const fn rewind_held(&self) -> bool {
@@ -1441,6 +1662,44 @@ mod tests {
         assert!(!s.player1().contains(Buttons::UP));
     }
 
+    #[test]
+    fn analog_axes_and_triggers_are_recorded_raw() {
+        use gilrs::{Axis, Button};
+        let mut s = InputState::with_defaults();
+        s.set_gamepad_axis(0, Axis::RightStickY, -1.0);
+        s.set_gamepad_axis(0, Axis::LeftStickX, 0.5);
+        s.set_gamepad_trigger(0, Button::RightTrigger2, 1.0);
+        let a = s.analog(0);
+        assert_eq!((a.right_y, a.left_x, a.right_trigger), (-127, 64, 255));
+        assert_eq!(AnalogInputs::unpack(a.pack()), a);
+        assert_eq!(s.analog(9), AnalogInputs::default());
+    }
+
+    #[test]
+    fn analog_device_mappings() {
+        let a = AnalogInputs {
+            left_x: -127,
+            left_y: -127,
+            right_x: 127,
+            right_trigger: 200,
+            ..AnalogInputs::default()
+        };
+        assert_eq!(hori_track_motion((2, -3), a), (2 + 7, -3));
+        assert_eq!(pachinko_plunger(a, None), 254);
+        assert_eq!(pachinko_plunger(AnalogInputs::default(), Some(239)), 255);
+        let (steer, throttle, keys) = top_rider_controls(Buttons::B | Buttons::UP, a);
+        assert_eq!((steer, throttle), (-3, 3));
+        assert_eq!(
+            keys,
+            rustynes_core::TOP_RIDER_BRAKE | rustynes_core::TOP_RIDER_WHEELIE
+        );
+        let hand = power_glove_hand((128 + 40, 120), true, false, Buttons::START, a);
+        assert_eq!((hand.x, hand.y, hand.fingers), (40, 0, 0xFF));
+        assert_eq!(hand.keypad, Buttons::START.bits());
+        let off = power_glove_hand((u16::MAX, u16::MAX), false, true, Buttons::empty(), a);
+        assert_eq!((off.x, off.z), (-127, 64));
+    }
+
     #[test]
     fn two_pads_drive_independent_players() {
         use gilrs::Button;
@@ -94,6 +94,7 @@ impl MacroBank {
                     p1: Buttons::from_bits_truncate(rec[0]),
                     p2: Buttons::from_bits_truncate(rec[1]),
                     expansion: rec[2],
+                    ..FrameInput::default()
                 });
             }
             macros.push(InputMacro { name, frames });
@@ -29,6 +29,10 @@ pub mod audio;
 // headphone crossfeed). Bypass-by-default (center pan, 0% reverb, 0 crossfeed)
 // reproduces today's mono-duplicated-to-stereo output bit-for-bit.
 pub mod audio_dsp;
+// v2.5.0 — host microphone capture for the Famicom mic: a cpal input stream
+// reduced to the per-frame `$4016` D2 slice pattern (native-only).
+#[cfg(not(target_arch = "wasm32"))]
+pub mod mic_capture;
 // v1.6.0 "Studio" Workstream G — A/V (video + synchronized audio) recording.
 // A read-only frontend tap on the already-produced framebuffer + drained audio
 // that pipes them to an external `ffmpeg` to mux an .mp4/.mkv. Native-only +
@@ -37,6 +41,9 @@ pub mod audio_dsp;
 // per-frame output, so the determinism contract is unaffected.
 #[cfg(all(not(target_arch = "wasm32"), feature = "av-record"))]
 pub mod av_record;
+// v2.5.0 — speedrun autosplitter: per-ROM split conditions in the debugger's
+// expression syntax driving a frame-counted timer.
+pub mod autosplit;
 // About-dialog input helper (native only; safe Rust, portable across all arches).
 #[cfg(not(target_arch = "wasm32"))]
 mod about_fx;
@@ -83,6 +90,9 @@ pub mod genie_db;
 // (frontend-only, pure; round-trips through the core decoder).
 pub mod genie_encode;
 pub mod gfx;
+// v2.5.0 — ghost replay: a movie racing beside live play on its own console,
+// drawn over the presented frame.
+pub mod ghost;
 // v2.1.8 "Performance" (A2) — vectorized software palette-index -> RGBA
 // blitter, byte-identical to the core's pixel-emit path. Reusable on every
 // target (native `wide` SIMD, wasm `+simd128`, scalar fallback); see the module
@@ -102,6 +112,10 @@ pub mod history_viewer;
 // v1.8.9 "Backlog" — input macros / templates: reusable per-frame input patterns
 // stamped into the TAStudio piano-roll.
 pub mod input_macros;
+// v2.5.0 — LiveSplit Server client mirroring the autosplitter's timer (TCP,
+// native-only).
+#[cfg(not(target_arch = "wasm32"))]
+pub mod livesplit;
 // v1.8.9 "Backlog" — FDS firmware (disksys.rom) recognition for the Settings FDS
 // picker (size + known-hash classification).
 pub mod fds_firmware;
@@ -162,6 +176,9 @@ pub mod present_buffer;
 // "Logging" checkbox). Native-only: it writes files under `perf-logs/`.
 #[cfg(not(target_arch = "wasm32"))]
 pub mod perf_log;
+// v2.5.0 — practice mode: per-ROM segment save states with an end condition
+// in the debugger's expression syntax, timed attempts and their history.
+pub mod practice;
 // v2.8.0 Phase 1 — 4-tap Hermite resampler + Near's dynamic-rate-control
 // law (the frontend half of "video master + audio DRC"; the core's sample
 // output stays byte-identical).
@@ -184,6 +201,9 @@ pub mod presentation_clock;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod refresh_probe;
 pub mod runahead;
+// v2.5.0 — Famicom 3D System stereo pairing (anaglyph / side-by-side / one
+// eye) of the presented frame. Pure; the core stream is untouched.
+pub mod stereo;
 /// v2.3.3 — the display refresh as reported by a Wayland compositor.
 ///
 /// The refresh source of last resort, for the (common) case where the
@@ -0,0 +1,260 @@
+//! v2.5.0 — client for `LiveSplit`'s TCP server.
+//!
+//! `LiveSplit`'s server component (and `LiveSplit` One's server
+//! mode) listens on a TCP port — 16834 by default — for text commands, one
+//! per line terminated by `\r\n`. The autosplitter's [`TimerEvent`]s map onto
+//! them directly, so an external timer mirrors the in-app one: the run starts,
+//! splits and resets together. `LiveSplit`'s game time runs on its own clock
+//! between events and is pinned to the emulator's frame count (`setgametime`)
+//! at every split, pause and resume, so loads and lag it was told about are
+//! removed exactly.
+//!
+//! The client only writes; the commands used here send no reply. Resolving
+//! the address, connecting and writing all happen on a worker thread, so a
+//! slow DNS lookup or an unreachable host never stalls the UI: the caller
+//! queues events with [`LiveSplitClient::send`] and learns how the connection
+//! went from [`LiveSplitClient::poll`]. A write error means `LiveSplit` went
+//! away, and the caller drops the client.
+
+use std::io::{self, Write as _};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::time::Duration;
+
+use crate::autosplit::TimerEvent;
+
+/// How long a connect or a write may take before the server counts as gone.
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A change in the connection, reported by [`LiveSplitClient::poll`].
+#[derive(Debug)]
+pub enum LiveSplitEvent {
+    /// The server accepted the connection; queued events are on their way.
+    Connected,
+    /// The address did not resolve, or no server accepted in time.
+    Failed(io::Error),
+    /// The server closed the connection or stopped reading.
+    Lost(io::Error),
+}
+
+/// A connection to a `LiveSplit` server, run by a worker thread.
+///
+/// Dropping the client closes its queue; the worker then finishes any
+/// connect in progress and exits on its own (it is not joined, so dropping
+/// never blocks).
+#[derive(Debug)]
+pub struct LiveSplitClient {
+    /// Command batches for the worker, one per timer event.
+    commands: Sender<String>,
+    /// Connection changes from the worker.
+    events: Receiver<LiveSplitEvent>,
+    addr: String,
+    connected: bool,
+}
+
+impl LiveSplitClient {
+    /// Start connecting to the server at `addr` (`host:port`) in the
+    /// background. Returns at once; the outcome arrives through
+    /// [`Self::poll`].
+    ///
+    /// # Errors
+    /// The worker thread could not be spawned.
+    pub fn connect(addr: &str) -> io::Result<Self> {
+        let (commands, command_rx) = mpsc::channel();
+        let (event_tx, events) = mpsc::channel();
+        let target = addr.to_owned();
+        std::thread::Builder::new()
+            .name("livesplit".into())
+            .spawn(move || worker_loop(&target, &command_rx, &event_tx))?;
+        Ok(Self {
+            commands,
+            events,
+            addr: addr.to_owned(),
+            connected: false,
+        })
+    }
+
+    /// The address this client connects to.
+    #[must_use]
+    pub fn addr(&self) -> &str {
+        &self.addr
+    }
+
+    /// Whether the server has accepted the connection (and not dropped it
+    /// since, as far as [`Self::poll`] has reported).
+    #[must_use]
+    pub const fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// The next connection change, if any. Call once per frame.
+    pub fn poll(&mut self) -> Option<LiveSplitEvent> {
+        let event = match self.events.try_recv() {
+            Ok(event) => event,
+            Err(TryRecvError::Empty) => return None,
+            // The worker is gone without a word, which only a panic does.
+            Err(TryRecvError::Disconnected) => LiveSplitEvent::Lost(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "LiveSplit worker stopped",
+            )),
+        };
+        self.connected = matches!(event, LiveSplitEvent::Connected);
+        Some(event)
+    }
+
+    /// Mirror one timer event. `game_time` is the run's game time as of the
+    /// event. Never blocks: an event sent while the connection is still
+    /// opening is delivered once it opens.
+    pub fn send(&self, event: TimerEvent, game_time: Duration) {
+        let mut out = String::new();
+        for cmd in commands(event, game_time) {
+            out.push_str(&cmd);
+            out.push_str("\r\n");
+        }
+        // A closed queue means the worker has already reported why.
+        let _ = self.commands.send(out);
+    }
+}
+
+/// The worker: connect, report it, then write each queued batch until the
+/// client is dropped or a write fails.
+fn worker_loop(addr: &str, commands: &Receiver<String>, events: &Sender<LiveSplitEvent>) {
+    let mut stream = match open(addr) {
+        Ok(stream) => {
+            let _ = events.send(LiveSplitEvent::Connected);
+            stream
+        }
+        Err(e) => {
+            let _ = events.send(LiveSplitEvent::Failed(e));
+            return;
+        }
+    };
+    while let Ok(batch) = commands.recv() {
+        if let Err(e) = stream.write_all(batch.as_bytes()) {
+            let _ = events.send(LiveSplitEvent::Lost(e));
+            return;
+        }
+    }
+}
+
+/// Resolve `addr` and connect to the first address that accepts.
+fn open(addr: &str) -> io::Result<TcpStream> {
+    let mut last = io::Error::new(io::ErrorKind::NotFound, "address resolved to nothing");
+    for sock in addr.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&sock, TIMEOUT) {
+            Ok(stream) => {
+                stream.set_nodelay(true)?;
+                stream.set_write_timeout(Some(TIMEOUT))?;
+                return Ok(stream);
+            }
+            Err(e) => last = e,
+        }
+    }
+    Err(last)
+}
+
+/// The server commands for one timer event.
+#[must_use]
+pub fn commands(event: TimerEvent, game_time: Duration) -> Vec<String> {
+    let set = || format!("setgametime {}", time_span(game_time));
+    match event {
+        TimerEvent::Start => vec!["starttimer".into(), "initgametime".into()],
+        TimerEvent::Split => vec![set(), "split".into()],
+        TimerEvent::Reset => vec!["reset".into()],
+        TimerEvent::PauseGameTime => vec![set(), "pausegametime".into()],
+        TimerEvent::ResumeGameTime => vec![set(), "unpausegametime".into()],
+    }
+}
+
+/// `t` as the `h:mm:ss.fff` time span the server parses.
+fn time_span(t: Duration) -> String {
+    let secs = t.as_secs();
+    format!(
+        "{}:{:02}:{:02}.{:03}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        t.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read as _;
+    use std::net::TcpListener;
+
+    #[test]
+    fn events_map_to_server_commands() {
+        let t = Duration::from_millis(83_250);
+        assert_eq!(commands(TimerEvent::Reset, t), ["reset"]);
+        assert_eq!(
+            commands(TimerEvent::Split, t),
+            ["setgametime 0:01:23.250", "split"]
+        );
+        assert_eq!(
+            commands(TimerEvent::ResumeGameTime, Duration::from_secs(3_661)),
+            ["setgametime 1:01:01.000", "unpausegametime"]
+        );
+    }
+
+    /// The client's next connection change, waiting up to five seconds.
+    fn wait(client: &mut LiveSplitClient) -> LiveSplitEvent {
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        loop {
+            if let Some(event) = client.poll() {
+                return event;
+            }
+            assert!(std::time::Instant::now() < deadline, "no connection news");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn commands_reach_a_local_listener() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let server = std::thread::spawn(move || {
+            let (mut conn, _) = listener.accept().unwrap();
+            let mut got = String::new();
+            conn.read_to_string(&mut got).unwrap();
+            got
+        });
+        let mut client = LiveSplitClient::connect(&addr).unwrap();
+        assert_eq!(client.addr(), addr);
+        // Queued before the connection opens, delivered after.
+        client.send(TimerEvent::Start, Duration::ZERO);
+        assert!(matches!(wait(&mut client), LiveSplitEvent::Connected));
+        assert!(client.is_connected());
+        client.send(TimerEvent::Split, Duration::from_millis(1_500));
+        client.send(TimerEvent::Reset, Duration::ZERO);
+        drop(client);
+        assert_eq!(
+            server.join().unwrap(),
+            "starttimer\r\ninitgametime\r\n\
+             setgametime 0:00:01.500\r\nsplit\r\nreset\r\n"
+        );
+    }
+
+    #[test]
+    fn connecting_to_nothing_fails() {
+        // Bind then drop, so the port is (almost certainly) closed.
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let mut client = LiveSplitClient::connect(&addr.to_string()).unwrap();
+        assert!(matches!(wait(&mut client), LiveSplitEvent::Failed(_)));
+        assert!(!client.is_connected());
+    }
+
+    #[test]
+    fn connect_returns_before_the_connection_resolves() {
+        // A non-routable address would hold a blocking connect for the whole
+        // timeout; the client hands it to the worker instead.
+        let started = std::time::Instant::now();
+        let client = LiveSplitClient::connect("10.255.255.1:16834").unwrap();
+        assert!(started.elapsed() < TIMEOUT / 2);
+        assert!(!client.is_connected(), "nothing is reported until polled");
+    }
+}
@@ -131,17 +131,23 @@ fn run_verify(movie_path: &std::path::Path, rom_path: &std::path::Path) -> ExitC
         rom_path.display()
     );
     println!("  {} frames to replay...", movie.len());
-    // The verifier builds a DEFAULT `Nes` from the ROM bytes. A recording made
-    // with a non-default core profile — Four Score, a PPU die-revision or
-    // power-on RAM model, a per-game database override, a soft-patched ROM —
-    // will not reproduce here, and would report a mismatch that is the profile's
-    // fault rather than the movie's. The format carries no profile field to
-    // check against, so the assumption is stated up front instead of silently
-    // mis-blaming the movie. (Review finding on PR #356; recording-side
-    // eligibility is tracked as follow-up.)
-    println!("  (replaying with a DEFAULT core profile — a recording made with");
-    println!("   Four Score, a PPU revision / power-on model, a game-database");
-    println!("   override, or a patched ROM will not reproduce here)");
+    // A recorded netplay match carries the settings its peers negotiated, so
+    // it replays under them rather than under the profile caveat below.
+    if let Some(info) = &movie.match_info {
+        if !rustynes_netplay::match_record::prepare_replay(&movie, &mut nes) {
+            eprintln!(
+                "rustynes: {} is a netplay match whose settings this build cannot read",
+                movie_path.display()
+            );
+            return ExitCode::from(1);
+        }
+        println!(
+            "  (netplay match: {} players, replaying under the match's negotiated settings)",
+            info.num_players
+        );
+    } else {
+        print_profile_caveat();
+    }
     match movie.verify(&mut nes) {
         Ok(VerifyOutcome::Match { frames, hash }) => {
             println!("VERIFIED: {frames} frames reproduced exactly (hash {hash:016x}).");
@@ -178,6 +184,24 @@ fn run_verify(movie_path: &std::path::Path, rom_path: &std::path::Path) -> ExitC
     }
 }
 
+/// State the verifier's default-profile assumption for a movie that carries
+/// no settings of its own.
+#[cfg(not(target_arch = "wasm32"))]
+fn print_profile_caveat() {
+    // The verifier builds a DEFAULT `Nes` from the ROM bytes. A recording made
+    // with a non-default core profile — Four Score, a PPU die-revision or
+    // power-on RAM model, a per-game database override, a soft-patched ROM —
+    // will not reproduce here, and would report a mismatch that is the profile's
+    // fault rather than the movie's. The format carries no profile field to
+    // check against — only a netplay match recording carries its settings —
+    // so the assumption is stated up front instead of silently mis-blaming
+    // the movie. (Review finding on PR #356; recording-side
+    // eligibility is tracked as follow-up.)
+    println!("  (replaying with a DEFAULT core profile — a recording made with");
+    println!("   Four Score, a PPU revision / power-on model, a game-database");
+    println!("   override, or a patched ROM will not reproduce here)");
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn run_emulator(rom: Option<std::path::PathBuf>) -> ExitCode {
     // No ROM: the native binary has no bare-launch path (`app::run` always
@@ -0,0 +1,307 @@
+//! v2.5.0 — host microphone capture for the Famicom built-in microphone.
+//!
+//! The Famicom's controller-II microphone reaches software as a single bit
+//! (`$4016` D2), so a host input stream is reduced to the same thing: a peak
+//! envelope follower feeding a Schmitt trigger (on at `threshold`, off once
+//! the envelope falls `hysteresis` below it). The trigger is evaluated in
+//! blocks of 1/480 s — eight per 60 Hz frame — and each block's level is
+//! shifted into a lock-free history word the winit thread reads when it
+//! latches the frame's input.
+//!
+//! The emulator never sees the audio. It sees the 8-bit slice pattern from
+//! [`MicCapture::pattern`], latched with the buttons and recorded in the
+//! movie's expansion byte, so a take with a real microphone replays (and
+//! runs over netplay) exactly like one with the hold-to-talk key.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use cpal::SampleFormat;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+
+use crate::audio::AudioError;
+
+/// Trigger blocks per second: eight slices per 60 Hz frame.
+pub const BLOCKS_PER_SECOND: u32 = 480;
+
+/// Envelope release time constant, in seconds.
+const RELEASE_SECONDS: f32 = 0.008;
+
+/// Pure detector state: envelope follower, Schmitt trigger and block
+/// accumulator. Separate from the stream so it can be unit-tested.
+#[derive(Debug, Clone)]
+pub struct MicDetector {
+    threshold: f32,
+    hysteresis: f32,
+    release: f32,
+    envelope: f32,
+    level: bool,
+    block_len: u32,
+    block_pos: u32,
+    block_hit: bool,
+}
+
+impl MicDetector {
+    /// Detector for a `sample_rate` Hz mono stream. `threshold` is the peak
+    /// amplitude (0..1) that turns the mic on; it turns off again once the
+    /// envelope drops below `threshold - hysteresis`.
+    ///
+    /// Out-of-range config is clamped rather than trusted: the threshold to
+    /// `0.001..=1.0` and the hysteresis to `0..=threshold`. A NaN threshold
+    /// becomes 1.0 (the least sensitive setting) and a NaN hysteresis 0.
+    #[must_use]
+    pub fn new(sample_rate: u32, threshold: f32, hysteresis: f32) -> Self {
+        #[allow(clippy::cast_precision_loss)] // audio rates are << 2^24.
+        let rate = sample_rate.max(1) as f32;
+        let threshold = if threshold.is_nan() {
+            1.0
+        } else {
+            threshold.clamp(0.001, 1.0)
+        };
+        let hysteresis = if hysteresis.is_nan() {
+            0.0
+        } else {
+            hysteresis.clamp(0.0, threshold)
+        };
+        Self {
+            threshold,
+            hysteresis,
+            release: (-1.0 / (RELEASE_SECONDS * rate)).exp(),
+            envelope: 0.0,
+            level: false,
+            block_len: (sample_rate / BLOCKS_PER_SECOND).max(1),
+            block_pos: 0,
+            block_hit: false,
+        }
+    }
+
+    /// Current trigger level.
+    #[must_use]
+    pub const fn level(&self) -> bool {
+        self.level
+    }
+
+    /// Feed mono samples; `on_block` receives each completed block's level
+    /// (on if the trigger was on at any point within it).
+    pub fn process(&mut self, samples: &[f32], mut on_block: impl FnMut(bool)) {
+        for &s in samples {
+            let peak = s.abs();
+            self.envelope = if peak > self.envelope {
+                peak
+            } else {
+                self.envelope * self.release
+            };
+            if self.level {
+                self.level = self.envelope >= self.threshold - self.hysteresis;
+            } else {
+                self.level = self.envelope >= self.threshold;
+            }
+            self.block_hit |= self.level;
+            self.block_pos += 1;
+            if self.block_pos >= self.block_len {
+                on_block(self.block_hit);
+                self.block_pos = 0;
+                self.block_hit = false;
+            }
+        }
+    }
+}
+
+/// Shift a block level into a history word (newest block in bit 0).
+const fn push_block(history: u32, on: bool) -> u32 {
+    (history << 1) | on as u32
+}
+
+/// The frame pattern for a history word: the last eight blocks, oldest in
+/// bit 0 (bit `i` = the `i`-th eighth of the frame).
+#[must_use]
+pub const fn pattern_from_history(history: u32) -> u8 {
+    #[allow(clippy::cast_possible_truncation)] // low byte is the last 8 blocks.
+    (history as u8).reverse_bits()
+}
+
+/// A running host capture stream. Dropping it closes the device.
+pub struct MicCapture {
+    history: Arc<AtomicU32>,
+    _stream: cpal::Stream,
+}
+
+impl std::fmt::Debug for MicCapture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MicCapture")
+            .field("pattern", &self.pattern())
+            .finish_non_exhaustive()
+    }
+}
+
+impl MicCapture {
+    /// Enumerate the names of the available input devices for the Settings
+    /// picker. Empty when the host exposes none.
+    #[must_use]
+    pub fn input_device_names() -> Vec<String> {
+        cpal::default_host()
+            .input_devices()
+            .map(|it| it.map(|d: cpal::Device| d.to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Open an input device (`None` or an absent name = the host default)
+    /// and start detecting.
+    ///
+    /// # Errors
+    ///
+    /// [`AudioError`] when no device / no config / the stream fails to build.
+    pub fn open(
+        device_name: Option<&str>,
+        threshold: f32,
+        hysteresis: f32,
+    ) -> Result<Self, AudioError> {
+        let host = cpal::default_host();
+        let device = device_name
+            .and_then(|want| {
+                host.input_devices()
+                    .ok()
+                    .and_then(|mut it| it.find(|d: &cpal::Device| d.to_string() == want))
+            })
+            .or_else(|| host.default_input_device())
+            .ok_or(AudioError::NoDevice)?;
+        let default_cfg = device
+            .default_input_config()
+            .map_err(|e| AudioError::Cpal(e.to_string()))?;
+        let config: cpal::StreamConfig = default_cfg.config();
+        let chans = usize::from(config.channels.max(1));
+        let detector = MicDetector::new(config.sample_rate, threshold, hysteresis);
+        let history = Arc::new(AtomicU32::new(0));
+        let stream = match default_cfg.sample_format() {
+            SampleFormat::F32 => build::<f32>(&device, &config, chans, detector, &history),
+            SampleFormat::I16 => build::<i16>(&device, &config, chans, detector, &history),
+            SampleFormat::U16 => build::<u16>(&device, &config, chans, detector, &history),
+            _ => Err(AudioError::NoConfig),
+        }?;
+        stream.play().map_err(|e| AudioError::Cpal(e.to_string()))?;
+        Ok(Self {
+            history,
+            _stream: stream,
+        })
+    }
+
+    /// The current frame's mic pattern for
+    /// [`rustynes_core::Nes::set_microphone_pattern`].
+    #[must_use]
+    pub fn pattern(&self) -> u8 {
+        pattern_from_history(self.history.load(Ordering::Relaxed))
+    }
+}
+
+/// Build the input stream for one sample format, downmixing to mono (first
+/// channel) before the detector. The callback reuses one scratch buffer.
+fn build<S>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    chans: usize,
+    mut detector: MicDetector,
+    history: &Arc<AtomicU32>,
+) -> Result<cpal::Stream, AudioError>
+where
+    S: cpal::SizedSample,
+    f32: cpal::FromSample<S>,
+{
+    let history = Arc::clone(history);
+    let mut mono: Vec<f32> = Vec::new();
+    device
+        .build_input_stream(
+            *config,
+            move |data: &[S], _| {
+                mono.clear();
+                mono.extend(
+                    data.chunks(chans)
+                        .map(|frame| <f32 as cpal::FromSample<S>>::from_sample_(frame[0])),
+                );
+                let mut h = history.load(Ordering::Relaxed);
+                detector.process(&mono, |on| h = push_block(h, on));
+                history.store(h, Ordering::Relaxed);
+            },
+            |e| eprintln!("cpal input stream error: {e}"),
+            None,
+        )
+        .map_err(|e| AudioError::Cpal(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 48_000;
+    const BLOCK: usize = (RATE / BLOCKS_PER_SECOND) as usize;
+
+    fn run(detector: &mut MicDetector, samples: &[f32]) -> Vec<bool> {
+        let mut blocks = Vec::new();
+        detector.process(samples, |on| blocks.push(on));
+        blocks
+    }
+
+    #[test]
+    fn silence_stays_off() {
+        let mut d = MicDetector::new(RATE, 0.1, 0.03);
+        let blocks = run(&mut d, &[0.0; BLOCK * 8]);
+        assert_eq!(blocks, [false; 8]);
+    }
+
+    #[test]
+    fn loud_block_turns_on_and_release_turns_off() {
+        let mut d = MicDetector::new(RATE, 0.1, 0.03);
+        let mut samples = vec![0.0; BLOCK * 2];
+        samples.extend(std::iter::repeat_n(0.5, BLOCK));
+        // The 8 ms release needs ~16 ms (8 blocks) to fall below the off
+        // threshold, so give it a few blocks of margin.
+        samples.extend(std::iter::repeat_n(0.0, BLOCK * 12));
+        let blocks = run(&mut d, &samples);
+        assert_eq!(&blocks[..3], [false, false, true]);
+        assert!(!d.level(), "envelope released below the off threshold");
+        assert!(!blocks[blocks.len() - 1]);
+    }
+
+    #[test]
+    fn hysteresis_holds_between_thresholds() {
+        let mut d = MicDetector::new(RATE, 0.1, 0.05);
+        run(&mut d, &[0.2; BLOCK]);
+        assert!(d.level());
+        // Between off (0.05) and on (0.1): stays on.
+        let blocks = run(&mut d, &[0.07; BLOCK * 4]);
+        assert!(blocks.iter().all(|&b| b));
+        // A fresh detector never turns on at that level.
+        let mut quiet = MicDetector::new(RATE, 0.1, 0.05);
+        assert!(run(&mut quiet, &[0.07; BLOCK * 4]).iter().all(|&b| !b));
+    }
+
+    #[test]
+    fn bad_config_is_clamped_instead_of_panicking() {
+        let d = MicDetector::new(RATE, -0.5, 0.03);
+        assert!((d.threshold - 0.001).abs() < f32::EPSILON);
+        assert!((d.hysteresis - 0.001).abs() < f32::EPSILON);
+
+        let d = MicDetector::new(RATE, f32::NAN, f32::NAN);
+        assert!((d.threshold - 1.0).abs() < f32::EPSILON);
+        assert!(d.hysteresis.abs() < f32::EPSILON);
+
+        let d = MicDetector::new(RATE, 0.1, -1.0);
+        assert!(d.hysteresis.abs() < f32::EPSILON);
+        let mut d = MicDetector::new(RATE, 0.1, 5.0);
+        assert!((d.hysteresis - 0.1).abs() < f32::EPSILON);
+        // Still a working trigger: silence is off, a loud block is on.
+        assert_eq!(run(&mut d, &[0.0; BLOCK]), [false]);
+        assert_eq!(run(&mut d, &[0.5; BLOCK]), [true]);
+    }
+
+    #[test]
+    fn pattern_orders_oldest_block_first() {
+        // Blocks on, off x7: the oldest (on) lands in bit 0.
+        let mut h = 0;
+        for on in [true, false, false, false, false, false, false, false] {
+            h = push_block(h, on);
+        }
+        assert_eq!(pattern_from_history(h), 0b0000_0001);
+        h = push_block(h, true);
+        assert_eq!(pattern_from_history(h), 0b1000_0000);
+    }
+}
@@ -3,9 +3,9 @@
 //! This is the frontend plumbing on top of the deterministic movie CORE
 //! that landed in Sprint 4.1 (`rustynes_core::{Movie, MovieRecorder,
 //! MoviePlayer, StartPoint}`). The core is caller-driven: the recorder's
-//! `capture` reads `Nes::buttons(0/1)` and must be called AFTER the
+//! `capture` reads the held input off `Nes` and must be called AFTER the
 //! frontend's `set_buttons` and BEFORE `run_frame`; the player's
-//! `apply_next` sets the buttons itself and must be called BEFORE
+//! `apply_next` applies the recorded frame itself and must be called BEFORE
 //! `run_frame`. This module wires those two hooks into the frontend's
 //! per-frame produce path (`App::produce_one_frame`) and tracks the
 //! record / play / idle mode for the egui status indicator.
@@ -24,7 +24,7 @@
 //!   `app.rs`. The build still compiles on wasm32 — this module is
 //!   target-agnostic and holds no native-only types.
 
-use rustynes_core::{Movie, MovieRecorder, Nes};
+use rustynes_core::{DeviceInput, Movie, MovieCommand, MovieRecorder, Nes, UnrecordableDevice};
 
 /// A read-only port-topology + timebase snapshot for the Replay / TAS window.
 ///
@@ -85,15 +85,21 @@ pub struct MovieUi {
     /// `rustynes_core::MoviePlayer` because the player borrows the movie (a
     /// self-referential field would need `Pin`/unsafe). Applying the
     /// current frame inline — reading `movie.frames[cursor]` and calling
-    /// `set_buttons` exactly as `MoviePlayer::apply_next` does — is
+    /// `FrameInput::apply` exactly as `MoviePlayer::apply_next` does — is
     /// equivalent and keeps the playback state owned + `Send`.
     playback: Option<Playback>,
+    /// v2.5.0 — a recording [`Self::before_frame`] ended because the
+    /// attached devices stopped fitting a movie frame, with the reason;
+    /// waiting for [`Self::take_halted`].
+    halted: Option<(Movie, UnrecordableDevice)>,
 }
 
 /// Owned movie + playback cursor.
 struct Playback {
     movie: Movie,
     cursor: usize,
+    /// [`Movie::is_extended`], computed once at load (v2.5.0).
+    extended: bool,
 }
 
 impl MovieUi {
@@ -147,10 +153,20 @@ impl MovieUi {
     /// carries a rolling hash of its video output that anyone can re-derive with
     /// `rustynes verify`. Callers should pass `false` while run-ahead is active
     /// — see [`Self::after_frame`] for why.
-    pub fn start_recording_power_on(&mut self, nes: &mut Nes, attest: bool) {
+    ///
+    /// # Errors
+    ///
+    /// Refuses, leaving everything untouched, when the attached devices
+    /// can't be recorded ([`DeviceInput::recordable`]).
+    pub fn start_recording_power_on(
+        &mut self,
+        nes: &mut Nes,
+        attest: bool,
+    ) -> Result<(), UnrecordableDevice> {
         if self.recorder.is_some() {
-            return;
+            return Ok(());
         }
+        DeviceInput::recordable(nes)?;
         self.playback = None;
         nes.power_cycle();
         let mut rec = MovieRecorder::power_on(nes);
@@ -158,19 +174,30 @@ impl MovieUi {
             rec.enable_attestation();
         }
         self.recorder = Some(rec);
+        Ok(())
     }
 
     /// Start recording a *branch* from `nes`'s current state (embeds a
     /// save-state start point). Stops any in-progress playback. Used both
     /// by the dedicated branch gesture and when the user starts recording
     /// mid-game without wanting a power-on reset.
-    pub fn start_recording_branch(&mut self, nes: &Nes, attest: bool) {
+    ///
+    /// # Errors
+    ///
+    /// As [`Self::start_recording_power_on`].
+    pub fn start_recording_branch(
+        &mut self,
+        nes: &Nes,
+        attest: bool,
+    ) -> Result<(), UnrecordableDevice> {
+        DeviceInput::recordable(nes)?;
         self.playback = None;
         let mut rec = MovieRecorder::from_current_state(nes);
         if attest {
             rec.enable_attestation();
         }
         self.recorder = Some(rec);
+        Ok(())
     }
 
     /// Finish recording and return the completed [`Movie`] for the caller
@@ -179,6 +206,14 @@ impl MovieUi {
         self.recorder.take().map(MovieRecorder::finish)
     }
 
+    /// v2.5.0 — the recording [`Self::before_frame`] stopped because a device
+    /// it can't record was attached, with the reason. The movie holds every
+    /// frame up to that point; the host should tell the user and offer to
+    /// save it.
+    pub const fn take_halted(&mut self) -> Option<(Movie, UnrecordableDevice)> {
+        self.halted.take()
+    }
+
     /// v1.6.0 B1 — clone the movie currently being played back (for export to an
     /// external `.fm2` / `.bk2`). Returns `None` if not playing.
     #[must_use]
@@ -191,7 +226,26 @@ impl MovieUi {
     /// any in-progress recording.
     pub fn start_playback(&mut self, movie: Movie) {
         self.recorder = None;
-        self.playback = Some(Playback { movie, cursor: 0 });
+        let extended = movie.is_extended();
+        self.playback = Some(Playback {
+            movie,
+            cursor: 0,
+            extended,
+        });
+    }
+
+    /// v2.5.0 — record a console command (reset, power cycle, disk swap, Vs.
+    /// coin) the host is about to carry out, so the next captured frame
+    /// replays it. The recording holds one command per frame: `false`, with
+    /// nothing recorded, when the next frame already has one — the host must
+    /// then hold the action back rather than run it unrecorded. Always `true`
+    /// when not recording.
+    #[must_use]
+    pub const fn record_command(&mut self, command: MovieCommand) -> bool {
+        match self.recorder.as_mut() {
+            Some(rec) => rec.queue_command(command),
+            None => true,
+        }
     }
 
     /// v2.3.2 "Lucid" — drop the in-progress attestation, keeping the recording.
@@ -263,8 +317,7 @@ impl MovieUi {
             let Some(input) = pb.movie.frames.get(i).copied() else {
                 break;
             };
-            nes.set_buttons(0, input.p1);
-            nes.set_buttons(1, input.p2);
+            input.apply(nes, pb.extended);
             nes.run_frame();
         }
         pb.cursor = target;
@@ -275,7 +328,9 @@ impl MovieUi {
     /// frontend's live `set_buttons` and BEFORE `run_frame`.
     ///
     /// - **Recording**: captures the inputs currently held on `nes` (the
-    ///   live ones the frontend just latched).
+    ///   live ones the frontend just latched). If the attached devices no
+    ///   longer fit a movie frame, the recording ends before this frame
+    ///   instead (see [`Self::take_halted`]).
     /// - **Playing**: overrides the live input with the movie's recorded
     ///   input for this frame. Returns `false` when the movie is exhausted
     ///   so the caller can stop playback and hand control back to live
@@ -284,20 +339,25 @@ impl MovieUi {
     /// Returns `true` for the idle and recording paths.
     pub fn before_frame(&mut self, nes: &mut Nes) -> bool {
         if let Some(rec) = self.recorder.as_mut() {
+            if let Err(why) = DeviceInput::recordable(nes) {
+                if let Some(rec) = self.recorder.take() {
+                    self.halted = Some((rec.finish(), why));
+                }
+                return true;
+            }
             rec.capture(nes);
             return true;
         }
         if let Some(pb) = self.playback.as_mut() {
             // Apply this frame's recorded input, mirroring
             // `MoviePlayer::apply_next` but against our owned movie +
-            // cursor: read the frame at the cursor and drive `set_buttons`,
-            // then advance. At end-of-movie return `false` (without
-            // applying anything) so the caller stops playback.
+            // cursor: read the frame at the cursor and apply it, then
+            // advance. At end-of-movie return `false` (without applying
+            // anything) so the caller stops playback.
             let Some(input) = pb.movie.frames.get(pb.cursor).copied() else {
                 return false;
             };
-            nes.set_buttons(0, input.p1);
-            nes.set_buttons(1, input.p2);
+            input.apply(nes, pb.extended);
             pb.cursor += 1;
             return true;
         }
@@ -349,7 +409,7 @@ mod tests {
     fn record_then_finish_yields_movie() {
         let mut nes = Nes::from_rom(&synth_nrom()).unwrap();
         let mut ui = MovieUi::default();
-        ui.start_recording_power_on(&mut nes, true);
+        ui.start_recording_power_on(&mut nes, true).unwrap();
         assert_eq!(ui.mode(), MovieMode::Recording);
         for _ in 0..5 {
             assert!(ui.before_frame(&mut nes));
@@ -362,6 +422,55 @@ mod tests {
         assert_eq!(ui.mode(), MovieMode::Idle);
     }
 
+    /// A command issued in a frame whose slot is taken is refused, not
+    /// swapped in over the first; the deferring host gets it in next frame.
+    #[test]
+    fn a_second_command_in_one_frame_waits_for_the_next() {
+        let mut nes = Nes::from_rom(&synth_nrom()).unwrap();
+        let mut ui = MovieUi::default();
+        ui.start_recording_power_on(&mut nes, false).unwrap();
+        assert!(ui.record_command(MovieCommand::SoftReset));
+        nes.reset();
+        assert!(!ui.record_command(MovieCommand::PowerCycle));
+        assert!(!ui.record_command(MovieCommand::ClearCoin));
+        assert!(ui.before_frame(&mut nes));
+        nes.run_frame();
+        assert!(ui.record_command(MovieCommand::ClearCoin));
+        assert!(ui.before_frame(&mut nes));
+        nes.run_frame();
+        let movie = ui.finish_recording().expect("a movie");
+        assert_eq!(movie.frames[0].command, MovieCommand::SoftReset);
+        assert_eq!(movie.frames[1].command, MovieCommand::ClearCoin);
+    }
+
+    /// v2.5.0 — a device a movie frame can't hold is refused up front, and
+    /// attaching one mid-recording ends the recording with what it has
+    /// rather than recording frames without it.
+    #[test]
+    fn an_unrecordable_device_refuses_or_halts_the_recording() {
+        let mut nes = Nes::from_rom(&synth_nrom()).unwrap();
+        let mut ui = MovieUi::default();
+        nes.set_hori_track(1, 0, 0, 0);
+        assert_eq!(
+            ui.start_recording_power_on(&mut nes, false),
+            Err(UnrecordableDevice::Unsupported("Hori Track"))
+        );
+        assert!(!ui.is_recording());
+
+        let mut nes = Nes::from_rom(&synth_nrom()).unwrap();
+        ui.start_recording_power_on(&mut nes, false).unwrap();
+        assert!(ui.before_frame(&mut nes));
+        nes.run_frame();
+        nes.set_zapper(1, 0, 0, false);
+        nes.set_power_pad(0, 0);
+        assert!(ui.before_frame(&mut nes));
+        assert!(!ui.is_recording());
+        let (movie, why) = ui.take_halted().expect("a halted recording");
+        assert_eq!(why, UnrecordableDevice::BothPorts);
+        assert_eq!(movie.len(), 1);
+        assert!(ui.take_halted().is_none());
+    }
+
     /// v2.3.4 (issue #360) — a recorded movie must actually VERIFY.
     ///
     /// Every recording test above drives `before_frame` + `run_frame` and then
@@ -380,7 +489,7 @@ mod tests {
         let rom = synth_nrom();
         let mut nes = Nes::from_rom(&rom).unwrap();
         let mut ui = MovieUi::default();
-        ui.start_recording_power_on(&mut nes, true);
+        ui.start_recording_power_on(&mut nes, true).unwrap();
         for i in 0..12u8 {
             // Vary the input per frame. The fixture ROM never reads the
             // controller, so this cannot change the VIDEO -- which is exactly
@@ -442,7 +551,7 @@ mod tests {
         let rom = synth_nrom();
         let mut nes = Nes::from_rom(&rom).unwrap();
         let mut ui = MovieUi::default();
-        ui.start_recording_power_on(&mut nes, true);
+        ui.start_recording_power_on(&mut nes, true).unwrap();
         for _ in 0..4 {
             assert!(ui.before_frame(&mut nes));
             nes.run_frame(); // deliberately NOT attested
@@ -470,7 +579,7 @@ mod tests {
         // Record a short movie first.
         let mut nes = Nes::from_rom(&rom).unwrap();
         let mut ui = MovieUi::default();
-        ui.start_recording_power_on(&mut nes, true);
+        ui.start_recording_power_on(&mut nes, true).unwrap();
         for _ in 0..3 {
             ui.before_frame(&mut nes);
             let fb = nes.run_frame().to_vec();
@@ -505,7 +614,7 @@ mod tests {
         let rom = synth_nrom();
         let mut nes = Nes::from_rom(&rom).unwrap();
         let mut ui = MovieUi::default();
-        ui.start_recording_power_on(&mut nes, true);
+        ui.start_recording_power_on(&mut nes, true).unwrap();
         for _ in 0..10 {
             ui.before_frame(&mut nes);
             let fb = nes.run_frame().to_vec();
@@ -542,7 +651,7 @@ mod tests {
         let mut ui = MovieUi::default();
         // Idle: seek is a no-op.
         assert!(!ui.seek_playback(&mut nes, 5));
-        ui.start_recording_power_on(&mut nes, true);
+        ui.start_recording_power_on(&mut nes, true).unwrap();
         for _ in 0..3 {
             ui.before_frame(&mut nes);
             let fb = nes.run_frame().to_vec();
@@ -564,7 +673,7 @@ mod tests {
         let mut ui = MovieUi::default();
 
         // Make a 2-frame movie to play.
-        ui.start_recording_power_on(&mut nes, true);
+        ui.start_recording_power_on(&mut nes, true).unwrap();
         ui.before_frame(&mut nes);
         let fb = nes.run_frame().to_vec();
         ui.after_frame(&fb);
@@ -578,7 +687,7 @@ mod tests {
         ui.start_playback(movie);
         assert!(ui.is_playing());
         // Starting a recording must drop playback.
-        ui.start_recording_branch(&replay, true);
+        ui.start_recording_branch(&replay, true).unwrap();
         assert!(ui.is_recording());
         assert!(!ui.is_playing());
         // Starting playback again must drop the recorder.
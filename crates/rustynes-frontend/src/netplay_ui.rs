@@ -40,13 +40,17 @@
 //! The **host is player 0 (P1, `$4016`)**; the joiner is player 1 (P2,
 //! `$4017`). Both peers feed their LOCAL keyboard/gamepad as `player1()`;
 //! the session routes it to the correct port via `SessionConfig.local_player`.
+//! The link is fixed for the match, so a departed joiner ends it: this UI
+//! never [vacates](RollbackSession::vacate_player) the slot for a newcomer.
+//! Spectators do join live (`SpectatorConfig::join_live`).
 
 use std::net::SocketAddr;
 
-use rustynes_core::{Buttons, Nes};
+use rustynes_core::{Buttons, DeviceInput, Nes};
 use rustynes_netplay::{
-    AdvanceOutcome, ConnectionState, DisconnectReason, NetplayConnection, NetplayError,
-    RollbackSession, SessionConfig, SpectatorConfig, SpectatorSession, UdpTransport,
+    AdvanceOutcome, ChatError, ChatLine, ConnectionState, DelayMode, DelayTuning, DisconnectReason,
+    MatchRecorder, NetSettings, NetplayConnection, NetplayError, RollbackSession, SecureChannel,
+    SessionConfig, SessionKey, SessionSave, SpectatorConfig, SpectatorSession, UdpTransport,
 };
 
 /// Default local UDP port a host binds when none is specified.
@@ -113,6 +117,11 @@ pub struct NetplayTick {
     /// `true` if the emulator advanced a frame this tick (present it).
     /// `false` while connecting, on error, or on a time-sync stall.
     pub produced_frame: bool,
+    /// `true` if the session authored this tick's local input. A stalled
+    /// tick's input is dropped (its frame was already authored), so input
+    /// that accumulates between ticks — mouse motion — must be kept for the
+    /// next one.
+    pub authored_input: bool,
 }
 
 impl NetplayTick {
@@ -120,6 +129,7 @@ impl NetplayTick {
     const INACTIVE: Self = Self {
         active: false,
         produced_frame: false,
+        authored_input: false,
     };
 }
 
@@ -152,14 +162,36 @@ pub struct NetplayUi {
     rom_hash: [u8; 32],
     /// Cached status for the HUD, refreshed each `tick`.
     status: NetplayStatus,
-    /// Session config (input delay, rollback window, checksum interval). The
-    /// `local_player` field is overwritten at connect from `is_host`.
+    /// Session config (input delay, rollback window, checksum interval, desync
+    /// recovery — on, input-delay suggestions — on). The `local_player` field
+    /// is overwritten at connect from `is_host`.
     config: SessionConfig,
     /// Extra delayed-stream buffer depth (frames) applied when *spectating* a
     /// match — a broadcast / anti-spoiler delay layered on top of the natural
     /// spectator lag. `0` (default) shows confirmed frames immediately. See
     /// [`SpectatorConfig::delay_frames`](rustynes_netplay::SpectatorConfig::delay_frames).
     spectator_delay_frames: u32,
+    /// The emulation settings the session runs under: our own, or the
+    /// host's once a joiner adopted them. Re-applied around the cold boot
+    /// that starts the session.
+    settings: Option<NetSettings>,
+    /// The user's own emulation settings, kept when a joiner adopted the
+    /// host's for the session so the App can put them back on leave (see
+    /// [`take_restore_settings`](NetplayUi::take_restore_settings)).
+    restore_settings: Option<NetSettings>,
+    /// The save RAM / FDS disk this peer had before the session, put back on
+    /// leave so the session's copy never reaches its real saves (see
+    /// [`take_restore_save`](NetplayUi::take_restore_save)).
+    restore_save: Option<SessionSave>,
+    /// A host keeps the session's save writes instead of restoring its own.
+    write_back_save: bool,
+    /// Record the next session's confirmed stream as an attested movie.
+    record_match: bool,
+    /// The last session's recording, kept past its end for
+    /// [`take_recording`](NetplayUi::take_recording).
+    recording: Option<MatchRecorder>,
+    /// The room passphrase; `None` plays unsecured.
+    passphrase: Option<String>,
 }
 
 impl Default for NetplayUi {
@@ -169,8 +201,22 @@ impl Default for NetplayUi {
             is_host: false,
             rom_hash: [0u8; 32],
             status: NetplayStatus::default(),
-            config: SessionConfig::default(),
+            config: SessionConfig {
+                desync_recovery: true,
+                delay_tuning: DelayTuning {
+                    mode: DelayMode::Propose,
+                    ..DelayTuning::default()
+                },
+                ..SessionConfig::default()
+            },
             spectator_delay_frames: 0,
+            settings: None,
+            restore_settings: None,
+            restore_save: None,
+            write_back_save: false,
+            record_match: false,
+            recording: None,
+            passphrase: None,
         }
     }
 }
@@ -193,6 +239,85 @@ impl NetplayUi {
         self.spectator_delay_frames = frames;
     }
 
+    /// Whether a host keeps the save RAM / disk writes a session made once it
+    /// ends. Off by default: every peer gets its pre-session save back from
+    /// [`take_restore_save`](Self::take_restore_save). Takes effect on the
+    /// next session start.
+    pub const fn set_write_back_save(&mut self, write_back: bool) {
+        self.write_back_save = write_back;
+    }
+
+    /// Whether the next session (played or watched) is recorded as an
+    /// attested movie, handed over by [`take_recording`](Self::take_recording)
+    /// once it ends. Takes effect on the next session start.
+    pub const fn set_record_match(&mut self, record: bool) {
+        self.record_match = record;
+    }
+
+    /// Whether a host adapts the match's input delay to the link by itself
+    /// ([`DelayMode::Auto`]) or only suggests one for
+    /// [`schedule_input_delay`](Self::schedule_input_delay)
+    /// ([`DelayMode::Propose`]). Takes effect on the next session start.
+    pub const fn set_adaptive_input_delay(&mut self, adaptive: bool) {
+        self.config.delay_tuning.mode = if adaptive {
+            DelayMode::Auto
+        } else {
+            DelayMode::Propose
+        };
+    }
+
+    /// Host only: switch every peer of the running match to `delay` frames
+    /// of input delay, from a frame shortly ahead. Returns that frame, or
+    /// `None` when not hosting a match.
+    pub fn schedule_input_delay(&mut self, delay: u32) -> Option<u32> {
+        match &mut self.state {
+            NetplayState::InGame(session) => session.schedule_input_delay(delay),
+            _ => None,
+        }
+    }
+
+    /// Send a chat line to the other players of the running match. Returns
+    /// `Ok(false)` (sending nothing) when no match is running — a spectator
+    /// watches silently.
+    ///
+    /// # Errors
+    ///
+    /// The session's [`ChatError`] for a line it refuses (empty, too long,
+    /// a control character, or over the rate limit).
+    pub fn send_chat(&mut self, text: &str) -> Result<bool, ChatError> {
+        match &mut self.state {
+            NetplayState::InGame(session) => session.send_chat(text).map(|()| true),
+            _ => Ok(false),
+        }
+    }
+
+    /// The chat lines received from the other players since the last call.
+    pub fn take_chat(&mut self) -> Vec<ChatLine> {
+        match &mut self.state {
+            NetplayState::InGame(session) => session.take_chat(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The room passphrase the next session is secured with. Every peer must
+    /// enter the same one; traffic is then encrypted and authenticated, and
+    /// anything sent without the key is dropped. Empty plays unsecured.
+    /// Takes effect on the next session start.
+    pub fn set_passphrase(&mut self, passphrase: &str) {
+        self.passphrase = (!passphrase.is_empty()).then(|| passphrase.to_owned());
+    }
+
+    /// A fresh sealing channel for the next session, keyed by the passphrase
+    /// and the ROM being played, or `None` when the session is unsecured.
+    fn secure_channel(&self) -> std::io::Result<Option<SecureChannel>> {
+        self.passphrase
+            .as_deref()
+            .map(|passphrase| {
+                SecureChannel::random(&SessionKey::derive(passphrase, &self.rom_hash))
+            })
+            .transpose()
+    }
+
     /// The configured spectator delayed-stream buffer depth (frames).
     #[must_use]
     pub const fn spectator_delay_frames(&self) -> u32 {
@@ -245,21 +370,61 @@ impl NetplayUi {
     /// The host no longer needs to pre-enter the joiner's address — it just
     /// shares its own listening `IP:port` and the joiner dials in (see
     /// [`NetplayConnection::host`]).
-    pub fn start_host(&mut self, local_port: u16, num_players: u8, rom_hash: [u8; 32]) {
+    ///
+    /// `settings` are the host's emulation settings; they are authoritative
+    /// for the session and pushed to any joiner whose settings differ. So is
+    /// `save`, the host's battery RAM / FDS disk, streamed to a joiner whose
+    /// save differs.
+    pub fn start_host(
+        &mut self,
+        local_port: u16,
+        num_players: u8,
+        rom_hash: [u8; 32],
+        settings: NetSettings,
+        save: SessionSave,
+    ) {
         let local = SocketAddr::from(([0, 0, 0, 0], local_port));
         self.is_host = true;
         self.rom_hash = rom_hash;
         self.config.num_players = num_players.clamp(2, 4);
         self.config.local_player = 0; // host = player 0.
+        self.config.settings_digest = settings.digest();
+        self.config.save_digest = save.digest();
+        self.settings = Some(settings.clone());
+        self.restore_settings = None;
+        self.restore_save = None;
+        let channel = match self.secure_channel() {
+            Ok(channel) => channel,
+            Err(e) => return self.fail(format!("secure channel failed: {e}")),
+        };
         match NetplayConnection::host(local, rom_hash) {
-            Ok(conn) => self.enter_connecting(conn, true),
+            Ok(conn) => {
+                let conn = conn.with_settings(settings).with_save(save);
+                self.enter_connecting(secure_connection(conn, channel), true);
+            }
             Err(e) => self.fail(format!("host bind failed: {e}")),
         }
     }
 
     /// Join a session hosted at `remote`: bind an ephemeral local port and
     /// begin the handshake as player 1 (P2). Any previous session is dropped.
-    pub fn start_join(&mut self, remote: SocketAddr, rom_hash: [u8; 32]) {
+    ///
+    /// `settings` are the joiner's own emulation settings. If they differ
+    /// from the host's, the host's are adopted for the session (the user's
+    /// are handed back by [`take_restore_settings`](Self::take_restore_settings)
+    /// after [`leave`](Self::leave)).
+    ///
+    /// `save` is the joiner's battery RAM / FDS disk. If it differs from the
+    /// host's, `use_host_save` plays the session on a copy of the host's;
+    /// otherwise the match is refused.
+    pub fn start_join(
+        &mut self,
+        remote: SocketAddr,
+        rom_hash: [u8; 32],
+        settings: NetSettings,
+        save: SessionSave,
+        use_host_save: bool,
+    ) {
         let local = SocketAddr::from(([0, 0, 0, 0], 0));
         self.is_host = false;
         self.rom_hash = rom_hash;
@@ -269,8 +434,24 @@ impl NetplayUi {
         // assignment is the deferred follow-up.)
         self.config.num_players = 2;
         self.config.local_player = 1; // joiner = player 1.
+        self.config.settings_digest = settings.digest();
+        self.config.save_digest = save.digest();
+        self.settings = Some(settings.clone());
+        self.restore_settings = None;
+        self.restore_save = None;
+        let channel = match self.secure_channel() {
+            Ok(channel) => channel,
+            Err(e) => return self.fail(format!("secure channel failed: {e}")),
+        };
         match NetplayConnection::connect(local, remote, rom_hash) {
-            Ok(conn) => self.enter_connecting(conn, false),
+            Ok(conn) => {
+                let conn = conn
+                    .with_settings(settings)
+                    .adopting_host_settings(true)
+                    .adopting_host_save(use_host_save)
+                    .with_save(save);
+                self.enter_connecting(secure_connection(conn, channel), false);
+            }
             Err(e) => self.fail(format!("connect failed: {e}")),
         }
     }
@@ -282,38 +463,70 @@ impl NetplayUi {
     /// perturb the match it is watching (the determinism-safety contract).
     ///
     /// It announces itself once with a single `Sync` so a spectator-aware host
-    /// learns where to relay the input stream; after that it is purely
-    /// poll-only. Any previous session is dropped.
+    /// learns where to relay the input stream, then joins live: it asks the
+    /// host for its confirmed state and starts from the match's present
+    /// rather than replaying it from cold boot. Beyond that join handshake it
+    /// is purely poll-only. Any previous session is dropped.
     ///
     /// The host-side spectator-broadcast wiring + the `deploy/` relay config are
     /// a documented maintainer-manual carryover (like the live 2-4p host/TURN
     /// matrix) — the frontend driver here is exercised by the loopback unit
     /// test. The local emulator is power-cycled to the deterministic cold-boot
     /// so frame 0 matches the players' canonical timeline.
-    pub fn start_spectate(&mut self, remote: SocketAddr, rom_hash: [u8; 32]) {
+    ///
+    /// `settings` are the spectator's own; a recording of the match is
+    /// stamped with them.
+    pub fn start_spectate(
+        &mut self,
+        remote: SocketAddr,
+        rom_hash: [u8; 32],
+        settings: &NetSettings,
+    ) {
         let local = SocketAddr::from(([0, 0, 0, 0], 0));
         self.is_host = false;
         self.rom_hash = rom_hash;
+        // A spectator plays back under its own settings; it never negotiates.
+        // Its save is overwritten by the host's live state anyway.
+        self.settings = None;
+        self.restore_settings = None;
+        self.restore_save = None;
+        self.recording = None;
         // A spectator does not own a controller port; the count is adopted from
         // the host's roster (defaults to 2 until then).
         self.config.num_players = 2;
+        let channel = match self.secure_channel() {
+            Ok(channel) => channel,
+            Err(e) => return self.fail(format!("secure channel failed: {e}")),
+        };
         match UdpTransport::bind(local, remote) {
-            Ok(mut transport) => {
+            Ok(transport) => {
+                use rustynes_netplay::{NetMessage, Transport as _};
+                let mut transport = match channel {
+                    Some(channel) => transport.secured(channel),
+                    None => transport,
+                };
                 // One-shot self-announce so a spectator-aware host can register
                 // us and start relaying the stream. After this we never send.
-                use rustynes_netplay::{NetMessage, Transport as _};
                 transport.send(&NetMessage::Sync {
                     magic: NetMessage::SYNC_MAGIC,
                     rom_hash,
+                    // Spectators play back whatever the host runs; they never
+                    // negotiate settings or saves.
+                    settings: 0,
+                    save: 0,
                 });
-                let session = SpectatorSession::new(
+                let mut session = SpectatorSession::new(
                     SpectatorConfig {
                         num_players: self.config.num_players,
                         delay_frames: self.spectator_delay_frames,
+                        join_live: true,
                     },
                     transport,
                     rom_hash,
                 );
+                if self.record_match {
+                    session.start_recording(settings);
+                }
                 self.state = NetplayState::Spectating(Box::new(session));
                 self.status = NetplayStatus {
                     phase: NetplayPhase::Spectating,
@@ -327,6 +540,7 @@ impl NetplayUi {
 
     /// Shared post-bind transition into the `Connecting` phase.
     fn enter_connecting(&mut self, conn: NetplayConnection, is_host: bool) {
+        self.recording = None;
         self.state = NetplayState::Connecting(Box::new(conn));
         self.status = NetplayStatus {
             phase: NetplayPhase::Connecting,
@@ -335,8 +549,42 @@ impl NetplayUi {
         };
     }
 
+    /// The user's own emulation settings to re-apply after
+    /// [`leave`](Self::leave), if the last join adopted the host's. Taking
+    /// them clears the slot.
+    pub const fn take_restore_settings(&mut self) -> Option<NetSettings> {
+        self.restore_settings.take()
+    }
+
+    /// The save RAM / FDS disk this peer had before the last session, to
+    /// re-apply after [`leave`](Self::leave). `None` for a host that keeps
+    /// the session's writes (see [`set_write_back_save`](Self::set_write_back_save)).
+    /// Taking it clears the slot.
+    pub const fn take_restore_save(&mut self) -> Option<SessionSave> {
+        self.restore_save.take()
+    }
+
+    /// The last session's match recording, once it has ended (left or
+    /// failed). Taking it clears the slot.
+    pub const fn take_recording(&mut self) -> Option<MatchRecorder> {
+        self.recording.take()
+    }
+
+    /// Keep the live session's recording, if any, before the session goes.
+    fn keep_recording(&mut self) {
+        let recording = match &mut self.state {
+            NetplayState::InGame(session) => session.take_recording(),
+            NetplayState::Spectating(session) => session.take_recording(),
+            _ => None,
+        };
+        if recording.is_some() {
+            self.recording = recording;
+        }
+    }
+
     /// Tear the session down and return to single-player. No-op if idle.
     pub fn leave(&mut self) {
+        self.keep_recording();
         self.state = NetplayState::Idle;
         self.status = NetplayStatus::default();
     }
@@ -355,10 +603,22 @@ impl NetplayUi {
     ///   `produced_frame` (false = time-sync stall → skip rendering).
     /// - **Error**: holds (no emulation) until [`leave`](Self::leave).
     pub fn tick(&mut self, nes: &mut Nes, local_buttons: Buttons) -> NetplayTick {
+        self.tick_with_device(nes, local_buttons, DeviceInput::None)
+    }
+
+    /// [`Self::tick`] with the local expansion device's state for this frame.
+    /// The session carries it only when it drives this player's own port
+    /// (the P2 seat drives the `$4017` device; see `rustynes_netplay::device`).
+    pub fn tick_with_device(
+        &mut self,
+        nes: &mut Nes,
+        local_buttons: Buttons,
+        device: DeviceInput,
+    ) -> NetplayTick {
         match &mut self.state {
             NetplayState::Idle => NetplayTick::INACTIVE,
             NetplayState::Connecting(_) => self.tick_connecting(nes),
-            NetplayState::InGame(_) => self.tick_in_game(nes, local_buttons),
+            NetplayState::InGame(_) => self.tick_in_game(nes, local_buttons, device),
             // A spectator ignores `local_buttons` — it never authors input.
             NetplayState::Spectating(_) => self.tick_spectating(nes),
             NetplayState::Error(msg) => {
@@ -367,6 +627,7 @@ impl NetplayUi {
                 NetplayTick {
                     active: true,
                     produced_frame: false,
+                    authored_input: false,
                 }
             }
         }
@@ -394,6 +655,7 @@ impl NetplayUi {
                 NetplayTick {
                     active: true,
                     produced_frame: false,
+                    authored_input: false,
                 }
             }
             ConnectionState::Synced => {
@@ -404,15 +666,57 @@ impl NetplayUi {
                 // phase) so the session's frame-0 checkpoint is byte-identical on
                 // every peer; otherwise the first confirmed-frame checksum trips
                 // a desync immediately.
+                //
+                // A joiner that adopted the host's settings switches to them
+                // (keeping its own for after the session). Every peer applies
+                // the session's settings around the cold boot: before it so
+                // the power-on RAM fill and palette match, and again after it
+                // because the cold boot drops OAM decay, the overclock and the
+                // expansion devices (see `NetSettings::apply`).
+                if let Some(host) = conn.adopted_settings() {
+                    self.restore_settings = Some(NetSettings::capture(nes));
+                    self.config.settings_digest = host.digest();
+                    self.settings = Some(host.clone());
+                }
+                if let Some(settings) = &self.settings {
+                    settings.apply(nes);
+                }
+                // The session plays on a copy of the save: ours, or the
+                // host's if this joiner adopted it. Keep ours for after the
+                // session. The cold boot clears the battery RAM, so every
+                // peer loads the session's copy back after it.
+                let own = SessionSave::capture(nes);
+                let save = conn.adopted_save().cloned().unwrap_or_else(|| own.clone());
+                self.config.save_digest = save.digest();
+                self.restore_save = (!(self.is_host && self.write_back_save)).then_some(own);
                 nes.power_cycle();
+                if let Some(settings) = &self.settings {
+                    settings.apply(nes);
+                }
+                if !save.apply(nes) {
+                    self.fail("the host's save does not fit this game".to_string());
+                    return NetplayTick {
+                        active: true,
+                        produced_frame: false,
+                        authored_input: false,
+                    };
+                }
                 // Hand the bound + handshaken transport to a fresh session.
                 let transport = conn.into_transport();
-                let session = RollbackSession::new(self.config, transport, self.rom_hash);
+                let mut session = RollbackSession::new(self.config, transport, self.rom_hash);
+                if self.record_match {
+                    let settings = self
+                        .settings
+                        .clone()
+                        .unwrap_or_else(|| NetSettings::capture(nes));
+                    session.start_recording(&settings);
+                }
                 self.state = NetplayState::InGame(Box::new(session));
                 self.status.phase = NetplayPhase::InGame;
                 NetplayTick {
                     active: true,
                     produced_frame: false,
+                    authored_input: false,
                 }
             }
             ConnectionState::Disconnected => {
@@ -420,6 +724,20 @@ impl NetplayUi {
                     Some(DisconnectReason::RomMismatch) => {
                         "peer is running a different ROM".to_string()
                     }
+                    Some(DisconnectReason::SettingsMismatch) => {
+                        let diff: Vec<String> = conn
+                            .settings_diff()
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect();
+                        format!(
+                            "peer uses different emulation settings: {}",
+                            diff.join("; ")
+                        )
+                    }
+                    Some(DisconnectReason::SaveMismatch) => {
+                        "peer starts from a different battery save or FDS disk".to_string()
+                    }
                     Some(DisconnectReason::HandshakeTimeout) => {
                         "handshake timed out (no peer answered)".to_string()
                     }
@@ -432,18 +750,24 @@ impl NetplayUi {
                 NetplayTick {
                     active: true,
                     produced_frame: false,
+                    authored_input: false,
                 }
             }
         }
     }
 
     /// Drive the rollback session one tick: feed the local input and advance.
-    fn tick_in_game(&mut self, nes: &mut Nes, local_buttons: Buttons) -> NetplayTick {
+    fn tick_in_game(
+        &mut self,
+        nes: &mut Nes,
+        local_buttons: Buttons,
+        device: DeviceInput,
+    ) -> NetplayTick {
         let NetplayState::InGame(session) = &mut self.state else {
             unreachable!("tick_in_game only runs in the InGame state");
         };
 
-        session.add_local_input(local_buttons);
+        let authored_input = session.add_local_device_input(local_buttons, device);
         match session.advance(nes) {
             Ok(AdvanceOutcome {
                 produced_frame,
@@ -462,6 +786,7 @@ impl NetplayUi {
                 NetplayTick {
                     active: true,
                     produced_frame,
+                    authored_input,
                 }
             }
             Err(e) => {
@@ -479,6 +804,8 @@ impl NetplayUi {
                         format!("desync at frame {frame} ({kind})")
                     }
                     NetplayError::RomMismatch => "rom mismatch".to_string(),
+                    NetplayError::SettingsMismatch => "emulation settings mismatch".to_string(),
+                    NetplayError::SaveMismatch => "battery save / disk mismatch".to_string(),
                     NetplayError::Restore(ref s) => format!("rollback restore failed: {s}"),
                     // `NetplayError` is `#[non_exhaustive]`; surface any future
                     // variant via its `Display` rather than panicking.
@@ -488,6 +815,7 @@ impl NetplayUi {
                 NetplayTick {
                     active: true,
                     produced_frame: false,
+                    authored_input: false,
                 }
             }
         }
@@ -497,7 +825,8 @@ impl NetplayUi {
 
     /// v1.7.0 H8 — drive the read-only spectator one tick: poll the input
     /// stream and advance the local emulator when the next frame is confirmed.
-    /// Never sends, predicts, or rolls back — so it cannot error. A tick that
+    /// Sends nothing beyond its join handshake and never predicts or rolls
+    /// back — so it cannot error. A tick that
     /// produces no frame is a "waiting for the next confirmed frame" stall (the
     /// caller skips rendering), exactly like the time-sync stall on the player
     /// path.
@@ -515,11 +844,13 @@ impl NetplayUi {
         NetplayTick {
             active: true,
             produced_frame: out.produced_frame,
+            authored_input: false,
         }
     }
 
     /// Transition to the terminal `Error` phase with a message.
     fn fail(&mut self, message: String) {
+        self.keep_recording();
         self.status = NetplayStatus {
             phase: NetplayPhase::Error,
             is_host: self.is_host,
@@ -530,6 +861,14 @@ impl NetplayUi {
     }
 }
 
+/// Seal `conn`'s traffic with `channel`, if the session is secured.
+fn secure_connection(conn: NetplayConnection, channel: Option<SecureChannel>) -> NetplayConnection {
+    match channel {
+        Some(channel) => conn.secured(channel),
+        None => conn,
+    }
+}
+
 /// v1.3.0 Workstream G1 — build the read-only [`NetplayDiagnosticsView`] from a
 /// live session's observational [`DesyncDiagnostics`] + topology. Pure read; it
 /// never mutates the session.
@@ -539,7 +878,7 @@ impl NetplayUi {
 fn diagnostics_view(
     session: &RollbackSession<UdpTransport>,
 ) -> crate::debugger::NetplayDiagnosticsView {
-    use crate::debugger::{CrcCompareView, NetplayDiagnosticsView};
+    use crate::debugger::{CrcCompareView, DelayChangeView, NetplayDiagnosticsView};
     let diag = session.diagnostics();
     let last = diag.last().map(|c| CrcCompareView {
         frame: c.frame,
@@ -562,6 +901,17 @@ fn diagnostics_view(
             same_framebuffer: c.same_framebuffer,
         })
         .collect();
+    let changes = session.input_delay_changes();
+    let delay_changes = changes[changes
+        .len()
+        .saturating_sub(NetplayDiagnosticsView::DELAY_CHANGES_SHOWN)..]
+        .iter()
+        .map(|c| DelayChangeView {
+            frame: c.frame,
+            from: c.from,
+            to: c.to,
+        })
+        .collect();
     NetplayDiagnosticsView {
         num_players: session.num_players(),
         local_player: session.local_player(),
@@ -570,8 +920,15 @@ fn diagnostics_view(
         consecutive_mismatches: diag.consecutive_mismatches(),
         total_compares: diag.total(),
         mismatches: diag.mismatches(),
+        recoveries: diag.recoveries(),
+        last_recovery_frame: diag.last_recovery_frame(),
         last_compare: last,
         recent,
+        input_delay: session.input_delay(),
+        scheduled_input_delay: session.scheduled_input_delay(),
+        suggested_input_delay: session.proposed_input_delay(),
+        round_trip_frames: session.delay_tuner().round_trip_ticks(),
+        delay_changes,
     }
 }
 
@@ -630,7 +987,13 @@ mod tests {
         // assert the simpler property: a successful host start enters
         // Connecting, and leaving returns to Idle cleanly.
         let mut ui = NetplayUi::default();
-        ui.start_host(0, 2, [0u8; 32]);
+        ui.start_host(
+            0,
+            2,
+            [0u8; 32],
+            NetSettings::default(),
+            SessionSave::default(),
+        );
         assert_eq!(ui.phase(), NetplayPhase::Connecting);
         assert!(ui.is_active());
         ui.leave();
@@ -642,12 +1005,17 @@ mod tests {
     /// `InGame` and advance a few frames. This exercises the host-listen /
     /// join handshake promotion (the host ADOPTS the joiner's address from its
     /// first `Sync`, so no joiner address is pre-entered) + the per-frame
-    /// `tick` drive without needing two separate OS processes. (Real 2-player
+    /// `tick` drive without needing two separate OS processes. The joiner
+    /// starts with a different OAM-decay setting and save RAM, plays the
+    /// session on the host's, and is handed its own back after leaving. (Real
+    /// 2-player
     /// play still needs two running instances — but the rollback / transport
     /// correctness is proven by the Stage 1 + 2 suites.)
     #[test]
     fn two_peers_reach_in_game_and_advance() {
-        let rom = synth_nrom();
+        let mut rom = synth_nrom();
+        // Battery-backed PRG-RAM, so the peers' saves are reconciled.
+        rom[6] |= 0x02;
         let hash = *Nes::from_rom(&rom).unwrap().rom_sha256();
 
         // Pick a free port for the host by binding + dropping a probe socket
@@ -658,14 +1026,31 @@ mod tests {
         let host_addr = probe.local_addr().unwrap();
         drop(probe);
 
+        let mut nes_host = Nes::from_rom(&rom).unwrap();
+        let mut nes_join = Nes::from_rom(&rom).unwrap();
+        let host_decay = nes_host.oam_decay_enabled();
+        nes_join.set_oam_decay(!host_decay);
+        nes_host.sram_mut().fill(0xA5);
+        nes_join.sram_mut().fill(0x3C);
+
         let mut host = NetplayUi::default();
-        host.start_host(host_addr.port(), 2, hash);
+        host.start_host(
+            host_addr.port(),
+            2,
+            hash,
+            NetSettings::capture(&nes_host),
+            SessionSave::capture(&nes_host),
+        );
 
         let mut join = NetplayUi::default();
-        join.start_join(host_addr, hash);
-
-        let mut nes_host = Nes::from_rom(&rom).unwrap();
-        let mut nes_join = Nes::from_rom(&rom).unwrap();
+        join.set_record_match(true);
+        join.start_join(
+            host_addr,
+            hash,
+            NetSettings::capture(&nes_join),
+            SessionSave::capture(&nes_join),
+            true,
+        );
 
         // Pump both until both reach InGame or a bounded number of rounds.
         let mut rounds = 0;
@@ -689,6 +1074,196 @@ mod tests {
         }
         assert_ne!(host.phase(), NetplayPhase::Error, "host did not error");
         assert_ne!(join.phase(), NetplayPhase::Error, "joiner did not error");
+
+        assert_eq!(
+            nes_join.oam_decay_enabled(),
+            host_decay,
+            "joiner adopted the host's settings"
+        );
+        assert!(
+            host.take_restore_settings().is_none(),
+            "the host keeps its own settings"
+        );
+        assert_eq!(nes_join.sram()[0], 0xA5, "joiner plays on the host's save");
+        join.leave();
+        let own = join
+            .take_restore_settings()
+            .expect("joiner's own settings kept");
+        own.apply(&mut nes_join);
+        assert_eq!(nes_join.oam_decay_enabled(), !host_decay);
+        let own = join.take_restore_save().expect("joiner's own save kept");
+        assert!(own.apply(&mut nes_join));
+        assert_eq!(nes_join.sram()[0], 0x3C);
+
+        // The joiner's recording of the match replays on a fresh core.
+        let movie = join
+            .take_recording()
+            .expect("the joiner recorded")
+            .finish()
+            .expect("frames were confirmed");
+        let mut verifier = Nes::from_rom(&rom).unwrap();
+        assert!(rustynes_netplay::match_record::prepare_replay(
+            &movie,
+            &mut verifier
+        ));
+        assert!(matches!(
+            movie.verify(&mut verifier),
+            Ok(rustynes_core::VerifyOutcome::Match { .. })
+        ));
+    }
+
+    /// A room passphrase secures the session: peers that share it reach
+    /// `InGame`, while a joiner with the wrong one is never heard by the host
+    /// and both stay `Connecting`.
+    #[test]
+    fn secured_peers_need_the_same_passphrase() {
+        let rom = synth_nrom();
+        let hash = *Nes::from_rom(&rom).unwrap().rom_sha256();
+        for (joiner_pass, connects) in [("open sesame", true), ("open says me", false)] {
+            let probe = UdpSocket::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))).unwrap();
+            let host_addr = probe.local_addr().unwrap();
+            drop(probe);
+
+            let mut nes_host = Nes::from_rom(&rom).unwrap();
+            let mut nes_join = Nes::from_rom(&rom).unwrap();
+            let mut host = NetplayUi::default();
+            host.set_passphrase("open sesame");
+            host.start_host(
+                host_addr.port(),
+                2,
+                hash,
+                NetSettings::capture(&nes_host),
+                SessionSave::capture(&nes_host),
+            );
+            let mut join = NetplayUi::default();
+            join.set_passphrase(joiner_pass);
+            join.start_join(
+                host_addr,
+                hash,
+                NetSettings::capture(&nes_join),
+                SessionSave::capture(&nes_join),
+                false,
+            );
+
+            for _ in 0..300 {
+                if host.phase() == NetplayPhase::InGame && join.phase() == NetplayPhase::InGame {
+                    break;
+                }
+                host.tick(&mut nes_host, Buttons::empty());
+                join.tick(&mut nes_join, Buttons::empty());
+                std::thread::sleep(std::time::Duration::from_millis(2));
+            }
+            let expected = if connects {
+                NetplayPhase::InGame
+            } else {
+                NetplayPhase::Connecting
+            };
+            assert_eq!(
+                host.phase(),
+                expected,
+                "host with joiner pass {joiner_pass:?}"
+            );
+            assert_eq!(join.phase(), expected, "joiner with pass {joiner_pass:?}");
+        }
+    }
+
+    /// Host and join a 2-player match over loopback UDP and tick both until
+    /// it is running. Returns `(host, joiner, host's Nes, joiner's Nes)`.
+    fn loopback_match(rom: &[u8]) -> (NetplayUi, NetplayUi, Nes, Nes) {
+        let hash = *Nes::from_rom(rom).unwrap().rom_sha256();
+        let probe = UdpSocket::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))).unwrap();
+        let host_addr = probe.local_addr().unwrap();
+        drop(probe);
+
+        let mut nes_host = Nes::from_rom(rom).unwrap();
+        let mut nes_join = Nes::from_rom(rom).unwrap();
+        let mut host = NetplayUi::default();
+        host.start_host(
+            host_addr.port(),
+            2,
+            hash,
+            NetSettings::capture(&nes_host),
+            SessionSave::capture(&nes_host),
+        );
+        let mut join = NetplayUi::default();
+        join.start_join(
+            host_addr,
+            hash,
+            NetSettings::capture(&nes_join),
+            SessionSave::capture(&nes_join),
+            false,
+        );
+        for _ in 0..500 {
+            if host.phase() == NetplayPhase::InGame && join.phase() == NetplayPhase::InGame {
+                break;
+            }
+            host.tick(&mut nes_host, Buttons::empty());
+            join.tick(&mut nes_join, Buttons::empty());
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        assert_eq!(host.phase(), NetplayPhase::InGame);
+        assert_eq!(join.phase(), NetplayPhase::InGame);
+        (host, join, nes_host, nes_join)
+    }
+
+    /// A chat line typed on one peer reaches the other; an idle UI or an
+    /// invalid line sends nothing.
+    #[test]
+    fn chat_reaches_the_other_peer() {
+        let rom = synth_nrom();
+        let (mut host, mut join, mut nes_host, mut nes_join) = loopback_match(&rom);
+
+        assert_eq!(NetplayUi::default().send_chat("hello?"), Ok(false));
+        assert_eq!(
+            join.send_chat("two\nlines"),
+            Err(ChatError::ControlCharacter)
+        );
+        assert_eq!(join.send_chat("glhf"), Ok(true));
+        let mut heard = Vec::new();
+        for _ in 0..500 {
+            heard.extend(host.take_chat());
+            if !heard.is_empty() {
+                break;
+            }
+            host.tick(&mut nes_host, Buttons::empty());
+            join.tick(&mut nes_join, Buttons::empty());
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert_eq!(
+            heard,
+            [ChatLine {
+                from: 1,
+                text: "glhf".to_string()
+            }]
+        );
+        assert!(join.take_chat().is_empty());
+    }
+
+    /// A host-applied input delay reaches the joiner: both switch on the same
+    /// frame, and the joiner cannot schedule one itself.
+    #[test]
+    fn host_input_delay_change_reaches_the_joiner() {
+        let rom = synth_nrom();
+        let (mut host, mut join, mut nes_host, mut nes_join) = loopback_match(&rom);
+
+        assert_eq!(join.schedule_input_delay(4), None, "only the host decides");
+        let from = host.schedule_input_delay(4).expect("the host schedules");
+        for _ in 0..500 {
+            let done = |ui: &NetplayUi| ui.status().diagnostics.input_delay == 4;
+            if done(&host) && done(&join) {
+                break;
+            }
+            host.tick(&mut nes_host, Buttons::empty());
+            join.tick(&mut nes_join, Buttons::empty());
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        let (h, j) = (host.status().diagnostics, join.status().diagnostics);
+        assert_eq!((h.input_delay, j.input_delay), (4, 4));
+        assert_eq!(h.delay_changes, j.delay_changes);
+        assert_eq!(
+            h.delay_changes.last().map(|c| (c.frame, c.to)),
+            Some((from, 4))
+        );
     }
 
     /// v1.7.0 H8 — starting a spectator binds cleanly, enters the read-only
@@ -706,7 +1281,7 @@ mod tests {
         // No host is listening; the bind still succeeds (we only dial), and the
         // spectator simply receives nothing.
         let remote = SocketAddr::from((Ipv4Addr::LOCALHOST, 7000));
-        ui.start_spectate(remote, hash);
+        ui.start_spectate(remote, hash, &NetSettings::capture(&nes));
         assert_eq!(ui.phase(), NetplayPhase::Spectating);
         assert!(ui.is_active());
 
@@ -124,7 +124,7 @@ impl PerGameOverrides {
 /// load path, byte-identical to today). The `video` / `audio` / `input` blocks
 /// are reserved free-form JSON values for forward-compat (they are persisted
 /// round-trip but not yet consumed — the H4 surface that *is* consumed is the
-/// `overrides` + the Vs. `dip_switches`).
+/// `overrides` + the Vs. `dip_switches`, plus the v2.5.0 `expansion_device`).
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 // The reserved `video`/`audio`/`input` blocks are free-form `serde_json::Value`,
 // which is `PartialEq` but not `Eq` (it can hold an `f64`), so the whole struct
@@ -149,17 +149,22 @@ pub struct PerGameConfig {
     /// (the default) applies nothing, so the load path stays byte-identical; an
     /// unknown name is ignored. Presentation-only — never touches the core.
     pub shader_preset: Option<String>,
+    /// v2.5.0 — the port-2 device to attach for this game (e.g. `"TurboFile"`
+    /// for a title that saves to the ASCII Turbo File), winning over the
+    /// global `[input] expansion_device` while the ROM is loaded. `None` (the
+    /// default) follows the global setting.
+    pub expansion_device: Option<crate::config::ExpansionDevice>,
     /// Free-form user notes (display only).
     pub notes: Option<String>,
 }
 
 impl PerGameConfig {
     /// `true` when the overlay carries nothing the load path acts on (no
-    /// overrides, no DIP). A blank overlay applies nothing, so the default load
-    /// path stays byte-identical.
+    /// overrides, no DIP, no port-2 device). A blank overlay applies nothing,
+    /// so the default load path stays byte-identical.
     #[must_use]
     pub const fn is_inert(&self) -> bool {
-        self.overrides.is_empty() && self.dip_switches.is_none()
+        self.overrides.is_empty() && self.dip_switches.is_none() && self.expansion_device.is_none()
     }
 }
 
@@ -323,6 +328,17 @@ mod tests {
         assert!(!cfg.is_inert(), "a DIP makes the overlay active");
     }
 
+    #[test]
+    fn expansion_device_selects_a_port_2_device() {
+        let cfg = parse(br#"{ "expansion_device": "TurboFile" }"#).expect("parses");
+        assert_eq!(
+            cfg.expansion_device,
+            Some(crate::config::ExpansionDevice::TurboFile)
+        );
+        assert!(!cfg.is_inert(), "a device makes the overlay active");
+        assert!(parse(br#"{ "expansion_device": "Floppy" }"#).is_none());
+    }
+
     #[test]
     fn overrides_map_to_game_db_entry() {
         let cfg = parse(
@@ -368,6 +384,7 @@ mod tests {
             audio: None,
             input: None,
             shader_preset: Some("CRT-Royale".into()),
+            expansion_device: Some(crate::config::ExpansionDevice::TurboFileTwin),
             notes: Some("note".into()),
         };
         let json = serde_json::to_vec(&cfg).expect("serialize");
@@ -0,0 +1,570 @@
+//! v2.5.0 — practice mode: segment save states with attempt timing.
+//!
+//! A [`PracticeSet`] is one ROM's list of named segments. Each segment is a
+//! save state to start from (its anchor) and an end condition in the
+//! debugger's expression syntax (see [`crate::debugger::expr`]). Reloading a
+//! segment starts an [`Attempt`]: a frame counter that runs from the load to
+//! the frame the end condition becomes true. Finished attempts are kept per
+//! segment, so the panel can show the best and average time, and the whole
+//! history exports as CSV.
+//!
+//! The condition fires on its rising edge, judged against its value at the
+//! load: a flag already set in the anchor state has to clear and set again.
+//! Reloading before the condition fires abandons the attempt and counts as a
+//! reset. Times are frames, converted with the console's own frame duration,
+//! like the autosplitter's (see [`crate::autosplit`]).
+//!
+//! A ROM's practice data lives in its own directory:
+//!
+//! ```text
+//! <data_dir>/practice/<rom_sha256_hex>/practice.toml
+//! <data_dir>/practice/<rom_sha256_hex>/seg-<id>.rns
+//! ```
+//!
+//! ```toml
+//! current = 0
+//! next_id = 2
+//!
+//! [[segment]]
+//! id = 0
+//! name = "1-1"
+//! end = "[$0760] == 1"
+//! attempts = [2210, 2184, 2231]
+//! resets = 4
+//!
+//! [[segment]]
+//! id = 1
+//! name = "1-2"
+//! end = "[$0760] == 2"
+//! ```
+//!
+//! The `.rns` anchors are ordinary save-state blobs, thumbnail included.
+
+use std::fmt::Write as _;
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use rustynes_core::Nes;
+use serde::{Deserialize, Serialize};
+
+use crate::autosplit::format_time;
+use crate::debugger::ReplayCtx;
+use crate::debugger::expr::{EvalContext, Expr};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::save_state::hex_sha256;
+
+/// One practice segment: its anchor, end condition and attempt history.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct Segment {
+    /// Stable id; names the anchor file (`seg-<id>.rns`).
+    pub id: u32,
+    /// Segment name, as the panel shows it.
+    pub name: String,
+    /// Expression that ends an attempt when it becomes true.
+    pub end: String,
+    /// Finished attempts, in frames, oldest first.
+    pub attempts: Vec<u64>,
+    /// Attempts abandoned by reloading before the end condition fired.
+    pub resets: u32,
+}
+
+/// Summary of a segment's finished attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttemptStats {
+    /// Number of finished attempts.
+    pub count: usize,
+    /// Fastest attempt, in frames.
+    pub best: u64,
+    /// Mean attempt, in frames (rounded down).
+    pub average: u64,
+}
+
+impl Segment {
+    /// Best and average over the finished attempts; `None` before the first.
+    #[must_use]
+    pub fn stats(&self) -> Option<AttemptStats> {
+        let best = *self.attempts.iter().min()?;
+        let total: u128 = self.attempts.iter().map(|&a| u128::from(a)).sum();
+        let count = self.attempts.len();
+        let average = u64::try_from(total / count as u128).unwrap_or(u64::MAX);
+        Some(AttemptStats {
+            count,
+            best,
+            average,
+        })
+    }
+}
+
+/// One ROM's practice segments (the per-ROM `practice.toml`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct PracticeSet {
+    /// Index of the segment the reload hotkey loads.
+    pub current: usize,
+    /// Id the next new segment gets.
+    pub next_id: u32,
+    /// The segments, in play order.
+    #[serde(rename = "segment")]
+    pub segments: Vec<Segment>,
+}
+
+impl PracticeSet {
+    /// Append a segment named `name` and make it current. Returns its id; the
+    /// caller writes the anchor state under it.
+    pub fn add(&mut self, name: String) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.segments.push(Segment {
+            id,
+            name,
+            ..Segment::default()
+        });
+        self.current = self.segments.len() - 1;
+        id
+    }
+
+    /// Remove the segment with `id`, keeping `current` in range. Returns
+    /// whether it existed.
+    pub fn remove(&mut self, id: u32) -> bool {
+        let Some(idx) = self.segments.iter().position(|s| s.id == id) else {
+            return false;
+        };
+        self.segments.remove(idx);
+        if self.current > idx || self.current >= self.segments.len() {
+            self.current = self.current.saturating_sub(1);
+        }
+        true
+    }
+
+    /// The segment the reload hotkey loads.
+    #[must_use]
+    pub fn current(&self) -> Option<&Segment> {
+        self.segments.get(self.current)
+    }
+
+    /// The segment with `id`.
+    pub fn segment_mut(&mut self, id: u32) -> Option<&mut Segment> {
+        self.segments.iter_mut().find(|s| s.id == id)
+    }
+
+    /// Every finished attempt as CSV: one row per attempt, with the time both
+    /// in frames and as a clock (`frame` is the console's frame duration).
+    #[must_use]
+    pub fn to_csv(&self, frame: Duration) -> String {
+        let mut out = String::from("segment,attempt,frames,time\n");
+        for seg in &self.segments {
+            for (n, &frames) in seg.attempts.iter().enumerate() {
+                let _ = writeln!(
+                    out,
+                    "{},{},{frames},{}",
+                    csv_field(&seg.name),
+                    n + 1,
+                    format_time(frames, frame)
+                );
+            }
+        }
+        out
+    }
+}
+
+/// `s` quoted for CSV when it needs to be.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+/// Directory holding one ROM's practice data.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub fn practice_dir(data_dir: &Path, rom_sha256: &[u8; 32]) -> PathBuf {
+    data_dir.join("practice").join(hex_sha256(rom_sha256))
+}
+
+/// Path of a segment's anchor state.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub fn anchor_path(data_dir: &Path, rom_sha256: &[u8; 32], id: u32) -> PathBuf {
+    practice_dir(data_dir, rom_sha256).join(format!("seg-{id}.rns"))
+}
+
+/// Load the practice set for the ROM identified by `rom_sha256`.
+///
+/// A missing file yields an empty [`PracticeSet`]; an unreadable or
+/// unparseable one logs a warning and does the same, as
+/// [`crate::autosplit::load`] does.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub fn load(data_dir: &Path, rom_sha256: &[u8; 32]) -> PracticeSet {
+    let path = practice_dir(data_dir, rom_sha256).join("practice.toml");
+    let text = match fs::read_to_string(&path) {
+        Ok(t) => t,
+        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => return PracticeSet::default(),
+        Err(e) => {
+            eprintln!(
+                "rustynes: practice {} unreadable, ignoring: {e}",
+                path.display()
+            );
+            return PracticeSet::default();
+        }
+    };
+    toml::from_str(&text).unwrap_or_else(|e| {
+        eprintln!(
+            "rustynes: practice {} unparseable, ignoring: {e}",
+            path.display()
+        );
+        PracticeSet::default()
+    })
+}
+
+/// Persist `set` as the practice set for the ROM identified by `rom_sha256`.
+///
+/// # Errors
+/// Any I/O error writing the file (a serialization failure is mapped to
+/// [`std::io::ErrorKind::InvalidData`]).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save(data_dir: &Path, rom_sha256: &[u8; 32], set: &PracticeSet) -> std::io::Result<()> {
+    let text = toml::to_string_pretty(set)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    let path = practice_dir(data_dir, rom_sha256).join("practice.toml");
+    crate::atomic_write::write_atomic(&path, text.as_bytes())
+}
+
+/// Write a segment's anchor state.
+///
+/// # Errors
+/// Any I/O error writing the file.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_anchor(
+    data_dir: &Path,
+    rom_sha256: &[u8; 32],
+    id: u32,
+    state: &[u8],
+) -> std::io::Result<()> {
+    crate::atomic_write::write_atomic(&anchor_path(data_dir, rom_sha256, id), state)
+}
+
+/// Read a segment's anchor state.
+///
+/// # Errors
+/// Any I/O error reading the file, including a missing one.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_anchor(data_dir: &Path, rom_sha256: &[u8; 32], id: u32) -> std::io::Result<Vec<u8>> {
+    fs::read(anchor_path(data_dir, rom_sha256, id))
+}
+
+/// Delete a segment's anchor state. A missing file is not an error.
+///
+/// # Errors
+/// Any other I/O error removing the file.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn remove_anchor(data_dir: &Path, rom_sha256: &[u8; 32], id: u32) -> std::io::Result<()> {
+    match fs::remove_file(anchor_path(data_dir, rom_sha256, id)) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// What the panel shows of the attempt in progress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttemptView {
+    /// Id of the segment being attempted.
+    pub segment: u32,
+    /// Frames since the load (the final time once finished).
+    pub frames: u64,
+    /// Whether the end condition has fired.
+    pub finished: bool,
+    /// Duration of one frame on this console.
+    pub frame: Duration,
+}
+
+impl AttemptView {
+    /// The attempt time as a clock.
+    #[must_use]
+    pub fn time(&self) -> String {
+        format_time(self.frames, self.frame)
+    }
+}
+
+/// One timed attempt at a segment, from its load to its end condition.
+#[derive(Debug)]
+pub struct Attempt {
+    rom_sha256: [u8; 32],
+    segment: u32,
+    end: Option<Expr>,
+    was: bool,
+    frames: u64,
+    finished: bool,
+    reported: bool,
+    frame: Duration,
+}
+
+impl Attempt {
+    /// Start timing `segment` of the ROM identified by `rom_sha256`, ending
+    /// on `end`. An empty end condition times until the next reload.
+    ///
+    /// # Errors
+    /// The end condition does not parse.
+    pub fn new(rom_sha256: [u8; 32], segment: u32, end: &str) -> Result<Self, String> {
+        let end = if end.trim().is_empty() {
+            None
+        } else {
+            Some(Expr::parse(end).map_err(|e| e.to_string())?)
+        };
+        Ok(Self {
+            rom_sha256,
+            segment,
+            end,
+            was: false,
+            frames: 0,
+            finished: false,
+            reported: false,
+            frame: Duration::from_nanos(16_639_267),
+        })
+    }
+
+    /// SHA-256 of the ROM the segment belongs to.
+    #[must_use]
+    pub const fn rom_sha256(&self) -> &[u8; 32] {
+        &self.rom_sha256
+    }
+
+    /// Id of the segment being attempted.
+    #[must_use]
+    pub const fn segment(&self) -> u32 {
+        self.segment
+    }
+
+    /// Whether the end condition has fired.
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Take the end condition's value in the just-loaded state, so only a
+    /// change from there ends the attempt.
+    pub fn arm(&mut self, nes: &mut Nes) {
+        self.frame = nes.frame_duration();
+        let ctx = ReplayCtx::end_of_frame(nes);
+        self.arm_with(&ctx);
+    }
+
+    /// [`Self::arm`] against any evaluation context.
+    pub(crate) fn arm_with(&mut self, ctx: &dyn EvalContext) {
+        self.was = self.eval(ctx);
+    }
+
+    /// Count a forward frame and check the end condition after it.
+    pub fn tick(&mut self, nes: &mut Nes) {
+        if self.finished {
+            return;
+        }
+        let ctx = ReplayCtx::end_of_frame(nes);
+        self.step(&ctx);
+    }
+
+    /// [`Self::tick`] against any evaluation context.
+    pub(crate) fn step(&mut self, ctx: &dyn EvalContext) {
+        if self.finished {
+            return;
+        }
+        self.frames += 1;
+        let now = self.eval(ctx);
+        self.finished = now && !self.was;
+        self.was = now;
+    }
+
+    fn eval(&self, ctx: &dyn EvalContext) -> bool {
+        self.end.as_ref().is_some_and(|e| e.eval_bool(ctx))
+    }
+
+    /// The finished time in frames, once: `None` while running and after it
+    /// has been taken.
+    pub const fn take_result(&mut self) -> Option<u64> {
+        if self.finished && !self.reported {
+            self.reported = true;
+            Some(self.frames)
+        } else {
+            None
+        }
+    }
+
+    /// Snapshot for the panel.
+    #[must_use]
+    pub const fn view(&self) -> AttemptView {
+        AttemptView {
+            segment: self.segment,
+            frames: self.frames,
+            finished: self.finished,
+            frame: self.frame,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debugger::expr::AccessContext;
+    use std::cell::RefCell;
+
+    /// A machine that is nothing but 2 KiB of RAM.
+    struct Ram(RefCell<[u8; 0x800]>);
+
+    impl Ram {
+        fn new() -> Self {
+            Self(RefCell::new([0; 0x800]))
+        }
+        fn set(&self, addr: u16, v: u8) {
+            self.0.borrow_mut()[usize::from(addr)] = v;
+        }
+    }
+
+    impl EvalContext for Ram {
+        fn a(&self) -> u8 {
+            0
+        }
+        fn x(&self) -> u8 {
+            0
+        }
+        fn y(&self) -> u8 {
+            0
+        }
+        fn s(&self) -> u8 {
+            0
+        }
+        fn p(&self) -> u8 {
+            0
+        }
+        fn pc(&self) -> u16 {
+            0
+        }
+        fn scanline(&self) -> i16 {
+            0
+        }
+        fn dot(&self) -> u16 {
+            0
+        }
+        fn frame(&self) -> u64 {
+            0
+        }
+        fn peek(&self, addr: u16) -> u8 {
+            self.0.borrow()[usize::from(addr) & 0x7FF]
+        }
+        fn access(&self) -> AccessContext {
+            AccessContext::default()
+        }
+    }
+
+    #[test]
+    fn an_attempt_times_load_to_the_end_condition() {
+        let ram = Ram::new();
+        let mut attempt = Attempt::new([0; 32], 3, "[$0760] == 1").unwrap();
+        attempt.arm_with(&ram);
+        for _ in 0..99 {
+            attempt.step(&ram);
+        }
+        assert_eq!(attempt.take_result(), None);
+        ram.set(0x0760, 1);
+        attempt.step(&ram);
+        assert!(attempt.is_finished());
+        assert_eq!(attempt.take_result(), Some(100));
+        // Taken once; the clock stops.
+        assert_eq!(attempt.take_result(), None);
+        attempt.step(&ram);
+        assert_eq!(attempt.view().frames, 100);
+        assert_eq!(attempt.view().segment, 3);
+    }
+
+    #[test]
+    fn a_condition_already_true_at_load_must_change_first() {
+        let ram = Ram::new();
+        ram.set(0x0760, 1);
+        let mut attempt = Attempt::new([0; 32], 0, "[$0760] == 1").unwrap();
+        attempt.arm_with(&ram);
+        attempt.step(&ram);
+        assert!(!attempt.is_finished());
+        ram.set(0x0760, 0);
+        attempt.step(&ram);
+        ram.set(0x0760, 1);
+        attempt.step(&ram);
+        assert_eq!(attempt.take_result(), Some(3));
+    }
+
+    #[test]
+    fn bad_or_empty_end_conditions() {
+        assert!(Attempt::new([0; 32], 0, "[$0760] ==").is_err());
+        let ram = Ram::new();
+        let mut open = Attempt::new([0; 32], 0, "  ").unwrap();
+        for _ in 0..10 {
+            open.step(&ram);
+        }
+        assert!(!open.is_finished());
+        assert_eq!(open.view().frames, 10);
+    }
+
+    #[test]
+    fn stats_and_segment_bookkeeping() {
+        let mut set = PracticeSet::default();
+        let a = set.add("1-1".into());
+        let b = set.add("1-2".into());
+        assert_eq!((a, b, set.current), (0, 1, 1));
+        assert_eq!(set.current().unwrap().name, "1-2");
+        assert_eq!(set.segments[0].stats(), None);
+        set.segment_mut(a).unwrap().attempts = vec![120, 100, 131];
+        assert_eq!(
+            set.segments[0].stats(),
+            Some(AttemptStats {
+                count: 3,
+                best: 100,
+                average: 117
+            })
+        );
+        assert!(set.remove(b));
+        assert!(!set.remove(b));
+        assert_eq!(set.current, 0);
+        // Ids are never reused.
+        assert_eq!(set.add("1-3".into()), 2);
+    }
+
+    #[test]
+    fn csv_lists_every_attempt() {
+        let mut set = PracticeSet::default();
+        let id = set.add("World 1, \"fast\"".into());
+        set.segment_mut(id).unwrap().attempts = vec![60, 90];
+        set.add("unplayed".into());
+        let csv = set.to_csv(Duration::from_millis(20));
+        assert_eq!(
+            csv,
+            "segment,attempt,frames,time\n\
+             \"World 1, \"\"fast\"\"\",1,60,0:01.20\n\
+             \"World 1, \"\"fast\"\"\",2,90,0:01.80\n"
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn practice_sets_and_anchors_persist_per_rom() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let sha = [7u8; 32];
+        assert_eq!(load(tmp.path(), &sha), PracticeSet::default());
+        let mut set = PracticeSet::default();
+        let id = set.add("1-1".into());
+        set.segments[0].end = "[$0760] == 1".into();
+        set.segments[0].attempts = vec![2210];
+        save(tmp.path(), &sha, &set).unwrap();
+        save_anchor(tmp.path(), &sha, id, b"state").unwrap();
+        assert_eq!(load(tmp.path(), &sha), set);
+        assert_eq!(load_anchor(tmp.path(), &sha, id).unwrap(), b"state");
+        assert!(anchor_path(tmp.path(), &sha, id).starts_with(tmp.path().join("practice")));
+        assert_eq!(load(tmp.path(), &[8u8; 32]), PracticeSet::default());
+        remove_anchor(tmp.path(), &sha, id).unwrap();
+        remove_anchor(tmp.path(), &sha, id).unwrap();
+        assert!(load_anchor(tmp.path(), &sha, id).is_err());
+    }
+}
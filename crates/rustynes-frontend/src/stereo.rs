@@ -0,0 +1,197 @@
+//! v2.5.0 — Famicom 3D System stereo presentation.
+//!
+//! With the 3D glasses attached the core tags each completed frame with the
+//! eye it was drawn for ([`rustynes_core::Nes::stereo_eye`]) and otherwise
+//! leaves the framebuffer alone. [`StereoPresenter`] keeps the latest frame
+//! of each eye — consecutive frames form the pair — and rewrites the
+//! *presented* copy (`present_fb`) per [`StereoMode`]. Movies, attestation
+//! and netplay all see the untouched core stream.
+//!
+//! Everything stays 256x240 so the present path, filters and shaders need no
+//! special case: side-by-side is the "half side-by-side" layout 3D TVs and
+//! cross-eyed / parallel viewing expect, each eye squeezed to half width.
+
+use rustynes_core::StereoEye;
+use serde::{Deserialize, Serialize};
+
+const W: usize = 256;
+const H: usize = 240;
+const FRAME_LEN: usize = W * H * 4;
+
+/// How a stereo pair is presented.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum StereoMode {
+    /// 3D glasses unplugged: the frames present as drawn (flickering
+    /// between the eyes, as on a TV without the glasses). The default.
+    #[default]
+    Off,
+    /// Red/cyan anaglyph: red from the left eye, green + blue from the right.
+    Anaglyph,
+    /// Half side-by-side: left eye in the left half, right eye in the right.
+    SideBySide,
+    /// Only the left eye's frames (a steady 2D picture at half rate).
+    LeftOnly,
+}
+
+impl StereoMode {
+    /// Every mode, in menu order.
+    pub const ALL: [Self; 4] = [Self::Off, Self::Anaglyph, Self::SideBySide, Self::LeftOnly];
+
+    /// Human label for the settings combo.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Anaglyph => "Red/cyan anaglyph",
+            Self::SideBySide => "Side-by-side",
+            Self::LeftOnly => "Left eye only",
+        }
+    }
+
+    /// `true` when the 3D glasses should be plugged into the core.
+    #[must_use]
+    pub const fn enabled(self) -> bool {
+        !matches!(self, Self::Off)
+    }
+}
+
+/// The latest frame of each eye plus the presentation settings.
+#[derive(Debug, Default)]
+pub struct StereoPresenter {
+    mode: StereoMode,
+    swap_eyes: bool,
+    left: Vec<u8>,
+    right: Vec<u8>,
+}
+
+impl StereoPresenter {
+    /// Apply new settings; the held pair is dropped so a stale eye from the
+    /// previous game never shows.
+    pub fn configure(&mut self, mode: StereoMode, swap_eyes: bool) {
+        self.mode = mode;
+        self.swap_eyes = swap_eyes;
+        self.left.clear();
+        self.right.clear();
+    }
+
+    /// Record `frame` as `eye`'s latest frame and rewrite it in place into the
+    /// presented image. A frame without an eye (no glasses) or a
+    /// short frame passes through.
+    pub fn present(&mut self, frame: &mut [u8], eye: Option<StereoEye>) {
+        let Some(mut eye) = eye else { return };
+        if !self.mode.enabled() || frame.len() != FRAME_LEN {
+            return;
+        }
+        if self.swap_eyes {
+            eye = eye.other();
+        }
+        let slot = match eye {
+            StereoEye::Left => &mut self.left,
+            StereoEye::Right => &mut self.right,
+        };
+        slot.clear();
+        slot.extend_from_slice(frame);
+        // Until both eyes have been seen, pair the frame with itself.
+        let left: &[u8] = if self.left.is_empty() {
+            &self.right
+        } else {
+            &self.left
+        };
+        let right: &[u8] = if self.right.is_empty() {
+            &self.left
+        } else {
+            &self.right
+        };
+        match self.mode {
+            StereoMode::Off => {}
+            StereoMode::Anaglyph => {
+                for ((out, l), r) in frame
+                    .chunks_exact_mut(4)
+                    .zip(left.chunks_exact(4))
+                    .zip(right.chunks_exact(4))
+                {
+                    out[0] = l[0];
+                    out[1] = r[1];
+                    out[2] = r[2];
+                }
+            }
+            StereoMode::SideBySide => {
+                for (y, row) in frame.chunks_exact_mut(W * 4).enumerate() {
+                    for (x, out) in row.chunks_exact_mut(4).enumerate() {
+                        let (src, sx) = if x < W / 2 {
+                            (left, x * 2)
+                        } else {
+                            (right, (x - W / 2) * 2)
+                        };
+                        let a = (y * W + sx) * 4;
+                        for c in 0..4 {
+                            let sum = u16::from(src[a + c]) + u16::from(src[a + 4 + c]);
+                            out[c] = u8::try_from(sum / 2).unwrap_or(u8::MAX);
+                        }
+                    }
+                }
+            }
+            StereoMode::LeftOnly => frame.copy_from_slice(left),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(r: u8, g: u8, b: u8) -> Vec<u8> {
+        [r, g, b, 0xFF].repeat(W * H)
+    }
+
+    #[test]
+    fn off_and_eyeless_frames_pass_through() {
+        let mut p = StereoPresenter::default();
+        let mut f = solid(1, 2, 3);
+        p.present(&mut f, Some(StereoEye::Left));
+        assert_eq!(f, solid(1, 2, 3));
+        p.configure(StereoMode::Anaglyph, false);
+        p.present(&mut f, None);
+        assert_eq!(f, solid(1, 2, 3));
+    }
+
+    #[test]
+    fn anaglyph_takes_red_from_left_and_cyan_from_right() {
+        let mut p = StereoPresenter::default();
+        p.configure(StereoMode::Anaglyph, false);
+        let mut l = solid(200, 10, 10);
+        p.present(&mut l, Some(StereoEye::Left));
+        let mut r = solid(20, 100, 150);
+        p.present(&mut r, Some(StereoEye::Right));
+        assert_eq!(&r[..4], &[200, 100, 150, 0xFF]);
+    }
+
+    #[test]
+    fn side_by_side_puts_each_eye_in_its_half() {
+        let mut p = StereoPresenter::default();
+        p.configure(StereoMode::SideBySide, false);
+        let mut l = solid(10, 10, 10);
+        p.present(&mut l, Some(StereoEye::Left));
+        let mut r = solid(90, 90, 90);
+        p.present(&mut r, Some(StereoEye::Right));
+        assert_eq!(r[0], 10);
+        assert_eq!(r[(W - 1) * 4], 90);
+    }
+
+    #[test]
+    fn left_only_repeats_the_left_eye_and_swap_flips_it() {
+        let mut p = StereoPresenter::default();
+        p.configure(StereoMode::LeftOnly, false);
+        let mut l = solid(10, 10, 10);
+        p.present(&mut l, Some(StereoEye::Left));
+        let mut r = solid(90, 90, 90);
+        p.present(&mut r, Some(StereoEye::Right));
+        assert_eq!(r[0], 10);
+        p.configure(StereoMode::LeftOnly, true);
+        l = solid(10, 10, 10);
+        p.present(&mut l, Some(StereoEye::Left)); // now the right eye
+        r = solid(90, 90, 90);
+        p.present(&mut r, Some(StereoEye::Right));
+        assert_eq!(r[0], 90);
+    }
+}
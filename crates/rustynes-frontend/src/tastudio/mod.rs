@@ -151,6 +151,7 @@ impl<'a> Reader<'a> {
                 p1: Buttons::from_bits_truncate(s[0]),
                 p2: Buttons::from_bits_truncate(s[1]),
                 expansion: s[2],
+                ..FrameInput::default()
             });
         }
         Ok(v)
@@ -379,6 +380,7 @@ impl TasEditor {
             // could honestly describe. Attesting an edited movie would be the
             // one thing this feature must not do.
             attestation: None,
+            match_info: None,
         }
     }
 
@@ -692,6 +694,7 @@ impl TasEditor {
             let input = self.input_log.get(f).copied().unwrap_or_default();
             nes.set_buttons(0, input.p1);
             nes.set_buttons(1, input.p2);
+            nes.set_microphone_pattern(input.expansion);
             nes.run_frame();
             self.record_lag(f, nes);
             let next = f + 1;
@@ -721,6 +724,7 @@ impl TasEditor {
         self.greenzone.invalidate_after(frame);
         nes.set_buttons(0, input.p1);
         nes.set_buttons(1, input.p2);
+        nes.set_microphone_pattern(input.expansion);
         nes.run_frame();
         self.record_lag(frame, nes);
         self.cursor = frame + 1;
@@ -119,6 +119,53 @@ pub enum SettingsTab {
     Emulation,
 }
 
+/// v2.5.0 — Emulation > Data Recorder controls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TapeAction {
+    /// Insert a tape loaded from a WAV file (native file dialog).
+    InsertWav,
+    /// Insert a blank tape (to record onto).
+    InsertBlank,
+    /// Start playback into the keyboard's tape-in line.
+    Play,
+    /// Start recording the keyboard's tape-out line.
+    Record,
+    /// Stop the motor.
+    Stop,
+    /// Stop and return to the start of the tape.
+    Rewind,
+    /// Save the tape as a WAV file (native save dialog).
+    SaveWav,
+    /// Eject the tape.
+    Eject,
+}
+
+/// v2.5.0 — Movies & Recording > Ghost controls. The overlay settings
+/// themselves are edited straight into `[ghost]` config; `Configure` pushes
+/// them to a loaded ghost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GhostAction {
+    /// Load a movie as the ghost (native file dialog).
+    Load,
+    /// Start the ghost over from its first frame.
+    Restart,
+    /// Drop the ghost.
+    Unload,
+    /// Apply the edited `[ghost]` settings.
+    Configure,
+}
+
+/// v2.5.0 — the inserted Data Recorder tape, snapshotted for the menu.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TapeStatus {
+    /// Transport mode.
+    pub mode: rustynes_core::TapeMode,
+    /// Transport position in seconds.
+    pub position_secs: f32,
+    /// Tape length in seconds.
+    pub length_secs: f32,
+}
+
 /// An action the user triggered in the shell that needs `&mut App`. Returned
 /// from [`ShellOutput`] and dispatched after the egui pass so the build closure
 /// never borrows the whole `App`.
@@ -155,6 +202,10 @@ pub enum MenuAction {
     CycleDiskSide,
     /// v1.8.9 — insert a specific FDS disk side (`Some(i)`) or eject (`None`).
     SetDiskSide(Option<usize>),
+    /// v2.5.0 — a Family BASIC Data Recorder (cassette) control.
+    Tape(TapeAction),
+    /// v2.5.0 — a ghost replay control.
+    Ghost(GhostAction),
     /// v1.0.0 — capture a screenshot of the current framebuffer (native).
     Screenshot,
     /// v1.0.0 — copy the current framebuffer to the system clipboard (native;
@@ -393,6 +444,15 @@ pub struct ShellFrame<'a> {
     /// v1.8.9 — the currently inserted FDS disk side, or `None` when ejected, for
     /// the Multi-Disk submenu's radio state.
     pub inserted_disk_side: Option<usize>,
+    /// v2.5.0 — whether the Family BASIC keyboard is the selected expansion
+    /// device (shows the Data Recorder submenu).
+    pub family_keyboard: bool,
+    /// v2.5.0 — the inserted Data Recorder tape, or `None` when empty.
+    pub tape: Option<TapeStatus>,
+    /// v2.5.0 — the loaded ghost's progress, or `None` without one.
+    pub ghost: Option<crate::ghost::GhostStatus>,
+    /// v2.5.0 — the speedrun timer, or `None` when the ROM has no splits.
+    pub autosplit: Option<crate::autosplit::TimerView>,
     /// v1.0.0 — whether the loaded game is a Vs. System title (enables the
     /// "Insert Coin" item).
     pub vs_system: bool,
@@ -444,6 +504,131 @@ pub struct ShellFrame<'a> {
     pub lag_frames: Option<u32>,
 }
 
+/// v2.5.0 — the Movies & Recording > Ghost submenu: load / restart / unload,
+/// then the `[ghost]` overlay and alignment settings.
+#[cfg(not(target_arch = "wasm32"))]
+fn ghost_menu(
+    ui: &mut egui::Ui,
+    config: &mut Config,
+    frame: &ShellFrame<'_>,
+    out: &mut ShellOutput,
+    movie_ok: bool,
+) {
+    use crate::icons::{glyph, label as ic};
+    let loaded = frame.ghost.is_some();
+    let items = [
+        (
+            GhostAction::Load,
+            glyph::FOLDER_OPEN,
+            "Load Ghost...",
+            movie_ok,
+        ),
+        (
+            GhostAction::Restart,
+            glyph::BACKWARD_FAST,
+            "Restart Ghost",
+            loaded,
+        ),
+        (GhostAction::Unload, glyph::XMARK, "Unload Ghost", loaded),
+    ];
+    for (action, icon, text, enabled) in items {
+        if ui
+            .add_enabled(enabled, egui::Button::new(ic(icon, text)))
+            .clicked()
+        {
+            out.action = Some(MenuAction::Ghost(action));
+            ui.close();
+        }
+    }
+    ui.separator();
+    let g = &mut config.ghost;
+    let mut changed = ui.checkbox(&mut g.overlay, "Sprite overlay").changed();
+    changed |= ui
+        .add(egui::Slider::new(&mut g.opacity, 0..=100).suffix("% opacity"))
+        .changed();
+    changed |= ui.checkbox(&mut g.pip, "Picture-in-picture").changed();
+    ui.separator();
+    for align in crate::ghost::GhostAlign::ALL {
+        changed |= ui.radio_value(&mut g.align, align, align.label()).changed();
+    }
+    ui.horizontal(|ui| {
+        ui.label("Split when RAM");
+        changed |= ui
+            .add(egui::DragValue::new(&mut g.split_addr).hexadecimal(4, false, true))
+            .changed();
+        ui.label("=");
+        changed |= ui
+            .add(egui::DragValue::new(&mut g.split_value).hexadecimal(2, false, true))
+            .changed();
+    });
+    if changed {
+        save_config(config);
+        out.action = Some(MenuAction::Ghost(GhostAction::Configure));
+    }
+}
+
+/// v2.5.0 — the speedrun timer drawn over the game's bottom-left corner:
+/// game time, the segment being run and the delta against the PB.
+fn timer_overlay(ctx: &egui::Context, view: &crate::autosplit::TimerView) {
+    use crate::autosplit::TimerPhase;
+    let time_color = match view.phase {
+        TimerPhase::Idle => egui::Color32::from_gray(160),
+        TimerPhase::Running if view.paused => egui::Color32::from_rgb(240, 200, 100),
+        TimerPhase::Running => egui::Color32::WHITE,
+        TimerPhase::Finished => egui::Color32::from_rgb(100, 180, 255),
+    };
+    egui::Area::new(egui::Id::new("autosplit_timer"))
+        .anchor(egui::Align2::LEFT_BOTTOM, egui::vec2(12.0, -36.0))
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::new()
+                .fill(egui::Color32::from_black_alpha(160))
+                .corner_radius(4.0)
+                .inner_margin(6.0)
+                .show(ui, |ui| {
+                    ui.label(
+                        egui::RichText::new(view.game_time())
+                            .monospace()
+                            .size(22.0)
+                            .color(time_color),
+                    );
+                    if let Some(segment) = view.segment.as_deref() {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{segment}  {}/{}",
+                                view.split_index + 1,
+                                view.split_count
+                            ))
+                            .color(egui::Color32::LIGHT_GRAY),
+                        );
+                    }
+                    if let Some(delta) = view.delta_label() {
+                        let color = if view.delta.is_some_and(|d| d <= 0) {
+                            egui::Color32::from_rgb(64, 192, 96)
+                        } else {
+                            egui::Color32::from_rgb(224, 96, 64)
+                        };
+                        ui.label(egui::RichText::new(delta).monospace().color(color));
+                    }
+                });
+        });
+}
+
+/// "Playing 0:12 / 1:30"-style readout for the Data Recorder submenu.
+fn tape_status_label(t: TapeStatus) -> String {
+    let mode = match t.mode {
+        rustynes_core::TapeMode::Stopped => "Stopped",
+        rustynes_core::TapeMode::Playing => "Playing",
+        rustynes_core::TapeMode::Recording => "Recording",
+    };
+    let mmss = |secs: f32| {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // non-negative, small
+        let s = secs.max(0.0) as u32;
+        format!("{}:{:02}", s / 60, s % 60)
+    };
+    format!("{mode} {} / {}", mmss(t.position_secs), mmss(t.length_secs))
+}
+
 impl UiShell {
     /// Build the always-on shell UI for this frame. Returns a [`ShellOutput`]
     /// carrying the menu action (if any) for the app to dispatch afterwards.
@@ -475,6 +660,11 @@ impl UiShell {
             self.menu_bar(root_ui, config, frame, &mut out);
         }
         self.status_bar(root_ui, frame, config);
+        if config.autosplit.overlay
+            && let Some(view) = frame.autosplit.as_ref()
+        {
+            timer_overlay(&ctx, view);
+        }
         self.settings_window(&ctx, config, &mut settings_body, &mut input_body);
         self.welcome_modal(&ctx, config);
         about_window(&ctx, &mut self.show_about);
@@ -996,6 +1186,90 @@ impl UiShell {
                             });
                         });
                     }
+                    // v2.5.0 — Family BASIC Data Recorder. Shown while the
+                    // keyboard is selected (the deck plugs into it) or a tape is
+                    // still inserted. Locked during a replay like the disk items:
+                    // the tape feeds `$4016`, so touching it diverges the run.
+                    if rom && (frame.family_keyboard || frame.tape.is_some()) {
+                        ui.separator();
+                        ui.menu_button(ic(glyph::CASSETTE_TAPE, "Data Recorder"), |ui| {
+                            if let Some(t) = frame.tape {
+                                ui.label(tape_status_label(t));
+                                ui.separator();
+                            }
+                            ui.add_enabled_ui(!replay_locked, |ui| {
+                                let loaded = frame.tape.is_some();
+                                let mode = frame.tape.map(|t| t.mode);
+                                let items = [
+                                    (TapeAction::Play, glyph::PLAY, "Play", loaded),
+                                    (TapeAction::Record, glyph::CIRCLE, "Record", loaded),
+                                    (TapeAction::Stop, glyph::STOP, "Stop", loaded),
+                                    (TapeAction::Rewind, glyph::BACKWARD_FAST, "Rewind", loaded),
+                                ];
+                                for (action, icon, text, enabled) in items {
+                                    let active = match action {
+                                        TapeAction::Play => {
+                                            mode == Some(rustynes_core::TapeMode::Playing)
+                                        }
+                                        TapeAction::Record => {
+                                            mode == Some(rustynes_core::TapeMode::Recording)
+                                        }
+                                        _ => false,
+                                    };
+                                    if ui
+                                        .add_enabled(
+                                            enabled,
+                                            egui::Button::selectable(active, ic(icon, text)),
+                                        )
+                                        .clicked()
+                                    {
+                                        out.action = Some(MenuAction::Tape(action));
+                                        ui.close();
+                                    }
+                                }
+                                ui.separator();
+                                #[cfg(not(target_arch = "wasm32"))]
+                                if ui
+                                    .button(ic(glyph::FOLDER_OPEN, "Insert Tape (WAV)..."))
+                                    .clicked()
+                                {
+                                    out.action = Some(MenuAction::Tape(TapeAction::InsertWav));
+                                    ui.close();
+                                }
+                                if ui
+                                    .button(ic(glyph::CASSETTE_TAPE, "Insert Blank Tape"))
+                                    .clicked()
+                                {
+                                    out.action = Some(MenuAction::Tape(TapeAction::InsertBlank));
+                                    ui.close();
+                                }
+                                #[cfg(not(target_arch = "wasm32"))]
+                                if ui
+                                    .add_enabled(
+                                        loaded,
+                                        egui::Button::new(ic(
+                                            glyph::FLOPPY_DISK,
+                                            "Save Tape as WAV...",
+                                        )),
+                                    )
+                                    .clicked()
+                                {
+                                    out.action = Some(MenuAction::Tape(TapeAction::SaveWav));
+                                    ui.close();
+                                }
+                                if ui
+                                    .add_enabled(
+                                        loaded,
+                                        egui::Button::new(ic(glyph::XMARK, "Eject Tape")),
+                                    )
+                                    .clicked()
+                                {
+                                    out.action = Some(MenuAction::Tape(TapeAction::Eject));
+                                    ui.close();
+                                }
+                            });
+                        });
+                    }
                 });
 
                 // ----- View -----
@@ -1241,6 +1515,40 @@ impl UiShell {
                                 out.action = Some(MenuAction::MovieExportSubtitles);
                                 ui.close();
                             }
+                            // v2.5.0 — ghost replay overlay.
+                            ui.menu_button(ic(glyph::VIDEO, "Ghost"), |ui| {
+                                ghost_menu(ui, config, frame, out, movie_ok);
+                            });
+                            // v2.5.0 — speedrun autosplitter: the panel holds
+                            // the timer, the LiveSplit link and the split
+                            // editor; the overlay toggle lives here.
+                            if ui
+                                .add_enabled(
+                                    rom,
+                                    egui::Button::new(ic(glyph::GAUGE, "Autosplitter...")),
+                                )
+                                .clicked()
+                            {
+                                out.action = Some(MenuAction::OpenPanel(ToolPanel::Autosplit));
+                                ui.close();
+                            }
+                            if ui
+                                .checkbox(&mut config.autosplit.overlay, "Speedrun Timer Overlay")
+                                .changed()
+                            {
+                                save_config(config);
+                            }
+                            // v2.5.0 — practice segments and attempt timing.
+                            if ui
+                                .add_enabled(
+                                    rom,
+                                    egui::Button::new(ic(glyph::CLOCK_ROTATE_LEFT, "Practice...")),
+                                )
+                                .clicked()
+                            {
+                                out.action = Some(MenuAction::OpenPanel(ToolPanel::Practice));
+                                ui.close();
+                            }
                         }
                         ui.separator();
                         // v1.6.0 "Studio" Workstream A2 — TAStudio piano-roll TAS
@@ -1322,6 +1630,13 @@ impl UiShell {
                             out.action = Some(MenuAction::OpenPanel(ToolPanel::InputDisplay));
                             ui.close();
                         }
+                        // v2.5.0 — the animated R.O.B. view (set up in the
+                        // Input window). Native-only, like the robot itself.
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui.button(ic(glyph::GAMEPAD, "R.O.B.")).clicked() {
+                            out.action = Some(MenuAction::OpenPanel(ToolPanel::Rob));
+                            ui.close();
+                        }
                         // v1.8.9 "Backlog" — the desktop on-screen virtual pad: a
                         // clickable egui controller that feeds player 1. Native-only
                         // (the browser build has the touch overlay).
@@ -1355,6 +1670,17 @@ impl UiShell {
                             out.action = Some(MenuAction::OpenPanel(ToolPanel::GameDb));
                             ui.close();
                         }
+                        // v2.5.0 — the FDS disk browser works on .fds files as
+                        // well as the inserted disk, so it needs no loaded ROM.
+                        // Native-only (file pickers).
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui
+                            .button(ic(glyph::FLOPPY_DISK, "FDS Disk Browser"))
+                            .clicked()
+                        {
+                            out.action = Some(MenuAction::OpenPanel(ToolPanel::FdsDisk));
+                            ui.close();
+                        }
                     });
                     // ---- Analysis ------------------------------------------
                     // The three tools that answer a question ABOUT the running
@@ -1768,6 +2094,14 @@ impl UiShell {
                             ui.separator();
                             ui.colored_label(egui::Color32::from_rgb(240, 200, 100), "HD-Pack REC");
                         }
+                        if let Some(ghost) = frame.ghost {
+                            ui.separator();
+                            ui.colored_label(egui::Color32::from_rgb(170, 170, 240), ghost.label())
+                                .on_hover_text(
+                                    "Ghost frame / length, and live minus ghost frames \
+                                     at the latest split (positive = behind)",
+                                );
+                        }
                         // v1.5.0 "Lens" Workstream I7 — the RetroAchievements
                         // readout relocated from the retired `` ` `` overlay HUD,
                         // placed between the emulator-state label and the FPS
@@ -2398,6 +2732,7 @@ fn system_hotkeys_grid(ui: &mut egui::Ui, config: &Config) {
                 ("Insert coin (Vs.)", s.insert_coin.as_str()),
                 ("Fullscreen", s.fullscreen.as_str()),
                 ("Toggle menu bar", s.toggle_menu_bar.as_str()),
+                ("Reload practice segment", s.practice_reload.as_str()),
                 // v1.7.0 "Forge" beta.5 (#55) — the backtick key now toggles the
                 // status-bar RetroAchievements read-out (compact <-> long-form).
                 // v1.7.1 — the debugger has no single toggle; each inspector
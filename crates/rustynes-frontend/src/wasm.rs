@@ -435,7 +435,10 @@ fn movie_record_toggle() {
             };
             // v2.3.2 "Lucid": wasm has no run-ahead on this path, so the
             // attestation always describes the persistent timeline.
-            emu.movie.start_recording_power_on(nes, true);
+            if let Err(e) = emu.movie.start_recording_power_on(nes, true) {
+                log(&format!("movie record refused: {e}"));
+                return;
+            }
             log("movie recording started (power-on)");
         }
     });
@@ -464,7 +467,10 @@ fn movie_branch() {
             log("movie branch: no ROM loaded");
             return;
         };
-        emu.movie.start_recording_branch(nes, true);
+        if let Err(e) = emu.movie.start_recording_branch(nes, true) {
+            log(&format!("movie branch refused: {e}"));
+            return;
+        }
         log("movie branch — recording from current state");
     });
 }
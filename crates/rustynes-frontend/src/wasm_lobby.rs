@@ -9,10 +9,10 @@
 //! A browser cannot open a UDP socket, so the native netplay panel
 //! (`debugger/netplay_panel.rs`) is a "native-only" note on wasm. This module is
 //! its browser counterpart: a small command + view surface for the WebRTC path.
-//! The user fills in the signaling-server URL, a room/lobby code, picks Host or
-//! Join + the player count, and clicks Connect; the lobby emits a
-//! [`LobbyRequest`] the `App` drains each rAF frame and acts on by driving the
-//! `BrowserNetplay` handshake.
+//! The user fills in the signaling-server URL, a room/lobby code and optional
+//! password, picks Host or Join + the player count, and clicks Connect; the
+//! lobby emits a [`LobbyRequest`] the `App` drains each rAF frame and acts on
+//! by driving the `BrowserNetplay` handshake.
 //!
 //! It is intentionally **bounded** — a functional lobby, not a polished
 //! multi-screen UI. A full end-to-end browser session needs the signaling server
@@ -21,8 +21,15 @@
 //!
 //! (Module-gated to `wasm32` at its `pub mod` declaration in `lib.rs`.)
 
+use std::collections::VecDeque;
+
+use rustynes_netplay::ChatLine;
+
 use crate::wasm_netplay::BrowserNetplayPhase;
 
+/// Chat lines the lobby keeps (oldest dropped first).
+const CHAT_LINES_KEPT: usize = 50;
+
 /// A request the lobby emits for the `App` to act on (drained each frame via
 /// [`WasmLobbyState::take_request`]).
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -35,11 +42,20 @@ pub enum LobbyRequest {
         signaling_url: String,
         /// The room / lobby code both peers share.
         room: String,
+        /// The room password: locks a room we open, unlocks one we join.
+        /// Empty for an open room.
+        password: String,
         /// `true` to host (P1), `false` to join (P2).
         host: bool,
         /// Player count (2..=4); 3-4 use the Four Score adapter.
         num_players: u8,
     },
+    /// Send a chat line to the other players (through the signaling room
+    /// while connecting, over the session once in game).
+    Chat {
+        /// The line to send.
+        text: String,
+    },
     /// Leave the current session / abort the handshake (back to single-player).
     Leave,
 }
@@ -54,6 +70,8 @@ pub struct WasmLobbyState {
     signaling_url: String,
     /// Room / lobby code buffer.
     room: String,
+    /// Room password buffer (empty = open room).
+    password: String,
     /// `true` = host (P1), `false` = join (P2).
     host: bool,
     /// Player count (2..=4).
@@ -67,6 +85,10 @@ pub struct WasmLobbyState {
     phase: BrowserNetplayPhase,
     /// Latest status message pushed by the `App`.
     message: String,
+    /// The chat line being typed.
+    chat_draft: String,
+    /// The chat so far, oldest first, each flagged `true` if we sent it.
+    chat: VecDeque<(ChatLine, bool)>,
 }
 
 impl Default for WasmLobbyState {
@@ -75,12 +97,15 @@ impl Default for WasmLobbyState {
             open: false,
             signaling_url: String::new(),
             room: String::new(),
+            password: String::new(),
             host: true,
             num_players: 2,
             seeded: false,
             request: None,
             phase: BrowserNetplayPhase::Idle,
             message: String::new(),
+            chat_draft: String::new(),
+            chat: VecDeque::new(),
         }
     }
 }
@@ -99,6 +124,14 @@ impl WasmLobbyState {
         self.message = message;
     }
 
+    /// Append a chat line to the chat pane; `mine` marks one we sent.
+    pub fn push_chat(&mut self, line: ChatLine, mine: bool) {
+        if self.chat.len() >= CHAT_LINES_KEPT {
+            self.chat.pop_front();
+        }
+        self.chat.push_back((line, mine));
+    }
+
     /// Seed the editable fields from config once (signaling URL + player count).
     fn seed(&mut self, signaling_url: &str, num_players: u8) {
         if self.seeded {
@@ -188,6 +221,16 @@ fn body(ui: &mut egui::Ui, state: &mut WasmLobbyState, config: &mut crate::confi
             );
         });
 
+        ui.horizontal(|ui| {
+            ui.label("password:");
+            ui.add(
+                egui::TextEdit::singleline(&mut state.password)
+                    .password(true)
+                    .hint_text("optional")
+                    .desired_width(160.0),
+            );
+        });
+
         ui.horizontal(|ui| {
             ui.label("role:");
             ui.selectable_value(&mut state.host, true, "Host (P1)");
@@ -229,6 +272,7 @@ fn body(ui: &mut egui::Ui, state: &mut WasmLobbyState, config: &mut crate::confi
                 state.request = Some(LobbyRequest::Connect {
                     signaling_url,
                     room,
+                    password: state.password.clone(),
                     host: state.host,
                     num_players,
                 });
@@ -238,6 +282,12 @@ fn body(ui: &mut egui::Ui, state: &mut WasmLobbyState, config: &mut crate::confi
 
     ui.separator();
 
+    // --- Chat (while connecting or in game) ---
+    if matches!(state.phase, Connecting | InGame) {
+        chat_section(ui, state);
+        ui.separator();
+    }
+
     // --- Leave (enabled while active) ---
     ui.add_enabled_ui(active, |ui| {
         if ui.button("Leave").clicked() {
@@ -265,6 +315,41 @@ fn body(ui: &mut egui::Ui, state: &mut WasmLobbyState, config: &mut crate::confi
     );
 }
 
+/// The chat pane: the lines so far and a one-line composer that queues a
+/// [`LobbyRequest::Chat`] on Send or Enter.
+fn chat_section(ui: &mut egui::Ui, state: &mut WasmLobbyState) {
+    ui.label(egui::RichText::new("Chat").strong());
+    egui::ScrollArea::vertical()
+        .id_salt("lobby-chat")
+        .max_height(96.0)
+        .stick_to_bottom(true)
+        .show(ui, |ui| {
+            for (line, mine) in &state.chat {
+                let who = if *mine {
+                    "you".to_string()
+                } else {
+                    format!("P{}", line.from + 1)
+                };
+                ui.label(format!("{who}: {}", line.text));
+            }
+        });
+    ui.horizontal(|ui| {
+        let edit = ui.add(
+            egui::TextEdit::singleline(&mut state.chat_draft)
+                .hint_text("message")
+                .char_limit(rustynes_netplay::MAX_CHAT_BYTES)
+                .desired_width(240.0),
+        );
+        let entered = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if (ui.button("Send").clicked() || entered) && !state.chat_draft.trim().is_empty() {
+            state.request = Some(LobbyRequest::Chat {
+                text: state.chat_draft.trim().to_string(),
+            });
+            state.chat_draft.clear();
+        }
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -287,6 +372,20 @@ mod tests {
         assert!(s.take_request().is_none());
     }
 
+    #[test]
+    fn chat_pane_keeps_the_newest_lines() {
+        let mut s = WasmLobbyState::default();
+        for i in 0..CHAT_LINES_KEPT + 2 {
+            let line = ChatLine {
+                from: 1,
+                text: format!("line {i}"),
+            };
+            s.push_chat(line, i % 2 == 0);
+        }
+        assert_eq!(s.chat.len(), CHAT_LINES_KEPT);
+        assert_eq!(s.chat.front().map(|(l, _)| l.text.as_str()), Some("line 2"));
+    }
+
     #[test]
     fn set_status_round_trips() {
         let mut s = WasmLobbyState::default();
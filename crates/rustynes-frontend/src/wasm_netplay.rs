@@ -10,8 +10,8 @@
 //! `UdpTransport` / `UdpMeshTransport`; a browser cannot open a raw UDP socket,
 //! so this path drives the SAME session core over a [`WebRtcMeshTransport`]
 //! instead, with each peer-to-peer WebRTC connection brokered through a
-//! WebSocket **signaling server** (the reference server is `rustynes-netplay`'s
-//! `signaling_server` example — see `docs/netplay-webrtc.md`).
+//! WebSocket **signaling server** (the `rustynes-lobby` server — see
+//! `docs/netplay-webrtc.md`).
 //!
 //! **What is verified:** this module COMPILES on `wasm32-unknown-unknown` for
 //! both the `wasm-winit` and `wasm-canvas` frontends, and the signaling +
@@ -25,7 +25,8 @@
 //!
 //! 1. [`BrowserNetplay::connect`] opens a `WebSocket` to the signaling server
 //!    and `join`s a room (by code) announcing the ROM hash + desired
-//!    `max_players`.
+//!    `max_players` (plus, for the room's first joiner, the game title shown in
+//!    the room list and an optional password locking the room).
 //! 2. The signaling server assigns the next free **slot** (`0..max_players`) and
 //!    reports each higher-slot newcomer to the already-present peers
 //!    (`peer-joined { slot }`).
@@ -38,6 +39,10 @@
 //!    [`BrowserNetplay::tick`] drives the session each rAF frame, exactly like
 //!    the native path drives it.
 //!
+//! Players can talk throughout: [`BrowserNetplay::send_chat`] relays a line
+//! through the signaling room while connecting, and over the session's own
+//! acknowledged chat once in game.
+//!
 //! All callbacks use the **safe** `Closure::wrap` / `JsCast` web-sys patterns;
 //! `#![forbid(unsafe_code)]` holds across the crate.
 //!
@@ -49,8 +54,8 @@ use std::rc::Rc;
 
 use rustynes_core::{Buttons, Nes};
 use rustynes_netplay::{
-    AdvanceOutcome, NetplayError, RollbackSession, SessionConfig, SignalMessage,
-    WebRtcMeshTransport,
+    AdvanceOutcome, ChatError, ChatLine, ChatRateLimit, NetplayError, RollbackSession,
+    SessionConfig, SignalMessage, WebRtcMeshTransport,
 };
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{JsCast, JsValue};
@@ -105,6 +110,9 @@ struct Shared {
     ice_servers: Vec<String>,
     /// A short status / error message for the HUD.
     message: String,
+    /// Lobby chat lines relayed by the signaling room, not yet taken by
+    /// [`BrowserNetplay::take_chat`].
+    chat: Vec<ChatLine>,
 }
 
 impl Shared {
@@ -122,6 +130,8 @@ impl Shared {
 pub struct BrowserNetplay {
     shared: Rc<RefCell<Shared>>,
     rom_hash: [u8; 32],
+    /// The game title a room we open is listed under.
+    game: String,
     /// The rollback session, once every data channel is open.
     session: Option<RollbackSession<WebRtcMeshTransport>>,
     config: SessionConfig,
@@ -129,6 +139,11 @@ pub struct BrowserNetplay {
     // the socket / closures would tear down signaling.
     keepalive_socket: Option<WebSocket>,
     keepalive_closures: Vec<Closure<dyn FnMut(JsValue)>>,
+    /// Ticks driven so far: the clock of the lobby chat rate limit.
+    ticks: u32,
+    /// The rate limit on lobby chat sent before the session starts (the
+    /// session limits its own).
+    lobby_chat_limit: ChatRateLimit,
 }
 
 impl BrowserNetplay {
@@ -138,10 +153,18 @@ impl BrowserNetplay {
         Self {
             shared: Rc::new(RefCell::new(Shared::default())),
             rom_hash,
+            game: String::new(),
             session: None,
-            config: SessionConfig::default(),
+            // A desync is repaired by a state transfer from the host (peer 0)
+            // instead of ending the match.
+            config: SessionConfig {
+                desync_recovery: true,
+                ..SessionConfig::default()
+            },
             keepalive_socket: None,
             keepalive_closures: Vec::new(),
+            ticks: 0,
+            lobby_chat_limit: ChatRateLimit::default(),
         }
     }
 
@@ -164,6 +187,48 @@ impl BrowserNetplay {
         self.shared.borrow().message.clone()
     }
 
+    /// Our slot in the room (the local player index once in game).
+    #[must_use]
+    pub fn slot(&self) -> u8 {
+        self.shared.borrow().slot
+    }
+
+    /// Send a chat line to the other players: over the session once in game,
+    /// else through the signaling room while connecting. Returns `Ok(false)`
+    /// (sending nothing) when idle or the socket refuses the frame.
+    ///
+    /// # Errors
+    ///
+    /// A [`ChatError`] for a line that is empty, too long or contains a
+    /// control character, or that the rate limit refuses.
+    pub fn send_chat(&mut self, text: &str) -> Result<bool, ChatError> {
+        if let Some(session) = self.session.as_mut() {
+            return session.send_chat(text).map(|()| true);
+        }
+        let Some(ws) = self.keepalive_socket.as_ref() else {
+            return Ok(false);
+        };
+        rustynes_netplay::chat::validate(text)?;
+        if !self.lobby_chat_limit.allow(self.ticks) {
+            return Err(ChatError::RateLimited);
+        }
+        let msg = SignalMessage::Chat {
+            from: self.shared.borrow().slot,
+            text: text.to_owned(),
+        };
+        Ok(ws.send_with_str(&msg.to_json()).is_ok())
+    }
+
+    /// The chat lines received since the last call — relayed by the
+    /// signaling room, then from the session — oldest first.
+    pub fn take_chat(&mut self) -> Vec<ChatLine> {
+        let mut lines = std::mem::take(&mut self.shared.borrow_mut().chat);
+        if let Some(session) = self.session.as_mut() {
+            lines.extend(session.take_chat());
+        }
+        lines
+    }
+
     /// Set the number of players (2..=4) for the session. 3-4 players
     /// auto-enable the Four Score adapter in the session core and form a full
     /// WebRTC mesh (every peer connected to every other). Clamped into `2..=4`.
@@ -171,10 +236,20 @@ impl BrowserNetplay {
         self.config.num_players = num_players.clamp(2, 4);
     }
 
+    /// Set the game title a room we open is listed under in the lobby's room
+    /// list. Joining an existing room keeps its title.
+    pub fn set_game(&mut self, title: &str) {
+        self.game = title.to_owned();
+    }
+
     /// Begin a browser netplay session: open the WebSocket to `signaling_url`,
     /// join `room` (announcing the desired player count), and wire the N-peer
     /// WebRTC mesh handshake. The local player is the signaling slot.
     ///
+    /// `password` locks a room we open (empty leaves it open); joining a
+    /// locked room needs the same one, else the server answers with a "wrong
+    /// password" error.
+    ///
     /// `ice_servers` is the STUN/TURN server URL list for each peer connection's
     /// `RtcConfiguration` (NAT traversal); pass the configured `[netplay]
     /// stun_servers`, or [`rustynes_netplay::DEFAULT_STUN_SERVERS`] for the public
@@ -190,6 +265,7 @@ impl BrowserNetplay {
         &mut self,
         signaling_url: &str,
         room: &str,
+        password: &str,
         ice_servers: &[String],
     ) -> Result<(), JsValue> {
         {
@@ -223,12 +299,16 @@ impl BrowserNetplay {
         let ws_for_open = ws.clone();
         let room_owned = room.to_string();
         let rom_hex_open = hex32(&self.rom_hash);
+        let game = self.game.clone();
+        let password = password.to_owned();
         let max_players = self.config.num_players;
         let on_open = Closure::<dyn FnMut(JsValue)>::wrap(Box::new(move |_ev: JsValue| {
             let join = SignalMessage::Join {
                 room: room_owned.clone(),
                 rom_hash: rom_hex_open.clone(),
                 max_players,
+                game: game.clone(),
+                password: password.clone(),
             };
             let _ = ws_for_open.send_with_str(&join.to_json());
         }));
@@ -262,6 +342,7 @@ impl BrowserNetplay {
     /// Returns `true` if netplay consumed this tick (so the caller must NOT also
     /// call `nes.run_frame()`).
     pub fn tick(&mut self, nes: &mut Nes, local_buttons: Buttons) -> bool {
+        self.ticks = self.ticks.wrapping_add(1);
         match self.phase() {
             BrowserNetplayPhase::Idle => false,
             BrowserNetplayPhase::Error => true,
@@ -271,7 +352,7 @@ impl BrowserNetplay {
             }
             BrowserNetplayPhase::InGame => {
                 if let Some(session) = self.session.as_mut() {
-                    session.add_local_input(local_buttons);
+                    let _ = session.add_local_input(local_buttons);
                     match session.advance(nes) {
                         Ok(AdvanceOutcome { .. }) => {}
                         Err(e) => {
@@ -377,6 +458,7 @@ fn new_peer_connection(servers: &[String]) -> Result<RtcPeerConnection, JsValue>
 /// - `Offer { from, .. }`: a lower-slot peer offered to us — we answer.
 /// - `Answer { from, .. }` / `Candidate { from, .. }`: feed the leg to peer
 ///   `from`.
+/// - `Chat { from, text }`: queue a lobby chat line for the UI.
 fn handle_signal(ws: &WebSocket, shared: &Rc<RefCell<Shared>>, msg: SignalMessage) {
     match msg {
         SignalMessage::Joined { slot, max_players } => {
@@ -417,6 +499,18 @@ fn handle_signal(ws: &WebSocket, shared: &Rc<RefCell<Shared>>, msg: SignalMessag
             s.phase = BrowserNetplayPhase::Error;
             s.message = format!("peer {slot} left");
         }
+        // Lobby chat relayed by the room. The server checks the line too;
+        // a peer's own line is never echoed, so one stamped with our slot
+        // is stale.
+        SignalMessage::Chat { from, text } => {
+            let mut s = shared.borrow_mut();
+            if from != s.slot
+                && rustynes_netplay::chat::validate(&text).is_ok()
+                && s.chat.len() < rustynes_netplay::chat::MAX_CHAT_INBOX
+            {
+                s.chat.push(ChatLine { from, text });
+            }
+        }
         SignalMessage::Error { reason } => {
             let mut s = shared.borrow_mut();
             s.phase = BrowserNetplayPhase::Error;
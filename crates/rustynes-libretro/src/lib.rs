@@ -43,7 +43,8 @@
 //!   intentionally NOT linked into this core, since it would be redundant with (and
 //!   conflict with) RetroArch's own equivalent systems.
 //! - **FDS**: `.fds` disk images are routed to [`rustynes_core::Nes::from_disk`] (looking
-//!   up `disksys.rom` in the frontend's system directory), and multi-side disk swapping is
+//!   up `disksys.rom` in the frontend's system directory, else falling back to the built-in
+//!   HLE BIOS when the `rustynes_fds_hle_bios` core option is on), and multi-side disk swapping is
 //!   exposed through libretro's disk-control interface (`on_set_eject_state` et al.).
 //! - **Cheats**: Native Game Genie code application via `on_cheat_set`/`on_cheat_reset`,
 //!   backed by [`rustynes_core::Nes::add_genie_code`] (excluded from serialized state, so
@@ -69,13 +70,7 @@
 #![allow(clippy::ptr_as_ptr)]
 #![warn(missing_docs)]
 
-use rust_libretro::{
-    contexts::*,
-    core::{Core, CoreOptions},
-    retro_core,
-    sys::*,
-    types::*,
-};
+use rust_libretro::{contexts::*, core::Core, proc::CoreOptions, retro_core, sys::*, types::*};
 use rustynes_core::{Emu, Nes, Region, VsDualSystem};
 use std::collections::BTreeMap;
 use std::ffi::{CStr, CString};
@@ -213,11 +208,27 @@ static CONTROLLER_INFO: ControllerTables = ControllerTables {
 /// and SRAM are registered under. See `register_memory_maps`.
 const PPU_ADDRSPACE: &CStr = c"PPU";
 
+/// Core option that lets an FDS disk boot on the built-in HLE BIOS when the
+/// system directory has no `disksys.rom`. Off by default, like the desktop
+/// frontend's `fds.hle_bios`.
+const FDS_HLE_BIOS_OPTION: &str = "rustynes_fds_hle_bios";
+
 /// The central libretro core structure for RustyNES.
 ///
 /// This struct holds the underlying cycle-accurate `Nes` emulator instance alongside
 /// the operational buffers necessary to interface with libretro's batch APIs. It is
 /// statically instantiated via the `retro_core!` macro.
+#[derive(CoreOptions)]
+#[options({
+    "rustynes_fds_hle_bios",
+    "FDS built-in BIOS",
+    "Boot Famicom Disk System images on the built-in high-level-emulated BIOS when the system directory has no disksys.rom. Applies from the next disk load.",
+    {
+        { "disabled" },
+        { "enabled" },
+    },
+    "disabled"
+})]
 pub struct RustyNesLibretro {
     /// The cycle-accurate RustyNES core instance (single-console carts).
     ///
@@ -298,8 +309,6 @@ impl Default for RustyNesLibretro {
     }
 }
 
-impl CoreOptions for RustyNesLibretro {}
-
 #[repr(C)]
 struct RetroGameInfoExt {
     full_path: *const std::os::raw::c_char,
@@ -929,13 +938,25 @@ impl Core for RustyNesLibretro {
                 .get_system_directory()
                 .ok_or("Frontend did not provide a system directory for the FDS BIOS")?;
             let bios_path = bios_dir.join("disksys.rom");
-            let bios = std::fs::read(&bios_path).map_err(|e| {
-                format!(
-                    "Missing FDS BIOS at {}: {e} (RustyNES needs disksys.rom in the \
-                     frontend's system directory to boot Famicom Disk System games)",
-                    bios_path.display()
-                )
-            })?;
+            // Without a disksys.rom in the system directory, boot with the
+            // clean-room HLE BIOS only when the core option allows it.
+            let bios = match std::fs::read(&bios_path) {
+                Ok(bytes) => bytes,
+                Err(e) if generic_ctx.get_variable(FDS_HLE_BIOS_OPTION) == Some("enabled") => {
+                    eprintln!(
+                        "[RustyNES] No FDS BIOS at {} ({e}); booting with the built-in HLE BIOS",
+                        bios_path.display()
+                    );
+                    rustynes_core::rustynes_mappers::fds_hle_bios()
+                }
+                Err(e) => {
+                    return Err(format!(
+                        "No FDS BIOS at {} ({e}); add disksys.rom or enable the \"FDS built-in BIOS\" core option",
+                        bios_path.display()
+                    )
+                    .into());
+                }
+            };
             match Nes::from_disk(&rom_data, &bios) {
                 Ok(nes) => Emu::Single(Box::new(nes)),
                 Err(e) => {
@@ -0,0 +1,223 @@
+//! The lobby's TOML configuration.
+//!
+//! Every key is optional; an empty file (or none at all) runs the defaults,
+//! which listen on localhost only:
+//!
+//! ```toml
+//! [signaling]
+//! listen = "127.0.0.1:9000"    # WebSocket signaling rooms
+//! max_clients = 4096           # connections served at once
+//! max_rooms = 1024             # rooms open at once
+//! max_message_bytes = 65536    # largest signaling frame accepted
+//! ping_interval_ms = 5000      # how often each client's round trip is measured
+//! outbound_queue = 256         # messages queued for a client before it is cut off
+//! password_failures_per_minute = 5  # wrong room passwords per address (0 = no limit)
+//!
+//! [rendezvous]
+//! enabled = true
+//! listen = "127.0.0.1:3478"    # STUN binding responder (UDP)
+//! ```
+//!
+//! Unknown keys are rejected, so a misspelled setting fails loudly instead of
+//! silently running the default.
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// A configuration file that could not be loaded.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// The file could not be read.
+    #[error("cannot read {}: {source}", path.display())]
+    Io {
+        /// The file that was asked for.
+        path: PathBuf,
+        /// What went wrong.
+        source: std::io::Error,
+    },
+    /// The file is not a valid lobby configuration.
+    #[error("invalid lobby config: {0}")]
+    Parse(#[from] toml::de::Error),
+}
+
+/// The whole lobby configuration.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct LobbyConfig {
+    /// The WebSocket signaling server.
+    pub signaling: SignalingConfig,
+    /// The UDP rendezvous (STUN) responder.
+    pub rendezvous: RendezvousConfig,
+}
+
+/// The `[signaling]` table.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SignalingConfig {
+    /// The address the WebSocket server listens on.
+    pub listen: SocketAddr,
+    /// The most connections served at once; further ones are dropped on
+    /// accept.
+    pub max_clients: usize,
+    /// The most rooms open at once; a joiner that would open another gets a
+    /// "server full" error.
+    pub max_rooms: usize,
+    /// The largest WebSocket message accepted, in bytes. Signaling frames are
+    /// small (an SDP blob is a few KiB); the cap stops a client from making
+    /// the server buffer an arbitrarily large one.
+    pub max_message_bytes: usize,
+    /// How often each client is pinged to measure its round trip, in
+    /// milliseconds. The room list reports each room host's.
+    pub ping_interval_ms: u64,
+    /// The most messages queued for one client. A client that falls this
+    /// far behind is not reading its socket, so it is disconnected rather
+    /// than buffered for without bound.
+    pub outbound_queue: usize,
+    /// The most wrong room passwords one address may send in a minute. Past
+    /// it, that address's joins are refused until the minute is up, so a
+    /// locked room's password cannot be guessed at connection speed. `0`
+    /// turns the limit off.
+    pub password_failures_per_minute: u32,
+}
+
+impl Default for SignalingConfig {
+    fn default() -> Self {
+        Self {
+            listen: SocketAddr::from((Ipv4Addr::LOCALHOST, 9000)),
+            max_clients: 4096,
+            max_rooms: 1024,
+            max_message_bytes: 64 * 1024,
+            ping_interval_ms: 5000,
+            outbound_queue: 256,
+            password_failures_per_minute: 5,
+        }
+    }
+}
+
+impl SignalingConfig {
+    /// [`ping_interval_ms`](Self::ping_interval_ms) as a [`Duration`], never
+    /// below one millisecond (a zero interval would spin).
+    #[must_use]
+    pub fn ping_interval(&self) -> Duration {
+        Duration::from_millis(self.ping_interval_ms.max(1))
+    }
+}
+
+/// The `[rendezvous]` table.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RendezvousConfig {
+    /// Whether to answer STUN binding requests at all. Turn it off when a
+    /// separate STUN/TURN server (e.g. coturn) already covers the port.
+    pub enabled: bool,
+    /// The UDP address the responder listens on. Native clients list it in
+    /// their `stun_servers`.
+    pub listen: SocketAddr,
+}
+
+impl Default for RendezvousConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            listen: SocketAddr::from((Ipv4Addr::LOCALHOST, 3478)),
+        }
+    }
+}
+
+impl LobbyConfig {
+    /// Parse a configuration from TOML text.
+    ///
+    /// # Errors
+    ///
+    /// [`ConfigError::Parse`] for malformed TOML, a mistyped value or an
+    /// unknown key.
+    pub fn from_toml(text: &str) -> Result<Self, ConfigError> {
+        Ok(toml::from_str(text)?)
+    }
+
+    /// Read and parse the configuration file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// [`ConfigError::Io`] if the file cannot be read, else as
+    /// [`from_toml`](Self::from_toml).
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_toml(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_runs_the_defaults() {
+        assert_eq!(LobbyConfig::from_toml("").unwrap(), LobbyConfig::default());
+    }
+
+    #[test]
+    fn tables_override_only_what_they_name() {
+        let cfg = LobbyConfig::from_toml(
+            r#"
+            [signaling]
+            listen = "0.0.0.0:9100"
+            max_rooms = 8
+
+            [rendezvous]
+            enabled = false
+            "#,
+        )
+        .unwrap();
+        assert_eq!(cfg.signaling.listen, "0.0.0.0:9100".parse().unwrap());
+        assert_eq!(cfg.signaling.max_rooms, 8);
+        assert_eq!(
+            cfg.signaling.max_clients,
+            SignalingConfig::default().max_clients
+        );
+        assert!(!cfg.rendezvous.enabled);
+        assert_eq!(cfg.rendezvous.listen, RendezvousConfig::default().listen);
+    }
+
+    #[test]
+    fn mistakes_are_rejected() {
+        for bad in [
+            "[signaling]\nlistne = \"0.0.0.0:9000\"",
+            "[signaling]\nlisten = \"not an address\"",
+            "[signaling]\nmax_rooms = -1",
+            "[turn]\nenabled = true",
+        ] {
+            assert!(
+                matches!(LobbyConfig::from_toml(bad), Err(ConfigError::Parse(_))),
+                "accepted {bad:?}"
+            );
+        }
+        assert!(matches!(
+            LobbyConfig::load(Path::new("/nonexistent/lobby.toml")),
+            Err(ConfigError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn deploy_config_parses() {
+        let cfg = LobbyConfig::from_toml(include_str!("../../../deploy/lobby.toml")).unwrap();
+        assert_eq!(cfg.signaling.listen, "0.0.0.0:9000".parse().unwrap());
+        assert_eq!(cfg.rendezvous.listen, "0.0.0.0:3479".parse().unwrap());
+    }
+
+    #[test]
+    fn ping_interval_is_never_zero() {
+        let cfg = SignalingConfig {
+            ping_interval_ms: 0,
+            ..SignalingConfig::default()
+        };
+        assert_eq!(cfg.ping_interval(), Duration::from_millis(1));
+    }
+}
@@ -0,0 +1,42 @@
+//! The netplay lobby: a headless server players point their
+//! emulators at to find each other.
+//!
+//! One process serves:
+//!
+//! - **Signaling rooms** over WebSocket — the room/relay protocol of
+//!   [`rustynes_netplay::signaling`]: browsers exchange their WebRTC
+//!   offers/answers/ICE candidates through it, native clients their public
+//!   addresses. It carries no gameplay traffic.
+//! - **A public room list** — each open room's code, game title and ROM hash,
+//!   player count, whether it needs a password, and its host's round trip to
+//!   the lobby (measured with WebSocket pings).
+//! - **Room passwords** — the first joiner may lock a room; later joiners must
+//!   match the password.
+//! - **A UDP rendezvous** — a STUN binding responder, so native clients can
+//!   discover their public address for hole punching from the lobby itself
+//!   (list its address in their `stun_servers`).
+//!
+//! Everything is set in one TOML file (see [`config`]).
+//!
+//! # Run
+//!
+//! ```text
+//! cargo run -p rustynes-lobby -- --config lobby.toml
+//! # or with the defaults (localhost only), overriding the listen address:
+//! cargo run -p rustynes-lobby -- --listen 0.0.0.0:9000 --rendezvous 0.0.0.0:3478
+//! ```
+//!
+//! The lobby speaks plain `ws://`; put a TLS-terminating proxy in front of it
+//! for browsers on `https://` pages (see `deploy/`). Rooms live in memory, so
+//! every player of a match must reach the same instance.
+//!
+//! # Embedding
+//!
+//! [`Lobby::bind`] then [`Lobby::run`] inside any tokio runtime; the
+//! end-to-end tests run it that way on localhost.
+
+pub mod config;
+mod server;
+
+pub use config::{ConfigError, LobbyConfig, RendezvousConfig, SignalingConfig};
+pub use server::Lobby;
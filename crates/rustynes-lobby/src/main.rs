@@ -0,0 +1,66 @@
+//! `rustynes-lobby`: run the netplay lobby server (see the library docs).
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use rustynes_lobby::{Lobby, LobbyConfig};
+
+/// Headless netplay lobby: WebSocket signaling rooms, a public room
+/// list and a STUN rendezvous for native hole punching.
+#[derive(Parser)]
+#[command(name = "rustynes-lobby", version)]
+struct Args {
+    /// The TOML configuration file. Every key is optional; without a file the
+    /// lobby listens on localhost only.
+    #[arg(short, long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Serve WebSocket signaling on this address (overrides `[signaling] listen`).
+    #[arg(long, value_name = "ADDR")]
+    listen: Option<SocketAddr>,
+    /// Answer STUN on this UDP address (overrides `[rendezvous] listen`).
+    #[arg(long, value_name = "ADDR", conflicts_with = "no_rendezvous")]
+    rendezvous: Option<SocketAddr>,
+    /// Do not run the STUN rendezvous.
+    #[arg(long)]
+    no_rendezvous: bool,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    let mut config = match args.config.as_deref().map(LobbyConfig::load) {
+        None => LobbyConfig::default(),
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            eprintln!("rustynes-lobby: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(listen) = args.listen {
+        config.signaling.listen = listen;
+    }
+    if let Some(rendezvous) = args.rendezvous {
+        config.rendezvous.listen = rendezvous;
+    }
+    if args.no_rendezvous {
+        config.rendezvous.enabled = false;
+    }
+
+    let lobby = match Lobby::bind(&config).await {
+        Ok(lobby) => lobby,
+        Err(e) => {
+            eprintln!("rustynes-lobby: cannot bind: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Ok(addr) = lobby.local_addr() {
+        println!("signaling on ws://{addr}/");
+    }
+    if let Some(addr) = lobby.rendezvous_addr() {
+        println!("STUN rendezvous on udp://{addr}");
+    }
+    lobby.run().await;
+    ExitCode::SUCCESS
+}
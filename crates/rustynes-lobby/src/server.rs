@@ -0,0 +1,451 @@
+//! The async plumbing around the pure [`Relay`]: a WebSocket server feeding it
+//! frames and fanning its [`Action`]s back out, a ping loop measuring each
+//! client's round trip, and a UDP task answering STUN binding requests.
+//!
+//! Two limits keep one client from costing the others: each client's outbound
+//! queue is bounded (a client that stops reading is disconnected once it
+//! fills), and wrong room passwords are counted per source address so a
+//! locked room cannot be brute-forced by reconnecting.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use futures_util::{SinkExt, StreamExt};
+use rustynes_netplay::build_binding_response;
+use rustynes_netplay::signaling::{Action, ClientId, Relay, SignalMessage, WRONG_PASSWORD};
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+use tokio::sync::{Mutex, Notify, mpsc};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
+
+use crate::config::{LobbyConfig, SignalingConfig};
+
+/// How long to back off after a failed `accept` (e.g. out of file
+/// descriptors) before trying again.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// The window [`SignalingConfig::password_failures_per_minute`] counts over.
+const PASSWORD_WINDOW: Duration = Duration::from_mins(1);
+
+/// The rejection a join gets while its address is throttled.
+const TOO_MANY_ATTEMPTS: &str = "too many password attempts";
+
+/// A bound lobby, ready to [`run`](Self::run).
+///
+/// Binding and serving are separate steps so the caller learns the actual
+/// addresses first — a config asking for port 0 gets an ephemeral one.
+pub struct Lobby {
+    listener: TcpListener,
+    rendezvous: Option<UdpSocket>,
+    config: SignalingConfig,
+}
+
+/// State shared by every connection task.
+struct Shared {
+    relay: Mutex<Relay>,
+    /// `client id -> outbound queue`. Each connection's writer task drains
+    /// its receiver.
+    outbound: Mutex<HashMap<ClientId, Outbound>>,
+    /// Wrong room passwords per source address.
+    password_failures: Mutex<PasswordThrottle>,
+    /// Connections currently served (checked against `max_clients`).
+    clients: AtomicUsize,
+    /// The origin of the ping timestamps.
+    epoch: Instant,
+    config: SignalingConfig,
+}
+
+impl Shared {
+    fn new(config: SignalingConfig) -> Self {
+        Self {
+            relay: Mutex::new(Relay::new().with_max_rooms(config.max_rooms)),
+            outbound: Mutex::new(HashMap::new()),
+            password_failures: Mutex::new(PasswordThrottle::new(
+                config.password_failures_per_minute,
+            )),
+            clients: AtomicUsize::new(0),
+            epoch: Instant::now(),
+            config,
+        }
+    }
+}
+
+/// One client's outbound queue, and the signal that cuts the client off when
+/// the queue overflows.
+struct Outbound {
+    tx: mpsc::Sender<SignalMessage>,
+    /// Notified once the client is dropped for falling behind; its reader
+    /// then ends the connection.
+    kick: Arc<Notify>,
+}
+
+/// Wrong room passwords counted per source address in fixed
+/// [`PASSWORD_WINDOW`]s.
+struct PasswordThrottle {
+    /// Failures allowed per window (`0` = unlimited).
+    limit: u32,
+    /// `address -> (window start, failures in it)`.
+    failures: HashMap<IpAddr, (Instant, u32)>,
+}
+
+impl PasswordThrottle {
+    fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            failures: HashMap::new(),
+        }
+    }
+
+    /// Whether joins from `ip` are refused at `now`.
+    fn blocked(&self, ip: IpAddr, now: Instant) -> bool {
+        self.limit > 0
+            && self.failures.get(&ip).is_some_and(|&(start, count)| {
+                now.duration_since(start) < PASSWORD_WINDOW && count >= self.limit
+            })
+    }
+
+    /// Count a wrong password from `ip` at `now`. Expired windows are
+    /// forgotten here, so the table only holds recently failing addresses.
+    fn record_failure(&mut self, ip: IpAddr, now: Instant) {
+        if self.limit == 0 {
+            return;
+        }
+        self.failures
+            .retain(|_, (start, _)| now.duration_since(*start) < PASSWORD_WINDOW);
+        let (_, count) = self.failures.entry(ip).or_insert((now, 0));
+        *count = count.saturating_add(1);
+    }
+}
+
+impl Lobby {
+    /// Bind the signaling listener and, if enabled, the rendezvous socket.
+    ///
+    /// # Errors
+    ///
+    /// Any error binding either address.
+    pub async fn bind(config: &LobbyConfig) -> io::Result<Self> {
+        let listener = TcpListener::bind(config.signaling.listen).await?;
+        let rendezvous = if config.rendezvous.enabled {
+            Some(UdpSocket::bind(config.rendezvous.listen).await?)
+        } else {
+            None
+        };
+        Ok(Self {
+            listener,
+            rendezvous,
+            config: config.signaling.clone(),
+        })
+    }
+
+    /// The address the WebSocket signaling server listens on.
+    ///
+    /// # Errors
+    ///
+    /// As [`TcpListener::local_addr`].
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// The address the STUN rendezvous answers on, if it is enabled.
+    #[must_use]
+    pub fn rendezvous_addr(&self) -> Option<SocketAddr> {
+        self.rendezvous.as_ref().and_then(|s| s.local_addr().ok())
+    }
+
+    /// Serve until the task is dropped. Each connection runs on its own
+    /// task; a connection past `max_clients` is dropped on accept.
+    pub async fn run(self) {
+        let shared = Arc::new(Shared::new(self.config));
+        if let Some(socket) = self.rendezvous {
+            tokio::spawn(serve_rendezvous(socket));
+        }
+
+        // Monotonic client-id allocator.
+        let mut next_id: ClientId = 0;
+        loop {
+            let (stream, peer) = match self.listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    eprintln!("accept failed: {e}");
+                    tokio::time::sleep(ACCEPT_BACKOFF).await;
+                    continue;
+                }
+            };
+            if shared.clients.load(Ordering::Relaxed) >= shared.config.max_clients {
+                eprintln!(
+                    "refusing {peer}: {} clients connected",
+                    shared.config.max_clients
+                );
+                continue;
+            }
+            shared.clients.fetch_add(1, Ordering::Relaxed);
+            let id = next_id;
+            next_id += 1;
+            let shared = Arc::clone(&shared);
+            tokio::spawn(async move {
+                if let Err(e) = handle_connection(&shared, stream, id, peer.ip()).await {
+                    eprintln!("client {id} ({peer}) ended: {e}");
+                }
+                shared.clients.fetch_sub(1, Ordering::Relaxed);
+            });
+        }
+    }
+}
+
+/// Drive one WebSocket connection: upgrade, register an outbound channel, then
+/// loop reading text frames -> [`Relay::handle`] -> dispatch actions, and
+/// pongs -> [`Relay::record_ping`], until the client leaves or is kicked for
+/// not reading.
+async fn handle_connection(
+    shared: &Shared,
+    stream: TcpStream,
+    id: ClientId,
+    ip: IpAddr,
+) -> Result<(), tokio_tungstenite::tungstenite::Error> {
+    let limit = Some(shared.config.max_message_bytes);
+    let ws_config = WebSocketConfig::default()
+        .max_message_size(limit)
+        .max_frame_size(limit);
+    let ws = tokio_tungstenite::accept_async_with_config(stream, Some(ws_config)).await?;
+    let (mut write, mut read) = ws.split();
+
+    // Per-connection outbound queue: any task routes messages here; the
+    // writer drains them onto the socket, interleaved with the pings.
+    let (tx, mut rx) = mpsc::channel::<SignalMessage>(shared.config.outbound_queue.max(1));
+    let kick = Arc::new(Notify::new());
+    shared.outbound.lock().await.insert(
+        id,
+        Outbound {
+            tx,
+            kick: Arc::clone(&kick),
+        },
+    );
+    let epoch = shared.epoch;
+    let mut ping = tokio::time::interval(shared.config.ping_interval());
+    let writer = tokio::spawn(async move {
+        loop {
+            let frame = tokio::select! {
+                msg = rx.recv() => match msg {
+                    Some(msg) => Message::Text(msg.to_json().into()),
+                    None => break,
+                },
+                _ = ping.tick() => Message::Ping(micros_since(epoch).to_be_bytes().to_vec().into()),
+            };
+            if write.send(frame).await.is_err() {
+                break;
+            }
+        }
+        let _ = write.close().await;
+    });
+
+    loop {
+        let frame = tokio::select! {
+            frame = read.next() => frame,
+            () = kick.notified() => {
+                eprintln!("client {id} ({ip}) dropped: outbound queue full");
+                break;
+            }
+        };
+        let Some(Ok(frame)) = frame else { break };
+        match frame {
+            Message::Text(txt) => {
+                // Unparseable frames are dropped (never panic).
+                if let Some(msg) = SignalMessage::parse(txt.as_str()) {
+                    let actions = handle_message(shared, id, ip, msg).await;
+                    dispatch(shared, actions).await;
+                }
+            }
+            Message::Pong(payload) => {
+                if let Some(ms) = round_trip_ms(epoch, &payload) {
+                    shared.relay.lock().await.record_ping(id, ms);
+                }
+            }
+            Message::Close(_) => break,
+            Message::Ping(_) | Message::Binary(_) | Message::Frame(_) => {}
+        }
+    }
+
+    // Disconnect: notify the room, clean up, drop the outbound sender.
+    let actions = shared.relay.lock().await.disconnect(id);
+    dispatch(shared, actions).await;
+    shared.outbound.lock().await.remove(&id);
+    writer.abort();
+    Ok(())
+}
+
+/// Run one parsed message through the relay. A join from an address that
+/// has sent too many wrong passwords is refused without reaching it, and a
+/// join the relay turns away for a wrong password counts against the
+/// address.
+async fn handle_message(
+    shared: &Shared,
+    id: ClientId,
+    ip: IpAddr,
+    msg: SignalMessage,
+) -> Vec<Action> {
+    let is_join = matches!(msg, SignalMessage::Join { .. });
+    if is_join
+        && shared
+            .password_failures
+            .lock()
+            .await
+            .blocked(ip, Instant::now())
+    {
+        return vec![
+            Action::Send {
+                to: id,
+                msg: SignalMessage::Error {
+                    reason: TOO_MANY_ATTEMPTS.into(),
+                },
+            },
+            Action::Close { who: id },
+        ];
+    }
+    let actions = shared.relay.lock().await.handle(id, msg);
+    let wrong_password = actions.iter().any(|a| {
+        matches!(a, Action::Send { to, msg: SignalMessage::Error { reason } }
+            if *to == id && reason == WRONG_PASSWORD)
+    });
+    if is_join && wrong_password {
+        shared
+            .password_failures
+            .lock()
+            .await
+            .record_failure(ip, Instant::now());
+    }
+    actions
+}
+
+/// Perform the relay's [`Action`]s: route each `Send` to the target client's
+/// outbound channel; a `Close` drops the channel (its writer then closes the
+/// socket). A client whose queue is full (or whose writer has gone) is
+/// dropped and kicked instead.
+async fn dispatch(shared: &Shared, actions: Vec<Action>) {
+    let mut outbound = shared.outbound.lock().await;
+    for action in actions {
+        match action {
+            Action::Send { to, msg } => {
+                if let Some(client) = outbound.get(&to)
+                    && client.tx.try_send(msg).is_err()
+                    && let Some(client) = outbound.remove(&to)
+                {
+                    client.kick.notify_one();
+                }
+            }
+            Action::Close { who } => {
+                outbound.remove(&who);
+            }
+        }
+    }
+}
+
+/// Answer every STUN binding request on `socket` with the address it came
+/// from. Anything else is ignored; errors (e.g. an ICMP unreachable reported
+/// on the next receive) never stop the loop.
+async fn serve_rendezvous(socket: UdpSocket) {
+    let mut buf = [0u8; 576];
+    loop {
+        match socket.recv_from(&mut buf).await {
+            Ok((len, from)) => {
+                if let Some(response) = build_binding_response(&buf[..len], from) {
+                    let _ = socket.send_to(&response, from).await;
+                }
+            }
+            Err(e) => eprintln!("rendezvous receive failed: {e}"),
+        }
+    }
+}
+
+/// Microseconds since `epoch`: the ping payload.
+fn micros_since(epoch: Instant) -> u64 {
+    u64::try_from(epoch.elapsed().as_micros()).unwrap_or(u64::MAX)
+}
+
+/// The round trip a pong reports, in milliseconds: now minus the timestamp
+/// its ping carried. `None` for a payload that is not one of our pings. A
+/// client can only misreport its own ping this way.
+fn round_trip_ms(epoch: Instant, payload: &[u8]) -> Option<u32> {
+    let sent = u64::from_be_bytes(payload.try_into().ok()?);
+    let micros = micros_since(epoch).checked_sub(sent)?;
+    Some(u32::try_from(micros / 1000).unwrap_or(u32::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_comes_from_the_ping_timestamp() {
+        let epoch = Instant::now().checked_sub(Duration::from_secs(10)).unwrap();
+        let sent = micros_since(epoch) - 25_000;
+        let ms = round_trip_ms(epoch, &sent.to_be_bytes()).unwrap();
+        assert!((25..1000).contains(&ms), "{ms}");
+        // Not a ping of ours: wrong length, or a timestamp from the future.
+        assert_eq!(round_trip_ms(epoch, b"hello"), None);
+        assert_eq!(round_trip_ms(epoch, &u64::MAX.to_be_bytes()), None);
+    }
+
+    #[test]
+    fn password_failures_block_an_address_for_the_window() {
+        let now = Instant::now();
+        let (guesser, other) = (IpAddr::from([10, 0, 0, 1]), IpAddr::from([10, 0, 0, 2]));
+        let mut throttle = PasswordThrottle::new(2);
+        throttle.record_failure(guesser, now);
+        assert!(!throttle.blocked(guesser, now), "one miss is allowed");
+        throttle.record_failure(guesser, now);
+        assert!(throttle.blocked(guesser, now));
+        assert!(
+            !throttle.blocked(other, now),
+            "other addresses are unaffected"
+        );
+        let later = now + PASSWORD_WINDOW;
+        assert!(!throttle.blocked(guesser, later), "the window expires");
+        throttle.record_failure(other, later);
+        assert!(
+            !throttle.failures.contains_key(&guesser),
+            "expired entries go"
+        );
+
+        let mut unlimited = PasswordThrottle::new(0);
+        for _ in 0..100 {
+            unlimited.record_failure(guesser, now);
+        }
+        assert!(!unlimited.blocked(guesser, now));
+    }
+
+    #[tokio::test]
+    async fn a_client_whose_queue_fills_is_kicked() {
+        use futures_util::FutureExt;
+
+        let shared = Shared::new(SignalingConfig::default());
+        // A queue of one that nobody drains: a client that stopped reading.
+        let (tx, _rx) = mpsc::channel(1);
+        let kick = Arc::new(Notify::new());
+        shared.outbound.lock().await.insert(
+            7,
+            Outbound {
+                tx,
+                kick: Arc::clone(&kick),
+            },
+        );
+        let send = || Action::Send {
+            to: 7,
+            msg: SignalMessage::PeerJoined { slot: 1 },
+        };
+        dispatch(&shared, vec![send()]).await;
+        assert!(
+            kick.notified().now_or_never().is_none(),
+            "still within bounds"
+        );
+        dispatch(&shared, vec![send()]).await;
+        assert!(!shared.outbound.lock().await.contains_key(&7));
+        assert!(
+            kick.notified().now_or_never().is_some(),
+            "the reader is told"
+        );
+    }
+}
@@ -0,0 +1,277 @@
+//! End to end on localhost: a real [`Lobby`] on ephemeral ports, driven by
+//! real clients.
+//!
+//! - WebSocket clients (the browser's side of the protocol) host a
+//!   password-locked room, find it in the room list with its title and the
+//!   host's measured ping, get turned away with the wrong password, join with
+//!   the right one, and chat.
+//! - Repeated wrong passwords from one address lock that address out of
+//!   joining, across reconnects.
+//! - Two native [`NatConnect`] orchestrators use the lobby for both signaling
+//!   and STUN: they meet in a locked room, discover their (loopback) public
+//!   addresses from the lobby's rendezvous, punch, and finish the netplay
+//!   handshake over the opened path. A third with the wrong password fails.
+
+use std::net::SocketAddr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use futures_util::stream::SplitSink;
+use futures_util::{SinkExt, StreamExt};
+use rustynes_lobby::{Lobby, LobbyConfig};
+use rustynes_netplay::signaling::SignalMessage;
+use rustynes_netplay::{NatConfig, NatConnect, NatPhase};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+const ROM_HASH: &str = "00c0ffee";
+/// How long any single step may take before the test gives up.
+const STEP: Duration = Duration::from_secs(10);
+
+/// Bind a lobby on ephemeral localhost ports and serve it on the current
+/// runtime. Returns its signaling URL and rendezvous address.
+async fn start_lobby() -> (String, SocketAddr) {
+    start_lobby_with(LobbyConfig::default()).await
+}
+
+/// [`start_lobby`] with `config`'s limits (its addresses are overridden).
+async fn start_lobby_with(mut config: LobbyConfig) -> (String, SocketAddr) {
+    config.signaling.listen = "127.0.0.1:0".parse().unwrap();
+    config.signaling.ping_interval_ms = 20;
+    config.rendezvous.listen = "127.0.0.1:0".parse().unwrap();
+    let lobby = Lobby::bind(&config).await.expect("bind the lobby");
+    let url = format!("ws://{}/", lobby.local_addr().unwrap());
+    let stun = lobby.rendezvous_addr().expect("rendezvous enabled");
+    tokio::spawn(lobby.run());
+    (url, stun)
+}
+
+type Sink = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
+
+/// A signaling client. A background task keeps reading (which also answers
+/// the lobby's pings) and queues every parsed message.
+struct Client {
+    sink: Sink,
+    inbox: mpsc::UnboundedReceiver<SignalMessage>,
+}
+
+impl Client {
+    async fn connect(url: &str) -> Self {
+        let (ws, _) = tokio_tungstenite::connect_async(url)
+            .await
+            .expect("connect to the lobby");
+        let (sink, mut stream) = ws.split();
+        let (tx, inbox) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(Ok(frame)) = stream.next().await {
+                if let Message::Text(text) = frame
+                    && let Some(msg) = SignalMessage::parse(text.as_str())
+                    && tx.send(msg).is_err()
+                {
+                    break;
+                }
+            }
+        });
+        Self { sink, inbox }
+    }
+
+    async fn send(&mut self, msg: SignalMessage) {
+        self.sink
+            .send(Message::Text(msg.to_json().into()))
+            .await
+            .expect("send to the lobby");
+    }
+
+    /// The next message, or `None` once the lobby has closed the socket.
+    async fn recv(&mut self) -> Option<SignalMessage> {
+        tokio::time::timeout(STEP, self.inbox.recv())
+            .await
+            .expect("the lobby answers in time")
+    }
+}
+
+fn join(room: &str, game: &str, password: &str) -> SignalMessage {
+    SignalMessage::Join {
+        room: room.into(),
+        rom_hash: ROM_HASH.into(),
+        max_players: 2,
+        game: game.into(),
+        password: password.into(),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn locked_room_is_listed_pinged_and_guarded() {
+    let (url, _) = start_lobby().await;
+
+    let mut host = Client::connect(&url).await;
+    host.send(join("ROOM42", "Tetris", "hunter2")).await;
+    assert_eq!(
+        host.recv().await,
+        Some(SignalMessage::Joined {
+            slot: 0,
+            max_players: 2
+        })
+    );
+
+    // A browser polls the room list until the host's ping has been measured.
+    let mut browser = Client::connect(&url).await;
+    let deadline = Instant::now() + STEP;
+    let room = loop {
+        browser
+            .send(SignalMessage::ListRooms {
+                rom_hash: ROM_HASH.into(),
+            })
+            .await;
+        let Some(SignalMessage::RoomList { rooms }) = browser.recv().await else {
+            panic!("expected a room list");
+        };
+        if let Some(room) = rooms.into_iter().find(|r| r.ping_ms.is_some()) {
+            break room;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "the host's ping was never measured"
+        );
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+    assert_eq!(room.code, "ROOM42");
+    assert_eq!(room.game, "Tetris");
+    assert_eq!(room.rom_hash, ROM_HASH);
+    assert_eq!((room.players, room.max_players), (1, 2));
+    assert!(room.locked);
+
+    // The wrong password is turned away and disconnected.
+    let mut stranger = Client::connect(&url).await;
+    stranger.send(join("ROOM42", "", "hunter3")).await;
+    assert_eq!(
+        stranger.recv().await,
+        Some(SignalMessage::Error {
+            reason: "wrong password".into()
+        })
+    );
+    assert_eq!(stranger.recv().await, None);
+
+    // The right one gets in, and the room can talk.
+    let mut friend = Client::connect(&url).await;
+    friend.send(join("ROOM42", "", "hunter2")).await;
+    assert_eq!(
+        friend.recv().await,
+        Some(SignalMessage::Joined {
+            slot: 1,
+            max_players: 2
+        })
+    );
+    assert_eq!(
+        host.recv().await,
+        Some(SignalMessage::PeerJoined { slot: 1 })
+    );
+    friend
+        .send(SignalMessage::Chat {
+            from: 0,
+            text: "ready".into(),
+        })
+        .await;
+    assert_eq!(
+        host.recv().await,
+        Some(SignalMessage::Chat {
+            from: 1,
+            text: "ready".into()
+        })
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn password_guessing_is_throttled_per_address() {
+    let mut config = LobbyConfig::default();
+    config.signaling.password_failures_per_minute = 2;
+    let (url, _) = start_lobby_with(config).await;
+
+    let mut host = Client::connect(&url).await;
+    host.send(join("VAULT", "", "hunter2")).await;
+    assert!(matches!(
+        host.recv().await,
+        Some(SignalMessage::Joined { .. })
+    ));
+
+    // Reconnecting does not reset the count: it is kept per address.
+    for guess in ["hunter3", "hunter4"] {
+        let mut guesser = Client::connect(&url).await;
+        guesser.send(join("VAULT", "", guess)).await;
+        assert_eq!(
+            guesser.recv().await,
+            Some(SignalMessage::Error {
+                reason: "wrong password".into()
+            })
+        );
+    }
+    // Even the right password is refused until the minute is up.
+    let mut guesser = Client::connect(&url).await;
+    guesser.send(join("VAULT", "", "hunter2")).await;
+    assert_eq!(
+        guesser.recv().await,
+        Some(SignalMessage::Error {
+            reason: "too many password attempts".into()
+        })
+    );
+    assert_eq!(guesser.recv().await, None);
+}
+
+#[test]
+fn native_peers_meet_and_punch_through_the_lobby() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let (url, stun) = runtime.block_on(start_lobby());
+    let rom_hash = [0x5A; 32];
+    let config = |password: &str| NatConfig {
+        stun_servers: vec![stun.to_string()],
+        turn: None,
+        signaling_url: url.clone(),
+        room_password: password.into(),
+    };
+
+    let (mut host, room) = NatConnect::host(2, rom_hash, config("hunter2"), 0x10bb).unwrap();
+    let mut joiner = NatConnect::join(&room, rom_hash, config("hunter2"), 2).unwrap();
+    let deadline = Instant::now() + STEP;
+    loop {
+        let phases = (host.pump(), joiner.pump());
+        match &phases {
+            (NatPhase::Synced, NatPhase::Synced) => break,
+            (NatPhase::Failed(reason), _) | (_, NatPhase::Failed(reason)) => {
+                panic!("traversal failed: {reason}")
+            }
+            _ => {}
+        }
+        assert!(Instant::now() < deadline, "stuck at {phases:?}");
+        thread::sleep(Duration::from_millis(2));
+    }
+    assert!(!host.is_relayed() && !joiner.is_relayed());
+
+    // The room is full and locked; a gatecrasher with the wrong password
+    // fails at signaling.
+    let mut gatecrasher = NatConnect::join(&room, rom_hash, config("guess"), 3).unwrap();
+    let deadline = Instant::now() + STEP;
+    let reason = loop {
+        if let NatPhase::Failed(reason) = gatecrasher.pump() {
+            break reason;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "the gatecrasher was never refused"
+        );
+        thread::sleep(Duration::from_millis(2));
+    };
+    assert!(reason.contains("wrong password"), "{reason}");
+
+    // The punched path carries the netplay handshake.
+    let mut host = host.into_connection();
+    let mut joiner = joiner.into_connection();
+    let deadline = Instant::now() + STEP;
+    while !(host.is_synced() && joiner.is_synced()) {
+        host.pump(0);
+        joiner.pump(0);
+        assert!(Instant::now() < deadline, "the handshake never completed");
+        thread::sleep(Duration::from_millis(1));
+    }
+}
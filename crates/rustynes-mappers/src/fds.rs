@@ -73,6 +73,26 @@
 //!   real-BIOS write-CRC path depends on a copyright FDS BIOS and is validated
 //!   only from a local, gitignored dump (see `docs/accuracy-ledger.md`).
 //!
+//! v2.5.0 replaces the synthesized wire image with a **physical-layer medium**
+//! ([`crate::fds_medium`]):
+//!
+//! - **Authoritative raw surface**: each side is stored as its wire stream
+//!   (lead-in gap, `$80` marks, payloads, *stored* CRC-16s, end-of-side), and
+//!   the drive reads and writes that stream directly. A block the BIOS appends
+//!   after the last file, or a rewritten header that re-sizes the data block
+//!   behind it, persists exactly as written; `.fds` bytes are derived on demand.
+//! - **Lossless `.fds` / QD conversion**: [`parse_fds`] records QD (65536-byte)
+//!   sides with their stored CRCs instead of truncating them to the 65500-byte
+//!   window, and [`FdsDisk::to_bytes`] / [`FdsDisk::to_qd_bytes`] export either
+//!   form from any source.
+//! - **Read-side CRC check**: the controller runs CRC-16 over each block it
+//!   streams; a block whose stored CRC does not match raises `$4030.D4` and is
+//!   recorded in the trace ([`FdsTraceRec`] kind 3), so copy-protected disks
+//!   that ship a deliberately bad CRC behave as on hardware.
+//! - **Controller CRC emission on write**: with `$4025.D4` set in write mode the
+//!   controller stores its running CRC-16 instead of the `$4024` latch, so a
+//!   newly appended block is closed with a valid CRC.
+//!
 //! The frontend wiring (BIOS prompt, side-swap keybind, `.fds.sav` file I/O)
 //! is provided by `rustynes-frontend`; this module owns the core API + state.
 //!
@@ -80,6 +100,10 @@
 //! - `Family_Computer_Disk_System.xhtml` — register map + IRQ + banks.
 //! - `FDS_disk_format.xhtml` / `FDS_file_format.xhtml` — the `.fds` container.
 //! - `FDS_BIOS.xhtml` — the 8 KiB `disksys.rom` BIOS.
+//!
+//! Without a `disksys.rom`, [`crate::fds_hle_bios`] supplies a clean-room BIOS
+//! whose disk API traps into Rust ([`crate::fds_hle`]); the device recognises
+//! it at construction and enables the `$40A0-$40A5` trap mailbox.
 
 #![allow(
     clippy::cast_possible_truncation,
@@ -98,8 +122,12 @@
 )]
 
 use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
+use core::cell::OnceCell;
 
 use crate::cartridge::{Mirroring, RomError};
+use crate::fds_fs::{FdsFsError, FdsSideFiles, parse_side_files};
+use crate::fds_hle::{FdsHle, FdsHleHost, HleEnv, is_hle_bios};
+use crate::fds_medium::{FDS_MEDIUM_CAPACITY, FdsSideMedium, WIRE_START_MARK, crc16_update};
 use crate::mapper::{Mapper, MapperCaps, MapperDebugInfo, MapperError};
 
 /// Bytes per disk side in the common `.fds` / fwNES file format (no CRCs, no
@@ -107,9 +135,10 @@ use crate::mapper::{Mapper, MapperCaps, MapperDebugInfo, MapperError};
 /// see `FDS_disk_format.xhtml` §True disk capacity.
 pub const FDS_SIDE_LEN: usize = 65500;
 
-/// QD-file side length (65536). Detected so QD images degrade gracefully to the
-/// 65500-byte read window rather than mis-parsing.
-const QD_SIDE_LEN: usize = 65536;
+/// QD-file side length (65536): the block payloads with each block's two CRC
+/// bytes stored after it. Detected by stride and recorded losslessly (see
+/// [`FdsDisk::to_qd_bytes`]).
+pub const QD_SIDE_LEN: usize = 65536;
 
 /// fwNES optional header length.
 const FWNES_HEADER_LEN: usize = 16;
@@ -287,162 +316,10 @@ pub fn fds_crc32(data: &[u8]) -> u32 {
     !crc
 }
 
-/// Lead-in gap length (in `$00` bytes) synthesized before the first block of a
-/// side. Hardware uses a long disk-start gap (≈26150-28300 bits ≈ 3300-3500
-/// bytes); the BIOS only requires "enough" zero bytes before the first `$80`
-/// start mark to settle its block-scan loop. A modest lead-in keeps the wire
-/// image small while still giving the loader its expected pre-disk gap.
-const WIRE_LEAD_IN_GAP: usize = 200;
-
-/// Inter-block gap length (in `$00` bytes) synthesized between consecutive
-/// blocks. Hardware uses ≥480 bits (≈60 bytes), 976 bits typical; the loader
-/// accepts a much smaller minimum (a few hundred bits). This value sits
-/// comfortably above the minimum the BIOS needs to re-detect a block start.
-const WIRE_BLOCK_GAP: usize = 100;
-
-/// The FDS block start mark. On the medium each gap is terminated by a single
-/// `1` bit; in byte terms (little-endian) that is `$80`. The BIOS scans the bit
-/// stream for this mark to find the start of every block.
-const WIRE_START_MARK: u8 = 0x80;
-
-/// Synthesized FDS block descriptor: where a block's payload lives in the raw
-/// `.fds` side and where it (and its surrounding gap/mark/CRC) lands in the
-/// synthesized wire image. The mapping lets the write path translate a wire
-/// head position back to a raw side offset so persistence (`disk_image_bytes`)
-/// keeps working.
-#[derive(Debug, Clone, Copy)]
-struct WireBlock {
-    /// Offset of the block's first byte (its block-code byte) in the raw side.
-    raw_start: usize,
-    /// Block payload length in bytes (block-code byte included; CRC excluded —
-    /// it is not stored in the `.fds` form).
-    len: usize,
-    /// Offset in the wire image of this block's first payload byte (i.e. just
-    /// after its `$80` start mark).
-    wire_payload_start: usize,
-}
-
-/// CRC-16/KERMIT (a.k.a. CRC-16/CCITT, reflected, poly 0x8408) over the start
-/// mark + block bytes, matching the FDS RP2C33 block CRC. The BIOS does not
-/// verify it for the standard load path, but synthesizing a correct value keeps
-/// the wire image faithful and avoids tripping `$4030.D4` on stricter loaders.
-fn fds_block_crc(start_mark: u8, block: &[u8]) -> u16 {
-    let mut crc: u16 = 0;
-    let update = |byte: u8, crc: &mut u16| {
-        *crc ^= u16::from(byte);
-        for _ in 0..8 {
-            let carry = *crc & 1 != 0;
-            *crc >>= 1;
-            if carry {
-                *crc ^= 0x8408;
-            }
-        }
-    };
-    update(start_mark, &mut crc);
-    for &b in block {
-        update(b, &mut crc);
-    }
-    crc
-}
-
-/// Walk a raw `.fds` side and return its block descriptors in stream order.
-///
-/// The `.fds` form stores only the block payloads concatenated (no gaps, no
-/// start marks, no CRCs). Blocks are self-describing via their leading
-/// block-code byte and a known/derivable length:
-/// - `$01` disk-info: 56 bytes.
-/// - `$02` file-amount: 2 bytes (byte 1 = file count).
-/// - `$03` file-header: 16 bytes (file size = LE u16 at offset 13).
-/// - `$04` file-data: `1 + size` bytes (size from the preceding header).
-///
-/// Parsing stops at the first byte that is not a valid next block code (the
-/// trailing `$00` padding terminates the walk).
-fn parse_side_blocks(side: &[u8]) -> Vec<(usize, usize)> {
-    let mut blocks = Vec::new();
-    let mut pos = 0usize;
-    let mut pending_file_size: Option<usize> = None;
-    loop {
-        if pos >= side.len() {
-            break;
-        }
-        let code = side[pos];
-        let len = match code {
-            0x01 => 56,
-            0x02 => 2,
-            0x03 => {
-                // File size lives at header offset 13-14 (LE u16); remember it
-                // for the file-data block that must follow.
-                let size = if pos + 15 <= side.len() {
-                    usize::from(side[pos + 13]) | (usize::from(side[pos + 14]) << 8)
-                } else {
-                    0
-                };
-                pending_file_size = Some(size);
-                16
-            }
-            0x04 => {
-                let size = pending_file_size.take().unwrap_or(0);
-                1 + size
-            }
-            // Any other leading byte (most commonly $00 trailing padding) ends
-            // the structured region of the side.
-            _ => break,
-        };
-        if pos + len > side.len() {
-            // A declared block runs past the end of the side: clamp and stop.
-            blocks.push((pos, side.len() - pos));
-            break;
-        }
-        blocks.push((pos, len));
-        pos += len;
-    }
-    blocks
-}
-
-/// Synthesize the on-disk **wire image** for one raw `.fds` side: the gap /
-/// start-mark / block / CRC structure the BIOS read routine scans for.
-///
-/// The layout is, for each parsed block:
-/// `[gap $00 × G] [$80 start mark] [block bytes] [crc_lo] [crc_hi]`
-/// with a long lead-in gap before block 1 and shorter inter-block gaps, then a
-/// run of trailing `$00` so the wire image always ends in a gap (so the head
-/// sitting at the inner track reads `$00`).
-///
-/// Returns the wire bytes plus the [`WireBlock`] descriptors mapping each
-/// block's wire payload region back to its raw side offset (used by the write
-/// path to persist BIOS-written blocks into the raw `.fds` side).
-fn build_side_wire(side: &[u8]) -> (Vec<u8>, Vec<WireBlock>) {
-    let raw_blocks = parse_side_blocks(side);
-    let mut wire = Vec::with_capacity(side.len() + raw_blocks.len() * 32 + WIRE_LEAD_IN_GAP);
-    let mut map = Vec::with_capacity(raw_blocks.len());
-    for (i, &(raw_start, len)) in raw_blocks.iter().enumerate() {
-        let gap = if i == 0 {
-            WIRE_LEAD_IN_GAP
-        } else {
-            WIRE_BLOCK_GAP
-        };
-        wire.resize(wire.len() + gap, 0x00);
-        wire.push(WIRE_START_MARK);
-        let wire_payload_start = wire.len();
-        let block = &side[raw_start..raw_start + len];
-        wire.extend_from_slice(block);
-        let crc = fds_block_crc(WIRE_START_MARK, block);
-        wire.push((crc & 0xFF) as u8);
-        wire.push((crc >> 8) as u8);
-        map.push(WireBlock {
-            raw_start,
-            len,
-            wire_payload_start,
-        });
-    }
-    // Trailing gap so the head reads `$00` past the last block instead of
-    // running straight off the end (a small, fixed tail is enough).
-    wire.resize(wire.len() + WIRE_BLOCK_GAP, 0x00);
-    (wire, map)
-}
-
 /// A structural fault found by [`Fds::medium_write_verify`] while walking a
-/// synthesized wire image. Used by the **synthetic FDS write-verify oracle**
+/// side's recorded medium.
+///
+/// Used by the **synthetic FDS write-verify oracle**
 /// (v2.2.0 "Capstone"): after driving the register-level write path, the test
 /// re-walks the medium and asserts every block's synthesized CRC-16 and
 /// surrounding gap/mark framing round-trips. This is the CI-verifiable half of
@@ -485,14 +362,18 @@ pub enum FdsMediumError {
 /// A parsed FDS disk image: an ordered list of disk sides.
 #[derive(Debug, Clone)]
 pub struct FdsDisk {
-    /// One entry per disk side; each is exactly [`FDS_SIDE_LEN`] bytes (longer
-    /// raw sides are truncated, shorter ones zero-padded, so the read window is
-    /// always well-defined). Heap-allocated to keep the 64 KiB-per-side payload
-    /// off the stack.
-    sides: Vec<Box<[u8]>>,
+    /// One physical medium per disk side — the authoritative storage the drive
+    /// reads and writes (see [`crate::fds_medium`]). The `.fds` / QD byte forms
+    /// are derived from it.
+    sides: Vec<FdsSideMedium>,
+    /// Lazily decoded `.fds` form of each side, backing [`Self::side`].
+    /// Cleared whenever that side's medium is handed out mutably.
+    fds_form: Vec<OnceCell<Vec<u8>>>,
     /// Side count declared by the fwNES header, when present (else derived from
     /// the file length).
     declared_side_count: u8,
+    /// Whether the source image used the QD (65536-byte, CRC-bearing) stride.
+    qd_source: bool,
 }
 
 impl FdsDisk {
@@ -508,19 +389,52 @@ impl FdsDisk {
         self.declared_side_count
     }
 
-    /// Borrow the raw bytes of side `idx` (panics if out of range — callers use
-    /// [`Self::side_count`] to bound).
+    /// Whether the image was loaded from a QD (65536-byte side) dump.
+    #[must_use]
+    pub fn is_qd_source(&self) -> bool {
+        self.qd_source
+    }
+
+    /// The `.fds`-form bytes of side `idx` ([`FDS_SIDE_LEN`] bytes, block
+    /// payloads only), decoded from the medium on first use and cached until
+    /// the side is next written. Panics if out of range — callers use
+    /// [`Self::side_count`] to bound.
     #[must_use]
     pub fn side(&self, idx: usize) -> &[u8] {
+        self.fds_form[idx].get_or_init(|| self.sides[idx].to_fds_side())
+    }
+
+    /// Borrow the physical medium of side `idx` (panics if out of range).
+    pub(crate) fn medium(&self, idx: usize) -> &FdsSideMedium {
         &self.sides[idx]
     }
 
-    /// Mutably borrow the raw bytes of side `idx` (panics if out of range).
-    /// Used by the disk-write path to store bytes at the head position.
-    pub fn side_mut(&mut self, idx: usize) -> &mut [u8] {
+    /// Mutably borrow the physical medium of side `idx` (panics if out of
+    /// range). Used by the drive's write path.
+    pub(crate) fn medium_mut(&mut self, idx: usize) -> &mut FdsSideMedium {
+        self.fds_form[idx].take();
         &mut self.sides[idx]
     }
 
+    /// Parse the file-system block structure of side `idx` (disk info, file
+    /// amount, and every file header / data pair). Panics if out of range.
+    ///
+    /// # Errors
+    ///
+    /// Whatever [`parse_side_files`] reports for a malformed side.
+    pub fn side_files(&self, idx: usize) -> Result<FdsSideFiles, FdsFsError> {
+        parse_side_files(self.side(idx))
+    }
+
+    /// Re-record side `idx` from `.fds`-form bytes (block payloads only, as
+    /// produced by [`FdsSideFiles::to_side`]), block by block: a block whose
+    /// payload is unchanged keeps its stored CRC-16, good or bad, and only the
+    /// blocks that changed get a freshly computed one, exactly as if the drive
+    /// had rewritten them. Panics if out of range.
+    pub fn replace_side(&mut self, idx: usize, side: &[u8]) {
+        self.medium_mut(idx).rewrite(side);
+    }
+
     /// Re-serialize every side back to the headerless `.fds` byte layout
     /// (`side_count` × [`FDS_SIDE_LEN`] bytes, concatenated). This is the form a
     /// host writes to a side-car `.fds.sav` so the modified disk persists. The
@@ -531,7 +445,20 @@ impl FdsDisk {
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut out = Vec::with_capacity(self.sides.len() * FDS_SIDE_LEN);
         for side in &self.sides {
-            out.extend_from_slice(side);
+            out.extend_from_slice(&side.to_fds_side());
+        }
+        out
+    }
+
+    /// Re-serialize every side to the raw QD layout (`side_count` ×
+    /// [`QD_SIDE_LEN`] bytes): each block followed by its *stored* CRC-16, so
+    /// a disk with deliberately bad CRCs — or one the BIOS has written — exports
+    /// with its medium intact. Round-trips through [`parse_fds`].
+    #[must_use]
+    pub fn to_qd_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.sides.len() * QD_SIDE_LEN);
+        for side in &self.sides {
+            out.extend_from_slice(&side.to_qd_side());
         }
         out
     }
@@ -545,8 +472,8 @@ impl FdsDisk {
 /// - The headerless raw form: 1+ concatenated sides, the first of which opens
 ///   with `\x01*NINTENDO-HVC*` (the disk-info block).
 ///
-/// QD-style 65536-byte sides are accepted and truncated to the 65500-byte read
-/// window (Stage 1 does not consume CRC/gap bytes).
+/// QD-style 65536-byte sides are accepted and recorded with their stored
+/// per-block CRCs (see [`FdsDisk::to_qd_bytes`]).
 ///
 /// # Errors
 ///
@@ -611,19 +538,24 @@ fn parse_sides(body: &[u8], declared: u8, has_header: bool) -> Result<FdsDisk, R
         available_sides.max(1)
     };
 
+    let qd_source = stride == QD_SIDE_LEN;
     let mut sides = Vec::with_capacity(side_count);
     for i in 0..side_count {
         let start = i * stride;
-        let mut side = vec![0u8; FDS_SIDE_LEN].into_boxed_slice();
-        let end = (start + FDS_SIDE_LEN).min(body.len());
-        let copy_len = end.saturating_sub(start);
-        side[..copy_len].copy_from_slice(&body[start..start + copy_len]);
-        sides.push(side);
+        let end = (start + stride).min(body.len());
+        let side = &body[start.min(end)..end];
+        sides.push(if qd_source {
+            FdsSideMedium::from_qd_side(side)
+        } else {
+            FdsSideMedium::from_fds_side(side)
+        });
     }
 
     Ok(FdsDisk {
+        fds_form: sides.iter().map(|_| OnceCell::new()).collect(),
         sides,
         declared_side_count: declared,
+        qd_source,
     })
 }
 
@@ -1185,14 +1117,18 @@ enum TransferState {
 /// ERR.07 stall. Recorded only after [`Fds::enable_trace`]; default builds never
 /// allocate or record, so the determinism contract is untouched. `kind`: 0 =
 /// `$4031` read (the disk byte the BIOS consumed), 1 = `$4025` control write, 2 =
-/// side change. `value` is the byte (or the new side index / `0xFF` for eject).
-/// `head` is the wire head; `side` is the inserted side (or `-1` ejected);
-/// `status` is the live `$4030` bits.
+/// side change, 3 = block CRC error (a streamed block's stored CRC-16 did not
+/// match; `$4030.D4` is now set). `value` is the byte (or the new side index /
+/// `0xFF` for eject, or the zero-based block index for a CRC error). `head` is
+/// the wire head; `side` is the inserted side (or `-1` ejected); `status` is the
+/// live `$4030` bits.
 #[derive(Clone, Copy, Debug)]
 pub struct FdsTraceRec {
-    /// Event kind: 0 = `$4031` read, 1 = `$4025` control write, 2 = side change.
+    /// Event kind: 0 = `$4031` read, 1 = `$4025` control write, 2 = side change,
+    /// 3 = block CRC error.
     pub kind: u8,
-    /// The byte read/written, or (for a side change) the new side index / `0xFF`.
+    /// The byte read/written, (for a side change) the new side index / `0xFF`,
+    /// or (for a CRC error) the block index.
     pub value: u8,
     /// Wire head position at the time of the event.
     pub head: u32,
@@ -1219,21 +1155,10 @@ pub struct Fds {
     /// Index of the currently inserted side (0-based), or `None` when ejected.
     /// Drives `$4032` bit 0 (disk-not-inserted) when `None`. Default = side 0.
     inserted_side: Option<usize>,
-    /// Read/write head position. This is an offset into the **wire image** of
-    /// the currently inserted side ([`Fds::wire`]) — the synthesized gap /
-    /// start-mark / block / CRC stream the BIOS scans — not a raw `.fds` side
-    /// offset. The write path maps it back to a raw side offset via
-    /// [`Fds::wire_map`].
+    /// Read/write head position: an offset into the inserted side's physical
+    /// medium ([`FdsSideMedium`]) — the gap / start-mark / block / CRC stream
+    /// the BIOS scans — not a `.fds` payload offset.
     head: usize,
-    /// Synthesized wire image of the currently inserted side (gap / `$80` /
-    /// block / CRC). Empty when ejected. Rebuilt on every insert/side-swap and
-    /// after the disk contents change (a BIOS save), so reads always present the
-    /// hardware wire format the loader expects.
-    wire: Vec<u8>,
-    /// Per-block wire→raw mapping for the currently inserted side's [`Fds::wire`].
-    /// Lets [`Fds::store_byte`] translate the wire head position back into the
-    /// raw side offset so BIOS-written block payloads persist to `.fds`.
-    wire_map: Vec<WireBlock>,
     /// Whether the drive has completed its spin-up since the last insert. The
     /// long spin-up not-ready window opens only on the FIRST motor-on after an
     /// insert (the cold spin-up the BIOS reset disk-check waits for). The BIOS
@@ -1325,6 +1250,23 @@ pub struct Fds {
     /// Byte most recently latched into the read shift register ($4031).
     read_data: u8,
 
+    // --- CRC generator ---
+    /// $4025 bit 4 — CRC transfer control. In write mode the controller stores
+    /// its running CRC-16 instead of the `$4024` latch while this is set.
+    crc_transfer: bool,
+    /// Index of the block the read engine synced to at its last start mark, or
+    /// `None` between blocks / at an unindexed mark. Its CRC is checked once
+    /// the second stored CRC byte streams past.
+    read_block: Option<usize>,
+    /// Running CRC-16 over the start mark + every byte streamed since (payload
+    /// and stored CRC); zero after an intact block.
+    read_crc: u16,
+    /// Running CRC-16 of the block being written, seeded by a stored `$80` mark.
+    write_crc: u16,
+    /// How many CRC bytes the controller has emitted for the current write
+    /// `$4025.D4` window (0, 1, then 2 = done; gap `$00` follows).
+    write_crc_emitted: u8,
+
     // --- IRQ line ---
     irq_pending: bool,
 
@@ -1340,6 +1282,12 @@ pub struct Fds {
     trace_on: bool,
     /// Accumulated trace records, drained by [`Fds::take_trace`].
     trace: Vec<FdsTraceRec>,
+
+    // --- High-level-emulated BIOS ---
+    /// Trap mailbox, present when the BIOS is the clean-room image from
+    /// [`crate::fds_hle_bios`]. Derived from the BIOS at construction, so it is
+    /// not part of the save-state.
+    hle: Option<FdsHle>,
 }
 
 impl Fds {
@@ -1355,12 +1303,6 @@ impl Fds {
                 bios.len()
             )));
         }
-        // Synthesize the wire image for the power-on inserted side (side 0).
-        let (wire, wire_map) = if disk.side_count() > 0 {
-            build_side_wire(disk.side(0))
-        } else {
-            (Vec::new(), Vec::new())
-        };
         // Resolve the per-game timing quirk from the disk-image CRC-32 (over the
         // headerless side bytes, matching `disk_image_bytes`).
         let quirk = quirk_for_crc(fds_crc32(&disk.to_bytes()));
@@ -1371,8 +1313,6 @@ impl Fds {
             disk,
             inserted_side: Some(0),
             head: 0,
-            wire,
-            wire_map,
             disk_dirty: false,
             write_protected: false,
             timer_reload: 0,
@@ -1398,6 +1338,11 @@ impl Fds {
             transfer: TransferState::Idle,
             transfer_timer: 0,
             read_data: 0,
+            crc_transfer: false,
+            read_block: None,
+            read_crc: 0,
+            write_crc: 0,
+            write_crc_emitted: 0,
             irq_pending: false,
             read_skipping_gap: true,
             spun_up: false,
@@ -1415,6 +1360,7 @@ impl Fds {
             audio: FdsAudio::default(),
             trace_on: false,
             trace: Vec::new(),
+            hle: is_hle_bios(bios).then(FdsHle::default),
         })
     }
 
@@ -1448,7 +1394,15 @@ impl Fds {
             TransferState::Writing
         };
         if self.transfer != desired {
+            if self.transfer == TransferState::Writing {
+                // A write just ended: re-index the side so blocks the BIOS
+                // appended or re-sized are visible to reads and persistence.
+                self.rescan_inserted();
+            }
             self.transfer = desired;
+            if desired == TransferState::Writing {
+                self.write_crc_emitted = 0;
+            }
             if desired != TransferState::Idle {
                 // Begin transferring from the current head position. The timer
                 // is seeded so the first byte lands one cadence later.
@@ -1467,19 +1421,24 @@ impl Fds {
     /// the read register, raise the byte-transfer flag (and IRQ if enabled), and
     /// move the head forward.
     fn deliver_byte(&mut self) {
-        if self.inserted_side.is_some() {
+        if let Some(idx) = self.inserted_side {
+            let medium = self.disk.medium(idx);
             // When re-syncing to a block, the controller bit-shifts past the
             // gap ($00 run) and its terminating $80 start mark in hardware
             // without raising a byte-transfer event; the first event delivers
             // the byte that follows the mark (the block's first byte).
             if self.read_skipping_gap {
-                while self.head < self.wire.len() && self.wire[self.head] == 0x00 {
+                while self.head < medium.len() && medium.byte(self.head) == 0x00 {
                     self.head += 1;
                 }
-                if self.head < self.wire.len() && self.wire[self.head] == WIRE_START_MARK {
+                if self.head < medium.len() && medium.byte(self.head) == WIRE_START_MARK {
                     self.head += 1;
                     self.read_skipping_gap = false;
-                } else if self.head >= self.wire.len() {
+                    // The CRC generator restarts on every start mark.
+                    self.read_block = medium.block_starting_at(self.head);
+                    self.read_crc = crc16_update(0, WIRE_START_MARK);
+                    self.crc_error = false;
+                } else if self.head >= medium.len() {
                     // No further start mark before the inner track: the head has
                     // reached the end of the side. Flag end-of-head and deliver
                     // $00; the BIOS uses $4030.D6 to detect "no more blocks".
@@ -1492,14 +1451,16 @@ impl Fds {
                     return;
                 } else {
                     // A non-zero, non-mark byte while skipping (should not occur
-                    // for a well-formed wire image): treat it as data and stop
+                    // for a well-formed medium): treat it as data and stop
                     // skipping so we never stall.
                     self.read_skipping_gap = false;
+                    self.read_block = None;
                 }
             }
-            if self.head < self.wire.len() {
-                self.read_data = self.wire[self.head];
+            if self.head < medium.len() {
+                self.read_data = medium.byte(self.head);
                 self.head += 1;
+                self.check_read_crc(idx);
             } else {
                 // The head reached the inner track (end of head): flag it and
                 // deliver $00. The BIOS detects "no more data" via $4030.D6.
@@ -1516,66 +1477,80 @@ impl Fds {
         }
     }
 
-    /// Rebuild the wire image + block map for the currently inserted side from
-    /// the raw `.fds` side contents. Called on insert/side-swap and after a BIOS
-    /// write changes the disk, so reads always present the up-to-date wire form.
-    fn rebuild_wire(&mut self) {
-        match self.inserted_side {
-            Some(idx) if idx < self.disk.side_count() => {
-                let (wire, map) = build_side_wire(self.disk.side(idx));
-                self.wire = wire;
-                self.wire_map = map;
-            }
-            _ => {
-                self.wire.clear();
-                self.wire_map.clear();
+    /// Fold the byte just streamed into the read CRC generator and, once the
+    /// second stored CRC byte of the synced block has passed the head, latch
+    /// `$4030.D4` if the residue is non-zero (a bad stored CRC). The check runs
+    /// only while CRC is enabled (`$4025.D6`), as on the RP2C33.
+    fn check_read_crc(&mut self, idx: usize) {
+        let Some(block) = self.read_block else {
+            return;
+        };
+        self.read_crc = crc16_update(self.read_crc, self.read_data);
+        let blocks = self.disk.medium(idx).blocks();
+        if blocks
+            .get(block)
+            .is_some_and(|b| self.head == b.payload_end() + 2)
+        {
+            self.read_block = None;
+            if self.read_crc != 0 && self.crc_enabled {
+                self.crc_error = true;
+                self.trace_event(3, block as u8);
             }
         }
     }
 
-    /// Map a wire head position to the raw side offset it corresponds to, when
-    /// the head sits inside a block's payload region. Returns `None` for gap /
-    /// start-mark / CRC positions (writes there modify only the synthesized
-    /// framing, which is regenerated from the raw side, so they are dropped).
-    fn wire_head_to_raw(&self, wire_pos: usize) -> Option<usize> {
-        for blk in &self.wire_map {
-            if wire_pos >= blk.wire_payload_start && wire_pos < blk.wire_payload_start + blk.len {
-                return Some(blk.raw_start + (wire_pos - blk.wire_payload_start));
-            }
+    /// Re-index the inserted side's medium after its recorded bytes changed.
+    fn rescan_inserted(&mut self) {
+        if let Some(idx) = self.inserted_side {
+            self.disk.medium_mut(idx).rescan();
         }
-        None
     }
 
     /// Advance the disk-write engine by one byte: store the byte last written to
-    /// `$4024` into the inserted side at the head position, mark the image
+    /// `$4024` (or, inside a `$4025.D4` window, the controller's running CRC)
+    /// onto the inserted side's medium at the head position, mark the image
     /// dirty, raise the byte-transfer flag (and IRQ if enabled), and move the
     /// head forward. A write-protected disk drops the byte (the medium is not
     /// modified) but still advances the transfer machinery so timing-dependent
-    /// BIOS code is unaffected. CRC/gap bytes are not synthesized (Stage-1
-    /// simplification) — only the raw `$4024` byte stream lands on the medium.
+    /// BIOS code is unaffected.
     fn store_byte(&mut self) {
+        let value = if self.crc_transfer {
+            // The controller closes the block with its own CRC-16 (low byte
+            // first), then shifts out gap zeros for the rest of the window.
+            let crc = self.write_crc.to_le_bytes();
+            let v = crc
+                .get(usize::from(self.write_crc_emitted))
+                .copied()
+                .unwrap_or(0);
+            self.write_crc_emitted = self.write_crc_emitted.saturating_add(1).min(2);
+            v
+        } else {
+            let v = self.write_data;
+            // A start mark written over gap restarts the CRC generator; every
+            // later byte of the block folds into it.
+            if v == WIRE_START_MARK && self.last_stored_is_gap() {
+                self.write_crc = 0;
+            }
+            self.write_crc = crc16_update(self.write_crc, v);
+            self.write_crc_emitted = 0;
+            v
+        };
         if let Some(idx) = self.inserted_side {
-            if self.head < self.wire.len() {
+            if self.head < FDS_MEDIUM_CAPACITY {
                 if !self.write_protected {
                     // The BIOS write stream is itself the wire format (gap,
-                    // start mark, block bytes, CRC). Land the byte on the wire
-                    // image, and when it falls inside a block payload, mirror it
-                    // into the raw `.fds` side so the modified disk persists. The
-                    // BIOS writes whole blocks back to the same position it read
-                    // them, so the existing block geometry stays valid.
-                    let raw_off = self.wire_head_to_raw(self.head);
-                    let written_pos = self.head;
-                    self.wire[self.head] = self.write_data;
-                    if let Some(off) = raw_off
-                        && off < self.disk.side(idx).len()
+                    // start mark, block bytes, CRC), so the byte lands on the
+                    // medium as-is. When it falls inside an indexed block's
+                    // payload, re-emit that block's stored CRC so the medium
+                    // stays self-consistent even before the controller closes
+                    // the block — the write-verify oracle and any
+                    // $4030.D4-checking loader then see a valid block.
+                    let medium = self.disk.medium_mut(idx);
+                    medium.store(self.head, value);
+                    if !self.crc_transfer
+                        && let Some(block) = medium.block_at(self.head)
                     {
-                        self.disk.side_mut(idx)[off] = self.write_data;
-                        // The FDS controller emits a fresh CRC-16 after each
-                        // block it writes; re-synthesize this block's stored CRC
-                        // over its (now-updated) payload so the medium stays
-                        // self-consistent — the synthetic write-verify oracle and
-                        // any $4030.D4-checking loader then see a valid block.
-                        self.resynth_block_crc(written_pos);
+                        medium.resynth_crc(block);
                     }
                     self.disk_dirty = true;
                 }
@@ -1592,6 +1567,18 @@ impl Fds {
         }
     }
 
+    /// Whether the byte before the head on the inserted side is gap (`$00`) —
+    /// i.e. a `$80` stored now is a start mark, not payload.
+    fn last_stored_is_gap(&self) -> bool {
+        match self.inserted_side {
+            Some(idx) if self.head > 0 => {
+                let medium = self.disk.medium(idx);
+                self.head > medium.len() || medium.byte(self.head - 1) == 0x00
+            }
+            _ => true,
+        }
+    }
+
     /// Compute the motor-restart re-seek not-ready window (CPU cycles).
     ///
     /// With the continuous head-seek model disabled (the default) this is the
@@ -1615,48 +1602,17 @@ impl Fds {
         base.saturating_add(self.quirk.extra_reseek_cycles)
     }
 
-    /// Re-emit the per-block CRC-16 for the block whose payload contains wire
-    /// offset `wire_pos`, recomputing it over the block's current payload bytes.
-    ///
-    /// Real FDS hardware appends a fresh CRC-16 immediately after each block it
-    /// writes; the RP2C33 controller's CRC generator runs continuously over the
-    /// block stream and the two CRC bytes it emits close the block. Modelling
-    /// that keeps the synthesized wire image self-consistent after a BIOS write:
-    /// once the last payload byte of a block lands, its stored CRC matches a
-    /// recomputation, so [`Self::medium_write_verify`] (and any stricter loader
-    /// that checks `$4030.D4`) sees a valid block. Gap / start-mark / CRC
-    /// positions map to no block payload and are skipped (their framing is
-    /// regenerated from the raw side on the next [`Self::rebuild_wire`]).
-    fn resynth_block_crc(&mut self, wire_pos: usize) {
-        for blk in &self.wire_map {
-            let payload_end = blk.wire_payload_start + blk.len;
-            if wire_pos >= blk.wire_payload_start && wire_pos < payload_end {
-                // The two CRC bytes sit immediately after the payload on the
-                // wire (build_side_wire lays them out `[block][crc_lo][crc_hi]`).
-                if payload_end + 1 < self.wire.len() {
-                    let crc = fds_block_crc(
-                        WIRE_START_MARK,
-                        &self.wire[blk.wire_payload_start..payload_end],
-                    );
-                    self.wire[payload_end] = (crc & 0xFF) as u8;
-                    self.wire[payload_end + 1] = (crc >> 8) as u8;
-                }
-                return;
-            }
-        }
-    }
-
-    /// Walk the synthesized wire image of the currently inserted side and verify
-    /// every block's gap / start-mark framing and per-block CRC-16 round-trips —
-    /// the **synthetic FDS write-verify oracle** (v2.2.0 "Capstone").
+    /// Walk the physical medium of the currently inserted side and verify every
+    /// block's gap / start-mark framing and stored CRC-16 round-trips — the
+    /// **synthetic FDS write-verify oracle** (v2.2.0 "Capstone").
     ///
     /// This is deliberately BIOS-free: it validates the emulator's own medium
-    /// synthesis (gap runs, `$80` start marks, CRC-16/KERMIT block CRCs) so the
-    /// write path can be exercised and checked entirely in CI without any
-    /// copyright FDS BIOS. The real-BIOS write path (which recomputes the CRC in
-    /// its own RAM and streams it to `$4024`) is validated only from a local,
-    /// gitignored dump — see `docs/accuracy-ledger.md` for the CI-verifiable vs
-    /// local-only split.
+    /// (gap runs, `$80` start marks, CRC-16/KERMIT block CRCs) so the write
+    /// path can be exercised and checked entirely in CI without any copyright
+    /// FDS BIOS. The real-BIOS write path (which streams the gap and mark to
+    /// `$4024` and lets the controller close each block with `$4025.D4`) is
+    /// validated only from a local, gitignored dump — see
+    /// `docs/accuracy-ledger.md` for the CI-verifiable vs local-only split.
     ///
     /// # Errors
     ///
@@ -1664,53 +1620,8 @@ impl Fds {
     /// CRC mismatch, truncated block, or a corrupted inter-block gap). Returns
     /// `Ok(())` when no side is inserted (nothing to verify).
     pub fn medium_write_verify(&self) -> Result<(), FdsMediumError> {
-        for (block, blk) in self.wire_map.iter().enumerate() {
-            // The `$80` start mark sits one byte before the payload.
-            if blk.wire_payload_start == 0
-                || self.wire[blk.wire_payload_start - 1] != WIRE_START_MARK
-            {
-                return Err(FdsMediumError::MissingStartMark {
-                    block,
-                    wire_pos: blk.wire_payload_start.saturating_sub(1),
-                });
-            }
-            let payload_end = blk.wire_payload_start + blk.len;
-            // Payload + its two CRC bytes must fit inside the wire.
-            if payload_end + 1 >= self.wire.len() {
-                return Err(FdsMediumError::Truncated { block });
-            }
-            let expected = fds_block_crc(
-                WIRE_START_MARK,
-                &self.wire[blk.wire_payload_start..payload_end],
-            );
-            let stored =
-                u16::from(self.wire[payload_end]) | (u16::from(self.wire[payload_end + 1]) << 8);
-            if stored != expected {
-                return Err(FdsMediumError::CrcMismatch {
-                    block,
-                    stored,
-                    expected,
-                });
-            }
-            // The gap before this block (from the prior block's CRC end, or the
-            // start of the wire) must be all `$00`.
-            let gap_start = if block == 0 {
-                0
-            } else {
-                let prev = &self.wire_map[block - 1];
-                prev.wire_payload_start + prev.len + 2
-            };
-            let mark_pos = blk.wire_payload_start - 1;
-            for (i, &b) in self.wire[gap_start..mark_pos].iter().enumerate() {
-                if b != 0x00 {
-                    return Err(FdsMediumError::GapNotZero {
-                        block,
-                        wire_pos: gap_start + i,
-                    });
-                }
-            }
-        }
-        Ok(())
+        self.inserted_side
+            .map_or(Ok(()), |idx| self.disk.medium(idx).verify())
     }
 
     /// Enable or disable the continuous analog head-seek model (default off).
@@ -1761,6 +1672,7 @@ impl Fds {
         //   bit1 = drive motor (0: start, 1: stop)
         //   bit2 = transfer mode (1: read, 0: write)
         //   bit3 = nametable arrangement
+        //   bit4 = CRC transfer (write mode: controller emits its CRC-16)
         //   bit5 = CRC transfer control (must be set for byte-transfer)
         //   bit6 = CRC enable (gates the byte-transfer flag)
         //   bit7 = byte-transfer IRQ enable
@@ -1769,6 +1681,7 @@ impl Fds {
         self.transfer_reset = (value & 0x01) != 0; // bit 0
         self.motor_on = (value & 0x02) == 0; // bit 1 (0: start, 1: stop)
         self.read_mode = (value & 0x04) != 0;
+        self.crc_transfer = (value & 0x10) != 0;
         self.crc_control = (value & 0x20) != 0;
         self.crc_enabled = (value & 0x40) != 0;
         if self.motor_on && !was_motor_on {
@@ -1821,6 +1734,7 @@ impl Fds {
             self.head = 0;
             self.end_of_head = false;
             self.read_skipping_gap = true;
+            self.read_block = None;
         }
         // Asserting transfer reset (its rising edge) resets the byte-transfer
         // timing and re-arms the read gap-skip so the next delivered byte
@@ -1998,6 +1912,10 @@ impl Fds {
     /// [`Mapper::set_disk_side`] trait override.
     fn do_set_disk_side(&mut self, side: Option<usize>) {
         self.trace_event(2, side.map_or(0xFF, |s| s as u8));
+        // Re-index the outgoing side first, so a write in progress when the
+        // disk leaves the drive is visible the next time it is inserted.
+        self.rescan_inserted();
+        self.read_block = None;
         match side {
             Some(i) if i < self.disk.side_count() => {
                 self.inserted_side = Some(i);
@@ -2006,7 +1924,6 @@ impl Fds {
                 self.insert_not_ready = INSERT_NOT_READY_CYCLES;
                 // A freshly inserted disk must spin up on the next motor-on.
                 self.spun_up = false;
-                self.rebuild_wire();
             }
             Some(_) => { /* out of range: ignore */ }
             None => {
@@ -2014,15 +1931,18 @@ impl Fds {
                 self.insert_not_ready = 0;
                 self.end_of_head = false;
                 self.spun_up = false;
-                self.rebuild_wire();
             }
         }
         // Recompute whether a transfer can run given the new inserted state.
         self.update_transfer_state();
     }
 
-    /// Parse the v3 disk tail starting at byte `off` (after the audio tail).
-    /// `base` is the fixed-prefix length used to validate the total v3 size.
+    /// Parse the v3+ disk tail starting at byte `off` (after the audio tail).
+    /// `base` is the fixed-prefix length used to validate the total size.
+    ///
+    /// v3/v4 carry each side as its fixed [`FDS_SIDE_LEN`] `.fds` bytes (re-
+    /// recorded onto a fresh medium); v5 carries each side's physical medium,
+    /// length-prefixed, so appended blocks and stored bad CRCs survive.
     fn load_disk_tail(
         &mut self,
         data: &[u8],
@@ -2030,31 +1950,68 @@ impl Fds {
         base: usize,
         version: u8,
     ) -> Result<(), MapperError> {
+        let truncated = |expected: usize| MapperError::Truncated {
+            expected,
+            got: data.len(),
+        };
         let saved_sides = u32::from_le_bytes(data[off..off + 4].try_into().unwrap()) as usize;
         off += 4;
-        // Validate the full length now that the side count is known. v4 appends
-        // the continuous head-seek tail after the v3 fields.
+        // v4 appends the continuous head-seek tail after the v3 fields; v5
+        // appends the CRC-generator tail after that.
         let v4_extra = if version >= 4 { FDS_V4_TAIL_LEN } else { 0 };
-        let expected =
-            base + FdsAudio::TAIL_LEN + 4 + saved_sides * FDS_SIDE_LEN + 4 + 4 + 1 + v4_extra;
+        let v5_extra = if version >= 5 { FDS_V5_TAIL_LEN } else { 0 };
+        let fixed_after_sides = 4 + 4 + 1 + v4_extra + v5_extra;
+        // Collect the per-side payload slices (validating the length as we go,
+        // since v5 sides are variable-length).
+        let mut saved = Vec::with_capacity(saved_sides.min(16));
+        for _ in 0..saved_sides {
+            let len = if version >= 5 {
+                let Some(len) = data.get(off..off + 4) else {
+                    return Err(truncated(off + 4));
+                };
+                off += 4;
+                let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+                if len > FDS_MEDIUM_CAPACITY {
+                    return Err(MapperError::Invalid(format!(
+                        "FDS side medium of {len} bytes exceeds capacity {FDS_MEDIUM_CAPACITY}"
+                    )));
+                }
+                len
+            } else {
+                FDS_SIDE_LEN
+            };
+            let Some(bytes) = data.get(off..off + len) else {
+                return Err(truncated(off + len + fixed_after_sides));
+            };
+            saved.push(bytes);
+            off += len;
+        }
+        let expected = off + fixed_after_sides;
         if data.len() != expected {
-            return Err(MapperError::Truncated {
-                expected,
-                got: data.len(),
-            });
+            return Err(truncated(expected));
         }
+        // v3/v4 validated their length from the side count alone; keep that
+        // invariant visible for the legacy layouts.
+        debug_assert!(
+            version >= 5
+                || expected
+                    == base
+                        + FdsAudio::TAIL_LEN
+                        + 4
+                        + saved_sides * FDS_SIDE_LEN
+                        + fixed_after_sides
+        );
         // Restore the side contents into the matching local sides. A foreign
         // blob with a different side count restores only the overlap so we never
         // index out of range either way.
         let restore = saved_sides.min(self.disk.side_count());
-        for s in 0..restore {
-            self.disk
-                .side_mut(s)
-                .copy_from_slice(&data[off..off + FDS_SIDE_LEN]);
-            off += FDS_SIDE_LEN;
-        }
-        // Skip any extra saved sides we have no local slot for.
-        off += (saved_sides - restore) * FDS_SIDE_LEN;
+        for (s, bytes) in saved.iter().take(restore).enumerate() {
+            if version >= 5 {
+                self.disk.medium_mut(s).restore_bytes(bytes);
+            } else {
+                *self.disk.medium_mut(s) = FdsSideMedium::from_fds_side(bytes);
+            }
+        }
         let inserted = u32::from_le_bytes(data[off..off + 4].try_into().unwrap());
         off += 4;
         self.inserted_side = if inserted == u32::MAX {
@@ -2075,14 +2032,30 @@ impl Fds {
             off += 1;
             self.pre_rewind_head =
                 u32::from_le_bytes(data[off..off + 4].try_into().unwrap()) as usize;
-            // Advance past the u32 we just consumed so `off` keeps reflecting the
-            // total bytes read — preserving the "offset == consumed" invariant so
-            // any future tail extension starts from the correct position.
             off += 4;
         } else {
             self.analog_head_seek = false;
             self.pre_rewind_head = 0;
         }
+        // v5 CRC-generator tail; older blobs restart the generators idle (the
+        // next start mark re-seeds them).
+        if version >= 5 {
+            let block = u32::from_le_bytes(data[off..off + 4].try_into().unwrap());
+            self.read_block = (block != u32::MAX).then_some(block as usize);
+            off += 4;
+            self.read_crc = u16::from_le_bytes([data[off], data[off + 1]]);
+            off += 2;
+            self.write_crc = u16::from_le_bytes([data[off], data[off + 1]]);
+            off += 2;
+            self.write_crc_emitted = data[off].min(2);
+            // Advance past the byte we just consumed so `off` keeps reflecting
+            // the total bytes read — preserving the "offset == consumed"
+            // invariant so any future tail extension starts from the correct
+            // position.
+            off += 1;
+        } else {
+            self.reset_crc_generators();
+        }
         // Both paths must have consumed exactly the blob the length check at the
         // top validated (`expected == data.len()`); assert the invariant and, in
         // doing so, read `off` on every path (no `unused_assignments`).
@@ -2093,6 +2066,14 @@ impl Fds {
         );
         Ok(())
     }
+
+    /// Idle the read/write CRC generators (legacy save-state restore).
+    fn reset_crc_generators(&mut self) {
+        self.read_block = None;
+        self.read_crc = 0;
+        self.write_crc = 0;
+        self.write_crc_emitted = 0;
+    }
 }
 
 /// Save-state format version for the FDS device.
@@ -2113,12 +2094,22 @@ impl Fds {
 ///   opt-in flag + the `pre_rewind_head` distance. Strictly additive: a v1/v2/v3
 ///   blob restores with the model disabled and `pre_rewind_head` = 0 (the
 ///   byte-identical default).
-const FDS_SAVE_VERSION: u8 = 4;
+/// - v5 (physical medium): each side in the disk tail is its physical medium
+///   (u32 length + wire bytes) instead of the fixed 65500 `.fds` bytes, so
+///   appended blocks and stored bad CRCs round-trip; appends the
+///   CRC-generator tail ([`FDS_V5_TAIL_LEN`]). v3/v4 blobs re-record their
+///   `.fds` sides onto fresh media with idle CRC generators.
+const FDS_SAVE_VERSION: u8 = 5;
 
 /// Extra bytes the v4 disk tail appends after the v3 tail: the
 /// `analog_head_seek` flag (1 byte) + `pre_rewind_head` (u32, 4 bytes).
 const FDS_V4_TAIL_LEN: usize = 1 + 4;
 
+/// Extra bytes the v5 disk tail appends after the v4 tail: `read_block` (u32,
+/// `u32::MAX` = none) + `read_crc` (u16) + `write_crc` (u16) +
+/// `write_crc_emitted` (u8).
+const FDS_V5_TAIL_LEN: usize = 4 + 2 + 2 + 1;
+
 impl Mapper for Fds {
     fn sram(&self) -> &[u8] {
         &self.prg_ram
@@ -2157,6 +2148,8 @@ impl Mapper for Fds {
                 }
             }
             0x4090..=0x4097 => self.audio.read_reg(addr).unwrap_or(0),
+            // HLE BIOS trap mailbox.
+            0x40A0..=0x40A3 => self.hle.as_ref().map_or(0, |hle| hle.read(addr)),
             // PRG-RAM at $6000-$DFFF.
             0x6000..=0xDFFF => self.prg_ram[(addr - 0x6000) as usize],
             // BIOS at $E000-$FFFF.
@@ -2222,6 +2215,12 @@ impl Mapper for Fds {
                     self.audio.write_reg(addr, value);
                 }
             }
+            // HLE BIOS trap mailbox.
+            0x40A0..=0x40A5 => {
+                if let Some(hle) = &mut self.hle {
+                    hle.write(addr, value);
+                }
+            }
             // PRG-RAM at $6000-$DFFF (writable).
             0x6000..=0xDFFF => self.prg_ram[(addr - 0x6000) as usize] = value,
             // BIOS / unmapped: ignore.
@@ -2238,6 +2237,10 @@ impl Mapper for Fds {
         if (0x4020..=0x409F).contains(&addr) {
             return false;
         }
+        // The HLE BIOS mailbox reads back through `cpu_read`.
+        if self.hle.is_some() && (0x40A0..=0x40A3).contains(&addr) {
+            return false;
+        }
         (0x40A0..=0x5FFF).contains(&addr)
     }
 
@@ -2367,6 +2370,25 @@ impl Mapper for Fds {
         self.disk.to_bytes()
     }
 
+    fn disk_image_qd_bytes(&self) -> Vec<u8> {
+        self.disk.to_qd_bytes()
+    }
+
+    fn replace_disk_image(&mut self, bytes: &[u8]) -> bool {
+        let Ok(disk) = parse_fds(bytes) else {
+            return false;
+        };
+        let side = self.inserted_side.filter(|&s| s < disk.side_count());
+        // Resolved from the image, as at construction.
+        self.quirk = quirk_for_crc(fds_crc32(&disk.to_bytes()));
+        self.disk = disk;
+        self.disk_dirty = false;
+        // Nothing to re-index on the outgoing image; insert into the new one.
+        self.inserted_side = None;
+        self.do_set_disk_side(side);
+        true
+    }
+
     fn disk_is_dirty(&self) -> bool {
         self.disk_dirty
     }
@@ -2379,6 +2401,42 @@ impl Mapper for Fds {
         self.write_protected = protected;
     }
 
+    fn fds_hle_active(&self) -> bool {
+        self.hle.is_some()
+    }
+
+    fn fds_hle_pending(&self) -> bool {
+        self.hle.as_ref().is_some_and(|hle| hle.pending)
+    }
+
+    fn run_fds_hle_service(&mut self, host: &mut dyn FdsHleHost) {
+        let Some(hle) = &mut self.hle else {
+            return;
+        };
+        let mut env = HleEnv {
+            host,
+            prg_ram: &mut self.prg_ram,
+            chr_ram: &mut self.chr_ram,
+            bios: &self.bios,
+            mirroring: self.mirroring,
+            disk: &mut self.disk,
+            side: self.inserted_side,
+            write_protected: self.write_protected,
+            dirty: &mut self.disk_dirty,
+        };
+        hle.run(&mut env);
+    }
+
+    fn fds_hle_boot_error(&self) -> Option<u8> {
+        self.hle.as_ref().and_then(|hle| hle.boot_error)
+    }
+
+    fn fds_hle_unsupported_calls(&self) -> Vec<u16> {
+        self.hle
+            .as_ref()
+            .map_or_else(Vec::new, |hle| hle.unsupported.clone())
+    }
+
     fn save_state(&self) -> Vec<u8> {
         let mut out = Vec::with_capacity(32 + self.prg_ram.len() + self.chr_ram.len());
         out.push(FDS_SAVE_VERSION);
@@ -2428,7 +2486,9 @@ impl Mapper for Fds {
         // v3 disk tail: mutable disk contents + insert/write-path state.
         out.extend_from_slice(&(self.disk.side_count() as u32).to_le_bytes());
         for s in 0..self.disk.side_count() {
-            out.extend_from_slice(self.disk.side(s));
+            let medium = self.disk.medium(s).bytes();
+            out.extend_from_slice(&(medium.len() as u32).to_le_bytes());
+            out.extend_from_slice(medium);
         }
         // inserted_side Option: 0xFFFF_FFFF sentinel for ejected.
         let inserted = self.inserted_side.map_or(u32::MAX, |i| i as u32);
@@ -2444,6 +2504,12 @@ impl Mapper for Fds {
         // model disabled and `pre_rewind_head` = 0 (the byte-identical default).
         out.push(u8::from(self.analog_head_seek));
         out.extend_from_slice(&(self.pre_rewind_head as u32).to_le_bytes());
+        // v5 tail: CRC-generator state.
+        let read_block = self.read_block.map_or(u32::MAX, |b| b as u32);
+        out.extend_from_slice(&read_block.to_le_bytes());
+        out.extend_from_slice(&self.read_crc.to_le_bytes());
+        out.extend_from_slice(&self.write_crc.to_le_bytes());
+        out.push(self.write_crc_emitted);
         out
     }
 
@@ -2473,7 +2539,7 @@ impl Mapper for Fds {
                     });
                 }
             }
-            3 | 4 => {
+            3..=5 => {
                 // Need at least the fixed prefix + audio tail + the disk tail's
                 // leading side-count u32 to learn how long the tail is.
                 let min = base + FdsAudio::TAIL_LEN + 4;
@@ -2575,12 +2641,14 @@ impl Mapper for Fds {
             // v1/v2/v3 predate the continuous head-seek model: default it off.
             self.analog_head_seek = false;
             self.pre_rewind_head = 0;
+            self.reset_crc_generators();
         }
-        // Rebuild the wire image from the (possibly modified) inserted side and
-        // clamp the restored head into it. The wire image is derived state — it
-        // is reconstructed from the saved raw side contents rather than stored.
-        self.rebuild_wire();
-        self.head = self.head.min(self.wire.len());
+        // Derive the $4025 CRC bits (D4-D6) from the saved control byte.
+        self.crc_transfer = (self.control & 0x10) != 0;
+        self.crc_control = (self.control & 0x20) != 0;
+        self.crc_enabled = (self.control & 0x40) != 0;
+        // Clamp the restored head to the physical end of the side.
+        self.head = self.head.min(FDS_MEDIUM_CAPACITY);
         Ok(())
     }
 
@@ -2637,6 +2705,7 @@ impl Mapper for Fds {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fds_medium::{WIRE_LEAD_IN_GAP, fds_block_crc};
 
     /// Build a synthetic fwNES image with `sides` sides, side `s` filled with a
     /// recognizable pattern keyed on the side index.
@@ -3375,8 +3444,8 @@ mod tests {
         }
         let blob = fds.save_state();
         assert_eq!(
-            blob[0], 4,
-            "FDS save version is 4 (Capstone head-seek tail)"
+            blob[0], FDS_SAVE_VERSION,
+            "FDS save version is 5 (physical-medium tail)"
         );
 
         let mut fresh = make_device(2);
@@ -3428,7 +3497,8 @@ mod tests {
         // Build a v1-shaped blob by truncating off the disk + audio tails and
         // stamping version 1. `save_state` now emits v4, so the disk tail
         // includes the Capstone head-seek extra.
-        let disk_tail = 4 + FDS_SIDE_LEN + 4 + 4 + 1 + FDS_V4_TAIL_LEN;
+        let disk_tail =
+            4 + 4 + fds.disk.medium(0).len() + 4 + 4 + 1 + FDS_V4_TAIL_LEN + FDS_V5_TAIL_LEN;
         let mut blob = fds.save_state();
         blob.truncate(blob.len() - disk_tail - FdsAudio::TAIL_LEN);
         blob[0] = 1;
@@ -3536,6 +3606,24 @@ mod tests {
         assert_eq!(&reparsed.side(0)[16..20], &[0xDE, 0xAD, 0xBE, 0xEF]);
     }
 
+    #[test]
+    fn replace_disk_image_swaps_in_a_written_copy() {
+        let mut written = make_device(2);
+        enable_disk_io(&mut written);
+        seek_head(&mut written, FIRST_BLOCK_WIRE_PAYLOAD + 16);
+        write_bytes(&mut written, &[0xDE, 0xAD, 0xBE, 0xEF]);
+        let copy = written.disk_image_qd_bytes();
+
+        let mut fds = make_device(2);
+        fds.set_disk_side(Some(1));
+        assert!(fds.replace_disk_image(&copy));
+        assert_eq!(fds.disk_image_qd_bytes(), copy, "lossless swap");
+        assert_eq!(fds.inserted_disk_side(), Some(1), "side stays inserted");
+        assert!(!fds.disk_is_dirty(), "a swapped-in image starts clean");
+        assert!(!fds.replace_disk_image(&[0x12; 7]), "junk is refused");
+        assert_eq!(fds.disk_image_qd_bytes(), copy);
+    }
+
     #[test]
     fn write_protected_disk_does_not_modify_medium() {
         let mut fds = make_device(1);
@@ -3816,7 +3904,7 @@ mod tests {
         assert!(fds.disk_is_dirty());
         assert_eq!(fds.transfer, TransferState::Writing);
         let blob = fds.save_state();
-        assert_eq!(blob[0], 4, "FDS save version bumped to 4 (Capstone)");
+        assert_eq!(blob[0], 5, "FDS save version bumped to 5 (physical medium)");
 
         let mut fresh = make_device(2);
         fresh.load_state(&blob).unwrap();
@@ -3867,9 +3955,11 @@ mod tests {
         // the disk un-modified, side 0 inserted, clean, writable.
         let mut fds = make_device(2);
         enable_disk_io(&mut fds);
-        // Build a v2-shaped blob by truncating off the v4 disk tail + stamping 2.
-        // The disk tail = 4 (side count) + sides*FDS_SIDE_LEN + 4 + 4 + 1 + v4.
-        let disk_tail = 4 + 2 * FDS_SIDE_LEN + 4 + 4 + 1 + FDS_V4_TAIL_LEN;
+        // Build a v2-shaped blob by truncating off the v5 disk tail + stamping 2.
+        // The disk tail = 4 (side count) + per side (4 + medium) + 4 + 4 + 1 +
+        // v4 + v5.
+        let media: usize = (0..2).map(|s| 4 + fds.disk.medium(s).len()).sum();
+        let disk_tail = 4 + media + 4 + 4 + 1 + FDS_V4_TAIL_LEN + FDS_V5_TAIL_LEN;
         let mut blob = fds.save_state();
         blob.truncate(blob.len() - disk_tail);
         blob[0] = 2;
@@ -3887,9 +3977,10 @@ mod tests {
     fn load_state_v1_blob_defaults_disk_clean() {
         // A v1 blob has neither audio nor disk tail; the disk defaults apply.
         let mut fds = make_device(1);
-        let blob_v3 = fds.save_state();
-        let disk_tail = 4 + FDS_SIDE_LEN + 4 + 4 + 1 + FDS_V4_TAIL_LEN;
-        let mut blob = blob_v3;
+        let blob_v5 = fds.save_state();
+        let disk_tail =
+            4 + 4 + fds.disk.medium(0).len() + 4 + 4 + 1 + FDS_V4_TAIL_LEN + FDS_V5_TAIL_LEN;
+        let mut blob = blob_v5;
         blob.truncate(blob.len() - FdsAudio::TAIL_LEN - disk_tail);
         blob[0] = 1;
         fds.set_disk_write_protected(true);
@@ -3929,7 +4020,10 @@ mod tests {
         // Prove the CRC oracle actually bites: corrupt one payload byte on the
         // wire WITHOUT re-synthesizing its CRC and confirm the verifier catches
         // the mismatch on the first (disk-info) block.
-        fds.wire[FIRST_BLOCK_WIRE_PAYLOAD] ^= 0xFF;
+        let corrupt = fds.disk.medium(0).byte(FIRST_BLOCK_WIRE_PAYLOAD) ^ 0xFF;
+        fds.disk
+            .medium_mut(0)
+            .store(FIRST_BLOCK_WIRE_PAYLOAD, corrupt);
         match fds.medium_write_verify() {
             Err(FdsMediumError::CrcMismatch { block: 0, .. }) => {}
             other => panic!("expected a block-0 CRC mismatch, got {other:?}"),
@@ -3944,10 +4038,12 @@ mod tests {
         enable_disk_io(&mut fds);
         seek_head(&mut fds, FIRST_BLOCK_WIRE_PAYLOAD);
         write_bytes(&mut fds, &[0x10, 0x20, 0x30]);
-        let blk = fds.wire_map[0];
-        let end = blk.wire_payload_start + blk.len;
-        let stored = u16::from(fds.wire[end]) | (u16::from(fds.wire[end + 1]) << 8);
-        let expected = fds_block_crc(WIRE_START_MARK, &fds.wire[blk.wire_payload_start..end]);
+        let medium = fds.disk.medium(0);
+        let blk = medium.blocks()[0];
+        let end = blk.payload_end();
+        let wire = medium.bytes();
+        let stored = u16::from_le_bytes([wire[end], wire[end + 1]]);
+        let expected = fds_block_crc(WIRE_START_MARK, &wire[blk.payload_start..end]);
         assert_eq!(
             stored, expected,
             "written block re-emits a consistent CRC-16"
@@ -3997,7 +4093,7 @@ mod tests {
         fds.set_analog_head_seek(true);
         fds.pre_rewind_head = 12_345;
         let blob = fds.save_state();
-        assert_eq!(blob[0], 4);
+        assert_eq!(blob[0], FDS_SAVE_VERSION);
         let mut fresh = make_device(2);
         fresh.load_state(&blob).unwrap();
         assert!(fresh.analog_head_seek(), "v4 restores the opt-in flag");
@@ -4007,4 +4103,175 @@ mod tests {
         );
         assert_eq!(fresh.save_state(), blob, "re-serialize is byte-identical");
     }
+
+    // --- v2.5.0 physical-layer medium ---
+
+    /// Read a whole side's first `n` delivered bytes through the register path,
+    /// resetting the transfer between blocks the way the BIOS does.
+    fn read_bytes(fds: &mut Fds, n: usize) -> Vec<u8> {
+        let mut out = Vec::with_capacity(n);
+        for _ in 0..n {
+            for _ in 0..DISK_BYTE_CYCLES {
+                fds.notify_cpu_cycle();
+            }
+            out.push(fds.cpu_read(0x4031));
+        }
+        out
+    }
+
+    #[test]
+    fn qd_image_round_trips_through_device() {
+        let fds = make_device(2);
+        let qd = fds.disk_image_qd_bytes();
+        assert_eq!(qd.len(), 2 * QD_SIDE_LEN);
+        let disk = parse_fds(&qd).unwrap();
+        assert!(disk.is_qd_source());
+        assert_eq!(disk.to_qd_bytes(), qd, "QD -> medium -> QD is lossless");
+        assert_eq!(disk.to_bytes(), fds.disk_image_bytes(), "same payloads");
+    }
+
+    #[test]
+    fn replace_side_rerecords_an_edited_file_list() {
+        // Side: disk info, file amount 1, one 2-byte PRG file.
+        let mut side = synth_raw_side();
+        side[56..58].copy_from_slice(&[0x02, 0x01]);
+        side[58] = 0x03;
+        side[61..69].copy_from_slice(b"KYODAKU-");
+        side[71] = 2;
+        side[74] = 0x04;
+        side[75..77].copy_from_slice(&[0x11, 0x22]);
+        let mut disk = parse_fds(&side).unwrap();
+        let mut files = disk.side_files(0).unwrap();
+        assert_eq!(files.files[0].name_lossy(), "KYODAKU-");
+        files.replace_file(0, vec![0x33; 5]).unwrap();
+        disk.replace_side(0, &files.to_side().unwrap());
+        let reparsed = disk.side_files(0).unwrap();
+        assert_eq!(reparsed.files[0].data, vec![0x33; 5]);
+        disk.medium(0)
+            .verify()
+            .expect("re-recorded side carries valid CRCs");
+    }
+
+    #[test]
+    fn replace_side_keeps_the_bad_crcs_of_untouched_blocks() {
+        // Disk info, file amount 2, a 2-byte file, then a 3-byte file.
+        let mut side = synth_raw_side();
+        side[56..58].copy_from_slice(&[0x02, 0x02]);
+        side[58] = 0x03;
+        side[71] = 2;
+        side[74] = 0x04;
+        side[75..77].copy_from_slice(&[0x11, 0x22]);
+        side[77] = 0x03;
+        side[78] = 1;
+        side[90] = 3;
+        side[93] = 0x04;
+        side[94..97].copy_from_slice(&[0x33, 0x44, 0x55]);
+        let mut qd = parse_fds(&side).unwrap().to_qd_bytes();
+        // Copy-protection style: break the disk-info CRC and the last file's.
+        qd[56] ^= 0xFF;
+        let last_crc = 56 + 2 + 2 + 2 + 16 + 2 + 3 + 2 + 16 + 2 + 4;
+        qd[last_crc] ^= 0xFF;
+        let mut disk = parse_fds(&qd).unwrap();
+        let mut files = disk.side_files(0).unwrap();
+        files.replace_file(0, vec![0x66, 0x77]).unwrap();
+        disk.replace_side(0, &files.to_side().unwrap());
+        let out = disk.to_qd_bytes();
+        assert_eq!(out[56], qd[56], "disk-info bad CRC survives");
+        assert_eq!(
+            out[last_crc], qd[last_crc],
+            "untouched file's bad CRC survives"
+        );
+        assert_eq!(disk.side_files(0).unwrap().files[0].data, vec![0x66, 0x77]);
+        assert!(disk.medium(0).block_crc_ok(3), "the patched block is valid");
+
+        // A replace that grows the first file moves the second; it keeps its
+        // stored CRC all the same.
+        let mut files = disk.side_files(0).unwrap();
+        files.replace_file(0, vec![0x88; 9]).unwrap();
+        disk.replace_side(0, &files.to_side().unwrap());
+        let medium = disk.medium(0);
+        assert!(!medium.block_crc_ok(0));
+        assert!(medium.block_crc_ok(2) && medium.block_crc_ok(3));
+        assert!(!medium.block_crc_ok(5), "moved block keeps its bad CRC");
+        assert_eq!(
+            disk.side_files(0).unwrap().files[1].data,
+            [0x33, 0x44, 0x55]
+        );
+    }
+
+    #[test]
+    fn bad_stored_crc_raises_4030_d4_and_traces() {
+        // A QD dump whose disk-info block carries a deliberately wrong CRC.
+        let mut qd = make_device(1).disk_image_qd_bytes();
+        qd[56] ^= 0xFF; // disk-info payload is 56 bytes; its CRC low byte follows
+        let disk = parse_fds(&qd).unwrap();
+        let mut fds = Fds::new(disk, &dummy_bios()).unwrap();
+        fds.enable_trace();
+        enable_disk_io(&mut fds);
+        fds.cpu_write(0x4025, 0b0110_0100); // motor on, read, CRC enabled
+        settle_drive(&mut fds);
+        let _ = read_bytes(&mut fds, 56 + 1);
+        assert_eq!(
+            fds.cpu_read(0x4030) & 0x10,
+            0x00,
+            "no verdict before CRC hi"
+        );
+        let _ = read_bytes(&mut fds, 1);
+        assert_eq!(
+            fds.cpu_read(0x4030) & 0x10,
+            0x10,
+            "bad CRC latches $4030.D4"
+        );
+        let trace = fds.take_trace();
+        assert!(
+            trace.iter().any(|r| r.kind == 3 && r.value == 0),
+            "CRC error recorded for block 0"
+        );
+    }
+
+    #[test]
+    fn intact_disk_never_reports_crc_error() {
+        let mut fds = make_device(1);
+        enable_disk_io(&mut fds);
+        fds.cpu_write(0x4025, 0b0110_0100);
+        settle_drive(&mut fds);
+        let _ = read_bytes(&mut fds, 56 + 2);
+        assert_eq!(fds.cpu_read(0x4030) & 0x10, 0x00);
+    }
+
+    #[test]
+    fn appended_block_persists_with_controller_crc() {
+        // Write a new file-amount block past the last recorded block, the way
+        // the BIOS appends: gap, $80 mark, payload, then $4025.D4 so the
+        // controller closes the block with its own CRC.
+        let mut fds = make_device(1);
+        enable_disk_io(&mut fds);
+        let end = fds.disk.medium(0).len();
+        seek_head(&mut fds, end);
+        write_bytes(&mut fds, &[0x00, 0x00, WIRE_START_MARK, 0x02, 0x09]);
+        fds.cpu_write(0x4025, 0b0111_0000); // write mode + CRC transfer (D4)
+        for _ in 0..(DISK_BYTE_CYCLES * 2) {
+            fds.notify_cpu_cycle();
+        }
+        fds.cpu_write(0x4025, 0b0110_0110); // motor off: the write ends
+        assert_eq!(fds.disk.medium(0).blocks().len(), 2, "new block indexed");
+        fds.medium_write_verify()
+            .expect("controller CRC closes the block");
+        let side = fds.disk_image_bytes();
+        assert_eq!(&side[56..58], &[0x02, 0x09], "appended block persists");
+    }
+
+    #[test]
+    fn v5_save_state_round_trips_medium_growth() {
+        let mut fds = make_device(1);
+        enable_disk_io(&mut fds);
+        let end = fds.disk.medium(0).len();
+        seek_head(&mut fds, end + 10);
+        write_bytes(&mut fds, &[0x42]);
+        let blob = fds.save_state();
+        let mut fresh = make_device(1);
+        fresh.load_state(&blob).unwrap();
+        assert_eq!(fresh.disk.medium(0).bytes(), fds.disk.medium(0).bytes());
+        assert_eq!(fresh.save_state(), blob);
+    }
 }
@@ -0,0 +1,586 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//! FDS disk file system: the block structure of one `.fds` side (v2.5.0).
+//!
+//! A disk side is a fixed sequence of blocks, each opening with its block code:
+//!
+//! | Code | Block | Length |
+//! |------|-------|--------|
+//! | `1`  | disk info (`*NINTENDO-HVC*`, game code, side / disk number, boot id) | 56 |
+//! | `2`  | file amount (the number of files the BIOS enumerates) | 2 |
+//! | `3`  | file header (number, id, name, load address, size, kind) | 16 |
+//! | `4`  | file data (the `size` bytes the preceding header declares) | 1 + size |
+//!
+//! with a `3`/`4` pair per file. [`parse_side_files`] walks that structure over
+//! the `.fds`-form bytes of a side ([`crate::FdsDisk::side`]) into an
+//! [`FdsSideFiles`]; [`FdsSideFiles::to_side`] re-serializes it, rewriting
+//! every file header's size and the file-amount byte from the edited file list,
+//! so a replaced or appended file comes back as a well-formed side that
+//! [`crate::FdsDisk::replace_side`] can write onto the medium.
+//!
+//! Files past the declared file amount are kept as **hidden** files: the BIOS
+//! never enumerates them, but games (and several copy-protection schemes) load
+//! them by reading on past the last counted file, so they round-trip untouched.
+//!
+//! This is a host-side tooling view — the drive never consults it. References:
+//! `FDS_disk_format.xhtml`, `FDS_file_format.xhtml`.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use thiserror::Error;
+
+use crate::fds::FDS_SIDE_LEN;
+
+/// Length of the disk-info block (block code 1), including the block code.
+pub const FDS_DISK_INFO_LEN: usize = 56;
+
+/// Length of a file-header block (block code 3), including the block code.
+const FILE_HEADER_LEN: usize = 16;
+
+/// The disk-info signature following block code 1.
+const DISK_INFO_SIGNATURE: &[u8; 14] = b"*NINTENDO-HVC*";
+
+/// Why a side's block structure could not be parsed or re-serialized.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum FdsFsError {
+    /// The side does not open with a `*NINTENDO-HVC*` disk-info block.
+    #[error("side does not open with a *NINTENDO-HVC* disk-info block")]
+    BadDiskInfo,
+
+    /// A block code other than the one the structure requires at `offset`.
+    #[error("expected block code {expected} at offset {offset:#06X}, found {found:#04X}")]
+    BadBlockCode {
+        /// Offset of the block within the `.fds` side.
+        offset: usize,
+        /// The block code the structure requires here.
+        expected: u8,
+        /// The byte actually found.
+        found: u8,
+    },
+
+    /// A block ran past the end of the side.
+    #[error("block at offset {offset:#06X} runs past the end of the side")]
+    Truncated {
+        /// Offset of the truncated block within the `.fds` side.
+        offset: usize,
+    },
+
+    /// A file's data is larger than the 16-bit size field can describe.
+    #[error("file {index} is {len} bytes; a file holds at most 65535")]
+    FileTooLarge {
+        /// Index of the file in the side's file list.
+        index: usize,
+        /// The data length that does not fit.
+        len: usize,
+    },
+
+    /// The side already holds the most files the 8-bit file amount can count.
+    #[error("a side holds at most 255 counted files")]
+    TooManyFiles,
+
+    /// The re-serialized side does not fit in one `.fds` side.
+    #[error("side needs {needed} bytes; a disk side holds {FDS_SIDE_LEN}")]
+    SideFull {
+        /// Bytes the serialized blocks need.
+        needed: usize,
+    },
+
+    /// A file index past the end of the side's file list.
+    #[error("no file {0} on this side")]
+    NoSuchFile(usize),
+}
+
+/// The kind byte of a file header: where the BIOS loads the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FdsFileKind {
+    /// Loaded into CPU address space (`$6000-$DFFF` PRG-RAM).
+    Prg,
+    /// Loaded into PPU pattern memory (CHR-RAM).
+    Chr,
+    /// Loaded into PPU nametable / palette memory.
+    Vram,
+    /// Any other kind byte, kept verbatim.
+    Other(u8),
+}
+
+impl FdsFileKind {
+    /// Decode a file-header kind byte.
+    #[must_use]
+    pub const fn from_byte(byte: u8) -> Self {
+        match byte {
+            0 => Self::Prg,
+            1 => Self::Chr,
+            2 => Self::Vram,
+            other => Self::Other(other),
+        }
+    }
+
+    /// The file-header kind byte.
+    #[must_use]
+    pub const fn to_byte(self) -> u8 {
+        match self {
+            Self::Prg => 0,
+            Self::Chr => 1,
+            Self::Vram => 2,
+            Self::Other(byte) => byte,
+        }
+    }
+
+    /// Short display label (`PRG` / `CHR` / `VRAM` / `?nn`).
+    #[must_use]
+    pub fn label(self) -> String {
+        match self {
+            Self::Prg => "PRG".into(),
+            Self::Chr => "CHR".into(),
+            Self::Vram => "VRAM".into(),
+            Self::Other(byte) => alloc::format!("?{byte:02X}"),
+        }
+    }
+}
+
+/// The disk-info block (block code 1) of a side, kept byte-exact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FdsDiskInfo {
+    /// The raw block, block code included.
+    pub raw: [u8; FDS_DISK_INFO_LEN],
+}
+
+impl FdsDiskInfo {
+    /// Licensee / manufacturer code.
+    #[must_use]
+    pub const fn manufacturer(&self) -> u8 {
+        self.raw[15]
+    }
+
+    /// The three-character game code (e.g. `ZEL`).
+    #[must_use]
+    pub fn game_code(&self) -> String {
+        ascii_lossy(&self.raw[16..19])
+    }
+
+    /// Game type byte (`' '` normal, `'E'` event, `'R'` reduction, ...).
+    #[must_use]
+    pub const fn game_type(&self) -> u8 {
+        self.raw[19]
+    }
+
+    /// Game revision number.
+    #[must_use]
+    pub const fn revision(&self) -> u8 {
+        self.raw[20]
+    }
+
+    /// Side number (0 = side A, 1 = side B).
+    #[must_use]
+    pub const fn side_number(&self) -> u8 {
+        self.raw[21]
+    }
+
+    /// Disk number (0 = first disk).
+    #[must_use]
+    pub const fn disk_number(&self) -> u8 {
+        self.raw[22]
+    }
+
+    /// Boot read file code: the BIOS loads every file whose id is at most this
+    /// value at boot.
+    #[must_use]
+    pub const fn boot_file_id(&self) -> u8 {
+        self.raw[25]
+    }
+}
+
+/// One file on a side: its header fields plus its data block payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FdsFile {
+    /// File number (the file's position on the side, as recorded).
+    pub number: u8,
+    /// File identification code; the BIOS boot-loads ids up to
+    /// [`FdsDiskInfo::boot_file_id`].
+    pub id: u8,
+    /// Eight-byte file name, space-padded ASCII by convention.
+    pub name: [u8; 8],
+    /// Load address in the destination address space.
+    pub load_address: u16,
+    /// Destination address space.
+    pub kind: FdsFileKind,
+    /// The file's data (the data block payload, block code excluded). Its length
+    /// is the header's size field.
+    pub data: Vec<u8>,
+}
+
+impl FdsFile {
+    /// The file name with non-printable bytes replaced by `.`, trailing spaces
+    /// trimmed.
+    #[must_use]
+    pub fn name_lossy(&self) -> String {
+        let mut name = ascii_lossy(&self.name);
+        name.truncate(name.trim_end().len());
+        name
+    }
+}
+
+/// The parsed block structure of one disk side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FdsSideFiles {
+    /// The disk-info block.
+    pub info: FdsDiskInfo,
+    /// The file-amount byte as recorded. Files at or past this index are
+    /// hidden from the BIOS's enumeration.
+    pub file_amount: u8,
+    /// Every file found on the side, counted and hidden, in disk order.
+    pub files: Vec<FdsFile>,
+    /// Bytes after the last parsed block up to the last non-zero byte (stale
+    /// data past the end of the file list), re-emitted verbatim.
+    pub trailer: Vec<u8>,
+}
+
+/// Parse the block structure of one `.fds`-form side.
+///
+/// Counted files must all be present; after them, further `3`/`4` pairs are
+/// collected as hidden files until the structure stops, and whatever follows is
+/// kept as [`FdsSideFiles::trailer`].
+///
+/// # Errors
+///
+/// [`FdsFsError::BadDiskInfo`] when the side lacks a disk-info block, and
+/// [`FdsFsError::BadBlockCode`] / [`FdsFsError::Truncated`] when a counted file
+/// is missing or cut short.
+pub fn parse_side_files(side: &[u8]) -> Result<FdsSideFiles, FdsFsError> {
+    if side.len() < FDS_DISK_INFO_LEN
+        || side[0] != 0x01
+        || &side[1..15] != DISK_INFO_SIGNATURE.as_slice()
+    {
+        return Err(FdsFsError::BadDiskInfo);
+    }
+    let mut raw = [0u8; FDS_DISK_INFO_LEN];
+    raw.copy_from_slice(&side[..FDS_DISK_INFO_LEN]);
+    let info = FdsDiskInfo { raw };
+
+    let mut pos = FDS_DISK_INFO_LEN;
+    expect_code(side, pos, 2)?;
+    let file_amount = *side
+        .get(pos + 1)
+        .ok_or(FdsFsError::Truncated { offset: pos })?;
+    pos += 2;
+
+    let mut files = Vec::new();
+    loop {
+        let counted = files.len() < usize::from(file_amount);
+        match read_file(side, pos) {
+            Ok((file, next)) => {
+                files.push(file);
+                pos = next;
+            }
+            Err(e) if counted => return Err(e),
+            Err(_) => break,
+        }
+    }
+
+    let end = side
+        .iter()
+        .rposition(|&b| b != 0)
+        .map_or(pos, |i| (i + 1).max(pos));
+    Ok(FdsSideFiles {
+        info,
+        file_amount,
+        files,
+        trailer: side[pos..end].to_vec(),
+    })
+}
+
+/// Read the `3`/`4` block pair at `pos`, returning the file and the offset
+/// past its data block.
+fn read_file(side: &[u8], pos: usize) -> Result<(FdsFile, usize), FdsFsError> {
+    expect_code(side, pos, 3)?;
+    let header = side
+        .get(pos..pos + FILE_HEADER_LEN)
+        .ok_or(FdsFsError::Truncated { offset: pos })?;
+    let size = usize::from(u16::from_le_bytes([header[13], header[14]]));
+    let data_pos = pos + FILE_HEADER_LEN;
+    expect_code(side, data_pos, 4)?;
+    let data = side
+        .get(data_pos + 1..data_pos + 1 + size)
+        .ok_or(FdsFsError::Truncated { offset: data_pos })?;
+    let mut name = [0u8; 8];
+    name.copy_from_slice(&header[3..11]);
+    let file = FdsFile {
+        number: header[1],
+        id: header[2],
+        name,
+        load_address: u16::from_le_bytes([header[11], header[12]]),
+        kind: FdsFileKind::from_byte(header[15]),
+        data: data.to_vec(),
+    };
+    Ok((file, data_pos + 1 + size))
+}
+
+/// Require block code `expected` at `offset`.
+fn expect_code(side: &[u8], offset: usize, expected: u8) -> Result<(), FdsFsError> {
+    match side.get(offset) {
+        Some(&found) if found == expected => Ok(()),
+        Some(&found) => Err(FdsFsError::BadBlockCode {
+            offset,
+            expected,
+            found,
+        }),
+        None => Err(FdsFsError::Truncated { offset }),
+    }
+}
+
+/// Printable-ASCII view of `bytes`, anything else shown as `.`.
+fn ascii_lossy(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                char::from(b)
+            } else {
+                '.'
+            }
+        })
+        .collect()
+}
+
+impl FdsSideFiles {
+    /// Number of files the BIOS enumerates (the counted prefix of
+    /// [`Self::files`]).
+    #[must_use]
+    pub fn counted_files(&self) -> usize {
+        usize::from(self.file_amount).min(self.files.len())
+    }
+
+    /// Replace file `index`'s data, keeping its header fields. The header's
+    /// size is rewritten from the new data on [`Self::to_side`].
+    ///
+    /// # Errors
+    ///
+    /// [`FdsFsError::NoSuchFile`] for an out-of-range index and
+    /// [`FdsFsError::FileTooLarge`] for data over 65535 bytes.
+    pub fn replace_file(&mut self, index: usize, data: Vec<u8>) -> Result<(), FdsFsError> {
+        if data.len() > usize::from(u16::MAX) {
+            return Err(FdsFsError::FileTooLarge {
+                index,
+                len: data.len(),
+            });
+        }
+        let file = self
+            .files
+            .get_mut(index)
+            .ok_or(FdsFsError::NoSuchFile(index))?;
+        file.data = data;
+        Ok(())
+    }
+
+    /// Append `file` after the last counted file, raising the file amount so
+    /// the BIOS enumerates it. Its file number is set to its position; hidden
+    /// files after it are renumbered to keep the sequence.
+    ///
+    /// # Errors
+    ///
+    /// [`FdsFsError::TooManyFiles`] when the file amount is already 255 and
+    /// [`FdsFsError::FileTooLarge`] for data over 65535 bytes.
+    pub fn insert_file(&mut self, mut file: FdsFile) -> Result<usize, FdsFsError> {
+        let index = self.counted_files();
+        if file.data.len() > usize::from(u16::MAX) {
+            return Err(FdsFsError::FileTooLarge {
+                index,
+                len: file.data.len(),
+            });
+        }
+        let number = u8::try_from(index)
+            .ok()
+            .filter(|&n| n < u8::MAX)
+            .ok_or(FdsFsError::TooManyFiles)?;
+        file.number = number;
+        self.files.insert(index, file);
+        self.file_amount = number + 1;
+        self.renumber_from(index + 1);
+        Ok(index)
+    }
+
+    /// Remove file `index`, lowering the file amount when it was counted and
+    /// renumbering the files after it.
+    ///
+    /// # Errors
+    ///
+    /// [`FdsFsError::NoSuchFile`] for an out-of-range index.
+    pub fn remove_file(&mut self, index: usize) -> Result<FdsFile, FdsFsError> {
+        if index >= self.files.len() {
+            return Err(FdsFsError::NoSuchFile(index));
+        }
+        if index < self.counted_files() {
+            self.file_amount -= 1;
+        }
+        let file = self.files.remove(index);
+        self.renumber_from(index);
+        Ok(file)
+    }
+
+    /// Give files from `start` on their position as file number. The number
+    /// field is one byte, so a (hidden) file past index 255 wraps like the
+    /// BIOS's own counter would.
+    #[allow(clippy::cast_possible_truncation)]
+    fn renumber_from(&mut self, start: usize) {
+        for (i, file) in self.files.iter_mut().enumerate().skip(start) {
+            file.number = i as u8;
+        }
+    }
+
+    /// Serialize back to one `.fds`-form side ([`FDS_SIDE_LEN`] bytes, zero
+    /// padded). Each file header's size field is written from its data and the
+    /// file-amount block from [`Self::file_amount`]; everything else is
+    /// re-emitted as parsed, so an unedited side round-trips byte for byte.
+    ///
+    /// # Errors
+    ///
+    /// [`FdsFsError::FileTooLarge`] for a file over 65535 bytes and
+    /// [`FdsFsError::SideFull`] when the blocks overflow the side.
+    pub fn to_side(&self) -> Result<Vec<u8>, FdsFsError> {
+        let mut out = Vec::with_capacity(FDS_SIDE_LEN);
+        out.extend_from_slice(&self.info.raw);
+        out.extend_from_slice(&[0x02, self.file_amount]);
+        for (index, file) in self.files.iter().enumerate() {
+            let size = u16::try_from(file.data.len()).map_err(|_| FdsFsError::FileTooLarge {
+                index,
+                len: file.data.len(),
+            })?;
+            out.extend_from_slice(&[0x03, file.number, file.id]);
+            out.extend_from_slice(&file.name);
+            out.extend_from_slice(&file.load_address.to_le_bytes());
+            out.extend_from_slice(&size.to_le_bytes());
+            out.push(file.kind.to_byte());
+            out.push(0x04);
+            out.extend_from_slice(&file.data);
+        }
+        out.extend_from_slice(&self.trailer);
+        if out.len() > FDS_SIDE_LEN {
+            return Err(FdsFsError::SideFull { needed: out.len() });
+        }
+        out.resize(FDS_SIDE_LEN, 0);
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::cast_possible_truncation)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// A side with `counted` counted files plus `hidden` hidden ones.
+    fn build_side(counted: u8, hidden: u8) -> Vec<u8> {
+        let mut side = vec![0u8; FDS_SIDE_LEN];
+        side[0] = 0x01;
+        side[1..15].copy_from_slice(DISK_INFO_SIGNATURE);
+        side[16..19].copy_from_slice(b"TST");
+        side[21] = 1;
+        side[25] = 0x0F;
+        side[56] = 0x02;
+        side[57] = counted;
+        let mut pos = 58;
+        for i in 0..counted + hidden {
+            let len = 4 + usize::from(i);
+            side[pos] = 0x03;
+            side[pos + 1] = i;
+            side[pos + 2] = 0x10 + i;
+            side[pos + 3..pos + 11].copy_from_slice(b"FILE    ");
+            side[pos + 10] = b'0' + i;
+            side[pos + 11..pos + 13].copy_from_slice(&0x6000u16.to_le_bytes());
+            side[pos + 13..pos + 15].copy_from_slice(&(len as u16).to_le_bytes());
+            side[pos + 15] = i % 3;
+            side[pos + 16] = 0x04;
+            for k in 0..len {
+                side[pos + 17 + k] = i.wrapping_mul(7) ^ k as u8;
+            }
+            pos += 17 + len;
+        }
+        side
+    }
+
+    #[test]
+    fn parses_info_files_and_kinds() {
+        let fs = parse_side_files(&build_side(3, 0)).unwrap();
+        assert_eq!(fs.info.game_code(), "TST");
+        assert_eq!(fs.info.side_number(), 1);
+        assert_eq!(fs.info.boot_file_id(), 0x0F);
+        assert_eq!(fs.file_amount, 3);
+        assert_eq!(fs.files.len(), 3);
+        assert_eq!(fs.files[1].name_lossy(), "FILE   1");
+        assert_eq!(fs.files[1].id, 0x11);
+        assert_eq!(fs.files[1].load_address, 0x6000);
+        assert_eq!(fs.files[1].kind, FdsFileKind::Chr);
+        assert_eq!(fs.files[2].kind, FdsFileKind::Vram);
+        assert_eq!(fs.files[2].data.len(), 6);
+        assert!(fs.trailer.is_empty());
+    }
+
+    #[test]
+    fn unedited_side_round_trips_with_hidden_files() {
+        let side = build_side(2, 2);
+        let fs = parse_side_files(&side).unwrap();
+        assert_eq!(fs.counted_files(), 2);
+        assert_eq!(
+            fs.files.len(),
+            4,
+            "hidden files past the file amount are kept"
+        );
+        assert_eq!(fs.to_side().unwrap(), side);
+    }
+
+    #[test]
+    fn missing_counted_file_is_an_error() {
+        let mut side = build_side(2, 0);
+        side[57] = 3;
+        assert!(matches!(
+            parse_side_files(&side),
+            Err(FdsFsError::BadBlockCode { expected: 3, .. })
+        ));
+        assert_eq!(parse_side_files(&[0u8; 64]), Err(FdsFsError::BadDiskInfo));
+    }
+
+    #[test]
+    fn replace_rewrites_size_and_shifts_following_files() {
+        let mut fs = parse_side_files(&build_side(3, 0)).unwrap();
+        let last = fs.files[2].clone();
+        fs.replace_file(0, vec![0xAA; 300]).unwrap();
+        let reparsed = parse_side_files(&fs.to_side().unwrap()).unwrap();
+        assert_eq!(reparsed.files[0].data, vec![0xAA; 300]);
+        assert_eq!(reparsed.files[2], last);
+    }
+
+    #[test]
+    fn insert_raises_file_amount_ahead_of_hidden_files() {
+        let mut fs = parse_side_files(&build_side(2, 1)).unwrap();
+        let new = FdsFile {
+            number: 0,
+            id: 0x20,
+            name: *b"NEWFILE ",
+            load_address: 0x0000,
+            kind: FdsFileKind::Chr,
+            data: vec![0x55; 16],
+        };
+        assert_eq!(fs.insert_file(new).unwrap(), 2);
+        let reparsed = parse_side_files(&fs.to_side().unwrap()).unwrap();
+        assert_eq!(reparsed.file_amount, 3);
+        assert_eq!(reparsed.files[2].name_lossy(), "NEWFILE");
+        assert_eq!(reparsed.files[3].number, 3, "the hidden file is renumbered");
+
+        let mut removed = reparsed;
+        removed.remove_file(0).unwrap();
+        assert_eq!(removed.file_amount, 2);
+        assert_eq!(removed.files[0].number, 0);
+    }
+
+    #[test]
+    fn overfull_side_is_rejected() {
+        let mut fs = parse_side_files(&build_side(1, 0)).unwrap();
+        fs.replace_file(0, vec![0; 0xFFFF]).unwrap();
+        assert!(matches!(fs.to_side(), Err(FdsFsError::SideFull { .. })));
+        assert!(matches!(
+            fs.replace_file(0, vec![0; 0x10000]),
+            Err(FdsFsError::FileTooLarge { .. })
+        ));
+    }
+}
@@ -0,0 +1,895 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//! High-level-emulated FDS BIOS (v2.5.0).
+//!
+//! The RAM adapter normally runs Nintendo's `disksys.rom`, which users must
+//! dump themselves. [`fds_hle_bios`] builds a clean-room 8 KiB stand-in: a
+//! small hand-assembled 6502 image (the same approach as the NSF driver in
+//! [`crate::NsfMapper`]) whose disk-API entry points trap into Rust instead of
+//! driving the disk controller bit by bit.
+//!
+//! The image provides:
+//!
+//! - **Boot sequence** (reset vector): the `$0102/$0103` soft-reset check, a
+//!   boot load of every file whose id is at most the disk's boot read file
+//!   code, the license-screen check, then `JMP ($DFFC)` with `$0100`/`$0101`
+//!   set to the game's NMI / IRQ vectors. The Nintendo logo animation is
+//!   skipped.
+//! - **Interrupt dispatch**: NMI on `$0100` bits 7-6 (`$DFF6`/`$DFF8`/`$DFFA`
+//!   or the `VINTWait` return), IRQ on `$0101` (`$DFFE` or acknowledge).
+//! - **Disk API** at the documented addresses: `LoadFiles` (`$E1F8`),
+//!   `AppendFile` (`$E237`), `WriteFile` (`$E239`), `CheckFileCount`
+//!   (`$E2B7`), `AdjustFileCount` (`$E2BB`), `SetFileCount1` (`$E301`),
+//!   `SetFileCount` (`$E305`) and `GetDiskInfo` (`$E32A`). Each is a five-byte
+//!   stub that jumps to a common trap routine ([`SVC_COMMON`]); the trap
+//!   stages the caller's `A` and return address in the `$40A0-$40A4` mailbox
+//!   and the write to `$40A3` runs the service against the inserted side
+//!   (via [`crate::parse_side_files`]) before the routine returns.
+//! - **Utilities** in plain 6502: `Delay132`, `Delayms`, the PPUMASK
+//!   show/hide family, `VINTWait`, `SpriteDMA`, `ReadPads`, `MemFill`,
+//!   `Random`, `SetScroll` and `JumpEngine`.
+//!
+//! Every other documented entry point traps as *unsupported*: the call returns
+//! immediately and its address is recorded ([`crate::Mapper::fds_hle_unsupported_calls`])
+//! so a title that fails to boot can be told why. The font, the logo
+//! and the VRAM-buffer helpers of the real BIOS are not reproduced.
+//!
+//! Disk access is instantaneous — no spin-up, no byte-transfer IRQs — and only
+//! games that go through the public API (nearly all licensed titles) boot; a
+//! game that drives `$4024/$4025` itself still sees the ordinary drive model.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::cartridge::Mirroring;
+use crate::fds::FdsDisk;
+use crate::fds_fs::{FdsFile, FdsFileKind, FdsFsError, FdsSideFiles};
+
+/// Signature at `$E000` that marks an image built by [`fds_hle_bios`].
+pub const FDS_HLE_SIGNATURE: &[u8; 16] = b"RUSTYNES HLE FDS";
+
+/// Mailbox: caller's `A` (write) / result `A` (read).
+pub(crate) const PORT_A: u16 = 0x40A0;
+/// Mailbox: return address low (write) / result `Y` (read).
+pub(crate) const PORT_RET_LO: u16 = 0x40A1;
+/// Mailbox: return address high (write) / new return address low (read).
+pub(crate) const PORT_RET_HI: u16 = 0x40A2;
+/// Mailbox: trigger (write) / new return address high (read).
+pub(crate) const PORT_TRIGGER: u16 = 0x40A3;
+/// Mailbox: service id (write).
+pub(crate) const PORT_SERVICE: u16 = 0x40A4;
+/// Boot-error latch: the reset code stores the failing error code here.
+pub(crate) const PORT_BOOT_ERROR: u16 = 0x40A5;
+
+/// Common trap routine every service stub jumps to.
+const SVC_COMMON: u16 = 0xF000;
+/// Reset handler.
+const RESET: u16 = 0xF100;
+/// NMI dispatcher.
+const NMI: u16 = 0xF190;
+/// IRQ dispatcher.
+const IRQ: u16 = 0xF1E0;
+/// Wildcard disk id (ten `$FF`) used by the boot load.
+const BOOT_DISK_ID: u16 = 0xFF00;
+/// Boot load list (`$FF`: load by boot read file code).
+const BOOT_LIST: u16 = 0xFF10;
+
+const SVC_LOAD_FILES: u8 = 0x01;
+const SVC_WRITE_FILE: u8 = 0x02;
+const SVC_CHECK_FILE_COUNT: u8 = 0x03;
+const SVC_ADJUST_FILE_COUNT: u8 = 0x04;
+const SVC_SET_FILE_COUNT1: u8 = 0x05;
+const SVC_SET_FILE_COUNT: u8 = 0x06;
+const SVC_GET_DISK_INFO: u8 = 0x07;
+const SVC_LICENSE: u8 = 0x08;
+const SVC_UNSUPPORTED: u8 = 0xFF;
+
+/// BIOS error codes returned in `A` (zero = success).
+mod err {
+    pub const NO_DISK: u8 = 0x01;
+    pub const WRITE_PROTECTED: u8 = 0x03;
+    /// Maker code mismatch; game name, version, side, disk number and the two
+    /// additional id bytes follow as `$05-$0A`.
+    pub const MAKER: u8 = 0x04;
+    pub const LICENSE: u8 = 0x20;
+    pub const NO_SIGNATURE: u8 = 0x21;
+    pub const READ: u8 = 0x27;
+    pub const DISK_FULL: u8 = 0x30;
+    pub const FILE_COUNT: u8 = 0x31;
+}
+
+/// Documented entry points that are not implemented; each traps as
+/// unsupported.
+const UNSUPPORTED_ENTRIES: &[u16] = &[
+    0xE445, 0xE484, 0xE492, 0xE4A0, 0xE4DA, 0xE7BB, 0xE844, 0xE86A, 0xE8B3, 0xE8D2, 0xE8E1, 0xE94F,
+    0xE97D, 0xE997, 0xE9D3, 0xEA1A, 0xEA1F, 0xEA36, 0xEA4C, 0xEA68, 0xEA84, 0xEB13, 0xEB66,
+];
+
+/// Build the 8 KiB clean-room BIOS image (`$E000-$FFFF`).
+///
+/// Pass it to [`crate::Fds::new`] (or any API taking a BIOS) in place of
+/// `disksys.rom`; the device recognises it by [`FDS_HLE_SIGNATURE`] and
+/// enables the trap mailbox.
+#[must_use]
+#[allow(clippy::too_many_lines)] // one flat listing, address-commented like the NSF driver
+pub fn fds_hle_bios() -> Vec<u8> {
+    let mut img = vec![0xFFu8; 0x2000];
+    let mut put = |addr: u16, bytes: &[u8]| {
+        let at = usize::from(addr - 0xE000);
+        img[at..at + bytes.len()].copy_from_slice(bytes);
+    };
+    put(0xE000, FDS_HLE_SIGNATURE);
+
+    // --- Entry points ---------------------------------------------------
+    let [sl, sh] = SVC_COMMON.to_le_bytes();
+    let stub = |id: u8| [0xA2, id, 0x4C, sl, sh]; // LDX #id; JMP SVC_COMMON
+    let jmp = |target: u16| {
+        let [l, h] = target.to_le_bytes();
+        [0x4C, l, h]
+    };
+    put(0xE149, &jmp(0xF200)); // Delay132
+    put(0xE153, &jmp(0xF210)); // Delayms
+    put(0xE161, &jmp(0xF220)); // DisPFObj
+    put(0xE16B, &jmp(0xF227)); // EnPFObj
+    put(0xE170, &jmp(0xF22E)); // DisObj
+    put(0xE178, &jmp(0xF235)); // EnObj
+    put(0xE17E, &jmp(0xF23C)); // DisPF
+    put(0xE185, &jmp(0xF243)); // EnPF
+    put(0xE1B2, &jmp(0xF1C0)); // VINTWait
+    put(0xE1F8, &stub(SVC_LOAD_FILES));
+    put(0xE237, &[0xA9, 0xFF]); // AppendFile: LDA #$FF, falls into WriteFile
+    put(0xE239, &stub(SVC_WRITE_FILE));
+    // CheckFileCount sits four bytes before AdjustFileCount: LDX #id; BNE to a
+    // JMP placed after AdjustFileCount's stub.
+    put(0xE2B7, &[0xA2, SVC_CHECK_FILE_COUNT, 0xD0, 0x05]);
+    put(0xE2BB, &stub(SVC_ADJUST_FILE_COUNT));
+    put(0xE2C0, &jmp(SVC_COMMON));
+    put(0xE301, &[0xA2, SVC_SET_FILE_COUNT1, 0xD0, 0x05]);
+    put(0xE305, &stub(SVC_SET_FILE_COUNT));
+    put(0xE30A, &jmp(SVC_COMMON));
+    put(0xE32A, &stub(SVC_GET_DISK_INFO));
+    put(0xE9B1, &jmp(0xF300)); // Random
+    put(0xE9C8, &jmp(0xF260)); // SpriteDMA
+    put(0xE9EB, &jmp(0xF2B0)); // ReadPads
+    put(0xEAD2, &jmp(0xF2E0)); // MemFill
+    put(0xEAEA, &jmp(0xF270)); // SetScroll
+    put(0xEAFD, &jmp(0xF290)); // JumpEngine
+    for &entry in UNSUPPORTED_ENTRIES {
+        put(entry, &stub(SVC_UNSUPPORTED));
+    }
+
+    // --- Common trap routine (X = service id, A = caller's A) -------------
+    put(
+        SVC_COMMON,
+        &[
+            0x8D, 0xA0, 0x40, // F000 STA $40A0
+            0x8E, 0xA4, 0x40, // F003 STX $40A4
+            0xBA, // F006 TSX
+            0xBD, 0x01, 0x01, // F007 LDA $0101,X   ; return address low
+            0x8D, 0xA1, 0x40, // F00A STA $40A1
+            0xBD, 0x02, 0x01, // F00D LDA $0102,X   ; return address high
+            0x8D, 0xA2, 0x40, // F010 STA $40A2
+            0x8D, 0xA3, 0x40, // F013 STA $40A3     ; run the service
+            0xAD, 0xA2, 0x40, // F016 LDA $40A2     ; skip inline parameters
+            0x9D, 0x01, 0x01, // F019 STA $0101,X
+            0xAD, 0xA3, 0x40, // F01C LDA $40A3
+            0x9D, 0x02, 0x01, // F01F STA $0102,X
+            0xAC, 0xA1, 0x40, // F022 LDY $40A1
+            0xAD, 0xA0, 0x40, // F025 LDA $40A0     ; error code, sets Z
+            0x60, // F028 RTS
+        ],
+    );
+
+    // --- Reset ----------------------------------------------------------
+    #[rustfmt::skip]
+    let reset: &[u8] = &[
+        0x78,                 // F100 SEI
+        0xD8,                 // F101 CLD
+        0xA2, 0xFF,           // F102 LDX #$FF
+        0x9A,                 // F104 TXS
+        0xA9, 0x00,           // F105 LDA #$00
+        0x8D, 0x00, 0x20,     // F107 STA $2000
+        0x8D, 0x01, 0x20,     // F10A STA $2001
+        0xA9, 0x83,           // F10D LDA #$83
+        0x8D, 0x23, 0x40,     // F10F STA $4023          ; disk + sound I/O on
+        0xA9, 0x2E,           // F112 LDA #$2E
+        0x85, 0xFA,           // F114 STA $FA            ; $4025 mirror
+        0x8D, 0x25, 0x40,     // F116 STA $4025
+        0xA9, 0xFF,           // F119 LDA #$FF
+        0x85, 0xF9,           // F11B STA $F9            ; $4026 mirror
+        0x8D, 0x26, 0x40,     // F11D STA $4026
+        0xAD, 0x02, 0x01,     // F120 LDA $0102
+        0xC9, 0x35,           // F123 CMP #$35
+        0xD0, 0x13,           // F125 BNE cold
+        0xAD, 0x03, 0x01,     // F127 LDA $0103
+        0xC9, 0xAC,           // F12A CMP #$AC
+        0xF0, 0x04,           // F12C BEQ warm
+        0xC9, 0x53,           // F12E CMP #$53
+        0xD0, 0x08,           // F130 BNE cold
+        0xA9, 0x53,           // F132 warm: LDA #$53
+        0x8D, 0x03, 0x01,     // F134 STA $0103
+        0x4C, 0x6E, 0xF1,     // F137 JMP start_game
+        0x2C, 0x02, 0x20,     // F13A cold: BIT $2002
+        0x10, 0xFB,           // F13D BPL cold
+        0x2C, 0x02, 0x20,     // F13F BIT $2002
+        0x10, 0xFB,           // F142 BPL F13F
+        0xA9, 0x10,           // F144 LDA #$10
+        0x85, 0xFF,           // F146 STA $FF            ; PPUCTRL mirror
+        0xA9, 0x06,           // F148 LDA #$06
+        0x85, 0xFE,           // F14A STA $FE            ; PPUMASK mirror
+        0xA9, 0x00,           // F14C LDA #$00
+        0x85, 0xFD,           // F14E STA $FD            ; scroll X mirror
+        0x85, 0xFC,           // F150 STA $FC            ; scroll Y mirror
+        0x85, 0xFB,           // F152 STA $FB            ; $4016 mirror
+        0x20, 0xF8, 0xE1,     // F154 JSR LoadFiles
+        0x00, 0xFF,           // F157 .dw BOOT_DISK_ID
+        0x10, 0xFF,           // F159 .dw BOOT_LIST
+        0xD0, 0x25,           // F15B BNE boot_error
+        0xA2, SVC_LICENSE,    // F15D LDX #license
+        0x20, 0x00, 0xF0,     // F15F JSR SVC_COMMON
+        0xD0, 0x1E,           // F162 BNE boot_error
+        0xA9, 0x35,           // F164 LDA #$35
+        0x8D, 0x02, 0x01,     // F166 STA $0102
+        0xA9, 0xAC,           // F169 LDA #$AC
+        0x8D, 0x03, 0x01,     // F16B STA $0103
+        0xA9, 0xC0,           // F16E start_game: LDA #$C0
+        0x8D, 0x00, 0x01,     // F170 STA $0100          ; NMI -> ($DFFA)
+        0x8D, 0x01, 0x01,     // F173 STA $0101          ; IRQ -> ($DFFE)
+        0xA5, 0xFF,           // F176 LDA $FF
+        0x09, 0x80,           // F178 ORA #$80
+        0x85, 0xFF,           // F17A STA $FF
+        0x8D, 0x00, 0x20,     // F17C STA $2000
+        0x6C, 0xFC, 0xDF,     // F17F JMP ($DFFC)
+        0x8D, 0xA5, 0x40,     // F182 boot_error: STA $40A5
+        0x4C, 0x85, 0xF1,     // F185 JMP F185
+    ];
+    put(RESET, reset);
+
+    // --- NMI: dispatch on $0100 bits 7-6 --------------------------------
+    put(
+        NMI,
+        &[
+            0x2C, 0x00, 0x01, // F190 BIT $0100
+            0x10, 0x08, // F193 BPL low
+            0x50, 0x03, // F195 BVC vec2
+            0x6C, 0xFA, 0xDF, // F197 JMP ($DFFA)   ; %11
+            0x6C, 0xF8, 0xDF, // F19A vec2: JMP ($DFF8) ; %10
+            0x50, 0x03, // F19D low: BVC wait
+            0x6C, 0xF6, 0xDF, // F19F JMP ($DFF6)   ; %01
+            0xA5, 0xFF, // F1A2 wait: LDA $FF   ; %00: VINTWait return
+            0x29, 0x7F, // F1A4 AND #$7F
+            0x85, 0xFF, // F1A6 STA $FF
+            0x8D, 0x00, 0x20, // F1A8 STA $2000
+            0xAD, 0x02, 0x20, // F1AB LDA $2002
+            0x68, // F1AE PLA               ; drop P
+            0x68, // F1AF PLA               ; drop PC
+            0x68, // F1B0 PLA
+            0x68, // F1B1 PLA               ; $0100 saved by VINTWait
+            0x8D, 0x00, 0x01, // F1B2 STA $0100
+            0x60, // F1B5 RTS               ; out of VINTWait
+        ],
+    );
+
+    // --- VINTWait --------------------------------------------------------
+    put(
+        0xF1C0,
+        &[
+            0xAD, 0x00, 0x01, // F1C0 LDA $0100
+            0x48, // F1C3 PHA
+            0xA9, 0x00, // F1C4 LDA #$00
+            0x8D, 0x00, 0x01, // F1C6 STA $0100
+            0xA5, 0xFF, // F1C9 LDA $FF
+            0x09, 0x80, // F1CB ORA #$80
+            0x85, 0xFF, // F1CD STA $FF
+            0x8D, 0x00, 0x20, // F1CF STA $2000
+            0x4C, 0xD2, 0xF1, // F1D2 JMP F1D2      ; NMI returns for us
+        ],
+    );
+
+    // --- IRQ: dispatch on $0101 bits 7-6 --------------------------------
+    put(
+        IRQ,
+        &[
+            0x2C, 0x01, 0x01, // F1E0 BIT $0101
+            0x30, 0x08, // F1E3 BMI high
+            0x48, // F1E5 ack: PHA
+            0xAD, 0x30, 0x40, // F1E6 LDA $4030
+            0x68, // F1E9 PLA
+            0x40, // F1EA RTI
+            0xEA, 0xEA, // F1EB
+            0x50, 0xF6, // F1ED high: BVC ack   ; %10
+            0x6C, 0xFE, 0xDF, // F1EF JMP ($DFFE)   ; %11
+        ],
+    );
+
+    // --- Utilities ------------------------------------------------------
+    put(
+        0xF200,
+        &[
+            0x48, // F200 Delay132: PHA
+            0xA9, 0x16, // F201 LDA #$16
+            0x38, // F203 SEC
+            0xE9, 0x01, // F204 SBC #$01
+            0xB0, 0xFC, // F206 BCS F204
+            0x68, // F208 PLA
+            0x60, // F209 RTS
+        ],
+    );
+    put(
+        0xF210,
+        &[
+            0xA2, 0xC6, // F210 Delayms: LDX #$C6
+            0xEA, // F212 NOP
+            0xEA, // F213 NOP
+            0xCA, // F214 DEX
+            0xD0, 0xFB, // F215 BNE F212
+            0x88, // F217 DEY
+            0xD0, 0xF6, // F218 BNE F210
+            0x60, // F21A RTS
+        ],
+    );
+    // PPUMASK family: adjust the $FE mirror, then the shared store at $F250.
+    for (at, op, mask) in [
+        (0xF220u16, 0x29u8, 0xE7u8), // DisPFObj: AND #$E7
+        (0xF227, 0x09, 0x18),        // EnPFObj:  ORA #$18
+        (0xF22E, 0x29, 0xEF),        // DisObj:   AND #$EF
+        (0xF235, 0x09, 0x10),        // EnObj:    ORA #$10
+        (0xF23C, 0x29, 0xF7),        // DisPF:    AND #$F7
+        (0xF243, 0x09, 0x08),        // EnPF:     ORA #$08
+    ] {
+        put(at, &[0xA5, 0xFE, op, mask, 0x4C, 0x50, 0xF2]);
+    }
+    put(
+        0xF250,
+        &[
+            0x85, 0xFE, // F250 STA $FE
+            0x8D, 0x01, 0x20, // F252 STA $2001
+            0x60, // F255 RTS
+        ],
+    );
+    put(
+        0xF260,
+        &[
+            0xA9, 0x00, // F260 SpriteDMA: LDA #$00
+            0x8D, 0x03, 0x20, // F262 STA $2003
+            0xA9, 0x02, // F265 LDA #$02
+            0x8D, 0x14, 0x40, // F267 STA $4014
+            0x60, // F26A RTS
+        ],
+    );
+    put(
+        0xF270,
+        &[
+            0xAD, 0x02, 0x20, // F270 SetScroll: LDA $2002
+            0xA5, 0xFD, // F273 LDA $FD
+            0x8D, 0x05, 0x20, // F275 STA $2005
+            0xA5, 0xFC, // F278 LDA $FC
+            0x8D, 0x05, 0x20, // F27A STA $2005
+            0xA5, 0xFF, // F27D LDA $FF
+            0x8D, 0x00, 0x20, // F27F STA $2000
+            0x60, // F282 RTS
+        ],
+    );
+    put(
+        0xF290,
+        &[
+            0x0A, // F290 JumpEngine: ASL A
+            0xA8, // F291 TAY
+            0x68, // F292 PLA
+            0x85, 0x00, // F293 STA $00
+            0x68, // F295 PLA
+            0x85, 0x01, // F296 STA $01
+            0xC8, // F298 INY
+            0xB1, 0x00, // F299 LDA ($00),Y
+            0x85, 0x02, // F29B STA $02
+            0xC8, // F29D INY
+            0xB1, 0x00, // F29E LDA ($00),Y
+            0x85, 0x03, // F2A0 STA $03
+            0x6C, 0x02, 0x00, // F2A2 JMP ($0002)
+        ],
+    );
+    put(
+        0xF2B0,
+        &[
+            0xA5, 0xFB, // F2B0 ReadPads: LDA $FB
+            0x09, 0x01, // F2B2 ORA #$01
+            0x8D, 0x16, 0x40, // F2B4 STA $4016
+            0x29, 0xFE, // F2B7 AND #$FE
+            0x8D, 0x16, 0x40, // F2B9 STA $4016
+            0xA2, 0x08, // F2BC LDX #$08
+            0xAD, 0x16, 0x40, // F2BE LDA $4016
+            0x4A, // F2C1 LSR A
+            0x26, 0xF5, // F2C2 ROL $F5        ; pad 1
+            0x4A, // F2C4 LSR A
+            0x26, 0x00, // F2C5 ROL $00        ; expansion pad 1
+            0xAD, 0x17, 0x40, // F2C7 LDA $4017
+            0x4A, // F2CA LSR A
+            0x26, 0xF6, // F2CB ROL $F6        ; pad 2
+            0x4A, // F2CD LSR A
+            0x26, 0x01, // F2CE ROL $01        ; expansion pad 2
+            0xCA, // F2D0 DEX
+            0xD0, 0xEB, // F2D1 BNE F2BE
+            0x60, // F2D3 RTS
+        ],
+    );
+    put(
+        0xF2E0,
+        &[
+            0x48, // F2E0 MemFill: PHA   ; A = value, X..Y = pages
+            0x86, 0x01, // F2E1 STX $01
+            0xA9, 0x00, // F2E3 LDA #$00
+            0x85, 0x00, // F2E5 STA $00
+            0x68, // F2E7 PLA
+            0x84, 0x02, // F2E8 STY $02
+            0xA0, 0x00, // F2EA LDY #$00
+            0x91, 0x00, // F2EC STA ($00),Y
+            0xC8, // F2EE INY
+            0xD0, 0xFB, // F2EF BNE F2EC
+            0xA6, 0x01, // F2F1 LDX $01
+            0xE4, 0x02, // F2F3 CPX $02
+            0xF0, 0x05, // F2F5 BEQ F2FC
+            0xE6, 0x01, // F2F7 INC $01
+            0x4C, 0xEA, 0xF2, // F2F9 JMP F2EA
+            0x60, // F2FC RTS
+        ],
+    );
+    put(
+        0xF300,
+        &[
+            0xB5, 0x00, // F300 Random: LDA $00,X  ; X = seed, Y = length
+            0x29, 0x02, // F302 AND #$02
+            0x85, 0x00, // F304 STA $00
+            0xB5, 0x01, // F306 LDA $01,X
+            0x29, 0x02, // F308 AND #$02
+            0x45, 0x00, // F30A EOR $00
+            0x18, // F30C CLC
+            0xF0, 0x01, // F30D BEQ F310
+            0x38, // F30F SEC
+            0x76, 0x00, // F310 ROR $00,X
+            0xE8, // F312 INX
+            0x88, // F313 DEY
+            0xD0, 0xFA, // F314 BNE F310
+            0x60, // F316 RTS
+        ],
+    );
+
+    // --- Boot data and vectors --------------------------------------------
+    put(BOOT_DISK_ID, &[0xFF; 10]);
+    put(BOOT_LIST, &[0xFF, 0xFF]);
+    let [nl, nh] = NMI.to_le_bytes();
+    let [rl, rh] = RESET.to_le_bytes();
+    let [il, ih] = IRQ.to_le_bytes();
+    put(0xFFFA, &[nl, nh, rl, rh, il, ih]);
+    img
+}
+
+/// Whether `bios` is an image built by [`fds_hle_bios`].
+#[must_use]
+pub fn is_hle_bios(bios: &[u8]) -> bool {
+    bios.starts_with(FDS_HLE_SIGNATURE)
+}
+
+/// The console memory an HLE service reaches outside the RAM adapter.
+///
+/// CPU work RAM and the PPU's nametable / palette storage; the bus implements
+/// it over its own RAM and PPU for the duration of one trapped call.
+pub trait FdsHleHost {
+    /// Read CPU work RAM (`addr` in `$0000-$1FFF`, mirrored).
+    fn read_ram(&mut self, addr: u16) -> u8;
+    /// Write CPU work RAM (`addr` in `$0000-$1FFF`, mirrored).
+    fn write_ram(&mut self, addr: u16, value: u8);
+    /// Read CIRAM at a physical offset (`0..0x800`).
+    fn read_ciram(&mut self, offset: usize) -> u8;
+    /// Write CIRAM at a physical offset (`0..0x800`).
+    fn write_ciram(&mut self, offset: usize, value: u8);
+    /// Read palette RAM (`addr` in `$3F00-$3FFF`).
+    fn read_palette(&mut self, addr: u16) -> u8;
+    /// Write palette RAM (`addr` in `$3F00-$3FFF`).
+    fn write_palette(&mut self, addr: u16, value: u8);
+}
+
+/// Trap mailbox and diagnostics of an HLE BIOS. Its contents only live for the
+/// duration of one trapped call, so it is not part of the save-state.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FdsHle {
+    arg_a: u8,
+    service: u8,
+    ret: u16,
+    result_a: u8,
+    result_y: u8,
+    /// A service was triggered and waits for the bus to run it.
+    pub(crate) pending: bool,
+    /// Error code the boot sequence stopped on, if any.
+    pub(crate) boot_error: Option<u8>,
+    /// Unsupported entry points called so far (deduplicated, call order).
+    pub(crate) unsupported: Vec<u16>,
+}
+
+/// Everything a service touches, borrowed from the device for one call.
+pub(crate) struct HleEnv<'a> {
+    pub host: &'a mut dyn FdsHleHost,
+    pub prg_ram: &'a mut [u8],
+    pub chr_ram: &'a mut [u8],
+    pub bios: &'a [u8],
+    pub mirroring: Mirroring,
+    pub disk: &'a mut FdsDisk,
+    pub side: Option<usize>,
+    pub write_protected: bool,
+    pub dirty: &'a mut bool,
+}
+
+impl HleEnv<'_> {
+    fn read_cpu(&mut self, addr: u16) -> u8 {
+        match addr {
+            0x0000..=0x1FFF => self.host.read_ram(addr),
+            0x6000..=0xDFFF => self.prg_ram[usize::from(addr - 0x6000)],
+            0xE000..=0xFFFF => self.bios[usize::from(addr - 0xE000)],
+            _ => 0,
+        }
+    }
+
+    fn write_cpu(&mut self, addr: u16, value: u8) {
+        match addr {
+            0x0000..=0x1FFF => self.host.write_ram(addr, value),
+            0x6000..=0xDFFF => self.prg_ram[usize::from(addr - 0x6000)] = value,
+            _ => {}
+        }
+    }
+
+    fn read_word(&mut self, addr: u16) -> u16 {
+        u16::from_le_bytes([self.read_cpu(addr), self.read_cpu(addr.wrapping_add(1))])
+    }
+
+    fn nametable_offset(&self, addr: u16) -> usize {
+        let table = ((addr - 0x2000) >> 10) & 0x03;
+        #[allow(clippy::cast_possible_truncation)]
+        let bank = self.mirroring.physical_bank(table as u8);
+        bank * 0x400 + usize::from(addr & 0x3FF)
+    }
+
+    fn read_ppu(&mut self, addr: u16) -> u8 {
+        let addr = addr & 0x3FFF;
+        match addr {
+            0x0000..=0x1FFF => self.chr_ram[usize::from(addr)],
+            0x2000..=0x3EFF => {
+                let offset = self.nametable_offset(addr & 0x2FFF);
+                self.host.read_ciram(offset)
+            }
+            _ => self.host.read_palette(addr),
+        }
+    }
+
+    fn write_ppu(&mut self, addr: u16, value: u8) {
+        let addr = addr & 0x3FFF;
+        match addr {
+            0x0000..=0x1FFF => self.chr_ram[usize::from(addr)] = value,
+            0x2000..=0x3EFF => {
+                let offset = self.nametable_offset(addr & 0x2FFF);
+                self.host.write_ciram(offset, value);
+            }
+            _ => self.host.write_palette(addr, value),
+        }
+    }
+
+    /// Parse the inserted side, mapping "no disk" and structural damage to
+    /// BIOS error codes.
+    fn side_files(&self) -> Result<(usize, FdsSideFiles), u8> {
+        let side = self.side.ok_or(err::NO_DISK)?;
+        let files = self.disk.side_files(side).map_err(|e| fs_error_code(&e))?;
+        Ok((side, files))
+    }
+
+    /// Compare the ten-byte disk id at `id_ptr` (`$FF` = don't care) against
+    /// the inserted side.
+    fn check_disk_id(&mut self, id_ptr: u16, files: &FdsSideFiles) -> Result<(), u8> {
+        const CODES: [u8; 10] = [0, 1, 1, 1, 1, 2, 3, 4, 5, 6];
+        for (i, code) in CODES.iter().enumerate() {
+            #[allow(clippy::cast_possible_truncation)]
+            let want = self.read_cpu(id_ptr.wrapping_add(i as u16));
+            if want != 0xFF && want != files.info.raw[15 + i] {
+                return Err(err::MAKER + code);
+            }
+        }
+        Ok(())
+    }
+
+    /// Record an edited file list onto `side`. [`FdsDisk::replace_side`]
+    /// patches only the blocks that changed, so the stored CRCs of the rest
+    /// (deliberately bad ones included) stay as the disk carried them.
+    fn write_back(&mut self, side: usize, files: &FdsSideFiles) -> Result<(), u8> {
+        if self.write_protected {
+            return Err(err::WRITE_PROTECTED);
+        }
+        let bytes = files.to_side().map_err(|e| fs_error_code(&e))?;
+        self.disk.replace_side(side, &bytes);
+        *self.dirty = true;
+        Ok(())
+    }
+}
+
+/// Map a file-system error to the closest BIOS error code.
+const fn fs_error_code(e: &FdsFsError) -> u8 {
+    match *e {
+        FdsFsError::BadDiskInfo => err::NO_SIGNATURE,
+        // $22-$25: block type 1-4 expected.
+        FdsFsError::BadBlockCode { expected, .. } => 0x21 + expected,
+        FdsFsError::SideFull { .. } | FdsFsError::TooManyFiles => err::DISK_FULL,
+        _ => err::READ,
+    }
+}
+
+impl FdsHle {
+    /// A mailbox register write (`$40A0-$40A5`).
+    pub(crate) fn write(&mut self, addr: u16, value: u8) {
+        match addr {
+            PORT_A => self.arg_a = value,
+            PORT_RET_LO => self.ret = (self.ret & 0xFF00) | u16::from(value),
+            PORT_RET_HI => self.ret = (self.ret & 0x00FF) | (u16::from(value) << 8),
+            PORT_TRIGGER => self.pending = true,
+            PORT_SERVICE => self.service = value,
+            PORT_BOOT_ERROR => self.boot_error = Some(value),
+            _ => {}
+        }
+    }
+
+    /// A mailbox register read (`$40A0-$40A3`).
+    pub(crate) const fn read(&self, addr: u16) -> u8 {
+        let [lo, hi] = self.ret.to_le_bytes();
+        match addr {
+            PORT_A => self.result_a,
+            PORT_RET_LO => self.result_y,
+            PORT_RET_HI => lo,
+            PORT_TRIGGER => hi,
+            _ => 0,
+        }
+    }
+
+    /// Run the pending service. On return the mailbox holds the result `A`,
+    /// `Y`, and the return address advanced past any inline parameters.
+    pub(crate) fn run(&mut self, env: &mut HleEnv<'_>) {
+        self.pending = false;
+        let params = self.ret.wrapping_add(1);
+        let (skip, outcome) = match self.service {
+            SVC_LOAD_FILES => (4, Self::load_files(env, params)),
+            SVC_WRITE_FILE => (4, Self::write_file(env, params, self.arg_a)),
+            SVC_CHECK_FILE_COUNT => (
+                2,
+                Self::set_file_count(
+                    env,
+                    params,
+                    |n, a| {
+                        if a > n { None } else { Some(a) }
+                    },
+                    self.arg_a,
+                ),
+            ),
+            SVC_ADJUST_FILE_COUNT => (
+                2,
+                Self::set_file_count(env, params, u8::checked_sub, self.arg_a),
+            ),
+            SVC_SET_FILE_COUNT1 => (
+                2,
+                Self::set_file_count(env, params, |_, a| a.checked_add(1), self.arg_a),
+            ),
+            SVC_SET_FILE_COUNT => (
+                2,
+                Self::set_file_count(env, params, |_, a| Some(a), self.arg_a),
+            ),
+            SVC_GET_DISK_INFO => (2, Self::get_disk_info(env, params)),
+            SVC_LICENSE => (0, Self::license_check(env)),
+            _ => {
+                // The JSR operand sits just below the return address.
+                let entry = env.read_word(self.ret.wrapping_sub(1));
+                if !self.unsupported.contains(&entry) {
+                    self.unsupported.push(entry);
+                }
+                (0, Ok(self.arg_a))
+            }
+        };
+        self.ret = self.ret.wrapping_add(skip);
+        match outcome {
+            Ok(y) => {
+                self.result_a = if self.service == SVC_UNSUPPORTED {
+                    self.arg_a
+                } else {
+                    0
+                };
+                self.result_y = y;
+            }
+            Err(code) => {
+                self.result_a = code;
+                self.result_y = 0;
+            }
+        }
+    }
+
+    /// `LoadFiles`: `.dw disk_id, .dw load_list`. Loads every counted file
+    /// whose id is in the `$FF`-terminated list (at most 20 ids), or — when the
+    /// list opens with `$FF` — every file up to the boot read file code.
+    /// Returns the number of files loaded in `Y`.
+    fn load_files(env: &mut HleEnv<'_>, params: u16) -> Result<u8, u8> {
+        let id_ptr = env.read_word(params);
+        let list_ptr = env.read_word(params.wrapping_add(2));
+        let (_, files) = env.side_files()?;
+        env.check_disk_id(id_ptr, &files)?;
+        let mut ids = Vec::new();
+        for i in 0..20u16 {
+            let id = env.read_cpu(list_ptr.wrapping_add(i));
+            if id == 0xFF {
+                break;
+            }
+            ids.push(id);
+        }
+        let boot = ids.is_empty();
+        let boot_id = files.info.boot_file_id();
+        let mut loaded = 0u8;
+        for file in &files.files[..files.counted_files()] {
+            let wanted = if boot {
+                file.id <= boot_id
+            } else {
+                ids.contains(&file.id)
+            };
+            if !wanted {
+                continue;
+            }
+            Self::load_file(env, file);
+            loaded = loaded.wrapping_add(1);
+        }
+        Ok(loaded)
+    }
+
+    fn load_file(env: &mut HleEnv<'_>, file: &FdsFile) {
+        let mut addr = file.load_address;
+        for &byte in &file.data {
+            if file.kind == FdsFileKind::Prg {
+                env.write_cpu(addr, byte);
+            } else {
+                env.write_ppu(addr, byte);
+            }
+            addr = addr.wrapping_add(1);
+        }
+    }
+
+    /// `WriteFile` (`AppendFile` = `A` `$FF`): `.dw disk_id, .dw header`,
+    /// `A` = file number to write at. The 17-byte header is id, name (8), load
+    /// address, size, kind, source address and source space (0 = CPU,
+    /// otherwise PPU). Files from that position on are overwritten and the
+    /// file amount becomes position + 1.
+    fn write_file(env: &mut HleEnv<'_>, params: u16, at: u8) -> Result<u8, u8> {
+        let id_ptr = env.read_word(params);
+        let hdr = env.read_word(params.wrapping_add(2));
+        let (side, mut files) = env.side_files()?;
+        env.check_disk_id(id_ptr, &files)?;
+        let counted = files.counted_files();
+        let index = if at == 0xFF {
+            counted
+        } else {
+            usize::from(at).min(counted)
+        };
+        let mut name = [0u8; 8];
+        for (i, b) in name.iter_mut().enumerate() {
+            #[allow(clippy::cast_possible_truncation)]
+            let addr = hdr.wrapping_add(1 + i as u16);
+            *b = env.read_cpu(addr);
+        }
+        let size = env.read_word(hdr.wrapping_add(11));
+        let src = env.read_word(hdr.wrapping_add(14));
+        let from_ppu = env.read_cpu(hdr.wrapping_add(16)) != 0;
+        let data = (0..size)
+            .map(|i| {
+                let addr = src.wrapping_add(i);
+                if from_ppu {
+                    env.read_ppu(addr)
+                } else {
+                    env.read_cpu(addr)
+                }
+            })
+            .collect();
+        let file = FdsFile {
+            number: 0,
+            id: env.read_cpu(hdr),
+            name,
+            load_address: env.read_word(hdr.wrapping_add(9)),
+            kind: FdsFileKind::from_byte(env.read_cpu(hdr.wrapping_add(13))),
+            data,
+        };
+        files.files.truncate(index);
+        files.trailer.clear();
+        files.file_amount = u8::try_from(index).map_err(|_| err::DISK_FULL)?;
+        files.insert_file(file).map_err(|e| fs_error_code(&e))?;
+        env.write_back(side, &files)?;
+        Ok(0)
+    }
+
+    /// The file-count family: `.dw disk_id`, `A` = operand. `update` maps the
+    /// current count and `A` to the new count, or `None` for a count mismatch.
+    fn set_file_count(
+        env: &mut HleEnv<'_>,
+        params: u16,
+        update: impl Fn(u8, u8) -> Option<u8>,
+        a: u8,
+    ) -> Result<u8, u8> {
+        let id_ptr = env.read_word(params);
+        let (side, mut files) = env.side_files()?;
+        env.check_disk_id(id_ptr, &files)?;
+        let count = update(files.file_amount, a).ok_or(err::FILE_COUNT)?;
+        files.file_amount = count;
+        env.write_back(side, &files)?;
+        Ok(0)
+    }
+
+    /// `GetDiskInfo`: `.dw buffer`. Fills the buffer with the ten-byte disk
+    /// id, the file count, an id + eight-byte name per counted file, and the
+    /// two-byte total of the file sizes (low byte first).
+    fn get_disk_info(env: &mut HleEnv<'_>, params: u16) -> Result<u8, u8> {
+        let mut out = env.read_word(params);
+        let (_, files) = env.side_files()?;
+        let counted = &files.files[..files.counted_files()];
+        let mut bytes = files.info.raw[15..25].to_vec();
+        bytes.push(files.file_amount);
+        let mut total = 0u16;
+        for file in counted {
+            bytes.push(file.id);
+            bytes.extend_from_slice(&file.name);
+            #[allow(clippy::cast_possible_truncation)]
+            let size = file.data.len() as u16;
+            total = total.wrapping_add(size);
+        }
+        bytes.extend_from_slice(&total.to_le_bytes());
+        for b in bytes {
+            env.write_cpu(out, b);
+            out = out.wrapping_add(1);
+        }
+        Ok(0)
+    }
+
+    /// The license-screen check: the boot files must include the 224-byte
+    /// VRAM file at `$2800` every licensed disk carries. Only its placement is
+    /// checked, not its text.
+    fn license_check(env: &HleEnv<'_>) -> Result<u8, u8> {
+        let (_, files) = env.side_files()?;
+        let boot_id = files.info.boot_file_id();
+        files.files[..files.counted_files()]
+            .iter()
+            .any(|f| {
+                f.id <= boot_id
+                    && f.kind == FdsFileKind::Vram
+                    && f.load_address == 0x2800
+                    && f.data.len() == 0xE0
+            })
+            .then_some(0)
+            .ok_or(err::LICENSE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_carries_signature_and_vectors() {
+        let bios = fds_hle_bios();
+        assert_eq!(bios.len(), 0x2000);
+        assert!(is_hle_bios(&bios));
+        assert!(!is_hle_bios(&[0u8; 0x2000]));
+        assert_eq!(&bios[0x1FFA..], &[0x90, 0xF1, 0x00, 0xF1, 0xE0, 0xF1]);
+        // LoadFiles stub jumps to the common trap routine.
+        assert_eq!(
+            &bios[0x01F8..0x01FD],
+            &[0xA2, SVC_LOAD_FILES, 0x4C, 0x00, 0xF0]
+        );
+        // AppendFile falls straight into WriteFile.
+        assert_eq!(&bios[0x0237..0x023B], &[0xA9, 0xFF, 0xA2, SVC_WRITE_FILE]);
+    }
+
+    #[test]
+    fn mailbox_returns_advanced_address() {
+        let mut hle = FdsHle::default();
+        hle.write(PORT_RET_LO, 0x56);
+        hle.write(PORT_RET_HI, 0x12);
+        hle.ret = hle.ret.wrapping_add(4);
+        assert_eq!(hle.read(PORT_RET_HI), 0x5A);
+        assert_eq!(hle.read(PORT_TRIGGER), 0x12);
+        hle.write(PORT_TRIGGER, 0);
+        assert!(hle.pending);
+    }
+}
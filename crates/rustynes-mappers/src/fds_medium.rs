@@ -0,0 +1,600 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//! Physical-layer FDS disk medium (v2.5.0).
+//!
+//! The `.fds` / fwNES container stores a side as its block payloads laid end to
+//! end — no gaps, no start marks, no CRCs — and the QD form adds only the two
+//! CRC bytes after each block. Neither is what the RP2C33 drive controller
+//! actually sees. [`FdsSideMedium`] is that physical view: the byte-granular
+//! wire stream of one side, with a lead-in gap, a `$80` start mark before every
+//! block, the block payload, its stored CRC-16, inter-block gaps, and an
+//! end-of-side where the recorded surface stops.
+//!
+//! The medium is the **authoritative** disk storage: the drive state machine in
+//! [`crate::fds::Fds`] reads and writes it directly, so a block the BIOS appends
+//! past the last file, a rewritten file header that changes the following data
+//! block's length, or a block whose stored CRC is deliberately wrong (copy
+//! protection) all survive as written. The `.fds` and QD byte forms are derived
+//! from it on demand and convert losslessly in both directions.
+//!
+//! References: `FDS_disk_format.xhtml` (gap / mark / CRC layout, QD format).
+
+use alloc::vec::Vec;
+
+use crate::fds::{FDS_SIDE_LEN, FdsMediumError, QD_SIDE_LEN};
+
+/// Lead-in gap length (in `$00` bytes) synthesized before the first block of a
+/// side. Hardware uses a long disk-start gap (≈26150-28300 bits ≈ 3300-3500
+/// bytes); the BIOS only requires "enough" zero bytes before the first `$80`
+/// start mark to settle its block-scan loop. A modest lead-in keeps the wire
+/// image small while still giving the loader its expected pre-disk gap.
+pub(crate) const WIRE_LEAD_IN_GAP: usize = 200;
+
+/// Inter-block gap length (in `$00` bytes) synthesized between consecutive
+/// blocks. Hardware uses ≥480 bits (≈60 bytes), 976 bits typical; the loader
+/// accepts a much smaller minimum (a few hundred bits). This value sits
+/// comfortably above the minimum the BIOS needs to re-detect a block start.
+pub(crate) const WIRE_BLOCK_GAP: usize = 100;
+
+/// The FDS block start mark. On the medium each gap is terminated by a single
+/// `1` bit; in byte terms (little-endian) that is `$80`. The BIOS scans the bit
+/// stream for this mark to find the start of every block.
+pub(crate) const WIRE_START_MARK: u8 = 0x80;
+
+/// Recordable length of one side's surface, in wire bytes.
+///
+/// A converted image records only as far as its last block plus a trailing gap;
+/// the rest of the surface up to this capacity is blank and is materialized
+/// (as `$00`) only when a write runs past the recorded end — which is how a
+/// BIOS `AppendFile` lands a new block after the last file. It covers a full QD
+/// side's payload plus gap/mark overhead for well over the 2 × 128 blocks a
+/// side's file-count byte can describe.
+pub const FDS_MEDIUM_CAPACITY: usize = WIRE_LEAD_IN_GAP + QD_SIDE_LEN + 0x8000;
+
+/// CRC-16/KERMIT (a.k.a. CRC-16/CCITT, reflected, poly 0x8408) update for one
+/// byte. Running it over the start mark + block + the two stored CRC bytes
+/// leaves a zero residue for an intact block, which is exactly the check the
+/// controller performs on read.
+#[must_use]
+pub(crate) fn crc16_update(mut crc: u16, byte: u8) -> u16 {
+    crc ^= u16::from(byte);
+    for _ in 0..8 {
+        let carry = crc & 1 != 0;
+        crc >>= 1;
+        if carry {
+            crc ^= 0x8408;
+        }
+    }
+    crc
+}
+
+/// CRC-16/KERMIT over the start mark + block bytes, matching the FDS RP2C33
+/// block CRC. The BIOS does not verify it for the standard load path, but
+/// synthesizing a correct value keeps the wire image faithful and avoids
+/// tripping `$4030.D4` on stricter loaders.
+#[must_use]
+pub(crate) fn fds_block_crc(start_mark: u8, block: &[u8]) -> u16 {
+    let mut crc = crc16_update(0, start_mark);
+    for &b in block {
+        crc = crc16_update(crc, b);
+    }
+    crc
+}
+
+/// Payload length of the block opening at `block[0]`, derived from its
+/// block-code byte:
+/// - `$01` disk-info: 56 bytes.
+/// - `$02` file-amount: 2 bytes (byte 1 = file count).
+/// - `$03` file-header: 16 bytes (file size = LE u16 at offset 13).
+/// - `$04` file-data: `1 + size` bytes (size from the preceding header).
+///
+/// `pending_file_size` carries a header's size forward to its data block.
+/// Returns `None` for any other code (most commonly `$00` padding), which ends
+/// the structured region of a side.
+fn block_len(block: &[u8], pending_file_size: &mut Option<usize>) -> Option<usize> {
+    match block.first()? {
+        0x01 => Some(56),
+        0x02 => Some(2),
+        0x03 => {
+            // File size lives at header offset 13-14 (LE u16); remember it for
+            // the file-data block that must follow.
+            let size = if block.len() >= 15 {
+                usize::from(block[13]) | (usize::from(block[14]) << 8)
+            } else {
+                0
+            };
+            *pending_file_size = Some(size);
+            Some(16)
+        }
+        0x04 => Some(1 + pending_file_size.take().unwrap_or(0)),
+        _ => None,
+    }
+}
+
+/// Walk a concatenated-block side and return its blocks as `(start, len)`
+/// pairs in stream order. `crc_len` is the number of CRC bytes stored after
+/// each block: 0 for the `.fds` form, 2 for QD.
+///
+/// A declared block that runs past the end of the side is clamped and ends the
+/// walk, so a truncated dump still yields every byte it carries.
+fn walk_blocks(side: &[u8], crc_len: usize) -> Vec<(usize, usize)> {
+    let mut blocks = Vec::new();
+    let mut pos = 0usize;
+    let mut pending_file_size = None;
+    while pos < side.len() {
+        let Some(len) = block_len(&side[pos..], &mut pending_file_size) else {
+            break;
+        };
+        if pos + len > side.len() {
+            blocks.push((pos, side.len() - pos));
+            break;
+        }
+        blocks.push((pos, len));
+        pos += len + crc_len;
+    }
+    blocks
+}
+
+/// Strip trailing `$00` bytes: a trailer is all-zero for nearly every dump, and
+/// storing only its significant prefix keeps a side's footprint to its blocks.
+fn trim_trailing_zeros(bytes: &[u8]) -> Vec<u8> {
+    let end = bytes.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    bytes[..end].to_vec()
+}
+
+/// One recorded block on a [`FdsSideMedium`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MediumBlock {
+    /// Wire offset of the block's first payload byte (its block-code byte),
+    /// i.e. just after its `$80` start mark.
+    pub(crate) payload_start: usize,
+    /// Payload length in bytes (block-code byte included; the two CRC bytes
+    /// that follow on the wire are not).
+    pub(crate) len: usize,
+}
+
+impl MediumBlock {
+    /// Wire offset one past the payload (where the CRC low byte sits).
+    pub(crate) const fn payload_end(&self) -> usize {
+        self.payload_start + self.len
+    }
+}
+
+/// The physical wire stream of one disk side.
+///
+/// Layout, for each block: `[gap $00 × G] [$80] [payload] [crc_lo] [crc_hi]`,
+/// with a long lead-in gap before block 1, shorter inter-block gaps, and a
+/// trailing gap before the end of the recorded surface. The CRC bytes are
+/// *stored*, not recomputed on read — a QD image carrying a deliberately bad
+/// CRC keeps it, and the drive reports it through `$4030.D4`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FdsSideMedium {
+    /// The recorded wire bytes. Reads at or past the end report end-of-head;
+    /// writes may extend it up to [`FDS_MEDIUM_CAPACITY`].
+    bytes: Vec<u8>,
+    /// Block index, in stream order. Derived from `bytes` by [`Self::rescan`];
+    /// kept so the drive can resolve a head position to a block in O(blocks).
+    blocks: Vec<MediumBlock>,
+    /// Bytes the source image carried after its last block (zero-trimmed).
+    /// Not part of any block, so not on the wire; re-appended after the blocks
+    /// when converting back so an unmodified image round-trips byte-exact.
+    trailer: Vec<u8>,
+}
+
+impl FdsSideMedium {
+    /// Lay out `blocks` (payload, stored CRC) pairs as a wire image.
+    fn from_blocks<'a>(
+        blocks: impl Iterator<Item = (&'a [u8], [u8; 2])>,
+        size_hint: usize,
+        trailer: Vec<u8>,
+    ) -> Self {
+        let mut bytes = Vec::with_capacity(size_hint + WIRE_LEAD_IN_GAP);
+        let mut index = Vec::new();
+        for (i, (payload, crc)) in blocks.enumerate() {
+            let gap = if i == 0 {
+                WIRE_LEAD_IN_GAP
+            } else {
+                WIRE_BLOCK_GAP
+            };
+            bytes.resize(bytes.len() + gap, 0x00);
+            bytes.push(WIRE_START_MARK);
+            index.push(MediumBlock {
+                payload_start: bytes.len(),
+                len: payload.len(),
+            });
+            bytes.extend_from_slice(payload);
+            bytes.extend_from_slice(&crc);
+        }
+        // Trailing gap so the head reads `$00` past the last block instead of
+        // running straight off the end (a small, fixed tail is enough).
+        bytes.resize(bytes.len() + WIRE_BLOCK_GAP, 0x00);
+        Self {
+            bytes,
+            blocks: index,
+            trailer,
+        }
+    }
+
+    /// Record a `.fds`-form side (concatenated payloads, no CRCs). Every block
+    /// gets a freshly computed CRC-16, as a real drive would have written it.
+    pub(crate) fn from_fds_side(side: &[u8]) -> Self {
+        let raw = walk_blocks(side, 0);
+        let end = raw.last().map_or(0, |&(s, l)| s + l);
+        let blocks = raw.iter().map(|&(s, l)| {
+            let payload = &side[s..s + l];
+            let crc = fds_block_crc(WIRE_START_MARK, payload);
+            (payload, crc.to_le_bytes())
+        });
+        Self::from_blocks(blocks, side.len(), trim_trailing_zeros(&side[end..]))
+    }
+
+    /// Record a QD-form side (each payload followed by its two stored CRC
+    /// bytes). The stored CRCs are kept verbatim, good or bad.
+    pub(crate) fn from_qd_side(side: &[u8]) -> Self {
+        let raw = walk_blocks(side, 2);
+        let end = raw.last().map_or(0, |&(s, l)| (s + l + 2).min(side.len()));
+        let blocks = raw.iter().map(|&(s, l)| {
+            let payload = &side[s..s + l];
+            // A payload truncated by the end of the side has no stored CRC;
+            // synthesize one rather than invent a CRC error.
+            let crc = side.get(s + l..s + l + 2).map_or_else(
+                || fds_block_crc(WIRE_START_MARK, payload).to_le_bytes(),
+                |c| [c[0], c[1]],
+            );
+            (payload, crc)
+        });
+        Self::from_blocks(blocks, side.len(), trim_trailing_zeros(&side[end..]))
+    }
+
+    /// The recorded wire bytes.
+    pub(crate) fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Recorded length in wire bytes (the end-of-side position).
+    pub(crate) const fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// The block index, in stream order.
+    pub(crate) fn blocks(&self) -> &[MediumBlock] {
+        &self.blocks
+    }
+
+    /// Byte at wire offset `pos` (callers bound by [`Self::len`]).
+    pub(crate) fn byte(&self, pos: usize) -> u8 {
+        self.bytes[pos]
+    }
+
+    /// Store `value` at wire offset `pos`, extending the recorded surface with
+    /// blank (`$00`) bytes when `pos` is past the current end. Returns `false`
+    /// (and stores nothing) at or past [`FDS_MEDIUM_CAPACITY`] — the physical
+    /// end of the side.
+    pub(crate) fn store(&mut self, pos: usize, value: u8) -> bool {
+        if pos >= FDS_MEDIUM_CAPACITY {
+            return false;
+        }
+        if pos >= self.bytes.len() {
+            self.bytes.resize(pos + 1, 0x00);
+        }
+        self.bytes[pos] = value;
+        true
+    }
+
+    /// Index of the block whose payload contains wire offset `pos`.
+    pub(crate) fn block_at(&self, pos: usize) -> Option<usize> {
+        self.blocks
+            .iter()
+            .position(|b| pos >= b.payload_start && pos < b.payload_end())
+    }
+
+    /// Index of the block whose payload starts exactly at wire offset `pos`.
+    pub(crate) fn block_starting_at(&self, pos: usize) -> Option<usize> {
+        self.blocks.iter().position(|b| b.payload_start == pos)
+    }
+
+    /// Re-emit the stored CRC-16 of block `idx` from its current payload — the
+    /// controller's CRC generator closing a block it has just written.
+    pub(crate) fn resynth_crc(&mut self, idx: usize) {
+        let blk = self.blocks[idx];
+        let end = blk.payload_end();
+        if end + 1 < self.bytes.len() {
+            let crc = fds_block_crc(WIRE_START_MARK, &self.bytes[blk.payload_start..end]);
+            self.bytes[end..end + 2].copy_from_slice(&crc.to_le_bytes());
+        }
+    }
+
+    /// Rebuild the block index from the recorded bytes after a write.
+    ///
+    /// Walks gap → `$80` → payload → CRC exactly as the read path does. A
+    /// payload's length comes from its block code when that is valid (so a
+    /// rewritten file header re-sizes the data block after it); a block whose
+    /// code byte was overwritten with something unrecognizable keeps its
+    /// previously indexed length, so a partial rewrite never drops the block.
+    /// The walk ends at the first non-gap byte that is not a start mark, or at
+    /// a block that does not fit before the end of the side. If a write left
+    /// the last block (or its CRC) at the very end of the recorded surface,
+    /// the trailing gap is laid down again, up to [`FDS_MEDIUM_CAPACITY`].
+    pub(crate) fn rescan(&mut self) {
+        let mut blocks = Vec::with_capacity(self.blocks.len());
+        let mut pending_file_size = None;
+        let mut pos = 0usize;
+        loop {
+            while pos < self.bytes.len() && self.bytes[pos] == 0x00 {
+                pos += 1;
+            }
+            if pos >= self.bytes.len() || self.bytes[pos] != WIRE_START_MARK {
+                break;
+            }
+            let payload_start = pos + 1;
+            let len = block_len(&self.bytes[payload_start..], &mut pending_file_size)
+                .filter(|&l| payload_start + l + 2 <= self.bytes.len())
+                .or_else(|| {
+                    self.blocks
+                        .iter()
+                        .find(|b| b.payload_start == payload_start)
+                        .map(|b| b.len)
+                });
+            let Some(len) = len else { break };
+            if payload_start + len + 2 > self.bytes.len() {
+                break;
+            }
+            blocks.push(MediumBlock { payload_start, len });
+            pos = payload_start + len + 2;
+        }
+        if let Some(last) = blocks.last() {
+            let tail = (last.payload_end() + 2 + WIRE_BLOCK_GAP).min(FDS_MEDIUM_CAPACITY);
+            if self.bytes.len() < tail {
+                self.bytes.resize(tail, 0x00);
+            }
+        }
+        self.blocks = blocks;
+    }
+
+    /// Re-record the side from `.fds`-form bytes, block by block, the way a
+    /// drive rewriting only what changed would leave it.
+    ///
+    /// Leading blocks whose length is unchanged stay where they are: an
+    /// identical payload keeps its stored CRC (good or bad) and a changed one
+    /// is patched in place with a fresh CRC. From the first block that changes
+    /// length, is new, or is gone, the rest of the side is laid out again; a
+    /// block there whose payload matches the old block at the same index still
+    /// keeps that block's stored CRC.
+    pub(crate) fn rewrite(&mut self, side: &[u8]) {
+        let raw = walk_blocks(side, 0);
+        let end = raw.last().map_or(0, |&(s, l)| s + l);
+        self.trailer = trim_trailing_zeros(&side[end..]);
+
+        let mut keep = 0;
+        while let (Some(&(s, l)), Some(&blk)) = (raw.get(keep), self.blocks.get(keep)) {
+            if blk.len != l {
+                break;
+            }
+            let payload = &side[s..s + l];
+            if self.bytes[blk.payload_start..blk.payload_end()] != *payload {
+                self.bytes[blk.payload_start..blk.payload_end()].copy_from_slice(payload);
+                self.resynth_crc(keep);
+            }
+            keep += 1;
+        }
+        if keep == raw.len() && keep == self.blocks.len() {
+            return;
+        }
+
+        let old = self.clone();
+        let cut = keep
+            .checked_sub(1)
+            .map_or(0, |last| self.blocks[last].payload_end() + 2);
+        self.bytes.truncate(cut);
+        self.blocks.truncate(keep);
+        for (i, &(s, l)) in raw.iter().enumerate().skip(keep) {
+            let payload = &side[s..s + l];
+            let crc = old
+                .blocks
+                .get(i)
+                .filter(|b| old.bytes[b.payload_start..b.payload_end()] == *payload)
+                .map_or_else(
+                    || fds_block_crc(WIRE_START_MARK, payload).to_le_bytes(),
+                    |b| [old.bytes[b.payload_end()], old.bytes[b.payload_end() + 1]],
+                );
+            let gap = if i == 0 {
+                WIRE_LEAD_IN_GAP
+            } else {
+                WIRE_BLOCK_GAP
+            };
+            self.bytes.resize(self.bytes.len() + gap, 0x00);
+            self.bytes.push(WIRE_START_MARK);
+            self.blocks.push(MediumBlock {
+                payload_start: self.bytes.len(),
+                len: l,
+            });
+            self.bytes.extend_from_slice(payload);
+            self.bytes.extend_from_slice(&crc);
+        }
+        self.bytes.resize(self.bytes.len() + WIRE_BLOCK_GAP, 0x00);
+    }
+
+    /// Replace the recorded bytes wholesale (save-state restore) and re-index.
+    pub(crate) fn restore_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.clear();
+        self.bytes.extend_from_slice(bytes);
+        self.rescan();
+    }
+
+    /// Whether block `idx`'s stored CRC matches its payload.
+    #[cfg(test)]
+    pub(crate) fn block_crc_ok(&self, idx: usize) -> bool {
+        let blk = self.blocks[idx];
+        let end = blk.payload_end();
+        let mut crc = fds_block_crc(WIRE_START_MARK, &self.bytes[blk.payload_start..end]);
+        crc = crc16_update(crc, self.bytes[end]);
+        crc16_update(crc, self.bytes[end + 1]) == 0
+    }
+
+    /// Serialize to the concatenated-block form with `crc_len` stored CRC
+    /// bytes per block (0 = `.fds`, 2 = QD), padded / truncated to `side_len`.
+    fn to_side(&self, crc_len: usize, side_len: usize) -> Vec<u8> {
+        let mut out = Vec::with_capacity(side_len);
+        for blk in &self.blocks {
+            out.extend_from_slice(&self.bytes[blk.payload_start..blk.payload_end() + crc_len]);
+        }
+        out.extend_from_slice(&self.trailer);
+        out.resize(side_len, 0x00);
+        out
+    }
+
+    /// The `.fds`-form side ([`FDS_SIDE_LEN`] bytes): block payloads only.
+    pub(crate) fn to_fds_side(&self) -> Vec<u8> {
+        self.to_side(0, FDS_SIDE_LEN)
+    }
+
+    /// The QD-form side ([`QD_SIDE_LEN`] bytes): each payload followed by its
+    /// stored CRC, so bad CRCs survive the export.
+    pub(crate) fn to_qd_side(&self) -> Vec<u8> {
+        self.to_side(2, QD_SIDE_LEN)
+    }
+
+    /// Walk the recorded surface and verify every block's gap / start-mark
+    /// framing and stored CRC-16. See [`crate::fds::Fds::medium_write_verify`].
+    pub(crate) fn verify(&self) -> Result<(), FdsMediumError> {
+        for (block, blk) in self.blocks.iter().enumerate() {
+            // The `$80` start mark sits one byte before the payload.
+            if blk.payload_start == 0 || self.bytes[blk.payload_start - 1] != WIRE_START_MARK {
+                return Err(FdsMediumError::MissingStartMark {
+                    block,
+                    wire_pos: blk.payload_start.saturating_sub(1),
+                });
+            }
+            let payload_end = blk.payload_end();
+            // Payload + its two CRC bytes must fit inside the recorded surface.
+            if payload_end + 1 >= self.bytes.len() {
+                return Err(FdsMediumError::Truncated { block });
+            }
+            let expected =
+                fds_block_crc(WIRE_START_MARK, &self.bytes[blk.payload_start..payload_end]);
+            let stored = u16::from_le_bytes([self.bytes[payload_end], self.bytes[payload_end + 1]]);
+            if stored != expected {
+                return Err(FdsMediumError::CrcMismatch {
+                    block,
+                    stored,
+                    expected,
+                });
+            }
+            // The gap before this block (from the prior block's CRC end, or the
+            // start of the side) must be all `$00`.
+            let gap_start = if block == 0 {
+                0
+            } else {
+                self.blocks[block - 1].payload_end() + 2
+            };
+            let mark_pos = blk.payload_start - 1;
+            if let Some(i) = self.bytes[gap_start..mark_pos].iter().position(|&b| b != 0) {
+                return Err(FdsMediumError::GapNotZero {
+                    block,
+                    wire_pos: gap_start + i,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// A side with a disk-info block, a file-amount block, and one file
+    /// (header + 4-byte data), followed by a non-zero trailer byte.
+    fn synth_side() -> Vec<u8> {
+        let mut side = vec![0u8; FDS_SIDE_LEN];
+        side[0] = 0x01;
+        side[1..15].copy_from_slice(b"*NINTENDO-HVC*");
+        side[56] = 0x02;
+        side[57] = 1;
+        let hdr = 58;
+        side[hdr] = 0x03;
+        side[hdr + 13] = 4; // file size = 4
+        side[hdr + 16] = 0x04;
+        side[hdr + 17..hdr + 21].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        side[hdr + 30] = 0x5A; // trailer garbage past the structured region
+        side
+    }
+
+    #[test]
+    fn kermit_residue_is_zero_over_block_and_crc() {
+        let payload = [0x01, 0x02, 0x03];
+        let crc = fds_block_crc(WIRE_START_MARK, &payload);
+        let mut r = fds_block_crc(WIRE_START_MARK, &payload);
+        for b in crc.to_le_bytes() {
+            r = crc16_update(r, b);
+        }
+        assert_eq!(r, 0);
+    }
+
+    #[test]
+    fn fds_side_round_trips_losslessly() {
+        let side = synth_side();
+        let medium = FdsSideMedium::from_fds_side(&side);
+        assert_eq!(medium.blocks().len(), 4);
+        assert_eq!(medium.to_fds_side(), side);
+        medium.verify().expect("converted side is well-formed");
+    }
+
+    #[test]
+    fn qd_side_round_trips_and_keeps_bad_crc() {
+        let fds = FdsSideMedium::from_fds_side(&synth_side());
+        let mut qd = fds.to_qd_side();
+        assert_eq!(qd.len(), QD_SIDE_LEN);
+        // Corrupt the file-amount block's stored CRC (copy-protection style).
+        let crc_pos = 56 + 2; // disk-info 56 + its 2 CRC bytes = file-amount start
+        qd[crc_pos + 2] ^= 0xFF;
+        let medium = FdsSideMedium::from_qd_side(&qd);
+        assert_eq!(medium.to_qd_side(), qd, "QD round-trip is byte-exact");
+        assert!(medium.block_crc_ok(0));
+        assert!(!medium.block_crc_ok(1), "the stored bad CRC is preserved");
+        assert_eq!(medium.to_fds_side(), synth_side(), "payloads are intact");
+    }
+
+    #[test]
+    fn rescan_picks_up_an_appended_block() {
+        let mut medium = FdsSideMedium::from_fds_side(&synth_side());
+        let end = medium.len();
+        // Append: gap, mark, a file-amount block, its CRC.
+        let mut pos = end;
+        for &b in &[0x00, 0x00, WIRE_START_MARK, 0x02, 0x07] {
+            assert!(medium.store(pos, b));
+            pos += 1;
+        }
+        let crc = fds_block_crc(WIRE_START_MARK, &[0x02, 0x07]);
+        for b in crc.to_le_bytes() {
+            medium.store(pos, b);
+            pos += 1;
+        }
+        assert_eq!(medium.len(), pos, "the write stopped right after the CRC");
+        medium.rescan();
+        assert_eq!(medium.blocks().len(), 5);
+        assert!(medium.block_crc_ok(4));
+        assert_eq!(medium.len(), pos + WIRE_BLOCK_GAP, "trailing gap rebuilt");
+        assert!(medium.bytes()[pos..].iter().all(|&b| b == 0x00));
+        let side = medium.to_fds_side();
+        assert_eq!(side[58 + 16 + 5..58 + 16 + 7], [0x02, 0x07]);
+    }
+
+    #[test]
+    fn rescan_keeps_a_block_whose_code_was_overwritten() {
+        let mut medium = FdsSideMedium::from_fds_side(&synth_side());
+        let first = medium.blocks()[0].payload_start;
+        medium.store(first, 0x11);
+        medium.rescan();
+        assert_eq!(medium.blocks().len(), 4);
+        assert_eq!(medium.to_fds_side()[0], 0x11);
+    }
+
+    #[test]
+    fn store_stops_at_capacity() {
+        let mut medium = FdsSideMedium::from_fds_side(&[]);
+        assert!(!medium.store(FDS_MEDIUM_CAPACITY, 0xFF));
+        assert!(medium.store(FDS_MEDIUM_CAPACITY - 1, 0xFF));
+        assert_eq!(medium.len(), FDS_MEDIUM_CAPACITY);
+    }
+}
@@ -42,6 +42,9 @@ use alloc::{boxed::Box, string::ToString};
 mod bmc_simple;
 mod cartridge;
 mod fds;
+mod fds_fs;
+mod fds_hle;
+mod fds_medium;
 mod header;
 mod homebrew_boards;
 mod jaleco_discrete;
@@ -139,9 +142,15 @@ mod waixing;
 pub use bmc_simple::{new_m164, new_m261, new_m286, new_m289, new_m320, new_m336, new_m349};
 pub use cartridge::{Cartridge, ConsoleType, Mirroring, Region, RomError, VsPpuPalette, VsPpuType};
 pub use fds::{
-    DISK_BYTE_CYCLES, FDS_SIDE_LEN, Fds, FdsDisk, FdsQuirk, FdsTraceRec, HEAD_RESEEK_CYCLES,
-    fds_crc32, parse_fds, quirk_for_crc,
+    DISK_BYTE_CYCLES, FDS_SIDE_LEN, Fds, FdsDisk, FdsMediumError, FdsQuirk, FdsTraceRec,
+    HEAD_RESEEK_CYCLES, QD_SIDE_LEN, fds_crc32, parse_fds, quirk_for_crc,
 };
+pub use fds_fs::{
+    FDS_DISK_INFO_LEN, FdsDiskInfo, FdsFile, FdsFileKind, FdsFsError, FdsSideFiles,
+    parse_side_files,
+};
+pub use fds_hle::{FDS_HLE_SIGNATURE, FdsHleHost, fds_hle_bios, is_hle_bios};
+pub use fds_medium::FDS_MEDIUM_CAPACITY;
 pub use header::{Header, parse_header, serialize_header};
 pub use homebrew_boards::{Action53M28, Cufrom29, Gtrom111, Inl31, MagicFloor218, Unrom512M30};
 pub use jaleco_discrete::{Jaleco72, Jaleco86, Jaleco92, Jaleco101, Jaleco140};
@@ -585,6 +585,21 @@ pub trait Mapper: Send {
         Vec::new()
     }
 
+    /// Re-serialize the (possibly-modified) disk image to the raw QD layout —
+    /// every block followed by its stored CRC-16 — for a lossless export of the
+    /// physical medium. Returns an empty vector for non-FDS mappers.
+    fn disk_image_qd_bytes(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    /// Swap the inserted disk for the image in `bytes` (`.fds` or QD layout),
+    /// keeping the inserted side when the new image has it. The new image is
+    /// clean (not dirty). Returns `false`, leaving the disk unchanged, for
+    /// non-FDS mappers or bytes that do not parse.
+    fn replace_disk_image(&mut self, _bytes: &[u8]) -> bool {
+        false
+    }
+
     /// Whether the disk image has unsaved writes. Always `false` for non-FDS
     /// mappers.
     fn disk_is_dirty(&self) -> bool {
@@ -599,6 +614,34 @@ pub trait Mapper: Send {
     /// for non-FDS mappers.
     fn set_disk_write_protected(&mut self, _protected: bool) {}
 
+    /// Whether the FDS device runs the high-level-emulated BIOS
+    /// ([`crate::fds_hle_bios`]). Always `false` for non-FDS mappers.
+    fn fds_hle_active(&self) -> bool {
+        false
+    }
+
+    /// Whether an HLE BIOS service call was trapped and waits for
+    /// [`Self::run_fds_hle_service`]. The bus polls this after a write to the
+    /// trap port.
+    fn fds_hle_pending(&self) -> bool {
+        false
+    }
+
+    /// Run the trapped HLE BIOS service against the console memory `host`
+    /// exposes. No-op for non-FDS mappers.
+    fn run_fds_hle_service(&mut self, _host: &mut dyn crate::FdsHleHost) {}
+
+    /// The BIOS error code the HLE boot sequence stopped on, if any.
+    fn fds_hle_boot_error(&self) -> Option<u8> {
+        None
+    }
+
+    /// Documented BIOS entry points the HLE BIOS does not implement that the
+    /// game has called, in first-call order.
+    fn fds_hle_unsupported_calls(&self) -> Vec<u16> {
+        Vec::new()
+    }
+
     // --- Optional NSF music-player interface ---
     //
     // Only the NSF player (`nsf::NsfMapper`) overrides these; every other mapper
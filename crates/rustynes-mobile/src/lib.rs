@@ -426,6 +426,8 @@ impl NpNetConfig {
             stun_servers,
             turn,
             signaling_url: self.signaling_url.clone(),
+            // Room codes are the mobile apps' only gate; their rooms are open.
+            room_password: String::new(),
         }
     }
 }
@@ -2053,6 +2055,7 @@ fn pre_tick_movie(g: &mut Inner) {
     if let Some(fi) = pb {
         g.nes.set_buttons(0, fi.p1);
         g.nes.set_buttons(1, fi.p2);
+        g.nes.set_microphone_pattern(fi.expansion);
     }
     // Stop playback once the movie is exhausted.
     if g.playback
@@ -2203,6 +2206,9 @@ fn np_tick_connecting(g: &mut Inner, mut conn: NetplayConnection, is_host: bool)
             let transport = conn.into_transport();
             let config = SessionConfig {
                 local_player: u8::from(!is_host), // host = 0 (P1), joiner = 1 (P2).
+                // Repair a desync with a state transfer from the host rather
+                // than ending the match.
+                desync_recovery: true,
                 ..SessionConfig::default()
             };
             let rom_hash = *g.nes.rom_sha256();
@@ -2215,6 +2221,12 @@ fn np_tick_connecting(g: &mut Inner, mut conn: NetplayConnection, is_host: bool)
                 Some(DisconnectReason::RomMismatch) => {
                     "peer is running a different ROM".to_string()
                 }
+                Some(DisconnectReason::SettingsMismatch) => {
+                    "peer uses different emulation settings".to_string()
+                }
+                Some(DisconnectReason::SaveMismatch) => {
+                    "peer starts from a different save".to_string()
+                }
                 Some(DisconnectReason::HandshakeTimeout) => {
                     "handshake timed out (no peer answered)".to_string()
                 }
@@ -2241,7 +2253,7 @@ fn np_tick_in_game(
     is_host: bool,
     local_mask: u8,
 ) -> NpTick {
-    session.add_local_input(Buttons::from_bits_truncate(local_mask));
+    let _ = session.add_local_input(Buttons::from_bits_truncate(local_mask));
     match session.advance(&mut g.nes) {
         Ok(AdvanceOutcome {
             produced_frame,
@@ -0,0 +1,495 @@
+//! In-session chat: bounded, rate-limited text with acknowledged delivery.
+//!
+//! A [`NetMessage::Chat`] carries one line of UTF-8 text (at most
+//! [`MAX_CHAT_BYTES`], no control characters) tagged with its sender and a
+//! per-sender sequence number. Every peer that receives one answers with a
+//! [`NetMessage::ChatAck`] naming the sender and that sequence; the sender
+//! resends each line every [`CHAT_RESEND_TICKS`] until every other player has
+//! acknowledged it, giving up after [`CHAT_EXPIRY_TICKS`] (a peer that left
+//! never will). A receiver delivers each sequence once — a resend whose ack
+//! was lost is acknowledged again but not repeated — so lines arrive exactly
+//! once, in arrival order (a line recovered by a resend can land after a
+//! later one).
+//!
+//! Both ends rate-limit with a token bucket of [`CHAT_BURST`] lines refilled
+//! one per [`CHAT_REFILL_TICKS`]: [`Chat::send`] refuses a line over the
+//! budget, and a receiver neither delivers nor acknowledges a sender's line
+//! over twice that budget, so a flooding peer is throttled by its own
+//! resends.
+//!
+//! Like [`crate::recovery`], this module is pure bookkeeping with no transport
+//! access: it returns the messages to send, and the session (`session.rs`)
+//! sends them and calls [`Chat::on_tick`] once per tick. Chat never touches
+//! emulated state, so it has no bearing on determinism.
+
+use std::collections::VecDeque;
+
+use crate::message::NetMessage;
+use crate::session::MAX_PLAYERS;
+
+/// Longest chat line, in UTF-8 bytes.
+pub const MAX_CHAT_BYTES: usize = 200;
+
+/// Lines a player may send back to back before the rate limit applies.
+pub const CHAT_BURST: u32 = 5;
+
+/// Ticks (frames at 60 Hz) to earn back one line of the burst.
+pub const CHAT_REFILL_TICKS: u32 = 60;
+
+/// Ticks between resends of a line some peer has not acknowledged.
+pub const CHAT_RESEND_TICKS: u32 = 15;
+
+/// Ticks after which an unacknowledged line is dropped from the resend queue.
+pub const CHAT_EXPIRY_TICKS: u32 = 600;
+
+/// Most lines waiting for acknowledgement before [`Chat::send`] refuses more.
+pub const MAX_CHAT_BACKLOG: usize = 16;
+
+/// Most received lines held for [`Chat::take_received`]; older ones are
+/// dropped first if the application never drains them.
+pub const MAX_CHAT_INBOX: usize = 64;
+
+/// Width of a receiver's duplicate-detection window, in sequence numbers.
+const SEEN_WINDOW: u32 = 64;
+
+/// Why a chat line was not sent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ChatError {
+    /// The line is empty (or only whitespace).
+    #[error("chat message is empty")]
+    Empty,
+    /// The line is longer than [`MAX_CHAT_BYTES`].
+    #[error("chat message is longer than {MAX_CHAT_BYTES} bytes")]
+    TooLong,
+    /// The line contains a control character (newline, escape, ...).
+    #[error("chat message contains a control character")]
+    ControlCharacter,
+    /// The sender is over the [`CHAT_BURST`] rate limit.
+    #[error("sending chat too fast")]
+    RateLimited,
+    /// [`MAX_CHAT_BACKLOG`] lines are still waiting for acknowledgement.
+    #[error("too many chat messages awaiting delivery")]
+    Backlog,
+}
+
+/// Check that `text` is a sendable chat line: non-blank, at most
+/// [`MAX_CHAT_BYTES`], and free of control characters.
+///
+/// Applied to outgoing lines, to every decoded [`NetMessage::Chat`] and to
+/// the signaling room's lobby chat.
+///
+/// # Errors
+///
+/// [`ChatError::Empty`], [`ChatError::TooLong`] or
+/// [`ChatError::ControlCharacter`].
+pub fn validate(text: &str) -> Result<(), ChatError> {
+    if text.trim().is_empty() {
+        Err(ChatError::Empty)
+    } else if text.len() > MAX_CHAT_BYTES {
+        Err(ChatError::TooLong)
+    } else if text.chars().any(char::is_control) {
+        Err(ChatError::ControlCharacter)
+    } else {
+        Ok(())
+    }
+}
+
+/// One delivered chat line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChatLine {
+    /// The sending player's index.
+    pub from: u8,
+    /// The line itself (already [validated](validate)).
+    pub text: String,
+}
+
+/// The chat rate limit, a token bucket counted in ticks.
+///
+/// Each line costs [`CHAT_REFILL_TICKS`] and the bucket holds [`CHAT_BURST`]
+/// lines' worth. [`Chat`] applies it to the session's lines; a lobby applies it to
+/// the lines it relays through the signaling room.
+#[derive(Clone, Copy, Debug)]
+pub struct ChatRateLimit {
+    /// Ticks of credit left, as of `at`.
+    credit: u32,
+    /// The tick `credit` was last brought up to date.
+    at: u32,
+}
+
+impl Default for ChatRateLimit {
+    fn default() -> Self {
+        Self::full(CHAT_BURST)
+    }
+}
+
+impl ChatRateLimit {
+    /// `true` (spending it) if a line may be sent at tick `now`, a clock
+    /// that advances once per tick.
+    pub fn allow(&mut self, now: u32) -> bool {
+        self.take(now, CHAT_BURST)
+    }
+
+    const fn full(burst: u32) -> Self {
+        Self {
+            credit: burst * CHAT_REFILL_TICKS,
+            at: 0,
+        }
+    }
+
+    /// Spend one line's credit at `now` if the bucket (of `burst` lines)
+    /// has it.
+    fn take(&mut self, now: u32, burst: u32) -> bool {
+        let credit = self
+            .credit
+            .saturating_add(now.wrapping_sub(self.at))
+            .min(burst * CHAT_REFILL_TICKS);
+        self.at = now;
+        let ok = credit >= CHAT_REFILL_TICKS;
+        self.credit = if ok {
+            credit - CHAT_REFILL_TICKS
+        } else {
+            credit
+        };
+        ok
+    }
+}
+
+/// A line of ours still owed an acknowledgement by some peer.
+#[derive(Clone, Debug)]
+struct Outgoing {
+    seq: u32,
+    text: String,
+    /// Bitmask of the players that have not acknowledged it yet.
+    pending: u8,
+    /// The tick it was queued (for [`CHAT_EXPIRY_TICKS`]).
+    queued_at: u32,
+    /// The tick it was last sent.
+    sent_at: u32,
+}
+
+/// Duplicate detection for one sender: the newest sequence seen and a bitmask
+/// of the [`SEEN_WINDOW`] sequences up to it (bit `n` = `newest - n`).
+#[derive(Clone, Copy, Debug, Default)]
+struct Seen {
+    newest: Option<u32>,
+    mask: u64,
+}
+
+impl Seen {
+    /// `true` if `seq` was seen already — or is too old to tell, which after
+    /// [`CHAT_EXPIRY_TICKS`] of resends it must have been.
+    fn contains(&self, seq: u32) -> bool {
+        self.newest.is_some_and(|newest| {
+            seq <= newest && (newest - seq >= SEEN_WINDOW || self.mask & (1 << (newest - seq)) != 0)
+        })
+    }
+
+    /// Record `seq` as seen.
+    const fn insert(&mut self, seq: u32) {
+        match self.newest {
+            Some(newest) if seq <= newest => self.mask |= 1 << (newest - seq),
+            Some(newest) => {
+                let shift = seq - newest;
+                self.mask = if shift >= SEEN_WINDOW {
+                    1
+                } else {
+                    (self.mask << shift) | 1
+                };
+                self.newest = Some(seq);
+            }
+            None => {
+                self.newest = Some(seq);
+                self.mask = 1;
+            }
+        }
+    }
+}
+
+/// One peer's chat state: its own lines awaiting acknowledgement and the
+/// lines it has received.
+#[derive(Clone, Debug)]
+pub struct Chat {
+    /// This peer's player index.
+    local: u8,
+    /// Bitmask of every other player (the peers that must ack a line).
+    peers: u8,
+    /// Ticks counted by [`Self::on_tick`]; the clock for resends and limits.
+    now: u32,
+    next_seq: u32,
+    outgoing: VecDeque<Outgoing>,
+    send_limit: ChatRateLimit,
+    seen: [Seen; MAX_PLAYERS],
+    receive_limit: [ChatRateLimit; MAX_PLAYERS],
+    inbox: VecDeque<ChatLine>,
+}
+
+impl Chat {
+    /// Chat state for `local` in a match of `num_players`.
+    #[must_use]
+    pub fn new(local: u8, num_players: u8) -> Self {
+        let peers = (0..num_players)
+            .take(MAX_PLAYERS)
+            .filter(|&p| p != local)
+            .fold(0u8, |m, p| m | (1 << p));
+        Self {
+            local,
+            peers,
+            now: 0,
+            next_seq: 0,
+            outgoing: VecDeque::new(),
+            send_limit: ChatRateLimit::full(CHAT_BURST),
+            seen: [Seen::default(); MAX_PLAYERS],
+            receive_limit: [ChatRateLimit::full(2 * CHAT_BURST); MAX_PLAYERS],
+            inbox: VecDeque::new(),
+        }
+    }
+
+    /// Queue `text` for every peer and return its first transmission.
+    ///
+    /// # Errors
+    ///
+    /// A [`validate`] error for an unsendable line, [`ChatError::Backlog`]
+    /// while [`MAX_CHAT_BACKLOG`] lines are undelivered, or
+    /// [`ChatError::RateLimited`] over the rate limit.
+    pub fn send(&mut self, text: &str) -> Result<NetMessage, ChatError> {
+        validate(text)?;
+        if self.outgoing.len() >= MAX_CHAT_BACKLOG {
+            return Err(ChatError::Backlog);
+        }
+        if !self.send_limit.take(self.now, CHAT_BURST) {
+            return Err(ChatError::RateLimited);
+        }
+        let seq = self.next_seq;
+        self.next_seq = self.next_seq.wrapping_add(1);
+        self.outgoing.push_back(Outgoing {
+            seq,
+            text: text.to_owned(),
+            pending: self.peers,
+            queued_at: self.now,
+            sent_at: self.now,
+        });
+        Ok(self.message(seq, text))
+    }
+
+    /// Advance the clock one tick: drop expired lines and return the resends
+    /// that are due.
+    pub fn on_tick(&mut self) -> Vec<NetMessage> {
+        self.now = self.now.wrapping_add(1);
+        let now = self.now;
+        self.outgoing
+            .retain(|line| now.wrapping_sub(line.queued_at) < CHAT_EXPIRY_TICKS);
+        let mut resends = Vec::new();
+        for line in &mut self.outgoing {
+            if now.wrapping_sub(line.sent_at) >= CHAT_RESEND_TICKS {
+                line.sent_at = now;
+                resends.push(NetMessage::Chat {
+                    player: self.local,
+                    seq: line.seq,
+                    text: line.text.clone(),
+                });
+            }
+        }
+        resends
+    }
+
+    /// Fold in a [`NetMessage::Chat`] from `player`. Returns the
+    /// [`NetMessage::ChatAck`] to send, or `None` for our own / an
+    /// out-of-range player or a line over the receive rate limit (left
+    /// unacknowledged so the sender retries later).
+    pub fn receive(&mut self, player: u8, seq: u32, text: String) -> Option<NetMessage> {
+        if player == self.local || self.peers & (1u8.checked_shl(u32::from(player))?) == 0 {
+            return None;
+        }
+        let p = usize::from(player);
+        if !self.seen[p].contains(seq) {
+            // A line not seen before: it must fit the sender's budget.
+            if !self.receive_limit[p].take(self.now, 2 * CHAT_BURST) {
+                return None;
+            }
+            self.seen[p].insert(seq);
+            if self.inbox.len() >= MAX_CHAT_INBOX {
+                self.inbox.pop_front();
+            }
+            self.inbox.push_back(ChatLine { from: player, text });
+        }
+        Some(NetMessage::ChatAck {
+            player: self.local,
+            to: player,
+            seq,
+        })
+    }
+
+    /// Fold in a [`NetMessage::ChatAck`]: `player` has our line `seq`.
+    pub fn ack(&mut self, player: u8, to: u8, seq: u32) {
+        if to != self.local || player >= 8 {
+            return;
+        }
+        if let Some(line) = self.outgoing.iter_mut().find(|line| line.seq == seq) {
+            line.pending &= !(1 << player);
+        }
+        self.outgoing.retain(|line| line.pending != 0);
+    }
+
+    /// Forget what was heard from `player`'s slot — a newcomer has taken it
+    /// over and numbers its lines from zero again.
+    pub fn reset_peer(&mut self, player: u8) {
+        if let Some(seen) = self.seen.get_mut(usize::from(player)) {
+            *seen = Seen::default();
+        }
+    }
+
+    /// Hand over the lines received since the last call, oldest first.
+    pub fn take_received(&mut self) -> Vec<ChatLine> {
+        self.inbox.drain(..).collect()
+    }
+
+    /// Lines of ours not yet acknowledged by every peer.
+    #[must_use]
+    pub fn undelivered(&self) -> usize {
+        self.outgoing.len()
+    }
+
+    fn message(&self, seq: u32, text: &str) -> NetMessage {
+        NetMessage::Chat {
+            player: self.local,
+            seq,
+            text: text.to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deliver `msgs` to `to`, returning its acks.
+    fn deliver(to: &mut Chat, msgs: Vec<NetMessage>) -> Vec<NetMessage> {
+        msgs.into_iter()
+            .filter_map(|msg| match msg {
+                NetMessage::Chat { player, seq, text } => to.receive(player, seq, text),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn acks(to: &mut Chat, msgs: Vec<NetMessage>) {
+        for msg in msgs {
+            if let NetMessage::ChatAck {
+                player,
+                to: dst,
+                seq,
+            } = msg
+            {
+                to.ack(player, dst, seq);
+            }
+        }
+    }
+
+    #[test]
+    fn validation_bounds_lines() {
+        assert_eq!(validate("gg"), Ok(()));
+        assert_eq!(validate("héllo ✓"), Ok(()));
+        assert_eq!(validate(""), Err(ChatError::Empty));
+        assert_eq!(validate("   "), Err(ChatError::Empty));
+        assert_eq!(validate("a\nb"), Err(ChatError::ControlCharacter));
+        assert_eq!(validate("\u{1b}[2J"), Err(ChatError::ControlCharacter));
+        assert_eq!(validate(&"x".repeat(MAX_CHAT_BYTES)), Ok(()));
+        assert_eq!(
+            validate(&"x".repeat(MAX_CHAT_BYTES + 1)),
+            Err(ChatError::TooLong)
+        );
+    }
+
+    #[test]
+    fn lost_lines_are_resent_until_acked_and_delivered_once() {
+        let mut a = Chat::new(0, 2);
+        let mut b = Chat::new(1, 2);
+        // The first transmission is lost.
+        let _lost = a.send("hello").unwrap();
+        assert_eq!(a.undelivered(), 1);
+        let mut resent = Vec::new();
+        for _ in 0..CHAT_RESEND_TICKS {
+            resent.extend(a.on_tick());
+        }
+        assert_eq!(resent.len(), 1);
+        // The resend lands, but its ack is lost: the next resend is acked
+        // again without repeating the line.
+        let _lost_ack = deliver(&mut b, resent);
+        let mut again = Vec::new();
+        for _ in 0..CHAT_RESEND_TICKS {
+            again.extend(a.on_tick());
+        }
+        let ack = deliver(&mut b, again);
+        assert_eq!(ack.len(), 1);
+        acks(&mut a, ack);
+        assert_eq!(a.undelivered(), 0);
+        assert_eq!(
+            b.take_received(),
+            vec![ChatLine {
+                from: 0,
+                text: "hello".into()
+            }]
+        );
+        assert!(b.take_received().is_empty());
+        for _ in 0..CHAT_RESEND_TICKS * 2 {
+            assert!(a.on_tick().is_empty());
+        }
+    }
+
+    #[test]
+    fn every_peer_must_ack_and_unacked_lines_expire() {
+        let mut a = Chat::new(0, 3);
+        let msg = a.send("hi all").unwrap();
+        let mut b = Chat::new(1, 3);
+        acks(&mut a, deliver(&mut b, vec![msg]));
+        // Player 2 never answers: the line stays queued, then expires.
+        assert_eq!(a.undelivered(), 1);
+        for _ in 0..CHAT_EXPIRY_TICKS {
+            a.on_tick();
+        }
+        assert_eq!(a.undelivered(), 0);
+        // Acks meant for someone else, or from nobody, change nothing.
+        let msg = a.send("again").unwrap();
+        a.ack(1, 2, 1);
+        a.ack(9, 0, 1);
+        assert_eq!(a.undelivered(), 1);
+        let _ = msg;
+    }
+
+    #[test]
+    fn both_ends_rate_limit() {
+        let mut a = Chat::new(0, 2);
+        for _ in 0..CHAT_BURST {
+            a.send("spam").unwrap();
+        }
+        assert_eq!(a.send("spam"), Err(ChatError::RateLimited));
+        for _ in 0..CHAT_REFILL_TICKS {
+            a.on_tick();
+        }
+        assert!(a.send("one more").is_ok());
+
+        // A peer ignoring the limit is throttled on receipt: the lines over
+        // the budget are neither delivered nor acked.
+        let mut b = Chat::new(1, 2);
+        let acked = (0..3 * CHAT_BURST)
+            .filter_map(|seq| b.receive(0, seq, "flood".into()))
+            .count();
+        assert_eq!(acked, 2 * CHAT_BURST as usize);
+        assert_eq!(b.take_received().len(), 2 * CHAT_BURST as usize);
+    }
+
+    #[test]
+    fn foreign_senders_are_ignored_and_a_new_occupant_starts_fresh() {
+        let mut b = Chat::new(1, 2);
+        assert!(b.receive(1, 0, "me".into()).is_none());
+        assert!(b.receive(3, 0, "nobody".into()).is_none());
+        assert!(b.receive(200, 0, "nobody".into()).is_none());
+        assert!(b.receive(0, 0, "first".into()).is_some());
+        assert!(b.receive(0, 0, "first".into()).is_some());
+        assert_eq!(b.take_received().len(), 1);
+        b.reset_peer(0);
+        assert!(b.receive(0, 0, "newcomer".into()).is_some());
+        assert_eq!(b.take_received()[0].text, "newcomer");
+    }
+}
@@ -16,7 +16,9 @@
 //!   so a flood cannot spin the loop unbounded. The remote may be **unknown**
 //!   at construction (host "listen" mode); until it is learned, `send` is a
 //!   silent no-op and [`poll`](Transport::poll) reports each datagram's source
-//!   so the connection can adopt it.
+//!   so the connection can adopt it. A transport
+//!   [`secured`](UdpTransport::secured) with a [`SecureChannel`] seals every
+//!   datagram and drops any that does not authenticate.
 //! - [`NetplayConnection`] — a small host/join state machine that owns a
 //!   `UdpTransport`, performs the [`NetMessage::Sync`] handshake (both sides
 //!   exchange + confirm a matching magic and identical `rom_hash`), and tracks
@@ -47,12 +49,15 @@ use std::time::{Duration, Instant};
 
 use crate::message::NetMessage;
 use crate::relay::RelayUdpSocket;
+use crate::save_sync::{SaveSync, SaveVerdict, SessionSave};
+use crate::secure::{SecureChannel, decode, encode, hello};
+use crate::settings::{NetSettings, SettingDiff};
 use crate::transport::Transport;
 
 /// Largest datagram we will ever read. The longest [`NetMessage`] encoding is
-/// the 37-byte `Sync` (1 tag + 4 magic + 32 hash); 1500 covers a standard MTU
-/// with generous headroom for any future variant or batched payload, and
-/// bounds the per-datagram receive buffer.
+/// a full `StateChunk` (12-byte header + 1024-byte payload), then a full
+/// `Settings` (3 + 512); 1500 covers a standard MTU with headroom and bounds
+/// the per-datagram receive buffer.
 const RECV_BUF_LEN: usize = 1500;
 
 /// Maximum datagrams drained in a single [`UdpTransport::poll`]. UDP is
@@ -115,6 +120,9 @@ pub struct UdpTransport {
     /// foreign-version) since construction. Exposed for diagnostics; never
     /// affects behaviour.
     dropped_invalid: u64,
+    /// Seals every outgoing datagram and drops every inbound one that does
+    /// not authenticate, once [`secured`](Self::secured).
+    secure: Option<SecureChannel>,
 }
 
 impl UdpTransport {
@@ -142,6 +150,7 @@ impl UdpTransport {
             socket: SocketKind::Direct(socket),
             remote,
             dropped_invalid: 0,
+            secure: None,
         })
     }
 
@@ -162,6 +171,7 @@ impl UdpTransport {
             socket: SocketKind::Relayed(relay),
             remote: Some(peer_relayed),
             dropped_invalid: 0,
+            secure: None,
         })
     }
 
@@ -230,8 +240,28 @@ impl UdpTransport {
         }
     }
 
+    /// Seal all traffic with `channel` (see [`crate::secure`]): every
+    /// datagram sent is encrypted and authenticated, and every datagram
+    /// received that does not open under the channel's key — or replays one
+    /// that already did — is dropped as invalid. The peer must be secured
+    /// with the same key. Works the same over a direct socket and a TURN
+    /// relay. Builder-style.
+    #[must_use]
+    pub fn secured(mut self, channel: SecureChannel) -> Self {
+        self.secure = Some(channel);
+        self
+    }
+
+    /// The channel sealing this transport's traffic, if
+    /// [`secured`](Self::secured).
+    #[must_use]
+    pub const fn secure_channel(&self) -> Option<&SecureChannel> {
+        self.secure.as_ref()
+    }
+
     /// Total datagrams dropped for being malformed / truncated / foreign
-    /// version. Diagnostic only.
+    /// version, or — when [`secured`](Self::secured) — unauthenticated.
+    /// Diagnostic only.
     #[must_use]
     pub const fn dropped_invalid(&self) -> u64 {
         self.dropped_invalid
@@ -277,10 +307,13 @@ impl UdpTransport {
                     // one logical peer); a foreign or malformed packet simply
                     // fails to decode and is dropped below. We do NOT trust the
                     // address to gate parsing.
-                    match NetMessage::from_bytes(&buf[..len]) {
+                    match decode(self.secure.as_mut(), &buf[..len]) {
                         Some(msg) => out.push((msg, from)),
                         None => self.dropped_invalid = self.dropped_invalid.saturating_add(1),
                     }
+                    if let Some(bytes) = hello(self.secure.as_mut()) {
+                        self.send_bytes_to(&bytes, from);
+                    }
                 }
                 RecvStep::Stray => {}
                 RecvStep::Empty => break,
@@ -290,6 +323,24 @@ impl UdpTransport {
     }
 }
 
+impl UdpTransport {
+    /// Send an encoded datagram to `to` over whichever socket this uses.
+    fn send_bytes_to(&mut self, bytes: &[u8], to: SocketAddr) {
+        // A failed send (e.g. transient ICMP port-unreachable surfacing as
+        // ConnectionReset on Windows, or a full socket buffer) is non-fatal:
+        // the rollback protocol tolerates loss, and the next resend covers it.
+        // We deliberately swallow the error rather than propagate or panic.
+        match &mut self.socket {
+            SocketKind::Direct(s) => {
+                let _ = s.send_to(bytes, to);
+            }
+            SocketKind::Relayed(r) => {
+                let _ = r.send_to(bytes, to);
+            }
+        }
+    }
+}
+
 /// The outcome of one datagram read inside [`UdpTransport::poll_with_source`],
 /// uniform across the direct and relay socket sources.
 enum RecvStep {
@@ -309,19 +360,8 @@ impl Transport for UdpTransport {
         let Some(remote) = self.remote else {
             return;
         };
-        let bytes = msg.to_bytes();
-        // A failed send (e.g. transient ICMP port-unreachable surfacing as
-        // ConnectionReset on Windows, or a full socket buffer) is non-fatal:
-        // the rollback protocol tolerates loss, and the next resend covers it.
-        // We deliberately swallow the error rather than propagate or panic.
-        match &mut self.socket {
-            SocketKind::Direct(s) => {
-                let _ = s.send_to(&bytes, remote);
-            }
-            SocketKind::Relayed(r) => {
-                let _ = r.send_to(&bytes, remote);
-            }
-        }
+        let bytes = encode(self.secure.as_mut(), msg);
+        self.send_bytes_to(&bytes, remote);
     }
 
     fn poll(&mut self) -> Vec<NetMessage> {
@@ -353,6 +393,15 @@ pub enum DisconnectReason {
     HandshakeTimeout,
     /// The peer announced a different ROM hash in its `Sync`.
     RomMismatch,
+    /// The peers' emulation settings differ and were not reconciled: a
+    /// joiner that does not adopt the host's settings refuses them, and a
+    /// host whose joiner never adopted them times out with this reason.
+    /// [`NetplayConnection::settings_diff`] lists the differences.
+    SettingsMismatch,
+    /// The peers' battery saves or FDS disks differ and were not reconciled:
+    /// a joiner that does not take the host's save refuses it, and a host
+    /// whose joiner never adopted its save times out with this reason.
+    SaveMismatch,
     /// The peer was synced but then went silent past the disconnect timeout
     /// (no datagram of any kind received for [`NetplayConnection`]'s
     /// `peer_disconnect_timeout`). See [`PeerLink`] for the graded liveness
@@ -360,6 +409,17 @@ pub enum DisconnectReason {
     PeerTimeout,
 }
 
+/// How a [`NetplayConnection`] treats a peer whose settings differ.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SettingsRole {
+    /// A host: its settings are the session's; it only records the diff.
+    Host,
+    /// A joiner that refuses with [`DisconnectReason::SettingsMismatch`].
+    Refuse,
+    /// A joiner that adopts the host's settings.
+    Adopt,
+}
+
 /// The liveness of an already-[`Synced`](ConnectionState::Synced) peer, graded
 /// by how long it has been since the last datagram of any kind arrived.
 ///
@@ -434,10 +494,27 @@ pub struct NetplayConnection {
     /// We have seen the peer's matching `Sync`.
     peer_synced: bool,
 
+    // --- emulation-settings negotiation (see `crate::settings`) ---
+    /// The settings we announce, `None` to announce none (digest `0`).
+    settings: Option<NetSettings>,
+    /// What a mismatching peer's settings make us do.
+    settings_role: SettingsRole,
+    /// `settings` were replaced by the host's.
+    adopted_settings: bool,
+    /// How our settings differ from the peer's, once its encoding arrived.
+    settings_diff: Vec<SettingDiff>,
+
+    // --- battery-save / FDS-disk reconciliation (see `crate::save_sync`) ---
+    /// `None` to announce no save (digest `0`).
+    save: Option<SaveSync>,
+    /// A joiner takes the host's save instead of refusing.
+    adopt_host_save: bool,
+
     // --- timing (host-side, non-deterministic) ---
     started: Instant,
     handshake_timeout: Duration,
-    last_sync_sent: Instant,
+    /// `None` until the opening `Sync` goes out on the first `pump`.
+    last_sync_sent: Option<Instant>,
     sync_resend_interval: Duration,
     last_ping_sent: Instant,
     ping_interval: Duration,
@@ -511,18 +588,17 @@ impl NetplayConnection {
     /// Returns any socket bind / configuration error.
     pub fn host(local: SocketAddr, rom_hash: [u8; 32]) -> io::Result<Self> {
         let transport = UdpTransport::bind_listening(local)?;
-        Ok(Self::with_transport(transport, rom_hash))
+        let mut conn = Self::with_transport(transport, rom_hash);
+        conn.settings_role = SettingsRole::Host;
+        Ok(conn)
     }
 
     /// Build a connection around an existing [`UdpTransport`] (e.g. one bound
-    /// with custom socket options). Sends the opening `Sync` immediately.
+    /// with custom socket options). The opening `Sync` goes out on the first
+    /// [`pump`](Self::pump), so it already carries the settings of
+    /// [`with_settings`](Self::with_settings).
     #[must_use]
-    pub fn with_transport(mut transport: UdpTransport, rom_hash: [u8; 32]) -> Self {
-        // Announce ourselves right away so a peer already listening syncs fast.
-        transport.send(&NetMessage::Sync {
-            magic: NetMessage::SYNC_MAGIC,
-            rom_hash,
-        });
+    pub fn with_transport(transport: UdpTransport, rom_hash: [u8; 32]) -> Self {
         let now = Instant::now();
         Self {
             transport,
@@ -530,9 +606,15 @@ impl NetplayConnection {
             state: ConnectionState::Connecting,
             disconnect_reason: None,
             peer_synced: false,
+            settings: None,
+            settings_role: SettingsRole::Refuse,
+            adopted_settings: false,
+            settings_diff: Vec::new(),
+            save: None,
+            adopt_host_save: false,
             started: now,
             handshake_timeout: Self::DEFAULT_HANDSHAKE_TIMEOUT,
-            last_sync_sent: now,
+            last_sync_sent: None,
             sync_resend_interval: Self::DEFAULT_SYNC_RESEND,
             last_ping_sent: now,
             ping_interval: Self::DEFAULT_PING_INTERVAL,
@@ -545,6 +627,86 @@ impl NetplayConnection {
         }
     }
 
+    /// Seal the connection's traffic — the handshake and everything after
+    /// it — with `channel` (see [`UdpTransport::secured`]). A peer without the
+    /// same key is never heard, so the handshake times out rather than
+    /// completing. Builder-style; call before the first [`pump`](Self::pump).
+    #[must_use]
+    pub fn secured(mut self, channel: SecureChannel) -> Self {
+        self.transport.secure = Some(channel);
+        self
+    }
+
+    /// Announce `settings` in the handshake (see [`crate::settings`]).
+    /// Without this the connection announces none and never refuses a peer
+    /// on settings. Builder-style.
+    #[must_use]
+    pub fn with_settings(mut self, settings: NetSettings) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    /// As a joiner, adopt the host's settings when they differ from ours
+    /// instead of refusing with [`DisconnectReason::SettingsMismatch`]. The
+    /// adopted settings are [`adopted_settings`](Self::adopted_settings); the
+    /// caller applies them for the session's duration. Ignored by a
+    /// [`host`](Self::host). Builder-style.
+    #[must_use]
+    pub const fn adopting_host_settings(mut self, adopt: bool) -> Self {
+        if !matches!(self.settings_role, SettingsRole::Host) {
+            self.settings_role = if adopt {
+                SettingsRole::Adopt
+            } else {
+                SettingsRole::Refuse
+            };
+        }
+        self
+    }
+
+    /// Announce `save` in the handshake (see [`crate::save_sync`]). A host
+    /// streams it to a joiner whose save differs. Without this the connection
+    /// announces none and never refuses a peer on its save. Call after
+    /// [`host`](Self::host). Builder-style.
+    #[must_use]
+    pub fn with_save(mut self, save: SessionSave) -> Self {
+        let host = matches!(self.settings_role, SettingsRole::Host);
+        self.save = Some(SaveSync::new(save, host, self.adopt_host_save));
+        self
+    }
+
+    /// As a joiner, take the host's save when it differs from ours instead
+    /// of refusing with [`DisconnectReason::SaveMismatch`]. The copy is
+    /// [`adopted_save`](Self::adopted_save); the caller loads it for the
+    /// session's duration. Ignored by a [`host`](Self::host). Builder-style.
+    #[must_use]
+    pub const fn adopting_host_save(mut self, adopt: bool) -> Self {
+        self.adopt_host_save = adopt;
+        if let Some(save) = self.save.as_mut() {
+            save.set_adopt(adopt);
+        }
+        self
+    }
+
+    /// The host's save, if this joiner adopted it in the handshake.
+    #[must_use]
+    pub fn adopted_save(&self) -> Option<&SessionSave> {
+        self.save.as_ref().and_then(SaveSync::adopted)
+    }
+
+    /// The host's settings, if this joiner adopted them in the handshake.
+    #[must_use]
+    pub fn adopted_settings(&self) -> Option<&NetSettings> {
+        self.settings.as_ref().filter(|_| self.adopted_settings)
+    }
+
+    /// How our settings differ from the peer's. Empty unless the handshake
+    /// saw a mismatch (and, on a joiner, cleared again once it adopted the
+    /// host's).
+    #[must_use]
+    pub fn settings_diff(&self) -> &[SettingDiff] {
+        &self.settings_diff
+    }
+
     /// Override the handshake timeout (default 10s). Builder-style.
     #[must_use]
     pub const fn with_handshake_timeout(mut self, timeout: Duration) -> Self {
@@ -703,36 +865,33 @@ impl NetplayConnection {
                 self.last_recv = now;
             }
             match msg {
-                NetMessage::Sync { magic, rom_hash } => {
+                NetMessage::Sync {
+                    magic,
+                    rom_hash,
+                    settings,
+                    save,
+                } => {
                     if magic != NetMessage::SYNC_MAGIC {
                         continue; // foreign / corrupt — ignore, never panic.
                     }
-                    if rom_hash != self.rom_hash {
-                        // A mismatched ROM is rejected the same way whether or
-                        // not we have adopted this peer yet.
-                        self.state = ConnectionState::Disconnected;
-                        self.disconnect_reason = Some(DisconnectReason::RomMismatch);
+                    if self.on_peer_sync(&rom_hash, settings, save, from, remote_known) {
                         return self.state;
                     }
-                    // Right magic + our ROM. If we are a listening host with no
-                    // remote yet, adopt THIS source as the peer (only the first
-                    // such packet — `set_remote` is a no-op once bound, so a
-                    // later third party cannot hijack the session).
-                    if !remote_known {
-                        let _ = self.transport.set_remote(from);
+                }
+                NetMessage::Settings { data } => {
+                    if remote_known && self.on_peer_settings(&data) {
+                        return self.state;
+                    }
+                }
+                NetMessage::SaveChunk { index, count, data } => {
+                    if remote_known {
+                        self.on_save_chunk(index, count, data);
                     }
-                    // Peer is running our ROM. Echo a Sync so it learns we are
-                    // here too (the handshake is symmetric: each side needs the
-                    // other's Sync), then mark the peer as synced. The echo now
-                    // has a concrete remote to reach (just adopted, if it was a
-                    // listening host).
-                    if !self.peer_synced {
-                        self.transport.send(&NetMessage::Sync {
-                            magic: NetMessage::SYNC_MAGIC,
-                            rom_hash: self.rom_hash,
-                        });
+                }
+                NetMessage::SaveAck { index } => {
+                    if remote_known && let Some(save) = self.save.as_mut() {
+                        save.on_ack(index);
                     }
-                    self.peer_synced = true;
                 }
                 NetMessage::Quality {
                     ping_ms,
@@ -743,32 +902,30 @@ impl NetplayConnection {
                         // non-Sync traffic — only a valid Sync may bind a peer.
                         continue;
                     }
-                    // A Quality message doubles as the ping pong: the peer
-                    // echoes our most recent measured ping back, and reports
-                    // its own frame advantage. Record the advantage; resolve
-                    // any in-flight ping into an RTT sample.
-                    self.remote_frame_advantage = frame_advantage;
                     let _ = ping_ms;
-                    if let Some(sent) = self.ping_in_flight.take() {
-                        let rtt_ms = now.saturating_duration_since(sent).as_secs_f64() * 1000.0;
-                        // Exponential moving average: prev*(1-a) + sample*a,
-                        // written via mul_add for accuracy.
-                        let smoothed = self.smoothed_ping_ms.map_or(rtt_ms, |prev| {
-                            prev.mul_add(1.0 - Self::PING_SMOOTHING, rtt_ms * Self::PING_SMOOTHING)
-                        });
-                        self.smoothed_ping_ms = Some(smoothed);
-                    }
+                    self.on_quality(frame_advantage, now);
                 }
-                // Input / InputAck / Checksum belong to the session, not the
-                // connection layer. A `Roster` belongs to the N-peer mesh
-                // handshake (`mesh_net`), not this 2-player connection. If pump()
-                // is the sole poller during the handshake these can only be
-                // early/stray and are safely ignored; once a session takes over
-                // polling it sees the session-bound ones.
+                // Input / InputAck / Checksum, the desync-recovery state
+                // transfer, the late-join handshake and input-delay schedules
+                // belong to the session, not the connection layer. A
+                // `Roster` belongs to the N-peer mesh handshake (`mesh_net`),
+                // not this 2-player connection. If pump() is the sole poller
+                // during the handshake these can only be early/stray and are
+                // safely ignored; once a session takes over polling it sees the
+                // session-bound ones.
                 NetMessage::Input { .. }
                 | NetMessage::InputAck { .. }
                 | NetMessage::Checksum { .. }
-                | NetMessage::Roster { .. } => {}
+                | NetMessage::Roster { .. }
+                | NetMessage::StateChunk { .. }
+                | NetMessage::StateAck { .. }
+                | NetMessage::ResyncRequest { .. }
+                | NetMessage::JoinRequest { .. }
+                | NetMessage::JoinAccept { .. }
+                | NetMessage::InputDelay { .. }
+                | NetMessage::Chat { .. }
+                | NetMessage::ChatAck { .. }
+                | NetMessage::Sealed { .. } => {}
             }
         }
 
@@ -792,19 +949,8 @@ impl NetplayConnection {
 
         // 3. While still connecting, re-send Sync periodically and enforce the
         //    handshake timeout.
-        if matches!(self.state, ConnectionState::Connecting) {
-            if now.saturating_duration_since(self.started) >= self.handshake_timeout {
-                self.state = ConnectionState::Disconnected;
-                self.disconnect_reason = Some(DisconnectReason::HandshakeTimeout);
-                return self.state;
-            }
-            if now.saturating_duration_since(self.last_sync_sent) >= self.sync_resend_interval {
-                self.transport.send(&NetMessage::Sync {
-                    magic: NetMessage::SYNC_MAGIC,
-                    rom_hash: self.rom_hash,
-                });
-                self.last_sync_sent = now;
-            }
+        if matches!(self.state, ConnectionState::Connecting) && self.pump_handshake(now) {
+            return self.state;
         }
 
         // 4. Emit a periodic Quality ping (carries our frame advantage + the
@@ -822,11 +968,186 @@ impl NetplayConnection {
 
         self.state
     }
+
+    /// The connecting-phase upkeep: enforce the handshake timeout, stream
+    /// any save transfer, and re-send `Sync`. Returns `true` if the
+    /// handshake timed out.
+    fn pump_handshake(&mut self, now: Instant) -> bool {
+        if now.saturating_duration_since(self.started) >= self.handshake_timeout {
+            self.state = ConnectionState::Disconnected;
+            self.disconnect_reason = Some(if !self.settings_diff.is_empty() {
+                DisconnectReason::SettingsMismatch
+            } else if self.save.as_ref().is_some_and(SaveSync::mismatched) {
+                DisconnectReason::SaveMismatch
+            } else {
+                DisconnectReason::HandshakeTimeout
+            });
+            return true;
+        }
+        if let Some(save) = self.save.as_mut() {
+            for chunk in save.next_chunks() {
+                self.transport.send(&chunk);
+            }
+        }
+        if self
+            .last_sync_sent
+            .is_none_or(|t| now.saturating_duration_since(t) >= self.sync_resend_interval)
+        {
+            self.transport.send(&self.sync_message());
+            self.last_sync_sent = Some(now);
+        }
+        false
+    }
+
+    /// The digest our `Sync`s carry (`0` = no settings announced).
+    fn settings_digest(&self) -> u64 {
+        self.settings.as_ref().map_or(0, NetSettings::digest)
+    }
+
+    fn sync_message(&self) -> NetMessage {
+        NetMessage::Sync {
+            magic: NetMessage::SYNC_MAGIC,
+            rom_hash: self.rom_hash,
+            settings: self.settings_digest(),
+            save: self.save.as_ref().map_or(0, SaveSync::digest),
+        }
+    }
+
+    /// Fold in a `Sync` with the right magic. Returns `true` if it ended the
+    /// connection (a different ROM, or a save this joiner refuses).
+    fn on_peer_sync(
+        &mut self,
+        rom_hash: &[u8; 32],
+        settings: u64,
+        save: u64,
+        from: SocketAddr,
+        remote_known: bool,
+    ) -> bool {
+        if *rom_hash != self.rom_hash {
+            // A mismatched ROM is rejected the same way whether or not we
+            // have adopted this peer yet.
+            self.state = ConnectionState::Disconnected;
+            self.disconnect_reason = Some(DisconnectReason::RomMismatch);
+            return true;
+        }
+        // Right magic + our ROM. If we are a listening host with no remote
+        // yet, adopt THIS source as the peer (only the first such packet —
+        // `set_remote` is a no-op once bound, so a later third party cannot
+        // hijack the session).
+        if !remote_known {
+            let _ = self.transport.set_remote(from);
+        }
+        // Different settings: not synced yet. Send ours so the host can push
+        // its settings or the joiner can diff them.
+        let ours = self.settings_digest();
+        if settings != 0 && ours != 0 && settings != ours {
+            if !self.peer_synced {
+                self.send_settings();
+            }
+            return false;
+        }
+        // Different saves: the host streams its copy (from `pump`) and waits
+        // for the joiner to re-announce it; a joiner waits for the copy or
+        // refuses.
+        if let Some(sync) = self.save.as_mut() {
+            match sync.on_peer_digest(save) {
+                SaveVerdict::Match => {}
+                SaveVerdict::Wait => return false,
+                SaveVerdict::Refuse => {
+                    self.state = ConnectionState::Disconnected;
+                    self.disconnect_reason = Some(DisconnectReason::SaveMismatch);
+                    return true;
+                }
+            }
+        }
+        // Peer is running our ROM. Echo a Sync so it learns we are here too
+        // (the handshake is symmetric: each side needs the other's Sync),
+        // then mark the peer as synced. The echo now has a concrete remote to
+        // reach (just adopted, if it was a listening host).
+        if !self.peer_synced {
+            self.transport.send(&self.sync_message());
+        }
+        self.peer_synced = true;
+        false
+    }
+
+    /// A Quality message doubles as the ping pong: the peer echoes our most
+    /// recent measured ping back, and reports its own frame advantage. Record
+    /// the advantage; resolve any in-flight ping into an RTT sample.
+    fn on_quality(&mut self, frame_advantage: i32, now: Instant) {
+        self.remote_frame_advantage = frame_advantage;
+        if let Some(sent) = self.ping_in_flight.take() {
+            let rtt_ms = now.saturating_duration_since(sent).as_secs_f64() * 1000.0;
+            // Exponential moving average: prev*(1-a) + sample*a, written via
+            // mul_add for accuracy.
+            let smoothed = self.smoothed_ping_ms.map_or(rtt_ms, |prev| {
+                prev.mul_add(1.0 - Self::PING_SMOOTHING, rtt_ms * Self::PING_SMOOTHING)
+            });
+            self.smoothed_ping_ms = Some(smoothed);
+        }
+    }
+
+    /// A joiner taking the host's save acknowledges every chunk (resends of
+    /// an already-adopted copy included, so the host stops) and re-announces
+    /// its `Sync` — now carrying the host's digest — once the copy is taken.
+    fn on_save_chunk(&mut self, index: u16, count: u16, data: Vec<u8>) {
+        if matches!(self.settings_role, SettingsRole::Host) || self.peer_synced {
+            return;
+        }
+        let Some(save) = self.save.as_mut().filter(|save| save.receiving()) else {
+            return;
+        };
+        let adopted = save.on_chunk(index, count, data);
+        self.transport.send(&NetMessage::SaveAck { index });
+        if adopted {
+            self.transport.send(&self.sync_message());
+        }
+    }
+
+    fn send_settings(&mut self) {
+        if let Some(settings) = &self.settings {
+            self.transport.send(&NetMessage::Settings {
+                data: settings.to_bytes(),
+            });
+        }
+    }
+
+    /// Fold in the peer's full settings. A joiner adopts the host's (and
+    /// re-announces at once) or refuses them; a host only records the
+    /// difference. Returns `true` if this ended the connection.
+    fn on_peer_settings(&mut self, data: &[u8]) -> bool {
+        let Some(ours) = self.settings.as_ref() else {
+            return false;
+        };
+        let Some(theirs) = NetSettings::from_bytes(data) else {
+            return false;
+        };
+        if self.peer_synced || theirs == *ours {
+            return false;
+        }
+        self.settings_diff = ours.diff(&theirs);
+        match self.settings_role {
+            SettingsRole::Host => false,
+            SettingsRole::Adopt => {
+                self.settings = Some(theirs);
+                self.adopted_settings = true;
+                self.settings_diff.clear();
+                self.transport.send(&self.sync_message());
+                false
+            }
+            SettingsRole::Refuse => {
+                self.state = ConnectionState::Disconnected;
+                self.disconnect_reason = Some(DisconnectReason::SettingsMismatch);
+                true
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rustynes_core::DeviceInput;
     use std::net::Ipv4Addr;
 
     /// Bind a loopback `UdpTransport` to an ephemeral port; fill in the remote
@@ -858,6 +1179,7 @@ mod tests {
                 player: 1,
                 frame: 0x0102_0304,
                 input: 0x5A,
+                device: DeviceInput::None,
             },
             NetMessage::InputAck { frame: 77 },
             NetMessage::Checksum {
@@ -1068,6 +1390,131 @@ mod tests {
         assert!(!a.is_synced() && !b.is_synced());
     }
 
+    /// A listening host plus a joiner dialing it, both announcing settings.
+    fn settings_pair(
+        host_settings: NetSettings,
+        join_settings: NetSettings,
+        adopt: bool,
+    ) -> (NetplayConnection, NetplayConnection) {
+        let hash = [0x55u8; 32];
+        let host = NetplayConnection::host(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)), hash)
+            .unwrap()
+            .with_settings(host_settings)
+            .with_handshake_timeout(Duration::from_millis(300));
+        let host_addr = host.transport().local_addr().unwrap();
+        let join = NetplayConnection::with_transport(bind_loopback(host_addr), hash)
+            .with_settings(join_settings)
+            .adopting_host_settings(adopt);
+        (host, join)
+    }
+
+    #[test]
+    fn joiner_adopts_host_settings() {
+        let host_settings = NetSettings::default().with_sprite_limit_disabled(true);
+        let (mut host, mut join) =
+            settings_pair(host_settings.clone(), NetSettings::default(), true);
+        let mut rounds = 0;
+        while !(host.is_synced() && join.is_synced()) && rounds < 200 {
+            host.pump(0);
+            join.pump(0);
+            rounds += 1;
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        assert!(host.is_synced() && join.is_synced(), "settings reconciled");
+        assert_eq!(join.adopted_settings(), Some(&host_settings));
+        assert!(host.adopted_settings().is_none(), "the host never adopts");
+        assert!(join.settings_diff().is_empty());
+    }
+
+    #[test]
+    fn refusing_joiner_reports_settings_diff() {
+        let host_settings = NetSettings::default().with_sprite_limit_disabled(true);
+        let (mut host, mut join) = settings_pair(host_settings, NetSettings::default(), false);
+        let mut rounds = 0;
+        while !matches!(join.state(), ConnectionState::Disconnected) && rounds < 200 {
+            host.pump(0);
+            join.pump(0);
+            rounds += 1;
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        assert_eq!(
+            join.disconnect_reason(),
+            Some(DisconnectReason::SettingsMismatch)
+        );
+        let names: Vec<_> = join.settings_diff().iter().map(|d| d.name).collect();
+        assert_eq!(names, ["sprite limit removal"]);
+        assert!(
+            !host.is_synced(),
+            "the host never syncs with a refusing joiner"
+        );
+    }
+
+    fn save_pair(
+        host_save: SessionSave,
+        join_save: SessionSave,
+        adopt: bool,
+    ) -> (NetplayConnection, NetplayConnection) {
+        let hash = [0x66u8; 32];
+        let host = NetplayConnection::host(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)), hash)
+            .unwrap()
+            .with_save(host_save)
+            .with_handshake_timeout(Duration::from_millis(300));
+        let host_addr = host.transport().local_addr().unwrap();
+        let join = NetplayConnection::with_transport(bind_loopback(host_addr), hash)
+            .adopting_host_save(adopt)
+            .with_save(join_save);
+        (host, join)
+    }
+
+    #[test]
+    fn joiner_adopts_host_save_over_several_chunks() {
+        // Incompressible enough to need several chunks.
+        let sram: Vec<u8> = (0..8192u32)
+            .map(|i| i.wrapping_mul(2_654_435_761).to_le_bytes()[2])
+            .collect();
+        let host_save = SessionSave::new(sram, Vec::new());
+        let (mut host, mut join) = save_pair(
+            host_save.clone(),
+            SessionSave::new(vec![0; 8192], Vec::new()),
+            true,
+        );
+        let mut rounds = 0;
+        while !(host.is_synced() && join.is_synced()) && rounds < 200 {
+            host.pump(0);
+            join.pump(0);
+            rounds += 1;
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        assert!(host.is_synced() && join.is_synced(), "saves reconciled");
+        assert_eq!(join.adopted_save(), Some(&host_save));
+        assert!(host.adopted_save().is_none(), "the host keeps its own save");
+    }
+
+    #[test]
+    fn refusing_joiner_reports_save_mismatch() {
+        let (mut host, mut join) = save_pair(
+            SessionSave::new(vec![1; 64], Vec::new()),
+            SessionSave::new(vec![2; 64], Vec::new()),
+            false,
+        );
+        let mut rounds = 0;
+        while !matches!(host.state(), ConnectionState::Disconnected) && rounds < 400 {
+            host.pump(0);
+            join.pump(0);
+            rounds += 1;
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        assert_eq!(
+            join.disconnect_reason(),
+            Some(DisconnectReason::SaveMismatch)
+        );
+        assert_eq!(
+            host.disconnect_reason(),
+            Some(DisconnectReason::SaveMismatch)
+        );
+        assert!(join.adopted_save().is_none());
+    }
+
     #[test]
     fn handshake_times_out_with_no_peer() {
         // Point at a dead port; no peer will ever answer.
@@ -0,0 +1,349 @@
+//! Adaptive input delay.
+//!
+//! [`SessionConfig::input_delay`](crate::SessionConfig::input_delay) trades
+//! input latency for rollbacks: the longer a local input is buffered before
+//! the frame it applies to, the more often the remote inputs have arrived by
+//! the time that frame runs. The right value depends on the link, which can
+//! change mid-match. A [`DelayTuner`] watches two signals the session already
+//! has and recommends a delay:
+//!
+//! - **Round trip.** Every local input is stamped with the session tick it was
+//!   authored on; the peer's cumulative `InputAck` for it closes a sample.
+//!   Ticks, not wall-clock time, so the session stays free of `std::time`. A
+//!   delay of half the round trip (the one-way trip, rounded up) lets the
+//!   input arrive just as its frame is due.
+//! - **Rollbacks.** The frames re-simulated per tick to correct a
+//!   misprediction (not the replays that carry the checkpoint over newly
+//!   confirmed frames). A window busy with rollbacks (jitter the mean round
+//!   trip hides) asks for one frame more; a lower delay is only proposed
+//!   after a quiet window.
+//!
+//! The tuner evaluates once per [`DelayTuning::window_ticks`]. Its proposal
+//! is bounded by [`DelayTuning::min_delay`] / [`DelayTuning::max_delay`] and
+//! has a one-frame dead band: it raises as soon as the target is above the
+//! current delay, but lowers only when the target is two or more frames
+//! below, so a round trip hovering on a boundary does not flip the delay back
+//! and forth.
+//!
+//! # Agreement
+//!
+//! The host (player 0) owns the delay. With [`DelayMode::Auto`] it schedules
+//! the tuner's proposal itself; with [`DelayMode::Propose`] the proposal is
+//! only surfaced, and the application applies it with
+//! [`RollbackSession::schedule_input_delay`](crate::RollbackSession::schedule_input_delay).
+//! A schedule names a frame [`DELAY_LEAD_FRAMES`] past the host's current
+//! one and is broadcast as [`NetMessage::InputDelay`] every tick
+//! until that frame is reached, so every peer switches on the same frame and
+//! every player's input keeps the same latency.
+//!
+//! The delay only decides *which frame* a local input is authored for, never
+//! what a frame's confirmed input is, so the switch cannot desync anyone. A
+//! longer delay leaves a gap of frames the new target skips over; they are
+//! authored with the last input held. A shorter one would aim at frames that
+//! were already authored and sent; those inputs are dropped rather than
+//! overwriting what the peers may have confirmed. A peer that misses a
+//! schedule entirely keeps its old delay and pays for it in latency or
+//! rollbacks only. Each change is logged as a [`DelayChange`].
+//!
+//! [`NetMessage::InputDelay`]: crate::message::NetMessage::InputDelay
+
+use std::collections::VecDeque;
+
+/// The largest input delay a session accepts, in frames (a quarter second at
+/// 60 fps). A [`NetMessage::InputDelay`](crate::NetMessage::InputDelay)
+/// naming more is malformed.
+pub const MAX_INPUT_DELAY: u32 = 15;
+
+/// How far past the host's current frame a scheduled delay change lands:
+/// half a second of resends for the schedule to reach every peer.
+pub const DELAY_LEAD_FRAMES: u32 = 30;
+
+/// How many authored-but-unacknowledged inputs the tuner keeps stamps for.
+/// A long outage beyond this only loses round-trip samples.
+const MAX_IN_FLIGHT: usize = 256;
+
+/// Re-simulating more than one frame per this many ticks makes a window
+/// busy: the delay is too short for the link's jitter.
+const BUSY_TICKS_PER_RESIM: u32 = 4;
+
+/// A window re-simulating at most one frame per this many ticks is quiet
+/// enough to try a shorter delay.
+const QUIET_TICKS_PER_RESIM: u32 = 16;
+
+/// Whether (and how) a session adapts its input delay.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DelayMode {
+    /// Keep [`SessionConfig::input_delay`](crate::SessionConfig::input_delay)
+    /// unless the application schedules a change. The tuner still measures.
+    #[default]
+    Fixed,
+    /// Measure and surface a proposal
+    /// ([`RollbackSession::proposed_input_delay`](crate::RollbackSession::proposed_input_delay)),
+    /// but leave applying it to the application.
+    Propose,
+    /// The host schedules every proposal itself.
+    Auto,
+}
+
+/// Configuration for adaptive input delay, carried in
+/// [`SessionConfig::delay_tuning`](crate::SessionConfig::delay_tuning).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DelayTuning {
+    /// Whether proposals are applied, surfaced, or neither. Defaults to
+    /// [`DelayMode::Fixed`].
+    pub mode: DelayMode,
+    /// The shortest delay proposed, in frames. Defaults to `1`.
+    pub min_delay: u32,
+    /// The longest delay proposed, in frames, capped at [`MAX_INPUT_DELAY`].
+    /// Defaults to `8`.
+    pub max_delay: u32,
+    /// How many ticks of measurements each evaluation covers. Defaults to
+    /// `120` (two seconds at 60 fps).
+    pub window_ticks: u32,
+}
+
+impl Default for DelayTuning {
+    fn default() -> Self {
+        Self {
+            mode: DelayMode::Fixed,
+            min_delay: 1,
+            max_delay: 8,
+            window_ticks: 120,
+        }
+    }
+}
+
+/// One input-delay change a session applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DelayChange {
+    /// The frame the new delay took effect on.
+    pub frame: u32,
+    /// The delay before the change.
+    pub from: u32,
+    /// The delay after it.
+    pub to: u32,
+}
+
+/// Measures round trips and rollbacks and recommends an input delay. See the
+/// module docs.
+#[derive(Clone, Debug)]
+pub struct DelayTuner {
+    tuning: DelayTuning,
+    /// Ticks counted so far (one per [`Self::on_tick`]).
+    tick: u32,
+    /// `(frame, tick)` stamps of authored local inputs not yet acknowledged,
+    /// in frame order.
+    in_flight: VecDeque<(u32, u32)>,
+    /// The tick the current window opened on.
+    window_start: u32,
+    /// Round-trip samples (ticks) in the current window.
+    rtt_sum: u64,
+    rtt_samples: u32,
+    /// Frames re-simulated in the current window.
+    resimulated: u32,
+    /// The last closed window's mean round trip, rounded up.
+    last_rtt: Option<u32>,
+    /// The last closed window's re-simulated frames.
+    last_resimulated: u32,
+    /// The delay recommended by the last closed window.
+    proposal: Option<u32>,
+}
+
+impl DelayTuner {
+    /// A tuner with no measurements yet.
+    #[must_use]
+    pub const fn new(tuning: DelayTuning) -> Self {
+        Self {
+            tuning,
+            tick: 0,
+            in_flight: VecDeque::new(),
+            window_start: 0,
+            rtt_sum: 0,
+            rtt_samples: 0,
+            resimulated: 0,
+            last_rtt: None,
+            last_resimulated: 0,
+            proposal: None,
+        }
+    }
+
+    /// The tuning this tuner runs with.
+    #[must_use]
+    pub const fn tuning(&self) -> &DelayTuning {
+        &self.tuning
+    }
+
+    /// Stamp the local input for `frame` as authored now. Re-authoring a
+    /// frame keeps its first stamp.
+    pub fn on_authored(&mut self, frame: u32) {
+        if self.in_flight.back().is_some_and(|&(f, _)| f >= frame) {
+            return;
+        }
+        if self.in_flight.len() == MAX_IN_FLIGHT {
+            self.in_flight.pop_front();
+        }
+        self.in_flight.push_back((frame, self.tick));
+    }
+
+    /// A peer acknowledged every local input up to `frame`: the newest such
+    /// stamp closes a round-trip sample.
+    pub fn on_ack(&mut self, frame: u32) {
+        let mut newest = None;
+        while let Some(&(f, tick)) = self.in_flight.front() {
+            if f > frame {
+                break;
+            }
+            newest = Some(tick);
+            self.in_flight.pop_front();
+        }
+        if let Some(sent) = newest {
+            self.rtt_sum += u64::from(self.tick - sent);
+            self.rtt_samples += 1;
+        }
+    }
+
+    /// Count one session tick that re-simulated `resimulated` frames to
+    /// correct a misprediction. When this closes a window, returns the delay
+    /// it recommends for a session now running `current` (which may be
+    /// `current` itself).
+    pub fn on_tick(&mut self, resimulated: u32, current: u32) -> Option<u32> {
+        self.tick += 1;
+        self.resimulated = self.resimulated.saturating_add(resimulated);
+        let window = self.tuning.window_ticks.max(1);
+        if self.tick - self.window_start < window {
+            return None;
+        }
+        self.last_rtt = (self.rtt_samples > 0)
+            .then(|| u32::try_from(self.rtt_sum.div_ceil(u64::from(self.rtt_samples))).ok())
+            .flatten();
+        self.last_resimulated = self.resimulated;
+        self.window_start = self.tick;
+        self.rtt_sum = 0;
+        self.rtt_samples = 0;
+        self.resimulated = 0;
+        self.proposal = Some(self.recommend(current, window));
+        self.proposal
+    }
+
+    /// The delay the last closed window recommends, if one has closed.
+    #[must_use]
+    pub const fn proposal(&self) -> Option<u32> {
+        self.proposal
+    }
+
+    /// The last closed window's mean round trip in ticks (≈ frames), if it
+    /// saw any acknowledgement.
+    #[must_use]
+    pub const fn round_trip_ticks(&self) -> Option<u32> {
+        self.last_rtt
+    }
+
+    /// The frames re-simulated to correct mispredictions during the last
+    /// closed window.
+    #[must_use]
+    pub const fn resimulated_frames(&self) -> u32 {
+        self.last_resimulated
+    }
+
+    /// The bounded, hysteretic recommendation for the window just closed.
+    fn recommend(&self, current: u32, window: u32) -> u32 {
+        let max = self.tuning.max_delay.min(MAX_INPUT_DELAY);
+        let min = self.tuning.min_delay.min(max);
+        let busy = self.last_resimulated.saturating_mul(BUSY_TICKS_PER_RESIM) > window;
+        let quiet = self.last_resimulated.saturating_mul(QUIET_TICKS_PER_RESIM) <= window;
+        let mut target = self.last_rtt.map_or(current, |rtt| rtt.div_ceil(2));
+        if busy {
+            target = target.max(current + 1);
+        }
+        let target = target.clamp(min, max);
+        let next = if target > current || (quiet && target + 1 < current) {
+            target
+        } else {
+            current
+        };
+        next.clamp(min, max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tuner(window: u32) -> DelayTuner {
+        DelayTuner::new(DelayTuning {
+            mode: DelayMode::Auto,
+            window_ticks: window,
+            ..DelayTuning::default()
+        })
+    }
+
+    /// Drive `ticks` ticks where each authored frame is acknowledged `rtt`
+    /// ticks later, re-simulating `resim` frames per tick; returns the last
+    /// recommendation.
+    fn drive(t: &mut DelayTuner, ticks: u32, rtt: u32, resim: u32, current: u32) -> Option<u32> {
+        let mut out = None;
+        for _ in 0..ticks {
+            let frame = t.tick;
+            t.on_authored(frame);
+            if let Some(acked) = frame.checked_sub(rtt) {
+                t.on_ack(acked);
+            }
+            if let Some(p) = t.on_tick(resim, current) {
+                out = Some(p);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn round_trip_is_measured_in_ticks() {
+        let mut t = tuner(60);
+        assert_eq!(drive(&mut t, 60, 6, 0, 2), Some(3));
+        assert_eq!(t.round_trip_ticks(), Some(6));
+        assert_eq!(t.resimulated_frames(), 0);
+        // Re-stamping an in-flight frame keeps the first stamp.
+        t.on_authored(0);
+        assert_eq!(t.in_flight.back().map(|&(f, _)| f), Some(59));
+    }
+
+    #[test]
+    fn raises_at_once_but_lowers_with_a_dead_band() {
+        let mut t = tuner(60);
+        // One-way 4 frames: up from 1 straight away.
+        assert_eq!(drive(&mut t, 60, 8, 0, 1), Some(4));
+        // One-way 3: within the dead band, hold 4.
+        assert_eq!(drive(&mut t, 60, 6, 0, 4), Some(4));
+        // One-way 1 on a quiet link: down.
+        assert_eq!(drive(&mut t, 60, 2, 0, 4), Some(1));
+        // The same link busy with rollbacks asks for a frame more instead.
+        assert_eq!(drive(&mut t, 60, 2, 1, 4), Some(5));
+    }
+
+    #[test]
+    fn busy_rollbacks_ask_for_one_more_frame() {
+        let mut t = tuner(60);
+        assert_eq!(drive(&mut t, 60, 4, 1, 2), Some(3));
+        assert_eq!(t.resimulated_frames(), 60);
+    }
+
+    #[test]
+    fn proposals_stay_within_bounds() {
+        let mut t = DelayTuner::new(DelayTuning {
+            mode: DelayMode::Propose,
+            min_delay: 2,
+            max_delay: 40,
+            window_ticks: 120,
+        });
+        assert_eq!(drive(&mut t, 120, 60, 0, 4), Some(MAX_INPUT_DELAY));
+        assert_eq!(drive(&mut t, 120, 0, 0, 9), Some(2));
+        // No acknowledgement at all: hold, within bounds.
+        let mut silent = tuner(30);
+        for _ in 0..30 {
+            let frame = silent.tick;
+            silent.on_authored(frame);
+            silent.on_tick(0, 12);
+        }
+        assert_eq!(silent.proposal(), Some(8));
+        assert_eq!(silent.round_trip_ticks(), None);
+    }
+}
@@ -0,0 +1,128 @@
+//! Expansion-device input over netplay.
+//!
+//! A player's frame input is their controller byte plus, optionally, the
+//! state of the expansion device on their port: the Zapper aim and trigger,
+//! the Vaus paddle, the SNES mouse, a Power Pad / Family Trainer mat or a
+//! keyboard matrix. It travels as a [`DeviceInput`] inside
+//! [`NetMessage::Input`](crate::NetMessage::Input) and is applied, like the
+//! buttons, every time the frame runs — so a rollback replays it exactly.
+//! Which *kind* of device each port has is already agreed by the handshake
+//! (see [`NetSettings`](crate::NetSettings)); only its per-frame state rides
+//! with the inputs.
+//!
+//! # Ports
+//!
+//! A device drives its owner's controller port: player 0 port 0, player 1
+//! port 1. Only a two-player session without the Four Score carries devices;
+//! with more players every port is a multiplexed pad, so the Four Score path
+//! is unchanged. [`for_player`] enforces the rule on both the authoring and
+//! the receiving side, so a malformed or foreign device can never drive
+//! another player's port.
+//!
+//! # Prediction
+//!
+//! Buttons are predicted by repeating the last known value. Analog inputs
+//! need a little more care ([`predict`]):
+//!
+//! - **Zapper, Vaus** — absolute positions: hold the last aim / knob position
+//!   and the trigger / fire button. A held position is almost always right
+//!   for the next few frames, and a wrong guess costs one short rollback.
+//! - **SNES mouse** — relative: repeating a movement would keep the cursor
+//!   drifting, so predict zero deltas and hold the buttons and sensitivity.
+//! - **Mats and keyboards** — button arrays: repeat, like a pad.
+
+use rustynes_core::DeviceInput;
+
+/// The device `player` may send (or be credited with) in a session of
+/// `num_players`: `device` itself when it drives the player's own port in a
+/// two-player session, [`DeviceInput::None`] otherwise.
+#[must_use]
+pub fn for_player(device: DeviceInput, player: u8, num_players: u8) -> DeviceInput {
+    if num_players == 2 && player < 2 && device.port() == Some(player) {
+        device
+    } else {
+        DeviceInput::None
+    }
+}
+
+/// The prediction for a frame whose real device state has not arrived yet,
+/// given the player's last known `device` state.
+#[must_use]
+pub const fn predict(device: DeviceInput) -> DeviceInput {
+    match device {
+        DeviceInput::Mouse {
+            port,
+            left,
+            right,
+            sensitivity,
+            ..
+        } => DeviceInput::Mouse {
+            port,
+            dx: 0,
+            dy: 0,
+            left,
+            right,
+            sensitivity,
+        },
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ZAPPER_P2: DeviceInput = DeviceInput::Zapper {
+        port: 1,
+        x: 120,
+        y: 96,
+        trigger: true,
+    };
+
+    #[test]
+    fn device_rides_only_its_owners_port_in_two_player_sessions() {
+        assert_eq!(for_player(ZAPPER_P2, 1, 2), ZAPPER_P2);
+        // Player 0 cannot drive port 1.
+        assert_eq!(for_player(ZAPPER_P2, 0, 2), DeviceInput::None);
+        // The Four Score path carries no devices.
+        assert_eq!(for_player(ZAPPER_P2, 1, 3), DeviceInput::None);
+        assert_eq!(for_player(ZAPPER_P2, 1, 4), DeviceInput::None);
+        let mat = DeviceInput::PowerPad {
+            port: 0,
+            buttons: 0x0003,
+        };
+        assert_eq!(for_player(mat, 0, 2), mat);
+        assert_eq!(for_player(DeviceInput::None, 0, 2), DeviceInput::None);
+    }
+
+    #[test]
+    fn absolute_devices_hold_and_the_mouse_stops() {
+        assert_eq!(predict(ZAPPER_P2), ZAPPER_P2);
+        let paddle = DeviceInput::Paddle {
+            port: 0,
+            position: 0x80,
+            fire: true,
+        };
+        assert_eq!(predict(paddle), paddle);
+        let mouse = DeviceInput::Mouse {
+            port: 1,
+            dx: 12,
+            dy: -7,
+            left: true,
+            right: false,
+            sensitivity: 2,
+        };
+        assert_eq!(
+            predict(mouse),
+            DeviceInput::Mouse {
+                port: 1,
+                dx: 0,
+                dy: 0,
+                left: true,
+                right: false,
+                sensitivity: 2,
+            }
+        );
+        assert_eq!(predict(DeviceInput::None), DeviceInput::None);
+    }
+}
@@ -17,6 +17,12 @@
 //! algorithm, the checksum exchange, or the emulator. So it cannot change
 //! correctness or perturb the determinism contract — disabling it would leave
 //! every produced frame, checksum, and rollback byte-identical.
+//!
+//! The one exception is opt-in: with
+//! [`SessionConfig::desync_recovery`](crate::session::SessionConfig::desync_recovery)
+//! the session consults the consecutive-mismatch run to decide when a
+//! divergence is confirmed and an authoritative state transfer is due, and
+//! reports each completed recovery back via [`DesyncDiagnostics::record_recovery`].
 
 use std::collections::VecDeque;
 
@@ -46,6 +52,7 @@ pub enum DesyncStatus {
     /// unrecoverable for a rollback session (the peers can never re-converge
     /// without a full state resync), so the surface never silently downgrades a
     /// confirmed [`Desynced`](Self::Desynced) back to [`Suspect`](Self::Suspect).
+    /// Only that full resync — a completed desync recovery — clears it.
     Desynced {
         /// The earliest frame that diverged.
         first_desync_frame: u32,
@@ -107,6 +114,10 @@ pub struct DesyncDiagnostics {
     desync_threshold: u32,
     /// The most recent comparison, for the "local vs remote CRC" readout.
     last: Option<CrcCompare>,
+    /// Completed desync recoveries (authoritative state transfers).
+    recoveries: u64,
+    /// The frame the most recent recovery restored, if any.
+    last_recovery_frame: Option<u32>,
 }
 
 impl Default for DesyncDiagnostics {
@@ -152,6 +163,8 @@ impl DesyncDiagnostics {
             peak_consecutive: 0,
             desync_threshold: desync_threshold.max(1),
             last: None,
+            recoveries: 0,
+            last_recovery_frame: None,
         }
     }
 
@@ -188,6 +201,29 @@ impl DesyncDiagnostics {
         self.last = Some(entry);
     }
 
+    /// Record a completed desync recovery that resynchronized the peers at
+    /// `frame`. Clears the live and peak mismatch runs — the only way out of a
+    /// sticky [`DesyncStatus::Desynced`] — while the historical counters and
+    /// the first-desync frame are kept.
+    pub const fn record_recovery(&mut self, frame: u32) {
+        self.recoveries += 1;
+        self.last_recovery_frame = Some(frame);
+        self.consecutive_mismatches = 0;
+        self.peak_consecutive = 0;
+    }
+
+    /// Completed desync recoveries across the whole session.
+    #[must_use]
+    pub const fn recoveries(&self) -> u64 {
+        self.recoveries
+    }
+
+    /// The frame the most recent desync recovery restored, if any.
+    #[must_use]
+    pub const fn last_recovery_frame(&self) -> Option<u32> {
+        self.last_recovery_frame
+    }
+
     /// `true` if no mismatch has ever been recorded.
     #[must_use]
     pub const fn in_sync(&self) -> bool {
@@ -440,6 +476,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn recovery_clears_a_confirmed_desync() {
+        let mut d = DesyncDiagnostics::new();
+        for f in [30, 60, 90] {
+            d.record(f, 1, 2, 0, 0);
+        }
+        assert!(d.is_desynced());
+        d.record_recovery(91);
+        assert_eq!(d.recoveries(), 1);
+        assert_eq!(d.last_recovery_frame(), Some(91));
+        assert_eq!(
+            d.status(),
+            DesyncStatus::Suspect {
+                consecutive: 0,
+                first_desync_frame: 30
+            },
+            "the run resets; the first divergence stays on record"
+        );
+        d.record(120, 5, 5, 0, 0);
+        assert_eq!(d.mismatches(), 3);
+    }
+
     #[test]
     fn threshold_zero_is_treated_as_one() {
         let mut d = DesyncDiagnostics::with_threshold(0);
@@ -14,7 +14,8 @@
 //!   through. Stage 2 plugs a UDP implementation in here without touching
 //!   the session.
 //! - [`MemoryTransport`] — a deterministic in-memory paired transport with
-//!   configurable latency / jitter / drop (seeded PRNG only), for tests.
+//!   configurable latency / jitter / drop / reordering / outages (seeded PRNG
+//!   only), for tests and the [`sim`] soak driver.
 //! - [`NetMessage`] — the versioned wire protocol (`Input`, `InputAck`,
 //!   `Sync`, `Checksum`, `Quality`), with a hand-rolled byte encoding ready
 //!   for the UDP layer.
@@ -97,10 +98,32 @@
 
 // The portable session core: transport-agnostic, no `std::net`, compiles on
 // `wasm32-unknown-unknown` (the v2.5.0 Phase C wasm-compile gate).
+// In-session chat: acknowledged, rate-limited lines over the lossy transport.
+pub mod chat;
+// Adaptive input delay: round-trip / rollback measurement and the proposals
+// a host schedules for every peer.
+pub mod delay;
+// Expansion-device input (Zapper, Vaus, mouse, mats) riding with the buttons:
+// the per-port rule and the analog prediction rules.
+pub mod device;
 pub mod diagnostics;
+// Recording a match's confirmed stream as an attested `.rnm` movie.
+pub mod match_record;
 pub mod message;
+// Desync recovery's chunked state-transfer bookkeeping (portable, std-free
+// apart from `alloc`): the host's outgoing snapshot + a peer's reassembly.
+pub mod recovery;
 pub mod rng;
+// Battery-RAM / FDS-disk reconciliation: the host's save streamed to a joiner
+// during the handshake so both start from the same media.
+pub mod save_sync;
+// Passphrase-keyed, AEAD-sealed traffic with replay protection: the secured
+// wrapper for every transport (portable; the UDP transports take it natively).
+pub mod secure;
 pub mod session;
+// Emulation-settings negotiation: the canonical digest + encoding of every
+// determinism-relevant setting, compared in the `Sync` handshake.
+pub mod settings;
 // v1.7.0 "Forge" Workstream H8 — the read-only spectator session: a
 // determinism-safe, receive-only extension of the rollback stack (it replays
 // the players' confirmed input stream, predicts nothing, sends nothing). It is
@@ -137,41 +160,55 @@ pub mod nat_connect;
 pub mod signaling_client;
 
 // The WebRTC signaling room/relay protocol (v2.6.0) — the pure, async-free core
-// of the reference signaling server (`examples/signaling_server.rs`, behind the
-// `signaling-server` feature). I/O-free + portable, so it compiles everywhere:
-// natively it backs the server + is unit-tested; on wasm the frontend uses its
-// `SignalMessage` parse/encode for the browser signaling client.
+// of the `rustynes-lobby` server. I/O-free + portable, so it compiles
+// everywhere: natively it backs the lobby + is unit-tested; on wasm the frontend
+// uses its `SignalMessage` parse/encode for the browser signaling client.
 pub mod signaling;
 
+// The network-conditions simulator behind the `netplay_sim` tool and the soak
+// test: N seeded peers over a hostile in-memory mesh. Portable like the
+// session core it drives.
+pub mod sim;
+
 // The WebRTC transport skeleton is wasm-only (it speaks `web_sys`).
 #[cfg(target_arch = "wasm32")]
 pub mod webrtc;
 
+pub use chat::{Chat, ChatError, ChatLine, ChatRateLimit, MAX_CHAT_BYTES};
 #[cfg(not(target_arch = "wasm32"))]
 pub use connection::{
     ConnectionState, DisconnectReason, NetplayConnection, PeerLink, UdpTransport,
 };
+pub use delay::{DelayChange, DelayMode, DelayTuner, DelayTuning};
 pub use diagnostics::{CrcCompare, DesyncDiagnostics, DesyncStatus};
+pub use match_record::{Interruption, MatchRecorder};
 #[cfg(not(target_arch = "wasm32"))]
 pub use mesh_net::{MeshError, MeshHost, MeshJoiner, UdpMeshTransport};
-pub use message::{NetMessage, PROTOCOL_VERSION, fnv1a64};
+pub use message::{NetMessage, PROTOCOL_VERSION, StateTransfer, fnv1a64};
 #[cfg(all(not(target_arch = "wasm32"), feature = "netplay-client"))]
 pub use nat_connect::{NatConfig, NatConnect, NatPhase};
+pub use recovery::RecoveryError;
 #[cfg(not(target_arch = "wasm32"))]
 pub use relay::{RelayUdpSocket, TurnClient, TurnConfig};
 pub use rng::SplitMix64;
+pub use save_sync::SessionSave;
+pub use secure::{SecureChannel, SecureTransport, SessionKey};
 pub use session::{AdvanceOutcome, MAX_PLAYERS, NetplayError, RollbackSession, SessionConfig};
-pub use signaling::{Action, ClientId, MAX_ROOM_LIST, Relay, RoomInfo, SignalMessage};
+pub use settings::{NetSettings, SettingDiff};
+pub use signaling::{
+    Action, ClientId, MAX_GAME_TITLE, MAX_ROOM_LIST, Relay, RoomInfo, SignalMessage, WRONG_PASSWORD,
+};
 #[cfg(all(not(target_arch = "wasm32"), feature = "netplay-client"))]
 pub use signaling_client::{SignalEvent, SignalingClient};
+pub use sim::{PeerStats, SimConfig, SimError, SimOutcome, SimReport};
 pub use spectator::{SpectatorConfig, SpectatorOutcome, SpectatorSession};
 #[cfg(not(target_arch = "wasm32"))]
 pub use stun::StunClient;
 pub use stun::{
     HolePunch, MAGIC_COOKIE, PunchState, TransactionId, build_binding_request,
-    parse_binding_response,
+    build_binding_response, parse_binding_response,
 };
-pub use transport::{LinkConditions, MemoryTransport, MeshTransport, Transport};
+pub use transport::{LinkConditions, LinkFaults, MemoryTransport, MeshTransport, Transport};
 
 #[cfg(target_arch = "wasm32")]
 pub use webrtc::{WebRtcMeshTransport, WebRtcTransport};
@@ -0,0 +1,313 @@
+//! Recording a netplay match as an attested `.rnm` movie.
+//!
+//! A [`MatchRecorder`] rides inside a [`RollbackSession`](crate::RollbackSession)
+//! or a [`SpectatorSession`](crate::spectator::SpectatorSession) and captures
+//! the *confirmed* stream only: every player's real input for each frame, and
+//! the framebuffer that frame produced on the canonical timeline. A rollback
+//! peer sees each frame run several times on predictions; the recorder hears
+//! about a frame once, when the session replays it from confirmed inputs and
+//! advances its checkpoint past it. A spectator only ever runs confirmed
+//! frames, so it records each one as it shows it.
+//!
+//! The movie starts from a save-state of the first recorded frame's entering
+//! state — after the cold boot, the negotiated settings and the session's save
+//! — so it replays on a verifier that has never seen the battery save or disk
+//! the peers played with. The settings themselves are not part of that state;
+//! they ride in the movie's [`MatchInfo`] tail, and a verifier applies them
+//! ([`NetSettings::apply`]) before [`Movie::verify`].
+//!
+//! A non-host peer that needed a desync recovery stops recording at the
+//! recovery: the frames after it continue from the host's state, which a
+//! replay of this peer's earlier frames does not reach. The movie keeps the
+//! frames before it, and [`MatchRecorder::interruption`] says so.
+//!
+//! A movie frame holds one expansion device. The recording stops the same way
+//! on the first frame where both players drive one (two Zappers, say), since
+//! the movie could not replay it, and the interruption names that reason so
+//! the frontend can tell the players.
+
+use rustynes_core::{
+    Buttons, DeviceInput, FrameInput, MatchInfo, Movie, MovieCommand, MovieRecorder, Nes,
+    UnrecordableDevice,
+};
+
+use crate::settings::NetSettings;
+
+/// Why a [`MatchRecorder`] stopped before the match ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interruption {
+    /// The timeline jumped under the recording (a desync recovery).
+    Recovery,
+    /// A frame's devices could not go into a movie frame.
+    Device(UnrecordableDevice),
+}
+
+/// Accumulates the confirmed frames of a match into a [`Movie`].
+#[derive(Clone, Debug)]
+pub struct MatchRecorder {
+    /// The canonical encoding of the session's settings.
+    settings: Vec<u8>,
+    /// `None` until the first confirmed frame is entered.
+    movie: Option<MovieRecorder>,
+    /// Players in the first recorded frame.
+    num_players: u8,
+    /// The frame the recording began at.
+    start_frame: u32,
+    /// The next frame the recording expects.
+    next_frame: u32,
+    /// Set when the recording had to stop; nothing after that point is
+    /// recorded.
+    interruption: Option<Interruption>,
+}
+
+impl MatchRecorder {
+    /// A recorder for a match played under `settings`.
+    #[must_use]
+    pub fn new(settings: &NetSettings) -> Self {
+        Self {
+            settings: settings.to_bytes(),
+            movie: None,
+            num_players: 0,
+            start_frame: 0,
+            next_frame: 0,
+            interruption: None,
+        }
+    }
+
+    /// Number of frames recorded so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.movie.as_ref().map_or(0, MovieRecorder::len)
+    }
+
+    /// `true` if no frame has been recorded yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The session frame the recording's first frame was, once one was
+    /// recorded.
+    #[must_use]
+    pub fn start_frame(&self) -> Option<u32> {
+        self.movie.as_ref().map(|_| self.start_frame)
+    }
+
+    /// `true` if the recording was cut short.
+    #[must_use]
+    pub const fn is_interrupted(&self) -> bool {
+        self.interruption.is_some()
+    }
+
+    /// What cut the recording short, if anything did.
+    #[must_use]
+    pub const fn interruption(&self) -> Option<Interruption> {
+        self.interruption
+    }
+
+    /// The session is about to run confirmed `frame` from `nes`'s current
+    /// state. Starts the recording on the first call; returns whether the
+    /// frame should be recorded (it must then be followed by one
+    /// [`record`](Self::record)).
+    pub(crate) fn enter(&mut self, nes: &Nes, frame: u32, num_players: u8) -> bool {
+        if self.interruption.is_some() {
+            return false;
+        }
+        if self.movie.is_none() {
+            let mut movie = MovieRecorder::from_current_state(nes);
+            movie.enable_attestation();
+            self.movie = Some(movie);
+            self.num_players = num_players;
+            self.start_frame = frame;
+            self.next_frame = frame;
+        } else if frame != self.next_frame {
+            self.interruption = Some(Interruption::Recovery);
+            return false;
+        }
+        true
+    }
+
+    /// Record the frame just [`enter`](Self::enter)ed: the players' inputs and
+    /// expansion devices in port order and the framebuffer the frame
+    /// produced.
+    pub(crate) fn record(&mut self, inputs: &[u8], devices: &[DeviceInput], framebuffer: &[u8]) {
+        let mut driven = devices.iter().filter(|&&d| d != DeviceInput::None);
+        let device = driven.next().copied().unwrap_or_default();
+        if driven.next().is_some() {
+            self.stop(Interruption::Device(UnrecordableDevice::BothPorts));
+            return;
+        }
+        let Some(movie) = self.movie.as_mut() else {
+            return;
+        };
+        let port = |p: usize| Buttons::from_bits_truncate(inputs.get(p).copied().unwrap_or(0));
+        movie.capture_input(FrameInput {
+            p1: port(0),
+            p2: port(1),
+            expansion: 0,
+            p3: port(2),
+            p4: port(3),
+            four_score: inputs.len() > 2,
+            device,
+            command: MovieCommand::None,
+        });
+        movie.attest_frame(framebuffer);
+        self.next_frame += 1;
+    }
+
+    /// Stop recording at the current frame: the timeline is about to jump.
+    /// A no-op before the first recorded frame.
+    pub(crate) const fn interrupt(&mut self) {
+        self.stop(Interruption::Recovery);
+    }
+
+    /// Stop recording for `why`. A no-op before the first recorded frame.
+    const fn stop(&mut self, why: Interruption) {
+        if self.movie.is_some() && self.interruption.is_none() {
+            self.interruption = Some(why);
+        }
+    }
+
+    /// Finish the recording. `None` if no frame was recorded.
+    #[must_use]
+    pub fn finish(self) -> Option<Movie> {
+        let mut movie = self.movie?;
+        movie.set_match_info(MatchInfo {
+            num_players: self.num_players,
+            settings: self.settings,
+        });
+        Some(movie.finish())
+    }
+}
+
+/// Configure `nes` the way a recorded match's peers were.
+///
+/// Applies the settings the movie's [`MatchInfo`] carries, ready for
+/// [`Movie::verify`] or playback. Returns `false` for a movie that is not a
+/// match recording or whose settings this build cannot decode.
+pub fn prepare_replay(movie: &Movie, nes: &mut Nes) -> bool {
+    let Some(settings) = movie
+        .match_info
+        .as_ref()
+        .and_then(|info| NetSettings::from_bytes(&info.settings))
+    else {
+        return false;
+    };
+    settings.apply(nes);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A minimal NROM (infinite loop). Mirrors the session/spectator fixtures.
+    fn synth_nrom() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"NES\x1A");
+        bytes.push(1);
+        bytes.push(1);
+        bytes.push(0);
+        bytes.push(0);
+        bytes.extend_from_slice(&[0u8; 8]);
+        let mut prg = vec![0u8; 16 * 1024];
+        prg[0] = 0x4C;
+        prg[1] = 0x00;
+        prg[2] = 0xC0;
+        let len = prg.len();
+        prg[len - 4] = 0x00;
+        prg[len - 3] = 0xC0;
+        prg[len - 6] = 0x00;
+        prg[len - 5] = 0xC0;
+        prg[len - 2] = 0x00;
+        prg[len - 1] = 0xC0;
+        bytes.extend_from_slice(&prg);
+        bytes.extend_from_slice(&vec![0u8; 8 * 1024]);
+        bytes
+    }
+
+    fn run(rec: &mut MatchRecorder, nes: &mut Nes, frame: u32, inputs: &[u8]) {
+        if rec.enter(nes, frame, 4) {
+            let fb = nes.run_frame().to_vec();
+            rec.record(inputs, &[], &fb);
+        } else {
+            let _ = nes.run_frame();
+        }
+    }
+
+    #[test]
+    fn four_player_frames_carry_every_port_and_the_four_score() {
+        let mut nes = Nes::from_rom(&synth_nrom()).expect("load");
+        let rec = MatchRecorder::new(&NetSettings::capture(&nes));
+        assert!(rec.finish().is_none(), "nothing recorded, no movie");
+
+        let mut rec = MatchRecorder::new(&NetSettings::capture(&nes));
+        run(&mut rec, &mut nes, 7, &[0x01, 0x02, 0x04, 0x08]);
+        assert_eq!(rec.start_frame(), Some(7));
+        let movie = rec.finish().expect("one frame");
+        let frame = movie.frames[0];
+        assert_eq!(
+            [frame.p1, frame.p2, frame.p3, frame.p4].map(|b| b.bits()),
+            [0x01, 0x02, 0x04, 0x08]
+        );
+        assert!(frame.four_score);
+        assert_eq!(movie.match_info.map(|m| m.num_players), Some(4));
+        assert!(matches!(
+            movie.start,
+            rustynes_core::StartPoint::SaveState(_)
+        ));
+    }
+
+    #[test]
+    fn a_jump_in_the_timeline_ends_the_recording() {
+        let mut nes = Nes::from_rom(&synth_nrom()).expect("load");
+        let mut rec = MatchRecorder::new(&NetSettings::capture(&nes));
+        // A recovery before the first frame does not count.
+        rec.interrupt();
+        run(&mut rec, &mut nes, 0, &[0; 4]);
+        run(&mut rec, &mut nes, 1, &[0; 4]);
+        run(&mut rec, &mut nes, 5, &[0; 4]);
+        run(&mut rec, &mut nes, 6, &[0; 4]);
+        assert_eq!(rec.interruption(), Some(Interruption::Recovery));
+        assert_eq!(rec.len(), 2, "the frames before the jump are kept");
+    }
+
+    #[test]
+    fn one_device_is_recorded_and_two_end_the_recording() {
+        let mut nes = Nes::from_rom(&synth_nrom()).expect("load");
+        let mut rec = MatchRecorder::new(&NetSettings::capture(&nes));
+        let zapper = DeviceInput::Zapper {
+            port: 1,
+            x: 64,
+            y: 32,
+            trigger: true,
+        };
+        let mat = DeviceInput::PowerPad {
+            port: 0,
+            buttons: 0x0010,
+        };
+        for (frame, devices) in [
+            [DeviceInput::None, zapper],
+            [DeviceInput::None, DeviceInput::None],
+            [mat, zapper],
+            [mat, DeviceInput::None],
+        ]
+        .iter()
+        .enumerate()
+        {
+            let frame = u32::try_from(frame).expect("small");
+            if rec.enter(&nes, frame, 2) {
+                let fb = nes.run_frame().to_vec();
+                rec.record(&[0, 0], devices, &fb);
+            }
+        }
+        assert_eq!(
+            rec.interruption(),
+            Some(Interruption::Device(UnrecordableDevice::BothPorts))
+        );
+        let movie = rec.finish().expect("frames before the pair");
+        assert_eq!(movie.frames.len(), 2);
+        assert_eq!(movie.frames[0].device, zapper);
+        assert_eq!(movie.frames[1].device, DeviceInput::None);
+    }
+}
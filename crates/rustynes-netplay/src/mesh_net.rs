@@ -27,6 +27,24 @@
 //!   **waits for the roster**; on receipt it builds a [`UdpMeshTransport`] wired
 //!   to the host and every *other* joiner (skipping its own entry).
 //!
+//! # Late joins
+//!
+//! The host need not wait for a full roster: [`MeshHost::start_partial`] starts
+//! with whoever has arrived, leaving the [`open_slots`](MeshHost::open_slots)
+//! for the session to [vacate](crate::RollbackSession::vacate_player). The
+//! host's transport keeps **admitting joins**: a
+//! [`JoinRequest`](crate::NetMessage::JoinRequest) from a new address for a slot
+//! missing from its roster adopts that address as the player and re-broadcasts
+//! the [`Roster`](crate::NetMessage::Roster), and every roster-tracking
+//! transport rewires its peer table from a roster the host sends. A newcomer
+//! needs no handshake object — it wraps a socket in
+//! `UdpMeshTransport::new(socket, vec![host])?.with_roster(slot, vec![(0, host)])`
+//! and hands that to
+//! [`RollbackSession::join_in_progress`](crate::RollbackSession::join_in_progress).
+//! When a player leaves, the host
+//! [`release_player`](UdpMeshTransport::release_player)s it so the slot can be
+//! taken over.
+//!
 //! # Determinism boundary
 //!
 //! Exactly as for [`NetplayConnection`](crate::NetplayConnection): all
@@ -43,6 +61,14 @@
 //! an already-adopted joiner does **not** re-adopt it or shift indices (idempotent
 //! adoption keyed by source address). A `Sync` carrying a mismatched ROM hash is
 //! rejected. None of these paths panic.
+//!
+//! # Secured meshes
+//!
+//! The host, every joiner and the transports they hand off can be
+//! [`secured`](MeshHost::secured) with a [`SecureChannel`] under one shared
+//! key: the handshake, the roster and all gameplay traffic are then sealed,
+//! and a datagram that does not authenticate is dropped like a malformed
+//! one. Each peer needs its own channel (its own sender id).
 
 use std::collections::BTreeMap;
 use std::io;
@@ -50,11 +76,13 @@ use std::net::{SocketAddr, UdpSocket};
 use std::time::{Duration, Instant};
 
 use crate::message::NetMessage;
+use crate::secure::{SecureChannel, decode, encode, hello};
 use crate::transport::Transport;
 
 /// Largest datagram read in one `recv_from`. The longest [`NetMessage`] is a
-/// full 4-peer `Roster` (tag + count + 4×(player + v6-addr-19) ≈ 82 bytes) or
-/// the 37-byte `Sync`; 1500 covers a standard MTU with headroom.
+/// full `StateChunk` (12-byte header + 1024-byte payload); the next longest a
+/// full 4-peer `Roster` (tag + count + 4×(player + v6-addr-19) ≈ 82 bytes).
+/// 1500 covers a standard MTU with headroom.
 const RECV_BUF_LEN: usize = 1500;
 
 /// Maximum datagrams drained in a single poll. UDP is hostile input; cap the
@@ -81,6 +109,15 @@ pub struct UdpMeshTransport {
     peers: Vec<SocketAddr>,
     /// Count of datagrams that failed to parse. Diagnostic only.
     dropped_invalid: u64,
+    /// This peer's player index, once the transport tracks the roster (see
+    /// [`Self::with_roster`]); `None` for a fixed peer table.
+    me: Option<u8>,
+    /// Every player's `(index, addr)`, this peer included, sorted by index.
+    roster: Vec<(u8, SocketAddr)>,
+    /// Host only: adopt a `JoinRequest`'s source as a new player.
+    admit_joins: bool,
+    /// Seals and authenticates all traffic, once [`secured`](Self::secured).
+    secure: Option<SecureChannel>,
 }
 
 impl UdpMeshTransport {
@@ -96,9 +133,61 @@ impl UdpMeshTransport {
             socket,
             peers,
             dropped_invalid: 0,
+            me: None,
+            roster: Vec::new(),
+            admit_joins: false,
+            secure: None,
         })
     }
 
+    /// Track the match roster as player `me`: replace the peer table with
+    /// every entry of `roster` but our own, and rewire it whenever the host
+    /// (the roster's player 0) sends a new [`NetMessage::Roster`] — so a
+    /// player who joins mid-match is picked up. Builder-style.
+    #[must_use]
+    pub fn with_roster(mut self, me: u8, mut roster: Vec<(u8, SocketAddr)>) -> Self {
+        roster.sort_by_key(|&(p, _)| p);
+        self.me = Some(me);
+        self.roster = roster;
+        self.rebuild_peers();
+        self
+    }
+
+    /// Host only: admit late joiners. A [`NetMessage::JoinRequest`] from a
+    /// new address for a player index missing from the roster adopts that
+    /// address as the player and broadcasts the amended roster. The request
+    /// is still handed to the session, which decides whether the slot is
+    /// actually open. Builder-style.
+    #[must_use]
+    pub const fn admitting_joins(mut self) -> Self {
+        self.admit_joins = true;
+        self
+    }
+
+    /// Seal all traffic with `channel` (see [`crate::secure`]); inbound
+    /// datagrams that do not authenticate are dropped as invalid. Every peer
+    /// of the mesh must be secured with the same key. Builder-style.
+    #[must_use]
+    pub fn secured(mut self, channel: SecureChannel) -> Self {
+        self.secure = Some(channel);
+        self
+    }
+
+    /// The channel sealing this transport's traffic, if
+    /// [`secured`](Self::secured).
+    #[must_use]
+    pub const fn secure_channel(&self) -> Option<&SecureChannel> {
+        self.secure.as_ref()
+    }
+
+    /// Drop `player` from the roster (its peer has left), so the transport
+    /// stops sending to it and — when [admitting joins](Self::admitting_joins)
+    /// — a newcomer may take the slot over.
+    pub fn release_player(&mut self, player: u8) {
+        self.roster.retain(|&(p, _)| p != player);
+        self.rebuild_peers();
+    }
+
     /// The local address the socket is bound to.
     ///
     /// # Errors
@@ -117,8 +206,81 @@ impl UdpMeshTransport {
         &self.peers
     }
 
+    /// The tracked roster, `(player, addr)` sorted by player (empty for a
+    /// fixed peer table).
+    // See `peers` for why this is not a `const fn`.
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn roster(&self) -> &[(u8, SocketAddr)] {
+        &self.roster
+    }
+
+    /// Recompute `peers` from the roster: everyone but us.
+    fn rebuild_peers(&mut self) {
+        self.peers = self
+            .roster
+            .iter()
+            .filter(|&&(p, _)| Some(p) != self.me)
+            .map(|&(_, a)| a)
+            .collect();
+    }
+
+    /// Send the current roster to `to`.
+    fn send_roster(&mut self, to: SocketAddr) {
+        let bytes = encode(
+            self.secure.as_mut(),
+            &NetMessage::Roster {
+                peers: self.roster.clone(),
+            },
+        );
+        let _ = self.socket.send_to(&bytes, to);
+    }
+
+    /// Fold one inbound message into the roster bookkeeping (before it is
+    /// handed to the session).
+    fn track_roster(&mut self, msg: &NetMessage, from: SocketAddr) {
+        let Some(me) = self.me else {
+            return;
+        };
+        let known = self.roster.iter().any(|&(_, a)| a == from);
+        match *msg {
+            NetMessage::JoinRequest { player } if self.admit_joins => {
+                if self.roster.contains(&(player, from)) {
+                    // Already admitted; its copy of the roster was lost.
+                    self.send_roster(from);
+                    return;
+                }
+                if player == me
+                    || usize::from(player) >= NetMessage::MAX_ROSTER
+                    || known
+                    || self.roster.iter().any(|&(p, _)| p == player)
+                {
+                    return;
+                }
+                self.roster.push((player, from));
+                self.roster.sort_by_key(|&(p, _)| p);
+                self.rebuild_peers();
+                for peer in self.peers.clone() {
+                    self.send_roster(peer);
+                }
+            }
+            // A handshake joiner still re-sending `Sync` lost the roster.
+            NetMessage::Sync { .. } if self.admit_joins && known => self.send_roster(from),
+            NetMessage::Roster { ref peers } if me != 0 => {
+                let host = self.roster.iter().find(|&&(p, _)| p == 0).map(|&(_, a)| a);
+                if host == Some(from) && peers.iter().any(|&(p, _)| p == 0) {
+                    self.roster.clone_from(peers);
+                    self.roster.sort_by_key(|&(p, _)| p);
+                    self.rebuild_peers();
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Total datagrams dropped for being malformed / truncated / foreign
-    /// version. Diagnostic only.
+    /// version, or — when [`secured`](Self::secured) — unauthenticated.
+    /// Diagnostic only.
     #[must_use]
     pub const fn dropped_invalid(&self) -> u64 {
         self.dropped_invalid
@@ -127,7 +289,8 @@ impl UdpMeshTransport {
 
 impl Transport for UdpMeshTransport {
     fn send(&mut self, msg: &NetMessage) {
-        let bytes = msg.to_bytes();
+        // One envelope serves every peer: each tracks our sender id on its own.
+        let bytes = encode(self.secure.as_mut(), msg);
         for peer in &self.peers {
             // A failed send is non-fatal (the rollback protocol tolerates loss
             // and resends); swallow it rather than panic.
@@ -140,10 +303,18 @@ impl Transport for UdpMeshTransport {
         let mut buf = [0u8; RECV_BUF_LEN];
         for _ in 0..MAX_DATAGRAMS_PER_POLL {
             match self.socket.recv_from(&mut buf) {
-                Ok((len, _from)) => match NetMessage::from_bytes(&buf[..len]) {
-                    Some(msg) => out.push(msg),
-                    None => self.dropped_invalid = self.dropped_invalid.saturating_add(1),
-                },
+                Ok((len, from)) => {
+                    match decode(self.secure.as_mut(), &buf[..len]) {
+                        Some(msg) => {
+                            self.track_roster(&msg, from);
+                            out.push(msg);
+                        }
+                        None => self.dropped_invalid = self.dropped_invalid.saturating_add(1),
+                    }
+                    if let Some(bytes) = hello(self.secure.as_mut()) {
+                        let _ = self.socket.send_to(&bytes, from);
+                    }
+                }
                 Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
                 // Windows can surface a prior send's ICMP unreachable as a
                 // ConnectionReset on the next recv — not fatal, keep draining.
@@ -201,6 +372,8 @@ pub struct MeshHost {
     /// How many extra roster re-broadcasts remain after the roster filled.
     roster_resends: u8,
     last_roster_sent: Option<Instant>,
+    /// Seals the handshake; handed on to the host's transport.
+    secure: Option<SecureChannel>,
 }
 
 impl MeshHost {
@@ -237,6 +410,7 @@ impl MeshHost {
             timeout: Self::DEFAULT_TIMEOUT,
             roster_resends: Self::ROSTER_RESENDS,
             last_roster_sent: None,
+            secure: None,
         })
     }
 
@@ -247,6 +421,15 @@ impl MeshHost {
         self
     }
 
+    /// Seal the handshake with `channel` and hand it on to the host's
+    /// [`UdpMeshTransport`]: only joiners secured with the same key are heard
+    /// (see the module docs). Builder-style.
+    #[must_use]
+    pub fn secured(mut self, channel: SecureChannel) -> Self {
+        self.secure = Some(channel);
+        self
+    }
+
     /// The local address the listening socket is bound to (resolves an ephemeral
     /// `:0` port). Joiners dial this.
     ///
@@ -273,6 +456,76 @@ impl MeshHost {
         self.joiners.len() + 1 >= self.num_players as usize
     }
 
+    /// The player indices no joiner has been adopted for yet.
+    #[must_use]
+    pub fn open_slots(&self) -> Vec<u8> {
+        (1..self.num_players)
+            .filter(|p| !self.joiners.values().any(|j| j == p))
+            .collect()
+    }
+
+    /// Start the match now with whoever has joined: broadcast the partial
+    /// roster and hand back the host's [`UdpMeshTransport`], as
+    /// [`pump`](Self::pump) does once the roster is full. The
+    /// [`open_slots`](Self::open_slots) must be
+    /// [vacated](crate::RollbackSession::vacate_player) on the host's session;
+    /// players can then take them over mid-match (see the module docs).
+    ///
+    /// # Errors
+    ///
+    /// [`MeshError::Timeout`] if the socket was already handed off or could
+    /// not be configured.
+    pub fn start_partial(&mut self) -> Result<UdpMeshTransport, MeshError> {
+        if self.socket.is_none() {
+            return Err(MeshError::Timeout);
+        }
+        self.hand_off(Instant::now())
+    }
+
+    /// Broadcast the roster (initial + a few resends) and hand back the
+    /// host's mesh transport, tracking the roster and admitting late joins.
+    fn hand_off(&mut self, now: Instant) -> Result<UdpMeshTransport, MeshError> {
+        let roster = self.build_roster();
+        let due = self
+            .last_roster_sent
+            .is_none_or(|t| now.saturating_duration_since(t) >= Self::ROSTER_RESEND_INTERVAL);
+        if due && self.roster_resends > 0 {
+            let bytes = encode(
+                self.secure.as_mut(),
+                &NetMessage::Roster {
+                    peers: roster.clone(),
+                },
+            );
+            let socket = self.socket.as_ref().expect("host socket present");
+            for &joiner in self.joiners.keys() {
+                let _ = socket.send_to(&bytes, joiner);
+            }
+            self.last_roster_sent = Some(now);
+            self.roster_resends -= 1;
+        }
+        // After the first broadcast, build + return the host's transport.
+        // (We still want the joiners to have received it; the resends above
+        // ride out loss, but the host can proceed immediately — its mesh
+        // transport simply starts sending Inputs, which also doubles as
+        // liveness for the joiners, and re-sends the roster to a joiner still
+        // `Sync`ing.)
+        let socket = self.socket.take().expect("host socket present to hand off");
+        let peers: Vec<SocketAddr> = self.joiners.keys().copied().collect();
+        // We never reach here without at least the resend above; map an I/O
+        // error on the (already-configured non-blocking) socket to a Timeout
+        // rather than panic.
+        let secure = self.secure.take();
+        UdpMeshTransport::new(socket, peers)
+            .map(|t| {
+                let t = t.with_roster(0, roster).admitting_joins();
+                match secure {
+                    Some(channel) => t.secured(channel),
+                    None => t,
+                }
+            })
+            .map_err(|_| MeshError::Timeout)
+    }
+
     /// Build the full roster (host first, then joiners in adoption order).
     fn build_roster(&self) -> Vec<(u8, SocketAddr)> {
         let mut peers = Vec::with_capacity(self.num_players as usize);
@@ -311,9 +564,12 @@ impl MeshHost {
         for _ in 0..MAX_DATAGRAMS_PER_POLL {
             match socket.recv_from(&mut buf) {
                 Ok((len, from)) => {
-                    if let Some(msg) = NetMessage::from_bytes(&buf[..len]) {
+                    if let Some(msg) = decode(self.secure.as_mut(), &buf[..len]) {
                         inbound.push((msg, from));
                     }
+                    if let Some(bytes) = hello(self.secure.as_mut()) {
+                        let _ = socket.send_to(&bytes, from);
+                    }
                 }
                 Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
                 Err(e) if e.kind() == io::ErrorKind::ConnectionReset => {}
@@ -322,7 +578,10 @@ impl MeshHost {
         }
 
         for (msg, from) in inbound {
-            if let NetMessage::Sync { magic, rom_hash } = msg {
+            if let NetMessage::Sync {
+                magic, rom_hash, ..
+            } = msg
+            {
                 if magic != NetMessage::SYNC_MAGIC {
                     continue;
                 }
@@ -340,37 +599,10 @@ impl MeshHost {
             // Non-Sync traffic during the handshake is ignored (early/stray).
         }
 
-        // Once the roster is full, broadcast it (initial + a few resends) and
-        // then hand back the host's mesh transport.
+        // Once the roster is full, broadcast it and hand back the host's mesh
+        // transport.
         if self.roster_full() {
-            let due = self
-                .last_roster_sent
-                .is_none_or(|t| now.saturating_duration_since(t) >= Self::ROSTER_RESEND_INTERVAL);
-            if due && self.roster_resends > 0 {
-                let roster = NetMessage::Roster {
-                    peers: self.build_roster(),
-                };
-                let bytes = roster.to_bytes();
-                let socket = self.socket.as_ref().expect("host socket present");
-                for &joiner in self.joiners.keys() {
-                    let _ = socket.send_to(&bytes, joiner);
-                }
-                self.last_roster_sent = Some(now);
-                self.roster_resends -= 1;
-            }
-            // After the first broadcast, build + return the host's transport.
-            // (We still want the joiners to have received it; the resends above
-            // ride out loss, but the host can proceed immediately — its mesh
-            // transport simply starts sending Inputs, which also doubles as
-            // liveness for the joiners.)
-            let socket = self.socket.take().expect("host socket present to hand off");
-            let peers: Vec<SocketAddr> = self.joiners.keys().copied().collect();
-            // We never reach here without at least the resend above; map an I/O
-            // error on the (already-configured non-blocking) socket to a Timeout
-            // rather than panic.
-            return UdpMeshTransport::new(socket, peers)
-                .map(Some)
-                .map_err(|_| MeshError::Timeout);
+            return self.hand_off(now).map(Some);
         }
 
         // Still gathering. Enforce the timeout.
@@ -409,15 +641,19 @@ pub struct MeshJoiner {
     my_player: u8,
     started: Instant,
     timeout: Duration,
+    /// `None` until the opening `Sync` goes out on the first `pump`.
     last_sync_sent: Option<Instant>,
+    /// Seals the handshake; handed on to the joiner's transport.
+    secure: Option<SecureChannel>,
 }
 
 impl MeshJoiner {
     const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
     const SYNC_RESEND_INTERVAL: Duration = Duration::from_millis(50);
 
-    /// Bind an ephemeral joiner socket, dial `host`, and send the opening
-    /// `Sync`. `my_player` is this joiner's player index (1..=3).
+    /// Bind an ephemeral joiner socket to dial `host`. The opening `Sync`
+    /// goes out on the first [`pump`](Self::pump). `my_player` is this
+    /// joiner's player index (1..=3).
     ///
     /// # Errors
     ///
@@ -430,24 +666,27 @@ impl MeshJoiner {
     ) -> io::Result<Self> {
         let socket = UdpSocket::bind(local)?;
         socket.set_nonblocking(true)?;
-        let sync = NetMessage::Sync {
-            magic: NetMessage::SYNC_MAGIC,
-            rom_hash,
-        }
-        .to_bytes();
-        let _ = socket.send_to(&sync, host);
-        let now = Instant::now();
         Ok(Self {
             socket: Some(socket),
             host,
             rom_hash,
             my_player,
-            started: now,
+            started: Instant::now(),
             timeout: Self::DEFAULT_TIMEOUT,
-            last_sync_sent: Some(now),
+            last_sync_sent: None,
+            secure: None,
         })
     }
 
+    /// Seal the handshake with `channel` and hand it on to the joiner's
+    /// [`UdpMeshTransport`]; the host must be secured with the same key.
+    /// Builder-style; call before the first [`pump`](Self::pump).
+    #[must_use]
+    pub fn secured(mut self, channel: SecureChannel) -> Self {
+        self.secure = Some(channel);
+        self
+    }
+
     /// Override the wait timeout (default 30s). Builder-style.
     #[must_use]
     pub const fn with_timeout(mut self, timeout: Duration) -> Self {
@@ -474,7 +713,7 @@ impl MeshJoiner {
         self.my_player
     }
 
-    /// Drive the joiner one step: re-send `Sync`, watch for the host's `Roster`,
+    /// Drive the joiner one step: (re-)send `Sync`, watch for the host's `Roster`,
     /// and on receipt build the [`UdpMeshTransport`] (wired to the host + every
     /// other joiner, skipping this joiner's own entry).
     ///
@@ -493,15 +732,21 @@ impl MeshJoiner {
         let mut roster: Option<Vec<(u8, SocketAddr)>> = None;
         for _ in 0..MAX_DATAGRAMS_PER_POLL {
             match socket.recv_from(&mut buf) {
-                Ok((len, _from)) => match NetMessage::from_bytes(&buf[..len]) {
-                    Some(NetMessage::Roster { peers }) => roster = Some(peers),
-                    Some(NetMessage::Sync { magic, rom_hash })
-                        if magic == NetMessage::SYNC_MAGIC && rom_hash != self.rom_hash =>
-                    {
-                        return Err(MeshError::RomMismatch);
+                Ok((len, from)) => {
+                    let msg = decode(self.secure.as_mut(), &buf[..len]);
+                    if let Some(bytes) = hello(self.secure.as_mut()) {
+                        let _ = socket.send_to(&bytes, from);
                     }
-                    _ => {}
-                },
+                    match msg {
+                        Some(NetMessage::Roster { peers }) => roster = Some(peers),
+                        Some(NetMessage::Sync {
+                            magic, rom_hash, ..
+                        }) if magic == NetMessage::SYNC_MAGIC && rom_hash != self.rom_hash => {
+                            return Err(MeshError::RomMismatch);
+                        }
+                        _ => {}
+                    }
+                }
                 Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
                 Err(e) if e.kind() == io::ErrorKind::ConnectionReset => {}
                 Err(_) => break,
@@ -522,7 +767,8 @@ impl MeshJoiner {
             {
                 self.my_player = p;
             }
-            // The mesh is every peer EXCEPT this joiner's own index.
+            // The mesh is every peer EXCEPT this joiner's own index; keep
+            // tracking the roster so a mid-match joiner is picked up.
             let others: Vec<SocketAddr> = peers
                 .iter()
                 .filter(|&&(p, _)| p != self.my_player)
@@ -532,7 +778,15 @@ impl MeshJoiner {
                 .socket
                 .take()
                 .expect("joiner socket present to hand off");
+            let secure = self.secure.take();
             return UdpMeshTransport::new(socket, others)
+                .map(|t| {
+                    let t = t.with_roster(self.my_player, peers);
+                    match secure {
+                        Some(channel) => t.secured(channel),
+                        None => t,
+                    }
+                })
                 .map(Some)
                 .map_err(|_| MeshError::Timeout);
         }
@@ -542,11 +796,16 @@ impl MeshJoiner {
             .last_sync_sent
             .is_none_or(|t| now.saturating_duration_since(t) >= Self::SYNC_RESEND_INTERVAL);
         if due && let Some(socket) = self.socket.as_ref() {
-            let sync = NetMessage::Sync {
-                magic: NetMessage::SYNC_MAGIC,
-                rom_hash: self.rom_hash,
-            }
-            .to_bytes();
+            // The mesh handshake does not negotiate emulation settings or saves.
+            let sync = encode(
+                self.secure.as_mut(),
+                &NetMessage::Sync {
+                    magic: NetMessage::SYNC_MAGIC,
+                    rom_hash: self.rom_hash,
+                    settings: 0,
+                    save: 0,
+                },
+            );
             let _ = socket.send_to(&sync, self.host);
             self.last_sync_sent = Some(now);
         }
@@ -561,6 +820,7 @@ impl MeshJoiner {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rustynes_core::DeviceInput;
     use std::net::Ipv4Addr;
 
     fn loopback() -> SocketAddr {
@@ -583,6 +843,7 @@ mod tests {
             player: 0,
             frame: 11,
             input: 0x42,
+            device: DeviceInput::None,
         };
         t0.send(&msg);
         // Give loopback a brief, bounded chance to deliver.
@@ -714,6 +975,111 @@ mod tests {
         assert_eq!(host.joiners_ready(), 1, "exactly one joiner adopted");
     }
 
+    #[test]
+    fn secured_host_hears_only_joiners_with_its_key() {
+        use crate::secure::SessionKey;
+        let hash = [0x66u8; 32];
+        let key = SessionKey::derive("mesh room", b"");
+        let probe = UdpSocket::bind(loopback()).unwrap();
+        let port = probe.local_addr().unwrap();
+        drop(probe);
+        let mut host = MeshHost::bind(port, port, 2, hash)
+            .unwrap()
+            .secured(SecureChannel::new(&key, [0; 16]));
+        // A plain joiner and one with the wrong key dial in first.
+        let mut plain = MeshJoiner::connect(loopback(), port, 1, hash).unwrap();
+        let mut stranger = MeshJoiner::connect(loopback(), port, 1, hash)
+            .unwrap()
+            .secured(SecureChannel::new(
+                &SessionKey::derive("guess", b""),
+                [2; 16],
+            ));
+        for _ in 0..20 {
+            let _ = plain.pump();
+            let _ = stranger.pump();
+            assert!(host.pump().expect("host pump").is_none());
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(host.joiners_ready(), 0, "no unauthenticated joiner adopted");
+
+        let mut joiner = MeshJoiner::connect(loopback(), port, 1, hash)
+            .unwrap()
+            .secured(SecureChannel::new(&key, [1; 16]));
+        let (mut host_t, mut joiner_t) = (None, None);
+        for _ in 0..500 {
+            if joiner_t.is_none() {
+                joiner_t = joiner.pump().expect("joiner pump");
+            }
+            if host_t.is_none() {
+                host_t = host.pump().expect("host pump");
+            }
+            if host_t.is_some() && joiner_t.is_some() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        let (mut host_t, mut joiner_t) = (host_t.expect("host"), joiner_t.expect("joiner"));
+        let msg = NetMessage::InputAck { frame: 3 };
+        joiner_t.send(&msg);
+        // A handshake `Sync` may still be in flight ahead of it.
+        let mut got = Vec::new();
+        for _ in 0..200 {
+            got.extend(host_t.poll());
+            if got.contains(&msg) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(got.contains(&msg), "sealed gameplay traffic arrives");
+        assert!(host_t.secure_channel().is_some_and(|c| c.accepted() > 0));
+    }
+
+    #[test]
+    fn host_admits_a_late_joiner_into_an_open_slot() {
+        let sh = UdpSocket::bind(loopback()).unwrap();
+        let sj = UdpSocket::bind(loopback()).unwrap();
+        let sn = UdpSocket::bind(loopback()).unwrap();
+        let (ah, aj, an) = (
+            sh.local_addr().unwrap(),
+            sj.local_addr().unwrap(),
+            sn.local_addr().unwrap(),
+        );
+        // A 3-player match that started with slot 2 open.
+        let roster = vec![(0, ah), (1, aj)];
+        let mut host = UdpMeshTransport::new(sh, vec![])
+            .unwrap()
+            .with_roster(0, roster.clone())
+            .admitting_joins();
+        let mut joiner = UdpMeshTransport::new(sj, vec![])
+            .unwrap()
+            .with_roster(1, roster);
+        let mut newcomer = UdpMeshTransport::new(sn, vec![ah])
+            .unwrap()
+            .with_roster(2, vec![(0, ah)]);
+
+        // An occupied slot is not handed over, but the request still reaches
+        // the session.
+        newcomer.send(&NetMessage::JoinRequest { player: 1 });
+        assert_eq!(
+            drain(&mut host, 1),
+            vec![NetMessage::JoinRequest { player: 1 }]
+        );
+        assert_eq!(host.peers(), [aj]);
+
+        newcomer.send(&NetMessage::JoinRequest { player: 2 });
+        drain(&mut host, 1);
+        assert_eq!(host.roster(), [(0, ah), (1, aj), (2, an)]);
+        // The amended roster rewires the existing joiner and the newcomer.
+        drain(&mut joiner, 1);
+        drain(&mut newcomer, 1);
+        assert_eq!(joiner.peers(), [ah, an]);
+        assert_eq!(newcomer.peers(), [ah, aj]);
+
+        // Once released, the slot is open to a different address again.
+        host.release_player(2);
+        assert_eq!(host.peers(), [aj]);
+    }
+
     fn drain(t: &mut UdpMeshTransport, want: usize) -> Vec<NetMessage> {
         let mut got = Vec::new();
         for _ in 0..200 {
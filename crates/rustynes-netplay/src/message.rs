@@ -11,6 +11,8 @@
 
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 
+use rustynes_core::{DEVICE_INPUT_WIRE_LEN, DeviceInput};
+
 /// Protocol version. Bumped if the wire layout of [`NetMessage`] changes so
 /// the [`NetMessage::SYNC_MAGIC`] handshake can reject mismatched peers.
 ///
@@ -27,8 +29,78 @@ use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 /// rejects the unknown tag cleanly (returns `None`), so a v2 peer simply drops
 /// a v3 `Roster` rather than mis-parsing it.
 ///
+/// `5`: added [`NetMessage::StateChunk`], [`NetMessage::StateAck`] and
+/// [`NetMessage::ResyncRequest`] — desync recovery by an authoritative state
+/// transfer from the host. New tags only; the existing layouts are unchanged.
+///
+/// `6`: added [`NetMessage::JoinRequest`] and [`NetMessage::JoinAccept`] —
+/// players and spectators joining a match already in progress. New tags only.
+///
+/// `7`: [`NetMessage::Sync`] gained a `settings` digest of every
+/// determinism-relevant emulation setting, and [`NetMessage::Settings`]
+/// carries the full settings when two peers' digests differ. `Sync` is 8
+/// bytes longer: a v7 peer drops a short v6 `Sync`, so mixed versions never
+/// complete the handshake.
+///
+/// `8`: [`NetMessage::Sync`] gained a `save` digest of the battery RAM and
+/// FDS disk, and [`NetMessage::SaveChunk`] / [`NetMessage::SaveAck`] stream
+/// the host's save to a joiner that adopts it. `Sync` is again 8 bytes
+/// longer, so a v8 peer drops a v7 `Sync`.
+///
+/// `9`: added [`NetMessage::Sealed`] — an encrypted, authenticated envelope
+/// around any other message, used by the secured transports (see
+/// [`crate::secure`]). A new tag only; plain sessions never send it.
+///
+/// `10`: added [`NetMessage::InputDelay`] — the host schedules a new input
+/// delay for every peer at an agreed frame (see [`crate::delay`]). A new tag
+/// only.
+///
+/// `11`: added [`NetMessage::Chat`] / [`NetMessage::ChatAck`] — in-session
+/// chat lines, resent until every peer acknowledges them (see
+/// [`crate::chat`]). New tags only; they never touch emulated state.
+///
+/// `12`: [`NetMessage::Input`] gained a `device` — the player's
+/// expansion-device state (Zapper, Vaus, SNES mouse, Power Pad, keyboards;
+/// see [`crate::device`]). After the `input` byte comes the device kind, and
+/// for any device but none its port and nine-byte payload. A v11 `Input` is
+/// a byte short and parses to nothing, so mixed versions never interoperate.
+///
+/// `13`: [`NetMessage::StateChunk`] gained a [`StateTransfer`] kind byte, so
+/// a peer tells a late joiner's state from a desync recovery by what the
+/// host says it is rather than by matching frames against a `JoinAccept`
+/// that may not have arrived.
+///
 /// [`from_bytes`]: NetMessage::from_bytes
-pub const PROTOCOL_VERSION: u32 = 4;
+pub const PROTOCOL_VERSION: u32 = 13;
+
+/// What a [`NetMessage::StateChunk`] transfer is for (protocol 13).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateTransfer {
+    /// A desync recovery: every peer restores it.
+    Recovery,
+    /// A match joined in progress: only the newcomer (or live spectator)
+    /// restores it.
+    Join,
+}
+
+impl StateTransfer {
+    /// The wire byte.
+    const fn to_u8(self) -> u8 {
+        match self {
+            Self::Recovery => 0,
+            Self::Join => 1,
+        }
+    }
+
+    /// Inverse of [`Self::to_u8`]; an unknown byte is `None`.
+    const fn from_u8(b: u8) -> Option<Self> {
+        match b {
+            0 => Some(Self::Recovery),
+            1 => Some(Self::Join),
+            _ => None,
+        }
+    }
+}
 
 /// Messages exchanged between two peers.
 ///
@@ -56,6 +128,9 @@ pub enum NetMessage {
         frame: u32,
         /// `Buttons::bits()` for that player on that frame.
         input: u8,
+        /// The player's expansion-device state on that frame, or
+        /// [`DeviceInput::None`] (protocol 12).
+        device: DeviceInput,
     },
 
     /// Acknowledges receipt of the remote's input up to `frame`. Lets the
@@ -66,13 +141,23 @@ pub enum NetMessage {
         frame: u32,
     },
 
-    /// Connection handshake: confirms protocol compatibility and an
-    /// identical ROM. Sent at session start and until the peer replies.
+    /// Connection handshake: confirms protocol compatibility, an identical
+    /// ROM and identical emulation settings. Sent at session start and until
+    /// the peer replies.
     Sync {
         /// Magic constant — must equal [`NetMessage::SYNC_MAGIC`].
         magic: u32,
         /// SHA-256 of the ROM both peers must be running.
         rom_hash: [u8; 32],
+        /// [`NetSettings::digest`](crate::NetSettings::digest) of the
+        /// sender's emulation settings (protocol 7), or `0` when the sender
+        /// does not announce them — a peer that announces nothing is never
+        /// refused on settings.
+        settings: u64,
+        /// [`SessionSave::digest`](crate::SessionSave::digest) of the
+        /// sender's battery RAM and FDS disk (protocol 8), or `0` when not
+        /// announced — never refused.
+        save: u64,
     },
 
     /// Periodic state checksum for desync detection. Both peers compute a
@@ -122,12 +207,166 @@ pub enum NetMessage {
         /// host's player; each other entry is a fellow joiner.
         peers: Vec<(u8, SocketAddr)>,
     },
+
+    /// One chunk of the host's authoritative state, sent to recover from a
+    /// desync (protocol 5). The host compresses the canonical snapshot
+    /// entering `frame`, splits it into `count` chunks of at most
+    /// [`Self::MAX_STATE_CHUNK`] bytes, and resends each one until every peer
+    /// has acknowledged it with a [`Self::StateAck`]. A peer restores the
+    /// reassembled state once it reaches `frame`. The same chunks carry a
+    /// late joiner's starting state, told apart by `kind`.
+    ///
+    /// Encoded as `kind`, `frame`, `index`, `count`, a little-endian `u16`
+    /// length and the payload. An unknown `kind`, a zero `count`, an `index`
+    /// outside it, or a payload over the cap decodes to `None`.
+    StateChunk {
+        /// What the transfer is for (protocol 13).
+        kind: StateTransfer,
+        /// The frame the transferred state enters (the agreed restore frame).
+        frame: u32,
+        /// This chunk's position, `0..count`.
+        index: u16,
+        /// How many chunks the transfer has.
+        count: u16,
+        /// The chunk's bytes of the compressed snapshot.
+        data: Vec<u8>,
+    },
+
+    /// Acknowledges one [`Self::StateChunk`] (protocol 5). Tagged with the
+    /// sender's player index so the host can track every peer of a mesh.
+    StateAck {
+        /// The acknowledging peer's player index.
+        player: u8,
+        /// The transfer's restore frame.
+        frame: u32,
+        /// The chunk received.
+        index: u16,
+    },
+
+    /// A peer saw its confirmed-frame checksum disagree at `frame` and asks
+    /// the host for an authoritative state (protocol 5). Resent every tick
+    /// until a transfer starts, so a dropped request self-heals.
+    ResyncRequest {
+        /// The requesting peer's player index.
+        player: u8,
+        /// The confirmed frame whose checksum mismatched.
+        frame: u32,
+    },
+
+    /// Asks the host to let the sender into a match already in progress
+    /// (protocol 6): take over the vacant player slot `player`, or watch when
+    /// `player` is [`Self::SPECTATOR`]. Resent every tick until the host's
+    /// [`Self::JoinAccept`] and state transfer arrive.
+    JoinRequest {
+        /// The vacant player slot asked for, or [`Self::SPECTATOR`].
+        player: u8,
+    },
+
+    /// The host's answer to a [`Self::JoinRequest`] (protocol 6), broadcast to
+    /// every peer while the join is in flight. The newcomer receives the
+    /// host's confirmed state entering `base_frame` as [`Self::StateChunk`]s;
+    /// every peer resends its inputs from `base_frame` so the newcomer can
+    /// replay forward; and `player` is the newcomer's from `join_frame` on —
+    /// until then the host keeps authoring the slot's idle input.
+    JoinAccept {
+        /// The slot being filled, or [`Self::SPECTATOR`].
+        player: u8,
+        /// The frame the transferred state enters.
+        base_frame: u32,
+        /// The first frame whose input the newcomer authors.
+        join_frame: u32,
+    },
+
+    /// The host's schedule for a new input delay (protocol 10): every peer
+    /// authors its local input `delay` frames ahead from `frame` on. Resent
+    /// every tick until `frame` is reached; a newer schedule replaces an
+    /// older one. See [`crate::delay`].
+    InputDelay {
+        /// The first frame the new delay applies to.
+        frame: u32,
+        /// The new delay in frames, at most
+        /// [`MAX_INPUT_DELAY`](crate::delay::MAX_INPUT_DELAY).
+        delay: u8,
+    },
+
+    /// One chat line (protocol 11): at most
+    /// [`MAX_CHAT_BYTES`](crate::chat::MAX_CHAT_BYTES) of UTF-8 with no
+    /// control characters — anything else decodes to `None`. Resent until
+    /// every other player answers with a [`Self::ChatAck`]; see
+    /// [`crate::chat`].
+    Chat {
+        /// The sending player's index.
+        player: u8,
+        /// The sender's sequence number for this line.
+        seq: u32,
+        /// The line itself.
+        text: String,
+    },
+
+    /// Acknowledges [`Self::Chat`] `seq` from player `to` (protocol 11).
+    ChatAck {
+        /// The acknowledging player's index.
+        player: u8,
+        /// The player whose line is acknowledged.
+        to: u8,
+        /// The acknowledged line's sequence number.
+        seq: u32,
+    },
+
+    /// The sender's full emulation settings (protocol 7), in the
+    /// [`NetSettings`](crate::NetSettings) canonical encoding. Exchanged
+    /// during the handshake when two peers' `Sync` digests differ: the host
+    /// pushes its settings for a joiner to adopt, and a joiner sends its own
+    /// so the host can report the difference. Bounded to
+    /// [`Self::MAX_SETTINGS`] bytes.
+    Settings {
+        /// The encoded settings.
+        data: Vec<u8>,
+    },
+
+    /// One chunk of the host's [`SessionSave`](crate::SessionSave)
+    /// (protocol 8), streamed during the handshake to a joiner whose save
+    /// digest differs. Chunked and resent like [`Self::StateChunk`], until
+    /// acknowledged with [`Self::SaveAck`].
+    SaveChunk {
+        /// This chunk's position, `0..count`.
+        index: u16,
+        /// How many chunks the save was split into.
+        count: u16,
+        /// At most [`Self::MAX_STATE_CHUNK`] bytes of the compressed save.
+        data: Vec<u8>,
+    },
+
+    /// Acknowledges [`Self::SaveChunk`] `index` (protocol 8).
+    SaveAck {
+        /// The chunk being acknowledged.
+        index: u16,
+    },
+
+    /// Another message, encrypted and authenticated under a session key
+    /// (protocol 9). Produced and opened by a
+    /// [`SecureChannel`](crate::secure::SecureChannel); `sender` and
+    /// `counter` form the AEAD nonce, so a peer never repeats the pair, and
+    /// the receiver refuses a pair it has already accepted. Bounded to
+    /// [`Self::MAX_SEALED`] bytes of ciphertext.
+    Sealed {
+        /// The sending channel's random identifier.
+        sender: [u8; 16],
+        /// The sender's per-message counter.
+        counter: u64,
+        /// The encrypted inner message followed by its authentication tag.
+        body: Vec<u8>,
+    },
 }
 
 impl NetMessage {
     /// The expected value of [`NetMessage::Sync::magic`].
     pub const SYNC_MAGIC: u32 = 0x524E_4553; // "RNES"
 
+    /// The `player` of a [`Self::JoinRequest`] / [`Self::JoinAccept`] (and
+    /// the [`Self::StateAck`]s) of a spectator joining live.
+    pub const SPECTATOR: u8 = 0xFF;
+
     // Tag bytes for the hand-rolled encoding.
     const TAG_INPUT: u8 = 0;
     const TAG_INPUT_ACK: u8 = 1;
@@ -135,6 +374,18 @@ impl NetMessage {
     const TAG_CHECKSUM: u8 = 3;
     const TAG_QUALITY: u8 = 4;
     const TAG_ROSTER: u8 = 5;
+    const TAG_STATE_CHUNK: u8 = 6;
+    const TAG_STATE_ACK: u8 = 7;
+    const TAG_RESYNC_REQUEST: u8 = 8;
+    const TAG_JOIN_REQUEST: u8 = 9;
+    const TAG_JOIN_ACCEPT: u8 = 10;
+    const TAG_SETTINGS: u8 = 11;
+    const TAG_SAVE_CHUNK: u8 = 12;
+    const TAG_SAVE_ACK: u8 = 13;
+    const TAG_SEALED: u8 = 14;
+    const TAG_INPUT_DELAY: u8 = 15;
+    const TAG_CHAT: u8 = 16;
+    const TAG_CHAT_ACK: u8 = 17;
 
     // IP-family tags inside a `Roster` entry's address encoding.
     const IP_V4: u8 = 4;
@@ -145,6 +396,21 @@ impl NetMessage {
     /// a hostile datagram cannot make [`Self::from_bytes`] allocate unbounded.
     pub const MAX_ROSTER: usize = 4;
 
+    /// The largest payload one [`Self::StateChunk`] may carry. With its 12-byte
+    /// header the datagram stays well inside a 1500-byte MTU (and the UDP
+    /// transports' receive buffer).
+    pub const MAX_STATE_CHUNK: usize = 1024;
+
+    /// The largest payload a [`Self::Settings`] may carry — far above the
+    /// encoding of [`NetSettings::MAX_GENIE_CODES`](crate::NetSettings::MAX_GENIE_CODES)
+    /// codes plus every other setting.
+    pub const MAX_SETTINGS: usize = 512;
+
+    /// The largest ciphertext a [`Self::Sealed`] may carry: the longest inner
+    /// message (a full [`Self::StateChunk`]) plus its 16-byte tag, with
+    /// headroom. The sealed datagram still fits the UDP receive buffers.
+    pub const MAX_SEALED: usize = Self::MAX_STATE_CHUNK + 128;
+
     /// Serialize to a canonical, versioned little-endian byte buffer.
     ///
     /// Provided so the Stage 2 UDP transport has a stable encoding without
@@ -159,20 +425,23 @@ impl NetMessage {
                 player,
                 frame,
                 input,
-            } => {
-                out.push(Self::TAG_INPUT);
-                out.push(player);
-                out.extend_from_slice(&frame.to_le_bytes());
-                out.push(input);
-            }
+                device,
+            } => Self::put_input(&mut out, player, frame, input, device),
             Self::InputAck { frame } => {
                 out.push(Self::TAG_INPUT_ACK);
                 out.extend_from_slice(&frame.to_le_bytes());
             }
-            Self::Sync { magic, rom_hash } => {
+            Self::Sync {
+                magic,
+                rom_hash,
+                settings,
+                save,
+            } => {
                 out.push(Self::TAG_SYNC);
                 out.extend_from_slice(&magic.to_le_bytes());
                 out.extend_from_slice(&rom_hash);
+                out.extend_from_slice(&settings.to_le_bytes());
+                out.extend_from_slice(&save.to_le_bytes());
             }
             Self::Checksum {
                 frame,
@@ -202,10 +471,133 @@ impl NetMessage {
                     Self::encode_addr(&mut out, addr);
                 }
             }
+            Self::StateChunk { .. }
+            | Self::StateAck { .. }
+            | Self::SaveChunk { .. }
+            | Self::SaveAck { .. } => self.encode_transfer(&mut out),
+            Self::Chat { .. } | Self::ChatAck { .. } => self.encode_chat(&mut out),
+            Self::ResyncRequest { player, frame } => {
+                out.push(Self::TAG_RESYNC_REQUEST);
+                out.push(player);
+                out.extend_from_slice(&frame.to_le_bytes());
+            }
+            Self::JoinRequest { player } => {
+                out.push(Self::TAG_JOIN_REQUEST);
+                out.push(player);
+            }
+            Self::JoinAccept {
+                player,
+                base_frame,
+                join_frame,
+            } => {
+                out.push(Self::TAG_JOIN_ACCEPT);
+                out.push(player);
+                out.extend_from_slice(&base_frame.to_le_bytes());
+                out.extend_from_slice(&join_frame.to_le_bytes());
+            }
+            Self::InputDelay { frame, delay } => {
+                out.push(Self::TAG_INPUT_DELAY);
+                out.extend_from_slice(&frame.to_le_bytes());
+                out.push(delay);
+            }
+            Self::Settings { ref data } => {
+                out.push(Self::TAG_SETTINGS);
+                Self::encode_payload(&mut out, data);
+            }
+            Self::Sealed {
+                sender,
+                counter,
+                ref body,
+            } => {
+                out.push(Self::TAG_SEALED);
+                out.extend_from_slice(&sender);
+                out.extend_from_slice(&counter.to_le_bytes());
+                Self::encode_payload(&mut out, body);
+            }
         }
         out
     }
 
+    /// Append the chunked state / save transfer messages (split out of
+    /// [`Self::to_bytes`] to keep it readable). Other variants append nothing.
+    fn encode_transfer(&self, out: &mut Vec<u8>) {
+        match *self {
+            Self::StateChunk {
+                kind,
+                frame,
+                index,
+                count,
+                ref data,
+            } => {
+                out.push(Self::TAG_STATE_CHUNK);
+                out.push(kind.to_u8());
+                out.extend_from_slice(&frame.to_le_bytes());
+                out.extend_from_slice(&index.to_le_bytes());
+                out.extend_from_slice(&count.to_le_bytes());
+                Self::encode_payload(out, data);
+            }
+            Self::StateAck {
+                player,
+                frame,
+                index,
+            } => {
+                out.push(Self::TAG_STATE_ACK);
+                out.push(player);
+                out.extend_from_slice(&frame.to_le_bytes());
+                out.extend_from_slice(&index.to_le_bytes());
+            }
+            Self::SaveChunk {
+                index,
+                count,
+                ref data,
+            } => {
+                out.push(Self::TAG_SAVE_CHUNK);
+                out.extend_from_slice(&index.to_le_bytes());
+                out.extend_from_slice(&count.to_le_bytes());
+                Self::encode_payload(out, data);
+            }
+            Self::SaveAck { index } => {
+                out.push(Self::TAG_SAVE_ACK);
+                out.extend_from_slice(&index.to_le_bytes());
+            }
+            _ => {}
+        }
+    }
+
+    /// Append a chat line or its ack (split out of [`Self::to_bytes`] like
+    /// [`Self::encode_transfer`]). Other variants append nothing.
+    fn encode_chat(&self, out: &mut Vec<u8>) {
+        match *self {
+            Self::Chat {
+                player,
+                seq,
+                ref text,
+            } => {
+                out.push(Self::TAG_CHAT);
+                out.push(player);
+                out.extend_from_slice(&seq.to_le_bytes());
+                Self::encode_payload(out, text.as_bytes());
+            }
+            Self::ChatAck { player, to, seq } => {
+                out.push(Self::TAG_CHAT_ACK);
+                out.push(player);
+                out.push(to);
+                out.extend_from_slice(&seq.to_le_bytes());
+            }
+            _ => {}
+        }
+    }
+
+    /// Append a `u16`-length-prefixed byte payload to `out`. Callers bound
+    /// their payloads (`MAX_STATE_CHUNK`, `MAX_SETTINGS`); clamp so an
+    /// oversized one still encodes a self-consistent (decodable-to-None)
+    /// length rather than a wrapped one.
+    fn encode_payload(out: &mut Vec<u8>, data: &[u8]) {
+        let len = data.len().min(u16::MAX as usize);
+        out.extend_from_slice(&u16::try_from(len).unwrap_or(u16::MAX).to_le_bytes());
+        out.extend_from_slice(&data[..len]);
+    }
+
     /// Append one [`SocketAddr`] to `out`: a 1-byte family tag, the raw address
     /// bytes (4 for v4, 16 for v6), then the port as a little-endian `u16`.
     fn encode_addr(out: &mut Vec<u8>, addr: SocketAddr) {
@@ -243,6 +635,150 @@ impl NetMessage {
         }
     }
 
+    /// Decode a `Roster` body: a count byte then `count` `(player, addr)`
+    /// entries, with no trailing bytes.
+    fn decode_roster(rest: &[u8]) -> Option<Self> {
+        let (&count, mut cursor) = rest.split_first()?;
+        let count = count as usize;
+        // Reject an oversized count before allocating (hostile input).
+        if count > Self::MAX_ROSTER {
+            return None;
+        }
+        let mut peers = Vec::with_capacity(count);
+        for _ in 0..count {
+            let (&player, after_player) = cursor.split_first()?;
+            let (addr, after_addr) = Self::decode_addr(after_player)?;
+            peers.push((player, addr));
+            cursor = after_addr;
+        }
+        // Trailing bytes after a well-formed roster ⇒ malformed.
+        if cursor.is_empty() {
+            Some(Self::Roster { peers })
+        } else {
+            None
+        }
+    }
+
+    /// Decode a `StateChunk` body, bounding the payload by
+    /// [`Self::MAX_STATE_CHUNK`].
+    fn decode_state_chunk(rest: &[u8]) -> Option<Self> {
+        let kind = StateTransfer::from_u8(*rest.first()?)?;
+        let frame = u32::from_le_bytes(rest.get(1..5)?.try_into().ok()?);
+        let (index, count, data) = Self::decode_chunk(rest.get(5..)?)?;
+        Some(Self::StateChunk {
+            kind,
+            frame,
+            index,
+            count,
+            data,
+        })
+    }
+
+    /// Decode a `JoinAccept` body, refusing a join before its base frame.
+    fn decode_join_accept(rest: &[u8]) -> Option<Self> {
+        let player = *rest.first()?;
+        let base_frame = u32::from_le_bytes(rest.get(1..5)?.try_into().ok()?);
+        let join_frame = u32::from_le_bytes(rest.get(5..9)?.try_into().ok()?);
+        // The newcomer can only take over from the state it is sent.
+        if join_frame < base_frame {
+            return None;
+        }
+        Some(Self::JoinAccept {
+            player,
+            base_frame,
+            join_frame,
+        })
+    }
+
+    /// Decode an `InputDelay` body, refusing a delay beyond
+    /// [`MAX_INPUT_DELAY`](crate::delay::MAX_INPUT_DELAY).
+    fn decode_input_delay(rest: &[u8]) -> Option<Self> {
+        let frame = u32::from_le_bytes(rest.get(0..4)?.try_into().ok()?);
+        let delay = *rest.get(4)?;
+        if u32::from(delay) > crate::delay::MAX_INPUT_DELAY {
+            return None;
+        }
+        Some(Self::InputDelay { frame, delay })
+    }
+
+    /// Decode a `Chat` body, refusing text that is not a valid chat line
+    /// (see [`crate::chat::validate`]).
+    fn decode_chat(rest: &[u8]) -> Option<Self> {
+        let player = *rest.first()?;
+        let seq = u32::from_le_bytes(rest.get(1..5)?.try_into().ok()?);
+        let len = usize::from(u16::from_le_bytes(rest.get(5..7)?.try_into().ok()?));
+        let data = rest.get(7..)?;
+        if len > crate::chat::MAX_CHAT_BYTES || data.len() != len {
+            return None;
+        }
+        let text = std::str::from_utf8(data).ok()?;
+        crate::chat::validate(text).ok()?;
+        Some(Self::Chat {
+            player,
+            seq,
+            text: text.to_owned(),
+        })
+    }
+
+    /// Decode a `Sealed` body: the sender id, the counter and the
+    /// length-prefixed ciphertext, bounded by [`Self::MAX_SEALED`].
+    fn decode_sealed(rest: &[u8]) -> Option<Self> {
+        let sender: [u8; 16] = rest.get(0..16)?.try_into().ok()?;
+        let counter = u64::from_le_bytes(rest.get(16..24)?.try_into().ok()?);
+        let len = usize::from(u16::from_le_bytes(rest.get(24..26)?.try_into().ok()?));
+        let body = rest.get(26..)?;
+        if len > Self::MAX_SEALED || body.len() != len {
+            return None;
+        }
+        Some(Self::Sealed {
+            sender,
+            counter,
+            body: body.to_vec(),
+        })
+    }
+
+    /// Decode the `index`, `count` and length-prefixed payload shared by
+    /// `StateChunk` and `SaveChunk`, bounding the payload by
+    /// [`Self::MAX_STATE_CHUNK`].
+    fn decode_chunk(rest: &[u8]) -> Option<(u16, u16, Vec<u8>)> {
+        let index = u16::from_le_bytes(rest.get(0..2)?.try_into().ok()?);
+        let count = u16::from_le_bytes(rest.get(2..4)?.try_into().ok()?);
+        let len = usize::from(u16::from_le_bytes(rest.get(4..6)?.try_into().ok()?));
+        if count == 0 || index >= count || len > Self::MAX_STATE_CHUNK {
+            return None;
+        }
+        // The payload must be exactly `len` bytes (no trailing data).
+        let data = rest.get(6..)?;
+        if data.len() != len {
+            return None;
+        }
+        Some((index, count, data.to_vec()))
+    }
+
+    /// Append an `Input`. The device is a lone `0` kind byte for none,
+    /// otherwise its kind, port and payload ([`DeviceInput::to_bytes`]).
+    fn put_input(out: &mut Vec<u8>, player: u8, frame: u32, input: u8, device: DeviceInput) {
+        out.push(Self::TAG_INPUT);
+        out.push(player);
+        out.extend_from_slice(&frame.to_le_bytes());
+        out.push(input);
+        if device == DeviceInput::None {
+            out.push(0);
+        } else {
+            out.extend_from_slice(&device.to_bytes());
+        }
+    }
+
+    /// Decode the device [`Self::put_input`] appends after `input`.
+    fn get_device(buf: &[u8]) -> Option<DeviceInput> {
+        if *buf.first()? == 0 {
+            return Some(DeviceInput::None);
+        }
+        let wire: &[u8; DEVICE_INPUT_WIRE_LEN] =
+            buf.get(..DEVICE_INPUT_WIRE_LEN)?.try_into().ok()?;
+        Some(DeviceInput::from_bytes(wire))
+    }
+
     /// Parse a buffer produced by [`Self::to_bytes`]. Returns `None` on a
     /// malformed / truncated / unknown-tag buffer (the UDP transport drops
     /// such datagrams rather than panicking).
@@ -254,10 +790,12 @@ impl NetMessage {
                 let player = *rest.first()?;
                 let frame = u32::from_le_bytes(rest.get(1..5)?.try_into().ok()?);
                 let input = *rest.get(5)?;
+                let device = Self::get_device(rest.get(6..)?)?;
                 Some(Self::Input {
                     player,
                     frame,
                     input,
+                    device,
                 })
             }
             Self::TAG_INPUT_ACK => {
@@ -267,7 +805,14 @@ impl NetMessage {
             Self::TAG_SYNC => {
                 let magic = u32::from_le_bytes(rest.get(0..4)?.try_into().ok()?);
                 let rom_hash: [u8; 32] = rest.get(4..36)?.try_into().ok()?;
-                Some(Self::Sync { magic, rom_hash })
+                let settings = u64::from_le_bytes(rest.get(36..44)?.try_into().ok()?);
+                let save = u64::from_le_bytes(rest.get(44..52)?.try_into().ok()?);
+                Some(Self::Sync {
+                    magic,
+                    rom_hash,
+                    settings,
+                    save,
+                })
             }
             Self::TAG_CHECKSUM => {
                 let frame = u32::from_le_bytes(rest.get(0..4)?.try_into().ok()?);
@@ -287,27 +832,53 @@ impl NetMessage {
                     frame_advantage,
                 })
             }
-            Self::TAG_ROSTER => {
-                let (&count, mut cursor) = rest.split_first()?;
-                let count = count as usize;
-                // Reject an oversized count before allocating (hostile input).
-                if count > Self::MAX_ROSTER {
+            Self::TAG_ROSTER => Self::decode_roster(rest),
+            Self::TAG_STATE_CHUNK => Self::decode_state_chunk(rest),
+            Self::TAG_STATE_ACK => {
+                let player = *rest.first()?;
+                let frame = u32::from_le_bytes(rest.get(1..5)?.try_into().ok()?);
+                let index = u16::from_le_bytes(rest.get(5..7)?.try_into().ok()?);
+                Some(Self::StateAck {
+                    player,
+                    frame,
+                    index,
+                })
+            }
+            Self::TAG_RESYNC_REQUEST => {
+                let player = *rest.first()?;
+                let frame = u32::from_le_bytes(rest.get(1..5)?.try_into().ok()?);
+                Some(Self::ResyncRequest { player, frame })
+            }
+            Self::TAG_JOIN_REQUEST => {
+                let player = *rest.first()?;
+                Some(Self::JoinRequest { player })
+            }
+            Self::TAG_JOIN_ACCEPT => Self::decode_join_accept(rest),
+            Self::TAG_SETTINGS => {
+                let len = usize::from(u16::from_le_bytes(rest.get(0..2)?.try_into().ok()?));
+                let data = rest.get(2..)?;
+                if len > Self::MAX_SETTINGS || data.len() != len {
                     return None;
                 }
-                let mut peers = Vec::with_capacity(count);
-                for _ in 0..count {
-                    let (&player, after_player) = cursor.split_first()?;
-                    let (addr, after_addr) = Self::decode_addr(after_player)?;
-                    peers.push((player, addr));
-                    cursor = after_addr;
-                }
-                // Trailing bytes after a well-formed roster ⇒ malformed.
-                if cursor.is_empty() {
-                    Some(Self::Roster { peers })
-                } else {
-                    None
-                }
+                Some(Self::Settings {
+                    data: data.to_vec(),
+                })
             }
+            Self::TAG_SAVE_CHUNK => {
+                let (index, count, data) = Self::decode_chunk(rest)?;
+                Some(Self::SaveChunk { index, count, data })
+            }
+            Self::TAG_SAVE_ACK => Some(Self::SaveAck {
+                index: u16::from_le_bytes(rest.get(0..2)?.try_into().ok()?),
+            }),
+            Self::TAG_SEALED => Self::decode_sealed(rest),
+            Self::TAG_INPUT_DELAY => Self::decode_input_delay(rest),
+            Self::TAG_CHAT => Self::decode_chat(rest),
+            Self::TAG_CHAT_ACK => Some(Self::ChatAck {
+                player: *rest.first()?,
+                to: *rest.get(1)?,
+                seq: u32::from_le_bytes(rest.get(2..6)?.try_into().ok()?),
+            }),
             _ => None,
         }
     }
@@ -346,11 +917,25 @@ mod tests {
             player: 3,
             frame: 0x1234_5678,
             input: 0xAB,
+            device: DeviceInput::None,
+        });
+        roundtrip(&NetMessage::Input {
+            player: 1,
+            frame: 77,
+            input: 0x01,
+            device: DeviceInput::Zapper {
+                port: 1,
+                x: 128,
+                y: 200,
+                trigger: true,
+            },
         });
         roundtrip(&NetMessage::InputAck { frame: 99 });
         roundtrip(&NetMessage::Sync {
             magic: NetMessage::SYNC_MAGIC,
             rom_hash: [7u8; 32],
+            settings: 0x0F0E_0D0C_0B0A_0908,
+            save: 0x1716_1514_1312_1110,
         });
         roundtrip(&NetMessage::Checksum {
             frame: 42,
@@ -361,6 +946,154 @@ mod tests {
             ping_ms: 33,
             frame_advantage: -4,
         });
+        roundtrip(&NetMessage::StateChunk {
+            kind: StateTransfer::Join,
+            frame: 300,
+            index: 2,
+            count: 3,
+            data: vec![0x5A; NetMessage::MAX_STATE_CHUNK],
+        });
+        roundtrip(&NetMessage::StateAck {
+            player: 2,
+            frame: 300,
+            index: 1,
+        });
+        roundtrip(&NetMessage::ResyncRequest {
+            player: 1,
+            frame: 270,
+        });
+        roundtrip(&NetMessage::JoinRequest {
+            player: NetMessage::SPECTATOR,
+        });
+        roundtrip(&NetMessage::JoinAccept {
+            player: 2,
+            base_frame: 1200,
+            join_frame: 1262,
+        });
+        roundtrip(&NetMessage::InputDelay {
+            frame: 4321,
+            delay: 5,
+        });
+        roundtrip(&NetMessage::Chat {
+            player: 1,
+            seq: 0x0A0B_0C0D,
+            text: "gg — rematch?".into(),
+        });
+        roundtrip(&NetMessage::ChatAck {
+            player: 3,
+            to: 1,
+            seq: 77,
+        });
+        roundtrip(&NetMessage::Settings {
+            data: vec![1, 0, 1, 0, 2, 0xFF],
+        });
+        roundtrip(&NetMessage::SaveChunk {
+            index: 4,
+            count: 9,
+            data: vec![0xA5; NetMessage::MAX_STATE_CHUNK],
+        });
+        roundtrip(&NetMessage::SaveAck { index: 8 });
+        roundtrip(&NetMessage::Sealed {
+            sender: [0x3C; 16],
+            counter: 0x0102_0304_0506_0708,
+            body: vec![0xEE; NetMessage::MAX_SEALED],
+        });
+    }
+
+    #[test]
+    fn settings_payload_is_bounded() {
+        let buf = NetMessage::Settings {
+            data: vec![0; NetMessage::MAX_SETTINGS + 1],
+        }
+        .to_bytes();
+        assert!(NetMessage::from_bytes(&buf).is_none());
+        let mut short = NetMessage::Settings { data: vec![1, 2] }.to_bytes();
+        short.pop();
+        assert!(NetMessage::from_bytes(&short).is_none());
+    }
+
+    #[test]
+    fn join_accept_rejects_a_join_before_its_base() {
+        let buf = NetMessage::JoinAccept {
+            player: 1,
+            base_frame: 500,
+            join_frame: 499,
+        }
+        .to_bytes();
+        assert!(NetMessage::from_bytes(&buf).is_none());
+        assert!(NetMessage::from_bytes(&buf[..6]).is_none());
+    }
+
+    #[test]
+    fn input_delay_is_bounded() {
+        let mut buf = NetMessage::InputDelay {
+            frame: 90,
+            delay: 0,
+        }
+        .to_bytes();
+        buf[5] = u8::try_from(crate::delay::MAX_INPUT_DELAY + 1).unwrap();
+        assert!(NetMessage::from_bytes(&buf).is_none());
+        assert!(NetMessage::from_bytes(&buf[..5]).is_none());
+    }
+
+    #[test]
+    fn chat_text_is_bounded_and_validated() {
+        let chat = |text: &str| {
+            NetMessage::Chat {
+                player: 0,
+                seq: 1,
+                text: text.into(),
+            }
+            .to_bytes()
+        };
+        let longest = "x".repeat(crate::chat::MAX_CHAT_BYTES);
+        assert!(NetMessage::from_bytes(&chat(&longest)).is_some());
+        assert!(NetMessage::from_bytes(&chat(&format!("{longest}x"))).is_none());
+        assert!(NetMessage::from_bytes(&chat("")).is_none());
+        assert!(NetMessage::from_bytes(&chat("line\nbreak")).is_none());
+        // Invalid UTF-8 of the right length is refused too.
+        let mut buf = chat("ab");
+        let n = buf.len();
+        buf[n - 1] = 0xFF;
+        assert!(NetMessage::from_bytes(&buf).is_none());
+        assert!(NetMessage::from_bytes(&buf[..n - 1]).is_none());
+    }
+
+    #[test]
+    fn state_chunk_rejects_bad_index_and_length() {
+        let chunk = |index: u16, count: u16, len: usize| {
+            NetMessage::StateChunk {
+                kind: StateTransfer::Recovery,
+                frame: 1,
+                index,
+                count,
+                data: vec![0; len],
+            }
+            .to_bytes()
+        };
+        // Index outside the transfer, or an empty transfer.
+        assert!(NetMessage::from_bytes(&chunk(3, 3, 4)).is_none());
+        assert!(NetMessage::from_bytes(&chunk(0, 0, 4)).is_none());
+        // A payload over the cap.
+        assert!(NetMessage::from_bytes(&chunk(0, 1, NetMessage::MAX_STATE_CHUNK + 1)).is_none());
+        // Truncated and trailing payloads.
+        let mut buf = chunk(0, 1, 8);
+        buf.pop();
+        assert!(NetMessage::from_bytes(&buf).is_none());
+        let mut buf = chunk(0, 1, 8);
+        buf.push(0);
+        assert!(NetMessage::from_bytes(&buf).is_none());
+        // An unknown transfer kind.
+        let mut buf = chunk(0, 1, 8);
+        buf[1] = 2;
+        assert!(NetMessage::from_bytes(&buf).is_none());
+        // A save chunk shares the same bounds.
+        let save = NetMessage::SaveChunk {
+            index: 2,
+            count: 2,
+            data: vec![0; 4],
+        };
+        assert!(NetMessage::from_bytes(&save.to_bytes()).is_none());
     }
 
     #[test]
@@ -72,6 +72,9 @@ pub struct NatConfig {
     pub turn: Option<TurnConfig>,
     /// The signaling relay URL (e.g. `wss://host` or `ws://host:9000`).
     pub signaling_url: String,
+    /// The room password: the host locks its room with it, a joiner must
+    /// match it. Empty for an open room.
+    pub room_password: String,
 }
 
 /// How long to attempt UDP hole punching before falling back to TURN (or
@@ -150,6 +153,8 @@ impl NatConnect {
             room: room.clone(),
             rom_hash: hex(&rom_hash),
             max_players: num_players,
+            game: String::new(),
+            password: cfg.room_password.clone(),
         });
         Ok((Self::new_inner(socket, rom_hash, cfg, signaling, rng), room))
     }
@@ -175,6 +180,8 @@ impl NatConnect {
             // The relay's max_players for a joiner is ignored; default 2.
             rom_hash: hex(&rom_hash),
             max_players: 2,
+            game: String::new(),
+            password: cfg.room_password.clone(),
         });
         Ok(Self::new_inner(socket, rom_hash, cfg, signaling, rng))
     }
@@ -429,9 +436,12 @@ impl NatConnect {
             .last_punch_sent
             .is_none_or(|t| now.saturating_duration_since(t) >= PUNCH_RESEND);
         if due {
+            // Settings are negotiated by the connection built afterwards.
             let pkt = NetMessage::Sync {
                 magic: NetMessage::SYNC_MAGIC,
                 rom_hash: self.rom_hash,
+                settings: 0,
+                save: 0,
             }
             .to_bytes();
             let _ = socket.send_to(&pkt, peer);
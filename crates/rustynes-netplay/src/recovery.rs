@@ -0,0 +1,351 @@
+//! Desync recovery: the chunked, acknowledged state transfer.
+//!
+//! When [`SessionConfig::desync_recovery`](crate::SessionConfig) is on, a
+//! confirmed desync no longer ends the session. The host (player 0) is the
+//! authority: it LZ4-compresses its canonical confirmed checkpoint, splits it
+//! into [`NetMessage::MAX_STATE_CHUNK`]-byte [`NetMessage::StateChunk`]s and
+//! resends every chunk until each peer has acknowledged it with a
+//! [`NetMessage::StateAck`]. A peer reassembles the chunks, decompresses the
+//! snapshot, and the session restores it at its frame and replays forward.
+//!
+//! This module holds only the transfer bookkeeping — the two halves below are
+//! pure data structures with no emulator or transport access, so the
+//! chunking, ack tracking and hostile-input bounds are unit-testable on their
+//! own. The session (`session.rs`) decides *when* to start a transfer and
+//! *how* to apply the result.
+
+use crate::message::{NetMessage, StateTransfer};
+
+/// Upper bound on the chunk count of a transfer a peer will reassemble.
+///
+/// At [`NetMessage::MAX_STATE_CHUNK`] bytes each this is 4 MiB of compressed
+/// state — far above a real core snapshot — and it bounds the reassembly
+/// buffer a malformed or hostile `StateChunk` can make us allocate.
+pub const MAX_STATE_CHUNKS: u16 = 4096;
+
+/// Upper bound on a decompressed recovery snapshot. Checked against the
+/// LZ4 size prefix *before* decompressing, so a forged prefix cannot make a
+/// peer allocate an arbitrary buffer.
+pub const MAX_STATE_BYTES: usize = 16 << 20;
+
+/// Why a reassembled transfer could not be turned back into a snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum RecoveryError {
+    /// The LZ4 size prefix exceeds [`MAX_STATE_BYTES`].
+    #[error("recovery state too large")]
+    TooLarge,
+    /// The reassembled payload is not a valid LZ4 block.
+    #[error("recovery state is corrupt")]
+    Corrupt,
+}
+
+/// The host half: one authoritative snapshot being streamed to every peer.
+#[derive(Clone, Debug)]
+pub struct OutgoingState {
+    /// What the transfer is for, stamped on every chunk.
+    kind: StateTransfer,
+    /// The frame the snapshot enters (the host's checkpoint frame).
+    frame: u32,
+    /// The compressed snapshot, split into wire-sized chunks.
+    chunks: Vec<Vec<u8>>,
+    /// Per chunk, a bitmask of the players still owed an ack for it.
+    pending: Vec<u8>,
+    /// Round-robin cursor so a capped resend burst cycles through every
+    /// outstanding chunk instead of starving the tail.
+    cursor: usize,
+}
+
+impl OutgoingState {
+    /// The `peers` bit a [`NetMessage::SPECTATOR`] ack clears. Player indices
+    /// stop at 3, so the top bit is free.
+    pub const SPECTATOR_BIT: u8 = 1 << 7;
+
+    /// Compress `snapshot` (the state entering `frame`) and split it for
+    /// `peers` — a bitmask of the player indices that must acknowledge every
+    /// chunk ([`Self::SPECTATOR_BIT`] for a spectator joining live). The
+    /// chunks say [`StateTransfer::Recovery`] unless [`Self::for_join`].
+    #[must_use]
+    pub fn new(frame: u32, snapshot: &[u8], peers: u8) -> Self {
+        let packed = lz4_flex::block::compress_prepend_size(snapshot);
+        let chunks: Vec<Vec<u8>> = packed
+            .chunks(NetMessage::MAX_STATE_CHUNK)
+            .map(<[u8]>::to_vec)
+            .collect();
+        let pending = vec![peers; chunks.len()];
+        Self {
+            kind: StateTransfer::Recovery,
+            frame,
+            chunks,
+            pending,
+            cursor: 0,
+        }
+    }
+
+    /// Mark the transfer as a late joiner's starting state
+    /// ([`StateTransfer::Join`]), which the peers already in the match
+    /// ignore.
+    #[must_use]
+    pub const fn for_join(mut self) -> Self {
+        self.kind = StateTransfer::Join;
+        self
+    }
+
+    /// What the transfer is for.
+    #[must_use]
+    pub const fn kind(&self) -> StateTransfer {
+        self.kind
+    }
+
+    /// The frame the snapshot enters.
+    #[must_use]
+    pub const fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// The number of chunks the snapshot was split into.
+    #[must_use]
+    pub const fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Fold in a peer's [`NetMessage::StateAck`]. Acks for another transfer,
+    /// an out-of-range chunk or a player index past the mask are ignored.
+    pub fn ack(&mut self, player: u8, frame: u32, index: u16) {
+        let bit = match player {
+            NetMessage::SPECTATOR => Self::SPECTATOR_BIT,
+            0..7 => 1 << player,
+            _ => return,
+        };
+        if frame != self.frame {
+            return;
+        }
+        if let Some(mask) = self.pending.get_mut(usize::from(index)) {
+            *mask &= !bit;
+        }
+    }
+
+    /// `true` once every peer has acknowledged every chunk.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.pending.iter().all(|&m| m == 0)
+    }
+
+    /// Up to `limit` still-unacknowledged chunks as wire messages, continuing
+    /// round-robin from where the previous call stopped.
+    pub fn next_unacked(&mut self, limit: usize) -> Vec<NetMessage> {
+        let n = self.chunks.len();
+        let count = u16::try_from(n).unwrap_or(u16::MAX);
+        let start = self.cursor;
+        let mut out = Vec::new();
+        for step in 0..n {
+            if out.len() == limit {
+                break;
+            }
+            let i = (start + step) % n;
+            if self.pending[i] != 0 {
+                out.push(NetMessage::StateChunk {
+                    kind: self.kind,
+                    frame: self.frame,
+                    index: u16::try_from(i).unwrap_or(u16::MAX),
+                    count,
+                    data: self.chunks[i].clone(),
+                });
+                self.cursor = (i + 1) % n;
+            }
+        }
+        out
+    }
+}
+
+/// The peer half: the chunks of one transfer, reassembled as they arrive in
+/// any order (duplicates are harmless).
+#[derive(Clone, Debug)]
+pub struct IncomingState {
+    /// The frame the snapshot enters.
+    frame: u32,
+    /// Every chunk slot; `None` until that chunk arrives.
+    chunks: Vec<Option<Vec<u8>>>,
+    /// How many slots are filled.
+    received: usize,
+}
+
+impl IncomingState {
+    /// Start reassembling a `count`-chunk transfer for `frame`. `None` if
+    /// `count` is zero or above [`MAX_STATE_CHUNKS`].
+    #[must_use]
+    pub fn new(frame: u32, count: u16) -> Option<Self> {
+        if count == 0 || count > MAX_STATE_CHUNKS {
+            return None;
+        }
+        Some(Self {
+            frame,
+            chunks: vec![None; usize::from(count)],
+            received: 0,
+        })
+    }
+
+    /// The frame the snapshot enters.
+    #[must_use]
+    pub const fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// The number of chunks the transfer consists of.
+    #[must_use]
+    pub const fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Store one chunk. A chunk whose `count` disagrees with the transfer's is
+    /// ignored. Returns `true` once every chunk has arrived.
+    pub fn accept(&mut self, index: u16, count: u16, data: Vec<u8>) -> bool {
+        if usize::from(count) == self.chunks.len()
+            && let Some(slot) = self.chunks.get_mut(usize::from(index))
+            && slot.is_none()
+        {
+            *slot = Some(data);
+            self.received += 1;
+        }
+        self.is_complete()
+    }
+
+    /// `true` once every chunk has arrived.
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.received == self.chunks.len()
+    }
+
+    /// Concatenate and decompress the complete transfer back into the
+    /// snapshot.
+    ///
+    /// # Errors
+    ///
+    /// [`RecoveryError::TooLarge`] if the size prefix exceeds
+    /// [`MAX_STATE_BYTES`]; [`RecoveryError::Corrupt`] if the transfer is
+    /// incomplete or not a valid LZ4 block.
+    pub fn finish(self) -> Result<Vec<u8>, RecoveryError> {
+        let mut packed = Vec::new();
+        for chunk in self.chunks {
+            packed.extend_from_slice(&chunk.ok_or(RecoveryError::Corrupt)?);
+        }
+        let prefix: [u8; 4] = packed
+            .get(..4)
+            .and_then(|p| p.try_into().ok())
+            .ok_or(RecoveryError::Corrupt)?;
+        if u32::from_le_bytes(prefix) as usize > MAX_STATE_BYTES {
+            return Err(RecoveryError::TooLarge);
+        }
+        lz4_flex::block::decompress_size_prepended(&packed).map_err(|_| RecoveryError::Corrupt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reassemble(out: &mut OutgoingState) -> Vec<u8> {
+        let msgs = out.next_unacked(usize::MAX);
+        let NetMessage::StateChunk { count, .. } = msgs[0] else {
+            panic!("expected a StateChunk");
+        };
+        let mut inc = IncomingState::new(out.frame(), count).expect("valid count");
+        // Deliver in reverse to exercise out-of-order reassembly.
+        for msg in msgs.into_iter().rev() {
+            if let NetMessage::StateChunk {
+                index, count, data, ..
+            } = msg
+            {
+                inc.accept(index, count, data);
+            }
+        }
+        assert!(inc.is_complete());
+        inc.finish().expect("valid transfer")
+    }
+
+    #[test]
+    fn roundtrips_a_multi_chunk_snapshot() {
+        // Pseudo-random bytes barely compress, forcing several chunks.
+        let mut x = 0x1234_5678_u32;
+        let snapshot: Vec<u8> = (0..10_000)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                x.to_le_bytes()[0]
+            })
+            .collect();
+        let mut out = OutgoingState::new(90, &snapshot, 0b10);
+        assert!(out.chunk_count() > 1);
+        assert_eq!(reassemble(&mut out), snapshot);
+    }
+
+    #[test]
+    fn completes_only_when_every_peer_acked_every_chunk() {
+        let mut out = OutgoingState::new(30, &vec![7u8; 5000], 0b1110);
+        let n = u16::try_from(out.chunk_count()).unwrap();
+        for i in 0..n {
+            out.ack(1, 30, i);
+            out.ack(2, 30, i);
+        }
+        assert!(!out.is_complete(), "player 3 still owes acks");
+        out.ack(3, 31, 0); // wrong transfer: ignored
+        assert!(!out.is_complete());
+        for i in 0..n {
+            out.ack(3, 30, i);
+        }
+        assert!(out.is_complete());
+        assert!(out.next_unacked(16).is_empty());
+    }
+
+    #[test]
+    fn spectator_acks_clear_the_spectator_bit() {
+        let mut out = OutgoingState::new(12, &[1, 2, 3], OutgoingState::SPECTATOR_BIT);
+        out.ack(7, 12, 0); // a player index, not the spectator marker
+        assert!(!out.is_complete());
+        out.ack(NetMessage::SPECTATOR, 12, 0);
+        assert!(out.is_complete());
+    }
+
+    #[test]
+    fn resend_burst_is_capped_and_round_robin() {
+        let mut x = 1u32;
+        let snapshot: Vec<u8> = (0..8000)
+            .map(|_| {
+                x = x.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                x.to_le_bytes()[2]
+            })
+            .collect();
+        let mut out = OutgoingState::new(0, &snapshot, 0b10);
+        assert!(out.chunk_count() > 2);
+        let first = out.next_unacked(2);
+        let second = out.next_unacked(2);
+        assert_eq!(first.len(), 2);
+        let index = |m: &NetMessage| match m {
+            NetMessage::StateChunk { index, .. } => *index,
+            _ => unreachable!(),
+        };
+        assert_eq!(index(&second[0]), 2, "continues after the previous burst");
+    }
+
+    #[test]
+    fn rejects_hostile_transfers() {
+        assert!(IncomingState::new(0, 0).is_none());
+        assert!(IncomingState::new(0, MAX_STATE_CHUNKS + 1).is_none());
+
+        // A forged size prefix past the cap is refused before decompressing.
+        let mut inc = IncomingState::new(0, 1).unwrap();
+        let mut forged = u32::try_from(MAX_STATE_BYTES + 1)
+            .unwrap()
+            .to_le_bytes()
+            .to_vec();
+        forged.extend_from_slice(&[0; 8]);
+        assert!(inc.accept(0, 1, forged));
+        assert_eq!(inc.finish(), Err(RecoveryError::TooLarge));
+
+        // A chunk whose count disagrees with the transfer is ignored.
+        let mut inc = IncomingState::new(0, 2).unwrap();
+        assert!(!inc.accept(0, 3, vec![1]));
+        assert!(!inc.is_complete());
+    }
+}
@@ -0,0 +1,401 @@
+//! Battery-save and FDS-disk synchronization for the netplay handshake.
+//!
+//! The cold boot that starts a match clears cartridge RAM, battery-backed or
+//! not, but keeps a Famicom Disk System disk the game has written to. Two
+//! peers whose written disks differ therefore diverge from the first frame
+//! that reads them, even with identical ROMs and settings; and a battery save
+//! is lost for the session unless every peer loads the same one back after
+//! the boot.
+//!
+//! [`SessionSave`] captures both from a live [`Nes`]. Its
+//! [`digest`](SessionSave::digest) rides in every
+//! [`NetMessage::Sync`](crate::NetMessage::Sync) next to the settings digest.
+//! When the digests differ, the host streams its copy to the joiner as
+//! acknowledged [`NetMessage::SaveChunk`]s (the chunking of
+//! [`crate::recovery`]). A joiner that chose "use host's save" adopts the
+//! copy once it arrives and matches the host's digest; any other joiner
+//! refuses. Every caller loads the session's save (its own, or the adopted
+//! copy) right after the cold boot and leaves the peer's save files alone
+//! (see [`SessionSave::apply`]).
+
+use rustynes_core::Nes;
+
+use crate::message::fnv1a64;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{
+    message::NetMessage,
+    recovery::{IncomingState, OutgoingState},
+};
+
+/// Version byte leading the canonical encoding.
+const ENCODING_VERSION: u8 = 1;
+
+/// The ack bit of the joiner (player 1) in the host's [`OutgoingState`].
+#[cfg(not(target_arch = "wasm32"))]
+const JOINER_BIT: u8 = 1 << 1;
+
+/// The transfer id the save rides under in the recovery bookkeeping. Save
+/// chunks carry no frame on the wire, so any fixed value works.
+#[cfg(not(target_arch = "wasm32"))]
+const TRANSFER_FRAME: u32 = 0;
+
+/// The persistent media of one peer: cartridge save RAM and the FDS disk.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SessionSave {
+    /// The cartridge's battery-backed save RAM. Empty for a cartridge
+    /// without a battery: its RAM is scratch space the cold boot clears.
+    sram: Vec<u8>,
+    /// The FDS disk in the lossless QD layout, written sectors and stored
+    /// CRCs included. Empty for cartridge builds.
+    disk: Vec<u8>,
+}
+
+impl SessionSave {
+    /// A save from raw save RAM and a QD-layout disk image (empty for none).
+    #[must_use]
+    pub const fn new(sram: Vec<u8>, disk: Vec<u8>) -> Self {
+        Self { sram, disk }
+    }
+
+    /// Capture the battery-backed save RAM and disk image of `nes`.
+    #[must_use]
+    pub fn capture(nes: &Nes) -> Self {
+        let sram = if nes.mapper_info().has_battery {
+            nes.sram().to_vec()
+        } else {
+            Vec::new()
+        };
+        Self {
+            sram,
+            disk: nes.disk_image_qd_bytes(),
+        }
+    }
+
+    /// `true` if the game has neither save RAM nor a disk.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.sram.is_empty() && self.disk.is_empty()
+    }
+
+    /// The captured save RAM.
+    #[must_use]
+    pub fn sram(&self) -> &[u8] {
+        &self.sram
+    }
+
+    /// The captured disk image (QD layout), empty for cartridge builds.
+    #[must_use]
+    pub fn disk(&self) -> &[u8] {
+        &self.disk
+    }
+
+    /// Load this save into `nes`: copy the save RAM and swap in the disk.
+    /// Only memory changes — nothing is written to the peer's save files.
+    /// Call it after [`Nes::power_cycle`], which clears the save RAM.
+    /// Returns `false` if the save does not fit this game (a different save
+    /// RAM size, or a disk the core cannot load); `nes` may then be partly
+    /// updated.
+    pub fn apply(&self, nes: &mut Nes) -> bool {
+        if !self.sram.is_empty() {
+            let sram = nes.sram_mut();
+            if sram.len() != self.sram.len() {
+                return false;
+            }
+            sram.copy_from_slice(&self.sram);
+        }
+        self.disk.is_empty() || nes.replace_disk_image(&self.disk)
+    }
+
+    /// The canonical digest carried in
+    /// [`NetMessage::Sync`](crate::NetMessage::Sync). Never `0`, which on the
+    /// wire means "no save announced".
+    #[must_use]
+    pub fn digest(&self) -> u64 {
+        fnv1a64(&self.to_bytes()).max(1)
+    }
+
+    /// The canonical encoding the host streams to a joiner.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(9 + self.sram.len() + self.disk.len());
+        out.push(ENCODING_VERSION);
+        for part in [&self.sram, &self.disk] {
+            out.extend_from_slice(&u32::try_from(part.len()).unwrap_or(u32::MAX).to_le_bytes());
+            out.extend_from_slice(part);
+        }
+        out
+    }
+
+    /// Decode [`Self::to_bytes`]. `None` on a truncated, trailing or
+    /// unknown-version encoding.
+    #[must_use]
+    pub fn from_bytes(buf: &[u8]) -> Option<Self> {
+        let (&version, mut rest) = buf.split_first()?;
+        if version != ENCODING_VERSION {
+            return None;
+        }
+        let mut parts = [Vec::new(), Vec::new()];
+        for part in &mut parts {
+            let (len, tail) = rest.split_at_checked(4)?;
+            let len = u32::from_le_bytes(len.try_into().ok()?) as usize;
+            let (bytes, tail) = tail.split_at_checked(len)?;
+            *part = bytes.to_vec();
+            rest = tail;
+        }
+        if !rest.is_empty() {
+            return None;
+        }
+        let [sram, disk] = parts;
+        Some(Self { sram, disk })
+    }
+}
+
+/// What a peer's announced save digest means for the handshake.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SaveVerdict {
+    /// The saves match (or one side announced none): carry on.
+    Match,
+    /// They differ and the transfer is under way: do not sync yet.
+    Wait,
+    /// They differ and this joiner will not take the host's save.
+    Refuse,
+}
+
+/// One side of the handshake's save reconciliation. Pure bookkeeping: the
+/// connection feeds it the peer's digests and save messages and sends what
+/// it returns. Native-only, like the connection that drives it.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug)]
+pub(crate) struct SaveSync {
+    /// Our own save and its digest.
+    ours: SessionSave,
+    digest: u64,
+    /// The host side streams its save; a joiner may receive one.
+    host: bool,
+    /// A joiner takes the host's save instead of refusing.
+    adopt: bool,
+    /// Host: the transfer to the joiner, once its digest differed.
+    outgoing: Option<OutgoingState>,
+    /// Joiner: the host's digest the reassembled copy must match.
+    expected: u64,
+    /// Joiner: the chunks received so far.
+    incoming: Option<IncomingState>,
+    /// Joiner: the host's save, once adopted.
+    adopted: Option<SessionSave>,
+    /// The saves differed at some point and were not reconciled.
+    mismatched: bool,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SaveSync {
+    /// Chunks sent per [`pump`](crate::NetplayConnection::pump).
+    const CHUNKS_PER_PUMP: usize = 16;
+
+    pub(crate) fn new(ours: SessionSave, host: bool, adopt: bool) -> Self {
+        let digest = ours.digest();
+        Self {
+            ours,
+            digest,
+            host,
+            adopt,
+            outgoing: None,
+            expected: 0,
+            incoming: None,
+            adopted: None,
+            mismatched: false,
+        }
+    }
+
+    pub(crate) const fn set_adopt(&mut self, adopt: bool) {
+        self.adopt = adopt;
+    }
+
+    /// The digest our `Sync`s carry: the host's once adopted.
+    pub(crate) const fn digest(&self) -> u64 {
+        if self.adopted.is_some() {
+            self.expected
+        } else {
+            self.digest
+        }
+    }
+
+    pub(crate) const fn adopted(&self) -> Option<&SessionSave> {
+        self.adopted.as_ref()
+    }
+
+    /// The saves differed and are not (yet) reconciled.
+    pub(crate) const fn mismatched(&self) -> bool {
+        self.mismatched
+    }
+
+    /// Joiner: the host's digest is known and its chunks are being taken
+    /// (or were, and resends should still be acknowledged).
+    pub(crate) const fn receiving(&self) -> bool {
+        !self.host && self.adopt && self.expected != 0
+    }
+
+    /// Fold in the digest from a peer's `Sync`.
+    pub(crate) fn on_peer_digest(&mut self, peer: u64) -> SaveVerdict {
+        let ours = self.digest();
+        if peer == 0 || peer == ours {
+            if self.adopted.is_some() || self.outgoing.is_some() {
+                self.mismatched = false;
+            }
+            return SaveVerdict::Match;
+        }
+        self.mismatched = true;
+        if self.host {
+            if self.outgoing.is_none() {
+                self.outgoing = Some(OutgoingState::new(
+                    TRANSFER_FRAME,
+                    &self.ours.to_bytes(),
+                    JOINER_BIT,
+                ));
+            }
+            return SaveVerdict::Wait;
+        }
+        if !self.adopt {
+            return SaveVerdict::Refuse;
+        }
+        if self.expected != peer {
+            self.expected = peer;
+            self.incoming = None;
+        }
+        SaveVerdict::Wait
+    }
+
+    /// Host: the next burst of unacknowledged chunks.
+    pub(crate) fn next_chunks(&mut self) -> Vec<NetMessage> {
+        let Some(out) = self.outgoing.as_mut() else {
+            return Vec::new();
+        };
+        out.next_unacked(Self::CHUNKS_PER_PUMP)
+            .into_iter()
+            .filter_map(|msg| match msg {
+                NetMessage::StateChunk {
+                    index, count, data, ..
+                } => Some(NetMessage::SaveChunk { index, count, data }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Host: the joiner acknowledged chunk `index`.
+    pub(crate) fn on_ack(&mut self, index: u16) {
+        if let Some(out) = self.outgoing.as_mut() {
+            out.ack(1, TRANSFER_FRAME, index);
+        }
+    }
+
+    /// Joiner: store one chunk of the host's save. Returns `true` once the
+    /// complete copy arrived, matched the host's digest, and was adopted.
+    pub(crate) fn on_chunk(&mut self, index: u16, count: u16, data: Vec<u8>) -> bool {
+        if self.host || !self.adopt || self.expected == 0 || self.adopted.is_some() {
+            return false;
+        }
+        if self
+            .incoming
+            .as_ref()
+            .is_none_or(|inc| inc.chunk_count() != usize::from(count))
+        {
+            self.incoming = IncomingState::new(TRANSFER_FRAME, count);
+        }
+        let Some(incoming) = self.incoming.as_mut() else {
+            return false;
+        };
+        if !incoming.accept(index, count, data) {
+            return false;
+        }
+        let bytes = self.incoming.take().and_then(|inc| inc.finish().ok());
+        match bytes.as_deref().and_then(SessionSave::from_bytes) {
+            Some(save) if save.digest() == self.expected => {
+                self.adopted = Some(save);
+                self.mismatched = false;
+                true
+            }
+            // A corrupt copy: start over from the host's resends.
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn save(sram: &[u8], disk: &[u8]) -> SessionSave {
+        SessionSave::new(sram.to_vec(), disk.to_vec())
+    }
+
+    #[test]
+    fn encoding_roundtrips_and_rejects_malformed() {
+        let s = save(&[1, 2, 3], &[9; 3000]);
+        let bytes = s.to_bytes();
+        assert_eq!(SessionSave::from_bytes(&bytes), Some(s.clone()));
+        assert!(SessionSave::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+        let mut trailing = bytes;
+        trailing.push(0);
+        assert!(SessionSave::from_bytes(&trailing).is_none());
+        assert_ne!(s.digest(), save(&[1, 2, 4], &[9; 3000]).digest());
+        assert_ne!(SessionSave::default().digest(), 0);
+    }
+
+    #[test]
+    fn joiner_adopts_the_hosts_save_through_the_transfer() {
+        let host_save = save(&[0xAA; 8192], &vec![0x5C; 65536]);
+        let mut host = SaveSync::new(host_save.clone(), true, false);
+        let mut join = SaveSync::new(save(&[0; 8192], &[]), false, true);
+
+        assert_eq!(host.on_peer_digest(join.digest()), SaveVerdict::Wait);
+        assert_eq!(join.on_peer_digest(host.digest()), SaveVerdict::Wait);
+        let mut adopted = false;
+        for _ in 0..64 {
+            // Drop every third chunk to exercise the resends.
+            for (i, msg) in host.next_chunks().into_iter().enumerate() {
+                let NetMessage::SaveChunk { index, count, data } = msg else {
+                    panic!("expected a SaveChunk");
+                };
+                if i % 3 != 2 {
+                    host.on_ack(index);
+                    adopted |= join.on_chunk(index, count, data);
+                }
+            }
+            if adopted {
+                break;
+            }
+        }
+        assert!(adopted, "the copy arrived");
+        assert_eq!(join.adopted(), Some(&host_save));
+        assert_eq!(join.digest(), host.digest());
+        assert_eq!(host.on_peer_digest(join.digest()), SaveVerdict::Match);
+        assert!(!host.mismatched() && !join.mismatched());
+    }
+
+    #[test]
+    fn refusing_joiner_and_corrupt_copies() {
+        let host = SaveSync::new(save(&[1], &[]), true, false);
+        let mut refuse = SaveSync::new(save(&[2], &[]), false, false);
+        assert_eq!(refuse.on_peer_digest(host.digest()), SaveVerdict::Refuse);
+        assert!(refuse.mismatched());
+        assert_eq!(
+            refuse.on_peer_digest(0),
+            SaveVerdict::Match,
+            "0 is never refused"
+        );
+
+        // A copy that does not hash to the announced digest is not adopted.
+        let mut join = SaveSync::new(save(&[2], &[]), false, true);
+        let _ = join.on_peer_digest(host.digest());
+        let mut forged = OutgoingState::new(TRANSFER_FRAME, &save(&[3], &[]).to_bytes(), 1);
+        for msg in forged.next_unacked(usize::MAX) {
+            if let NetMessage::StateChunk {
+                index, count, data, ..
+            } = msg
+            {
+                assert!(!join.on_chunk(index, count, data));
+            }
+        }
+        assert!(join.adopted().is_none());
+    }
+}
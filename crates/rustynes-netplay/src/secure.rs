@@ -0,0 +1,744 @@
+//! Authenticated, encrypted netplay traffic.
+//!
+//! The plain transports carry [`NetMessage`] bytes as-is, so anyone who can
+//! reach a peer's port can inject inputs or spoof checksums. A
+//! [`SecureChannel`] closes that: every outgoing message is sealed into a
+//! [`NetMessage::Sealed`] envelope under a key both peers derive from a shared
+//! passphrase or room code ([`SessionKey`]), and every inbound datagram that
+//! does not open under that key, that was already accepted once, or that
+//! belongs to another session, is dropped before anything parses its
+//! contents.
+//!
+//! - **Key.** HKDF-SHA256 over the passphrase, salted with a context both
+//!   peers agree on (the frontend uses the ROM hash).
+//! - **Sealing.** XChaCha20-Poly1305. The 24-byte nonce is the channel's random
+//!   16-byte sender id followed by its 8-byte message counter, so peers
+//!   sharing a key never reuse a nonce, and the envelope carries both in the
+//!   clear.
+//! - **Sessions.** A channel is created per session with a fresh random
+//!   sender id, and every envelope names (inside the sealed part) the sender
+//!   ids it has heard from. A receiver only accepts messages from a sender
+//!   once that sender names the receiver's own id, which did not exist before
+//!   this session, so nothing recorded from an earlier session with the same
+//!   passphrase is ever accepted. Until then the receiver answers each
+//!   envelope with a sealed *hello* naming the sender, which the transport
+//!   sends back; the first messages each way are dropped like loss.
+//! - **Replay.** The receiver keeps a sliding window per accepted sender and
+//!   refuses a counter it has accepted before or that fell behind the window.
+//!   Windows are kept for the whole session, never evicted. A datagram
+//!   carrying the receiver's *own* sender id (a reflection) is refused too.
+//!
+//! ## Threat model
+//!
+//! The channel protects a session from anyone on the path who does **not**
+//! know the passphrase: they cannot read inputs or chat, inject or alter
+//! messages, or replay traffic from this or an earlier session. Everyone who
+//! knows the passphrase is trusted as a peer. The passphrase is not stretched
+//! and there is no PAKE: HKDF is fast, so anyone who captures one datagram can
+//! test passphrase guesses offline at full speed. A short or dictionary
+//! passphrase protects only against casual injection; use a generated room
+//! code or a long random passphrase when that matters. The sender ids, message
+//! counters and datagram sizes and timing are visible on the wire.
+//!
+//! The channel composes with every transport without touching the
+//! [`RollbackSession`](crate::RollbackSession): [`SecureTransport`] wraps any
+//! message-level [`Transport`] (the in-memory [`MeshTransport`](crate::MeshTransport)
+//! and [`MemoryTransport`](crate::MemoryTransport), the browser's WebRTC
+//! transports), and the UDP transports take a channel directly —
+//! `UdpTransport::secured` (direct and TURN-relayed alike, handshake
+//! included) and the `mesh_net` host, joiner and transport. Peers must agree
+//! on whether a session is secured: a secured peer drops a plain peer's
+//! traffic as unauthenticated, and the handshake times out.
+
+use chacha20poly1305::aead::{Aead, Payload};
+use chacha20poly1305::{KeyInit, XChaCha20Poly1305, XNonce};
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+use crate::message::NetMessage;
+use crate::transport::Transport;
+
+/// Domain separation for the key derivation and the AEAD's associated data:
+/// a key or envelope from another protocol (or another version of this one)
+/// never opens here.
+const LABEL: &[u8] = b"rustynes-netplay sealed v2";
+
+/// How many counters behind the newest one a datagram may arrive and still be
+/// accepted. Reordering deeper than this is indistinguishable from loss, which
+/// the rollback protocol already tolerates.
+const REPLAY_WINDOW: u64 = 128;
+
+/// The most senders a channel remembers hearing from without yet being named
+/// by them; the least recently heard is forgotten past this. Accepted senders
+/// are not counted: their windows last the whole session.
+const MAX_UNCONFIRMED: usize = 16;
+
+/// How much of a sender id an envelope uses to name it. 64 random bits cannot
+/// be guessed ahead of a session.
+const TAG_LEN: usize = 8;
+
+/// The Poly1305 tag every sealed body ends with.
+const AEAD_TAG_LEN: usize = 16;
+
+/// The part of `sender` an envelope names it by.
+fn tag(sender: &[u8; 16]) -> [u8; TAG_LEN] {
+    let mut t = [0u8; TAG_LEN];
+    t.copy_from_slice(&sender[..TAG_LEN]);
+    t
+}
+
+/// A 256-bit session key shared by every peer of a secured session.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SessionKey([u8; 32]);
+
+impl SessionKey {
+    /// Derive the key from a shared `passphrase` (or room code) and a
+    /// `context` both peers agree on (may be empty). The same inputs always
+    /// give the same key.
+    #[must_use]
+    pub fn derive(passphrase: &str, context: &[u8]) -> Self {
+        let hk = Hkdf::<Sha256>::new(Some(context), passphrase.as_bytes());
+        let mut key = [0u8; 32];
+        // 32 bytes is far below HKDF-SHA256's 8160-byte output limit.
+        hk.expand(LABEL, &mut key)
+            .expect("a 32-byte HKDF-SHA256 output is always valid");
+        Self(key)
+    }
+
+    /// Use `bytes` as the key directly (one exchanged out of band).
+    #[must_use]
+    pub const fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl std::fmt::Debug for SessionKey {
+    // Never print key material.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SessionKey(..)")
+    }
+}
+
+/// The counters recently accepted from one remote sender.
+#[derive(Clone, Debug)]
+struct ReplayWindow {
+    sender: [u8; 16],
+    /// The highest counter accepted.
+    newest: u64,
+    /// Bit `i` set: counter `newest - i` was accepted.
+    seen: u128,
+}
+
+impl ReplayWindow {
+    /// `true` if `counter` has not been accepted and is not too old.
+    const fn admits(&self, counter: u64) -> bool {
+        if counter > self.newest {
+            return true;
+        }
+        let age = self.newest - counter;
+        age < REPLAY_WINDOW && self.seen & (1 << age) == 0
+    }
+
+    /// Record `counter` as accepted (it must have been [`admits`](Self::admits)).
+    const fn accept(&mut self, counter: u64) {
+        if counter > self.newest {
+            let shift = counter - self.newest;
+            self.seen = if shift >= REPLAY_WINDOW {
+                0
+            } else {
+                self.seen << shift
+            };
+            self.seen |= 1;
+            self.newest = counter;
+        } else {
+            self.seen |= 1 << (self.newest - counter);
+        }
+    }
+}
+
+/// A sender heard from that has not named this channel yet.
+#[derive(Clone, Copy, Debug)]
+struct Unconfirmed {
+    sender: [u8; 16],
+    /// The channel's `heard` count when this sender was last heard, for
+    /// eviction.
+    last_heard: u64,
+}
+
+/// What [`SecureChannel::try_open`] made of an envelope.
+enum Opened {
+    /// A message from an accepted sender.
+    Message(NetMessage),
+    /// Authentic but carrying nothing to deliver: a hello, or a message from
+    /// a sender that has not named this channel yet.
+    Nothing,
+    /// Not sealed under this key, altered, replayed or reflected.
+    Refused,
+}
+
+/// Seals outgoing messages and opens inbound ones under a [`SessionKey`].
+///
+/// Each peer owns one channel per session; its sender id must be unique among
+/// the peers sharing the key and must not be reused by a later session
+/// ([`random`](Self::random) picks one).
+#[derive(Clone)]
+pub struct SecureChannel {
+    cipher: XChaCha20Poly1305,
+    /// This channel's id, the first 16 bytes of every nonce it seals with.
+    sender: [u8; 16],
+    /// The next counter to seal with.
+    next_counter: u64,
+    /// Replay windows of the senders that have named this channel.
+    windows: Vec<ReplayWindow>,
+    /// Senders heard from that have not named this channel yet.
+    unconfirmed: Vec<Unconfirmed>,
+    /// Authentic envelopes from unconfirmed senders, for eviction order.
+    heard: u64,
+    /// An unconfirmed sender is waiting for a hello naming it.
+    hello_due: bool,
+    /// Envelopes opened successfully.
+    accepted: u64,
+    /// Datagrams refused: unsealed, forged, corrupted, replayed or reflected.
+    rejected: u64,
+}
+
+impl SecureChannel {
+    /// A channel sealing as `sender`. Two channels sharing a key must not
+    /// share a sender id; prefer [`random`](Self::random) outside tests.
+    #[must_use]
+    pub fn new(key: &SessionKey, sender: [u8; 16]) -> Self {
+        Self {
+            cipher: XChaCha20Poly1305::new(&key.0.into()),
+            sender,
+            next_counter: 0,
+            windows: Vec::new(),
+            unconfirmed: Vec::new(),
+            heard: 0,
+            hello_due: false,
+            accepted: 0,
+            rejected: 0,
+        }
+    }
+
+    /// A channel with a sender id drawn from the OS random number generator.
+    ///
+    /// # Errors
+    ///
+    /// Returns the OS error if no randomness is available.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn random(key: &SessionKey) -> std::io::Result<Self> {
+        let mut sender = [0u8; 16];
+        getrandom::fill(&mut sender).map_err(std::io::Error::from)?;
+        Ok(Self::new(key, sender))
+    }
+
+    /// This channel's sender id.
+    #[must_use]
+    pub const fn sender(&self) -> [u8; 16] {
+        self.sender
+    }
+
+    /// Envelopes opened successfully so far.
+    #[must_use]
+    pub const fn accepted(&self) -> u64 {
+        self.accepted
+    }
+
+    /// Datagrams refused so far: anything not sealed under this key, altered
+    /// in flight, replayed, or reflected back at us. Diagnostic only.
+    #[must_use]
+    pub const fn rejected(&self) -> u64 {
+        self.rejected
+    }
+
+    /// Seal `msg` into a [`NetMessage::Sealed`] envelope.
+    #[must_use]
+    pub fn seal(&mut self, msg: &NetMessage) -> NetMessage {
+        self.seal_body(&msg.to_bytes())
+    }
+
+    /// Seal `msg` and encode the envelope for a datagram.
+    #[must_use]
+    pub fn seal_to_bytes(&mut self, msg: &NetMessage) -> Vec<u8> {
+        self.seal(msg).to_bytes()
+    }
+
+    /// A hello naming every sender heard from, if one has been heard that
+    /// has not named this channel yet; the transport sends it back where the
+    /// last datagram came from. Carries no message.
+    #[must_use]
+    pub fn take_hello(&mut self) -> Option<NetMessage> {
+        if !self.hello_due {
+            return None;
+        }
+        self.hello_due = false;
+        Some(self.seal_body(&[]))
+    }
+
+    /// Seal `message` (empty for a hello) behind the names of the senders
+    /// heard from: a count byte, then a [`TAG_LEN`]-byte tag each. Only as
+    /// many names as keep the body within [`NetMessage::MAX_SEALED`] are
+    /// listed; the rest ride on a later, shorter envelope.
+    fn seal_body(&mut self, message: &[u8]) -> NetMessage {
+        let counter = self.next_counter;
+        self.next_counter += 1;
+        let room = (NetMessage::MAX_SEALED - AEAD_TAG_LEN - 1).saturating_sub(message.len());
+        let heard = self
+            .windows
+            .iter()
+            .map(|w| w.sender)
+            .chain(self.unconfirmed.iter().map(|u| u.sender))
+            .take(usize::from(u8::MAX).min(room / TAG_LEN));
+        let mut plain = Vec::with_capacity(1 + TAG_LEN * 4 + message.len());
+        plain.push(0);
+        for sender in heard {
+            plain[0] += 1;
+            plain.extend_from_slice(&tag(&sender));
+        }
+        plain.extend_from_slice(message);
+        let body = self
+            .cipher
+            .encrypt(
+                &nonce(&self.sender, counter),
+                Payload {
+                    msg: &plain,
+                    aad: LABEL,
+                },
+            )
+            .expect("XChaCha20-Poly1305 seals any message shorter than 256 GiB");
+        NetMessage::Sealed {
+            sender: self.sender,
+            counter,
+            body,
+        }
+    }
+
+    /// Open an envelope from a peer. `None` for a hello, for a message from a
+    /// sender that has not named this channel yet (see the
+    /// [module docs](self)), and — counted in [`rejected`](Self::rejected) —
+    /// for anything but a [`NetMessage::Sealed`] that authenticates under this
+    /// key, is not one of ours, and has not been opened before.
+    pub fn open(&mut self, msg: &NetMessage) -> Option<NetMessage> {
+        match self.try_open(msg) {
+            Opened::Message(inner) => {
+                self.accepted += 1;
+                Some(inner)
+            }
+            Opened::Nothing => None,
+            Opened::Refused => {
+                self.rejected += 1;
+                None
+            }
+        }
+    }
+
+    /// Decode a datagram and [`open`](Self::open) it. A datagram that does not
+    /// even decode is refused the same way.
+    pub fn open_bytes(&mut self, datagram: &[u8]) -> Option<NetMessage> {
+        let Some(msg) = NetMessage::from_bytes(datagram) else {
+            self.rejected += 1;
+            return None;
+        };
+        self.open(&msg)
+    }
+
+    fn try_open(&mut self, msg: &NetMessage) -> Opened {
+        let NetMessage::Sealed {
+            sender,
+            counter,
+            ref body,
+        } = *msg
+        else {
+            return Opened::Refused;
+        };
+        if sender == self.sender {
+            return Opened::Refused;
+        }
+        let slot = self.windows.iter().position(|w| w.sender == sender);
+        if slot.is_some_and(|i| !self.windows[i].admits(counter)) {
+            return Opened::Refused;
+        }
+        let Ok(plain) = self.cipher.decrypt(
+            &nonce(&sender, counter),
+            Payload {
+                msg: body,
+                aad: LABEL,
+            },
+        ) else {
+            return Opened::Refused;
+        };
+        let Some((&count, rest)) = plain.split_first() else {
+            return Opened::Refused;
+        };
+        let Some((tags, message)) = rest.split_at_checked(usize::from(count) * TAG_LEN) else {
+            return Opened::Refused;
+        };
+        // Envelopes do not nest.
+        let inner = if message.is_empty() {
+            None
+        } else {
+            match NetMessage::from_bytes(message) {
+                Some(m) if !matches!(m, NetMessage::Sealed { .. }) => Some(m),
+                _ => return Opened::Refused,
+            }
+        };
+        let slot = match slot {
+            Some(i) => i,
+            None if tags.chunks_exact(TAG_LEN).any(|t| t == tag(&self.sender)) => {
+                self.confirm(sender)
+            }
+            None => {
+                self.hear_unconfirmed(sender);
+                return Opened::Nothing;
+            }
+        };
+        self.windows[slot].accept(counter);
+        inner.map_or(Opened::Nothing, Opened::Message)
+    }
+
+    /// Start a replay window for `sender`, which has just named this channel,
+    /// and return its index.
+    fn confirm(&mut self, sender: [u8; 16]) -> usize {
+        self.unconfirmed.retain(|u| u.sender != sender);
+        self.windows.push(ReplayWindow {
+            sender,
+            newest: 0,
+            seen: 0,
+        });
+        self.windows.len() - 1
+    }
+
+    /// Note an authentic envelope from `sender`, which has not named this
+    /// channel yet, and queue a hello naming it (forgetting the stalest
+    /// unconfirmed sender if the table is full).
+    fn hear_unconfirmed(&mut self, sender: [u8; 16]) {
+        self.heard += 1;
+        self.hello_due = true;
+        let last_heard = self.heard;
+        if let Some(u) = self.unconfirmed.iter_mut().find(|u| u.sender == sender) {
+            u.last_heard = last_heard;
+            return;
+        }
+        if self.unconfirmed.len() >= MAX_UNCONFIRMED
+            && let Some(stalest) = self
+                .unconfirmed
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, u)| u.last_heard)
+                .map(|(i, _)| i)
+        {
+            self.unconfirmed.swap_remove(stalest);
+        }
+        self.unconfirmed.push(Unconfirmed { sender, last_heard });
+    }
+}
+
+impl std::fmt::Debug for SecureChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecureChannel")
+            .field("next_counter", &self.next_counter)
+            .field("senders", &self.windows.len())
+            .field("accepted", &self.accepted)
+            .field("rejected", &self.rejected)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Encode `msg` for a datagram, sealed if the transport is secured.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn encode(secure: Option<&mut SecureChannel>, msg: &NetMessage) -> Vec<u8> {
+    secure.map_or_else(|| msg.to_bytes(), |channel| channel.seal_to_bytes(msg))
+}
+
+/// Decode a datagram, opening it if the transport is secured. `None` for a
+/// malformed datagram or — when secured — an unauthenticated one.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn decode(secure: Option<&mut SecureChannel>, datagram: &[u8]) -> Option<NetMessage> {
+    secure.map_or_else(
+        || NetMessage::from_bytes(datagram),
+        |channel| channel.open_bytes(datagram),
+    )
+}
+
+/// The hello a secured transport owes the source of the datagram it just
+/// decoded, encoded for a datagram (see [`SecureChannel::take_hello`]).
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn hello(secure: Option<&mut SecureChannel>) -> Option<Vec<u8>> {
+    secure
+        .and_then(SecureChannel::take_hello)
+        .map(|hello| hello.to_bytes())
+}
+
+/// The AEAD nonce for `counter` from `sender`.
+fn nonce(sender: &[u8; 16], counter: u64) -> XNonce {
+    let mut n = [0u8; 24];
+    n[..16].copy_from_slice(sender);
+    n[16..].copy_from_slice(&counter.to_le_bytes());
+    n.into()
+}
+
+/// A [`Transport`] that seals everything it sends and drops everything it
+/// receives that does not open under its [`SecureChannel`].
+///
+/// Wraps any message-level transport; the session drives it unchanged.
+#[derive(Debug)]
+pub struct SecureTransport<T> {
+    inner: T,
+    channel: SecureChannel,
+}
+
+impl<T: Transport> SecureTransport<T> {
+    /// Secure `inner` with `channel`.
+    #[must_use]
+    pub const fn new(inner: T, channel: SecureChannel) -> Self {
+        Self { inner, channel }
+    }
+
+    /// The wrapped transport.
+    #[must_use]
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// The wrapped transport, mutably.
+    pub const fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// The channel, for its counters.
+    #[must_use]
+    pub const fn channel(&self) -> &SecureChannel {
+        &self.channel
+    }
+
+    /// Unwrap the inner transport.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Transport> Transport for SecureTransport<T> {
+    fn send(&mut self, msg: &NetMessage) {
+        let sealed = self.channel.seal(msg);
+        self.inner.send(&sealed);
+    }
+
+    fn poll(&mut self) -> Vec<NetMessage> {
+        let opened = self
+            .inner
+            .poll()
+            .iter()
+            .filter_map(|msg| self.channel.open(msg))
+            .collect();
+        if let Some(hello) = self.channel.take_hello() {
+            self.inner.send(&hello);
+        }
+        opened
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::{LinkConditions, MemoryTransport};
+    use rustynes_core::DeviceInput;
+
+    fn channels() -> (SecureChannel, SecureChannel) {
+        let key = SessionKey::derive("correct horse battery staple", b"room-42");
+        (
+            SecureChannel::new(&key, [1; 16]),
+            SecureChannel::new(&key, [2; 16]),
+        )
+    }
+
+    /// Channels that have each named the other, as two peers are after
+    /// their first exchange.
+    fn introduced() -> (SecureChannel, SecureChannel) {
+        let (mut a, mut b) = channels();
+        introduce(&mut a, &mut b);
+        (a, b)
+    }
+
+    /// Run the hello exchange between `a` and `b`: `a` speaks first, and its
+    /// opening message is dropped.
+    fn introduce(a: &mut SecureChannel, b: &mut SecureChannel) {
+        let opening = a.seal(&input(0));
+        assert_eq!(b.open(&opening), None, "b has not been named yet");
+        let hello = b.take_hello().expect("b greets a new sender");
+        assert_eq!(a.open(&hello), None, "a hello carries no message");
+        assert!(
+            b.take_hello().is_none(),
+            "one hello per unconfirmed envelope"
+        );
+    }
+
+    fn input(frame: u32) -> NetMessage {
+        NetMessage::Input {
+            player: 1,
+            frame,
+            input: 0x81,
+            device: DeviceInput::None,
+        }
+    }
+
+    #[test]
+    fn key_derivation_depends_on_passphrase_and_context() {
+        let k = SessionKey::derive("pass", b"ctx");
+        assert_eq!(k, SessionKey::derive("pass", b"ctx"));
+        assert_ne!(k, SessionKey::derive("pass2", b"ctx"));
+        assert_ne!(k, SessionKey::derive("pass", b"ctx2"));
+        assert_eq!(format!("{k:?}"), "SessionKey(..)");
+    }
+
+    #[test]
+    fn sealed_messages_round_trip_and_hide_their_contents() {
+        let (mut a, mut b) = introduced();
+        let msg = input(77);
+        let wire = a.seal_to_bytes(&msg);
+        assert!(
+            !wire.windows(4).any(|w| w == 77u32.to_le_bytes()),
+            "the frame number does not appear in the clear"
+        );
+        assert_eq!(b.open_bytes(&wire), Some(msg));
+        assert_eq!((b.accepted(), b.rejected()), (1, 0));
+    }
+
+    #[test]
+    fn plain_forged_and_wrong_key_traffic_is_rejected() {
+        let (mut a, mut b) = channels();
+        // A plain message, as an unsecured peer or an attacker would send.
+        assert_eq!(b.open(&input(1)), None);
+        // A tampered ciphertext.
+        let NetMessage::Sealed {
+            sender,
+            counter,
+            mut body,
+        } = a.seal(&input(2))
+        else {
+            unreachable!()
+        };
+        body[0] ^= 1;
+        let forged = NetMessage::Sealed {
+            sender,
+            counter,
+            body,
+        };
+        assert_eq!(b.open(&forged), None);
+        // A different passphrase.
+        let mut stranger = SecureChannel::new(&SessionKey::derive("guess", b"room-42"), [3; 16]);
+        assert_eq!(b.open(&stranger.seal(&input(3))), None);
+        // Garbage bytes.
+        assert_eq!(b.open_bytes(&[14, 0, 1]), None);
+        assert_eq!(b.rejected(), 4);
+        assert_eq!(b.accepted(), 0);
+    }
+
+    #[test]
+    fn replays_and_reflections_are_rejected() {
+        let (mut a, mut b) = introduced();
+        let first = a.seal(&input(1));
+        let second = a.seal(&input(2));
+        // Out of order is fine; a second copy of either is not.
+        assert!(b.open(&second).is_some());
+        assert!(b.open(&first).is_some());
+        assert!(b.open(&first).is_none());
+        assert!(b.open(&second).is_none());
+        // A's own envelope bounced back at it.
+        let mine = a.seal(&input(3));
+        assert!(a.open(&mine).is_none());
+        // Far behind the window.
+        let stale = a.seal(&input(4));
+        for f in 0..=REPLAY_WINDOW {
+            let _ = b.open(&a.seal(&input(5 + u32::try_from(f).unwrap())));
+        }
+        assert!(b.open(&stale).is_none(), "older than the replay window");
+    }
+
+    /// Traffic recorded from one session cannot be replayed into a later
+    /// session under the same passphrase: the later channels never named the
+    /// recorded sender, and it never named them.
+    #[test]
+    fn a_datagram_from_another_session_is_rejected() {
+        let (mut a1, mut b1) = introduced();
+        let recorded: Vec<Vec<u8>> = (1..=4).map(|f| a1.seal_to_bytes(&input(f))).collect();
+        for wire in &recorded {
+            assert!(b1.open_bytes(wire).is_some(), "accepted in its own session");
+        }
+
+        // The next session: the same passphrase, fresh channels.
+        let key = SessionKey::derive("correct horse battery staple", b"room-42");
+        let mut a2 = SecureChannel::random(&key).unwrap();
+        let mut b2 = SecureChannel::random(&key).unwrap();
+        introduce(&mut a2, &mut b2);
+        for wire in &recorded {
+            assert_eq!(b2.open_bytes(wire), None, "replayed into a new session");
+        }
+        assert_eq!(b2.accepted(), 0);
+        // Its hello names the recorded sender, which is gone; the live peer
+        // still gets through.
+        let _ = b2.take_hello();
+        assert_eq!(b2.open(&a2.seal(&input(9))), Some(input(9)));
+    }
+
+    /// Accepted senders are never forgotten, so however many peers pass
+    /// through a session, an early sender's old counters stay refused.
+    #[test]
+    fn many_senders_do_not_readmit_old_counters() {
+        let key = SessionKey::derive("correct horse battery staple", b"room-42");
+        let mut b = SecureChannel::new(&key, [2; 16]);
+        let mut first = SecureChannel::new(&key, [3; 16]);
+        introduce(&mut first, &mut b);
+        let early = first.seal(&input(1));
+        assert!(b.open(&early).is_some());
+        for id in 4..40 {
+            let mut other = SecureChannel::new(&key, [id; 16]);
+            introduce(&mut other, &mut b);
+            assert!(b.open(&other.seal(&input(2))).is_some());
+        }
+        assert_eq!(b.open(&early), None);
+    }
+
+    /// However many senders a channel has heard, a full state chunk still
+    /// seals within the bound the receiver decodes.
+    #[test]
+    fn a_full_state_chunk_seals_within_the_bound_with_many_senders() {
+        let key = SessionKey::derive("correct horse battery staple", b"room-42");
+        let mut a = SecureChannel::new(&key, [2; 16]);
+        let mut b = SecureChannel::new(&key, [3; 16]);
+        introduce(&mut b, &mut a);
+        for id in 4..60 {
+            let mut other = SecureChannel::new(&key, [id; 16]);
+            introduce(&mut other, &mut a);
+        }
+        let chunk = NetMessage::StateChunk {
+            kind: crate::message::StateTransfer::Join,
+            frame: 600,
+            index: 0,
+            count: 1,
+            data: vec![0x5A; NetMessage::MAX_STATE_CHUNK],
+        };
+        let wire = a.seal_to_bytes(&chunk);
+        assert_eq!(b.open_bytes(&wire), Some(chunk));
+        let small = a.seal_to_bytes(&input(1));
+        assert_eq!(b.open_bytes(&small), Some(input(1)));
+    }
+
+    #[test]
+    fn secure_transport_drops_an_injected_message() {
+        let (ta, tb) = MemoryTransport::pair(LinkConditions::PERFECT, 9);
+        let (a, b) = channels();
+        let mut a = SecureTransport::new(ta, a);
+        let mut b = SecureTransport::new(tb, b);
+        // The first exchange introduces the peers: b answers a's opening
+        // message with a hello, and drops the message itself.
+        a.send(&input(4));
+        assert!(b.poll().is_empty());
+        assert!(a.poll().is_empty());
+        a.send(&input(5));
+        // An attacker with access to the link but not the key.
+        a.inner_mut().send(&input(6));
+        assert_eq!(b.poll(), vec![input(5)]);
+        assert_eq!(b.channel().rejected(), 1);
+    }
+}
@@ -26,14 +26,65 @@
 //!    step.
 //! 3. **Predict** — fill each not-yet-known remote player's input for
 //!    `current_frame` by repeating that player's last known input (the
-//!    standard heuristic).
+//!    standard heuristic; an expansion device follows [`crate::device`]'s
+//!    analog rules).
 //! 4. **Advance** — snapshot the present, apply all players' inputs, run one
 //!    frame.
 //! 5. **Send** — transmit the local input to every peer.
 //! 6. **Confirm** — recompute `last_confirmed_frame` = the newest frame for
 //!    which ALL players' real inputs are known.
 //! 7. **Checksum** — periodically hash state at a confirmed frame and
-//!    exchange it; a mismatch is a fatal [`NetplayError::Desync`].
+//!    exchange it; a mismatch is a fatal [`NetplayError::Desync`] — unless
+//!    [`SessionConfig::desync_recovery`] is on (see below).
+//!
+//! # Desync recovery
+//!
+//! With [`SessionConfig::desync_recovery`] enabled, a *confirmed* desync
+//! (the [`DesyncDiagnostics`] hysteresis threshold of consecutive mismatches)
+//! is repaired instead of fatal. The host (player 0) is the authority: it
+//! streams its canonical confirmed checkpoint to every peer as acknowledged
+//! [`NetMessage::StateChunk`]s (see [`crate::recovery`]); a diverged peer that
+//! notices first asks for it with a [`NetMessage::ResyncRequest`]. Once a peer
+//! has the whole snapshot and has reached its frame, it restores it as the new
+//! checkpoint, forgets every checksum from that frame on, and replays to the
+//! present — the same `resync` path a rollback takes. Mismatches on frames
+//! before the newest recovery are stale and ignored.
+//!
+//! # Joining a match in progress
+//!
+//! The host can [`vacate`](RollbackSession::vacate_player) a player slot — its
+//! peer left, or never arrived — and from then on authors that slot's input as
+//! idle (no buttons), exactly like its own, so the match carries on. A
+//! newcomer started with [`RollbackSession::join_in_progress`] asks for the
+//! slot with a [`NetMessage::JoinRequest`]; the host picks a `join_frame` a
+//! short lead ahead of its present and broadcasts a [`NetMessage::JoinAccept`]
+//! naming it and the `base_frame` of its canonical confirmed checkpoint. The
+//! checkpoint travels to the newcomer over the same chunked transfer desync
+//! recovery uses, and every peer resends its inputs from `base_frame` until
+//! the join has landed, so the newcomer restores the state and replays the
+//! confirmed tail. The host authors the slot up to `join_frame`; the newcomer
+//! authors it from there. A spectator joins the same way (as
+//! [`NetMessage::SPECTATOR`]) to start from the present instead of replaying
+//! the whole match.
+//!
+//! # Adaptive input delay
+//!
+//! [`SessionConfig::input_delay`] can change mid-match. The session measures
+//! its round trip (in ticks, from the cumulative `InputAck`s) and its
+//! rollbacks with a [`DelayTuner`], and the host schedules a new delay at an
+//! agreed future frame with a [`NetMessage::InputDelay`] — on its own with
+//! [`DelayMode::Auto`], or when the application calls
+//! [`RollbackSession::schedule_input_delay`]. The delay only moves which frame
+//! a local input is authored for, so a change is determinism-safe; see
+//! [`crate::delay`].
+//!
+//! # Chat
+//!
+//! [`RollbackSession::send_chat`] sends a line of text to every other player
+//! as a [`NetMessage::Chat`]; `advance` resends it until each peer answers
+//! with a [`NetMessage::ChatAck`], and delivers the lines it receives to
+//! [`RollbackSession::take_chat`]. Chat rides the same transport as the
+//! inputs but never touches emulated state; see [`crate::chat`].
 //!
 //! # Topology
 //!
@@ -48,10 +99,18 @@
 //! 2-player path is exactly the prior pairwise link (`player` is always the
 //! one remote index).
 
-use rustynes_core::{Buttons, Nes};
+use rustynes_core::{Buttons, DeviceInput, Nes};
 
+use crate::chat::{Chat, ChatError, ChatLine};
+use crate::delay::{
+    DELAY_LEAD_FRAMES, DelayChange, DelayMode, DelayTuner, DelayTuning, MAX_INPUT_DELAY,
+};
+use crate::device;
 use crate::diagnostics::DesyncDiagnostics;
-use crate::message::{NetMessage, fnv1a64};
+use crate::match_record::MatchRecorder;
+use crate::message::{NetMessage, StateTransfer, fnv1a64};
+use crate::recovery::{IncomingState, OutgoingState, RecoveryError};
+use crate::settings::NetSettings;
 use crate::transport::Transport;
 
 /// The maximum number of players.
@@ -68,6 +127,20 @@ pub const MAX_PLAYERS: usize = 4;
 /// window) are resent.
 const INPUT_RESEND_WINDOW: u32 = 64;
 
+/// Max number of unacknowledged recovery [`NetMessage::StateChunk`]s the host
+/// resends per tick. A core snapshot compresses to a few dozen chunks, so a
+/// transfer completes within a handful of ticks without flooding the link.
+const STATE_CHUNKS_PER_TICK: usize = 8;
+
+/// How far past the host's newest authored frame a late joiner's `join_frame`
+/// is set: enough for the state transfer and the input tail to land before
+/// the newcomer's first own input is due, so the live peers barely stall.
+const JOIN_LEAD_FRAMES: u32 = 60;
+
+/// A join whose newcomer has not shown up this many frames past its
+/// `join_frame` is abandoned (peers stop resending the tail for it).
+const JOIN_TIMEOUT_FRAMES: u32 = 600;
+
 /// Errors that abort a netplay session.
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
@@ -100,6 +173,21 @@ pub enum NetplayError {
     /// two peers are not running the same game.
     #[error("rom mismatch: peer is running a different ROM")]
     RomMismatch,
+
+    /// A peer's `Sync` announced different emulation settings (see
+    /// [`SessionConfig::settings_digest`]).
+    #[error("settings mismatch: peer runs different emulation settings")]
+    SettingsMismatch,
+
+    /// A peer's `Sync` announced a different battery save / FDS disk (see
+    /// [`SessionConfig::save_digest`]).
+    #[error("save mismatch: peer starts from a different battery save or disk")]
+    SaveMismatch,
+
+    /// A desync-recovery state transfer from the host could not be decoded
+    /// (see [`SessionConfig::desync_recovery`]).
+    #[error("desync recovery failed: {0}")]
+    Recovery(#[from] RecoveryError),
 }
 
 /// Configuration for a [`RollbackSession`].
@@ -123,6 +211,25 @@ pub struct SessionConfig {
     /// Exchange a checksum every `checksum_interval` frames (`0` disables
     /// checksums / desync detection). Checksums cover only confirmed frames.
     pub checksum_interval: u32,
+    /// Repair a confirmed desync with an authoritative state transfer from
+    /// the host (player 0) instead of ending the session with
+    /// [`NetplayError::Desync`]. Requires `checksum_interval != 0`. Defaults
+    /// to `false`.
+    pub desync_recovery: bool,
+    /// The [`NetSettings::digest`](crate::NetSettings::digest) of this peer's
+    /// emulation settings, announced in every `Sync`. A peer announcing a
+    /// different non-zero digest is refused with
+    /// [`NetplayError::SettingsMismatch`]. `0` (the default) announces none.
+    pub settings_digest: u64,
+    /// The [`SessionSave::digest`](crate::SessionSave::digest) of the battery
+    /// RAM / FDS disk this peer started from, announced in every `Sync`. A
+    /// different non-zero digest is refused with
+    /// [`NetplayError::SaveMismatch`]. `0` (the default) announces none.
+    pub save_digest: u64,
+    /// Whether the session adapts [`Self::input_delay`] to the link (see
+    /// [`crate::delay`]). Only the host's mode decides; every peer follows
+    /// the host's schedule. Defaults to [`DelayMode::Fixed`].
+    pub delay_tuning: DelayTuning,
 }
 
 impl Default for SessionConfig {
@@ -133,6 +240,10 @@ impl Default for SessionConfig {
             input_delay: 2,
             max_rollback_frames: 8,
             checksum_interval: 30,
+            desync_recovery: false,
+            settings_digest: 0,
+            save_digest: 0,
+            delay_tuning: DelayTuning::default(),
         }
     }
 }
@@ -168,10 +279,12 @@ impl AdvanceOutcome {
 /// it). For a remote player it is `false` (holding a prediction) until the
 /// real value arrives over the transport. The flag lets a rollback detect a
 /// misprediction by comparing a newly-arrived value against the (predicted)
-/// value we previously ran with.
+/// value we previously ran with. `device` is the player's expansion-device
+/// state (see [`crate::device`]), [`DeviceInput::None`] for a plain pad.
 #[derive(Clone, Copy, Debug, Default)]
 struct PlayerInput {
     input: u8,
+    device: DeviceInput,
     confirmed: bool,
 }
 
@@ -186,10 +299,25 @@ struct InputSlot {
     simulated: bool,
 }
 
+/// A player slot the host authors idle input for (see
+/// [`RollbackSession::vacate_player`]).
+#[derive(Clone, Copy, Debug)]
+struct Vacancy {
+    /// The first frame the host authored for the slot. Inputs the departed
+    /// peer still sends for it or later are ignored.
+    since: u32,
+    /// The next frame the host will author.
+    next: u32,
+    /// The frame a newcomer takes the slot over from (`u32::MAX` while the
+    /// slot is open).
+    until: u32,
+}
+
 /// An `N`-player GGPO-style rollback session driving one [`Nes`] over a
 /// [`Transport`].
 ///
-/// Construct with [`Self::new`], feed the local input each tick with
+/// Construct with [`Self::new`] (or [`Self::join_in_progress`] to join a
+/// running match), feed the local input each tick with
 /// [`Self::add_local_input`], then call [`Self::advance`] once per visual
 /// frame. See the module docs for the algorithm. `N` (2..=4) is
 /// [`SessionConfig::num_players`]; with `N == 2` this is exactly the
@@ -217,8 +345,11 @@ pub struct RollbackSession<T: Transport> {
     /// Save-state ring: `snapshots[f]` is the emulator state *before* frame
     /// `f` was run (so restoring it and running forward reproduces frame f).
     /// Indexed by frame; only the trailing `max_rollback_frames + 1` entries
-    /// are guaranteed present (older are `None` after pruning).
+    /// are kept — `resync` prunes everything older (recycling the buffer), so
+    /// a long session holds a bounded number of ~250 KiB blobs.
     snapshots: Vec<Option<Vec<u8>>>,
+    /// Every `snapshots` entry below this frame has been pruned.
+    snapshots_floor: usize,
 
     /// Checksums we have *sent* at confirmed frames, kept so a later remote
     /// `Checksum` for the same frame can be compared and so we don't re-send.
@@ -262,7 +393,55 @@ pub struct RollbackSession<T: Transport> {
     /// frontend's `DesyncMonitor`-style panel. It only reads the digests the
     /// session already computes and exchanges, never feeding back into the
     /// rollback algorithm, so it cannot perturb determinism or correctness.
+    /// (With [`SessionConfig::desync_recovery`] its mismatch run also decides
+    /// when a recovery transfer starts.)
     diagnostics: DesyncDiagnostics,
+
+    /// Host only: the authoritative snapshot being streamed to the peers,
+    /// until every peer has acknowledged every chunk.
+    outgoing_state: Option<OutgoingState>,
+    /// Peer only: the recovery transfer being reassembled.
+    incoming_state: Option<IncomingState>,
+    /// Peer only: a reassembled `(frame, snapshot)` waiting for
+    /// `current_frame` to reach its frame before it is restored.
+    pending_restore: Option<(u32, Vec<u8>)>,
+    /// Peer only: the mismatched frame a [`NetMessage::ResyncRequest`] is
+    /// outstanding for (re-sent every tick until a transfer arrives).
+    resync_requested: Option<u32>,
+    /// The frame of the newest recovery (sent by the host, applied by a
+    /// peer). Checksum mismatches on earlier frames predate it and are stale.
+    recovered_frame: Option<u32>,
+
+    /// Host only: the slots whose idle input this host authors, indexed by
+    /// player.
+    vacancies: [Option<Vacancy>; MAX_PLAYERS],
+    /// The late join in flight, as announced by the host's
+    /// [`NetMessage::JoinAccept`]: `(player, base_frame, join_frame)`. While
+    /// set, this peer resends its inputs from `base_frame`.
+    join: Option<(u8, u32, u32)>,
+    /// Newcomer only: `true` from [`Self::join_in_progress`] until the host's
+    /// state has been installed.
+    joining: bool,
+    /// The first frame whose local input this peer authors: `0`, or a late
+    /// joiner's `join_frame` (the host authors the slot before it).
+    local_from: u32,
+    /// The match recording in progress, fed each confirmed frame as `resync`
+    /// advances the checkpoint past it.
+    recording: Option<MatchRecorder>,
+
+    /// Round-trip / rollback measurements behind the input-delay proposals.
+    tuner: DelayTuner,
+    /// The scheduled input-delay change `(frame, delay)` not yet applied.
+    pending_delay: Option<(u32, u32)>,
+    /// Peer only: the newest schedule frame heard from the host, so resends
+    /// and stale schedules are ignored.
+    delay_heard: Option<u32>,
+    /// Every input-delay change applied so far.
+    delay_changes: Vec<DelayChange>,
+    /// The newest frame this peer has authored its local input for.
+    newest_local: Option<u32>,
+    /// Chat lines in flight both ways.
+    chat: Chat,
 }
 
 impl<T: Transport> RollbackSession<T> {
@@ -273,7 +452,44 @@ impl<T: Transport> RollbackSession<T> {
     ///
     /// Panics in debug builds if `config.num_players` is not in `2..=4` or
     /// `config.local_player >= config.num_players`.
-    pub fn new(config: SessionConfig, mut transport: T, rom_hash: [u8; 32]) -> Self {
+    pub fn new(config: SessionConfig, transport: T, rom_hash: [u8; 32]) -> Self {
+        let mut session = Self::unseeded(config, transport, rom_hash);
+        // GGPO input-delay convention: the first `input_delay` frames have no
+        // buffered local input, so they run with "no buttons" and are
+        // immediately confirmable. Seed (and announce) those empty inputs for
+        // this peer's local player so confirmation can begin from frame 0 once
+        // the other peers' matching empty inputs arrive.
+        for f in 0..config.input_delay {
+            session.author_local(f, 0, DeviceInput::None);
+        }
+        session
+    }
+
+    /// Create a session that joins a match already in progress, taking over
+    /// the slot `config.local_player` once the host has
+    /// [vacated](Self::vacate_player) it. Sends the opening `Sync`, then a
+    /// [`NetMessage::JoinRequest`] every tick; [`Self::advance`] produces
+    /// nothing until the host's confirmed state has arrived, then resumes
+    /// from that state's frame. Local input is authored from the host's
+    /// agreed `join_frame` on — earlier [`Self::add_local_input`] calls are
+    /// dropped. See the module docs.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `config.num_players` is not in `2..=4` or
+    /// `config.local_player` is not in `1..num_players` (the host never joins
+    /// late).
+    pub fn join_in_progress(config: SessionConfig, transport: T, rom_hash: [u8; 32]) -> Self {
+        debug_assert!(config.local_player != 0, "the host cannot join late");
+        let mut session = Self::unseeded(config, transport, rom_hash);
+        session.joining = true;
+        session.local_from = u32::MAX;
+        session
+    }
+
+    /// The shared constructor: validate `config`, send the `Sync` and build
+    /// an empty session.
+    fn unseeded(config: SessionConfig, mut transport: T, rom_hash: [u8; 32]) -> Self {
         debug_assert!(
             (2..=4).contains(&config.num_players),
             "num_players must be 2..=4"
@@ -285,8 +501,10 @@ impl<T: Transport> RollbackSession<T> {
         transport.send(&NetMessage::Sync {
             magic: NetMessage::SYNC_MAGIC,
             rom_hash,
+            settings: config.settings_digest,
+            save: config.save_digest,
         });
-        let mut session = Self {
+        Self {
             config,
             transport,
             rom_hash,
@@ -296,32 +514,30 @@ impl<T: Transport> RollbackSession<T> {
             synced: false,
             history: Vec::new(),
             snapshots: Vec::new(),
+            snapshots_floor: 0,
             local_checksums: Vec::new(),
             confirmed_hashes: Vec::new(),
             remote_checksums: Vec::new(),
             checkpoint: None,
             confirmed_entering: Vec::new(),
             diagnostics: DesyncDiagnostics::new(),
-        };
-        // GGPO input-delay convention: the first `input_delay` frames have no
-        // buffered local input, so they run with "no buttons" and are
-        // immediately confirmable. Seed (and announce) those empty inputs for
-        // this peer's local player so confirmation can begin from frame 0 once
-        // the other peers' matching empty inputs arrive.
-        let lp = config.local_player;
-        for f in 0..config.input_delay {
-            session.ensure_frame(f);
-            session.history[f as usize].players[lp as usize] = PlayerInput {
-                input: 0,
-                confirmed: true,
-            };
-            session.transport.send(&NetMessage::Input {
-                player: lp,
-                frame: f,
-                input: 0,
-            });
+            outgoing_state: None,
+            incoming_state: None,
+            pending_restore: None,
+            resync_requested: None,
+            recovered_frame: None,
+            vacancies: [None; MAX_PLAYERS],
+            join: None,
+            joining: false,
+            local_from: 0,
+            recording: None,
+            tuner: DelayTuner::new(config.delay_tuning),
+            pending_delay: None,
+            delay_heard: None,
+            delay_changes: Vec::new(),
+            newest_local: None,
+            chat: Chat::new(config.local_player, config.num_players),
         }
-        session
     }
 
     /// The frame the session will produce next.
@@ -375,6 +591,24 @@ impl<T: Transport> RollbackSession<T> {
         }
     }
 
+    /// The confirmed expansion-device state applied at `frame`, per port like
+    /// [`Self::confirmed_input`], if every player is confirmed. Ports without
+    /// a device are [`DeviceInput::None`]. For tests / debugging.
+    #[must_use]
+    pub fn confirmed_devices(&self, frame: u32) -> Option<[DeviceInput; MAX_PLAYERS]> {
+        let slot = self.history.get(frame as usize).copied()?;
+        let n = self.config.num_players as usize;
+        if (0..n).all(|p| slot.players[p].confirmed) {
+            let mut out = [DeviceInput::None; MAX_PLAYERS];
+            for (p, cell) in slot.players.iter().enumerate().take(n) {
+                out[p] = cell.device;
+            }
+            Some(out)
+        } else {
+            None
+        }
+    }
+
     /// `true` once a peer's `Sync` handshake has been validated.
     #[must_use]
     pub const fn is_synced(&self) -> bool {
@@ -397,30 +631,222 @@ impl<T: Transport> RollbackSession<T> {
         &self.diagnostics
     }
 
+    /// Start recording the match, played under `settings`, as an attested
+    /// movie. Recording begins at the next frame to be confirmed; see
+    /// [`crate::match_record`]. Replaces any recording already in progress.
+    pub fn start_recording(&mut self, settings: &NetSettings) {
+        self.recording = Some(MatchRecorder::new(settings));
+    }
+
+    /// The match recording in progress, if any.
+    #[must_use]
+    pub const fn recording(&self) -> Option<&MatchRecorder> {
+        self.recording.as_ref()
+    }
+
+    /// Stop recording and hand back what was recorded.
+    pub const fn take_recording(&mut self) -> Option<MatchRecorder> {
+        self.recording.take()
+    }
+
+    /// The input delay local input is authored with now (frames).
+    #[must_use]
+    pub const fn input_delay(&self) -> u32 {
+        self.config.input_delay
+    }
+
+    /// The input-delay change `(frame, delay)` scheduled but not yet in
+    /// effect, if any.
+    #[must_use]
+    pub const fn scheduled_input_delay(&self) -> Option<(u32, u32)> {
+        self.pending_delay
+    }
+
+    /// Every input-delay change applied so far, oldest first.
+    #[must_use]
+    pub fn input_delay_changes(&self) -> &[DelayChange] {
+        &self.delay_changes
+    }
+
+    /// The round-trip and rollback measurements behind the input-delay
+    /// proposals.
+    #[must_use]
+    pub const fn delay_tuner(&self) -> &DelayTuner {
+        &self.tuner
+    }
+
+    /// The delay the tuner recommends, when it differs from the current one
+    /// and no change is already scheduled. With [`DelayMode::Propose`] this
+    /// is what a host offers to apply with [`Self::schedule_input_delay`].
+    #[must_use]
+    pub fn proposed_input_delay(&self) -> Option<u32> {
+        self.tuner
+            .proposal()
+            .filter(|&delay| delay != self.config.input_delay && self.pending_delay.is_none())
+    }
+
+    /// Host only: switch every peer to `delay` (capped at
+    /// [`MAX_INPUT_DELAY`]) from a frame [`DELAY_LEAD_FRAMES`] ahead,
+    /// replacing any change still pending. Returns that frame, or `None` on a
+    /// non-host peer (which follows the host's schedule instead).
+    pub fn schedule_input_delay(&mut self, delay: u32) -> Option<u32> {
+        if !self.is_host() {
+            return None;
+        }
+        let frame = self.current_frame + DELAY_LEAD_FRAMES;
+        self.pending_delay = Some((frame, delay.min(MAX_INPUT_DELAY)));
+        self.send_delay_schedule();
+        Some(frame)
+    }
+
+    /// Send a chat line to every other player. It is resent each
+    /// [`CHAT_RESEND_TICKS`](crate::chat::CHAT_RESEND_TICKS) of
+    /// [`Self::advance`] until acknowledged; see [`crate::chat`].
+    ///
+    /// # Errors
+    ///
+    /// A [`ChatError`] for a line that is empty, too long or contains a
+    /// control character, or that the rate limit or backlog refuses.
+    pub fn send_chat(&mut self, text: &str) -> Result<(), ChatError> {
+        let msg = self.chat.send(text)?;
+        self.transport.send(&msg);
+        Ok(())
+    }
+
+    /// The chat lines received since the last call, oldest first.
+    pub fn take_chat(&mut self) -> Vec<ChatLine> {
+        self.chat.take_received()
+    }
+
     /// Borrow the transport (e.g. to inspect link stats). Mainly for tests.
     pub const fn transport(&self) -> &T {
         &self.transport
     }
 
+    /// `true` while a [`Self::join_in_progress`] session is still waiting for
+    /// the host's state (it produces no frames until then).
+    #[must_use]
+    pub const fn is_joining(&self) -> bool {
+        self.joining
+    }
+
+    /// `true` if `player`'s slot is open for a newcomer: the host has
+    /// [vacated](Self::vacate_player) it and no join has claimed it yet.
+    /// Always `false` on a non-host peer.
+    #[must_use]
+    pub fn is_vacant(&self, player: u8) -> bool {
+        self.vacancies
+            .get(usize::from(player))
+            .copied()
+            .flatten()
+            .is_some_and(|v| v.until == u32::MAX)
+    }
+
+    /// Host only: mark `player`'s slot vacant — its peer has left, or never
+    /// arrived — and author its input as idle (no buttons) from its first
+    /// unconfirmed frame on, so the match carries on without it. A newcomer
+    /// can then take the slot over (see [`Self::join_in_progress`]).
+    /// Vacating a slot whose join is still under way abandons that join and
+    /// reopens it.
+    ///
+    /// Returns `false` (and does nothing) on a non-host peer, for the host's
+    /// own or an out-of-range slot, and for a slot that is already open.
+    pub fn vacate_player(&mut self, player: u8) -> bool {
+        if !self.is_host() || player == 0 || player >= self.config.num_players {
+            return false;
+        }
+        let p = usize::from(player);
+        if let Some(vacancy) = self.vacancies[p].as_mut() {
+            if vacancy.until == u32::MAX {
+                return false;
+            }
+            vacancy.until = u32::MAX;
+            if self.join.is_some_and(|(j, ..)| j == player) {
+                self.join = None;
+                if self.outgoing_is_join() {
+                    self.outgoing_state = None;
+                }
+            }
+            return true;
+        }
+        // Author from the first frame the departed peer's input is missing.
+        let mut since = self.last_confirmed_frame.map_or(0, |c| c + 1);
+        while self
+            .history
+            .get(since as usize)
+            .is_some_and(|slot| slot.players[p].confirmed)
+        {
+            since += 1;
+        }
+        self.vacancies[p] = Some(Vacancy {
+            since,
+            next: since,
+            until: u32::MAX,
+        });
+        true
+    }
+
     /// Record the local player's input for the frame it will apply to
     /// (`current_frame + input_delay`). Call once per tick before
     /// [`Self::advance`].
-    pub fn add_local_input(&mut self, input: Buttons) {
+    ///
+    /// A frame is never authored twice: the first input sent for it is the
+    /// one every peer confirms, so a later one could only desync them. A
+    /// stalled tick's re-add (the frame has not moved) is dropped, and so,
+    /// after the delay shrinks, are inputs aimed at frames already authored;
+    /// after it grows the skipped frames hold the last input. Returns whether
+    /// `input` was authored.
+    #[must_use]
+    pub fn add_local_input(&mut self, input: Buttons) -> bool {
+        self.add_local_device_input(input, DeviceInput::None)
+    }
+
+    /// [`Self::add_local_input`] with the state of the expansion device on
+    /// the local player's port. The device is dropped unless it drives that
+    /// port in a two-player session (see [`crate::device`]); frames skipped
+    /// by a delay increase hold its predicted state.
+    #[must_use]
+    pub fn add_local_device_input(&mut self, input: Buttons, device: DeviceInput) -> bool {
+        self.apply_due_delay();
+        let device = device::for_player(device, self.local_player(), self.config.num_players);
         let target = self.current_frame + self.config.input_delay;
+        // A late joiner's slot belongs to the host until its join frame.
+        if target < self.local_from {
+            return false;
+        }
+        if let Some(newest) = self.newest_local {
+            if target <= newest {
+                return false;
+            }
+            let lp = usize::from(self.local_player());
+            let held = self.history[newest as usize].players[lp];
+            for frame in newest + 1..target {
+                self.author_local(frame, held.input, device::predict(held.device));
+            }
+        }
+        self.author_local(target, input.bits(), device);
+        true
+    }
+
+    /// Author the local input for `frame` and send it. Redundant resends of
+    /// recent inputs happen every tick in `advance`; the transport fans this
+    /// out to all other players (mesh topology).
+    fn author_local(&mut self, frame: u32, input: u8, device: DeviceInput) {
         let lp = self.local_player();
-        self.ensure_frame(target);
-        self.history[target as usize].players[lp as usize] = PlayerInput {
-            input: input.bits(),
+        self.ensure_frame(frame);
+        self.history[frame as usize].players[usize::from(lp)] = PlayerInput {
+            input,
+            device,
             confirmed: true,
         };
-        // Immediately tell every peer (redundant resend of recent inputs is
-        // the UDP transport's job in Stage 2; here we send once per add). The
-        // transport fans this out to all other players (mesh topology).
         self.transport.send(&NetMessage::Input {
             player: lp,
-            frame: target,
-            input: input.bits(),
+            frame,
+            input,
+            device,
         });
+        self.tuner.on_authored(frame);
+        self.newest_local = Some(frame);
     }
 
     /// Grow the per-frame vectors so index `frame` is addressable.
@@ -451,19 +877,52 @@ impl<T: Transport> RollbackSession<T> {
     /// # Errors
     ///
     /// Returns [`NetplayError::Desync`] if a confirmed-frame checksum from
-    /// the peer disagrees with ours, [`NetplayError::RomMismatch`] if a peer
-    /// is running a different ROM, or [`NetplayError::Restore`] if a
-    /// rollback's save-state restore fails.
+    /// the peer disagrees with ours (and [`SessionConfig::desync_recovery`] is
+    /// off), [`NetplayError::RomMismatch`] if a peer is running a different
+    /// ROM, [`NetplayError::Restore`] if a rollback's save-state restore
+    /// fails, or [`NetplayError::Recovery`] if a recovery state transfer is
+    /// malformed.
     pub fn advance(&mut self, nes: &mut Nes) -> Result<AdvanceOutcome, NetplayError> {
         // 1. Ingest everything the peers sent us, detecting the earliest frame
         //    whose prediction was just contradicted by a real remote input.
-        let earliest_mispredict = self.ingest(nes)?;
+        let mut earliest_mispredict = self.ingest(nes)?;
+
+        // 1b. Desync recovery / late join: install a fully-received
+        //     authoritative state once we have reached its frame (forces the
+        //     replay below).
+        let recovered = self.apply_pending_restore();
+
+        // 1c'. A scheduled input-delay change whose frame has come takes
+        //      effect before this tick's resends.
+        self.apply_due_delay();
+
+        // 1d. Resend chat lines still owed an ack (even while joining).
+        for msg in self.chat.on_tick() {
+            self.transport.send(&msg);
+        }
+
+        // A late joiner has nothing to simulate until the host's state is in;
+        // keep asking for the slot.
+        if self.joining {
+            self.transport.send(&NetMessage::JoinRequest {
+                player: self.local_player(),
+            });
+            return Ok(AdvanceOutcome::STALLED);
+        }
+
+        // 1c. Host: author idle input for every vacated slot. Replacing a
+        //     prediction on an already-run frame is a misprediction like any
+        //     late remote input.
+        if let Some(frame) = self.author_vacancies() {
+            earliest_mispredict = Some(earliest_mispredict.map_or(frame, |e| e.min(frame)));
+        }
 
         // 2. Recompute confirmation: newly-arrived inputs may extend the
         //    confirmed prefix, advancing the canonical checkpoint.
         let confirmed_before = self.last_confirmed_frame;
         self.recompute_confirmed();
         let confirmation_advanced = self.last_confirmed_frame != confirmed_before;
+        self.finish_join();
 
         // 2b. Acknowledge inputs with a CUMULATIVE (contiguous) frame, not the
         //     highest one received. `last_confirmed_frame` is the highest frame
@@ -490,10 +949,13 @@ impl<T: Transport> RollbackSession<T> {
         let mispredicted = earliest_mispredict.is_some_and(|m| m < self.current_frame);
         let mut rolled_back = false;
         let mut resimulated = 0u32;
-        if (mispredicted || confirmation_advanced) && self.checkpoint.is_some() {
+        if (mispredicted || confirmation_advanced || recovered) && self.checkpoint.is_some() {
             resimulated = self.resync(nes)?;
             rolled_back = mispredicted;
         }
+        // Only a misprediction's replay says the delay is too short; moving
+        // the checkpoint over newly confirmed frames replays them too.
+        let mispredicted_frames = if rolled_back { resimulated } else { 0 };
 
         // 3b. Resolve any remote checksums now that the checkpoint advanced.
         self.compare_pending_checksums()?;
@@ -507,10 +969,18 @@ impl<T: Transport> RollbackSession<T> {
         //     any drop within a few frames. Runs even when we stall below.
         self.resend_unacked_local_inputs();
 
+        // 3d. Keep any state transfer moving (host: resend unacked state
+        //     chunks and the `JoinAccept` they serve; peer: repeat an
+        //     outstanding resync request). Like the input resend, this must
+        //     run even while stalled.
+        self.pump_recovery();
+        self.send_delay_schedule();
+
         // Time-sync: if we are running too far ahead of the confirmed frame,
         // stall so the peers can catch up and we stay inside the window.
         if self.should_stall() {
             self.send_quality();
+            self.tune(mispredicted_frames);
             return Ok(AdvanceOutcome::STALLED);
         }
 
@@ -535,12 +1005,13 @@ impl<T: Transport> RollbackSession<T> {
         // 6. Send our (already-confirmed-local) input for this frame to the
         //    peers. (add_local_input also sent it for the input-delay target;
         //    this resend covers the immediate frame for the zero-delay case.)
-        let lp = self.local_player() as usize;
-        if self.history[frame as usize].players[lp].confirmed {
+        let local = self.history[frame as usize].players[self.local_player() as usize];
+        if frame >= self.local_from && local.confirmed {
             self.transport.send(&NetMessage::Input {
                 player: self.local_player(),
                 frame,
-                input: self.history[frame as usize].players[lp].input,
+                input: local.input,
+                device: local.device,
             });
         }
 
@@ -550,6 +1021,9 @@ impl<T: Transport> RollbackSession<T> {
         // 8. Periodically checksum a confirmed frame and exchange it.
         self.maybe_send_checksum();
 
+        // 9. Feed the input-delay tuner.
+        self.tune(mispredicted_frames);
+
         Ok(AdvanceOutcome {
             produced_frame: true,
             rolled_back,
@@ -558,50 +1032,95 @@ impl<T: Transport> RollbackSession<T> {
         })
     }
 
+    /// Fold in one remote input. Returns `frame` if it contradicts the
+    /// prediction an already-simulated frame used.
+    fn ingest_input(
+        &mut self,
+        player: u8,
+        frame: u32,
+        input: u8,
+        device: DeviceInput,
+    ) -> Option<u32> {
+        // Ignore an out-of-range or self-addressed player index (a malformed /
+        // foreign packet must never corrupt our own authored input or index
+        // out of bounds). A late joiner's slot before its join frame is the
+        // host's to author.
+        if player >= self.config.num_players
+            || (player == self.local_player() && frame >= self.local_from)
+        {
+            return None;
+        }
+        // Host: the same goes for a vacated slot — a straggling packet from
+        // its departed peer must not override the idle input we authored.
+        if self.vacancies[player as usize].is_some_and(|v| (v.since..v.until).contains(&frame)) {
+            return None;
+        }
+        let device = device::for_player(device, player, self.config.num_players);
+        self.ensure_frame(frame);
+        let slot = &mut self.history[frame as usize];
+        let cell = &mut slot.players[player as usize];
+        let prev = (cell.input, cell.device);
+        cell.input = input;
+        cell.device = device;
+        cell.confirmed = true;
+        // Misprediction: a frame we already ran used a value that the
+        // just-arrived real input contradicts. Re-running it (and everything
+        // after) is required. A second copy of an identical input (idempotent
+        // resend, whether previously a prediction or already confirmed) leaves
+        // `prev` equal, so it is correctly NOT treated as a misprediction.
+        (slot.simulated && prev != (input, device)).then_some(frame)
+    }
+
+    /// Fold in a (re)sent handshake `Sync`: a wrong magic is ignored, while a
+    /// different ROM or announced settings digest is fatal (0 = not
+    /// announced, never refused).
+    fn ingest_sync(
+        &mut self,
+        magic: u32,
+        rom_hash: &[u8; 32],
+        settings: u64,
+        save: u64,
+    ) -> Result<(), NetplayError> {
+        if magic != NetMessage::SYNC_MAGIC {
+            return Ok(());
+        }
+        if *rom_hash != self.rom_hash {
+            return Err(NetplayError::RomMismatch);
+        }
+        let ours = self.config.settings_digest;
+        if settings != 0 && ours != 0 && settings != ours {
+            return Err(NetplayError::SettingsMismatch);
+        }
+        let ours = self.config.save_digest;
+        if save != 0 && ours != 0 && save != ours {
+            return Err(NetplayError::SaveMismatch);
+        }
+        self.synced = true;
+        Ok(())
+    }
+
     /// Poll the transport and fold in every message. Returns the earliest
     /// frame (if any) whose previously-used prediction was contradicted by a
     /// newly-arrived real remote input.
     fn ingest(&mut self, nes: &Nes) -> Result<Option<u32>, NetplayError> {
         let mut earliest_mispredict: Option<u32> = None;
         let messages = self.transport.poll();
-        let lp = self.local_player();
 
         for msg in messages {
             match msg {
-                NetMessage::Sync { magic, rom_hash } => {
-                    if magic != NetMessage::SYNC_MAGIC {
-                        continue;
-                    }
-                    if rom_hash != self.rom_hash {
-                        return Err(NetplayError::RomMismatch);
-                    }
-                    self.synced = true;
-                }
+                NetMessage::Sync {
+                    magic,
+                    rom_hash,
+                    settings,
+                    save,
+                } => self.ingest_sync(magic, &rom_hash, settings, save)?,
                 NetMessage::Input {
                     player,
                     frame,
                     input,
+                    device,
                 } => {
-                    // Ignore an out-of-range or self-addressed player index
-                    // (a malformed / foreign packet must never corrupt our own
-                    // authored input or index out of bounds).
-                    if player >= self.config.num_players || player == lp {
-                        continue;
-                    }
-                    self.ensure_frame(frame);
-                    let slot = &mut self.history[frame as usize];
-                    let cell = &mut slot.players[player as usize];
-                    let prev = cell.input;
-                    let already_simulated = slot.simulated;
-                    cell.input = input;
-                    cell.confirmed = true;
-                    // Misprediction: a frame we already ran used a value that
-                    // the just-arrived real input contradicts. Re-running it
-                    // (and everything after) is required. A second copy of an
-                    // identical input (idempotent resend, whether previously a
-                    // prediction or already confirmed) leaves `prev == input`,
-                    // so it is correctly NOT treated as a misprediction.
-                    if already_simulated && prev != input {
+                    if let Some(frame) = self.ingest_input(player, frame, input, device) {
                         earliest_mispredict =
                             Some(earliest_mispredict.map_or(frame, |e| e.min(frame)));
                     }
@@ -609,7 +1128,17 @@ impl<T: Transport> RollbackSession<T> {
                 NetMessage::InputAck { frame } => {
                     self.remote_ack_frame =
                         Some(self.remote_ack_frame.map_or(frame, |a| a.max(frame)));
+                    self.tuner.on_ack(frame);
                 }
+                NetMessage::InputDelay { frame, delay } => {
+                    self.ingest_input_delay(frame, u32::from(delay));
+                }
+                NetMessage::Chat { player, seq, text } => {
+                    if let Some(ack) = self.chat.receive(player, seq, text) {
+                        self.transport.send(&ack);
+                    }
+                }
+                NetMessage::ChatAck { player, to, seq } => self.chat.ack(player, to, seq),
                 NetMessage::Checksum {
                     frame,
                     hash,
@@ -629,12 +1158,12 @@ impl<T: Transport> RollbackSession<T> {
                             .record(frame, local, hash, local_fb, fb_hash);
                         self.remote_checksums[frame as usize] = None;
                         if local != hash {
-                            return Err(NetplayError::Desync {
+                            self.on_mismatch(NetplayError::Desync {
                                 frame,
                                 local,
                                 remote: hash,
                                 same_framebuffer: local_fb == fb_hash,
-                            });
+                            })?;
                         }
                     }
                     let _ = nes;
@@ -646,14 +1175,66 @@ impl<T: Transport> RollbackSession<T> {
                 // `Roster`: consumed by the N-peer handshake
                 // (`mesh_net::MeshHost`/`MeshJoiner`) BEFORE the session exists;
                 // a stray one reaching the running session is ignored (it never
-                // affects deterministic state).
-                NetMessage::Quality { .. } | NetMessage::Roster { .. } => {}
+                // affects deterministic state). So are `Settings` and the
+                // save transfer, which only the connection handshake handles.
+                // A `Sealed` envelope is opened by a secured transport before
+                // the session sees it; one that reaches it unopened is noise.
+                NetMessage::Quality { .. }
+                | NetMessage::Roster { .. }
+                | NetMessage::Settings { .. }
+                | NetMessage::SaveChunk { .. }
+                | NetMessage::SaveAck { .. }
+                | NetMessage::Sealed { .. } => {}
+                NetMessage::StateChunk {
+                    kind,
+                    frame,
+                    index,
+                    count,
+                    data,
+                } => self.ingest_state_chunk(kind, frame, index, count, data)?,
+                NetMessage::StateAck {
+                    player,
+                    frame,
+                    index,
+                } => {
+                    if let Some(out) = self.outgoing_state.as_mut() {
+                        out.ack(player, frame, index);
+                    }
+                }
+                NetMessage::ResyncRequest { frame, .. } => {
+                    if self.config.desync_recovery
+                        && self.is_host()
+                        && self.recovered_frame.is_none_or(|r| frame >= r)
+                    {
+                        self.begin_state_transfer();
+                    }
+                }
+                NetMessage::JoinRequest { player } => self.accept_join(player),
+                NetMessage::JoinAccept {
+                    player,
+                    base_frame,
+                    join_frame,
+                } => self.ingest_join_accept(player, base_frame, join_frame),
             }
         }
 
         Ok(earliest_mispredict)
     }
 
+    /// Drop every snapshot more than the rollback window behind frame `f`,
+    /// returning one of the freed buffers for reuse.
+    fn prune_snapshots_before(&mut self, f: u32) -> Option<Vec<u8>> {
+        let window = self.config.max_rollback_frames as usize + 1;
+        let mut spare = None;
+        while self.snapshots_floor + window <= f as usize {
+            if let Some(buf) = self.snapshots[self.snapshots_floor].take() {
+                spare.get_or_insert(buf);
+            }
+            self.snapshots_floor += 1;
+        }
+        spare
+    }
+
     /// Restore the canonical confirmed checkpoint and replay forward to
     /// `current_frame`, applying each frame's best-known inputs from history.
     ///
@@ -687,7 +1268,10 @@ impl<T: Transport> RollbackSession<T> {
             // v2.8.0 Phase 3 — encode straight into the ring slot's reused
             // buffer (the old path built a full thumbnail-carrying snapshot
             // AND cloned it: two ~320 KiB allocations per replayed frame).
-            let mut slot = self.snapshots[f as usize].take().unwrap_or_default();
+            let mut slot = self.snapshots[f as usize]
+                .take()
+                .or_else(|| self.prune_snapshots_before(f))
+                .unwrap_or_default();
             nes.snapshot_core_into(&mut slot);
             self.snapshots[f as usize] = Some(slot);
             // A confirmed replay enters `f` (f < boundary) on a canonical
@@ -702,12 +1286,28 @@ impl<T: Transport> RollbackSession<T> {
             if f < confirmed_boundary {
                 self.confirmed_entering[f as usize] = Some(Self::gameplay_digest(nes));
             }
+            let n = self.config.num_players;
+            let recording = f < confirmed_boundary
+                && self
+                    .recording
+                    .as_mut()
+                    .is_some_and(|rec| rec.enter(nes, f, n));
 
             self.apply_and_run(nes, f);
             self.history[f as usize].simulated = true;
             count += 1;
 
             if f < confirmed_boundary {
+                if recording
+                    && let (Some(inputs), Some(devices), Some(rec)) = (
+                        self.confirmed_input(f),
+                        self.confirmed_devices(f),
+                        self.recording.as_mut(),
+                    )
+                {
+                    let n = usize::from(n);
+                    rec.record(&inputs[..n], &devices[..n], nes.framebuffer());
+                }
                 // The resulting state is the canonical entering state of
                 // `f + 1`. Advance the checkpoint (we restore the full
                 // snapshot, whose audio transients are harmless for replay)
@@ -735,7 +1335,7 @@ impl<T: Transport> RollbackSession<T> {
     /// snapshot, which also serializes audio-synthesis transients that vary
     /// with audio-drain history but never affect future frames). This is the
     /// right cross-peer desync / sync digest.
-    fn gameplay_digest(nes: &Nes) -> u64 {
+    pub(crate) fn gameplay_digest(nes: &Nes) -> u64 {
         Self::gameplay_digest_parts(nes).0
     }
 
@@ -756,6 +1356,8 @@ impl<T: Transport> RollbackSession<T> {
     /// Apply every player's input for `frame` from history and run one
     /// emulator frame. Each player index maps directly to its controller port;
     /// the Four Score adapter is enabled when there are more than two players.
+    /// A player's expansion device, when one rides with the input, is driven
+    /// too (attaching it if the port has none).
     fn apply_and_run(&self, nes: &mut Nes, frame: u32) {
         let slot = self.history[frame as usize];
         let n = self.config.num_players as usize;
@@ -765,14 +1367,16 @@ impl<T: Transport> RollbackSession<T> {
         nes.set_four_score(n > 2);
         for (port, cell) in slot.players.iter().enumerate().take(n) {
             nes.set_buttons(port, Buttons::from_bits_truncate(cell.input));
+            cell.device.apply(nes);
         }
         let _ = nes.run_frame();
     }
 
     /// Fill each not-yet-confirmed remote player's input for `frame` with a
     /// prediction: repeat that player's most recent confirmed input (the
-    /// standard GGPO heuristic). If nothing is known yet, predict "no
-    /// buttons". The local player's cell is left untouched (we authored it).
+    /// standard GGPO heuristic) and [`device::predict`] its device. If nothing
+    /// is known yet, predict "no buttons" and no device. The local player's
+    /// cell is left untouched (we authored it).
     fn predict_remotes(&mut self, frame: u32) {
         let n = self.config.num_players;
         let lp = self.local_player();
@@ -785,16 +1389,18 @@ impl<T: Transport> RollbackSession<T> {
                 continue;
             }
             // Walk back to this player's most recent confirmed input.
-            let mut predicted = 0u8;
+            let mut predicted = (0u8, DeviceInput::None);
             let mut f = frame;
             while f > 0 {
                 f -= 1;
-                if self.history[f as usize].players[p].confirmed {
-                    predicted = self.history[f as usize].players[p].input;
+                let known = self.history[f as usize].players[p];
+                if known.confirmed {
+                    predicted = (known.input, device::predict(known.device));
                     break;
                 }
             }
-            self.history[frame as usize].players[p].input = predicted;
+            let cell = &mut self.history[frame as usize].players[p];
+            (cell.input, cell.device) = predicted;
         }
     }
 
@@ -845,18 +1451,430 @@ impl<T: Transport> RollbackSession<T> {
                 self.diagnostics
                     .record(f, local_combined, remote_combined, local_fb, remote_fb);
                 if local_combined != remote_combined {
-                    return Err(NetplayError::Desync {
+                    self.on_mismatch(NetplayError::Desync {
                         frame: f,
                         local: local_combined,
                         remote: remote_combined,
                         same_framebuffer: local_fb == remote_fb,
-                    });
+                    })?;
                 }
             }
         }
         Ok(())
     }
 
+    /// `true` if this peer is the desync-recovery authority (player 0).
+    const fn is_host(&self) -> bool {
+        self.config.local_player == 0
+    }
+
+    /// Handle a checksum mismatch already recorded in the diagnostics. Without
+    /// recovery it is the fatal `desync`. With recovery, a mismatch predating
+    /// the newest recovery is stale; otherwise, once the hysteresis threshold
+    /// confirms the divergence, the host starts a state transfer and a peer
+    /// asks the host for one.
+    fn on_mismatch(&mut self, desync: NetplayError) -> Result<(), NetplayError> {
+        if !self.config.desync_recovery {
+            return Err(desync);
+        }
+        let NetplayError::Desync { frame, .. } = desync else {
+            return Err(desync);
+        };
+        if self.recovered_frame.is_some_and(|r| frame < r)
+            || self.diagnostics.consecutive_mismatches() < self.diagnostics.desync_threshold()
+        {
+            return Ok(());
+        }
+        if self.is_host() {
+            self.begin_state_transfer();
+        } else if self.incoming_state.is_none() && self.pending_restore.is_none() {
+            self.resync_requested.get_or_insert(frame);
+        }
+        Ok(())
+    }
+
+    /// Host: start streaming the canonical confirmed checkpoint to every
+    /// other player, unless a transfer is already in flight.
+    fn begin_state_transfer(&mut self) {
+        if self.outgoing_state.is_some() {
+            return;
+        }
+        let Some((frame, snapshot)) = self.checkpoint.as_ref() else {
+            return;
+        };
+        let peers = (1..self.config.num_players).fold(0u8, |m, p| m | (1 << p));
+        self.outgoing_state = Some(OutgoingState::new(*frame, snapshot, peers));
+        self.recovered_frame = Some(*frame);
+    }
+
+    /// Host: answer a newcomer's [`NetMessage::JoinRequest`] for a vacated
+    /// slot (or as a spectator). The newcomer takes over at a `join_frame`
+    /// [`JOIN_LEAD_FRAMES`] past the newest frame we have authored, starting
+    /// from our canonical checkpoint; the accept is broadcast with every
+    /// resend of the transfer (see `pump_recovery`). A repeated request for
+    /// the join under way is answered again; any other request waits until
+    /// no transfer is in flight (the newcomer keeps asking).
+    fn accept_join(&mut self, player: u8) {
+        if !self.is_host() {
+            return;
+        }
+        // Re-validate the ROM for the newcomer alongside the accept.
+        let sync = NetMessage::Sync {
+            magic: NetMessage::SYNC_MAGIC,
+            rom_hash: self.rom_hash,
+            settings: self.config.settings_digest,
+            save: self.config.save_digest,
+        };
+        if let Some((joining, base_frame, join_frame)) = self.join {
+            if joining == player {
+                self.transport.send(&sync);
+                self.transport.send(&NetMessage::JoinAccept {
+                    player,
+                    base_frame,
+                    join_frame,
+                });
+            }
+            return;
+        }
+        if self.outgoing_state.is_some() {
+            return;
+        }
+        let Some((base_frame, snapshot)) = self.checkpoint.as_ref() else {
+            return;
+        };
+        let join_frame = self.current_frame + self.config.input_delay + JOIN_LEAD_FRAMES;
+        let peers = if player == NetMessage::SPECTATOR {
+            OutgoingState::SPECTATOR_BIT
+        } else if self.is_vacant(player) {
+            if let Some(vacancy) = self.vacancies[usize::from(player)].as_mut() {
+                vacancy.until = join_frame;
+            }
+            1 << player
+        } else {
+            return;
+        };
+        self.outgoing_state = Some(OutgoingState::new(*base_frame, snapshot, peers).for_join());
+        self.join = Some((player, *base_frame, join_frame));
+        self.chat.reset_peer(player);
+        self.transport.send(&sync);
+    }
+
+    /// Fold in the host's (re)broadcast [`NetMessage::JoinAccept`]. A
+    /// newcomer only cares about its own slot; an existing peer starts
+    /// resending its tail for anyone's join.
+    fn ingest_join_accept(&mut self, player: u8, base_frame: u32, join_frame: u32) {
+        if self.is_host()
+            || (self.joining && (player != self.local_player() || self.join.is_some()))
+        {
+            return;
+        }
+        // A newcomer taking a slot numbers its chat from zero.
+        if self.join != Some((player, base_frame, join_frame)) {
+            self.chat.reset_peer(player);
+        }
+        self.join = Some((player, base_frame, join_frame));
+        if self.joining {
+            self.local_from = join_frame;
+        }
+    }
+
+    /// Host: author idle input for every vacated slot up to the newest frame
+    /// we author our own input for (and never past a newcomer's join frame),
+    /// sending each like a local input. Returns the earliest already-run frame
+    /// whose prediction the authored input contradicted, if any.
+    fn author_vacancies(&mut self) -> Option<u32> {
+        let newest = self.current_frame + self.config.input_delay;
+        let mut earliest_mispredict = None;
+        for player in 0..self.config.num_players {
+            let Some(mut vacancy) = self.vacancies[usize::from(player)] else {
+                continue;
+            };
+            while vacancy.next <= newest && vacancy.next < vacancy.until {
+                let frame = vacancy.next;
+                self.ensure_frame(frame);
+                let slot = &mut self.history[frame as usize];
+                let cell = &mut slot.players[usize::from(player)];
+                if slot.simulated && (cell.input != 0 || cell.device != DeviceInput::None) {
+                    earliest_mispredict.get_or_insert(frame);
+                }
+                *cell = PlayerInput {
+                    input: 0,
+                    device: DeviceInput::None,
+                    confirmed: true,
+                };
+                self.transport.send(&NetMessage::Input {
+                    player,
+                    frame,
+                    input: 0,
+                    device: DeviceInput::None,
+                });
+                vacancy.next += 1;
+            }
+            self.vacancies[usize::from(player)] = Some(vacancy);
+        }
+        earliest_mispredict
+    }
+
+    /// Retire the join in flight once it has landed. A newcomer's own input
+    /// confirmed `input_delay + max_rollback_frames` past its join frame
+    /// proves it has confirmed everything before it (it could not have run
+    /// that far ahead otherwise); a spectator sends no input, so its join
+    /// lands once this peer has confirmed the join frame. The host then hands
+    /// the slot over for good. A join whose newcomer never shows up is
+    /// abandoned after [`JOIN_TIMEOUT_FRAMES`].
+    fn finish_join(&mut self) {
+        let Some((player, _, join_frame)) = self.join else {
+            return;
+        };
+        let landed = if player == NetMessage::SPECTATOR {
+            self.last_confirmed_frame.is_some_and(|c| c >= join_frame)
+        } else {
+            let proof = join_frame + self.config.input_delay + self.config.max_rollback_frames;
+            self.history
+                .get(proof as usize)
+                .and_then(|slot| slot.players.get(usize::from(player)))
+                .is_some_and(|cell| cell.confirmed)
+        };
+        if landed {
+            if let Some(slot) = self.vacancies.get_mut(usize::from(player))
+                && slot.is_some_and(|v| v.until == join_frame)
+            {
+                *slot = None;
+            }
+        } else if self.current_frame <= join_frame.saturating_add(JOIN_TIMEOUT_FRAMES) {
+            return;
+        }
+        self.join = None;
+    }
+
+    /// Peer: fold in one recovery (or, for a late joiner, join) chunk from the
+    /// host. Every chunk is acknowledged — even a duplicate or stale one — so
+    /// the host stops resending it.
+    ///
+    /// The chunk's `kind` decides who restores it, never whether a
+    /// `JoinAccept` for the same frame happened to arrive first: a join
+    /// transfer is only for the newcomer, and a newcomer acknowledges a
+    /// recovery (its slot is owed one) without letting it displace its own.
+    fn ingest_state_chunk(
+        &mut self,
+        kind: StateTransfer,
+        frame: u32,
+        index: u16,
+        count: u16,
+        data: Vec<u8>,
+    ) -> Result<(), NetplayError> {
+        if self.is_host() {
+            return Ok(());
+        }
+        let (ack, restore) = match kind {
+            StateTransfer::Join => (self.joining, self.joining),
+            StateTransfer::Recovery => (
+                self.config.desync_recovery || self.joining,
+                self.config.desync_recovery && !self.joining,
+            ),
+        };
+        if !ack {
+            return Ok(());
+        }
+        self.transport.send(&NetMessage::StateAck {
+            player: self.local_player(),
+            frame,
+            index,
+        });
+        if !restore {
+            return Ok(());
+        }
+        // Already applied, already reassembled, or older than the transfer in
+        // progress: nothing to do.
+        if self.recovered_frame.is_some_and(|r| frame <= r)
+            || self
+                .pending_restore
+                .as_ref()
+                .is_some_and(|(f, _)| *f >= frame)
+            || self
+                .incoming_state
+                .as_ref()
+                .is_some_and(|s| s.frame() > frame)
+        {
+            return Ok(());
+        }
+        if self
+            .incoming_state
+            .as_ref()
+            .is_none_or(|s| s.frame() != frame)
+        {
+            self.incoming_state = IncomingState::new(frame, count);
+        }
+        let Some(incoming) = self.incoming_state.as_mut() else {
+            return Ok(());
+        };
+        if incoming.accept(index, count, data) {
+            let incoming = self.incoming_state.take().expect("checked above");
+            self.pending_restore = Some((frame, incoming.finish()?));
+        }
+        Ok(())
+    }
+
+    /// Host: `true` while `outgoing_state` serves a late join rather than a
+    /// recovery.
+    fn outgoing_is_join(&self) -> bool {
+        self.outgoing_state
+            .as_ref()
+            .is_some_and(|out| out.kind() == StateTransfer::Join)
+    }
+
+    /// Peer: install a fully-received recovery state as the new canonical
+    /// checkpoint once `current_frame` has reached its frame. Every checksum
+    /// and confirmed digest from that frame on is forgotten (they were derived
+    /// from the diverged state); the caller's `resync` then replays from the
+    /// new checkpoint and recomputes them. Returns `true` if a state was
+    /// installed.
+    ///
+    /// A late joiner instead installs the state of its `JoinAccept`'s
+    /// `base_frame` straight away (once the host's `Sync` has validated the
+    /// ROM), jumping `current_frame` to it.
+    fn apply_pending_restore(&mut self) -> bool {
+        let Some(frame) = self.pending_restore.as_ref().map(|(f, _)| *f) else {
+            return false;
+        };
+        if self.joining {
+            let Some((_, base, _)) = self.join else {
+                return false;
+            };
+            if frame != base {
+                self.pending_restore = None;
+                return false;
+            }
+            if !self.synced {
+                return false;
+            }
+            self.current_frame = frame;
+        } else if frame > self.current_frame {
+            return false;
+        }
+        let (frame, snapshot) = self.pending_restore.take().expect("checked above");
+        self.ensure_frame(frame);
+        let from = frame as usize;
+        self.confirmed_hashes[from..].fill(None);
+        self.local_checksums[from..].fill(None);
+        self.remote_checksums[from..].fill(None);
+        self.confirmed_entering[from..].fill(None);
+        self.checkpoint = Some((frame, snapshot));
+        // The host only sends a checkpoint it derived from confirmed inputs,
+        // so everything before `frame` is confirmed by construction.
+        if let Some(before) = frame.checked_sub(1) {
+            self.last_confirmed_frame =
+                Some(self.last_confirmed_frame.map_or(before, |c| c.max(before)));
+        }
+        self.recovered_frame = Some(frame);
+        self.resync_requested = None;
+        // The timeline continues from the host's state, which a replay of
+        // what was recorded so far does not reach.
+        if let Some(rec) = self.recording.as_mut() {
+            rec.interrupt();
+        }
+        if self.joining {
+            self.joining = false;
+        } else {
+            self.diagnostics.record_recovery(frame);
+        }
+        true
+    }
+
+    /// Drive an in-flight transfer one tick: the host resends its still-
+    /// unacknowledged chunks — preceded, for a late join, by the `JoinAccept`
+    /// they belong to — and records a recovery once every peer has them all;
+    /// a diverged peer repeats its resync request until the host's transfer
+    /// starts arriving.
+    fn pump_recovery(&mut self) {
+        if let Some(out) = self.outgoing_state.as_mut() {
+            if out.is_complete() {
+                let (frame, kind) = (out.frame(), out.kind());
+                self.outgoing_state = None;
+                if kind == StateTransfer::Recovery {
+                    self.diagnostics.record_recovery(frame);
+                }
+            } else {
+                if out.kind() == StateTransfer::Join
+                    && let Some((player, base_frame, join_frame)) = self.join
+                {
+                    self.transport.send(&NetMessage::JoinAccept {
+                        player,
+                        base_frame,
+                        join_frame,
+                    });
+                }
+                for msg in out.next_unacked(STATE_CHUNKS_PER_TICK) {
+                    self.transport.send(&msg);
+                }
+            }
+        }
+        if let Some(frame) = self.resync_requested
+            && self.incoming_state.is_none()
+            && self.pending_restore.is_none()
+        {
+            self.transport.send(&NetMessage::ResyncRequest {
+                player: self.local_player(),
+                frame,
+            });
+        }
+    }
+
+    /// Peer: adopt the host's input-delay schedule, unless it is one already
+    /// heard (a resend) or older than one heard.
+    fn ingest_input_delay(&mut self, frame: u32, delay: u32) {
+        if self.is_host() || self.delay_heard.is_some_and(|heard| heard >= frame) {
+            return;
+        }
+        self.delay_heard = Some(frame);
+        self.pending_delay = Some((frame, delay.min(MAX_INPUT_DELAY)));
+    }
+
+    /// Host: repeat the pending input-delay schedule until its frame comes.
+    fn send_delay_schedule(&mut self) {
+        if let Some((frame, delay)) = self.pending_delay.filter(|_| self.is_host()) {
+            self.transport.send(&NetMessage::InputDelay {
+                frame,
+                delay: u8::try_from(delay).unwrap_or(u8::MAX),
+            });
+        }
+    }
+
+    /// Switch to the scheduled input delay once its frame has come (at once,
+    /// if the schedule arrived late), logging the change.
+    fn apply_due_delay(&mut self) {
+        let Some((frame, delay)) = self.pending_delay else {
+            return;
+        };
+        if self.current_frame < frame {
+            return;
+        }
+        self.pending_delay = None;
+        let from = self.config.input_delay;
+        if delay != from {
+            self.config.input_delay = delay;
+            self.delay_changes.push(DelayChange {
+                frame: self.current_frame,
+                from,
+                to: delay,
+            });
+        }
+    }
+
+    /// Count this tick, and the frames a misprediction re-ran, for the
+    /// input-delay tuner. A host running [`DelayMode::Auto`] schedules a
+    /// proposal that differs from its current delay.
+    fn tune(&mut self, resimulated: u32) {
+        let current = self.config.input_delay;
+        let proposal = self.tuner.on_tick(resimulated, current);
+        if self.config.delay_tuning.mode == DelayMode::Auto
+            && self.pending_delay.is_none()
+            && let Some(delay) = proposal.filter(|&delay| delay != current)
+        {
+            self.schedule_input_delay(delay);
+        }
+    }
+
     /// Send a time-sync hint to the peers.
     fn send_quality(&mut self) {
         self.transport.send(&NetMessage::Quality {
@@ -873,28 +1891,44 @@ impl<T: Transport> RollbackSession<T> {
     /// capped by [`INPUT_RESEND_WINDOW`] so a long outage can't burst the whole
     /// input history in one tick; normally — with acks flowing — only the few
     /// in-flight (latency-sized) frames are resent.
+    ///
+    /// The host resends the vacated slots it authors the same way. While a
+    /// late join is in flight the window instead reaches back to its
+    /// `base_frame`, uncapped, so the newcomer receives the whole confirmed
+    /// tail it must replay.
     fn resend_unacked_local_inputs(&mut self) {
         let lp = self.local_player();
-        let lp_idx = lp as usize;
         // The newest local input we hold (set `input_delay` frames ahead by
-        // `add_local_input`).
-        let newest = self.current_frame + self.config.input_delay;
+        // `add_local_input`, or further if the delay has since shrunk).
+        let ahead = self.current_frame + self.config.input_delay;
+        let newest = self.newest_local.map_or(ahead, |n| n.max(ahead));
         // First un-acked frame (the remote has everything <= remote_ack_frame).
         let first_unacked = self.remote_ack_frame.map_or(0, |a| a.saturating_add(1));
-        let start = first_unacked.max(newest.saturating_sub(INPUT_RESEND_WINDOW));
+        let mut start = first_unacked.max(newest.saturating_sub(INPUT_RESEND_WINDOW));
+        if let Some((_, base_frame, _)) = self.join {
+            start = start.min(base_frame);
+        }
         for frame in start..=newest {
             let idx = frame as usize;
             if idx >= self.history.len() {
                 break;
             }
-            let slot = self.history[idx].players[lp_idx];
-            // Only resend frames we actually authored (confirmed-local).
-            if slot.confirmed {
-                self.transport.send(&NetMessage::Input {
-                    player: lp,
-                    frame,
-                    input: slot.input,
-                });
+            for player in 0..self.config.num_players {
+                let authored = if player == lp {
+                    frame >= self.local_from
+                } else {
+                    self.vacancies[usize::from(player)].is_some_and(|v| frame < v.until)
+                };
+                let cell = self.history[idx].players[usize::from(player)];
+                // Only resend frames we actually authored (confirmed-local).
+                if authored && cell.confirmed {
+                    self.transport.send(&NetMessage::Input {
+                        player,
+                        frame,
+                        input: cell.input,
+                        device: cell.device,
+                    });
+                }
             }
         }
     }
@@ -0,0 +1,572 @@
+//! Emulation-settings negotiation for the netplay handshake.
+//!
+//! Two peers running the same ROM can still diverge when they run it under
+//! different emulation settings: OAM decay, a PPU or 2A03 die revision, the
+//! power-on RAM fill, the extra-scanline overclock, Game Genie codes, a
+//! game-database mirroring correction, the Vs. DIP switches or a different
+//! expansion device. None of these are part of the save-state, so the
+//! session's checksums only notice the difference once it has already
+//! desynced.
+//!
+//! [`NetSettings`] captures every such setting from a live [`Nes`] into a
+//! canonical encoding. Its [`digest`](NetSettings::digest) rides in every
+//! [`NetMessage::Sync`](crate::NetMessage::Sync), so peers compare settings
+//! before the first frame. When the digests differ, the full encoding is
+//! exchanged as [`NetMessage::Settings`](crate::NetMessage::Settings): a
+//! joiner either adopts the host's settings for the session
+//! ([`apply`](NetSettings::apply)) or refuses with a per-setting
+//! [`diff`](NetSettings::diff).
+//!
+//! The frontend's `fast_dotloop` switch is deliberately absent: both dot
+//! paths produce the identical frame, so peers may differ on it freely.
+
+use core::fmt;
+
+use rustynes_core::rustynes_mappers::Mirroring;
+use rustynes_core::{
+    BandaiHyperShotState, Cpu2A03Revision, FamilyKeyboardState, HoriTrackState, InputDevice,
+    KonamiHyperShotState, Nes, OekaKidsTabletState, PachinkoState, PaletteInit, PowerGloveState,
+    PowerOnRam, PowerPadState, PpuRevision, SnesMouseState, TopRiderState, TurboFileState,
+    VausState, ZapperState,
+};
+
+use crate::message::fnv1a64;
+
+/// Version byte leading the canonical encoding. Bumped when a setting is
+/// added, so an older peer's [`NetSettings::from_bytes`] refuses the newer
+/// layout instead of misreading it.
+const ENCODING_VERSION: u8 = 1;
+
+/// Every determinism-relevant emulation setting of one peer.
+///
+/// Captured from the running core with [`Self::capture`]; the one setting
+/// the core does not hold (sprite-limit removal, a frontend enhancement) is
+/// supplied with [`Self::with_sprite_limit_disabled`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NetSettings {
+    oam_decay: bool,
+    ppu_revision: PpuRevision,
+    power_up_palette: PaletteInit,
+    power_on_ram: PowerOnRam,
+    cpu_revision: Cpu2A03Revision,
+    extra_scanlines: u16,
+    disable_sprite_limit: bool,
+    mirroring_override: Option<Mirroring>,
+    vs_dip: u8,
+    /// Canonical (upper-case) Game Genie codes, sorted.
+    genie_codes: Vec<String>,
+    /// The expansion device kind on each port (see `device_tag`), `0` = none.
+    expansion: [u8; 2],
+}
+
+/// One setting two peers disagree on, for the mismatch report.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SettingDiff {
+    /// The setting's name.
+    pub name: &'static str,
+    /// This peer's value.
+    pub local: String,
+    /// The other peer's value.
+    pub remote: String,
+}
+
+impl fmt::Display for SettingDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} here, {} on the peer",
+            self.name, self.local, self.remote
+        )
+    }
+}
+
+impl NetSettings {
+    /// The most Game Genie codes an encoding may carry. Bounds the decode of
+    /// a hostile [`NetMessage::Settings`](crate::NetMessage::Settings).
+    pub const MAX_GENIE_CODES: usize = 32;
+
+    /// Read every determinism-relevant setting from `nes`.
+    #[must_use]
+    pub fn capture(nes: &Nes) -> Self {
+        let mut genie_codes: Vec<String> = nes
+            .genie_codes()
+            .map(|c| c.code().to_ascii_uppercase())
+            .collect();
+        genie_codes.sort();
+        genie_codes.truncate(Self::MAX_GENIE_CODES);
+        Self {
+            oam_decay: nes.oam_decay_enabled(),
+            ppu_revision: nes.ppu_revision(),
+            power_up_palette: nes.power_up_palette(),
+            power_on_ram: nes.power_on_ram(),
+            cpu_revision: nes.cpu_2a03_revision(),
+            extra_scanlines: nes.extra_scanlines(),
+            disable_sprite_limit: false,
+            mirroring_override: nes.mirroring_override(),
+            vs_dip: nes.vs_dip(),
+            genie_codes,
+            expansion: [0, 1].map(|port| nes.expansion_device(port).as_ref().map_or(0, device_tag)),
+        }
+    }
+
+    /// Record the frontend's sprite-limit removal, which has no core knob.
+    #[must_use]
+    pub const fn with_sprite_limit_disabled(mut self, disabled: bool) -> Self {
+        self.disable_sprite_limit = disabled;
+        self
+    }
+
+    /// Whether sprite-limit removal is on. [`Self::apply`] cannot set it:
+    /// the frontend owns the switch (staged config today), so peers only
+    /// have to agree on it.
+    #[must_use]
+    pub const fn sprite_limit_disabled(&self) -> bool {
+        self.disable_sprite_limit
+    }
+
+    /// Push these settings into `nes`. Expansion devices are re-created in
+    /// their idle state.
+    ///
+    /// To start a session, apply, power-cycle, then apply again: the cold
+    /// boot is what makes the power-on settings (RAM fill, palette) take
+    /// effect, but it also drops OAM decay, the overclock and the expansion
+    /// devices. Every peer must do the same, or they diverge from frame 0.
+    pub fn apply(&self, nes: &mut Nes) {
+        nes.set_oam_decay(self.oam_decay);
+        nes.set_ppu_revision(self.ppu_revision);
+        nes.set_power_up_palette(self.power_up_palette);
+        nes.set_power_on_ram(self.power_on_ram);
+        nes.set_cpu_2a03_revision(self.cpu_revision);
+        nes.set_extra_scanlines(self.extra_scanlines);
+        nes.set_mirroring_override(self.mirroring_override);
+        nes.set_vs_dip(self.vs_dip);
+        nes.clear_genie_codes();
+        for code in &self.genie_codes {
+            // Every code was read back from a core that accepted it.
+            let _ = nes.add_genie_code(code);
+        }
+        for (port, &tag) in self.expansion.iter().enumerate() {
+            let current = nes.expansion_device(port).as_ref().map_or(0, device_tag);
+            if current != tag {
+                nes.set_expansion_device(port, device_from_tag(tag));
+            }
+        }
+    }
+
+    /// The canonical digest of these settings, as carried in
+    /// [`NetMessage::Sync`](crate::NetMessage::Sync). Never `0`, which on the
+    /// wire means "settings not announced".
+    #[must_use]
+    pub fn digest(&self) -> u64 {
+        fnv1a64(&self.to_bytes()).max(1)
+    }
+
+    /// The canonical encoding, as carried in
+    /// [`NetMessage::Settings`](crate::NetMessage::Settings).
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![
+            ENCODING_VERSION,
+            u8::from(self.oam_decay),
+            u8::from(self.ppu_revision == PpuRevision::Rp2c02G),
+            u8::from(self.power_up_palette == PaletteInit::Blargg),
+        ];
+        match self.power_on_ram {
+            PowerOnRam::Zeroed => out.push(0),
+            PowerOnRam::Seeded(seed) => {
+                out.push(1);
+                out.extend_from_slice(&seed.to_le_bytes());
+            }
+            PowerOnRam::Filled(byte) => out.extend_from_slice(&[2, byte]),
+        }
+        out.push(u8::from(self.cpu_revision == Cpu2A03Revision::Rp2A03H));
+        out.extend_from_slice(&self.extra_scanlines.to_le_bytes());
+        out.push(u8::from(self.disable_sprite_limit));
+        out.push(self.mirroring_override.map_or(0, mirroring_tag));
+        out.push(self.vs_dip);
+        out.extend_from_slice(&self.expansion);
+        let codes = &self.genie_codes[..self.genie_codes.len().min(Self::MAX_GENIE_CODES)];
+        out.push(u8::try_from(codes.len()).unwrap_or(u8::MAX));
+        for code in codes {
+            let bytes = &code.as_bytes()[..code.len().min(8)];
+            out.push(u8::try_from(bytes.len()).unwrap_or(u8::MAX));
+            out.extend_from_slice(bytes);
+        }
+        out
+    }
+
+    /// Decode [`Self::to_bytes`]. `None` on a truncated, trailing, unknown
+    /// or out-of-range encoding.
+    #[must_use]
+    pub fn from_bytes(buf: &[u8]) -> Option<Self> {
+        let mut r = Reader(buf);
+        if r.u8()? != ENCODING_VERSION {
+            return None;
+        }
+        let oam_decay = r.flag()?;
+        let ppu_revision = if r.flag()? {
+            PpuRevision::Rp2c02G
+        } else {
+            PpuRevision::Rp2c02H
+        };
+        let power_up_palette = if r.flag()? {
+            PaletteInit::Blargg
+        } else {
+            PaletteInit::Zeroed
+        };
+        let power_on_ram = match r.u8()? {
+            0 => PowerOnRam::Zeroed,
+            1 => PowerOnRam::Seeded(u64::from_le_bytes(r.take(8)?.try_into().ok()?)),
+            2 => PowerOnRam::Filled(r.u8()?),
+            _ => return None,
+        };
+        let cpu_revision = if r.flag()? {
+            Cpu2A03Revision::Rp2A03H
+        } else {
+            Cpu2A03Revision::Rp2A03G
+        };
+        let extra_scanlines = u16::from_le_bytes(r.take(2)?.try_into().ok()?);
+        let disable_sprite_limit = r.flag()?;
+        let mirroring_override = match r.u8()? {
+            0 => None,
+            tag => Some(mirroring_from_tag(tag)?),
+        };
+        let vs_dip = r.u8()?;
+        let expansion = [r.u8()?, r.u8()?];
+        if expansion
+            .iter()
+            .any(|&t| t != 0 && device_from_tag(t).is_none())
+        {
+            return None;
+        }
+        let count = usize::from(r.u8()?);
+        if count > Self::MAX_GENIE_CODES {
+            return None;
+        }
+        let mut genie_codes = Vec::with_capacity(count);
+        for _ in 0..count {
+            let len = usize::from(r.u8()?);
+            if len > 8 {
+                return None;
+            }
+            genie_codes.push(String::from_utf8(r.take(len)?.to_vec()).ok()?);
+        }
+        if !r.0.is_empty() {
+            return None;
+        }
+        Some(Self {
+            oam_decay,
+            ppu_revision,
+            power_up_palette,
+            power_on_ram,
+            cpu_revision,
+            extra_scanlines,
+            disable_sprite_limit,
+            mirroring_override,
+            vs_dip,
+            genie_codes,
+            expansion,
+        })
+    }
+
+    /// Every setting on which `self` (this peer) and `remote` disagree, in a
+    /// stable order.
+    #[must_use]
+    pub fn diff(&self, remote: &Self) -> Vec<SettingDiff> {
+        let on_off = |b: bool| if b { "on" } else { "off" }.to_string();
+        let mut out = Vec::new();
+        let mut push = |name, local: String, remote: String| {
+            if local != remote {
+                out.push(SettingDiff {
+                    name,
+                    local,
+                    remote,
+                });
+            }
+        };
+        push(
+            "OAM decay",
+            on_off(self.oam_decay),
+            on_off(remote.oam_decay),
+        );
+        push(
+            "PPU revision",
+            format!("{:?}", self.ppu_revision),
+            format!("{:?}", remote.ppu_revision),
+        );
+        push(
+            "power-up palette",
+            format!("{:?}", self.power_up_palette),
+            format!("{:?}", remote.power_up_palette),
+        );
+        push(
+            "power-on RAM",
+            format!("{:?}", self.power_on_ram),
+            format!("{:?}", remote.power_on_ram),
+        );
+        push(
+            "2A03 revision",
+            format!("{:?}", self.cpu_revision),
+            format!("{:?}", remote.cpu_revision),
+        );
+        push(
+            "overclock scanlines",
+            self.extra_scanlines.to_string(),
+            remote.extra_scanlines.to_string(),
+        );
+        push(
+            "sprite limit removal",
+            on_off(self.disable_sprite_limit),
+            on_off(remote.disable_sprite_limit),
+        );
+        push(
+            "mirroring override",
+            format!("{:?}", self.mirroring_override),
+            format!("{:?}", remote.mirroring_override),
+        );
+        push(
+            "Vs. DIP switches",
+            format!("{:#04x}", self.vs_dip),
+            format!("{:#04x}", remote.vs_dip),
+        );
+        push(
+            "Game Genie codes",
+            list_or_none(&self.genie_codes),
+            list_or_none(&remote.genie_codes),
+        );
+        for port in 0..2 {
+            push(
+                if port == 0 {
+                    "expansion device (port 1)"
+                } else {
+                    "expansion device (port 2)"
+                },
+                device_name(self.expansion[port]).to_string(),
+                device_name(remote.expansion[port]).to_string(),
+            );
+        }
+        out
+    }
+}
+
+/// A minimal cursor over an encoding being decoded.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let (head, rest) = self.0.split_at_checked(n)?;
+        self.0 = rest;
+        Some(head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    /// A strict boolean byte: anything but `0`/`1` is malformed.
+    fn flag(&mut self) -> Option<bool> {
+        match self.u8()? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+fn list_or_none(codes: &[String]) -> String {
+    if codes.is_empty() {
+        "none".to_string()
+    } else {
+        codes.join(", ")
+    }
+}
+
+const fn mirroring_tag(m: Mirroring) -> u8 {
+    match m {
+        Mirroring::Horizontal => 1,
+        Mirroring::Vertical => 2,
+        Mirroring::SingleScreenA => 3,
+        Mirroring::SingleScreenB => 4,
+        Mirroring::FourScreen => 5,
+        Mirroring::MapperControlled => 6,
+    }
+}
+
+const fn mirroring_from_tag(tag: u8) -> Option<Mirroring> {
+    Some(match tag {
+        1 => Mirroring::Horizontal,
+        2 => Mirroring::Vertical,
+        3 => Mirroring::SingleScreenA,
+        4 => Mirroring::SingleScreenB,
+        5 => Mirroring::FourScreen,
+        6 => Mirroring::MapperControlled,
+        _ => return None,
+    })
+}
+
+/// The wire tag of an expansion device's kind (its live state is input, not
+/// a setting).
+const fn device_tag(device: &InputDevice) -> u8 {
+    match device {
+        InputDevice::Zapper(_) => 1,
+        InputDevice::Vaus(_) => 2,
+        InputDevice::PowerPad(_) => 3,
+        InputDevice::SnesMouse(_) => 4,
+        InputDevice::FamilyKeyboard(_) => 5,
+        InputDevice::FamilyTrainer(_) => 6,
+        InputDevice::SuborKeyboard(_) => 7,
+        InputDevice::KonamiHyperShot(_) => 8,
+        InputDevice::BandaiHyperShot(_) => 9,
+        InputDevice::OekaKidsTablet(_) => 10,
+        InputDevice::HoriTrack(_) => 11,
+        InputDevice::Pachinko(_) => 12,
+        InputDevice::TopRider(_) => 13,
+        InputDevice::PowerGlove(_) => 14,
+        InputDevice::TurboFile(_) => 15,
+    }
+}
+
+/// A freshly-plugged device of the kind `tag` names. `None` for `0` (no
+/// device) and unknown tags.
+fn device_from_tag(tag: u8) -> Option<InputDevice> {
+    Some(match tag {
+        1 => InputDevice::Zapper(ZapperState::default()),
+        2 => InputDevice::Vaus(VausState::default()),
+        3 => InputDevice::PowerPad(PowerPadState::default()),
+        4 => InputDevice::SnesMouse(SnesMouseState::default()),
+        5 => InputDevice::FamilyKeyboard(FamilyKeyboardState::default()),
+        6 => InputDevice::FamilyTrainer(PowerPadState::default()),
+        7 => InputDevice::SuborKeyboard(FamilyKeyboardState::default()),
+        8 => InputDevice::KonamiHyperShot(KonamiHyperShotState::default()),
+        9 => InputDevice::BandaiHyperShot(BandaiHyperShotState::default()),
+        10 => InputDevice::OekaKidsTablet(OekaKidsTabletState::default()),
+        11 => InputDevice::HoriTrack(HoriTrackState::default()),
+        12 => InputDevice::Pachinko(PachinkoState::default()),
+        13 => InputDevice::TopRider(TopRiderState::default()),
+        14 => InputDevice::PowerGlove(PowerGloveState::default()),
+        15 => InputDevice::TurboFile(TurboFileState::default()),
+        _ => return None,
+    })
+}
+
+const fn device_name(tag: u8) -> &'static str {
+    match tag {
+        1 => "Zapper",
+        2 => "Arkanoid Vaus",
+        3 => "Power Pad",
+        4 => "SNES mouse",
+        5 => "Family BASIC keyboard",
+        6 => "Family Trainer",
+        7 => "Subor keyboard",
+        8 => "Konami Hyper Shot",
+        9 => "Bandai Hyper Shot",
+        10 => "Oeka Kids tablet",
+        11 => "Hori Track",
+        12 => "Pachinko",
+        13 => "Top Rider",
+        14 => "Power Glove",
+        15 => "Turbo File",
+        _ => "none",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom() -> NetSettings {
+        NetSettings {
+            oam_decay: true,
+            ppu_revision: PpuRevision::Rp2c02G,
+            power_on_ram: PowerOnRam::Seeded(0xDEAD_BEEF),
+            extra_scanlines: 40,
+            mirroring_override: Some(Mirroring::Vertical),
+            vs_dip: 0x5A,
+            genie_codes: vec!["SXIOPO".to_string(), "YYKPOYZZ".to_string()],
+            expansion: [0, 1],
+            ..NetSettings::default()
+        }
+        .with_sprite_limit_disabled(true)
+    }
+
+    #[test]
+    fn encoding_roundtrips_and_digest_tracks_every_field() {
+        for s in [NetSettings::default(), custom()] {
+            assert_eq!(NetSettings::from_bytes(&s.to_bytes()), Some(s.clone()));
+            assert_ne!(s.digest(), 0, "0 means \"not announced\"");
+        }
+        assert_ne!(NetSettings::default().digest(), custom().digest());
+        let filled = NetSettings {
+            power_on_ram: PowerOnRam::Filled(0xFF),
+            ..NetSettings::default()
+        };
+        assert_ne!(filled.digest(), NetSettings::default().digest());
+    }
+
+    #[test]
+    fn diff_names_each_disagreeing_setting() {
+        let diff = custom().diff(&NetSettings::default());
+        let names: Vec<_> = diff.iter().map(|d| d.name).collect();
+        assert_eq!(
+            names,
+            [
+                "OAM decay",
+                "PPU revision",
+                "power-on RAM",
+                "overclock scanlines",
+                "sprite limit removal",
+                "mirroring override",
+                "Vs. DIP switches",
+                "Game Genie codes",
+                "expansion device (port 2)",
+            ]
+        );
+        assert_eq!(diff[0].to_string(), "OAM decay: on here, off on the peer");
+        assert_eq!(diff[7].local, "SXIOPO, YYKPOYZZ");
+        assert_eq!(diff[8].local, "Zapper");
+        assert!(custom().diff(&custom()).is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_encodings() {
+        let good = custom().to_bytes();
+        assert!(NetSettings::from_bytes(&good[..good.len() - 1]).is_none());
+        let mut trailing = good.clone();
+        trailing.push(0);
+        assert!(NetSettings::from_bytes(&trailing).is_none());
+        let mut version = good.clone();
+        version[0] = ENCODING_VERSION + 1;
+        assert!(NetSettings::from_bytes(&version).is_none());
+        let mut flag = good;
+        flag[1] = 2;
+        assert!(NetSettings::from_bytes(&flag).is_none());
+    }
+
+    #[test]
+    fn apply_then_capture_is_the_identity() {
+        let mut nes = Nes::from_rom(&nrom()).expect("valid NROM");
+        let mut want = custom();
+        want.mirroring_override = None; // NROM's header mirroring stands
+        want.apply(&mut nes);
+        nes.power_cycle();
+        want.apply(&mut nes);
+        let got = NetSettings::capture(&nes).with_sprite_limit_disabled(true);
+        assert_eq!(got, want);
+    }
+
+    // A minimal NROM (infinite loop). Mirrors the session/spectator test
+    // fixtures.
+    fn nrom() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"NES\x1A");
+        bytes.extend_from_slice(&[1, 1, 0, 0]);
+        bytes.extend_from_slice(&[0u8; 8]);
+        let mut prg = vec![0u8; 16 * 1024];
+        prg[..3].copy_from_slice(&[0x4C, 0x00, 0xC0]);
+        let len = prg.len();
+        prg[len - 6..].copy_from_slice(&[0x00, 0xC0, 0x00, 0xC0, 0x00, 0xC0]);
+        bytes.extend_from_slice(&prg);
+        bytes.extend_from_slice(&vec![0u8; 8 * 1024]);
+        bytes
+    }
+}
@@ -21,9 +21,9 @@
 //! parsing/encoding ([`SignalMessage`]) and the per-server room bookkeeping +
 //! routing decision ([`Relay`]). It does **no** I/O and pulls in **no** async
 //! runtime, so it is unit-tested headlessly in the default build. The async
-//! WebSocket plumbing that drives it lives in `examples/signaling_server.rs`
-//! (behind the `signaling-server` feature), which is a thin loop:
-//! parse a frame → [`Relay::handle`] → send the resulting [`Action`]s out.
+//! WebSocket plumbing that drives it lives in the `rustynes-lobby` server
+//! binary, which is a thin loop: parse a frame → [`Relay::handle`] → send the
+//! resulting [`Action`]s out.
 //!
 //! # Wire format
 //!
@@ -35,14 +35,15 @@
 //! `{ from, to }` slots and are **routed to the `to` peer**.
 //!
 //! ```text
-//! → { "type": "join",      "room": "<code>", "rom_hash": "<hex>", "max_players": 4 }
+//! → { "type": "join",      "room": "<code>", "rom_hash": "<hex>", "max_players": 4, "game": "...", "password": "..." }
 //! ← { "type": "joined",    "slot": 2, "max_players": 4 }        (assigned slot + room size)
 //! ← { "type": "peer-joined","slot": 3 }                         (a higher-slot peer joined → offer to it)
 //! ↔ { "type": "offer",     "from": 1, "to": 3, "sdp": "..." }   (routed to slot 3)
 //! ↔ { "type": "answer",    "from": 3, "to": 1, "sdp": "..." }   (routed to slot 1)
 //! ↔ { "type": "candidate", "from": 1, "to": 3, "candidate": "...", "sdp_mid": "...", "sdp_m_line_index": N }
 //! ← { "type": "peer-left", "slot": 2 }                          (a peer disconnected)
-//! ← { "type": "error",     "reason": "..." }                   (room full / rom mismatch)
+//! ↔ { "type": "chat",      "from": 1, "text": "..." }          (lobby chat → every other peer)
+//! ← { "type": "error",     "reason": "..." }                   (room full / rom mismatch / wrong password)
 //! ```
 //!
 //! The server verifies every peer in a room announced the **same** `rom_hash`
@@ -50,6 +51,20 @@
 //! rejects the joiner. A 2-player session is just the `max_players = 2` case
 //! (and a legacy client that omits `max_players` / `from` / `to` defaults to 2
 //! players and the "other peer" routing).
+//!
+//! The first joiner also names the room's `game` (a display title for the
+//! room list, trimmed to [`MAX_GAME_TITLE`] characters) and may set a
+//! `password`; a later joiner must send the same one. An empty password
+//! leaves the room open. A locked room is still listed (flagged `locked`, so
+//! a browser can ask for the password) but quick-match never picks it. Each
+//! listed room also carries its host's round trip to the server when the
+//! server has measured one ([`Relay::record_ping`]).
+//!
+//! `chat` lets the peers in a room talk before their connection is up. The
+//! server overwrites `from` with the sender's real slot and forwards the line
+//! to every other peer in the room, dropping one that is not a valid chat
+//! line (see [`crate::chat::validate`]); once the session runs, chat moves to
+//! the data channels as [`NetMessage::Chat`](crate::NetMessage::Chat).
 
 use std::collections::HashMap;
 
@@ -70,14 +85,24 @@ pub type ClientId = u64;
 /// them.
 pub const MAX_ROOM_LIST: usize = 256;
 
+/// The longest `game` title a room keeps, in characters. A longer title
+/// from a joiner is cut short, so the room list stays small.
+pub const MAX_GAME_TITLE: usize = 64;
+
+/// The [`SignalMessage::Error`] reason a joiner gets for a wrong room
+/// password, so a server can tell those rejections apart (e.g. to throttle
+/// password guessing).
+pub const WRONG_PASSWORD: &str = "wrong password";
+
 /// One open room's public metadata, for the lobby browser
 /// ([`SignalMessage::RoomList`]).
 ///
 /// Carries only what a joiner needs to decide whether to join: the room `code`,
-/// how many players are present vs. the room's capacity, and the `rom_hash` the
-/// room is playing (so the browser can label / filter by game). It deliberately
-/// exposes **no** SDP, ICE, or per-client identity — the lobby is a directory,
-/// not a transport.
+/// how many players are present vs. the room's capacity, the `rom_hash` and
+/// `game` title the room is playing (so the browser can label / filter by
+/// game), whether it needs a password, and how far its host is from the
+/// server. It deliberately exposes **no** SDP, ICE, password, or per-client
+/// identity — the lobby is a directory, not a transport.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RoomInfo {
     /// The room code a joiner passes to [`SignalMessage::Join`].
@@ -89,6 +114,14 @@ pub struct RoomInfo {
     /// Hex SHA-256 of the ROM the room is playing (may be empty for a room that
     /// announced no hash).
     pub rom_hash: String,
+    /// The game's display title, as the room's first joiner named it (may be
+    /// empty).
+    pub game: String,
+    /// `true` if joining needs the room's password.
+    pub locked: bool,
+    /// The room host's last measured round trip to the server, in
+    /// milliseconds (`None` until the server has measured one).
+    pub ping_ms: Option<u32>,
 }
 
 impl RoomInfo {
@@ -100,12 +133,17 @@ impl RoomInfo {
 
     /// Encode as a flat JSON object (an element of the `rooms` array).
     fn to_json_object(&self) -> String {
+        let ping = self
+            .ping_ms
+            .map_or_else(String::new, |ms| format!(r#","ping_ms":{ms}"#));
         format!(
-            r#"{{"room":{},"players":{},"max_players":{},"rom_hash":{}}}"#,
+            r#"{{"room":{},"players":{},"max_players":{},"rom_hash":{},"game":{},"locked":{}{ping}}}"#,
             json_quote(&self.code),
             self.players,
             self.max_players,
-            json_quote(&self.rom_hash)
+            json_quote(&self.rom_hash),
+            json_quote(&self.game),
+            self.locked
         )
     }
 
@@ -117,6 +155,9 @@ impl RoomInfo {
             players: u8::try_from(json_num_field(obj, "players")?).ok()?,
             max_players: u8::try_from(json_num_field(obj, "max_players")?).ok()?,
             rom_hash: json_str_field(obj, "rom_hash").unwrap_or_default(),
+            game: json_str_field(obj, "game").unwrap_or_default(),
+            locked: json_bool_field(obj, "locked").unwrap_or(false),
+            ping_ms: json_num_field(obj, "ping_ms").and_then(|n| u32::try_from(n).ok()),
         })
     }
 }
@@ -131,8 +172,9 @@ impl RoomInfo {
 pub enum SignalMessage {
     /// Client → server: join `room`, announcing the `rom_hash` (hex) it will
     /// play and the `max_players` the room should hold (2..=4). The first joiner
-    /// sets the room size; subsequent joiners' `max_players` is ignored. Each
-    /// joiner is assigned the next free slot (0..`max_players`).
+    /// sets the room size, title and password; subsequent joiners'
+    /// `max_players` and `game` are ignored. Each joiner is assigned the next
+    /// free slot (0..`max_players`).
     Join {
         /// The room code grouping the peers.
         room: String,
@@ -141,6 +183,11 @@ pub enum SignalMessage {
         /// The total player count the first joiner wants (2..=4). Defaults to 2
         /// when absent (the legacy 2-player wire form).
         max_players: u8,
+        /// The game's display title for the room list (may be empty).
+        game: String,
+        /// The room password: set by the first joiner, required of the rest.
+        /// Empty for an open room (and when absent on the wire).
+        password: String,
     },
     /// Server → client: you are in the room at `slot`, which holds `max_players`
     /// peers total.
@@ -212,6 +259,17 @@ pub enum SignalMessage {
         /// [`SocketAddr`](std::net::SocketAddr) string).
         addr: String,
     },
+    /// Relayed peer→peers: a lobby chat line, sent before (or instead of)
+    /// the session's own [`NetMessage::Chat`](crate::NetMessage::Chat). The
+    /// server stamps `from` with the sender's slot and forwards the line to
+    /// every other peer in the room, dropping text that fails
+    /// [`crate::chat::validate`].
+    Chat {
+        /// The sender's slot (set by the server; ignored from a client).
+        from: u8,
+        /// The line itself.
+        text: String,
+    },
     /// Server → client: a fatal signaling error (room full, rom mismatch, …).
     Error {
         /// A short human-readable reason.
@@ -246,6 +304,8 @@ pub enum SignalMessage {
         rom_hash: String,
         /// The player count to request when *creating* a new room (2..=4).
         max_players: u8,
+        /// The game's display title, used if a new room is created.
+        game: String,
     },
     /// Server → client: a [`QuickMatch`](Self::QuickMatch) landed you in room
     /// `room` at `slot` (which holds `max_players`). Distinct from
@@ -285,6 +345,8 @@ impl SignalMessage {
                 max_players: json_num_field(json, "max_players")
                     .and_then(|n| u8::try_from(n).ok())
                     .unwrap_or(2),
+                game: json_str_field(json, "game").unwrap_or_default(),
+                password: json_str_field(json, "password").unwrap_or_default(),
             }),
             "joined" => Some(Self::Joined {
                 slot: u8::try_from(json_num_field(json, "slot")?).ok()?,
@@ -320,6 +382,10 @@ impl SignalMessage {
                 to: slot_or("to"),
                 addr: json_str_field(json, "addr")?,
             }),
+            "chat" => Some(Self::Chat {
+                from: slot_or("from"),
+                text: json_str_field(json, "text")?,
+            }),
             "error" => Some(Self::Error {
                 reason: json_str_field(json, "reason").unwrap_or_default(),
             }),
@@ -334,6 +400,7 @@ impl SignalMessage {
                 max_players: json_num_field(json, "max_players")
                     .and_then(|n| u8::try_from(n).ok())
                     .unwrap_or(2),
+                game: json_str_field(json, "game").unwrap_or_default(),
             }),
             "matched" => Some(Self::Matched {
                 room: json_str_field(json, "room")?,
@@ -354,10 +421,14 @@ impl SignalMessage {
                 room,
                 rom_hash,
                 max_players,
+                game,
+                password,
             } => format!(
-                r#"{{"type":"join","room":{},"rom_hash":{},"max_players":{max_players}}}"#,
+                r#"{{"type":"join","room":{},"rom_hash":{},"max_players":{max_players},"game":{},"password":{}}}"#,
                 json_quote(room),
-                json_quote(rom_hash)
+                json_quote(rom_hash),
+                json_quote(game),
+                json_quote(password)
             ),
             Self::Joined { slot, max_players } => {
                 format!(r#"{{"type":"joined","slot":{slot},"max_players":{max_players}}}"#)
@@ -396,6 +467,12 @@ impl SignalMessage {
                     json_quote(addr)
                 )
             }
+            Self::Chat { from, text } => {
+                format!(
+                    r#"{{"type":"chat","from":{from},"text":{}}}"#,
+                    json_quote(text)
+                )
+            }
             Self::Error { reason } => {
                 format!(r#"{{"type":"error","reason":{}}}"#, json_quote(reason))
             }
@@ -419,9 +496,11 @@ impl SignalMessage {
             Self::QuickMatch {
                 rom_hash,
                 max_players,
+                game,
             } => format!(
-                r#"{{"type":"quick-match","rom_hash":{},"max_players":{max_players}}}"#,
-                json_quote(rom_hash)
+                r#"{{"type":"quick-match","rom_hash":{},"max_players":{max_players},"game":{}}}"#,
+                json_quote(rom_hash),
+                json_quote(game)
             ),
             Self::Matched {
                 room,
@@ -508,7 +587,7 @@ pub enum Action {
         msg: SignalMessage,
     },
     /// Close client `who` (after delivering any preceding [`Action::Send`]s to
-    /// it). Used to reject a room-full / rom-mismatch joiner.
+    /// it). Used to reject a joiner the room or the server turns away.
     Close {
         /// The client to close.
         who: ClientId,
@@ -516,8 +595,8 @@ pub enum Action {
 }
 
 /// One room: up to `max_players` peers (each assigned the next free slot; the
-/// lower slot of any pair is the WebRTC offerer) and the `rom_hash` the first
-/// joiner announced.
+/// lower slot of any pair is the WebRTC offerer) and the `rom_hash`, title and
+/// password the first joiner announced.
 #[derive(Debug, Default)]
 struct Room {
     /// The clients in the room, in slot order. `slots[i]` is the peer at slot
@@ -528,6 +607,20 @@ struct Room {
     /// The total player count the first joiner requested (2..=4). Once the room
     /// holds this many peers it is full and further joiners are rejected.
     max_players: u8,
+    /// The game title the first joiner announced (for the room list).
+    game: String,
+    /// The password later joiners must send (empty = open room).
+    password: String,
+}
+
+/// What a joiner asks of the room it enters: the fields of a
+/// [`SignalMessage::Join`] (or a [`SignalMessage::QuickMatch`], which never
+/// carries a password).
+struct Entry<'a> {
+    rom_hash: &'a str,
+    max_players: u8,
+    game: &'a str,
+    password: &'a str,
 }
 
 /// The pure signaling **relay**: room bookkeeping + the routing decision, with
@@ -543,6 +636,12 @@ pub struct Relay {
     /// ([`SignalMessage::QuickMatch`] rooms). Deterministic (no RNG in this
     /// I/O-free core), so the server behaves reproducibly in tests.
     quick_match_seq: u64,
+    /// Each client's last measured round trip to the server, in milliseconds
+    /// ([`Relay::record_ping`]).
+    pings: HashMap<ClientId, u32>,
+    /// The most rooms the server holds at once (`None` = no cap). A joiner
+    /// that would open one more is turned away.
+    max_rooms: Option<usize>,
 }
 
 impl Relay {
@@ -552,22 +651,41 @@ impl Relay {
         Self::default()
     }
 
+    /// Cap the number of rooms open at once. Past the cap, a joiner that
+    /// would open a new room gets a "server full" rejection; existing rooms
+    /// still fill up.
+    #[must_use]
+    pub const fn with_max_rooms(mut self, max_rooms: usize) -> Self {
+        self.max_rooms = Some(max_rooms);
+        self
+    }
+
     /// The number of active rooms (diagnostic / tests).
     #[must_use]
     pub fn room_count(&self) -> usize {
         self.rooms.len()
     }
 
+    /// Record `client`'s latest round trip to the server, in milliseconds.
+    ///
+    /// The async layer measures it (e.g. with WebSocket pings); the room
+    /// list reports each room's host's. Forgotten on [`disconnect`](Self::disconnect).
+    pub fn record_ping(&mut self, client: ClientId, rtt_ms: u32) {
+        self.pings.insert(client, rtt_ms);
+    }
+
     /// Handle one inbound `msg` from `client`. Returns the actions to perform.
     ///
-    /// - A `Join` adds the client to the room at the next free slot; a joiner
-    ///   past `max_players` or a rom-hash mismatch is rejected (`Error` +
-    ///   `Close`). The newcomer is told its slot (`Joined`); every *existing*
+    /// - A `Join` adds the client to the room at the next free slot; a wrong
+    ///   password, a joiner past `max_players` or a rom-hash mismatch is
+    ///   rejected (`Error` + `Close`). The newcomer is told its slot (`Joined`); every *existing*
     ///   peer is nudged with `PeerJoined { slot: newcomer }` so it offers to the
     ///   newcomer (lower slot offers to higher).
     /// - An `Offer` / `Answer` / `Candidate` carries a `to` slot and is
     ///   **relayed to that specific peer** in the same room (a 2-peer room with
     ///   a legacy `to = 0` falls back to "the other peer").
+    /// - A `Chat` is stamped with the sender's slot and forwarded to every
+    ///   other peer in the room (an invalid line is dropped).
     /// - Anything else from a client (server→client message types) is ignored.
     #[must_use]
     pub fn handle(&mut self, client: ClientId, msg: SignalMessage) -> Vec<Action> {
@@ -576,16 +694,36 @@ impl Relay {
                 room,
                 rom_hash,
                 max_players,
-            } => self.handle_join(client, &room, &rom_hash, max_players),
+                game,
+                password,
+            } => {
+                let entry = Entry {
+                    rom_hash: &rom_hash,
+                    max_players,
+                    game: &game,
+                    password: &password,
+                };
+                self.handle_join(client, &room, &entry)
+            }
             relayable @ (SignalMessage::Offer { .. }
             | SignalMessage::Answer { .. }
             | SignalMessage::Candidate { .. }
             | SignalMessage::PublicAddr { .. }) => self.relay(client, &relayable),
+            SignalMessage::Chat { text, .. } => self.handle_chat(client, text),
             SignalMessage::ListRooms { rom_hash } => self.handle_list_rooms(client, &rom_hash),
             SignalMessage::QuickMatch {
                 rom_hash,
                 max_players,
-            } => self.handle_quick_match(client, &rom_hash, max_players),
+                game,
+            } => {
+                let entry = Entry {
+                    rom_hash: &rom_hash,
+                    max_players,
+                    game: &game,
+                    password: "",
+                };
+                self.handle_quick_match(client, &entry)
+            }
             // Server→client message types arriving FROM a client are not
             // expected; ignore them rather than trust them.
             _ => Vec::new(),
@@ -599,6 +737,7 @@ impl Relay {
     /// remaining peers' slots are left compacted.
     #[must_use]
     pub fn disconnect(&mut self, client: ClientId) -> Vec<Action> {
+        self.pings.remove(&client);
         let Some(room_code) = self.client_room.remove(&client) else {
             return Vec::new();
         };
@@ -626,14 +765,8 @@ impl Relay {
         actions
     }
 
-    fn handle_join(
-        &mut self,
-        client: ClientId,
-        room_code: &str,
-        rom_hash: &str,
-        req_max_players: u8,
-    ) -> Vec<Action> {
-        match self.add_to_room(client, room_code, rom_hash, req_max_players) {
+    fn handle_join(&mut self, client: ClientId, room_code: &str, entry: &Entry<'_>) -> Vec<Action> {
+        match self.add_to_room(client, room_code, entry) {
             Ok((slot, max_players, existing_peers)) => {
                 Self::join_actions(client, slot, max_players, &existing_peers, None)
             }
@@ -641,6 +774,39 @@ impl Relay {
         }
     }
 
+    /// Forward a lobby [`SignalMessage::Chat`] to every other peer in the
+    /// sender's room, stamped with the sender's slot. A client in no room, or
+    /// a line [`crate::chat::validate`] refuses, gets nothing relayed.
+    fn handle_chat(&self, client: ClientId, text: String) -> Vec<Action> {
+        let Some(room) = self
+            .client_room
+            .get(&client)
+            .and_then(|code| self.rooms.get(code))
+        else {
+            return Vec::new();
+        };
+        let Some(from) = room
+            .slots
+            .iter()
+            .position(|&c| c == client)
+            .and_then(|i| u8::try_from(i).ok())
+        else {
+            return Vec::new();
+        };
+        if crate::chat::validate(&text).is_err() {
+            return Vec::new();
+        }
+        let msg = SignalMessage::Chat { from, text };
+        room.slots
+            .iter()
+            .filter(|&&c| c != client)
+            .map(|&peer| Action::Send {
+                to: peer,
+                msg: msg.clone(),
+            })
+            .collect()
+    }
+
     /// Reply to a [`SignalMessage::ListRooms`] with the open, joinable rooms —
     /// the lobby directory (v2.2.0). Only sent to the requester. An optional
     /// non-empty `rom_hash` filter restricts the listing to rooms playing that
@@ -658,13 +824,9 @@ impl Relay {
     /// Handle a [`SignalMessage::QuickMatch`]: join the client to any open room
     /// playing `rom_hash`, or create a fresh room if none exists (v2.2.0). The
     /// client learns the resolved room via [`SignalMessage::Matched`]; existing
-    /// peers get the usual `PeerJoined` nudge.
-    fn handle_quick_match(
-        &mut self,
-        client: ClientId,
-        rom_hash: &str,
-        max_players: u8,
-    ) -> Vec<Action> {
+    /// peers get the usual `PeerJoined` nudge. Password-locked rooms are never
+    /// picked.
+    fn handle_quick_match(&mut self, client: ClientId, entry: &Entry<'_>) -> Vec<Action> {
         // Prefer an existing open room for this exact game with a free slot.
         // Scan `self.rooms` directly (not `open_rooms`, which truncates at
         // `MAX_ROOM_LIST`) so matchmaking reaches every joinable room even in a
@@ -676,12 +838,13 @@ impl Relay {
             .find(|(_, r)| {
                 !r.slots.is_empty()
                     && r.slots.len() < usize::from(r.max_players)
-                    && (rom_hash.is_empty() || r.rom_hash == rom_hash)
+                    && r.password.is_empty()
+                    && (entry.rom_hash.is_empty() || r.rom_hash == entry.rom_hash)
             })
             .map(|(code, _)| code.clone());
 
         let room_code = target.unwrap_or_else(|| self.next_room_code());
-        match self.add_to_room(client, &room_code, rom_hash, max_players) {
+        match self.add_to_room(client, &room_code, entry) {
             Ok((slot, max, existing_peers)) => {
                 Self::join_actions(client, slot, max, &existing_peers, Some(room_code))
             }
@@ -691,39 +854,50 @@ impl Relay {
         }
     }
 
-    /// The shared room-entry primitive: create/lookup the room, enforce capacity
-    /// + rom-hash matching, add the client, and return `(slot, max_players,
-    /// existing_peers)` — or an error reason for a full / mismatched room.
+    /// The shared room-entry primitive: create/lookup the room, enforce the
+    /// room cap, password, capacity + rom-hash matching, add the client, and
+    /// return `(slot, max_players, existing_peers)` — or an error reason for a
+    /// full / locked / mismatched room.
     fn add_to_room(
         &mut self,
         client: ClientId,
         room_code: &str,
-        rom_hash: &str,
-        req_max_players: u8,
+        entry: &Entry<'_>,
     ) -> Result<(u8, u8, Vec<ClientId>), &'static str> {
+        // Checked before the entry is created, so a turned-away joiner leaves
+        // no ghost room behind.
+        if !self.rooms.contains_key(room_code)
+            && self.max_rooms.is_some_and(|max| self.rooms.len() >= max)
+        {
+            return Err("server full");
+        }
         let room = self.rooms.entry(room_code.to_string()).or_default();
 
-        // The first joiner sets the room size (clamped 2..=4); later joiners
-        // inherit it.
         if room.slots.is_empty() {
-            room.max_players = req_max_players.clamp(2, 4);
-        }
-        let max_players = room.max_players;
-
-        // Reject a joiner past the room's player count. A freshly-created room
-        // (empty `slots`) can never be full here — `max_players` was just clamped
-        // to 2..=4 above — so no ghost-room cleanup is needed on this path.
-        if room.slots.len() >= usize::from(max_players) {
+            // The first joiner sets the room up: its size (clamped 2..=4), rom
+            // hash, title and password. A fresh room can never be full, so
+            // none of the rejections below apply to it.
+            room.max_players = entry.max_players.clamp(2, 4);
+            room.rom_hash = entry.rom_hash.to_string();
+            room.game = entry
+                .game
+                .chars()
+                .filter(|c| !c.is_control())
+                .take(MAX_GAME_TITLE)
+                .collect();
+            room.password = entry.password.to_string();
+        } else if room.password != entry.password {
+            return Err(WRONG_PASSWORD);
+        } else if room.slots.len() >= usize::from(room.max_players) {
             return Err("room full");
-        }
-
-        // The first joiner sets the room's rom hash; the rest must match it
-        // (a non-empty mismatch is rejected; an empty hash skips the check).
-        if room.slots.is_empty() {
-            room.rom_hash = rom_hash.to_string();
-        } else if !room.rom_hash.is_empty() && !rom_hash.is_empty() && room.rom_hash != rom_hash {
+        } else if !room.rom_hash.is_empty()
+            && !entry.rom_hash.is_empty()
+            && room.rom_hash != entry.rom_hash
+        {
+            // An empty hash on either side skips the check.
             return Err("rom mismatch");
         }
+        let max_players = room.max_players;
 
         let slot = u8::try_from(room.slots.len()).unwrap_or(u8::MAX);
         let existing_peers: Vec<ClientId> = room.slots.clone();
@@ -763,7 +937,8 @@ impl Relay {
         actions
     }
 
-    /// A room-full / rom-mismatch rejection: an `Error` then `Close`.
+    /// A rejected joiner (room full, rom mismatch, wrong password, server
+    /// full): an `Error` then `Close`.
     fn reject(client: ClientId, reason: &str) -> Vec<Action> {
         vec![
             Action::Send {
@@ -777,8 +952,9 @@ impl Relay {
     }
 
     /// The open (has a free slot), optionally `rom_hash`-filtered rooms as
-    /// [`RoomInfo`]s, capped at [`MAX_ROOM_LIST`]. The order is unspecified
-    /// (`HashMap` iteration); a client sorts for display.
+    /// [`RoomInfo`]s, capped at [`MAX_ROOM_LIST`]. Password-locked rooms are
+    /// included and flagged. The order is unspecified (`HashMap` iteration); a
+    /// client sorts for display.
     #[must_use]
     pub fn open_rooms(&self, rom_hash_filter: &str) -> Vec<RoomInfo> {
         self.rooms
@@ -791,6 +967,9 @@ impl Relay {
                 players: u8::try_from(r.slots.len()).unwrap_or(u8::MAX),
                 max_players: r.max_players,
                 rom_hash: r.rom_hash.clone(),
+                game: r.game.clone(),
+                locked: !r.password.is_empty(),
+                ping_ms: r.slots.first().and_then(|c| self.pings.get(c)).copied(),
             })
             .collect()
     }
@@ -939,6 +1118,19 @@ fn json_str_field(json: &str, key: &str) -> Option<String> {
     None
 }
 
+/// Extract a boolean field `"key":true` / `"key":false` from a flat JSON
+/// object.
+fn json_bool_field(json: &str, key: &str) -> Option<bool> {
+    let rest = field_value_start(json, key)?;
+    if rest.starts_with("true") {
+        Some(true)
+    } else if rest.starts_with("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
 /// Extract a non-negative integer field `"key":N` from a flat JSON object.
 fn json_num_field(json: &str, key: &str) -> Option<u64> {
     let rest = field_value_start(json, key)?;
@@ -961,6 +1153,8 @@ mod tests {
                 room: "abc123".into(),
                 rom_hash: "deadbeef".into(),
                 max_players: 4,
+                game: "Super \"Mario\" Bros.".into(),
+                password: "hunter2".into(),
             },
             SignalMessage::Joined {
                 slot: 1,
@@ -990,6 +1184,10 @@ mod tests {
                 to: 1,
                 addr: "203.0.113.7:51234".into(),
             },
+            SignalMessage::Chat {
+                from: 2,
+                text: "ready? \"go\" \\o/".into(),
+            },
             SignalMessage::Error {
                 reason: "room full".into(),
             },
@@ -1002,6 +1200,63 @@ mod tests {
         }
     }
 
+    #[test]
+    fn lobby_chat_is_stamped_and_sent_to_the_rest_of_the_room() {
+        let mut relay = Relay::new();
+        let _ = relay.handle(1, join_n("r", "h", 4));
+        let _ = relay.handle(2, join_n("r", "h", 4));
+        let _ = relay.handle(3, join_n("r", "h", 4));
+        let _ = relay.handle(9, join_n("elsewhere", "h", 4));
+        // The client's claimed `from` is replaced by its real slot.
+        let acts = relay.handle(
+            2,
+            SignalMessage::Chat {
+                from: 0,
+                text: "hi".into(),
+            },
+        );
+        let expected = SignalMessage::Chat {
+            from: 1,
+            text: "hi".into(),
+        };
+        assert_eq!(
+            acts,
+            vec![
+                Action::Send {
+                    to: 1,
+                    msg: expected.clone()
+                },
+                Action::Send {
+                    to: 3,
+                    msg: expected
+                },
+            ]
+        );
+        // Invalid lines and roomless senders relay nothing.
+        for text in [
+            String::new(),
+            "a\nb".into(),
+            "x".repeat(crate::chat::MAX_CHAT_BYTES + 1),
+        ] {
+            assert!(
+                relay
+                    .handle(1, SignalMessage::Chat { from: 0, text })
+                    .is_empty()
+            );
+        }
+        assert!(
+            relay
+                .handle(
+                    42,
+                    SignalMessage::Chat {
+                        from: 0,
+                        text: "anyone?".into()
+                    }
+                )
+                .is_empty()
+        );
+    }
+
     #[test]
     fn public_addr_relays_to_the_named_slot() {
         // The native raw-UDP rendezvous rides the same slot-routed relay as the
@@ -1279,7 +1534,9 @@ mod tests {
             SignalMessage::Join {
                 room: "r".into(),
                 rom_hash: "h".into(),
-                max_players: 2
+                max_players: 2,
+                game: String::new(),
+                password: String::new(),
             }
         );
     }
@@ -1301,18 +1558,25 @@ mod tests {
                         players: 1,
                         max_players: 2,
                         rom_hash: "deadbeef".into(),
+                        game: "Contra {J}".into(),
+                        locked: true,
+                        ping_ms: Some(38),
                     },
                     RoomInfo {
                         code: "QM-000001".into(),
                         players: 3,
                         max_players: 4,
                         rom_hash: String::new(),
+                        game: String::new(),
+                        locked: false,
+                        ping_ms: None,
                     },
                 ],
             },
             SignalMessage::QuickMatch {
                 rom_hash: "cafe".into(),
                 max_players: 3,
+                game: "Tetris".into(),
             },
             SignalMessage::Matched {
                 room: "QM-000007".into(),
@@ -1386,6 +1650,7 @@ mod tests {
             SignalMessage::QuickMatch {
                 rom_hash: "aa".into(),
                 max_players: 2,
+                game: String::new(),
             },
         );
         assert!(acts.contains(&Action::Send {
@@ -1408,6 +1673,7 @@ mod tests {
             SignalMessage::QuickMatch {
                 rom_hash: "zz".into(),
                 max_players: 2,
+                game: String::new(),
             },
         );
         let Action::Send {
@@ -1430,6 +1696,114 @@ mod tests {
         assert_eq!(*max_players, 2);
     }
 
+    #[test]
+    fn locked_rooms_need_the_password_and_skip_quick_match() {
+        let mut relay = Relay::new();
+        let _ = relay.handle(1, join_locked("r", "h", "Contra", "hunter2"));
+        for (id, password) in [(2, "hunter3"), (3, "")] {
+            let acts = relay.handle(id, join_locked("r", "h", "", password));
+            assert_eq!(
+                acts,
+                vec![
+                    Action::Send {
+                        to: id,
+                        msg: SignalMessage::Error {
+                            reason: "wrong password".into()
+                        }
+                    },
+                    Action::Close { who: id },
+                ]
+            );
+        }
+
+        // Quick-match passes the locked room by and opens a new one.
+        let acts = relay.handle(
+            4,
+            SignalMessage::QuickMatch {
+                rom_hash: "h".into(),
+                max_players: 2,
+                game: String::new(),
+            },
+        );
+        let Action::Send {
+            msg: SignalMessage::Matched { room, slot: 0, .. },
+            ..
+        } = &acts[0]
+        else {
+            panic!("expected a fresh matched room, got {acts:?}");
+        };
+        assert_ne!(room, "r");
+
+        // The right password gets in.
+        let acts = relay.handle(5, join_locked("r", "h", "", "hunter2"));
+        assert!(acts.contains(&Action::Send {
+            to: 5,
+            msg: SignalMessage::Joined {
+                slot: 1,
+                max_players: 2
+            }
+        }));
+    }
+
+    #[test]
+    fn room_list_reports_title_lock_and_host_ping() {
+        let mut relay = Relay::new();
+        let long = "x".repeat(MAX_GAME_TITLE + 10);
+        let _ = relay.handle(1, join_locked("open", "h", "Tetris\n", ""));
+        let _ = relay.handle(2, join_locked("locked", "h", &long, "hunter2"));
+        relay.record_ping(1, 42);
+        relay.record_ping(2, 7);
+
+        let mut rooms = relay.open_rooms("");
+        rooms.sort_by(|a, b| a.code.cmp(&b.code));
+        assert_eq!(rooms.len(), 2);
+        assert_eq!(rooms[0].code, "locked");
+        assert!(rooms[0].locked);
+        assert_eq!(rooms[0].game.chars().count(), MAX_GAME_TITLE);
+        assert_eq!(rooms[0].ping_ms, Some(7));
+        assert_eq!(rooms[1].game, "Tetris");
+        assert!(!rooms[1].locked);
+        assert_eq!(rooms[1].ping_ms, Some(42));
+
+        // The listing survives the wire and never carries the password.
+        let list = SignalMessage::RoomList { rooms };
+        let json = list.to_json();
+        assert!(!json.contains("hunter2"));
+        assert_eq!(SignalMessage::parse(&json), Some(list));
+
+        // The host's ping goes with it: the room's new first peer has none.
+        let _ = relay.handle(3, join_n("open", "h", 2));
+        let _ = relay.disconnect(1);
+        let rooms = relay.open_rooms("");
+        let open = rooms.iter().find(|r| r.code == "open").unwrap();
+        assert_eq!(open.ping_ms, None);
+    }
+
+    #[test]
+    fn room_cap_turns_away_only_new_rooms() {
+        let mut relay = Relay::new().with_max_rooms(1);
+        let _ = relay.handle(1, join("a", "h"));
+        let acts = relay.handle(2, join("b", "h"));
+        assert!(acts.contains(&Action::Send {
+            to: 2,
+            msg: SignalMessage::Error {
+                reason: "server full".into()
+            }
+        }));
+        assert!(acts.contains(&Action::Close { who: 2 }));
+        assert_eq!(relay.room_count(), 1);
+
+        // The open room still fills up.
+        let acts = relay.handle(3, join("a", "h"));
+        assert!(acts.contains(&Action::Send {
+            to: 3,
+            msg: SignalMessage::Joined {
+                slot: 1,
+                max_players: 2
+            }
+        }));
+    }
+
     fn join(room: &str, hash: &str) -> SignalMessage {
         join_n(room, hash, 2)
     }
@@ -1439,6 +1813,18 @@ mod tests {
             room: room.into(),
             rom_hash: hash.into(),
             max_players,
+            game: String::new(),
+            password: String::new(),
+        }
+    }
+
+    fn join_locked(room: &str, hash: &str, game: &str, password: &str) -> SignalMessage {
+        SignalMessage::Join {
+            room: room.into(),
+            rom_hash: hash.into(),
+            max_players: 2,
+            game: game.into(),
+            password: password.into(),
         }
     }
 }
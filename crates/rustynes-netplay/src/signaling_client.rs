@@ -6,10 +6,10 @@
 //!
 //! # Why a blocking worker, not async
 //!
-//! The reference signaling **server** uses tokio + tokio-tungstenite (behind the
-//! non-default `signaling-server` feature). The **client** must not: it has to
-//! cross-compile to `aarch64-linux-android` for the mobile bridge, where pulling
-//! a tokio runtime is unwanted weight. So this mirrors the proven pattern in
+//! The signaling **server** (the `rustynes-lobby` binary) uses tokio +
+//! tokio-tungstenite. The **client** must not: it has to cross-compile to
+//! `aarch64-linux-android` for the mobile bridge, where pulling a tokio runtime
+//! is unwanted weight. So this mirrors the proven pattern in
 //! `rustynes-cheevos`'s `http.rs`: a single worker thread does the blocking I/O
 //! (here a synchronous [`tungstenite`] WebSocket instead of `ureq` HTTP), and
 //! the caller polls a completion channel from its own thread (here the
@@ -19,7 +19,7 @@
 //! # Transport choice (WebSocket, not HTTP-rendezvous)
 //!
 //! The deployed relay is genuinely WebSocket-on-the-wire (Caddy terminates TLS
-//! to `wss://`, fronting the `signaling_server` example — see
+//! to `wss://`, fronting the `rustynes-lobby` server — see
 //! `docs/netplay-webrtc.md` §3.2/§3.4). To interoperate with that *same*
 //! deployed relay — one relay serving both the browser SDP handshake and this
 //! native raw-UDP rendezvous — the client must speak WebSocket. The synchronous
@@ -0,0 +1,452 @@
+//! Network-conditions simulator: N rollback peers against a real ROM over a
+//! hostile in-memory mesh.
+//!
+//! The determinism harness proves the session stays in sync for a few hundred
+//! frames at a time. Changes to the netcode also need the long view — thousands
+//! of frames over a link that drops, reorders and goes down in bursts — with
+//! the costs that users feel reported alongside the verdict. [`run`] drives
+//! that: every peer is a [`RollbackSession`] with its own [`Nes`], wired
+//! through a [`MeshTransport`] under the chosen [`LinkConditions`] and
+//! [`LinkFaults`], each ticked
+//! once per simulated 60 Hz frame just as the frontend ticks a live session
+//! (author this tick's input, then [`advance`](RollbackSession::advance)).
+//!
+//! The run ends once every peer has confirmed [`SimConfig::frames`] frames.
+//! Its [`SimReport`] carries per-peer [`PeerStats`] (rollbacks, re-simulated
+//! frames, stall time) and a [`SimOutcome`]: the peers agree when they
+//! confirmed the same inputs, reached the same state entering the final
+//! frame, and that state is what a plain no-rollback run of those inputs
+//! reaches.
+//!
+//! Everything — each player's input, every link's delay / loss / reordering /
+//! outages — is drawn from PRNGs seeded by [`SimConfig::seed`], so a failing
+//! run replays exactly from its seed. The `netplay_sim` tool in
+//! `rustynes-test-harness` is the command-line front end; `tests/soak.rs` runs
+//! it as a soak test.
+
+use std::fmt;
+use std::time::Duration;
+
+use rustynes_core::rustynes_mappers::RomError;
+use rustynes_core::{Buttons, FRAME_DURATION_NTSC, Nes};
+
+use crate::rng::SplitMix64;
+use crate::session::{MAX_PLAYERS, NetplayError, RollbackSession, SessionConfig};
+use crate::transport::{LinkConditions, LinkFaults, MeshTransport};
+
+/// Named link presets, one-way, in 60 Hz polls (one poll ≈ 16.7 ms): each
+/// name's [`LinkConditions`] and the [`LinkFaults`] layered on them.
+pub const PROFILES: &[(&str, LinkConditions, LinkFaults)] = &[
+    ("perfect", LinkConditions::PERFECT, LinkFaults::NONE),
+    ("lan", LinkConditions::fixed_latency(1), LinkFaults::NONE),
+    (
+        "wifi",
+        LinkConditions {
+            latency_polls: 2,
+            jitter_polls: 2,
+            drop_prob: 0.02,
+        },
+        LinkFaults::NONE.with_reorder(0.01),
+    ),
+    (
+        "broadband",
+        LinkConditions {
+            latency_polls: 4,
+            jitter_polls: 2,
+            drop_prob: 0.01,
+        },
+        LinkFaults::NONE,
+    ),
+    (
+        "intercontinental",
+        LinkConditions {
+            latency_polls: 9,
+            jitter_polls: 3,
+            drop_prob: 0.02,
+        },
+        LinkFaults::NONE.with_reorder(0.02),
+    ),
+    (
+        "lossy",
+        LinkConditions {
+            latency_polls: 3,
+            jitter_polls: 3,
+            drop_prob: 0.15,
+        },
+        LinkFaults::NONE.with_reorder(0.05),
+    ),
+    (
+        "bursty",
+        LinkConditions {
+            latency_polls: 3,
+            jitter_polls: 2,
+            drop_prob: 0.02,
+        },
+        LinkFaults::NONE.with_reorder(0.02).with_outages(0.01, 20),
+    ),
+];
+
+/// The preset called `name`, if there is one (see [`PROFILES`]).
+#[must_use]
+pub fn profile(name: &str) -> Option<(LinkConditions, LinkFaults)> {
+    PROFILES
+        .iter()
+        .find(|(n, ..)| *n == name)
+        .map(|&(_, conditions, faults)| (conditions, faults))
+}
+
+/// Why a simulation could not start.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum SimError {
+    /// The ROM did not load.
+    #[error("ROM failed to load: {0}")]
+    Rom(#[from] RomError),
+    /// The session needs 2..=4 players.
+    #[error("{0} players: a session needs 2 to {MAX_PLAYERS}")]
+    Players(u8),
+    /// There is nothing to simulate.
+    #[error("frame count must be greater than zero")]
+    NoFrames,
+}
+
+/// One simulation run.
+#[derive(Clone, Copy, Debug)]
+pub struct SimConfig {
+    /// The session every peer runs; `num_players` sets the peer count and
+    /// `local_player` is assigned per peer.
+    pub session: SessionConfig,
+    /// Frames every peer must confirm.
+    pub frames: u32,
+    /// Every directed link's conditions.
+    pub conditions: LinkConditions,
+    /// Every directed link's reordering and outages.
+    pub faults: LinkFaults,
+    /// Seeds the players' inputs and every link's draws.
+    pub seed: u64,
+}
+
+impl SimConfig {
+    /// How many ticks the peers get to confirm [`Self::frames`] before the
+    /// run counts as stuck: four per frame — far beyond what stalls cost even
+    /// over the harshest preset — plus ten seconds to settle.
+    #[must_use]
+    pub const fn tick_budget(&self) -> u32 {
+        self.frames.saturating_mul(4).saturating_add(600)
+    }
+}
+
+/// What one peer went through during a run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PeerStats {
+    /// Ticks that rolled back over a misprediction.
+    pub rollbacks: u32,
+    /// Frames re-simulated, by rollbacks and by checkpoint moves alike.
+    pub resimulated_frames: u64,
+    /// The deepest single rollback, in frames.
+    pub max_rollback: u32,
+    /// Ticks the session stalled to let the others catch up.
+    pub stalled_ticks: u32,
+    /// The frame the peer would produce next.
+    pub current_frame: u32,
+    /// Newest frame the peer confirmed.
+    pub confirmed_frame: Option<u32>,
+}
+
+impl PeerStats {
+    /// Wall-clock time the stalls would have cost at NTSC frame rate.
+    #[must_use]
+    pub fn stall_time(&self) -> Duration {
+        FRAME_DURATION_NTSC * self.stalled_ticks
+    }
+}
+
+/// How a run ended.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SimOutcome {
+    /// Every peer confirmed the same inputs and reached the same state — the
+    /// state a plain run of those inputs reaches.
+    Agreed {
+        /// The gameplay digest entering the final frame.
+        digest: u64,
+    },
+    /// `player` confirmed different inputs than player 0 for `frame`.
+    InputsDiffer {
+        /// The disagreeing peer.
+        player: u8,
+        /// The first frame they disagree on.
+        frame: u32,
+    },
+    /// The peers reached different states entering the final frame.
+    StatesDiffer {
+        /// Each peer's gameplay digest, by player.
+        digests: Vec<u64>,
+    },
+    /// The peers agree with each other, but not with a plain run of the
+    /// inputs they confirmed.
+    ReferenceDiffers {
+        /// The digest the peers agree on.
+        session: u64,
+        /// The digest of the plain run.
+        reference: u64,
+    },
+    /// A session ended with an error.
+    Failed {
+        /// The peer whose session failed.
+        player: u8,
+        /// What it failed with.
+        error: NetplayError,
+    },
+    /// The tick budget ran out before every peer confirmed the final frame.
+    Stuck,
+}
+
+impl SimOutcome {
+    /// `true` for [`SimOutcome::Agreed`].
+    #[must_use]
+    pub const fn is_agreed(&self) -> bool {
+        matches!(self, Self::Agreed { .. })
+    }
+}
+
+impl fmt::Display for SimOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Agreed { digest } => write!(f, "agreed (digest {digest:016x})"),
+            Self::InputsDiffer { player, frame } => {
+                write!(
+                    f,
+                    "player {player} confirmed different inputs at frame {frame}"
+                )
+            }
+            Self::StatesDiffer { digests } => {
+                write!(f, "states differ: ")?;
+                for (p, d) in digests.iter().enumerate() {
+                    write!(f, "{}p{p} {d:016x}", if p == 0 { "" } else { ", " })?;
+                }
+                Ok(())
+            }
+            Self::ReferenceDiffers { session, reference } => write!(
+                f,
+                "peers agree on {session:016x} but a plain run reaches {reference:016x}"
+            ),
+            Self::Failed { player, error } => write!(f, "player {player} failed: {error}"),
+            Self::Stuck => write!(f, "stuck: the final frame was never confirmed"),
+        }
+    }
+}
+
+/// The result of [`run`].
+#[derive(Debug)]
+pub struct SimReport {
+    /// Per-peer statistics, by player.
+    pub peers: Vec<PeerStats>,
+    /// Ticks the run took.
+    pub ticks: u32,
+    /// The verdict.
+    pub outcome: SimOutcome,
+}
+
+/// A simulated player: holds a button combination for a while, then changes
+/// it, the way hands on a pad do.
+struct Player {
+    rng: SplitMix64,
+    held: Buttons,
+}
+
+impl Player {
+    fn new(seed: u64, player: u8) -> Self {
+        Self {
+            rng: SplitMix64::new(seed ^ 0x1111_1111_u64.wrapping_mul(u64::from(player) + 1)),
+            held: Buttons::empty(),
+        }
+    }
+
+    /// This tick's input: a new combination one tick in eight.
+    fn next(&mut self) -> Buttons {
+        if self.rng.next_below(8) == 0 {
+            self.held = Buttons::from_bits_truncate(self.rng.next_u8());
+        }
+        self.held
+    }
+}
+
+/// Run one simulation of `config` on `rom`.
+///
+/// # Errors
+///
+/// Returns [`SimError`] if the ROM does not load, the player count is not
+/// 2..=4, or `frames` is zero. A run that goes wrong is not an error: it is
+/// reported in [`SimReport::outcome`].
+pub fn run(rom: &[u8], config: &SimConfig) -> Result<SimReport, SimError> {
+    let num_players = config.session.num_players;
+    if !(2..=MAX_PLAYERS).contains(&usize::from(num_players)) {
+        return Err(SimError::Players(num_players));
+    }
+    if config.frames == 0 {
+        return Err(SimError::NoFrames);
+    }
+    let mut nes = (0..num_players)
+        .map(|_| Nes::from_rom(rom))
+        .collect::<Result<Vec<_>, _>>()?;
+    let hash = *nes[0].rom_sha256();
+    let mut sessions: Vec<_> = MeshTransport::mesh(num_players, config.conditions, config.seed)
+        .into_iter()
+        .map(|t| t.with_faults(config.faults))
+        .zip(0..)
+        .map(|(t, local_player)| {
+            let cfg = SessionConfig {
+                local_player,
+                ..config.session
+            };
+            RollbackSession::new(cfg, t, hash)
+        })
+        .collect();
+    let mut players: Vec<_> = (0..num_players)
+        .map(|p| Player::new(config.seed, p))
+        .collect();
+    let mut peers = vec![PeerStats::default(); usize::from(num_players)];
+
+    let done = |sessions: &[RollbackSession<MeshTransport>]| {
+        sessions
+            .iter()
+            .all(|s| s.confirmed_entering_digest(config.frames).is_some())
+    };
+    let mut ticks = 0;
+    while !done(&sessions) {
+        if ticks == config.tick_budget() {
+            return Ok(report(peers, &sessions, ticks, SimOutcome::Stuck));
+        }
+        ticks += 1;
+        for (p, ((session, nes), (player, stats))) in sessions
+            .iter_mut()
+            .zip(&mut nes)
+            .zip(players.iter_mut().zip(&mut peers))
+            .enumerate()
+        {
+            let _ = session.add_local_input(player.next());
+            match session.advance(nes) {
+                Ok(outcome) => {
+                    stats.resimulated_frames += u64::from(outcome.resimulated_frames);
+                    if outcome.rolled_back {
+                        stats.rollbacks += 1;
+                        stats.max_rollback = stats.max_rollback.max(outcome.resimulated_frames);
+                    }
+                    if !outcome.produced_frame {
+                        stats.stalled_ticks += 1;
+                    }
+                }
+                Err(error) => {
+                    let player = u8::try_from(p).unwrap_or(u8::MAX);
+                    let outcome = SimOutcome::Failed { player, error };
+                    return Ok(report(peers, &sessions, ticks, outcome));
+                }
+            }
+        }
+    }
+    let outcome = verdict(rom, &sessions, config.frames)?;
+    Ok(report(peers, &sessions, ticks, outcome))
+}
+
+/// Fill in the per-peer frame counters and assemble the report.
+fn report(
+    mut peers: Vec<PeerStats>,
+    sessions: &[RollbackSession<MeshTransport>],
+    ticks: u32,
+    outcome: SimOutcome,
+) -> SimReport {
+    for (stats, session) in peers.iter_mut().zip(sessions) {
+        stats.current_frame = session.current_frame();
+        stats.confirmed_frame = session.last_confirmed_frame();
+    }
+    SimReport {
+        peers,
+        ticks,
+        outcome,
+    }
+}
+
+/// Compare every peer's confirmed inputs and final state with player 0's,
+/// then with a plain run of those inputs.
+fn verdict(
+    rom: &[u8],
+    sessions: &[RollbackSession<MeshTransport>],
+    frames: u32,
+) -> Result<SimOutcome, SimError> {
+    let host = &sessions[0];
+    for frame in 0..frames {
+        let inputs = host.confirmed_input(frame);
+        if let Some(p) = sessions
+            .iter()
+            .position(|s| s.confirmed_input(frame) != inputs)
+        {
+            let player = u8::try_from(p).unwrap_or(u8::MAX);
+            return Ok(SimOutcome::InputsDiffer { player, frame });
+        }
+    }
+    let digests: Vec<u64> = sessions
+        .iter()
+        .map(|s| s.confirmed_entering_digest(frames).unwrap_or_default())
+        .collect();
+    if digests.iter().any(|&d| d != digests[0]) {
+        return Ok(SimOutcome::StatesDiffer { digests });
+    }
+
+    let mut nes = Nes::from_rom(rom)?;
+    nes.set_four_score(host.num_players() > 2);
+    for frame in 0..frames {
+        let inputs = host.confirmed_input(frame).unwrap_or_default();
+        for (port, &bits) in inputs.iter().enumerate() {
+            nes.set_buttons(port, Buttons::from_bits_truncate(bits));
+        }
+        let _ = nes.run_frame();
+    }
+    let reference = RollbackSession::<MeshTransport>::gameplay_digest(&nes);
+    Ok(if reference == digests[0] {
+        SimOutcome::Agreed { digest: reference }
+    } else {
+        SimOutcome::ReferenceDiffers {
+            session: digests[0],
+            reference,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_are_found_by_name() {
+        assert!(profile("bursty").is_some_and(|(_, f)| f.outage_polls > 0));
+        assert!(profile("lan").is_some_and(|(c, _)| c.latency_polls == 1));
+        assert!(profile("dial-up").is_none());
+    }
+
+    #[test]
+    fn bad_configs_are_refused() {
+        let config = SimConfig {
+            session: SessionConfig {
+                num_players: 5,
+                ..SessionConfig::default()
+            },
+            frames: 10,
+            conditions: LinkConditions::PERFECT,
+            faults: LinkFaults::NONE,
+            seed: 1,
+        };
+        assert!(matches!(run(&[], &config), Err(SimError::Players(5))));
+        let config = SimConfig {
+            frames: 0,
+            session: SessionConfig::default(),
+            ..config
+        };
+        assert!(matches!(run(&[], &config), Err(SimError::NoFrames)));
+        let config = SimConfig {
+            frames: 1,
+            ..config
+        };
+        assert!(matches!(run(&[0; 16], &config), Err(SimError::Rom(_))));
+    }
+}
@@ -29,6 +29,20 @@
 //! players' [`RollbackSession`](crate::RollbackSession) already drops any
 //! unexpected / foreign packet.
 //!
+//! # Joining live
+//!
+//! By default a spectator replays the match from cold boot, which for a long
+//! match means a long fast-forward. With [`SpectatorConfig::join_live`] it
+//! instead joins the way a late player does (see the session's "Joining a
+//! match in progress"): it asks the host with a
+//! [`NetMessage::JoinRequest`] for [`NetMessage::SPECTATOR`], acknowledges the
+//! host's confirmed state as it streams in, restores it at the accepted
+//! `base_frame` and replays the input tail from there. That handshake is the
+//! only thing such a spectator ever sends; it still never touches the
+//! players' inputs. A host that never answers (an older build, or a relay
+//! with no way back) does not strand it: after a bounded wait it falls back
+//! to the cold-boot replay.
+//!
 //! # Lag-behind, never ahead
 //!
 //! A spectator runs `input_delay + network-latency` frames behind the live
@@ -37,10 +51,14 @@
 //! frames are buffered but not yet shown, so the frontend can fast-forward to
 //! catch up when it falls behind.
 
-use rustynes_core::{Buttons, Nes};
+use rustynes_core::{Buttons, DeviceInput, Nes};
 
-use crate::message::NetMessage;
+use crate::device;
+use crate::match_record::MatchRecorder;
+use crate::message::{NetMessage, StateTransfer};
+use crate::recovery::IncomingState;
 use crate::session::MAX_PLAYERS;
+use crate::settings::NetSettings;
 use crate::transport::Transport;
 
 /// How far ahead of the current confirmed/horizon frame a peer-supplied
@@ -57,6 +75,12 @@ use crate::transport::Transport;
 /// dropped (mirrors the beta.4 movie-parser bounds hardening).
 const MAX_SPECTATOR_FRAME_LOOKAHEAD: u32 = 1024;
 
+/// [`SpectatorSession::advance`] calls a [`join_live`](SpectatorConfig::join_live)
+/// spectator waits for the host's state before it gives up and replays from
+/// cold boot instead: about ten seconds at 60 Hz, the same span after which
+/// the host abandons a join nobody completed.
+const JOIN_LIVE_TIMEOUT_TICKS: u32 = 600;
+
 /// Configuration for a [`SpectatorSession`].
 #[derive(Clone, Copy, Debug)]
 pub struct SpectatorConfig {
@@ -85,6 +109,12 @@ pub struct SpectatorConfig {
     /// value is clamped to [`SpectatorConfig::MAX_DELAY_FRAMES`] on use so it can
     /// never push the reveal point past the bounded lookahead window.
     pub delay_frames: u32,
+    /// Start from the match's present instead of cold boot: ask the host for
+    /// its confirmed state and replay only from there (see the module docs).
+    /// Needs a stream with a way back to the host; without an answer the
+    /// spectator falls back to cold boot after a bounded wait. Defaults to
+    /// `false`.
+    pub join_live: bool,
 }
 
 impl SpectatorConfig {
@@ -101,6 +131,7 @@ impl Default for SpectatorConfig {
         Self {
             num_players: 2,
             delay_frames: 0,
+            join_live: false,
         }
     }
 }
@@ -110,6 +141,8 @@ impl Default for SpectatorConfig {
 struct FrameInputs {
     /// One cell per player index (only `0..num_players` are meaningful).
     inputs: [u8; MAX_PLAYERS],
+    /// Each player's expansion-device state (see [`crate::device`]).
+    devices: [DeviceInput; MAX_PLAYERS],
     /// Bit `p` set once player `p`'s real input for this frame has arrived.
     arrived: u8,
 }
@@ -132,7 +165,8 @@ pub struct SpectatorOutcome {
 /// Construct with [`Self::new`], then call [`Self::advance`] once per visual
 /// frame. The session polls the transport for [`NetMessage::Input`] (every
 /// player's stream) + [`NetMessage::Sync`] (handshake) + [`NetMessage::Roster`]
-/// (the player count, when relayed) and **never sends anything**.
+/// (the player count, when relayed) and **never sends anything** — save the
+/// join handshake of a [`join_live`](SpectatorConfig::join_live) spectator.
 pub struct SpectatorSession<T: Transport> {
     config: SpectatorConfig,
     transport: T,
@@ -149,6 +183,20 @@ pub struct SpectatorSession<T: Transport> {
 
     /// Per-frame confirmed input history, indexed by frame. Append-only.
     history: Vec<FrameInputs>,
+
+    /// `true` while a [`join_live`](SpectatorConfig::join_live) spectator is
+    /// still waiting for the host's state.
+    joining: bool,
+    /// `advance` calls spent joining, against [`JOIN_LIVE_TIMEOUT_TICKS`].
+    join_ticks: u32,
+    /// The `base_frame` of the host's `JoinAccept`, once it arrived.
+    join_base: Option<u32>,
+    /// The host's state transfer being reassembled.
+    incoming: Option<IncomingState>,
+    /// A reassembled `(frame, snapshot)` not yet installed.
+    restore: Option<(u32, Vec<u8>)>,
+    /// The match recording in progress, fed every frame shown.
+    recording: Option<MatchRecorder>,
 }
 
 impl<T: Transport> SpectatorSession<T> {
@@ -177,6 +225,12 @@ impl<T: Transport> SpectatorSession<T> {
             last_confirmed_frame: None,
             synced: false,
             history: Vec::new(),
+            joining: config.join_live,
+            join_ticks: 0,
+            join_base: None,
+            incoming: None,
+            restore: None,
+            recording: None,
         }
     }
 
@@ -226,6 +280,13 @@ impl<T: Transport> SpectatorSession<T> {
         self.synced
     }
 
+    /// `true` while a [`join_live`](SpectatorConfig::join_live) spectator is
+    /// still waiting for the host's state (it shows nothing until then).
+    #[must_use]
+    pub const fn is_joining(&self) -> bool {
+        self.joining
+    }
+
     /// How many fully-confirmed frames are buffered but not yet shown — i.e.
     /// how far the spectator is *behind* the live match. The frontend can
     /// fast-forward (call [`Self::advance`] repeatedly) to catch up.
@@ -237,6 +298,23 @@ impl<T: Transport> SpectatorSession<T> {
         }
     }
 
+    /// Start recording the match, played under `settings`, as an attested
+    /// movie from the next frame shown. See [`crate::match_record`].
+    pub fn start_recording(&mut self, settings: &NetSettings) {
+        self.recording = Some(MatchRecorder::new(settings));
+    }
+
+    /// The match recording in progress, if any.
+    #[must_use]
+    pub const fn recording(&self) -> Option<&MatchRecorder> {
+        self.recording.as_ref()
+    }
+
+    /// Stop recording and hand back what was recorded.
+    pub const fn take_recording(&mut self) -> Option<MatchRecorder> {
+        self.recording.take()
+    }
+
     /// Borrow the transport (e.g. to inspect link stats). Mainly for tests.
     #[must_use]
     pub const fn transport(&self) -> &T {
@@ -257,6 +335,24 @@ impl<T: Transport> SpectatorSession<T> {
     /// false (the caller can surface that via [`is_synced`](Self::is_synced)).
     pub fn advance(&mut self, nes: &mut Nes) -> SpectatorOutcome {
         self.ingest();
+        if self.joining {
+            self.install_join_state(nes);
+            if self.joining {
+                self.join_ticks += 1;
+                if self.join_ticks <= JOIN_LIVE_TIMEOUT_TICKS {
+                    self.transport.send(&NetMessage::JoinRequest {
+                        player: NetMessage::SPECTATOR,
+                    });
+                    return SpectatorOutcome::default();
+                }
+                // No state in time: replay from cold boot instead. `nes` has
+                // not run a frame yet, so it is still at power-on.
+                self.joining = false;
+                self.join_base = None;
+                self.incoming = None;
+                self.restore = None;
+            }
+        }
         self.recompute_confirmed();
 
         // Show the next frame only once every player's real input is known AND
@@ -270,7 +366,17 @@ impl<T: Transport> SpectatorSession<T> {
             return SpectatorOutcome::default();
         }
 
+        let n = self.config.num_players;
+        let recording = self
+            .recording
+            .as_mut()
+            .is_some_and(|rec| rec.enter(nes, frame, n));
         self.apply_and_run(nes, frame);
+        if recording && let Some(rec) = self.recording.as_mut() {
+            let slot = self.history[frame as usize];
+            let n = usize::from(n);
+            rec.record(&slot.inputs[..n], &slot.devices[..n], nes.framebuffer());
+        }
         self.current_frame += 1;
         SpectatorOutcome {
             produced_frame: true,
@@ -284,7 +390,9 @@ impl<T: Transport> SpectatorSession<T> {
         let messages = self.transport.poll();
         for msg in messages {
             match msg {
-                NetMessage::Sync { magic, rom_hash } => {
+                NetMessage::Sync {
+                    magic, rom_hash, ..
+                } => {
                     if magic == NetMessage::SYNC_MAGIC && rom_hash == self.rom_hash {
                         self.synced = true;
                     }
@@ -293,6 +401,7 @@ impl<T: Transport> SpectatorSession<T> {
                     player,
                     frame,
                     input,
+                    device,
                 } => {
                     // Drop an out-of-range player index. `num_players` is fixed
                     // by construction (and only ever set by a `Roster` BEFORE
@@ -319,6 +428,8 @@ impl<T: Transport> SpectatorSession<T> {
                     self.ensure_frame(frame);
                     let slot = &mut self.history[frame as usize];
                     slot.inputs[player as usize] = input;
+                    slot.devices[player as usize] =
+                        device::for_player(device, player, self.config.num_players);
                     slot.arrived |= 1 << player;
                 }
                 NetMessage::Roster { peers } => {
@@ -339,16 +450,88 @@ impl<T: Transport> SpectatorSession<T> {
                         self.config.num_players = n.clamp(2, 4);
                     }
                 }
+                NetMessage::JoinAccept {
+                    player: NetMessage::SPECTATOR,
+                    base_frame,
+                    ..
+                } if self.joining => {
+                    self.join_base.get_or_insert(base_frame);
+                }
+                NetMessage::StateChunk {
+                    kind: StateTransfer::Join,
+                    frame,
+                    index,
+                    count,
+                    data,
+                } if self.joining => self.ingest_state_chunk(frame, index, count, data),
                 // A spectator ignores acks (it sends no input to ack), peer
-                // checksums (it does not participate in desync detection), and
-                // quality hints (it never stalls the players).
+                // checksums and the desync-recovery traffic (it does not
+                // participate in desync detection — it replays confirmed inputs
+                // only), quality hints (it never stalls the players), and
+                // other newcomers' join handshakes, settings and saves, and the
+                // players' input-delay schedules (it authors no input), and
+                // the players' chat.
                 NetMessage::InputAck { .. }
+                | NetMessage::Settings { .. }
+                | NetMessage::SaveChunk { .. }
+                | NetMessage::SaveAck { .. }
                 | NetMessage::Checksum { .. }
-                | NetMessage::Quality { .. } => {}
+                | NetMessage::Quality { .. }
+                | NetMessage::StateChunk { .. }
+                | NetMessage::StateAck { .. }
+                | NetMessage::ResyncRequest { .. }
+                | NetMessage::JoinRequest { .. }
+                | NetMessage::JoinAccept { .. }
+                | NetMessage::InputDelay { .. }
+                | NetMessage::Chat { .. }
+                | NetMessage::ChatAck { .. }
+                | NetMessage::Sealed { .. } => {}
             }
         }
     }
 
+    /// Fold in one chunk of the host's state while joining live. Every chunk
+    /// is acknowledged so the host stops resending it.
+    fn ingest_state_chunk(&mut self, frame: u32, index: u16, count: u16, data: Vec<u8>) {
+        self.transport.send(&NetMessage::StateAck {
+            player: NetMessage::SPECTATOR,
+            frame,
+            index,
+        });
+        if self.restore.is_some() {
+            return;
+        }
+        if self.incoming.as_ref().is_none_or(|s| s.frame() != frame) {
+            self.incoming = IncomingState::new(frame, count);
+        }
+        if let Some(incoming) = self.incoming.as_mut()
+            && incoming.accept(index, count, data)
+        {
+            let incoming = self.incoming.take().expect("checked above");
+            // A corrupt transfer is dropped; the spectator keeps asking and
+            // the host sends a fresh one once this join has lapsed.
+            self.restore = incoming.finish().ok().map(|state| (frame, state));
+        }
+    }
+
+    /// Install the host's reassembled state once it matches the accepted
+    /// `base_frame` and the ROM is validated, resuming from that frame.
+    fn install_join_state(&mut self, nes: &mut Nes) {
+        let (Some(base), true) = (self.join_base, self.synced) else {
+            return;
+        };
+        let Some((frame, state)) = self.restore.take() else {
+            return;
+        };
+        if frame != base || nes.restore_quiet(&state).is_err() {
+            return;
+        }
+        self.current_frame = base;
+        self.last_confirmed_frame = base.checked_sub(1);
+        self.ensure_frame(base);
+        self.joining = false;
+    }
+
     /// Grow the history so index `frame` is addressable.
     fn ensure_frame(&mut self, frame: u32) {
         let need = frame as usize + 1;
@@ -387,6 +570,7 @@ impl<T: Transport> SpectatorSession<T> {
         nes.set_four_score(n > 2);
         for (port, &input) in slot.inputs.iter().enumerate().take(n) {
             nes.set_buttons(port, Buttons::from_bits_truncate(input));
+            slot.devices[port].apply(nes);
         }
         let _ = nes.run_frame();
     }
@@ -450,6 +634,7 @@ mod tests {
             SpectatorConfig {
                 num_players: 2,
                 delay_frames: DELAY,
+                join_live: false,
             },
             spec_link,
             hash,
@@ -463,6 +648,7 @@ mod tests {
                     player,
                     frame: f,
                     input: 0,
+                    device: DeviceInput::None,
                 });
             }
         }
@@ -480,6 +666,7 @@ mod tests {
                 player,
                 frame: DELAY,
                 input: 0,
+                device: DeviceInput::None,
             });
         }
         let out = spec.advance(&mut nes);
@@ -491,6 +678,41 @@ mod tests {
         assert_eq!(spec.delay_frames(), DELAY);
     }
 
+    /// A live-joining spectator whose host never answers stops asking after
+    /// the timeout and replays the buffered inputs from cold boot.
+    #[test]
+    fn join_live_falls_back_to_cold_boot_when_the_host_is_silent() {
+        let rom = synth_nrom();
+        let hash = *Nes::from_rom(&rom).unwrap().rom_sha256();
+        let (spec_link, mut feeder) = MemoryTransport::pair(LinkConditions::PERFECT, 3);
+        let mut spec = SpectatorSession::new(
+            SpectatorConfig {
+                num_players: 2,
+                delay_frames: 0,
+                join_live: true,
+            },
+            spec_link,
+            hash,
+        );
+        let mut nes = Nes::from_rom(&rom).unwrap();
+        for player in 0..2 {
+            feeder.send(&NetMessage::Input {
+                player,
+                frame: 0,
+                input: 0,
+                device: DeviceInput::None,
+            });
+        }
+        for _ in 0..JOIN_LIVE_TIMEOUT_TICKS {
+            assert!(!spec.advance(&mut nes).produced_frame);
+        }
+        assert!(spec.is_joining());
+        let out = spec.advance(&mut nes);
+        assert!(!spec.is_joining());
+        assert!(out.produced_frame, "the cold-boot replay starts at once");
+        assert_eq!(out.frame, 0);
+    }
+
     /// An absurd `delay_frames` is clamped to `MAX_DELAY_FRAMES`, so it cannot
     /// push the reveal point past the accept window.
     #[test]
@@ -502,6 +724,7 @@ mod tests {
             SpectatorConfig {
                 num_players: 2,
                 delay_frames: u32::MAX,
+                join_live: false,
             },
             a,
             hash,
@@ -527,6 +750,7 @@ mod tests {
             SpectatorConfig {
                 num_players: 2,
                 delay_frames: 0,
+                join_live: false,
             },
             spec_link,
             hash,
@@ -539,11 +763,13 @@ mod tests {
             player: 0,
             frame: u32::MAX - 5,
             input: 0xFF,
+            device: DeviceInput::None,
         });
         feeder.send(&NetMessage::Input {
             player: 1,
             frame: u32::MAX,
             input: 0x0F,
+            device: DeviceInput::None,
         });
         let out = spec.advance(&mut nes);
         assert!(!out.produced_frame, "out-of-window frames produce nothing");
@@ -559,11 +785,13 @@ mod tests {
             player: 0,
             frame: 0,
             input: 0,
+            device: DeviceInput::None,
         });
         feeder.send(&NetMessage::Input {
             player: 1,
             frame: 0,
             input: 0,
+            device: DeviceInput::None,
         });
         let out = spec.advance(&mut nes);
         assert!(out.produced_frame, "in-window frame 0 is shown");
@@ -590,6 +818,7 @@ mod tests {
             SpectatorConfig {
                 num_players: 2,
                 delay_frames: 0,
+                join_live: false,
             },
             spec_link,
             hash,
@@ -601,11 +830,13 @@ mod tests {
             player: 0,
             frame: 0,
             input: 0,
+            device: DeviceInput::None,
         });
         feeder.send(&NetMessage::Input {
             player: 1,
             frame: 0,
             input: 0,
+            device: DeviceInput::None,
         });
         assert!(spec.advance(&mut nes).produced_frame);
         assert_eq!(spec.num_players(), 2);
@@ -654,6 +885,7 @@ mod tests {
             SpectatorConfig {
                 num_players: 2,
                 delay_frames: 0,
+                join_live: false,
             },
             spec_link,
             hash,
@@ -664,6 +896,8 @@ mod tests {
         feeder.send(&NetMessage::Sync {
             magic: NetMessage::SYNC_MAGIC,
             rom_hash: hash,
+            settings: 0,
+            save: 0,
         });
         for (f, (&p0, &p1)) in p0_script.iter().zip(p1_script.iter()).enumerate() {
             let frame = u32::try_from(f).unwrap();
@@ -671,11 +905,13 @@ mod tests {
                 player: 0,
                 frame,
                 input: p0,
+                device: DeviceInput::None,
             });
             feeder.send(&NetMessage::Input {
                 player: 1,
                 frame,
                 input: p1,
+                device: DeviceInput::None,
             });
         }
 
@@ -21,6 +21,9 @@
 //!   back to the deprecated **MAPPED-ADDRESS** (`0x0001`) if that is all the
 //!   server sent. IPv4 and IPv6 are both decoded. Malformed / truncated / wrong-
 //!   cookie / non-success responses are rejected (`None`), never panic.
+//! - [`build_binding_response`] — the server side: answers a Binding Request
+//!   with the source address it arrived from, so a lobby server can double as
+//!   the STUN server for its own rooms.
 //! - [`StunClient`] — ties a request to its response: it remembers the
 //!   transaction id it generated so a response with a different id (a stray
 //!   datagram) is rejected.
@@ -164,6 +167,64 @@ pub fn parse_binding_response(
     fallback
 }
 
+/// Answer a STUN Binding Request from `observed`, its source address.
+///
+/// The reply is a Binding Success Response carrying `observed` in an
+/// **XOR-MAPPED-ADDRESS** attribute, under the request's transaction id.
+///
+/// Returns `None` (never panics) for anything that is not a well-formed
+/// Binding Request: too short, a wrong magic cookie, another message type, or
+/// a declared length that overruns the buffer. The request's attributes are
+/// not needed and are ignored.
+#[must_use]
+pub fn build_binding_response(request: &[u8], observed: SocketAddr) -> Option<Vec<u8>> {
+    if request.len() < HEADER_LEN {
+        return None;
+    }
+    let msg_type = u16::from_be_bytes([request[0], request[1]]);
+    let msg_len = u16::from_be_bytes([request[2], request[3]]) as usize;
+    let cookie = u32::from_be_bytes([request[4], request[5], request[6], request[7]]);
+    if msg_type != MSG_BINDING_REQUEST
+        || cookie != MAGIC_COOKIE
+        || request.len() < HEADER_LEN + msg_len
+    {
+        return None;
+    }
+    let tx_id: TransactionId = request[8..20].try_into().ok()?;
+    let value = encode_xor_address(observed, &tx_id);
+    let attr_len = u16::try_from(value.len()).ok()?;
+
+    let mut buf = Vec::with_capacity(HEADER_LEN + 4 + value.len());
+    buf.extend_from_slice(&MSG_BINDING_SUCCESS.to_be_bytes());
+    buf.extend_from_slice(&(attr_len + 4).to_be_bytes()); // one attribute
+    buf.extend_from_slice(&MAGIC_COOKIE.to_be_bytes());
+    buf.extend_from_slice(&tx_id);
+    buf.extend_from_slice(&ATTR_XOR_MAPPED_ADDRESS.to_be_bytes());
+    buf.extend_from_slice(&attr_len.to_be_bytes());
+    buf.extend_from_slice(&value);
+    Some(buf)
+}
+
+/// Encode `addr` as an XOR-MAPPED-ADDRESS attribute value: the inverse of
+/// [`decode_address`] with `xor` set. Both values are a multiple of four
+/// bytes long (8 for IPv4, 20 for IPv6), so no padding is needed.
+fn encode_xor_address(addr: SocketAddr, tx_id: &TransactionId) -> Vec<u8> {
+    let cookie_be = MAGIC_COOKIE.to_be_bytes();
+    #[allow(clippy::cast_possible_truncation)]
+    let cookie_hi16 = (MAGIC_COOKIE >> 16) as u16;
+    let (family, octets): (u8, Vec<u8>) = match addr {
+        SocketAddr::V4(v4) => (FAMILY_IPV4, v4.ip().octets().to_vec()),
+        SocketAddr::V6(v6) => (FAMILY_IPV6, v6.ip().octets().to_vec()),
+    };
+    // IPv4 is keyed by the cookie alone; IPv6 by the cookie then the
+    // transaction id (RFC 5389 §15.2).
+    let key = cookie_be.iter().chain(tx_id.iter());
+    let mut value = vec![0, family];
+    value.extend_from_slice(&(addr.port() ^ cookie_hi16).to_be_bytes());
+    value.extend(octets.iter().zip(key).map(|(b, k)| b ^ k));
+    value
+}
+
 /// Decode a (XOR-)MAPPED-ADDRESS attribute value into a [`SocketAddr`].
 ///
 /// Value layout: `[0]` reserved, `[1]` family, `[2..4]` port, `[4..]` address.
@@ -605,6 +666,35 @@ mod tests {
         assert_eq!(got, SocketAddr::V4(SocketAddrV4::new(ip, port)));
     }
 
+    #[test]
+    fn binding_response_reflects_the_observed_address() {
+        let mut rng = SplitMix64::new(0x5157);
+        for observed in [
+            SocketAddr::from((Ipv4Addr::new(203, 0, 113, 9), 40_001)),
+            SocketAddr::from((Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0x42), 443)),
+        ] {
+            let (req, tx) = build_binding_request(&mut rng);
+            let resp = build_binding_response(&req, observed).expect("a binding request");
+            assert_eq!(parse_binding_response(&resp, Some(&tx)), Some(observed));
+        }
+    }
+
+    #[test]
+    fn binding_response_needs_a_binding_request() {
+        let observed = SocketAddr::from((Ipv4Addr::LOCALHOST, 9));
+        let (req, _) = build_binding_request(&mut SplitMix64::new(1));
+        // A response is not a request, so two servers cannot ping-pong.
+        let resp = build_binding_response(&req, observed).unwrap();
+        assert!(build_binding_response(&resp, observed).is_none());
+        assert!(build_binding_response(&req[..HEADER_LEN - 1], observed).is_none());
+        let mut bad_cookie = req.clone();
+        bad_cookie[4] ^= 0xFF;
+        assert!(build_binding_response(&bad_cookie, observed).is_none());
+        let mut overrun = req;
+        overrun[3] = 8;
+        assert!(build_binding_response(&overrun, observed).is_none());
+    }
+
     #[test]
     fn short_response_is_rejected() {
         assert!(parse_binding_response(&[], None).is_none());
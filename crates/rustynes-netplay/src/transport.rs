@@ -5,9 +5,9 @@
 //! exclusively through [`Transport`]. Stage 2 will add a `UdpTransport` that
 //! implements this same trait (serializing via [`NetMessage::to_bytes`]); the
 //! session does not change. Stage 1 ships [`MemoryTransport`], a fully
-//! deterministic in-memory link whose latency / jitter / drop behaviour is
-//! driven by a seeded PRNG so the determinism harness can reproduce a run
-//! exactly.
+//! deterministic in-memory link whose latency / jitter / loss / reordering /
+//! outage behaviour is driven by a seeded PRNG so the determinism harness can
+//! reproduce a run exactly.
 //!
 //! [`NetMessage::to_bytes`]: crate::message::NetMessage::to_bytes
 
@@ -48,7 +48,9 @@ struct InFlight {
 /// `send` pushes here; endpoint B's `poll` drains the ready entries.
 type Wire = Rc<RefCell<VecDeque<InFlight>>>;
 
-/// Latency / jitter / drop configuration for [`MemoryTransport`].
+/// Latency / jitter / drop configuration for [`MemoryTransport`] and
+/// [`MeshTransport`]. Reordering and outages are layered on top with
+/// [`LinkFaults`].
 ///
 /// All of it is applied deterministically via the endpoint's seeded PRNG.
 #[derive(Clone, Copy, Debug)]
@@ -82,8 +84,121 @@ impl LinkConditions {
     pub const fn fixed_latency(polls: u32) -> Self {
         Self {
             latency_polls: polls,
-            jitter_polls: 0,
-            drop_prob: 0.0,
+            ..Self::PERFECT
+        }
+    }
+}
+
+/// The faults a [`LinkConditions`] link can suffer beyond loss and jitter:
+/// reordering and bursty outages. Attach them with
+/// [`MemoryTransport::with_faults`] / [`MeshTransport::with_faults`].
+///
+/// Non-exhaustive so new fault kinds can be added; build one from
+/// [`Self::NONE`] with the `with_*` methods.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct LinkFaults {
+    /// Reorder probability in `[0.0, 1.0)`: a surviving message is held back
+    /// an extra `latency_polls + jitter_polls + 1` polls, so it lands after
+    /// messages sent later. `0.0` disables reordering beyond what jitter
+    /// already causes.
+    pub reorder_prob: f64,
+    /// Per-poll probability in `[0.0, 1.0)` that the sending side starts a
+    /// bursty outage. `0.0` disables outages.
+    pub outage_prob: f64,
+    /// Length of one outage in polls; every message sent while it lasts is
+    /// lost (a Wi-Fi hiccup, a cell handover).
+    pub outage_polls: u32,
+}
+
+impl LinkFaults {
+    /// No reordering and no outages.
+    pub const NONE: Self = Self {
+        reorder_prob: 0.0,
+        outage_prob: 0.0,
+        outage_polls: 0,
+    };
+
+    /// These faults with messages reordered at probability `prob`.
+    #[must_use]
+    pub const fn with_reorder(mut self, prob: f64) -> Self {
+        self.reorder_prob = prob;
+        self
+    }
+
+    /// These faults with `polls`-long outages starting at probability `prob`
+    /// per poll.
+    #[must_use]
+    pub const fn with_outages(mut self, prob: f64, polls: u32) -> Self {
+        self.outage_prob = prob;
+        self.outage_polls = polls;
+        self
+    }
+}
+
+impl Default for LinkFaults {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// The sending half of one endpoint's link model: its [`LinkConditions`] and
+/// [`LinkFaults`], the seeded PRNG every draw comes from, and the state of any outage in
+/// progress. Shared by [`MemoryTransport`] and [`MeshTransport`] so the two
+/// behave identically per link.
+struct Link {
+    conditions: LinkConditions,
+    faults: LinkFaults,
+    rng: SplitMix64,
+    /// Polls left in the current outage; 0 when the link is up.
+    outage_left: u32,
+}
+
+impl Link {
+    const fn new(conditions: LinkConditions, rng: SplitMix64) -> Self {
+        Self {
+            conditions,
+            faults: LinkFaults::NONE,
+            rng,
+            outage_left: 0,
+        }
+    }
+
+    /// The delivery delay for one message, or `None` if it is lost.
+    ///
+    /// The drop check comes first and always consumes its draw (so a dropped
+    /// message keeps the stream aligned regardless of outcome), then jitter,
+    /// then reordering. A disabled feature draws nothing, so adding the
+    /// reorder and outage knobs left existing seeds' behaviour unchanged.
+    fn delay(&mut self) -> Option<u32> {
+        if self.outage_left > 0 {
+            return None;
+        }
+        let c = self.conditions;
+        let dropped = c.drop_prob > 0.0 && self.rng.next_unit() < c.drop_prob;
+        let jitter = if c.jitter_polls > 0 {
+            self.rng.next_below(c.jitter_polls + 1)
+        } else {
+            0
+        };
+        let reorder = self.faults.reorder_prob;
+        let held = if reorder > 0.0 && self.rng.next_unit() < reorder {
+            c.latency_polls + c.jitter_polls + 1
+        } else {
+            0
+        };
+        if dropped {
+            return None;
+        }
+        Some(c.latency_polls + jitter + held)
+    }
+
+    /// Advance the outage clock by one poll, possibly starting a new outage.
+    fn tick(&mut self) {
+        if self.outage_left > 0 {
+            self.outage_left -= 1;
+        } else if self.faults.outage_prob > 0.0 && self.rng.next_unit() < self.faults.outage_prob {
+            self.outage_left = self.faults.outage_polls;
         }
     }
 }
@@ -100,8 +215,7 @@ pub struct MemoryTransport {
     outbound: Wire,
     /// Messages destined for this endpoint, sent by the peer.
     inbound: Wire,
-    conditions: LinkConditions,
-    rng: SplitMix64,
+    link: Link,
 }
 
 impl MemoryTransport {
@@ -125,36 +239,31 @@ impl MemoryTransport {
         let endpoint_a = Self {
             outbound: Rc::clone(&wire_a_to_b),
             inbound: Rc::clone(&wire_b_to_a),
-            conditions: a_to_b,
             // Distinct seeds per direction; XOR with a constant keeps them
             // distinct even when `seed` is small.
-            rng: SplitMix64::new(seed ^ 0xA5A5_A5A5_A5A5_A5A5),
+            link: Link::new(a_to_b, SplitMix64::new(seed ^ 0xA5A5_A5A5_A5A5_A5A5)),
         };
         let endpoint_b = Self {
             outbound: wire_b_to_a,
             inbound: wire_a_to_b,
-            conditions: b_to_a,
-            rng: SplitMix64::new(seed ^ 0x5A5A_5A5A_5A5A_5A5A),
+            link: Link::new(b_to_a, SplitMix64::new(seed ^ 0x5A5A_5A5A_5A5A_5A5A)),
         };
         (endpoint_a, endpoint_b)
     }
+
+    /// This endpoint with `faults` on the messages it sends.
+    #[must_use]
+    pub const fn with_faults(mut self, faults: LinkFaults) -> Self {
+        self.link.faults = faults;
+        self
+    }
 }
 
 impl Transport for MemoryTransport {
     fn send(&mut self, msg: &NetMessage) {
-        // Deterministic drop check first (so a dropped message still consumes
-        // one PRNG draw, keeping the stream aligned regardless of outcome).
-        let dropped =
-            self.conditions.drop_prob > 0.0 && self.rng.next_unit() < self.conditions.drop_prob;
-        let jitter = if self.conditions.jitter_polls > 0 {
-            self.rng.next_below(self.conditions.jitter_polls + 1)
-        } else {
-            0
-        };
-        if dropped {
+        let Some(deliver_in) = self.link.delay() else {
             return;
-        }
-        let deliver_in = self.conditions.latency_polls + jitter;
+        };
         self.outbound.borrow_mut().push_back(InFlight {
             msg: msg.clone(),
             deliver_in,
@@ -162,6 +271,7 @@ impl Transport for MemoryTransport {
     }
 
     fn poll(&mut self) -> Vec<NetMessage> {
+        self.link.tick();
         let mut ready = Vec::new();
         let mut inbound = self.inbound.borrow_mut();
         // Decrement every in-flight entry; deliver those whose countdown has
@@ -201,8 +311,7 @@ pub struct MeshTransport {
     /// One directed wire from every *other* peer (this peer's `poll` drains
     /// all of them).
     inbound: Vec<Wire>,
-    conditions: LinkConditions,
-    rng: SplitMix64,
+    link: Link,
 }
 
 impl MeshTransport {
@@ -250,31 +359,17 @@ impl MeshTransport {
             endpoints.push(Self {
                 outbound,
                 inbound,
-                conditions,
-                rng,
+                link: Link::new(conditions, rng),
             });
         }
         endpoints
     }
 
-    /// Push `msg` onto one directed wire with a PRNG-derived delay (or drop
-    /// it). Mirrors `MemoryTransport::send`'s per-message model so the two
-    /// transports behave identically per link.
-    fn push_one(rng: &mut SplitMix64, conditions: LinkConditions, wire: &Wire, msg: &NetMessage) {
-        let dropped = conditions.drop_prob > 0.0 && rng.next_unit() < conditions.drop_prob;
-        let jitter = if conditions.jitter_polls > 0 {
-            rng.next_below(conditions.jitter_polls + 1)
-        } else {
-            0
-        };
-        if dropped {
-            return;
-        }
-        let deliver_in = conditions.latency_polls + jitter;
-        wire.borrow_mut().push_back(InFlight {
-            msg: msg.clone(),
-            deliver_in,
-        });
+    /// This endpoint with `faults` on the messages it sends.
+    #[must_use]
+    pub const fn with_faults(mut self, faults: LinkFaults) -> Self {
+        self.link.faults = faults;
+        self
     }
 
     /// Drain the ready entries of one inbound wire into `ready`.
@@ -296,17 +391,20 @@ impl MeshTransport {
 impl Transport for MeshTransport {
     fn send(&mut self, msg: &NetMessage) {
         // Fan out to every other peer, each link drawing its own delay/drop so
-        // the broadcast is not artificially correlated across recipients.
-        let conditions = self.conditions;
-        // Clone the wire handles up front so `self.rng` and `self.outbound`
-        // are not borrowed simultaneously in the loop.
-        let wires: Vec<Wire> = self.outbound.iter().map(Rc::clone).collect();
-        for wire in &wires {
-            Self::push_one(&mut self.rng, conditions, wire, msg);
+        // the broadcast is not artificially correlated across recipients. An
+        // outage takes the whole endpoint down, like a real uplink would.
+        for wire in &self.outbound {
+            if let Some(deliver_in) = self.link.delay() {
+                wire.borrow_mut().push_back(InFlight {
+                    msg: msg.clone(),
+                    deliver_in,
+                });
+            }
         }
     }
 
     fn poll(&mut self) -> Vec<NetMessage> {
+        self.link.tick();
         let mut ready = Vec::new();
         for wire in &self.inbound {
             Self::drain_one(wire, &mut ready);
@@ -318,6 +416,7 @@ impl Transport for MeshTransport {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rustynes_core::DeviceInput;
 
     #[test]
     fn perfect_link_delivers_next_poll() {
@@ -367,6 +466,62 @@ mod tests {
         assert!(!d.is_empty() && d.len() < 50);
     }
 
+    #[test]
+    fn reordered_messages_land_after_later_sends() {
+        let (a, mut b) = MemoryTransport::pair(LinkConditions::fixed_latency(1), 0x0DD_0BE5);
+        let mut a = a.with_faults(LinkFaults::NONE.with_reorder(0.5));
+        let mut order = Vec::new();
+        for f in 0..40u32 {
+            a.send(&NetMessage::InputAck { frame: f });
+            for m in b.poll() {
+                if let NetMessage::InputAck { frame } = m {
+                    order.push(frame);
+                }
+            }
+        }
+        for _ in 0..4 {
+            for m in b.poll() {
+                if let NetMessage::InputAck { frame } = m {
+                    order.push(frame);
+                }
+            }
+        }
+        // Nothing lost, but some arrived behind a later send.
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..40).collect::<Vec<_>>());
+        assert_ne!(order, sorted, "some messages were reordered");
+    }
+
+    #[test]
+    fn an_outage_drops_every_send_for_its_length() {
+        let (a, mut b) = MemoryTransport::pair(LinkConditions::PERFECT, 77);
+        let mut a = a.with_faults(LinkFaults::NONE.with_outages(0.2, 10));
+        // Sends are polled by the sender too, which is what ticks its outage.
+        let mut delivered = Vec::new();
+        for f in 0..200u32 {
+            a.send(&NetMessage::InputAck { frame: f });
+            let _ = a.poll();
+            for m in b.poll() {
+                if let NetMessage::InputAck { frame } = m {
+                    delivered.push(frame);
+                }
+            }
+        }
+        // Losses come in runs of the outage length, never as single drops.
+        let lost: Vec<u32> = (0..200).filter(|f| !delivered.contains(f)).collect();
+        assert!(!lost.is_empty(), "an outage happened");
+        let mut run = 1;
+        for pair in lost.windows(2) {
+            if pair[1] == pair[0] + 1 {
+                run += 1;
+            } else {
+                assert_eq!(run % 10, 0, "a whole outage's sends are lost");
+                run = 1;
+            }
+        }
+    }
+
     #[test]
     fn directions_are_independent() {
         let (mut a, mut b) = MemoryTransport::pair(LinkConditions::PERFECT, 9);
@@ -385,6 +540,7 @@ mod tests {
             player: 0,
             frame: 7,
             input: 0x55,
+            device: DeviceInput::None,
         };
         peers[0].send(&msg);
         assert!(peers[0].poll().is_empty(), "sender hears no echo of itself");
@@ -409,6 +565,7 @@ mod tests {
                         player: u8::try_from(p).unwrap(),
                         frame: f,
                         input: u8::try_from(f & 0xFF).unwrap(),
+                        device: DeviceInput::None,
                     });
                 }
                 for (p, peer) in peers.iter_mut().enumerate() {
@@ -14,10 +14,11 @@
 
 use std::path::PathBuf;
 
-use rustynes_core::{Buttons, Nes};
+use rustynes_core::{Buttons, DeviceInput, Movie, Nes, VerifyOutcome};
 use rustynes_netplay::{
-    LinkConditions, MemoryTransport, MeshTransport, NetplayError, RollbackSession, SessionConfig,
-    SplitMix64, fnv1a64,
+    DelayMode, DelayTuning, LinkConditions, MatchRecorder, MemoryTransport, MeshTransport,
+    NetMessage, NetSettings, NetplayError, RollbackSession, SessionConfig, SpectatorConfig,
+    SpectatorSession, SplitMix64, Transport, fnv1a64, match_record,
 };
 
 /// The deterministic gameplay digest used for cross-peer comparison —
@@ -113,14 +114,14 @@ fn asymmetric_realtime_drive_stays_in_sync() {
             // advances an independent 0-2 times this iteration so they drift.
             for _ in 0..(rng.next_u8() % 3) {
                 while a0 <= s0.current_frame() {
-                    s0.add_local_input(Buttons::empty());
+                    let _ = s0.add_local_input(Buttons::empty());
                     a0 += 1;
                 }
                 let _ = s0.advance(&mut nes0).expect("s0 advance");
             }
             for _ in 0..(rng.next_u8() % 3) {
                 while a1 <= s1.current_frame() {
-                    s1.add_local_input(Buttons::empty());
+                    let _ = s1.add_local_input(Buttons::empty());
                     a1 += 1;
                 }
                 let _ = s1.advance(&mut nes1).expect("s1 advance");
@@ -409,13 +410,13 @@ fn run_two_sessions(
         ticks += 1;
 
         while authored0 <= s0.current_frame() && (authored0 as usize) < p0.len() {
-            s0.add_local_input(p0[authored0 as usize]);
+            let _ = s0.add_local_input(p0[authored0 as usize]);
             authored0 += 1;
         }
         let _ = s0.advance(&mut nes0)?;
 
         while authored1 <= s1.current_frame() && (authored1 as usize) < p1.len() {
-            s1.add_local_input(p1[authored1 as usize]);
+            let _ = s1.add_local_input(p1[authored1 as usize]);
             authored1 += 1;
         }
         let _ = s1.advance(&mut nes1)?;
@@ -579,6 +580,30 @@ fn zero_rollback_is_byte_identical() {
     );
 }
 
+/// Wraps a `MemoryTransport`, flipping the bits of every `Input` it delivers
+/// for a frame in `target_frames`. Persistent: retransmits stay corrupted.
+struct CorruptingTransport {
+    inner: MemoryTransport,
+    target_frames: std::ops::RangeInclusive<u32>,
+}
+
+impl Transport for CorruptingTransport {
+    fn send(&mut self, msg: &NetMessage) {
+        self.inner.send(msg);
+    }
+    fn poll(&mut self) -> Vec<NetMessage> {
+        let mut msgs = self.inner.poll();
+        for m in &mut msgs {
+            if let NetMessage::Input { frame, input, .. } = m
+                && self.target_frames.contains(frame)
+            {
+                *input = !*input;
+            }
+        }
+        msgs
+    }
+}
+
 /// Desync detection: when one peer's emulator is forced to diverge (we
 /// persistently corrupt the remote input it sees for one frame, so it runs
 /// different state), the periodic `Checksum` exchange must surface a `Desync`
@@ -591,31 +616,6 @@ fn zero_rollback_is_byte_identical() {
 /// confirmed-frame checksum mismatches.
 #[test]
 fn desync_detection() {
-    use rustynes_netplay::{NetMessage, Transport};
-
-    /// Wraps a `MemoryTransport`, flipping the bits of every `Input` it
-    /// delivers for `target_frame`. Persistent: retransmits stay corrupted.
-    struct CorruptingTransport {
-        inner: MemoryTransport,
-        target_frame: u32,
-    }
-    impl Transport for CorruptingTransport {
-        fn send(&mut self, msg: &NetMessage) {
-            self.inner.send(msg);
-        }
-        fn poll(&mut self) -> Vec<NetMessage> {
-            let mut msgs = self.inner.poll();
-            for m in &mut msgs {
-                if let NetMessage::Input { frame, input, .. } = m
-                    && *frame == self.target_frame
-                {
-                    *input = !*input;
-                }
-            }
-            msgs
-        }
-    }
-
     let rom = nestest_rom();
     let frames = 200u32;
     let (p0, p1) = make_input_streams(frames, 0xDEAD_BEEF);
@@ -645,7 +645,7 @@ fn desync_detection() {
             inner: t1,
             // A frame past the input-delay prefix, so the corrupted value is a
             // real authored input that the divergence propagates from.
-            target_frame: 30,
+            target_frames: 30..=30,
         },
         hash,
     );
@@ -656,7 +656,7 @@ fn desync_detection() {
     for _ in 0..(frames * 8) {
         if s0.current_frame() < frames {
             while authored0 <= s0.current_frame() && (authored0 as usize) < p0.len() {
-                s0.add_local_input(p0[authored0 as usize]);
+                let _ = s0.add_local_input(p0[authored0 as usize]);
                 authored0 += 1;
             }
             if let Err(NetplayError::Desync { .. }) = s0.advance(&mut nes0) {
@@ -666,7 +666,7 @@ fn desync_detection() {
         }
         if s1.current_frame() < frames {
             while authored1 <= s1.current_frame() && (authored1 as usize) < p1.len() {
-                s1.add_local_input(p1[authored1 as usize]);
+                let _ = s1.add_local_input(p1[authored1 as usize]);
                 authored1 += 1;
             }
             if let Err(NetplayError::Desync { .. }) = s1.advance(&mut nes1) {
@@ -685,6 +685,94 @@ fn desync_detection() {
     );
 }
 
+/// With `desync_recovery` on, the same persistent divergence is repaired
+/// instead of fatal: the host streams its confirmed checkpoint to the diverged
+/// peer, which restores it and replays, after which the confirmed state and
+/// every later checksum agree again.
+#[test]
+fn desync_recovery_resynchronizes_peers() {
+    let rom = nestest_rom();
+    let frames = 400u32;
+    let (p0, p1) = make_input_streams(frames, 0xDEAD_BEEF);
+
+    let conditions = LinkConditions::fixed_latency(1);
+    let (t0, t1) = MemoryTransport::pair(conditions, 0x00C0_FFEE);
+    let mut nes0 = Nes::from_rom(&rom).expect("load");
+    let mut nes1 = Nes::from_rom(&rom).expect("load");
+    let hash = *nes0.rom_sha256();
+
+    let cfg0 = SessionConfig {
+        local_player: 0,
+        checksum_interval: 5,
+        desync_recovery: true,
+        ..SessionConfig::default()
+    };
+    let cfg1 = SessionConfig {
+        local_player: 1,
+        ..cfg0
+    };
+    let mut s0 = RollbackSession::new(cfg0, t0, hash);
+    let mut s1 = RollbackSession::new(
+        cfg1,
+        CorruptingTransport {
+            inner: t1,
+            // A sustained window: a single flipped input can wash out of the
+            // picture within a few frames, but a 60-frame run keeps the peers
+            // diverged across several checksums — and a recovery that lands
+            // inside the window diverges again, so this also exercises a
+            // repeated transfer.
+            target_frames: 30..=90,
+        },
+        hash,
+    );
+
+    let mut authored0 = 0u32;
+    let mut authored1 = 0u32;
+    for _ in 0..(frames * 8) {
+        if s0.current_frame() < frames {
+            while authored0 <= s0.current_frame() && (authored0 as usize) < p0.len() {
+                let _ = s0.add_local_input(p0[authored0 as usize]);
+                authored0 += 1;
+            }
+            s0.advance(&mut nes0).expect("host advance");
+        }
+        if s1.current_frame() < frames {
+            while authored1 <= s1.current_frame() && (authored1 as usize) < p1.len() {
+                let _ = s1.add_local_input(p1[authored1 as usize]);
+                authored1 += 1;
+            }
+            s1.advance(&mut nes1).expect("peer advance");
+        }
+        if s0.current_frame() >= frames && s1.current_frame() >= frames {
+            break;
+        }
+    }
+
+    assert!(
+        s0.diagnostics().first_desync_frame().is_some(),
+        "divergence was detected"
+    );
+    assert!(
+        s0.diagnostics().recoveries() >= 1,
+        "the host completed a transfer"
+    );
+    assert!(s1.diagnostics().recoveries() >= 1, "the peer applied it");
+    assert!(!s1.diagnostics().is_desynced());
+    let last = s1.diagnostics().last().expect("checksums were exchanged");
+    assert!(
+        last.matched,
+        "checksums agree after recovery (frame {})",
+        last.frame
+    );
+    let probe = frames - 40;
+    assert_eq!(
+        s0.confirmed_entering_digest(probe),
+        s1.confirmed_entering_digest(probe),
+        "confirmed state reconverged by frame {probe}"
+    );
+    assert!(s0.confirmed_entering_digest(probe).is_some());
+}
+
 /// A ROM-hash mismatch in the `Sync` handshake must be rejected.
 #[test]
 fn rom_mismatch_rejected() {
@@ -706,7 +794,7 @@ fn rom_mismatch_rejected() {
         wrong,
     );
 
-    s0.add_local_input(Buttons::empty());
+    let _ = s0.add_local_input(Buttons::empty());
     let err = s0.advance(&mut nes0);
     assert!(matches!(err, Err(NetplayError::RomMismatch)));
 }
@@ -748,7 +836,7 @@ fn advance_outcome_reports_rollbacks() {
     let mut saw_resim = false;
     for _ in 0..frames {
         while a0 <= s0.current_frame() && (a0 as usize) < p0.len() {
-            s0.add_local_input(p0[a0 as usize]);
+            let _ = s0.add_local_input(p0[a0 as usize]);
             a0 += 1;
         }
         let o0 = s0.advance(&mut nes0).unwrap();
@@ -758,7 +846,7 @@ fn advance_outcome_reports_rollbacks() {
             saw_resim = true;
         }
         while a1 <= s1.current_frame() && (a1 as usize) < p1.len() {
-            s1.add_local_input(p1[a1 as usize]);
+            let _ = s1.add_local_input(p1[a1 as usize]);
             a1 += 1;
         }
         let _ = s1.advance(&mut nes1).unwrap();
@@ -863,7 +951,7 @@ fn run_n_sessions(
             while authored[p] <= sessions[p].current_frame()
                 && (authored[p] as usize) < streams[p].len()
             {
-                sessions[p].add_local_input(streams[p][authored[p] as usize]);
+                let _ = sessions[p].add_local_input(streams[p][authored[p] as usize]);
                 authored[p] += 1;
             }
             let _ = sessions[p].advance(&mut nes[p])?;
@@ -980,8 +1068,6 @@ fn four_player_rollback_stress_high_latency() {
 #[test]
 #[allow(clippy::too_many_lines)]
 fn n_player_desync_detection() {
-    use rustynes_netplay::{NetMessage, Transport};
-
     /// Flips every `Input` it delivers for `target_frame`, persistently (even
     /// retransmits stay corrupted — a genuine divergence).
     struct CorruptingMesh {
@@ -1062,7 +1148,7 @@ fn n_player_desync_detection() {
         // s0
         if s0.current_frame() < frames {
             while authored[0] <= s0.current_frame() && (authored[0] as usize) < streams[0].len() {
-                s0.add_local_input(streams[0][authored[0] as usize]);
+                let _ = s0.add_local_input(streams[0][authored[0] as usize]);
                 authored[0] += 1;
             }
             if let Err(NetplayError::Desync { .. }) = s0.advance(&mut nes[0]) {
@@ -1073,7 +1159,7 @@ fn n_player_desync_detection() {
         // s1
         if s1.current_frame() < frames {
             while authored[1] <= s1.current_frame() && (authored[1] as usize) < streams[1].len() {
-                s1.add_local_input(streams[1][authored[1] as usize]);
+                let _ = s1.add_local_input(streams[1][authored[1] as usize]);
                 authored[1] += 1;
             }
             if let Err(NetplayError::Desync { .. }) = s1.advance(&mut nes[1]) {
@@ -1084,7 +1170,7 @@ fn n_player_desync_detection() {
         // s2 (corrupted)
         if s2.current_frame() < frames {
             while authored[2] <= s2.current_frame() && (authored[2] as usize) < streams[2].len() {
-                s2.add_local_input(streams[2][authored[2] as usize]);
+                let _ = s2.add_local_input(streams[2][authored[2] as usize]);
                 authored[2] += 1;
             }
             if let Err(NetplayError::Desync { .. }) = s2.advance(&mut nes[2]) {
@@ -1139,3 +1225,842 @@ fn n_player_zero_rollback_is_byte_identical() {
         );
     }
 }
+
+// ───────────────────────────────────────────────────────────────────────────
+// Joining a match in progress.
+//
+// A newcomer's mesh leg exists from the start but is drained unread until it
+// joins, as if it were not connected: everything it knows must come from the
+// host's state transfer and the peers' resent input tail.
+// ───────────────────────────────────────────────────────────────────────────
+
+/// Ticks the live peers run before the newcomer shows up.
+const JOIN_AFTER_TICKS: u32 = 240;
+
+/// A third player takes over a slot the host vacated at frame 0: it starts
+/// from the host's confirmed state (not cold boot), the host's idle input
+/// fills the slot until the agreed join frame, the newcomer's own input
+/// drives it afterwards, and all three peers agree on the confirmed state.
+#[test]
+fn late_joiner_takes_over_a_vacated_slot() {
+    let rom = nestest_rom();
+    let frames = 900u32;
+    let streams = make_n_input_streams(3, frames, 0x1A7E_0003);
+    let conditions = LinkConditions {
+        latency_polls: 2,
+        jitter_polls: 1,
+        drop_prob: 0.0,
+    };
+    let mut legs = MeshTransport::mesh(3, conditions, 0x0001_A7E0).into_iter();
+    let (t0, t1, mut t2) = (
+        legs.next().expect("leg 0"),
+        legs.next().expect("leg 1"),
+        legs.next().expect("leg 2"),
+    );
+    let mut nes: Vec<Nes> = (0..3).map(|_| Nes::from_rom(&rom).expect("load")).collect();
+    let hash = *nes[0].rom_sha256();
+    let cfg = |local_player| SessionConfig {
+        num_players: 3,
+        local_player,
+        ..SessionConfig::default()
+    };
+
+    let mut s0 = RollbackSession::new(cfg(0), t0, hash);
+    let mut s1 = RollbackSession::new(cfg(1), t1, hash);
+    assert!(s0.vacate_player(2), "the host opens the empty slot");
+    assert!(s0.is_vacant(2));
+
+    let mut authored = [0u32; 2];
+    let mut drive = |s0: &mut RollbackSession<MeshTransport>,
+                     s1: &mut RollbackSession<MeshTransport>,
+                     nes: &mut [Nes]| {
+        for (p, s) in [s0, s1].into_iter().enumerate() {
+            while authored[p] <= s.current_frame() && (authored[p] as usize) < frames as usize {
+                let _ = s.add_local_input(streams[p][authored[p] as usize]);
+                authored[p] += 1;
+            }
+            s.advance(&mut nes[p]).expect("live peer advance");
+        }
+    };
+    for _ in 0..JOIN_AFTER_TICKS {
+        drive(&mut s0, &mut s1, &mut nes);
+        let _ = t2.poll();
+    }
+    assert!(s0.current_frame() > 100, "the match ran without player 3");
+
+    let mut s2 = RollbackSession::join_in_progress(cfg(2), t2, hash);
+    let mut first_frame = None;
+    let probe = frames - 60;
+    for _ in 0..frames * 8 {
+        drive(&mut s0, &mut s1, &mut nes);
+        if !s2.is_joining() {
+            let target = s2.current_frame() + cfg(2).input_delay;
+            let _ = s2.add_local_input(streams[2][target.min(frames - 1) as usize]);
+        }
+        let out = s2.advance(&mut nes[2]).expect("newcomer advance");
+        if out.produced_frame {
+            first_frame.get_or_insert(out.frame);
+        }
+        let all_confirmed = [&s0, &s1, &s2]
+            .iter()
+            .all(|s| s.last_confirmed_frame().is_some_and(|c| c >= probe));
+        if all_confirmed {
+            break;
+        }
+    }
+
+    let first_frame = first_frame.expect("the newcomer produced frames");
+    assert!(
+        first_frame > 100,
+        "the newcomer resumed from the host's state, not cold boot (frame {first_frame})"
+    );
+    assert!(!s0.is_vacant(2), "the slot was taken over");
+    let digests: Vec<_> = [&s0, &s1, &s2]
+        .iter()
+        .map(|s| s.confirmed_entering_digest(probe))
+        .collect();
+    assert!(digests[0].is_some(), "every peer confirmed frame {probe}");
+    assert!(
+        digests.windows(2).all(|w| w[0] == w[1]),
+        "all three peers agree after the join: {digests:?}"
+    );
+    let early = s0.confirmed_input(first_frame).expect("confirmed");
+    assert_eq!(
+        early[2], 0,
+        "the host's idle input filled the slot before the join"
+    );
+    let late = s0.confirmed_input(probe - 1).expect("confirmed");
+    assert_eq!(
+        late[2],
+        streams[2][(probe - 1) as usize].bits(),
+        "the newcomer's input drives the slot after the join"
+    );
+}
+
+/// Wraps a `MeshTransport`, losing every `JoinAccept` for `polls` polls
+/// after the first one arrives, so the host's join state reaches the peer
+/// before (or without) the message that announces it.
+struct LateJoinAccept {
+    inner: MeshTransport,
+    polls: u32,
+    started: bool,
+}
+
+impl LateJoinAccept {
+    const fn new(inner: MeshTransport, polls: u32) -> Self {
+        Self {
+            inner,
+            polls,
+            started: false,
+        }
+    }
+}
+
+impl Transport for LateJoinAccept {
+    fn send(&mut self, msg: &NetMessage) {
+        self.inner.send(msg);
+    }
+    fn poll(&mut self) -> Vec<NetMessage> {
+        let mut msgs = self.inner.poll();
+        self.started |= msgs
+            .iter()
+            .any(|m| matches!(m, NetMessage::JoinAccept { .. }));
+        if self.started && self.polls > 0 {
+            self.polls -= 1;
+            msgs.retain(|m| !matches!(m, NetMessage::JoinAccept { .. }));
+        }
+        msgs
+    }
+}
+
+/// The join's state chunks say what they are: a peer already in the match
+/// that has not seen the `JoinAccept` yet must not take them for a desync
+/// recovery, and a newcomer whose `JoinAccept` is late still restores them
+/// once it arrives.
+#[test]
+fn a_late_join_accept_does_not_turn_the_join_into_a_recovery() {
+    let rom = nestest_rom();
+    let frames = 700u32;
+    let streams = make_n_input_streams(3, frames, 0x1A7E_0004);
+    let mut legs = MeshTransport::mesh(3, LinkConditions::PERFECT, 0x0002_A7E0).into_iter();
+    let (t0, t1, t2) = (
+        legs.next().expect("leg 0"),
+        LateJoinAccept::new(legs.next().expect("leg 1"), 120),
+        legs.next().expect("leg 2"),
+    );
+    let mut nes: Vec<Nes> = (0..3).map(|_| Nes::from_rom(&rom).expect("load")).collect();
+    let hash = *nes[0].rom_sha256();
+    let cfg = |local_player| SessionConfig {
+        num_players: 3,
+        local_player,
+        desync_recovery: true,
+        ..SessionConfig::default()
+    };
+
+    let mut s0 = RollbackSession::new(cfg(0), t0, hash);
+    let mut s1 = RollbackSession::new(cfg(1), t1, hash);
+    assert!(s0.vacate_player(2));
+    let mut t2 = LateJoinAccept::new(t2, 60);
+
+    let mut authored = [0u32; 2];
+    let mut drive = |s0: &mut RollbackSession<MeshTransport>,
+                     s1: &mut RollbackSession<LateJoinAccept>,
+                     nes: &mut [Nes]| {
+        for (p, s) in [s0 as &mut dyn Peer, s1].into_iter().enumerate() {
+            while authored[p] <= s.frame() && (authored[p] as usize) < frames as usize {
+                s.input(streams[p][authored[p] as usize]);
+                authored[p] += 1;
+            }
+            s.step(&mut nes[p]);
+        }
+    };
+    for _ in 0..JOIN_AFTER_TICKS {
+        drive(&mut s0, &mut s1, &mut nes);
+        let _ = t2.poll();
+    }
+
+    let mut s2 = RollbackSession::join_in_progress(cfg(2), t2, hash);
+    let probe = frames - 60;
+    for _ in 0..frames * 8 {
+        drive(&mut s0, &mut s1, &mut nes);
+        if !s2.is_joining() {
+            let target = s2.current_frame() + cfg(2).input_delay;
+            let _ = s2.add_local_input(streams[2][target.min(frames - 1) as usize]);
+        }
+        s2.advance(&mut nes[2]).expect("newcomer advance");
+        if s2.last_confirmed_frame().is_some_and(|c| c >= probe)
+            && s0.last_confirmed_frame().is_some_and(|c| c >= probe)
+            && s1.last_confirmed_frame().is_some_and(|c| c >= probe)
+        {
+            break;
+        }
+    }
+
+    assert_eq!(
+        s1.diagnostics().recoveries(),
+        0,
+        "the join's state was not installed as a recovery"
+    );
+    assert!(!s0.is_vacant(2), "the newcomer joined");
+    let digests = [
+        s0.confirmed_entering_digest(probe),
+        s1.confirmed_entering_digest(probe),
+        s2.confirmed_entering_digest(probe),
+    ];
+    assert!(digests[0].is_some(), "every peer confirmed frame {probe}");
+    assert!(
+        digests.windows(2).all(|w| w[0] == w[1]),
+        "all three peers agree after the join: {digests:?}"
+    );
+}
+
+/// The two live peers of [`a_late_join_accept_does_not_turn_the_join_into_a_recovery`]
+/// ride different transports; this lets one loop drive both.
+trait Peer {
+    fn frame(&self) -> u32;
+    fn input(&mut self, buttons: Buttons);
+    fn step(&mut self, nes: &mut Nes);
+}
+
+impl<T: Transport> Peer for RollbackSession<T> {
+    fn frame(&self) -> u32 {
+        self.current_frame()
+    }
+    fn input(&mut self, buttons: Buttons) {
+        let _ = self.add_local_input(buttons);
+    }
+    fn step(&mut self, nes: &mut Nes) {
+        self.advance(nes).expect("live peer advance");
+    }
+}
+
+/// A `join_live` spectator starts from the match's present: it receives the
+/// host's confirmed state, replays only the tail, and shows exactly the
+/// players' confirmed picture.
+#[test]
+fn live_spectator_starts_from_the_present() {
+    let rom = nestest_rom();
+    let frames = 700u32;
+    let (p0, p1) = make_input_streams(frames, 0x5BEC_7A7E);
+    let conditions = LinkConditions {
+        latency_polls: 1,
+        jitter_polls: 1,
+        drop_prob: 0.0,
+    };
+    // Two players plus a third leg for the spectator: the players' broadcasts
+    // reach it, and its join handshake reaches the host.
+    let mut legs = MeshTransport::mesh(3, conditions, 0x5BEC).into_iter();
+    let (t0, t1, mut t2) = (
+        legs.next().expect("leg 0"),
+        legs.next().expect("leg 1"),
+        legs.next().expect("leg 2"),
+    );
+    let mut nes0 = Nes::from_rom(&rom).expect("load");
+    let mut nes1 = Nes::from_rom(&rom).expect("load");
+    let mut spec_nes = Nes::from_rom(&rom).expect("load");
+    let hash = *nes0.rom_sha256();
+    let mut s0 = RollbackSession::new(SessionConfig::default(), t0, hash);
+    let mut s1 = RollbackSession::new(
+        SessionConfig {
+            local_player: 1,
+            ..SessionConfig::default()
+        },
+        t1,
+        hash,
+    );
+
+    let mut authored = [0u32; 2];
+    let mut drive = |s0: &mut RollbackSession<MeshTransport>,
+                     s1: &mut RollbackSession<MeshTransport>| {
+        for (p, (s, nes, stream)) in [(s0, &mut nes0, &p0), (s1, &mut nes1, &p1)]
+            .into_iter()
+            .enumerate()
+        {
+            while authored[p] <= s.current_frame() && (authored[p] as usize) < stream.len() {
+                let _ = s.add_local_input(stream[authored[p] as usize]);
+                authored[p] += 1;
+            }
+            s.advance(nes).expect("player advance");
+        }
+    };
+    for _ in 0..JOIN_AFTER_TICKS {
+        drive(&mut s0, &mut s1);
+        let _ = t2.poll();
+    }
+
+    let mut spec = SpectatorSession::new(
+        SpectatorConfig {
+            join_live: true,
+            ..SpectatorConfig::default()
+        },
+        t2,
+        hash,
+    );
+    let mut first_frame = None;
+    let target = frames - 100;
+    for _ in 0..frames * 8 {
+        drive(&mut s0, &mut s1);
+        if spec.current_frame() < target {
+            let out = spec.advance(&mut spec_nes);
+            if out.produced_frame {
+                first_frame.get_or_insert(out.frame);
+            }
+        } else if s0.last_confirmed_frame().is_some_and(|c| c >= target) {
+            break;
+        }
+    }
+
+    let first_frame = first_frame.expect("the spectator showed frames");
+    assert!(
+        first_frame > 100,
+        "the spectator started from the present, not cold boot (frame {first_frame})"
+    );
+    assert_eq!(spec.current_frame(), target);
+    assert_eq!(
+        Some(gameplay_digest(&spec_nes)),
+        s0.confirmed_entering_digest(target),
+        "the spectator shows the players' confirmed state entering frame {target}"
+    );
+}
+
+// ───────────────────────────────────────────────────────────────────────────
+// Match recording
+// ───────────────────────────────────────────────────────────────────────────
+
+/// Both players and a spectator record the same match over a latent link.
+/// Every recording holds the confirmed stream only, so despite the rollbacks
+/// they agree frame for frame and hash for hash, and each one verifies on a
+/// fresh core that never took part.
+#[test]
+fn recorded_match_verifies_on_a_fresh_core() {
+    let rom = nestest_rom();
+    let frames = 300u32;
+    let (p0, p1) = make_input_streams(frames, 0x4EC0_7D3D);
+    let conditions = LinkConditions {
+        latency_polls: 3,
+        jitter_polls: 2,
+        drop_prob: 0.0,
+    };
+    let mut legs = MeshTransport::mesh(3, conditions, 0x4EC0).into_iter();
+    let (t0, t1, t2) = (
+        legs.next().expect("leg 0"),
+        legs.next().expect("leg 1"),
+        legs.next().expect("leg 2"),
+    );
+    let mut nes0 = Nes::from_rom(&rom).expect("load");
+    let mut nes1 = Nes::from_rom(&rom).expect("load");
+    let mut spec_nes = Nes::from_rom(&rom).expect("load");
+    let hash = *nes0.rom_sha256();
+    let settings = NetSettings::capture(&nes0);
+    let mut s0 = RollbackSession::new(SessionConfig::default(), t0, hash);
+    let mut s1 = RollbackSession::new(
+        SessionConfig {
+            local_player: 1,
+            ..SessionConfig::default()
+        },
+        t1,
+        hash,
+    );
+    let mut spec = SpectatorSession::new(SpectatorConfig::default(), t2, hash);
+    // Input delay pads the front of the match with idle frames.
+    let delay = SessionConfig::default().input_delay;
+    let total = frames + delay;
+    s0.start_recording(&settings);
+    s1.start_recording(&settings);
+    spec.start_recording(&settings);
+
+    let recorded = |r: Option<&MatchRecorder>| r.map_or(0, MatchRecorder::len);
+    let mut authored = [0u32; 2];
+    let mut rolled_back = false;
+    for _ in 0..frames * 8 {
+        for (p, (s, nes, stream)) in [(&mut s0, &mut nes0, &p0), (&mut s1, &mut nes1, &p1)]
+            .into_iter()
+            .enumerate()
+        {
+            while authored[p] <= s.current_frame() && (authored[p] as usize) < stream.len() {
+                let _ = s.add_local_input(stream[authored[p] as usize]);
+                authored[p] += 1;
+            }
+            rolled_back |= s.advance(nes).expect("player advance").rolled_back;
+        }
+        let _ = spec.advance(&mut spec_nes);
+        if [s0.recording(), s1.recording(), spec.recording()]
+            .into_iter()
+            .all(|r| recorded(r) == total as usize)
+        {
+            break;
+        }
+    }
+    assert!(rolled_back, "the link should force rollbacks");
+
+    let movies: Vec<_> = [
+        s0.take_recording(),
+        s1.take_recording(),
+        spec.take_recording(),
+    ]
+    .into_iter()
+    .map(|r| {
+        let r = r.expect("recording in progress");
+        assert!(!r.is_interrupted());
+        assert_eq!(r.start_frame(), Some(0));
+        r.finish().expect("frames were recorded")
+    })
+    .collect();
+    for movie in &movies {
+        assert_eq!(movie.len(), total as usize);
+        assert_eq!(movie.frames, movies[0].frames);
+        assert_eq!(movie.attestation, movies[0].attestation);
+        let info = movie.match_info.as_ref().expect("match metadata");
+        assert_eq!(info.num_players, 2);
+
+        let parsed = Movie::deserialize(&movie.serialize()).expect("round-trip");
+        let mut verifier = Nes::from_rom(&rom).expect("load");
+        assert!(match_record::prepare_replay(&parsed, &mut verifier));
+        assert!(
+            matches!(
+                parsed.verify(&mut verifier),
+                Ok(VerifyOutcome::Match { frames: f, .. }) if f == total
+            ),
+            "the recording replays to its attested hash"
+        );
+    }
+    // Every recorded frame carries the inputs the players authored.
+    let (e0, e1) = (effective(&p0, total, delay), effective(&p1, total, delay));
+    for (f, input) in movies[0].frames.iter().enumerate() {
+        assert_eq!((input.p1, input.p2), (e0[f], e1[f]), "frame {f}");
+    }
+}
+
+/// Drive two sessions the way the frontend does: every tick each peer adds
+/// its input for that tick — stalled or not, so the session decides which
+/// frame it lands on — then advances. `host_tick` runs on the host before
+/// each of its ticks (to schedule delay changes). Returns the sessions after
+/// `ticks` ticks.
+fn run_realtime(
+    rom: &[u8],
+    (p0, p1): (&[Buttons], &[Buttons]),
+    conditions: LinkConditions,
+    base: SessionConfig,
+    mut host_tick: impl FnMut(u32, &mut RollbackSession<MemoryTransport>),
+) -> (
+    RollbackSession<MemoryTransport>,
+    RollbackSession<MemoryTransport>,
+) {
+    let (t0, t1) = MemoryTransport::pair(conditions, 0xDE1A_7000);
+    let mut nes0 = Nes::from_rom(rom).expect("load");
+    let mut nes1 = Nes::from_rom(rom).expect("load");
+    let hash = *nes0.rom_sha256();
+    let mut s0 = RollbackSession::new(base, t0, hash);
+    let mut s1 = RollbackSession::new(
+        SessionConfig {
+            local_player: 1,
+            ..base
+        },
+        t1,
+        hash,
+    );
+    for (tick, (&i0, &i1)) in (0u32..).zip(p0.iter().zip(p1)) {
+        host_tick(tick, &mut s0);
+        let _ = s0.add_local_input(i0);
+        let _ = s0.advance(&mut nes0).expect("s0 advance");
+        let _ = s1.add_local_input(i1);
+        let _ = s1.advance(&mut nes1).expect("s1 advance");
+    }
+    (s0, s1)
+}
+
+/// Both peers agree on every confirmed frame's inputs and on the state they
+/// produce, and that state is what a plain no-rollback run of those inputs
+/// reaches. Each port's confirmed inputs are the inputs its player authored,
+/// in order — a delay change may hold one (growing) or drop some (shrinking),
+/// but never reorders them. Returns the newest frame both peers confirmed.
+fn assert_confirmed_match(
+    rom: &[u8],
+    s0: &RollbackSession<MemoryTransport>,
+    s1: &RollbackSession<MemoryTransport>,
+    authored: [&[Buttons]; 2],
+) -> u32 {
+    let confirmed = s0
+        .last_confirmed_frame()
+        .zip(s1.last_confirmed_frame())
+        .map(|(a, b)| a.min(b))
+        .expect("both peers confirmed frames");
+    let mut nes = Nes::from_rom(rom).expect("load");
+    let mut cursor = [0usize; 2];
+    for f in 0..confirmed {
+        let input = s0.confirmed_input(f).expect("s0 confirmed");
+        assert_eq!(Some(input), s1.confirmed_input(f), "frame {f} inputs");
+        for (port, stream) in authored.iter().enumerate() {
+            let bits = Buttons::from_bits_truncate(input[port]);
+            // Empty input also fills the frames seeded before the first
+            // authored one, so only held buttons are traced back.
+            if !bits.is_empty() {
+                cursor[port] += stream[cursor[port]..]
+                    .iter()
+                    .position(|&b| b == bits)
+                    .unwrap_or_else(|| panic!("frame {f} port {port}: input out of order"));
+            }
+            nes.set_buttons(port, bits);
+        }
+        let _ = nes.run_frame();
+    }
+    let d0 = s0.confirmed_entering_digest(confirmed).expect("s0 digest");
+    assert_eq!(Some(d0), s1.confirmed_entering_digest(confirmed));
+    assert_eq!(
+        d0,
+        gameplay_digest(&nes),
+        "matches a plain run of the inputs"
+    );
+    confirmed
+}
+
+/// A host-scheduled input-delay change — growing, then shrinking — takes
+/// effect on the same frame on both peers over a jittery, lossy link, and
+/// the match stays a deterministic function of the confirmed inputs.
+#[test]
+fn scheduled_input_delay_changes_stay_in_sync() {
+    let rom = nestest_rom();
+    let (p0, p1) = make_input_streams(600, 0x0DE1_A7ED);
+    let conditions = LinkConditions {
+        latency_polls: 3,
+        jitter_polls: 2,
+        drop_prob: 0.05,
+    };
+    let base = SessionConfig {
+        input_delay: 2,
+        ..SessionConfig::default()
+    };
+    let (s0, s1) = run_realtime(
+        &rom,
+        (&p0, &p1),
+        conditions,
+        base,
+        |tick, host| match tick {
+            150 => assert!(host.schedule_input_delay(6).is_some()),
+            350 => assert!(host.schedule_input_delay(1).is_some()),
+            _ => {}
+        },
+    );
+
+    let changes = s0.input_delay_changes();
+    assert_eq!(changes, s1.input_delay_changes(), "peers switch together");
+    let steps: Vec<_> = changes.iter().map(|c| (c.from, c.to)).collect();
+    assert_eq!(steps, [(2, 6), (6, 1)]);
+    assert_eq!((s0.input_delay(), s1.input_delay()), (1, 1));
+
+    let confirmed = assert_confirmed_match(&rom, &s0, &s1, [&p0, &p1]);
+    assert!(confirmed > changes[1].frame, "confirmed past the shrink");
+}
+
+/// With [`DelayMode::Auto`] the host settles on a delay that covers the
+/// link's round trip, and the other peer follows it frame for frame.
+#[test]
+fn auto_input_delay_follows_the_link() {
+    let rom = nestest_rom();
+    // Buttons held for a few frames at a time, as a player would, so what
+    // rolls back is the link rather than a new press every tick.
+    let (p0, p1) = make_input_streams(900, 0xA070_DE1A);
+    let held = |p: &[Buttons]| -> Vec<Buttons> { (0..p.len()).map(|i| p[i & !7]).collect() };
+    let (p0, p1) = (held(&p0), held(&p1));
+    let base = SessionConfig {
+        input_delay: 1,
+        delay_tuning: DelayTuning {
+            mode: DelayMode::Auto,
+            window_ticks: 60,
+            ..DelayTuning::default()
+        },
+        ..SessionConfig::default()
+    };
+    let (s0, s1) = run_realtime(
+        &rom,
+        (&p0, &p1),
+        LinkConditions::fixed_latency(4),
+        base,
+        |_, _| {},
+    );
+
+    let changes = s0.input_delay_changes();
+    assert!(!changes.is_empty(), "the host adapted its delay");
+    assert_eq!(changes, s1.input_delay_changes(), "the peer followed");
+    let settled = s0.input_delay();
+    assert_eq!(s1.input_delay(), settled);
+    assert!(
+        (4..=8).contains(&settled),
+        "a 4-poll link needs at least its one-way latency, got {settled}"
+    );
+    assert!(s0.delay_tuner().round_trip_ticks().is_some());
+
+    assert_confirmed_match(&rom, &s0, &s1, [&p0, &p1]);
+}
+
+/// Chat lines sent mid-match over a lossy link reach the other peer exactly
+/// once, and never perturb the emulated match.
+#[test]
+fn chat_over_a_lossy_link_arrives_once() {
+    let rom = nestest_rom();
+    let (p0, p1) = make_input_streams(400, 0xC4A7_0001);
+    let conditions = LinkConditions {
+        latency_polls: 2,
+        jitter_polls: 2,
+        drop_prob: 0.25,
+    };
+    let (mut s0, mut s1) = run_realtime(
+        &rom,
+        (&p0, &p1),
+        conditions,
+        SessionConfig {
+            input_delay: 2,
+            ..SessionConfig::default()
+        },
+        |tick, host| match tick {
+            10 => host.send_chat("glhf").expect("send"),
+            11 => host.send_chat("best of three?").expect("send"),
+            200 => host.send_chat("gg").expect("send"),
+            _ => {}
+        },
+    );
+
+    let mut heard: Vec<_> = s1
+        .take_chat()
+        .into_iter()
+        .map(|l| (l.from, l.text))
+        .collect();
+    heard.sort();
+    assert_eq!(
+        heard,
+        [
+            (0, "best of three?".to_owned()),
+            (0, "gg".to_owned()),
+            (0, "glhf".to_owned()),
+        ]
+    );
+    assert!(s0.take_chat().is_empty());
+    assert_confirmed_match(&rom, &s0, &s1, [&p0, &p1]);
+}
+
+/// Expansion devices ride with the inputs: player 1 mouses on port 0 while
+/// player 2 aims a Zapper on port 1, over a lossy, jittery link. Both peers
+/// confirm the same devices frame for frame — each on its owner's port — and
+/// the state they reach is a plain no-rollback run of those inputs.
+#[test]
+fn expansion_devices_roll_back_in_sync() {
+    let rom = nestest_rom();
+    let ticks = 500u32;
+    let (p0, p1) = make_input_streams(ticks, 0xDE71_CE00);
+    let mut rng = SplitMix64::new(0xDE71_CE01);
+    let mut delta = || i8::try_from(rng.next_below(9)).unwrap_or(0) - 4;
+    let mice: Vec<_> = (0..ticks)
+        .map(|t| DeviceInput::Mouse {
+            port: 0,
+            dx: delta(),
+            dy: delta(),
+            left: t % 40 < 10,
+            right: false,
+            sensitivity: 1,
+        })
+        .collect();
+    // The aim moves every eight ticks, as a hand would; the trigger is
+    // pulled in short bursts.
+    let zappers: Vec<_> = (0..ticks)
+        .map(|t| DeviceInput::Zapper {
+            port: 1,
+            x: u16::try_from((t / 8 * 37) % 256).unwrap_or(0),
+            y: u16::try_from((t / 8 * 23) % 240).unwrap_or(0),
+            trigger: t % 30 < 3,
+        })
+        .collect();
+
+    let (t0, t1) = MemoryTransport::pair(
+        LinkConditions {
+            latency_polls: 3,
+            jitter_polls: 3,
+            drop_prob: 0.15,
+        },
+        0xDE71_CE02,
+    );
+    let mut nes0 = Nes::from_rom(&rom).expect("load");
+    let mut nes1 = Nes::from_rom(&rom).expect("load");
+    let hash = *nes0.rom_sha256();
+    let mut s0 = RollbackSession::new(SessionConfig::default(), t0, hash);
+    let mut s1 = RollbackSession::new(
+        SessionConfig {
+            local_player: 1,
+            ..SessionConfig::default()
+        },
+        t1,
+        hash,
+    );
+    let mut rolled_back = false;
+    for t in 0..ticks as usize {
+        let _ = s0.add_local_device_input(p0[t], mice[t]);
+        rolled_back |= s0.advance(&mut nes0).expect("s0 advance").rolled_back;
+        let _ = s1.add_local_device_input(p1[t], zappers[t]);
+        rolled_back |= s1.advance(&mut nes1).expect("s1 advance").rolled_back;
+    }
+    assert!(rolled_back, "the link should force rollbacks");
+
+    let confirmed = s0
+        .last_confirmed_frame()
+        .zip(s1.last_confirmed_frame())
+        .map(|(a, b)| a.min(b))
+        .expect("both peers confirmed frames");
+    assert!(confirmed > ticks / 2, "confirmed {confirmed} of {ticks}");
+    let mut nes = Nes::from_rom(&rom).expect("load");
+    let (mut aimed, mut moused) = (0, 0);
+    for f in 0..confirmed {
+        let input = s0.confirmed_input(f).expect("s0 confirmed");
+        let devices = s0.confirmed_devices(f).expect("s0 devices");
+        assert_eq!(Some(input), s1.confirmed_input(f), "frame {f} inputs");
+        assert_eq!(Some(devices), s1.confirmed_devices(f), "frame {f} devices");
+        assert!(matches!(
+            devices[0],
+            DeviceInput::None | DeviceInput::Mouse { port: 0, .. }
+        ));
+        assert!(matches!(
+            devices[1],
+            DeviceInput::None | DeviceInput::Zapper { port: 1, .. }
+        ));
+        moused += usize::from(devices[0] != DeviceInput::None);
+        aimed += usize::from(devices[1] != DeviceInput::None);
+        for (port, device) in devices.iter().enumerate().take(2) {
+            nes.set_buttons(port, Buttons::from_bits_truncate(input[port]));
+            device.apply(&mut nes);
+        }
+        let _ = nes.run_frame();
+    }
+    assert!(moused > 0 && aimed > 0, "both devices were carried");
+    let d0 = s0.confirmed_entering_digest(confirmed).expect("s0 digest");
+    assert_eq!(Some(d0), s1.confirmed_entering_digest(confirmed));
+    assert_eq!(d0, gameplay_digest(&nes), "matches a plain run");
+}
+
+/// A device claimed for another player's port, or in a Four Score match, is
+/// never credited: the receiving session drops it and keeps the buttons.
+#[test]
+fn devices_off_their_owners_port_are_dropped() {
+    let rom = nestest_rom();
+    let mut nes = Nes::from_rom(&rom).expect("load");
+    let hash = *nes.rom_sha256();
+    let (mut feeder, t) = MemoryTransport::pair(LinkConditions::PERFECT, 1);
+    let mut s = RollbackSession::new(SessionConfig::default(), t, hash);
+    let zapper = |port| DeviceInput::Zapper {
+        port,
+        x: 10,
+        y: 20,
+        trigger: true,
+    };
+    for frame in 0..4 {
+        // Player 2 sends a Zapper on player 1's port on even frames.
+        feeder.send(&NetMessage::Input {
+            player: 1,
+            frame,
+            input: 0x08,
+            device: zapper(u8::from(frame % 2 == 1)),
+        });
+    }
+    for _ in 0..6 {
+        let _ = s.add_local_input(Buttons::empty());
+        let _ = s.advance(&mut nes).expect("advance");
+    }
+    for frame in 0..4 {
+        let expected = if frame % 2 == 1 {
+            zapper(1)
+        } else {
+            DeviceInput::None
+        };
+        assert_eq!(s.confirmed_devices(frame).map(|d| d[1]), Some(expected));
+        assert_eq!(s.confirmed_input(frame).map(|i| i[1]), Some(0x08));
+    }
+}
+
+/// A stalled tick's input is dropped rather than replacing the one already
+/// sent for that frame: the peer may have confirmed the first and acked it,
+/// which ends the resends that would have carried a replacement — so the
+/// two would confirm different inputs. (Found soak-testing bursty outages.)
+#[test]
+fn stalled_re_add_keeps_the_input_already_sent() {
+    let rom = nestest_rom();
+    let (t0, t1) = MemoryTransport::pair(LinkConditions::PERFECT, 0x5EAD_D000);
+    let mut nes0 = Nes::from_rom(&rom).expect("load");
+    let mut nes1 = Nes::from_rom(&rom).expect("load");
+    let hash = *nes0.rom_sha256();
+    let mut s0 = RollbackSession::new(SessionConfig::default(), t0, hash);
+    let mut s1 = RollbackSession::new(
+        SessionConfig {
+            local_player: 1,
+            ..SessionConfig::default()
+        },
+        t1,
+        hash,
+    );
+
+    // Only s0 runs, so it stalls once it is a rollback window ahead.
+    let mut stalled = false;
+    for _ in 0..40 {
+        let _ = s0.add_local_input(Buttons::A);
+        if !s0.advance(&mut nes0).expect("s0 advance").produced_frame {
+            stalled = true;
+            break;
+        }
+    }
+    assert!(stalled, "s0 stalls without its peer");
+    let frame = s0.current_frame() + s0.input_delay();
+    assert!(
+        !s0.add_local_input(Buttons::B),
+        "a stalled tick's input is dropped"
+    );
+
+    for _ in 0..60 {
+        let _ = s0.add_local_input(Buttons::empty());
+        let _ = s0.advance(&mut nes0).expect("s0 advance");
+        let _ = s1.add_local_input(Buttons::empty());
+        let _ = s1.advance(&mut nes1).expect("s1 advance");
+    }
+    for s in [&s0, &s1] {
+        assert_eq!(
+            s.confirmed_input(frame).map(|i| i[0]),
+            Some(Buttons::A.bits()),
+            "frame {frame} keeps the input first sent"
+        );
+    }
+}
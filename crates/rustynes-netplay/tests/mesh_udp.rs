@@ -189,7 +189,7 @@ fn run_n_sessions_over_udp(
             while authored[p] <= sessions[p].current_frame()
                 && (authored[p] as usize) < streams[p].len()
             {
-                sessions[p].add_local_input(streams[p][authored[p] as usize]);
+                let _ = sessions[p].add_local_input(streams[p][authored[p] as usize]);
                 authored[p] += 1;
             }
             let _ = sessions[p].advance(&mut nes[p])?;
@@ -12,7 +12,7 @@
 //!   on `127.0.0.1` that drives the production
 //!   [`rustynes_netplay::signaling::Relay`] routing logic, so the two clients
 //!   exchange `Join` / `Joined` / `PeerJoined` / `PublicAddr` exactly as they
-//!   would against the deployed `signaling_server`;
+//!   would against the deployed `rustynes-lobby`;
 //! - a **mock STUN responder** — a UDP socket on `127.0.0.1` that answers each
 //!   Binding Request with the source address it observed (i.e. the client's own
 //!   loopback `IP:port`), so discovery yields a reflexive address the peer can
@@ -270,6 +270,7 @@ fn nat_connect_loopback_punch_then_session_digests_agree() {
         stun_servers: vec![stun_addr.to_string()],
         turn: None,
         signaling_url: url.to_string(),
+        room_password: String::new(),
     };
 
     let (mut host, room) =
@@ -360,11 +361,11 @@ fn nat_connect_loopback_punch_then_session_digests_agree() {
     {
         ticks += 1;
         while ha <= host_sess.current_frame() && (ha as usize) < host_stream.len() {
-            host_sess.add_local_input(host_stream[ha as usize]);
+            let _ = host_sess.add_local_input(host_stream[ha as usize]);
             ha += 1;
         }
         while ja <= join_sess.current_frame() && (ja as usize) < join_stream.len() {
-            join_sess.add_local_input(join_stream[ja as usize]);
+            let _ = join_sess.add_local_input(join_stream[ja as usize]);
             ja += 1;
         }
         host_sess.advance(&mut host_nes).expect("host advance");
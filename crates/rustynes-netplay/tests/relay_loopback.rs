@@ -51,7 +51,7 @@ use rustynes_core::{Buttons, Nes};
 use rustynes_netplay::signaling::{Action, ClientId, Relay, SignalMessage};
 use rustynes_netplay::{
     ConnectionState, NatConfig, NatConnect, NatPhase, NetplayConnection, RollbackSession,
-    SessionConfig, SplitMix64, TurnConfig, fnv1a64,
+    SecureChannel, SessionConfig, SessionKey, SplitMix64, TurnConfig, fnv1a64,
 };
 
 const MAGIC_COOKIE: u32 = 0x2112_A442;
@@ -467,6 +467,20 @@ fn input_stream(frames: u32, seed: u64) -> Vec<Buttons> {
 
 #[test]
 fn nat_connect_loopback_relay_then_session_digests_agree() {
+    relay_match(None);
+}
+
+/// The same match with both peers' traffic sealed under a shared room key:
+/// the secured transport rides the TURN relay exactly like the plain one.
+#[test]
+fn secured_match_over_the_relay_digests_agree() {
+    relay_match(Some(&SessionKey::derive("relay-room-code", b"")));
+}
+
+/// Orchestrate a host + joiner onto the mock TURN relay, handshake (sealed
+/// under `key` if given), and run a short session whose confirmed digests
+/// must agree.
+fn relay_match(key: Option<&SessionKey>) {
     let rom = nestest_rom();
     let mut host_nes = Nes::from_rom(&rom).expect("host nes");
     let mut join_nes = Nes::from_rom(&rom).expect("join nes");
@@ -486,6 +500,7 @@ fn nat_connect_loopback_relay_then_session_digests_agree() {
         stun_servers: vec![stun_addr.to_string()],
         turn: Some(turn_cfg.clone()),
         signaling_url: url.to_string(),
+        room_password: String::new(),
     };
 
     let (mut host, room) =
@@ -523,6 +538,10 @@ fn nat_connect_loopback_relay_then_session_digests_agree() {
     // Hand off the relay transports + assert is_relayed propagates.
     let mut host_conn = host.into_connection();
     let mut join_conn = join.into_connection();
+    if let Some(key) = key {
+        host_conn = host_conn.secured(SecureChannel::random(key).expect("os rng"));
+        join_conn = join_conn.secured(SecureChannel::random(key).expect("os rng"));
+    }
     assert!(host_conn.is_relayed(), "host connection rides the relay");
     assert!(join_conn.is_relayed(), "join connection rides the relay");
 
@@ -584,11 +603,11 @@ fn nat_connect_loopback_relay_then_session_digests_agree() {
     {
         ticks += 1;
         while ha <= host_sess.current_frame() && (ha as usize) < host_stream.len() {
-            host_sess.add_local_input(host_stream[ha as usize]);
+            let _ = host_sess.add_local_input(host_stream[ha as usize]);
             ha += 1;
         }
         while ja <= join_sess.current_frame() && (ja as usize) < join_stream.len() {
-            join_sess.add_local_input(join_stream[ja as usize]);
+            let _ = join_sess.add_local_input(join_stream[ja as usize]);
             ja += 1;
         }
         host_sess.advance(&mut host_nes).expect("host advance");
@@ -607,6 +626,12 @@ fn nat_connect_loopback_relay_then_session_digests_agree() {
         host_digest, join_digest,
         "the two peers must hold identical confirmed state after a TURN-relay handoff"
     );
+    if key.is_some() {
+        for t in [host_sess.transport(), join_sess.transport()] {
+            let channel = t.secure_channel().expect("secured transport");
+            assert!(channel.accepted() > 0, "sealed traffic was opened");
+        }
+    }
 
     stun_stop.store(true, Ordering::Relaxed);
     turn_stop.store(true, Ordering::Relaxed);
@@ -0,0 +1,101 @@
+//! The netplay soak test: seeded peers run the committed nestest ROM for
+//! thousands of frames over links that drop, reorder and suffer bursty
+//! outages, through [`rustynes_netplay::sim`] — the same driver as the
+//! `netplay_sim` tool. Each run must end with every peer agreeing on the
+//! confirmed inputs and on the state they produce, and that state must match
+//! a plain no-rollback run of those inputs.
+//!
+//! The default runs are sized for the regular test gate. The long soak is
+//! `#[ignore]`d; run it with
+//! `cargo test --release -p rustynes-netplay --test soak -- --ignored`.
+//! A failure prints the seed: `netplay_sim --seed <seed>` with the same
+//! players, frames and profile replays it exactly.
+
+use std::path::PathBuf;
+
+use rustynes_netplay::sim::{self, SimConfig};
+use rustynes_netplay::{LinkConditions, LinkFaults, SessionConfig};
+
+/// Resolve the committed public-domain nestest ROM (`tests/roms/nestest/`)
+/// from the workspace root, derived from this crate's manifest dir.
+fn nestest_rom() -> Vec<u8> {
+    let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let root = manifest
+        .parent()
+        .and_then(|p| p.parent())
+        .expect("workspace root is two levels above the crate manifest");
+    let rom = root.join("tests/roms/nestest/nestest.nes");
+    std::fs::read(&rom).unwrap_or_else(|e| panic!("read nestest rom {}: {e}", rom.display()))
+}
+
+/// Run `config` and assert agreement, naming the seed on failure.
+fn soak(rom: &[u8], profile: &str, config: &SimConfig) {
+    let report = sim::run(rom, config).expect("simulation starts");
+    assert!(
+        report.outcome.is_agreed(),
+        "{} players, {} frames, profile {profile}, seed {:#x}: {}",
+        config.session.num_players,
+        config.frames,
+        config.seed,
+        report.outcome,
+    );
+    // A hostile link that never made anyone roll back would mean the
+    // conditions were not applied.
+    assert!(
+        report.peers.iter().any(|p| p.rollbacks > 0),
+        "profile {profile} never rolled back: {:?}",
+        report.peers,
+    );
+}
+
+fn config(
+    num_players: u8,
+    frames: u32,
+    (conditions, faults): (LinkConditions, LinkFaults),
+    seed: u64,
+) -> SimConfig {
+    SimConfig {
+        session: SessionConfig {
+            num_players,
+            ..SessionConfig::default()
+        },
+        frames,
+        conditions,
+        faults,
+        seed,
+    }
+}
+
+/// Two players over the bursty preset: outages long enough to stall both
+/// peers, with loss and reordering in between.
+#[test]
+fn two_players_survive_bursty_outages() {
+    let rom = nestest_rom();
+    let bursty = sim::profile("bursty").expect("preset");
+    soak(&rom, "bursty", &config(2, 900, bursty, 0x50A4_0001));
+}
+
+/// Three players over the lossy preset, on the Four Score mesh.
+#[test]
+fn three_players_survive_a_lossy_mesh() {
+    let rom = nestest_rom();
+    let lossy = sim::profile("lossy").expect("preset");
+    soak(&rom, "lossy", &config(3, 600, lossy, 0x50A4_0002));
+}
+
+/// The long soak: every hostile preset, two to four players, several seeds,
+/// ten thousand frames each.
+#[test]
+#[ignore = "long soak; run explicitly with --release -- --ignored"]
+fn long_soak_every_hostile_profile() {
+    let rom = nestest_rom();
+    for name in ["wifi", "intercontinental", "lossy", "bursty"] {
+        let link = sim::profile(name).expect("preset");
+        for num_players in 2..=4 {
+            for seed in 0..3 {
+                let seed = 0x50A4_1000 + u64::from(num_players) * 16 + seed;
+                soak(&rom, name, &config(num_players, 10_000, link, seed));
+            }
+        }
+    }
+}
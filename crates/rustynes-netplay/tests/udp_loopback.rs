@@ -31,9 +31,10 @@ use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
 use std::path::PathBuf;
 use std::time::Duration;
 
-use rustynes_core::{Buttons, Nes};
+use rustynes_core::{Buttons, DeviceInput, Nes};
 use rustynes_netplay::{
-    NetplayError, RollbackSession, SessionConfig, SplitMix64, UdpTransport, fnv1a64,
+    NetMessage, NetplayError, RollbackSession, SecureChannel, SessionConfig, SessionKey,
+    SplitMix64, UdpTransport, fnv1a64,
 };
 
 fn gameplay_digest(nes: &Nes) -> u64 {
@@ -139,14 +140,14 @@ fn udp_asymmetric_idle_drive_stays_in_sync_sprite_heavy() {
             ticks += 1;
             for _ in 0..(rng.next_u8() % 3) {
                 while a0 <= s0.current_frame() {
-                    s0.add_local_input(Buttons::empty());
+                    let _ = s0.add_local_input(Buttons::empty());
                     a0 += 1;
                 }
                 s0.advance(&mut nes0).expect("s0 advance");
             }
             for _ in 0..(rng.next_u8() % 3) {
                 while a1 <= s1.current_frame() {
-                    s1.add_local_input(Buttons::empty());
+                    let _ = s1.add_local_input(Buttons::empty());
                     a1 += 1;
                 }
                 s1.advance(&mut nes1).expect("s1 advance");
@@ -182,6 +183,30 @@ fn run_two_sessions_over_udp(
     base: SessionConfig,
 ) -> Result<(u64, u64), NetplayError> {
     let (t0, t1) = udp_pair();
+    let (s0, s1) = run_sessions(t0, t1, rom, p0, p1, compare_frame, base, |_| {})?;
+    let d0 = s0
+        .confirmed_entering_digest(compare_frame)
+        .expect("s0 confirmed digest present");
+    let d1 = s1
+        .confirmed_entering_digest(compare_frame)
+        .expect("s1 confirmed digest present");
+    Ok((d0, d1))
+}
+
+/// Drive two sessions over the given transport pair until both confirm at
+/// least `compare_frame`, calling `each_tick` with the tick number before both
+/// peers advance.
+#[allow(clippy::too_many_arguments)]
+fn run_sessions(
+    t0: UdpTransport,
+    t1: UdpTransport,
+    rom: &[u8],
+    p0: &[Buttons],
+    p1: &[Buttons],
+    compare_frame: u32,
+    base: SessionConfig,
+    mut each_tick: impl FnMut(u32),
+) -> Result<(RollbackSession<UdpTransport>, RollbackSession<UdpTransport>), NetplayError> {
     let mut nes0 = Nes::from_rom(rom).expect("load nestest p0");
     let mut nes1 = Nes::from_rom(rom).expect("load nestest p1");
     let hash = *nes0.rom_sha256();
@@ -211,15 +236,16 @@ fn run_two_sessions_over_udp(
     let mut ticks = 0;
     while !(confirmed_at(&s0) && confirmed_at(&s1)) && ticks < max_ticks {
         ticks += 1;
+        each_tick(ticks);
 
         while authored0 <= s0.current_frame() && (authored0 as usize) < p0.len() {
-            s0.add_local_input(p0[authored0 as usize]);
+            let _ = s0.add_local_input(p0[authored0 as usize]);
             authored0 += 1;
         }
         let _ = s0.advance(&mut nes0)?;
 
         while authored1 <= s1.current_frame() && (authored1 as usize) < p1.len() {
-            s1.add_local_input(p1[authored1 as usize]);
+            let _ = s1.add_local_input(p1[authored1 as usize]);
             authored1 += 1;
         }
         let _ = s1.advance(&mut nes1)?;
@@ -240,13 +266,7 @@ fn run_two_sessions_over_udp(
         s1.current_frame()
     );
 
-    let d0 = s0
-        .confirmed_entering_digest(compare_frame)
-        .expect("s0 confirmed digest present");
-    let d1 = s1
-        .confirmed_entering_digest(compare_frame)
-        .expect("s1 confirmed digest present");
-    Ok((d0, d1))
+    Ok((s0, s1))
 }
 
 /// THE STAGE-2 HEADLINE: the rollback session reproduces the no-rollback
@@ -274,3 +294,59 @@ fn rollback_over_udp_matches_reference() {
         "rollback over real UDP must equal the no-rollback reference"
     );
 }
+
+/// A secured pair confirms exactly the inputs its players authored while a
+/// third party who can reach both ports, but lacks the key, injects inputs
+/// around every frame: plain ones and ones sealed under a guessed passphrase.
+/// Unsecured, an injected input that lands after the real one and before the
+/// frame confirms would replace it.
+#[test]
+fn secured_udp_ignores_injected_inputs() {
+    let rom = nestest_rom();
+    let frames = 300u32;
+    let (p0, p1) = make_input_streams(frames, 0x5EC0_0001);
+    let cfg = SessionConfig::default();
+    let compare_frame = frames - 40;
+
+    let key = SessionKey::derive("a long shared passphrase", b"");
+    let (t0, t1) = udp_pair();
+    let addr0 = t0.local_addr().unwrap();
+    let addr1 = t1.local_addr().unwrap();
+    let t0 = t0.secured(SecureChannel::random(&key).unwrap());
+    let t1 = t1.secured(SecureChannel::random(&key).unwrap());
+
+    let attacker = UdpSocket::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))).unwrap();
+    let mut guess = SecureChannel::random(&SessionKey::derive("password", b"")).unwrap();
+    let inject = |tick: u32| {
+        // Claim to be the other player for the frames around each peer's
+        // current one.
+        for (player, to) in [(1u8, addr0), (0u8, addr1)] {
+            for frame in tick.saturating_sub(8)..tick + 8 {
+                let forged = NetMessage::Input {
+                    player,
+                    frame,
+                    input: 0xFF,
+                    device: DeviceInput::None,
+                };
+                let _ = attacker.send_to(&forged.to_bytes(), to);
+                let _ = attacker.send_to(&guess.seal_to_bytes(&forged), to);
+            }
+        }
+    };
+
+    let (s0, s1) =
+        run_sessions(t0, t1, &rom, &p0, &p1, compare_frame, cfg, inject).expect("no desync");
+    let e0 = effective(&p0, compare_frame, cfg.input_delay);
+    let e1 = effective(&p1, compare_frame, cfg.input_delay);
+    for f in 0..compare_frame {
+        let want = [e0[f as usize].bits(), e1[f as usize].bits()];
+        for s in [&s0, &s1] {
+            let got = s.confirmed_input(f).expect("confirmed");
+            assert_eq!(got[..2], want, "frame {f}: an injected input was confirmed");
+        }
+    }
+    for s in [&s0, &s1] {
+        let channel = s.transport().secure_channel().expect("secured");
+        assert!(channel.rejected() > 0, "the injections reached the peer");
+    }
+}
@@ -2176,6 +2176,21 @@ impl Ppu {
         self.ciram[phys & 0x07FF] = value;
     }
 
+    /// v2.5.0 — store one CIRAM byte at a physical offset on behalf of the
+    /// high-level-emulated FDS BIOS, whose file loads bypass `$2007`. Reached
+    /// only from a trapped BIOS call inside a CPU write, so it is part of the
+    /// deterministic run loop exactly like the `$2007` writes it replaces.
+    pub const fn hle_write_ciram(&mut self, phys: usize, value: u8) {
+        self.ciram[phys & 0x07FF] = value;
+    }
+
+    /// v2.5.0 — store one palette byte for the high-level-emulated FDS BIOS,
+    /// with the `$2007` mirroring and 6-bit masking (see
+    /// [`Self::hle_write_ciram`]).
+    pub const fn hle_write_palette(&mut self, addr: u16, value: u8) {
+        self.palette_ram[palette_index(addr)] = value & 0x3F;
+    }
+
     /// `true` when sprites are rendered in 8x16 mode (CTRL bit 5).
     #[must_use]
     pub const fn sprite_size_16(&self) -> bool {
@@ -0,0 +1,304 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//! Netplay network-conditions simulator.
+//!
+//! ## Why this exists
+//!
+//! The determinism harness proves rollback stays in sync over a few hundred
+//! frames of a mildly jittery link. Netcode changes also have to hold up over
+//! thousands of frames of the links people actually play on — Wi-Fi that drops
+//! out for a third of a second, a transatlantic hop that reorders — and what
+//! users feel there is the cost: how often the game rolls back, how far, and
+//! how long it freezes waiting for the others. This tool runs N simulated peers
+//! against a real ROM through `rustynes_netplay::sim`, prints those costs per
+//! peer, and checks that every peer ends on the same confirmed inputs and state
+//! as a plain no-rollback run.
+//!
+//! Every draw is seeded. A failing run prints its seed and the exact command
+//! that replays it; `--runs N` walks N consecutive seeds to hunt for one.
+//!
+//! ## Usage
+//!
+//! ```text
+//! netplay_sim                                  # nestest, 2 players, wifi, 3000 frames
+//! netplay_sim --profile bursty --players 4     # preset link, four peers
+//! netplay_sim --latency 6 --loss 0.1           # override any preset knob
+//! netplay_sim --runs 50 --seed 0x1000          # fifty seeds, stop at the first failure
+//! netplay_sim --rom path/to.nes --frames 20000 # a real game, a longer soak
+//! ```
+//!
+//! Presets (one-way, in 60 Hz polls): `perfect`, `lan`, `wifi`, `broadband`,
+//! `intercontinental`, `lossy`, `bursty`. Build with `--release` for long runs:
+//!
+//! ```text
+//! cargo run --release -p rustynes-test-harness --bin netplay_sim -- --profile bursty --runs 20
+//! ```
+//!
+//! Exit status: 0 when every run agreed, 1 when one did not, 2 on a usage error.
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Instant;
+
+use rustynes_netplay::sim::{self, PROFILES, SimConfig, SimReport};
+use rustynes_netplay::{DelayMode, DelayTuning, LinkConditions, LinkFaults, SessionConfig};
+
+/// The committed public-domain ROM the simulator runs by default.
+const DEFAULT_ROM: &str = "tests/roms/nestest/nestest.nes";
+
+/// The preset used when `--profile` is not given.
+const DEFAULT_PROFILE: &str = "wifi";
+
+/// Frames every peer must confirm per run.
+const DEFAULT_FRAMES: u32 = 3000;
+
+/// Workspace root, resolved from the compile-time manifest directory; only
+/// used to locate the default ROM. Pass `--rom` when running a relocated
+/// binary.
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .and_then(|p| p.parent())
+        .expect("workspace root is two levels above the crate manifest")
+        .to_path_buf()
+}
+
+/// Exit with a usage error.
+fn usage_error(msg: &str) -> ! {
+    eprintln!("netplay_sim: {msg}");
+    eprintln!("netplay_sim: see --help for accepted flags");
+    std::process::exit(2);
+}
+
+/// Parse a flag's value, exiting with a usage error rather than falling back
+/// to a default: a simulation of parameters nobody asked for would report on
+/// the wrong link.
+fn parse_value<T: FromStr>(value: Option<&str>, flag: &str) -> T {
+    let Some(raw) = value else {
+        usage_error(&format!("{flag} requires a value"));
+    };
+    raw.parse()
+        .unwrap_or_else(|_| usage_error(&format!("{flag}: cannot parse {raw:?}")))
+}
+
+/// Parse a probability in `[0.0, 1.0)`.
+fn parse_prob(value: Option<&str>, flag: &str) -> f64 {
+    let p: f64 = parse_value(value, flag);
+    if !(0.0..1.0).contains(&p) {
+        usage_error(&format!("{flag} must be in [0, 1), got {p}"));
+    }
+    p
+}
+
+/// Parse a seed, decimal or `0x`-prefixed hex.
+fn parse_seed(value: Option<&str>) -> u64 {
+    let Some(raw) = value else {
+        usage_error("--seed requires a value");
+    };
+    let parsed = raw
+        .strip_prefix("0x")
+        .map_or_else(|| raw.parse(), |hex| u64::from_str_radix(hex, 16));
+    parsed.unwrap_or_else(|_| usage_error(&format!("--seed: cannot parse {raw:?}")))
+}
+
+/// Everything the command line asked for.
+struct Args {
+    rom: PathBuf,
+    players: u8,
+    frames: u32,
+    delay: u32,
+    auto_delay: bool,
+    profile: String,
+    conditions: LinkConditions,
+    faults: LinkFaults,
+    seed: u64,
+    runs: u32,
+}
+
+const HELP: &str = "\
+netplay_sim [--rom PATH] [--players N] [--frames N] [--delay N] [--auto-delay]
+            [--profile NAME] [--latency POLLS] [--jitter POLLS] [--loss P]
+            [--reorder P] [--outage-prob P] [--outage-polls POLLS]
+            [--seed N] [--runs N]
+
+Runs N rollback peers against a ROM over a simulated link and reports
+rollbacks, re-simulated frames and stall time per peer, then checks every
+peer agrees with a plain run of the confirmed inputs. Link knobs are one-way
+and in 60 Hz polls; probabilities are in [0, 1). Overrides apply on top of
+the preset. A failure prints the seed and the command that replays it.";
+
+fn parse_args() -> Args {
+    let mut args = Args {
+        rom: workspace_root().join(DEFAULT_ROM),
+        players: 2,
+        frames: DEFAULT_FRAMES,
+        delay: SessionConfig::default().input_delay,
+        auto_delay: false,
+        profile: DEFAULT_PROFILE.to_owned(),
+        conditions: LinkConditions::PERFECT,
+        faults: LinkFaults::NONE,
+        seed: 1,
+        runs: 1,
+    };
+    // Overrides apply after the preset whatever the flag order.
+    let mut overrides: Vec<(String, Option<String>)> = Vec::new();
+    let mut it = std::env::args().skip(1);
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--rom" => {
+                let Some(p) = it.next() else {
+                    usage_error("--rom requires a path");
+                };
+                args.rom = PathBuf::from(p);
+            }
+            "--players" => args.players = parse_value(it.next().as_deref(), "--players"),
+            "--frames" => args.frames = parse_value(it.next().as_deref(), "--frames"),
+            "--delay" => args.delay = parse_value(it.next().as_deref(), "--delay"),
+            "--auto-delay" => args.auto_delay = true,
+            "--profile" => {
+                let Some(name) = it.next() else {
+                    usage_error("--profile requires a name");
+                };
+                args.profile = name;
+            }
+            "--latency" | "--jitter" | "--loss" | "--reorder" | "--outage-prob"
+            | "--outage-polls" => overrides.push((arg.clone(), it.next())),
+            "--seed" => args.seed = parse_seed(it.next().as_deref()),
+            "--runs" => args.runs = parse_value(it.next().as_deref(), "--runs"),
+            "--help" | "-h" => {
+                println!("{HELP}");
+                std::process::exit(0);
+            }
+            other => usage_error(&format!("unknown argument {other:?}")),
+        }
+    }
+    let Some(preset) = sim::profile(&args.profile) else {
+        let names: Vec<&str> = PROFILES.iter().map(|(n, ..)| *n).collect();
+        usage_error(&format!(
+            "unknown profile {:?} (one of: {})",
+            args.profile,
+            names.join(", ")
+        ));
+    };
+    (args.conditions, args.faults) = preset;
+    for (flag, value) in &overrides {
+        let v = value.as_deref();
+        let (c, f) = (&mut args.conditions, &mut args.faults);
+        match flag.as_str() {
+            "--latency" => c.latency_polls = parse_value(v, flag),
+            "--jitter" => c.jitter_polls = parse_value(v, flag),
+            "--loss" => c.drop_prob = parse_prob(v, flag),
+            "--reorder" => f.reorder_prob = parse_prob(v, flag),
+            "--outage-prob" => f.outage_prob = parse_prob(v, flag),
+            _ => f.outage_polls = parse_value(v, flag),
+        }
+    }
+    if args.runs == 0 {
+        usage_error("--runs must be greater than zero");
+    }
+    args
+}
+
+/// The command that replays the run with `seed`, every link knob explicit so
+/// it does not depend on the presets staying as they are.
+fn replay_command(args: &Args, seed: u64) -> String {
+    let (c, f) = (args.conditions, args.faults);
+    format!(
+        "netplay_sim --rom {} --players {} --frames {} --delay {}{} --latency {} --jitter {} \
+         --loss {} --reorder {} --outage-prob {} --outage-polls {} --seed {seed:#x}",
+        args.rom.display(),
+        args.players,
+        args.frames,
+        args.delay,
+        if args.auto_delay { " --auto-delay" } else { "" },
+        c.latency_polls,
+        c.jitter_polls,
+        c.drop_prob,
+        f.reorder_prob,
+        f.outage_prob,
+        f.outage_polls,
+    )
+}
+
+fn print_report(report: &SimReport) {
+    println!(
+        "  {:<6} {:>9} {:>11} {:>9} {:>7} {:>10} {:>10}",
+        "player", "rollbacks", "resimulated", "max depth", "stalls", "stall ms", "confirmed"
+    );
+    for (p, s) in report.peers.iter().enumerate() {
+        println!(
+            "  {p:<6} {:>9} {:>11} {:>9} {:>7} {:>10.0} {:>10}",
+            s.rollbacks,
+            s.resimulated_frames,
+            s.max_rollback,
+            s.stalled_ticks,
+            s.stall_time().as_secs_f64() * 1.0e3,
+            s.confirmed_frame
+                .map_or_else(|| "-".to_owned(), |f| f.to_string()),
+        );
+    }
+    println!("  {} ticks: {}", report.ticks, report.outcome);
+}
+
+fn main() {
+    let args = parse_args();
+    let rom = std::fs::read(&args.rom).unwrap_or_else(|e| {
+        eprintln!("netplay_sim: read {}: {e}", args.rom.display());
+        std::process::exit(1);
+    });
+    let (c, f) = (args.conditions, args.faults);
+    println!(
+        "netplay_sim — {} players, {} frames, delay {}{}, profile {} (latency {}, jitter {}, \
+         loss {}, reorder {}, outage {} x {} polls)",
+        args.players,
+        args.frames,
+        args.delay,
+        if args.auto_delay { " (auto)" } else { "" },
+        args.profile,
+        c.latency_polls,
+        c.jitter_polls,
+        c.drop_prob,
+        f.reorder_prob,
+        f.outage_prob,
+        f.outage_polls,
+    );
+
+    let session = SessionConfig {
+        num_players: args.players,
+        input_delay: args.delay,
+        delay_tuning: DelayTuning {
+            mode: if args.auto_delay {
+                DelayMode::Auto
+            } else {
+                DelayMode::Fixed
+            },
+            ..DelayTuning::default()
+        },
+        ..SessionConfig::default()
+    };
+    for run in 0..args.runs {
+        let seed = args.seed.wrapping_add(u64::from(run));
+        let config = SimConfig {
+            session,
+            frames: args.frames,
+            conditions: args.conditions,
+            faults: args.faults,
+            seed,
+        };
+        let started = Instant::now();
+        let report = sim::run(&rom, &config).unwrap_or_else(|e| {
+            eprintln!("netplay_sim: {e}");
+            std::process::exit(2);
+        });
+        println!(
+            "\nrun {}/{} — seed {seed:#x} ({:.1} s)",
+            run + 1,
+            args.runs,
+            started.elapsed().as_secs_f64()
+        );
+        print_report(&report);
+        if !report.outcome.is_agreed() {
+            println!("\nFAILED — replay with:\n  {}", replay_command(&args, seed));
+            std::process::exit(1);
+        }
+    }
+}
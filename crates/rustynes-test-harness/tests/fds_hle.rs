@@ -0,0 +1,303 @@
+//! High-level-emulated FDS BIOS harness (v2.5.0).
+//!
+//! The default tests boot a synthetic disk through the clean-room BIOS
+//! ([`rustynes_core::rustynes_mappers::fds_hle_bios`]) with no `disksys.rom`
+//! at all: a license-screen file, a boot program and its vectors, plus a file
+//! the program loads itself. The program then exercises `LoadFiles`,
+//! `GetDiskInfo`, `WriteFile`, an unsupported entry point and a disk-id
+//! mismatch, leaving each result in work RAM for the test to check.
+//!
+//! The `commercial-roms`-gated [`compat`] module tracks HLE compatibility
+//! against a list of FDS titles staged under `tests/roms/external/fds/`
+//! (gitignored, user-supplied dumps):
+//!
+//! ```text
+//! cargo test -p rustynes-test-harness --features commercial-roms \
+//!     --test fds_hle -- --nocapture
+//! ```
+
+use rustynes_core::{DEFAULT_SAMPLE_RATE, Nes};
+
+const FDS_SIDE_LEN: usize = 65500;
+
+/// One synthetic file: id, name, load address, kind, data.
+struct File {
+    id: u8,
+    name: &'static [u8; 8],
+    load: u16,
+    kind: u8,
+    data: Vec<u8>,
+}
+
+/// Serialize a single-side headerless disk with boot read file code 1.
+fn build_disk(files: &[File]) -> Vec<u8> {
+    let mut side = vec![0u8; 56];
+    side[0] = 0x01;
+    side[1..15].copy_from_slice(b"*NINTENDO-HVC*");
+    side[15] = 0x01; // maker
+    side[16..20].copy_from_slice(b"HLE ");
+    side[25] = 0x01; // boot read file code
+    side.extend_from_slice(&[0x02, u8::try_from(files.len()).unwrap()]);
+    for (i, f) in files.iter().enumerate() {
+        side.extend_from_slice(&[0x03, u8::try_from(i).unwrap(), f.id]);
+        side.extend_from_slice(f.name);
+        side.extend_from_slice(&f.load.to_le_bytes());
+        side.extend_from_slice(&u16::try_from(f.data.len()).unwrap().to_le_bytes());
+        side.push(f.kind);
+        side.push(0x04);
+        side.extend_from_slice(&f.data);
+    }
+    side.resize(FDS_SIDE_LEN, 0);
+    side
+}
+
+/// The boot program at `$6000`. Results land in `$0400-$0406`.
+fn program() -> Vec<u8> {
+    let mut p = vec![0xEAu8; 0x80];
+    let code: &[u8] = &[
+        0x20, 0xF8, 0xE1, // 6000 JSR LoadFiles
+        0x40, 0x60, //       .dw disk_id ($6040)
+        0x50, 0x60, //       .dw list ($6050)
+        0x8D, 0x00, 0x04, // 6007 STA $0400
+        0x8C, 0x01, 0x04, // 600A STY $0401
+        0x20, 0x2A, 0xE3, // 600D JSR GetDiskInfo
+        0x00, 0x05, //       .dw $0500
+        0x8D, 0x02, 0x04, // 6012 STA $0402
+        0xA9, 0xFF, //       6015 LDA #$FF
+        0x20, 0x39, 0xE2, // 6017 JSR WriteFile
+        0x40, 0x60, //       .dw disk_id
+        0x58, 0x60, //       .dw header ($6058)
+        0x8D, 0x03, 0x04, // 601E STA $0403
+        0x20, 0x45, 0xE4, // 6021 JSR $E445 (unsupported)
+        0x20, 0xF8, 0xE1, // 6024 JSR LoadFiles
+        0x70, 0x60, //       .dw wrong_side ($6070)
+        0x50, 0x60, //       .dw list
+        0x8D, 0x05, 0x04, // 602B STA $0405
+        0xA9, 0xA5, //       602E LDA #$A5
+        0x8D, 0x04, 0x04, // 6030 STA $0404
+        0x4C, 0x33, 0x60, // 6033 JMP $6033
+    ];
+    p[..code.len()].copy_from_slice(code);
+    p[0x40..0x4A].copy_from_slice(&[0xFF; 10]);
+    p[0x50..0x52].copy_from_slice(&[0x05, 0xFF]);
+    // WriteFile header: id, name, load, size, kind, source, source space.
+    let header: &[u8] = &[
+        0x09, b'S', b'A', b'V', b'E', b'D', b'A', b'T', b'A', 0x00, 0x03, 0x04, 0x00, 0x00, 0x00,
+        0x03, 0x00,
+    ];
+    p[0x58..0x58 + header.len()].copy_from_slice(header);
+    // Disk id expecting side B.
+    p[0x70..0x7A].copy_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01, 0xFF, 0xFF, 0xFF]);
+    // NMI handler at $6080: INC $0406; RTI. IRQ at $6090: RTI.
+    p.resize(0x91, 0xEA);
+    p[0x80..0x84].copy_from_slice(&[0xEE, 0x06, 0x04, 0x40]);
+    p[0x90] = 0x40;
+    p
+}
+
+fn disk(with_license: bool) -> Vec<u8> {
+    let mut files = Vec::new();
+    if with_license {
+        files.push(File {
+            id: 0,
+            name: b"KYODAKU-",
+            load: 0x2800,
+            kind: 2,
+            data: vec![0x24; 0xE0],
+        });
+    }
+    files.push(File {
+        id: 1,
+        name: b"MAIN    ",
+        load: 0x6000,
+        kind: 0,
+        data: program(),
+    });
+    // Game vectors: NMI x3 -> $6080, reset -> $6000, IRQ -> $6090.
+    files.push(File {
+        id: 1,
+        name: b"VECTORS ",
+        load: 0xDFF6,
+        kind: 0,
+        data: vec![0x80, 0x60, 0x80, 0x60, 0x80, 0x60, 0x00, 0x60, 0x90, 0x60],
+    });
+    files.push(File {
+        id: 5,
+        name: b"EXTRA   ",
+        load: 0x0300,
+        kind: 0,
+        data: vec![0xDE, 0xAD, 0xBE, 0xEF],
+    });
+    build_disk(&files)
+}
+
+fn boot(disk: &[u8], frames: usize) -> Nes {
+    let mut nes = Nes::from_disk_hle(disk, DEFAULT_SAMPLE_RATE).expect("HLE FDS construction");
+    for _ in 0..frames {
+        nes.run_frame();
+    }
+    nes
+}
+
+#[test]
+fn hle_bios_boots_and_serves_the_disk_api() {
+    let mut nes = boot(&disk(true), 10);
+    assert!(nes.fds_hle_active());
+    assert_eq!(nes.fds_hle_boot_error(), None);
+    assert_eq!(nes.peek(0x0404), 0xA5, "boot program ran to completion");
+    // LoadFiles of id 5: success, one file, its bytes at $0300.
+    assert_eq!(nes.peek(0x0400), 0x00);
+    assert_eq!(nes.peek(0x0401), 1);
+    let loaded: Vec<u8> = (0x0300..0x0304).map(|a| nes.peek(a)).collect();
+    assert_eq!(loaded, [0xDE, 0xAD, 0xBE, 0xEF]);
+    // GetDiskInfo: disk id (maker first), then the file count.
+    assert_eq!(nes.peek(0x0402), 0x00);
+    assert_eq!(nes.peek(0x0500), 0x01);
+    assert_eq!(nes.peek(0x0501), b'H');
+    assert_eq!(nes.peek(0x050A), 4);
+    // WriteFile appended a fifth file and dirtied the disk.
+    assert_eq!(nes.peek(0x0403), 0x00);
+    assert!(nes.disk_is_dirty());
+    let side = rustynes_core::rustynes_mappers::parse_side_files(&nes.disk_image_bytes())
+        .expect("written side parses");
+    assert_eq!(side.file_amount, 5);
+    assert_eq!(side.files[4].name_lossy(), "SAVEDATA");
+    assert_eq!(side.files[4].data, [0xDE, 0xAD, 0xBE, 0xEF]);
+    // Unsupported entry recorded; wrong-side disk id reports error $07.
+    assert_eq!(nes.fds_hle_unsupported_calls(), [0xE445]);
+    assert_eq!(nes.peek(0x0405), 0x07);
+    // The license file landed in the $2800 nametable (horizontal: bank 1).
+    assert_eq!(nes.bus().ppu().ciram()[0x400], 0x24);
+    // NMIs dispatch through the game's third vector once booted.
+    assert!(nes.peek(0x0406) > 0);
+}
+
+#[test]
+fn hle_write_keeps_the_stored_crcs_of_untouched_blocks() {
+    let mut qd = rustynes_core::rustynes_mappers::parse_fds(&disk(true))
+        .expect("synthetic disk parses")
+        .to_qd_bytes();
+    // Break the disk-info CRC and the EXTRA file's data CRC; WriteFile
+    // appends after EXTRA and must leave both as recorded.
+    qd[56] ^= 0xFF;
+    let extra = qd
+        .windows(5)
+        .position(|w| w == [0x04, 0xDE, 0xAD, 0xBE, 0xEF])
+        .expect("EXTRA data block")
+        + 5;
+    qd[extra] ^= 0xFF;
+    let mut nes = boot(&qd, 10);
+    assert_eq!(nes.peek(0x0403), 0x00, "WriteFile succeeded");
+    let out = nes.disk_image_qd_bytes();
+    assert_eq!(out[56], qd[56], "disk-info bad CRC survives");
+    assert_eq!(out[extra], qd[extra], "EXTRA bad CRC survives");
+}
+
+#[test]
+fn hle_bios_rejects_a_disk_without_license_file() {
+    let nes = boot(&disk(false), 5);
+    assert_eq!(nes.fds_hle_boot_error(), Some(0x20));
+}
+
+#[test]
+fn hle_write_honours_write_protect() {
+    let mut nes = Nes::from_disk_hle(&disk(true), DEFAULT_SAMPLE_RATE).expect("construct");
+    nes.set_disk_write_protected(true);
+    for _ in 0..10 {
+        nes.run_frame();
+    }
+    assert_eq!(nes.peek(0x0403), 0x03);
+    assert!(!nes.disk_is_dirty());
+}
+
+#[test]
+fn hle_boot_is_deterministic() {
+    let a = boot(&disk(true), 8);
+    let b = boot(&disk(true), 8);
+    assert_eq!(a.framebuffer(), b.framebuffer());
+    assert_eq!(a.disk_image_bytes(), b.disk_image_bytes());
+}
+
+/// HLE compatibility list. Each title is looked up as
+/// `tests/roms/external/fds/<file>`; missing dumps are skipped. `expect` is
+/// the status last measured by a maintainer: a title recorded as booting that
+/// stops booting fails the test. An unverified title is only reported, with a
+/// note when it boots so its entry can be pinned.
+#[cfg(feature = "commercial-roms")]
+mod compat {
+    use super::{DEFAULT_SAMPLE_RATE, Nes};
+    use std::path::PathBuf;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Status {
+        /// Boots past the BIOS into the game with no unsupported calls.
+        Boots,
+        /// Not yet measured against the HLE BIOS.
+        Unverified,
+    }
+
+    const TITLES: &[(&str, &str, Status)] = &[
+        ("Zelda no Densetsu", "zelda.fds", Status::Unverified),
+        ("Super Mario Bros. 2", "smb2.fds", Status::Unverified),
+        ("Metroid", "metroid.fds", Status::Unverified),
+        ("Palutena no Kagami", "palutena.fds", Status::Unverified),
+        (
+            "Yume Koujou Doki Doki Panic",
+            "dokidoki.fds",
+            Status::Unverified,
+        ),
+        ("Link no Bouken", "link.fds", Status::Unverified),
+        ("Nazo no Murasame-jou", "murasame.fds", Status::Unverified),
+        ("Akumajou Dracula", "dracula.fds", Status::Unverified),
+        ("Famicom Tantei Club", "tantei.fds", Status::Unverified),
+        ("Ai Senshi Nicol", "nicol.fds", Status::Unverified),
+        ("Bio Miracle Bokutte Upa", "upa.fds", Status::Unverified),
+        ("Falsion", "falsion.fds", Status::Unverified),
+    ];
+
+    fn disk_path(file: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../tests/roms/external/fds")
+            .join(file)
+    }
+
+    #[test]
+    fn hle_compatibility_list() {
+        let mut regressions = Vec::new();
+        for &(title, file, expect) in TITLES {
+            let Ok(bytes) = std::fs::read(disk_path(file)) else {
+                eprintln!("[fds-hle] {title}: no dump at fds/{file}; skipping");
+                continue;
+            };
+            let mut nes = match Nes::from_disk_hle(&bytes, DEFAULT_SAMPLE_RATE) {
+                Ok(nes) => nes,
+                Err(e) => {
+                    eprintln!("[fds-hle] {title}: unparseable disk: {e}");
+                    if expect == Status::Boots {
+                        regressions.push(title);
+                    }
+                    continue;
+                }
+            };
+            for _ in 0..600 {
+                nes.run_frame();
+            }
+            let error = nes.fds_hle_boot_error();
+            let unsupported = nes.fds_hle_unsupported_calls();
+            let boots = error.is_none() && unsupported.is_empty();
+            let note = if boots && expect == Status::Unverified {
+                "; boots, pin it as Status::Boots"
+            } else {
+                ""
+            };
+            eprintln!(
+                "[fds-hle] {title}: boot error {error:02X?}, unsupported {unsupported:04X?} \
+                 (expected {expect:?}){note}"
+            );
+            if expect == Status::Boots && !boots {
+                regressions.push(title);
+            }
+        }
+        assert!(regressions.is_empty(), "HLE regressions: {regressions:?}");
+    }
+}
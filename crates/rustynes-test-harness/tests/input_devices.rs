@@ -199,6 +199,100 @@ fn zapper_attach_smoke() {
     assert!(nes.bus_expansion_device_kind(1).is_none());
 }
 
+#[test]
+fn oeka_kids_tablet_attach_smoke() {
+    // v2.5.0 — the tablet self-attaches on port 2 and survives a save-state.
+    let path = rom_path(VAUS_ROM);
+    let bytes = fs::read(&path).unwrap_or_else(|e| panic!("read {}: {}", path.display(), e));
+    let mut nes = Nes::from_rom(&bytes).unwrap_or_else(|e| panic!("parse: {e}"));
+    nes.set_oeka_kids_tablet(1, 100, 120, true, false);
+    nes.run_frame();
+    let state = nes.snapshot();
+    nes.set_expansion_device(1, None);
+    nes.restore(&state).expect("restore");
+    assert_eq!(
+        nes.bus_expansion_device_kind(1),
+        Some(DeviceKind::OekaKidsTablet)
+    );
+}
+
+/// Attaches one device to a running `Nes`.
+type Attach = fn(&mut Nes);
+
+#[test]
+fn analog_expansion_controllers_attach_smoke() {
+    // v2.5.0 — each analog controller self-attaches on port 2, runs a frame
+    // and survives a save-state.
+    let path = rom_path(VAUS_ROM);
+    let bytes = fs::read(&path).unwrap_or_else(|e| panic!("read {}: {}", path.display(), e));
+    let mut nes = Nes::from_rom(&bytes).unwrap_or_else(|e| panic!("parse: {e}"));
+    let attach: [(Attach, DeviceKind); 4] = [
+        (|n| n.set_hori_track(1, 3, -3, 0x01), DeviceKind::HoriTrack),
+        (|n| n.set_pachinko(1, 0x08, 0x80), DeviceKind::Pachinko),
+        (|n| n.set_top_rider(1, -2, 3, 0x01), DeviceKind::TopRider),
+        (
+            |n| {
+                n.set_power_glove(
+                    1,
+                    rustynes_core::PowerGloveHand::default(),
+                    rustynes_core::PowerGloveProgram::Punch,
+                );
+            },
+            DeviceKind::PowerGlove,
+        ),
+    ];
+    for (set, kind) in attach {
+        set(&mut nes);
+        nes.run_frame();
+        let state = nes.snapshot();
+        nes.set_expansion_device(1, None);
+        nes.restore(&state).expect("restore");
+        assert_eq!(nes.bus_expansion_device_kind(1), Some(kind));
+    }
+}
+
+#[test]
+fn turbo_file_image_travels_with_snapshots() {
+    // v2.5.0 — the Turbo File is battery-backed storage: every snapshot,
+    // full or per-frame core (rewind, run-ahead, netplay), carries its image
+    // like cartridge SRAM.
+    let path = rom_path(VAUS_ROM);
+    let bytes = fs::read(&path).unwrap_or_else(|e| panic!("read {}: {}", path.display(), e));
+    let mut nes = Nes::from_rom(&bytes).unwrap_or_else(|e| panic!("parse: {e}"));
+    let image: Vec<u8> = (0..=u8::MAX)
+        .cycle()
+        .step_by(7)
+        .take(rustynes_core::TURBO_FILE_BANK_SIZE)
+        .collect();
+    let file = rustynes_core::TurboFileImage::from_storage(image.clone()).expect("8 KiB image");
+    nes.set_turbo_file(1, 0);
+    nes.set_turbo_file_image(Some(file));
+    nes.run_frame();
+    let state = nes.snapshot();
+    let mut core = Vec::new();
+    nes.snapshot_core_into(&mut core);
+
+    nes.set_turbo_file_image(Some(rustynes_core::TurboFileImage::new()));
+    nes.restore_quiet(&core).expect("restore core snapshot");
+    assert_eq!(
+        nes.turbo_file_image().expect("image inserted").storage(),
+        image.as_slice(),
+        "a core snapshot brings the image back"
+    );
+    nes.set_turbo_file_image(Some(rustynes_core::TurboFileImage::new()));
+
+    nes.set_expansion_device(1, None);
+    nes.restore(&state).expect("restore");
+    assert_eq!(
+        nes.bus_expansion_device_kind(1),
+        Some(DeviceKind::TurboFile)
+    );
+    let restored = nes.turbo_file_image().expect("image inserted");
+    assert!(!restored.is_twin());
+    assert!(restored.is_dirty(), "a restored image is due a flush");
+    assert_eq!(restored.storage(), image.as_slice());
+}
+
 /// Local enum so the test can assert the attached device kind without leaking
 /// internal state. Mirrors `InputDevice`'s discriminant.
 #[derive(Debug, PartialEq, Eq)]
@@ -212,6 +306,12 @@ enum DeviceKind {
     SuborKeyboard,
     KonamiHyperShot,
     BandaiHyperShot,
+    OekaKidsTablet,
+    HoriTrack,
+    Pachinko,
+    TopRider,
+    PowerGlove,
+    TurboFile,
 }
 
 trait DeviceKindExt {
@@ -230,6 +330,12 @@ impl DeviceKindExt for Nes {
             Some(InputDevice::SuborKeyboard(_)) => Some(DeviceKind::SuborKeyboard),
             Some(InputDevice::KonamiHyperShot(_)) => Some(DeviceKind::KonamiHyperShot),
             Some(InputDevice::BandaiHyperShot(_)) => Some(DeviceKind::BandaiHyperShot),
+            Some(InputDevice::OekaKidsTablet(_)) => Some(DeviceKind::OekaKidsTablet),
+            Some(InputDevice::HoriTrack(_)) => Some(DeviceKind::HoriTrack),
+            Some(InputDevice::Pachinko(_)) => Some(DeviceKind::Pachinko),
+            Some(InputDevice::TopRider(_)) => Some(DeviceKind::TopRider),
+            Some(InputDevice::PowerGlove(_)) => Some(DeviceKind::PowerGlove),
+            Some(InputDevice::TurboFile(_)) => Some(DeviceKind::TurboFile),
             None => None,
         }
     }
@@ -248,6 +354,18 @@ fn input_device_state_types_constructible() {
     let _ = InputDevice::SuborKeyboard(rustynes_core::FamilyKeyboardState::new());
     let _ = InputDevice::KonamiHyperShot(rustynes_core::KonamiHyperShotState::new());
     let _ = InputDevice::BandaiHyperShot(rustynes_core::BandaiHyperShotState::new());
+    // v2.5.0 — the Oeka Kids tablet.
+    let _ = InputDevice::OekaKidsTablet(rustynes_core::OekaKidsTabletState::new());
+    // v2.5.0 — the analog expansion controllers.
+    let _ = InputDevice::HoriTrack(rustynes_core::HoriTrackState::new());
+    let _ = InputDevice::Pachinko(rustynes_core::PachinkoState::new());
+    let _ = InputDevice::TopRider(rustynes_core::TopRiderState::new());
+    let _ = InputDevice::PowerGlove(rustynes_core::PowerGloveState::new(
+        rustynes_core::PowerGloveProgram::Joystick,
+    ));
+    // v2.5.0 — the Turbo File storage device.
+    let _ = InputDevice::TurboFile(rustynes_core::TurboFileState::new());
+    let _ = rustynes_core::TurboFileImage::new_twin();
 }
 
 /// v2.3.6 regression — a Zapper shot in *Duck Hunt* must be able to score.